@@ -0,0 +1,179 @@
+"""Receiver of the triggers of a Qovery job.
+
+Creates a job from the suspended cronjob of the job each time its webhook is called,
+or a message is received from its SQS queue. Only relies on the python standard library.
+"""
+
+import datetime
+import hashlib
+import hmac
+import json
+import os
+import ssl
+import sys
+import threading
+import time
+import urllib.parse
+import urllib.request
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+
+NAMESPACE = os.environ["NAMESPACE"]
+CRONJOB_NAME = os.environ["CRONJOB_NAME"]
+SERVICE_ID = os.environ["SERVICE_ID"]
+CONCURRENCY_POLICY = os.environ.get("CONCURRENCY_POLICY", "Forbid")
+MAX_PAYLOAD_SIZE = 32 * 1024
+SERVICE_ACCOUNT_DIR = "/var/run/secrets/kubernetes.io/serviceaccount"
+KUBE_API = "https://kubernetes.default.svc"
+TRIGGER_LOCK = threading.Lock()
+
+
+class ConcurrentRunForbidden(Exception):
+    pass
+
+
+def kube(method, path, body=None):
+    with open(f"{SERVICE_ACCOUNT_DIR}/token") as f:
+        token = f.read().strip()
+    request = urllib.request.Request(
+        KUBE_API + path,
+        data=json.dumps(body).encode() if body is not None else None,
+        method=method,
+        headers={"Authorization": f"Bearer {token}", "Content-Type": "application/json"},
+    )
+    context = ssl.create_default_context(cafile=f"{SERVICE_ACCOUNT_DIR}/ca.crt")
+    with urllib.request.urlopen(request, context=context, timeout=30) as response:
+        return json.load(response)
+
+
+def is_finished(job):
+    conditions = (job.get("status") or {}).get("conditions") or []
+    return any(c["type"] in ("Complete", "Failed") and c["status"] == "True" for c in conditions)
+
+
+def trigger(source, payload):
+    with TRIGGER_LOCK:
+        jobs_path = f"/apis/batch/v1/namespaces/{NAMESPACE}/jobs"
+        selector = urllib.parse.quote(f"qovery.com/service-id={SERVICE_ID}")
+        running_jobs = [j for j in kube("GET", f"{jobs_path}?labelSelector={selector}")["items"] if not is_finished(j)]
+        if running_jobs and CONCURRENCY_POLICY == "Forbid":
+            raise ConcurrentRunForbidden()
+        if CONCURRENCY_POLICY == "Replace":
+            for job in running_jobs:
+                kube("DELETE", f"{jobs_path}/{job['metadata']['name']}", {"propagationPolicy": "Background"})
+
+        cronjob = kube("GET", f"/apis/batch/v1/namespaces/{NAMESPACE}/cronjobs/{CRONJOB_NAME}")
+        template = cronjob["spec"]["jobTemplate"]
+        metadata = template.get("metadata") or {}
+        metadata.pop("name", None)
+        metadata["generateName"] = CRONJOB_NAME[:52] + "-"
+        metadata.setdefault("labels", {})["qovery.com/job-trigger"] = source
+        # owned by the cronjob, so its history limits apply to the triggered jobs
+        metadata["ownerReferences"] = [
+            {
+                "apiVersion": "batch/v1",
+                "kind": "CronJob",
+                "name": CRONJOB_NAME,
+                "uid": cronjob["metadata"]["uid"],
+                "controller": True,
+            }
+        ]
+        container = template["spec"]["template"]["spec"]["containers"][0]
+        container.setdefault("env", []).extend(
+            [
+                {"name": "QOVERY_JOB_TRIGGER_SOURCE", "value": source},
+                {"name": "QOVERY_JOB_TRIGGER_PAYLOAD", "value": payload},
+            ]
+        )
+        job = kube("POST", jobs_path, {"apiVersion": "batch/v1", "kind": "Job", "metadata": metadata, "spec": template["spec"]})
+        name = job["metadata"]["name"]
+        print(f"Job {name} created on {source} trigger", flush=True)
+        return name
+
+
+class WebhookHandler(BaseHTTPRequestHandler):
+    def do_GET(self):
+        self.reply(200 if self.path == "/healthz" else 404, {})
+
+    def do_POST(self):
+        token = os.environ.get("WEBHOOK_TOKEN")
+        if not token:
+            return self.reply(404, {"error": "webhook trigger is not enabled"})
+        if not hmac.compare_digest(self.headers.get("Authorization", "").encode(), f"Bearer {token}".encode()):
+            return self.reply(401, {"error": "invalid token"})
+        length = int(self.headers.get("Content-Length") or 0)
+        if length > MAX_PAYLOAD_SIZE:
+            return self.reply(413, {"error": f"payload is larger than {MAX_PAYLOAD_SIZE} bytes"})
+
+        try:
+            name = trigger("webhook", self.rfile.read(length).decode("utf-8", "replace"))
+        except ConcurrentRunForbidden:
+            return self.reply(409, {"error": "job is already running"})
+        except Exception as e:
+            print(f"Cannot trigger job: {e}", file=sys.stderr, flush=True)
+            return self.reply(500, {"error": "cannot trigger job"})
+        self.reply(202, {"job": name})
+
+    def reply(self, code, body):
+        data = json.dumps(body).encode()
+        self.send_response(code)
+        self.send_header("Content-Type", "application/json")
+        self.send_header("Content-Length", str(len(data)))
+        self.end_headers()
+        self.wfile.write(data)
+
+
+def sqs(action, body):
+    region = os.environ["AWS_REGION"]
+    host = f"sqs.{region}.amazonaws.com"
+    payload = json.dumps(body).encode()
+    now = datetime.datetime.now(datetime.timezone.utc)
+    amz_date, date = now.strftime("%Y%m%dT%H%M%SZ"), now.strftime("%Y%m%d")
+    headers = {
+        "content-type": "application/x-amz-json-1.0",
+        "host": host,
+        "x-amz-date": amz_date,
+        "x-amz-target": f"AmazonSQS.{action}",
+    }
+
+    # AWS signature version 4
+    signed_headers = ";".join(sorted(headers))
+    canonical_headers = "".join(f"{k}:{headers[k]}\n" for k in sorted(headers))
+    canonical_request = "\n".join(["POST", "/", "", canonical_headers, signed_headers, hashlib.sha256(payload).hexdigest()])
+    scope = f"{date}/{region}/sqs/aws4_request"
+    string_to_sign = "\n".join(["AWS4-HMAC-SHA256", amz_date, scope, hashlib.sha256(canonical_request.encode()).hexdigest()])
+    key = ("AWS4" + os.environ["AWS_SECRET_ACCESS_KEY"]).encode()
+    for part in (date, region, "sqs", "aws4_request"):
+        key = hmac.new(key, part.encode(), hashlib.sha256).digest()
+    signature = hmac.new(key, string_to_sign.encode(), hashlib.sha256).hexdigest()
+    headers["authorization"] = (
+        f"AWS4-HMAC-SHA256 Credential={os.environ['AWS_ACCESS_KEY_ID']}/{scope}, "
+        f"SignedHeaders={signed_headers}, Signature={signature}"
+    )
+
+    request = urllib.request.Request(f"https://{host}/", data=payload, method="POST", headers=headers)
+    with urllib.request.urlopen(request, timeout=30) as response:
+        return json.load(response)
+
+
+def poll_queue(queue_url):
+    while True:
+        try:
+            response = sqs("ReceiveMessage", {"QueueUrl": queue_url, "MaxNumberOfMessages": 1, "WaitTimeSeconds": 20})
+            for message in response.get("Messages", []):
+                try:
+                    trigger("queue", message["Body"][:MAX_PAYLOAD_SIZE])
+                except ConcurrentRunForbidden:
+                    # the message is received again once its visibility timeout expires
+                    time.sleep(10)
+                    continue
+                sqs("DeleteMessage", {"QueueUrl": queue_url, "ReceiptHandle": message["ReceiptHandle"]})
+        except Exception as e:
+            print(f"Cannot poll queue: {e}", file=sys.stderr, flush=True)
+            time.sleep(10)
+
+
+if __name__ == "__main__":
+    if os.environ.get("SQS_QUEUE_URL"):
+        threading.Thread(target=poll_queue, args=(os.environ["SQS_QUEUE_URL"],), daemon=True).start()
+    # always listening, health checks are served even without webhook trigger
+    ThreadingHTTPServer(("", int(os.environ["PORT"])), WebhookHandler).serve_forever()
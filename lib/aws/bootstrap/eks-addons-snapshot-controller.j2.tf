@@ -0,0 +1,14 @@
+resource "aws_eks_addon" "snapshot_controller" {
+  cluster_name             = aws_eks_cluster.eks_cluster.name
+  addon_name               = "snapshot-controller"
+
+  # Pick the recommended version for the k8s version
+  addon_version            = "{{ eks_addon_snapshot_controller.version }}"
+  resolve_conflicts        = "OVERWRITE"
+
+  tags                     = local.tags_eks
+
+  depends_on = [
+    aws_eks_addon.aws_ebs_csi_driver,
+  ]
+}
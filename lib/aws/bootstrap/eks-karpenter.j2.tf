@@ -0,0 +1,168 @@
+{%- if aws_eks_karpenter_enabled %}
+locals {
+  {% if user_provided_network -%}
+  karpenter_subnet_ids = flatten([data.aws_subnet.eks_zone_a[*].id, data.aws_subnet.eks_zone_b[*].id, data.aws_subnet.eks_zone_c[*].id])
+  {%- else -%}
+  karpenter_subnet_ids = flatten([aws_subnet.eks_zone_a[*].id, aws_subnet.eks_zone_b[*].id, aws_subnet.eks_zone_c[*].id])
+  {%- endif %}
+}
+
+resource "aws_iam_role" "iam_eks_karpenter" {
+  name        = "qovery-karpenter-${var.kubernetes_cluster_id}"
+  description = "Karpenter controller role for EKS cluster ${var.kubernetes_cluster_id}"
+  tags        = local.tags_eks
+
+  assume_role_policy = <<POLICY
+{
+  "Version": "2012-10-17",
+  "Statement": [
+    {
+      "Effect": "Allow",
+      "Principal": {
+        "Federated": "${aws_iam_openid_connect_provider.oidc.arn}"
+      },
+      "Action": "sts:AssumeRoleWithWebIdentity",
+      "Condition": {
+        "StringEquals": {
+          "${replace(aws_iam_openid_connect_provider.oidc.url, "https://", "")}:sub": "system:serviceaccount:kube-system:karpenter"
+        }
+      }
+    }
+  ]
+}
+POLICY
+}
+
+resource "aws_iam_policy" "karpenter_policy" {
+  name = aws_iam_role.iam_eks_karpenter.name
+  description = "Policy for karpenter"
+
+  policy = <<POLICY
+{
+  "Version": "2012-10-17",
+  "Statement": [
+    {
+      "Effect": "Allow",
+      "Action": [
+        "ec2:CreateFleet",
+        "ec2:CreateLaunchTemplate",
+        "ec2:CreateTags",
+        "ec2:DeleteLaunchTemplate",
+        "ec2:DescribeAvailabilityZones",
+        "ec2:DescribeImages",
+        "ec2:DescribeInstances",
+        "ec2:DescribeInstanceTypeOfferings",
+        "ec2:DescribeInstanceTypes",
+        "ec2:DescribeLaunchTemplates",
+        "ec2:DescribeSecurityGroups",
+        "ec2:DescribeSpotPriceHistory",
+        "ec2:DescribeSubnets",
+        "ec2:RunInstances",
+        "ec2:TerminateInstances",
+        "pricing:GetProducts",
+        "ssm:GetParameter",
+        "iam:AddRoleToInstanceProfile",
+        "iam:CreateInstanceProfile",
+        "iam:DeleteInstanceProfile",
+        "iam:GetInstanceProfile",
+        "iam:RemoveRoleFromInstanceProfile",
+        "iam:TagInstanceProfile"
+      ],
+      "Resource": ["*"]
+    },
+    {
+      "Effect": "Allow",
+      "Action": ["iam:PassRole"],
+      "Resource": ["${aws_iam_role.eks_workers.arn}"]
+    },
+    {
+      "Effect": "Allow",
+      "Action": ["eks:DescribeCluster"],
+      "Resource": ["${aws_eks_cluster.eks_cluster.arn}"]
+    },
+    {
+      "Effect": "Allow",
+      "Action": [
+        "sqs:DeleteMessage",
+        "sqs:GetQueueAttributes",
+        "sqs:GetQueueUrl",
+        "sqs:ReceiveMessage"
+      ],
+      "Resource": ["${aws_sqs_queue.karpenter_interruption.arn}"]
+    }
+  ]
+}
+POLICY
+}
+
+resource "aws_iam_role_policy_attachment" "karpenter_attachment" {
+  role       = aws_iam_role.iam_eks_karpenter.name
+  policy_arn = aws_iam_policy.karpenter_policy.arn
+}
+
+// Spot interruptions, rebalance recommendations, scheduled maintenances and instance state changes
+// are forwarded to karpenter, so it can drain the nodes before they go away
+resource "aws_sqs_queue" "karpenter_interruption" {
+  name                      = "qovery-karpenter-${var.kubernetes_cluster_id}"
+  message_retention_seconds = 300
+  sqs_managed_sse_enabled   = true
+  tags                      = local.tags_eks
+}
+
+resource "aws_sqs_queue_policy" "karpenter_interruption" {
+  queue_url = aws_sqs_queue.karpenter_interruption.url
+
+  policy = <<POLICY
+{
+  "Version": "2012-10-17",
+  "Statement": [
+    {
+      "Effect": "Allow",
+      "Principal": {
+        "Service": ["events.amazonaws.com", "sqs.amazonaws.com"]
+      },
+      "Action": "sqs:SendMessage",
+      "Resource": "${aws_sqs_queue.karpenter_interruption.arn}"
+    }
+  ]
+}
+POLICY
+}
+
+locals {
+  karpenter_interruption_events = {
+    health_event = {
+      source      = ["aws.health"]
+      detail-type = ["AWS Health Event"]
+    }
+    spot_interruption = {
+      source      = ["aws.ec2"]
+      detail-type = ["EC2 Spot Instance Interruption Warning"]
+    }
+    instance_rebalance = {
+      source      = ["aws.ec2"]
+      detail-type = ["EC2 Instance Rebalance Recommendation"]
+    }
+    instance_state_change = {
+      source      = ["aws.ec2"]
+      detail-type = ["EC2 Instance State-change Notification"]
+    }
+  }
+}
+
+resource "aws_cloudwatch_event_rule" "karpenter_interruption" {
+  for_each = local.karpenter_interruption_events
+
+  name          = "qovery-karpenter-${each.key}-${var.kubernetes_cluster_id}"
+  event_pattern = jsonencode(each.value)
+  tags          = local.tags_eks
+}
+
+resource "aws_cloudwatch_event_target" "karpenter_interruption" {
+  for_each = local.karpenter_interruption_events
+
+  rule      = aws_cloudwatch_event_rule.karpenter_interruption[each.key].name
+  target_id = "KarpenterInterruptionQueueTarget"
+  arn       = aws_sqs_queue.karpenter_interruption.arn
+}
+{%- endif %}
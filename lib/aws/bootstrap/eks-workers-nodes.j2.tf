@@ -34,7 +34,8 @@ resource "aws_eks_node_group" "eks_cluster_workers_{{ loop.index }}" {
   {%- else -%}
   subnet_ids       = flatten([aws_subnet.eks_zone_a[*].id, aws_subnet.eks_zone_b[*].id, aws_subnet.eks_zone_c[*].id])
   {%- endif %}
-  instance_types   = ["{{ eks_worker_node.instance_type }}"]
+  instance_types   = ["{{ eks_worker_node.instance_type }}"{% for fallback_instance_type in eks_worker_node.fallback_instance_types %}, "{{ fallback_instance_type }}"{% endfor %}]
+  capacity_type    = "{% if eks_worker_node.capacity_type == "OnDemand" %}ON_DEMAND{% else %}SPOT{% endif %}"
   {% if eks_worker_node.instance_architecture == "ARM64" -%}
   ami_type         = "AL2_ARM_64"
   {%- else -%}
@@ -49,6 +50,19 @@ resource "aws_eks_node_group" "eks_cluster_workers_{{ loop.index }}" {
   }
   )
 
+  labels = {
+    {%- for key, value in eks_worker_node.labels %}
+    "{{ key }}" = "{{ value }}"
+    {%- endfor %}
+  }
+  {% for taint in eks_worker_node.taints %}
+  taint {
+    key    = "{{ taint.key }}"
+    value  = "{{ taint.value }}"
+    effect = "{% if taint.effect == "NoSchedule" %}NO_SCHEDULE{% elif taint.effect == "PreferNoSchedule" %}PREFER_NO_SCHEDULE{% else %}NO_EXECUTE{% endif %}"
+  }
+  {% endfor %}
+
   launch_template {
     id      = aws_launch_template.eks_workers_nodes_{{ loop.index }}.id
     version = aws_launch_template.eks_workers_nodes_{{ loop.index }}.latest_version
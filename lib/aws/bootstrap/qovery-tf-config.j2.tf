@@ -3,6 +3,15 @@ locals {
 {
   "aws_iam_eks_user_mapper_role_arn": "${aws_iam_role.iam_eks_user_mapper.arn}",
   "aws_iam_cluster_autoscaler_role_arn": "${aws_iam_role.iam_eks_cluster_autoscaler.arn}",
+  {%- if aws_eks_karpenter_enabled %}
+  "karpenter": {
+    "aws_iam_karpenter_role_arn": "${aws_iam_role.iam_eks_karpenter.arn}",
+    "interruption_queue_name": "${aws_sqs_queue.karpenter_interruption.name}",
+    "node_role_name": "${aws_iam_role.eks_workers.name}",
+    "subnet_ids": ${jsonencode(local.karpenter_subnet_ids)},
+    "security_group_id": "${aws_eks_cluster.eks_cluster.vpc_config[0].cluster_security_group_id}"
+  },
+  {%- endif %}
   "aws_iam_cloudwatch_role_arn": "${aws_iam_role.iam_grafana_cloudwatch.arn}",
   "loki_storage_config_aws_s3": "s3://${var.region}/${aws_s3_bucket.loki_bucket.bucket}",
   "aws_iam_loki_role_arn": "${aws_iam_role.iam_eks_loki.arn}",
@@ -0,0 +1,6 @@
+resource "local_file" "kubeconfig" {
+  filename = "${var.object_storage_kubeconfig_bucket}/${var.kubernetes_cluster_id}.yaml"
+  content = azurerm_kubernetes_cluster.primary.kube_config_raw
+  file_permission = "0644"
+  depends_on = [azurerm_kubernetes_cluster.primary]
+}
@@ -0,0 +1,82 @@
+{%- set default_node_pool = aks_node_pools | first %}
+resource "azurerm_kubernetes_cluster" "primary" {
+  name                = var.kubernetes_cluster_name
+  location            = azurerm_resource_group.qovery.location
+  resource_group_name = azurerm_resource_group.qovery.name
+  dns_prefix          = "qovery-${var.kubernetes_cluster_id}"
+  kubernetes_version  = var.kubernetes_version
+  # let Azure handle nodes OS upgrades, Kubernetes upgrades are driven by the engine
+  automatic_channel_upgrade = "node-image"
+  sku_tier                  = {% if test_cluster %}"Free"{% else %}"Standard"{% endif %}
+
+  default_node_pool {
+    name                         = "{{ default_node_pool.name }}"
+    vm_size                      = "{{ default_node_pool.instance_type }}"
+    os_disk_size_gb              = {{ default_node_pool.disk_size_in_gib }}
+    orchestrator_version         = var.kubernetes_version
+    enable_auto_scaling          = true
+    min_count                    = {{ default_node_pool.min_nodes }}
+    max_count                    = {{ default_node_pool.max_nodes }}
+    zones                        = length(var.zones) > 0 ? var.zones : null
+    only_critical_addons_enabled = false
+    temporary_name_for_rotation  = "{{ default_node_pool.name | truncate(length=9, end="") }}tmp"
+    {%- if default_node_pool.labels %}
+    node_labels                  = { {% for key, value in default_node_pool.labels %}"{{ key }}" = "{{ value }}"{% if not loop.last %}, {% endif %}{% endfor %} }
+    {%- endif %}
+    tags                         = merge(local.tags_common, { QoveryNodeGroupName = "{{ default_node_pool.name }}" })
+  }
+
+  identity {
+    type = "SystemAssigned"
+  }
+
+  network_profile {
+    network_plugin    = "azure"
+    network_policy    = "azure"
+    load_balancer_sku = "standard"
+  }
+
+  storage_profile {
+    disk_driver_enabled = true
+    blob_driver_enabled = false
+  }
+
+  tags = local.tags_common
+
+  lifecycle {
+    ignore_changes = [
+      default_node_pool[0].node_count,
+    ]
+  }
+}
+{% for node_pool in aks_node_pools %}{% if not loop.first %}
+resource "azurerm_kubernetes_cluster_node_pool" "{{ node_pool.name }}" {
+  name                  = "{{ node_pool.name }}"
+  kubernetes_cluster_id = azurerm_kubernetes_cluster.primary.id
+  vm_size               = "{{ node_pool.instance_type }}"
+  os_disk_size_gb       = {{ node_pool.disk_size_in_gib }}
+  enable_auto_scaling   = true
+  min_count             = {{ node_pool.min_nodes }}
+  max_count             = {{ node_pool.max_nodes }}
+  zones                 = length(var.zones) > 0 ? var.zones : null
+  orchestrator_version  = var.kubernetes_version
+  {%- if node_pool.spot %}
+  priority              = "Spot"
+  eviction_policy       = "Delete"
+  spot_max_price        = -1
+  {%- endif %}
+  {%- if node_pool.labels %}
+  node_labels           = { {% for key, value in node_pool.labels %}"{{ key }}" = "{{ value }}"{% if not loop.last %}, {% endif %}{% endfor %} }
+  {%- endif %}
+  {%- if node_pool.taints %}
+  node_taints           = [{% for taint in node_pool.taints %}"{{ taint }}"{% if not loop.last %}, {% endif %}{% endfor %}]
+  {%- endif %}
+  tags                  = merge(local.tags_common, { QoveryNodeGroupName = "{{ node_pool.name }}" })
+
+  lifecycle {
+    ignore_changes = [
+      node_count,
+    ]
+  }
+}
+{% endif %}{% endfor %}
@@ -0,0 +1,21 @@
+locals {
+  tags_common = {
+    ClusterId          = var.kubernetes_cluster_id
+    ClusterLongId      = var.kubernetes_cluster_long_id
+    OrganizationId     = var.organization_id
+    OrganizationLongId = var.organization_long_id
+    Region             = var.location
+    creationDate       = time_static.on_cluster_create.rfc3339
+{%- if resource_expiration_in_seconds > -1 %}
+    ttl                = var.resource_expiration_in_seconds
+{%- endif %}
+  }
+}
+
+resource "time_static" "on_cluster_create" {}
+
+resource "azurerm_resource_group" "qovery" {
+  name     = var.resource_group_name
+  location = var.location
+  tags     = local.tags_common
+}
@@ -0,0 +1,13 @@
+locals {
+  qovery_tf_config = <<TF_CONFIG
+{
+  "aks_cluster_public_hostname": "${azurerm_kubernetes_cluster.primary.fqdn}"
+}
+TF_CONFIG
+}
+
+resource "local_file" "qovery_tf_config" {
+  filename = "qovery-tf-config.json"
+  content = local.qovery_tf_config
+  file_permission = "0644"
+}
@@ -0,0 +1,23 @@
+resource "azurerm_container_registry" "qovery" {
+  name                = var.container_registry_name
+  resource_group_name = azurerm_resource_group.qovery.name
+  location            = azurerm_resource_group.qovery.location
+  sku                 = "Standard"
+  admin_enabled       = false
+  tags                = local.tags_common
+}
+
+# Allow engine service principal to push built images
+resource "azurerm_role_assignment" "engine_acr_push" {
+  scope                = azurerm_container_registry.qovery.id
+  role_definition_name = "AcrPush"
+  principal_id         = data.azurerm_client_config.current.object_id
+}
+
+# Allow cluster nodes to pull images
+resource "azurerm_role_assignment" "aks_acr_pull" {
+  scope                            = azurerm_container_registry.qovery.id
+  role_definition_name             = "AcrPull"
+  principal_id                     = azurerm_kubernetes_cluster.primary.kubelet_identity[0].object_id
+  skip_service_principal_aad_check = true
+}
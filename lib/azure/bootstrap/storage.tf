@@ -0,0 +1,18 @@
+# Storage account hosting Qovery managed blob containers (kubeconfig, logs), containers are created by the engine
+resource "azurerm_storage_account" "qovery" {
+  name                            = var.storage_account_name
+  resource_group_name             = azurerm_resource_group.qovery.name
+  location                        = azurerm_resource_group.qovery.location
+  account_tier                    = "Standard"
+  account_replication_type        = "LRS"
+  min_tls_version                 = "TLS1_2"
+  allow_nested_items_to_be_public = false
+  tags                            = local.tags_common
+}
+
+# Allow engine service principal to manage blob containers and their content
+resource "azurerm_role_assignment" "engine_storage_blob_data_contributor" {
+  scope                = azurerm_storage_account.qovery.id
+  role_definition_name = "Storage Blob Data Contributor"
+  principal_id         = data.azurerm_client_config.current.object_id
+}
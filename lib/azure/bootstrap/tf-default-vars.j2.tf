@@ -0,0 +1,101 @@
+# Qovery
+variable "cloud_provider" {
+  description = "Cloud provider name"
+  default = "azure"
+  type = string
+}
+
+variable "organization_id" {
+  description = "Qovery Organization ID"
+  default     = "{{ organization_id }}"
+  type        = string
+}
+
+variable "organization_long_id" {
+  description = "Qovery Organization long ID"
+  default     = "{{ organization_long_id }}"
+  type        = string
+}
+
+variable "object_storage_kubeconfig_bucket" {
+  description = "Object storage bucket name containing cluster's kubeconfig"
+  default     = "{{ object_storage_kubeconfig_bucket }}"
+  type        = string
+}
+
+variable "object_storage_logs_bucket" {
+  description = "Object storage bucket name containing cluster's logs"
+  default     = "{{ object_storage_logs_bucket }}"
+  type        = string
+}
+
+{%- if resource_expiration_in_seconds > -1 %}
+# Pleco ttl
+variable "resource_expiration_in_seconds" {
+  description = "Resource expiration in seconds"
+  default = {{ resource_expiration_in_seconds }}
+  type = number
+}
+{% endif %}
+
+# Azure specific
+variable "location" {
+  description = "Azure location to host the cluster in"
+  default     = "{{ azure_location }}"
+  type        = string
+}
+
+variable "zones" {
+  description = "Availability zones used by the cluster node pools, empty if the location doesn't support availability zones"
+  default     = [{% for zone in azure_zones %}"{{ zone }}"{% if not loop.last %}, {% endif %}{% endfor %}]
+  type        = list(string)
+}
+
+variable "resource_group_name" {
+  description = "Resource group containing all cluster resources"
+  default     = "{{ azure_resource_group_name }}"
+  type        = string
+}
+
+variable "storage_account_name" {
+  description = "Storage account hosting Qovery managed blob containers"
+  default     = "{{ azure_storage_account_name }}"
+  type        = string
+}
+
+variable "container_registry_name" {
+  description = "Azure container registry name"
+  default     = "{{ azure_container_registry_name }}"
+  type        = string
+}
+
+# Kubernetes
+variable "kubernetes_cluster_long_id" {
+  description = "Kubernetes cluster long id"
+  default     = "{{ kubernetes_cluster_long_id }}"
+  type        = string
+}
+
+variable "kubernetes_cluster_id" {
+  description = "Kubernetes cluster id"
+  default     = "{{ kubernetes_cluster_id }}"
+  type        = string
+}
+
+variable "kubernetes_cluster_name" {
+  description = "Kubernetes cluster name"
+  default     = "{{ kubernetes_cluster_name }}"
+  type        = string
+}
+
+variable "kubernetes_version" {
+  description = "Kubernetes cluster version"
+  default     = "{{ kubernetes_cluster_version }}"
+  type        = string
+}
+
+variable "test_cluster" {
+  description = "Is this a test cluster?"
+  default     = "{{ test_cluster }}"
+  type        = string
+}
@@ -61,3 +61,20 @@ Create the name of the service account to use
 {{- default "default" .Values.serviceAccount.name }}
 {{- end }}
 {{- end }}
+
+{{/*
+HTTP-01 solver, challenges are served by the cluster gateway when the Gateway API is enabled
+*/}}
+{{- define "cert-manager-configs.http01Solver" -}}
+- http01:
+    {{- if .Values.gatewayHttp01Parent }}
+    gatewayHTTPRoute:
+      parentRefs:
+        - name: {{ .Values.gatewayHttp01Parent.name }}
+          namespace: {{ .Values.gatewayHttp01Parent.namespace }}
+          kind: Gateway
+    {{- else }}
+    ingress:
+      class: nginx-qovery
+    {{- end }}
+{{- end }}
@@ -0,0 +1,17 @@
+# Private service access gives managed databases (Cloud SQL, Memorystore) a private IP in the cluster VPC.
+# An existing VPC is expected to have its own private service access configured.
+{% if not vpc_use_existing %}
+resource "google_compute_global_address" "private_service_access" {
+  name          = "${var.kubernetes_cluster_name}-psa"
+  purpose       = "VPC_PEERING"
+  address_type  = "INTERNAL"
+  prefix_length = 20
+  network       = google_compute_network.vpc_network.id
+}
+
+resource "google_service_networking_connection" "private_service_access" {
+  network                 = google_compute_network.vpc_network.id
+  service                 = "servicenetworking.googleapis.com"
+  reserved_peering_ranges = [google_compute_global_address.private_service_access.name]
+}
+{% endif %}
@@ -0,0 +1,62 @@
+# Qovery
+variable "cluster_name" {
+  description = "Kubernetes cluster name"
+  default     = "{{ cluster_name }}"
+  type        = string
+}
+
+variable "region" {
+  description = "GCP region of the cluster"
+  default     = "{{ gcp_region }}"
+  type        = string
+}
+
+variable "kubernetes_cluster_id" {
+  description = "Kubernetes cluster id"
+  default     = "{{ kubernetes_cluster_id }}"
+  type        = string
+}
+
+variable "database_labels" {
+  description = "Qovery database labels"
+  default     = {
+    "cluster_id"       = "{{ kubernetes_cluster_id }}"
+    "region"           = "{{ gcp_region }}"
+    "q_client_id"      = "{{ owner_id }}"
+    "q_environment_id" = "{{ environment_id }}"
+    "q_project_id"     = "{{ project_id }}"
+    "database_id"      = "{{ database_id }}"
+    {% if resource_expiration_in_seconds > -1 %}
+    "ttl"              = "{{ resource_expiration_in_seconds }}"
+    {% endif %}
+  }
+  type        = map(string)
+}
+
+# Network
+
+variable "publicly_accessible" {
+  description = "Instance publicly accessible"
+  default     = {{ publicly_accessible }}
+  type        = bool
+}
+
+# Backups and availability
+
+variable "activate_backups" {
+  description = "Backups activated"
+  default     = {{ activate_backups }}
+  type        = bool
+}
+
+variable "backup_retention_period" {
+  description = "Number of retained backups"
+  default     = 7
+  type        = number
+}
+
+variable "activate_high_availability" {
+  description = "Define if DB should be replicated in another zone of the region"
+  default     = {{ activate_high_availability }}
+  type        = bool
+}
@@ -0,0 +1,29 @@
+terraform {
+  required_providers {
+    google = {
+      source  = "hashicorp/google"
+      version = ">= 4.80.0, < 5.0, !=4.65.0, !=4.65.1"
+    }
+    local = {
+      source = "hashicorp/local"
+      version = "2.2.3"
+    }
+    time = {
+      source  = "hashicorp/time"
+      version = "0.9.0"
+    }
+  }
+  required_version = "1.3.3"
+}
+
+# credentials are given by GOOGLE_CREDENTIALS environment variable
+provider "google" {
+  project = "{{ gcp_project_id }}"
+  region  = "{{ gcp_region }}"
+}
+
+# databases get a private IP in the cluster VPC, through its private service access
+data "google_container_cluster" "kubernetes_cluster" {
+  name     = "{{ kubernetes_cluster_name }}"
+  location = "{{ gcp_region }}"
+}
@@ -0,0 +1,16 @@
+locals {
+  database_tf_config = <<TF_CONFIG
+{
+  "database_target_id": "${google_sql_database_instance.mysql_instance.name}",
+  "database_target_hostname": "${var.publicly_accessible ? google_sql_database_instance.mysql_instance.public_ip_address : google_sql_database_instance.mysql_instance.private_ip_address}",
+  "database_target_fqdn_id": "{{ fqdn_id }}",
+  "database_target_fqdn": "{{ fqdn }}"
+}
+TF_CONFIG
+}
+
+resource "local_file" "database_tf_config" {
+  filename = "database-tf-config.json"
+  content = local.database_tf_config
+  file_permission = "0600"
+}
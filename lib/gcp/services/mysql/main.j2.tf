@@ -0,0 +1,59 @@
+resource "google_sql_database_instance" "mysql_instance" {
+  name             = var.mysql_identifier
+  database_version = var.mysql_version
+  region           = var.region
+
+  # deletion is requested by Qovery only
+  deletion_protection = false
+
+  settings {
+    tier              = var.instance_class
+    activation_policy = var.activation_policy
+    availability_type = var.activate_high_availability ? "REGIONAL" : "ZONAL"
+    user_labels       = var.database_labels
+
+    disk_type       = "PD_SSD"
+    disk_size       = var.disk_size
+    disk_autoresize = false
+
+    ip_configuration {
+      ipv4_enabled    = var.publicly_accessible
+      private_network = data.google_container_cluster.kubernetes_cluster.network
+
+      dynamic "authorized_networks" {
+        for_each = var.publicly_accessible ? [1] : []
+        content {
+          name  = "accessible from any host"
+          value = "0.0.0.0/0"
+        }
+      }
+    }
+
+    # high availability relies on backups and binary logs for MySQL
+    backup_configuration {
+      enabled            = var.activate_backups || var.activate_high_availability
+      binary_log_enabled = var.activate_backups || var.activate_high_availability
+      start_time         = "00:00"
+      backup_retention_settings {
+        retained_backups = var.backup_retention_period
+      }
+    }
+
+    maintenance_window {
+      day          = 2
+      hour         = 2
+      update_track = "stable"
+    }
+  }
+}
+
+resource "google_sql_database" "mysql_main" {
+  name     = var.database_name
+  instance = google_sql_database_instance.mysql_instance.name
+}
+
+resource "google_sql_user" "mysql_admin" {
+  name     = var.username
+  password = var.password
+  instance = google_sql_database_instance.mysql_instance.name
+}
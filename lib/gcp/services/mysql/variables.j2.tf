@@ -0,0 +1,49 @@
+# MySQL instance basics
+
+variable "mysql_identifier" {
+  description = "MySQL instance name (DB identifier)"
+  default     = "{{ fqdn_id }}"
+  type        = string
+}
+
+variable "disk_size" {
+  description = "Disk instance size in GiB"
+  default     = {{ database_disk_size_in_gib }}
+  type        = number
+}
+
+variable "mysql_version" {
+  description = "Cloud SQL MySQL version, e.q MYSQL_8_0"
+  default     = "{{ database_version }}"
+  type        = string
+}
+
+variable "instance_class" {
+  description = "Cloud SQL tier: https://cloud.google.com/sql/docs/mysql/instance-settings"
+  default     = "{{ database_instance_type }}"
+  type        = string
+}
+
+variable "activation_policy" {
+  description = "ALWAYS to run the instance, NEVER to stop it"
+  default     = "{{ activation_policy }}"
+  type        = string
+}
+
+variable "username" {
+  description = "Admin username for the master DB user"
+  default     = "{{ database_login }}"
+  type        = string
+}
+
+variable "password" {
+  description = "Admin password for the master DB user"
+  default     = "{{ database_password }}"
+  type        = string
+}
+
+variable "database_name" {
+  description = "The name of the database to create when the DB instance is created"
+  default     = "{{ database_name }}"
+  type        = string
+}
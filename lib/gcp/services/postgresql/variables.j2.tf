@@ -0,0 +1,49 @@
+# PostgreSQL instance basics
+
+variable "postgresql_identifier" {
+  description = "PostgreSQL instance name (DB identifier)"
+  default     = "{{ fqdn_id }}"
+  type        = string
+}
+
+variable "disk_size" {
+  description = "Disk instance size in GiB"
+  default     = {{ database_disk_size_in_gib }}
+  type        = number
+}
+
+variable "postgresql_version" {
+  description = "Cloud SQL PostgreSQL version, e.q POSTGRES_15"
+  default     = "{{ database_version }}"
+  type        = string
+}
+
+variable "instance_class" {
+  description = "Cloud SQL tier: https://cloud.google.com/sql/docs/postgres/instance-settings"
+  default     = "{{ database_instance_type }}"
+  type        = string
+}
+
+variable "activation_policy" {
+  description = "ALWAYS to run the instance, NEVER to stop it"
+  default     = "{{ activation_policy }}"
+  type        = string
+}
+
+variable "username" {
+  description = "Admin username for the master DB user"
+  default     = "{{ database_login }}"
+  type        = string
+}
+
+variable "password" {
+  description = "Admin password for the master DB user"
+  default     = "{{ database_password }}"
+  type        = string
+}
+
+variable "database_name" {
+  description = "The name of the database to create when the DB instance is created"
+  default     = "{{ database_name }}"
+  type        = string
+}
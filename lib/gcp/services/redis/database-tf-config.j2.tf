@@ -0,0 +1,18 @@
+locals {
+  database_tf_config = <<TF_CONFIG
+{
+  "database_target_id": "${google_redis_instance.redis_instance.name}",
+  "database_target_hostname": "${google_redis_instance.redis_instance.host}",
+  "database_target_port": ${google_redis_instance.redis_instance.port},
+  "database_target_password": "${google_redis_instance.redis_instance.auth_string}",
+  "database_target_fqdn_id": "{{ fqdn_id }}",
+  "database_target_fqdn": "{{ fqdn }}"
+}
+TF_CONFIG
+}
+
+resource "local_file" "database_tf_config" {
+  filename = "database-tf-config.json"
+  content = local.database_tf_config
+  file_permission = "0600"
+}
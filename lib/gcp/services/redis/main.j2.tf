@@ -0,0 +1,37 @@
+resource "google_redis_instance" "redis_instance" {
+  name           = var.redis_identifier
+  redis_version  = var.redis_version
+  region         = var.region
+  memory_size_gb = var.memory_size
+  labels         = var.database_labels
+
+  # a replica in another zone is only available with the standard tier
+  tier = var.activate_high_availability ? "STANDARD_HA" : "BASIC"
+
+  # Memorystore has no public endpoint, it is reachable from the cluster VPC only
+  authorized_network = data.google_container_cluster.kubernetes_cluster.network
+  connect_mode       = "PRIVATE_SERVICE_ACCESS"
+
+  # AUTH string is generated by Memorystore and can't be set to the database password,
+  # it is sent back to the core as the password of the database
+  auth_enabled = true
+
+  # clients connect with TLS on port 6378, the server certificate is signed by the instance CA
+  transit_encryption_mode = "SERVER_AUTHENTICATION"
+
+  # Memorystore doesn't support pausing, RDB snapshots are used as backups
+  persistence_config {
+    persistence_mode    = var.activate_backups ? "RDB" : "DISABLED"
+    rdb_snapshot_period = var.activate_backups ? "TWENTY_FOUR_HOURS" : null
+  }
+
+  maintenance_policy {
+    weekly_maintenance_window {
+      day = "TUESDAY"
+      start_time {
+        hours   = 2
+        minutes = 0
+      }
+    }
+  }
+}
@@ -0,0 +1,19 @@
+# Redis instance basics
+
+variable "redis_identifier" {
+  description = "Memorystore instance name (DB identifier)"
+  default     = "{{ fqdn_id }}"
+  type        = string
+}
+
+variable "redis_version" {
+  description = "Memorystore Redis version, e.q REDIS_7_0"
+  default     = "{{ database_version }}"
+  type        = string
+}
+
+variable "memory_size" {
+  description = "Instance memory size in GiB, given by the instance type"
+  default     = {{ database_memory_size_in_gib }}
+  type        = number
+}
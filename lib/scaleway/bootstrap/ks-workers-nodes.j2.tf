@@ -27,6 +27,11 @@ resource "scaleway_k8s_pool" "kubernetes_cluster_workers_{{ loop.index }}" {
   lifecycle {
     create_before_destroy = true
   }
-  tags          =  concat(local.tags_ks_list, ["QoveryNodeGroupName:{{ scw_ks_worker_node.name }}", "QoveryNodeGroupId:${var.kubernetes_cluster_id}_{{ scw_ks_worker_node.instance_type }}_{{ loop.index }}"])
+  # node labels and taints are set from pool tags, `noprefix` avoids Scaleway prefixing them with `k8s.scaleway.com/`
+  tags          =  concat(
+    local.tags_ks_list,
+    ["QoveryNodeGroupName:{{ scw_ks_worker_node.name }}", "QoveryNodeGroupId:${var.kubernetes_cluster_id}_{{ scw_ks_worker_node.instance_type }}_{{ loop.index }}"],
+    [{% for key, value in scw_ks_worker_node.labels %}"noprefix={{ key }}={{ value }}", {% endfor %}{% for taint in scw_ks_worker_node.taints %}"taint=noprefix={{ taint.key }}={{ taint.value }}:{{ taint.effect }}", {% endfor %}]
+  )
 }
 {% endfor %}
@@ -1,37 +1,28 @@
 #![allow(clippy::redundant_closure)]
 
-use std::io::{Error, ErrorKind};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use std::{env, fs};
 
-use git2::{Cred, CredentialType};
 use sysinfo::{DiskExt, RefreshKind, SystemExt};
 use uuid::Uuid;
 
 use crate::build_platform::dockerfile_utils::extract_dockerfile_args;
-use crate::build_platform::{to_build_error, Build, BuildError, BuildPlatform, Kind};
+use crate::build_platform::repository::{checkout_repository, repository_build_root_path};
+use crate::build_platform::{buildpacks_order, to_build_error, Build, BuildError, BuildPlatform, Kind};
 use crate::cmd::command::CommandError::Killed;
 use crate::cmd::command::{CommandKiller, ExecutableCommand, QoveryCommand};
+use crate::cmd::docker;
 use crate::cmd::docker::{Architecture, ContainerImage};
-use crate::cmd::git_lfs::{GitLfs, GitLfsError};
-use crate::cmd::{command, docker};
 use crate::deployment_report::logger::EnvLogger;
 
-use crate::fs::workspace_directory;
-use crate::git;
 use crate::io_models::context::Context;
 use crate::metrics_registry::{MetricsRegistry, StepLabel, StepName, StepStatus};
 use crate::utilities::to_short_id;
 
-/// https://github.com/heroku/builder
-const BUILDPACKS_BUILDERS: [&str; 1] = [
-    "heroku/builder-classic:22",
-    // removed because it does not support dynamic port binding
-    //"gcr.io/buildpacks/builder:v1",
-    //"paketobuildpacks/builder:base",
-];
+/// Tag of the buildpacks cache image, pushed next to the application image
+const BUILDPACKS_CACHE_IMAGE_TAG: &str = "buildpacks-cache";
 
 /// use Docker in local
 #[derive(Clone)]
@@ -42,9 +33,6 @@ pub struct LocalDocker {
     name: String,
 }
 
-const MAX_GIT_LFS_SIZE_GB: u64 = 5;
-const MAX_GIT_LFS_SIZE_KB: u64 = MAX_GIT_LFS_SIZE_GB * 1024 * 1024; // 5GB
-
 impl LocalDocker {
     pub fn new(context: Context, long_id: Uuid, name: &str) -> Result<Self, BuildError> {
         Ok(LocalDocker {
@@ -208,85 +196,27 @@ impl LocalDocker {
         logger: &EnvLogger,
         is_task_canceled: &dyn Fn() -> bool,
     ) -> Result<(), BuildError> {
-        const LATEST_TAG: &str = "latest";
-        let name_with_tag = build.image.full_image_name_with_tag();
-        let name_with_latest_tag = format!("{}:{}", build.image.full_image_name(), LATEST_TAG);
-        let mut exit_status: Result<(), command::CommandError> = Err(command::CommandError::ExecutionError(
-            Error::new(ErrorKind::InvalidData, "No builder names".to_string()),
-        ));
-
-        for builder_name in BUILDPACKS_BUILDERS.iter() {
-            let mut buildpacks_args = if !use_build_cache {
-                vec!["build", "--publish", name_with_tag.as_str(), "--clear-cache"]
-            } else {
-                vec!["build", "--publish", name_with_tag.as_str()]
-            };
-
-            // always add 'latest' tag
-            buildpacks_args.extend(vec!["-t", name_with_latest_tag.as_str()]);
-            buildpacks_args.extend(vec!["--path", into_dir_docker_style]);
-
-            let mut args_buffer = Vec::with_capacity(build.environment_variables.len());
-            for (key, value) in &build.environment_variables {
-                args_buffer.push("--env".to_string());
-                args_buffer.push(format!("{key}={value}"));
-            }
-            buildpacks_args.extend(args_buffer.iter().map(|value| value.as_str()).collect::<Vec<&str>>());
-
-            buildpacks_args.push("-B");
-            buildpacks_args.push(builder_name);
-            if let Some(buildpacks_language) = &build.git_repository.buildpack_language {
-                buildpacks_args.push("-b");
-                match buildpacks_language.split('@').collect::<Vec<&str>>().as_slice() {
-                    [builder] => {
-                        // no version specified, so we use the latest builder
-                        buildpacks_args.push(builder);
-                    }
-                    [builder, _version] => {
-                        // version specified, we need to use the specified builder
-                        // but also ensure that the user has set the correct runtime version in his project
-                        // this is language dependent
-                        // https://elements.heroku.com/buildpacks/heroku/heroku-buildpack-python
-                        // https://devcenter.heroku.com/articles/buildpacks
-                        // TODO: Check user project is correctly configured for this builder and version
-                        buildpacks_args.push(builder);
-                    }
-                    _ => {
-                        return Err(BuildError::InvalidConfig {
-                            application: build.image.service_id.clone(),
-                            raw_error_message: format!(
-                                "Invalid buildpacks language format: expected `builder[@version]` got {buildpacks_language}"
-                            ),
-                        });
-                    }
-                }
-            }
-
-            // Just a fallback for now to help our bot loving users deploy their apps
-            // Long term solution requires lots of changes in UI and Core as well
-            // And passing some params to the engine
-            if let Ok(content) = fs::read_to_string(format!("{}/{}", into_dir_docker_style, "Procfile")) {
-                if content.contains("worker") {
-                    buildpacks_args.push("--default-process");
-                    buildpacks_args.push("worker");
-                }
-            }
-
-            // buildpacks build
-            let mut cmd = QoveryCommand::new("pack", &buildpacks_args, &self.get_docker_host_envs());
-            cmd.set_kill_grace_period(Duration::from_secs(0));
-            let cmd_killer = CommandKiller::from(build.timeout, is_task_canceled);
-            exit_status = cmd.exec_with_abort(
-                &mut |line| logger.send_progress(line),
-                &mut |line| logger.send_progress(line),
-                &cmd_killer,
-            );
-
-            if exit_status.is_ok() {
-                // quit now if the builder successfully build the app
-                break;
-            }
-        }
+        // Just a fallback for now to help our bot loving users deploy their apps
+        // Long term solution requires lots of changes in UI and Core as well
+        // And passing some params to the engine
+        let has_worker_process = fs::read_to_string(format!("{}/{}", into_dir_docker_style, "Procfile"))
+            .map(|content| content.contains("worker"))
+            .unwrap_or(false);
+        let buildpacks_args = buildpacks_args(build, into_dir_docker_style, use_build_cache, has_worker_process)?;
+
+        // buildpacks build
+        let mut cmd = QoveryCommand::new(
+            "pack",
+            &buildpacks_args.iter().map(|arg| arg.as_str()).collect::<Vec<&str>>(),
+            &self.get_docker_host_envs(),
+        );
+        cmd.set_kill_grace_period(Duration::from_secs(0));
+        let cmd_killer = CommandKiller::from(build.timeout, is_task_canceled);
+        let exit_status = cmd.exec_with_abort(
+            &mut |line| logger.send_progress(line),
+            &mut |line| logger.send_progress(line),
+            &cmd_killer,
+        );
 
         match exit_status {
             Ok(_) => Ok(()),
@@ -299,19 +229,6 @@ impl LocalDocker {
             }),
         }
     }
-
-    fn get_repository_build_root_path(&self, build: &Build) -> Result<String, BuildError> {
-        workspace_directory(
-            self.context.workspace_root_dir(),
-            self.context.execution_id(),
-            format!("build/{}", build.image.name.as_str()),
-        )
-        .map_err(|err| BuildError::IoError {
-            application: build.image.service_id.clone(),
-            action_description: "when creating build workspace".to_string(),
-            raw_error: err,
-        })
-    }
 }
 
 impl BuildPlatform for LocalDocker {
@@ -345,159 +262,20 @@ impl BuildPlatform for LocalDocker {
             });
         }
 
-        // LOGGING
-        let repository_root_path = PathBuf::from(self.get_repository_build_root_path(build)?);
-        logger.send_progress(format!("📥 Cloning repository {}", build.git_repository.url));
-
-        // Retrieve git credentials
-        let git_user_creds = match build.git_repository.credentials() {
-            None => None,
-            Some(Ok(creds)) => Some(creds),
-            Some(Err(err)) => {
-                logger.send_warning(format!("🗝️ Unable to get credentials for git repository: {err}"));
-                None
-            }
-        };
-
-        // Create callback that will be called by git to provide credentials per user
-        // If people use submodule, they need to provide us their ssh key
-        let get_credentials = |user: &str| {
-            let mut creds: Vec<(CredentialType, Cred)> = Vec::with_capacity(build.git_repository.ssh_keys.len() + 1);
-            for ssh_key in build.git_repository.ssh_keys.iter() {
-                let public_key = ssh_key.public_key.as_deref();
-                let passphrase = ssh_key.passphrase.as_deref();
-                if let Ok(cred) = Cred::ssh_key_from_memory(user, public_key, &ssh_key.private_key, passphrase) {
-                    creds.push((CredentialType::SSH_MEMORY, cred));
-                }
-            }
-
-            if let Some(git_creds) = &git_user_creds {
-                creds.push((
-                    CredentialType::USER_PASS_PLAINTEXT,
-                    Cred::userpass_plaintext(&git_creds.login, &git_creds.password).unwrap(),
-                ));
-            }
-
-            creds
-        };
-
-        // Cleanup, mono repo can require to clone multiple time the same repo
-        // FIXME: re-use the same repo and just checkout at the correct commit
-        if repository_root_path.exists() {
-            let app_id = build.image.service_id.clone();
-            fs::remove_dir_all(&repository_root_path).map_err(|err| BuildError::IoError {
-                application: app_id,
-                action_description: "cleaning old repository".to_string(),
-                raw_error: err,
-            })?;
-        }
-
-        // Do the real git clone
-        let git_clone_record =
-            metrics_registry.start_record(build.image.service_long_id, StepLabel::Service, StepName::GitClone);
-        if let Err(clone_error) = git::clone_at_commit(
-            &build.git_repository.url,
-            &build.git_repository.commit_id,
-            &repository_root_path,
-            &get_credentials,
-        ) {
-            git_clone_record.stop(StepStatus::Error);
-            return Err(BuildError::GitError {
-                application: build.image.service_id.clone(),
-                raw_error: clone_error,
-            });
-        }
-        git_clone_record.stop(StepStatus::Success);
-
+        let repository_root_path = repository_build_root_path(&self.context, build)?;
         let _git_cleanup = scopeguard::guard(&repository_root_path, |path| {
             info!("Removing git repository at path: {:?}", path);
             let _ = fs::remove_dir_all(path);
         });
 
-        if is_task_canceled() {
-            return Err(BuildError::Aborted {
-                application: build.image.service_id.clone(),
-            });
-        }
-
         // ensure docker_path is a mounted volume, otherwise ignore because it's not what Qovery does in production
         // ex: this cause regular cleanup on CI, leading to random tests errors
         self.reclaim_space_if_needed();
 
+        let build_context_path =
+            checkout_repository(build, &repository_root_path, logger, &metrics_registry, is_task_canceled)?;
         let app_id = build.image.service_id.clone();
 
-        // Fetch git-lfs/big files for the repository if necessary
-        let git_lfs = if let Some(creds) = git_user_creds {
-            GitLfs::new(creds.login, creds.password)
-        } else {
-            GitLfs::default()
-        };
-        let cmd_killer = CommandKiller::from_cancelable(is_task_canceled);
-        let size_estimate_kb = git_lfs
-            .files_size_estimate_in_kb(&repository_root_path, &build.git_repository.commit_id, &cmd_killer)
-            .unwrap_or(0);
-
-        if size_estimate_kb > 0 {
-            if size_estimate_kb > MAX_GIT_LFS_SIZE_KB {
-                return Err(BuildError::InvalidConfig {
-                    application: app_id,
-                    raw_error_message: format!(
-                        "GIT LFS files size are too big and are over the max allowed size of {MAX_GIT_LFS_SIZE_GB} GB"
-                    ),
-                });
-            }
-
-            info!("fetching git-lfs files");
-            logger.send_progress("🗜️ Fetching git-lfs files for repository".to_string());
-            match git_lfs.checkout_files_for_commit(&repository_root_path, &build.git_repository.commit_id, &cmd_killer)
-            {
-                Ok(_) => {}
-                Err(GitLfsError::Aborted { .. }) => return Err(BuildError::Aborted { application: app_id }),
-                Err(GitLfsError::Timeout { .. }) => return Err(BuildError::Aborted { application: app_id }),
-                Err(GitLfsError::ExecutionError { raw_error }) => {
-                    return Err(BuildError::IoError {
-                        application: app_id,
-                        action_description: "git lfs checkout".to_string(),
-                        raw_error,
-                    })
-                }
-                Err(GitLfsError::ExitStatusError { .. }) => {
-                    return Err(BuildError::IoError {
-                        application: app_id,
-                        action_description: "git lfs checkout".to_string(),
-                        raw_error: Error::new(ErrorKind::Other, "git lfs checkout failed"),
-                    })
-                }
-            }
-        }
-
-        // Check that the build context is correct
-        let build_context_path = repository_root_path.join(&build.git_repository.root_path);
-        if !build_context_path.is_dir() {
-            return Err(BuildError::InvalidConfig {
-                application: app_id,
-                raw_error_message: format!(
-                    "Specified build context path {:?} does not exist within the repository",
-                    &build.git_repository.root_path
-                ),
-            });
-        }
-
-        // Safety check to ensure we can't go up in the directory
-        if !build_context_path
-            .canonicalize()
-            .unwrap_or_default()
-            .starts_with(repository_root_path.canonicalize().unwrap_or_default())
-        {
-            return Err(BuildError::InvalidConfig {
-                application: app_id,
-                raw_error_message: format!(
-                    "Specified build context path {:?} tries to access directory outside of his git repository",
-                    &build.git_repository.root_path,
-                ),
-            });
-        }
-
         // now we have to decide if we use buildpack or docker to build our application
         // If no Dockerfile specified, we should use BuildPacks
         if let Some(dockerfile_path) = &build.git_repository.dockerfile_path {
@@ -544,3 +322,219 @@ impl BuildPlatform for LocalDocker {
         }
     }
 }
+
+/// Arguments of the `pack build` command building and publishing the application image
+fn buildpacks_args(
+    build: &Build,
+    into_dir_docker_style: &str,
+    use_build_cache: bool,
+    has_worker_process: bool,
+) -> Result<Vec<String>, BuildError> {
+    const LATEST_TAG: &str = "latest";
+    let settings = &build.git_repository.buildpacks;
+
+    let mut buildpacks_args = vec![
+        "build".to_string(),
+        "--publish".to_string(),
+        build.image.full_image_name_with_tag(),
+    ];
+    if !use_build_cache {
+        buildpacks_args.push("--clear-cache".to_string());
+    }
+
+    // layers cache is stored in the registry, so it is kept between builds whatever the builder host is
+    buildpacks_args.push("--cache-image".to_string());
+    buildpacks_args.push(format!("{}:{}", build.image.full_image_name(), BUILDPACKS_CACHE_IMAGE_TAG));
+
+    // always add 'latest' tag
+    buildpacks_args.push("-t".to_string());
+    buildpacks_args.push(format!("{}:{}", build.image.full_image_name(), LATEST_TAG));
+    buildpacks_args.push("--path".to_string());
+    buildpacks_args.push(into_dir_docker_style.to_string());
+
+    // build env is set last, so it takes precedence over the application environment variables
+    for (key, value) in build.environment_variables.iter().chain(settings.build_env.iter()) {
+        buildpacks_args.push("--env".to_string());
+        buildpacks_args.push(format!("{key}={value}"));
+    }
+
+    buildpacks_args.push("-B".to_string());
+    buildpacks_args.push(settings.builder.image().to_string());
+    for buildpack in buildpacks_order(build)? {
+        buildpacks_args.push("-b".to_string());
+        buildpacks_args.push(buildpack);
+    }
+
+    if has_worker_process {
+        buildpacks_args.push("--default-process".to_string());
+        buildpacks_args.push("worker".to_string());
+    }
+
+    Ok(buildpacks_args)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::build_platform::local_docker::buildpacks_args;
+    use crate::build_platform::{Build, BuildpacksBuilder, BuildpacksSettings, GitRepository, Image};
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use std::time::Duration;
+    use url::Url;
+
+    fn build(buildpack_language: Option<&str>, buildpacks: BuildpacksSettings) -> Build {
+        Build {
+            git_repository: GitRepository {
+                url: Url::parse("https://github.com/Qovery/engine-testing.git").unwrap(),
+                get_credentials: None,
+                ssh_keys: vec![],
+                commit_id: "my_commit_id".to_string(),
+                dockerfile_path: None,
+                root_path: PathBuf::from("/"),
+                buildpack_language: buildpack_language.map(|l| l.to_string()),
+                buildpacks,
+            },
+            image: Image {
+                name: "my-repository/my-app".to_string(),
+                tag: "my-tag".to_string(),
+                registry_url: Url::parse("https://registry.qovery.com").unwrap(),
+                ..Default::default()
+            },
+            environment_variables: BTreeMap::from([
+                ("APP_ENV".to_string(), "production".to_string()),
+                ("NODE_ENV".to_string(), "production".to_string()),
+            ]),
+            disable_cache: false,
+            timeout: Duration::from_secs(60),
+            architectures: vec![],
+            max_cpu_in_milli: 1000,
+            max_ram_in_gib: 2,
+            vulnerability_allowlist: vec![],
+        }
+    }
+
+    #[test]
+    fn test_buildpacks_builder_image() {
+        assert_eq!(BuildpacksBuilder::default().image(), "heroku/builder-classic:22");
+        assert_eq!(
+            BuildpacksBuilder::PaketoBase.image(),
+            "paketobuildpacks/builder-jammy-base:0.4.246"
+        );
+        assert_eq!(
+            BuildpacksBuilder::PaketoFull.image(),
+            "paketobuildpacks/builder-jammy-full:0.3.310"
+        );
+        assert_eq!(BuildpacksBuilder::Google.image(), "gcr.io/buildpacks/builder:v1");
+        assert_eq!(
+            BuildpacksBuilder::Custom("registry.acme.com/builders/java:3".to_string()).image(),
+            "registry.acme.com/builders/java:3"
+        );
+    }
+
+    #[test]
+    fn test_buildpacks_args() {
+        // setup:
+        struct TestCase<'a> {
+            build: Build,
+            use_build_cache: bool,
+            has_worker_process: bool,
+            expected: Vec<&'a str>,
+            description: &'a str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                build: build(None, BuildpacksSettings::default()),
+                use_build_cache: true,
+                has_worker_process: false,
+                expected: vec![
+                    "build",
+                    "--publish",
+                    "registry.qovery.com/my-repository/my-app:my-tag",
+                    "--cache-image",
+                    "registry.qovery.com/my-repository/my-app:buildpacks-cache",
+                    "-t",
+                    "registry.qovery.com/my-repository/my-app:latest",
+                    "--path",
+                    "/tmp/build",
+                    "--env",
+                    "APP_ENV=production",
+                    "--env",
+                    "NODE_ENV=production",
+                    "-B",
+                    "heroku/builder-classic:22",
+                ],
+                description: "default builder",
+            },
+            TestCase {
+                build: build(
+                    Some("heroku/nodejs@18"),
+                    BuildpacksSettings {
+                        builder: BuildpacksBuilder::PaketoBase,
+                        buildpacks: vec![
+                            "paketo-buildpacks/ca-certificates".to_string(),
+                            "paketo-buildpacks/procfile".to_string(),
+                        ],
+                        build_env: BTreeMap::from([("NODE_ENV".to_string(), "development".to_string())]),
+                    },
+                ),
+                use_build_cache: false,
+                has_worker_process: true,
+                expected: vec![
+                    "build",
+                    "--publish",
+                    "registry.qovery.com/my-repository/my-app:my-tag",
+                    "--clear-cache",
+                    "--cache-image",
+                    "registry.qovery.com/my-repository/my-app:buildpacks-cache",
+                    "-t",
+                    "registry.qovery.com/my-repository/my-app:latest",
+                    "--path",
+                    "/tmp/build",
+                    "--env",
+                    "APP_ENV=production",
+                    "--env",
+                    "NODE_ENV=production",
+                    "--env",
+                    "NODE_ENV=development",
+                    "-B",
+                    "paketobuildpacks/builder-jammy-base:latest",
+                    "-b",
+                    "heroku/nodejs",
+                    "-b",
+                    "paketo-buildpacks/ca-certificates",
+                    "-b",
+                    "paketo-buildpacks/procfile",
+                    "--default-process",
+                    "worker",
+                ],
+                description: "paketo builder with buildpacks, build env and no cache",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = buildpacks_args(&tc.build, "/tmp/build", tc.use_build_cache, tc.has_worker_process);
+
+            // verify:
+            assert_eq!(
+                result.expect("buildpacks args should be valid"),
+                tc.expected,
+                "case: {}",
+                tc.description
+            );
+        }
+    }
+
+    #[test]
+    fn test_buildpacks_args_invalid_language() {
+        // setup:
+        let build = build(Some("heroku/nodejs@18@19"), BuildpacksSettings::default());
+
+        // execute:
+        let result = buildpacks_args(&build, "/tmp/build", true, false);
+
+        // verify:
+        assert!(result.is_err());
+    }
+}
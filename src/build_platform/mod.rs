@@ -1,3 +1,5 @@
+use base64::engine::general_purpose;
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -20,7 +22,9 @@ use url::Url;
 use uuid::Uuid;
 
 pub mod dockerfile_utils;
+pub mod kubernetes_pod;
 pub mod local_docker;
+mod repository;
 
 #[derive(thiserror::Error, Debug)]
 pub enum BuildError {
@@ -57,6 +61,12 @@ pub enum BuildError {
         application: String,
         raw_error: CommandError,
     },
+
+    #[error("Cannot build Application {application:?} due to an error with the build pod: {raw_error_message:?}")]
+    BuildPodError {
+        application: String,
+        raw_error_message: String,
+    },
 }
 
 pub fn to_build_error(service_id: String, err: DockerError) -> BuildError {
@@ -71,6 +81,39 @@ pub fn to_build_error(service_id: String, err: DockerError) -> BuildError {
     }
 }
 
+/// Buildpacks explicitly requested for the build, in order. The builder detects them when empty.
+fn buildpacks_order(build: &Build) -> Result<Vec<String>, BuildError> {
+    let mut buildpacks = Vec::with_capacity(build.git_repository.buildpacks.buildpacks.len() + 1);
+    if let Some(buildpacks_language) = &build.git_repository.buildpack_language {
+        match buildpacks_language.split('@').collect::<Vec<&str>>().as_slice() {
+            [builder] => {
+                // no version specified, so we use the latest builder
+                buildpacks.push(builder.to_string());
+            }
+            [builder, _version] => {
+                // version specified, we need to use the specified builder
+                // but also ensure that the user has set the correct runtime version in his project
+                // this is language dependent
+                // https://elements.heroku.com/buildpacks/heroku/heroku-buildpack-python
+                // https://devcenter.heroku.com/articles/buildpacks
+                // TODO: Check user project is correctly configured for this builder and version
+                buildpacks.push(builder.to_string());
+            }
+            _ => {
+                return Err(BuildError::InvalidConfig {
+                    application: build.image.service_id.clone(),
+                    raw_error_message: format!(
+                        "Invalid buildpacks language format: expected `builder[@version]` got {buildpacks_language}"
+                    ),
+                });
+            }
+        }
+    }
+    buildpacks.extend(build.git_repository.buildpacks.buildpacks.iter().cloned());
+
+    Ok(buildpacks)
+}
+
 pub fn to_engine_error(event_details: EventDetails, err: BuildError, user_message: String) -> EngineError {
     match err {
         BuildError::Aborted { .. } => EngineError::new_task_cancellation_requested(event_details),
@@ -101,6 +144,8 @@ pub struct Build {
     pub architectures: Vec<CpuArchitecture>,
     pub max_cpu_in_milli: u32,
     pub max_ram_in_gib: u32,
+    // vulnerabilities ignored by the image scanning gate
+    pub vulnerability_allowlist: Vec<String>,
 }
 
 impl Build {
@@ -145,6 +190,7 @@ pub struct GitRepository {
     pub dockerfile_path: Option<PathBuf>,
     pub root_path: PathBuf,
     pub buildpack_language: Option<String>,
+    pub buildpacks: BuildpacksSettings,
 }
 impl GitRepository {
     fn credentials(&self) -> Option<anyhow::Result<Credentials>> {
@@ -152,6 +198,47 @@ impl GitRepository {
     }
 }
 
+/// Cloud Native Buildpacks builder used when the application has no Dockerfile
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum BuildpacksBuilder {
+    /// https://github.com/heroku/builder
+    #[default]
+    HerokuClassic,
+    /// https://github.com/paketo-buildpacks/builder-jammy-base
+    PaketoBase,
+    /// https://github.com/paketo-buildpacks/builder-jammy-full
+    PaketoFull,
+    /// https://github.com/GoogleCloudPlatform/buildpacks
+    Google,
+    /// Builder image reference, i.e: an in-house builder
+    Custom(String),
+}
+
+impl BuildpacksBuilder {
+    pub fn image(&self) -> &str {
+        match self {
+            BuildpacksBuilder::HerokuClassic => "heroku/builder-classic:22",
+            // pinned, as `latest` would change the stacks and buildpacks of the images between two builds
+            BuildpacksBuilder::PaketoBase => "paketobuildpacks/builder-jammy-base:0.4.246",
+            BuildpacksBuilder::PaketoFull => "paketobuildpacks/builder-jammy-full:0.3.310",
+            BuildpacksBuilder::Google => "gcr.io/buildpacks/builder:v1",
+            BuildpacksBuilder::Custom(image) => image,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct BuildpacksSettings {
+    #[serde(default)]
+    pub builder: BuildpacksBuilder,
+    /// Buildpacks to use instead of the ones detected by the builder, in order
+    #[serde(default)]
+    pub buildpacks: Vec<String>,
+    /// Environment variables only set during the build, they override the application ones
+    #[serde(default)]
+    pub build_env: BTreeMap<String, String>,
+}
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct Image {
     pub service_id: String,
@@ -208,6 +295,18 @@ impl Image {
             .strip_prefix(&format!("{}/", self.repository_name()))
             .unwrap_or(&self.name)
     }
+
+    /// Registry host and its base64 encoded basic auth credentials, from the registry url
+    pub fn registry_auth(&self) -> Option<(String, String)> {
+        let password = self.registry_url.password()?;
+        let username = urlencoding::decode(self.registry_url.username()).ok()?;
+        let password = urlencoding::decode(password).ok()?;
+
+        Some((
+            self.registry_host().to_string(),
+            general_purpose::STANDARD.encode(format!("{username}:{password}")),
+        ))
+    }
 }
 
 impl Default for Image {
@@ -241,4 +340,5 @@ impl Display for Image {
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Kind {
     LocalDocker,
+    KubernetesPod,
 }
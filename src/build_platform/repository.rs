@@ -0,0 +1,186 @@
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use git2::{Cred, CredentialType};
+
+use crate::build_platform::{Build, BuildError};
+use crate::cmd::command::CommandKiller;
+use crate::cmd::git_lfs::{GitLfs, GitLfsError};
+use crate::deployment_report::logger::EnvLogger;
+use crate::fs::workspace_directory;
+use crate::git;
+use crate::io_models::context::Context;
+use crate::metrics_registry::{MetricsRegistry, StepLabel, StepName, StepStatus};
+
+const MAX_GIT_LFS_SIZE_GB: u64 = 5;
+const MAX_GIT_LFS_SIZE_KB: u64 = MAX_GIT_LFS_SIZE_GB * 1024 * 1024; // 5GB
+
+/// Directory the repository of the service is cloned into for its build
+pub fn repository_build_root_path(context: &Context, build: &Build) -> Result<PathBuf, BuildError> {
+    workspace_directory(
+        context.workspace_root_dir(),
+        context.execution_id(),
+        format!("build/{}", build.image.name.as_str()),
+    )
+    .map(PathBuf::from)
+    .map_err(|err| BuildError::IoError {
+        application: build.image.service_id.clone(),
+        action_description: "when creating build workspace".to_string(),
+        raw_error: err,
+    })
+}
+
+/// Clone the repository at the build commit, with its git-lfs files, and return the path of the build context.
+/// Cleaning up the repository is left to the caller.
+pub fn checkout_repository(
+    build: &Build,
+    repository_root_path: &Path,
+    logger: &EnvLogger,
+    metrics_registry: &Arc<dyn MetricsRegistry>,
+    is_task_canceled: &dyn Fn() -> bool,
+) -> Result<PathBuf, BuildError> {
+    logger.send_progress(format!("📥 Cloning repository {}", build.git_repository.url));
+
+    // Retrieve git credentials
+    let git_user_creds = match build.git_repository.credentials() {
+        None => None,
+        Some(Ok(creds)) => Some(creds),
+        Some(Err(err)) => {
+            logger.send_warning(format!("🗝️ Unable to get credentials for git repository: {err}"));
+            None
+        }
+    };
+
+    // Create callback that will be called by git to provide credentials per user
+    // If people use submodule, they need to provide us their ssh key
+    let get_credentials = |user: &str| {
+        let mut creds: Vec<(CredentialType, Cred)> = Vec::with_capacity(build.git_repository.ssh_keys.len() + 1);
+        for ssh_key in build.git_repository.ssh_keys.iter() {
+            let public_key = ssh_key.public_key.as_deref();
+            let passphrase = ssh_key.passphrase.as_deref();
+            if let Ok(cred) = Cred::ssh_key_from_memory(user, public_key, &ssh_key.private_key, passphrase) {
+                creds.push((CredentialType::SSH_MEMORY, cred));
+            }
+        }
+
+        if let Some(git_creds) = &git_user_creds {
+            creds.push((
+                CredentialType::USER_PASS_PLAINTEXT,
+                Cred::userpass_plaintext(&git_creds.login, &git_creds.password).unwrap(),
+            ));
+        }
+
+        creds
+    };
+
+    // Cleanup, mono repo can require to clone multiple time the same repo
+    // FIXME: re-use the same repo and just checkout at the correct commit
+    if repository_root_path.exists() {
+        let app_id = build.image.service_id.clone();
+        fs::remove_dir_all(repository_root_path).map_err(|err| BuildError::IoError {
+            application: app_id,
+            action_description: "cleaning old repository".to_string(),
+            raw_error: err,
+        })?;
+    }
+
+    // Do the real git clone
+    let git_clone_record =
+        metrics_registry.start_record(build.image.service_long_id, StepLabel::Service, StepName::GitClone);
+    if let Err(clone_error) = git::clone_at_commit(
+        &build.git_repository.url,
+        &build.git_repository.commit_id,
+        repository_root_path,
+        &get_credentials,
+    ) {
+        git_clone_record.stop(StepStatus::Error);
+        return Err(BuildError::GitError {
+            application: build.image.service_id.clone(),
+            raw_error: clone_error,
+        });
+    }
+    git_clone_record.stop(StepStatus::Success);
+
+    if is_task_canceled() {
+        return Err(BuildError::Aborted {
+            application: build.image.service_id.clone(),
+        });
+    }
+
+    let app_id = build.image.service_id.clone();
+
+    // Fetch git-lfs/big files for the repository if necessary
+    let git_lfs = if let Some(creds) = git_user_creds {
+        GitLfs::new(creds.login, creds.password)
+    } else {
+        GitLfs::default()
+    };
+    let cmd_killer = CommandKiller::from_cancelable(is_task_canceled);
+    let size_estimate_kb = git_lfs
+        .files_size_estimate_in_kb(repository_root_path, &build.git_repository.commit_id, &cmd_killer)
+        .unwrap_or(0);
+
+    if size_estimate_kb > 0 {
+        if size_estimate_kb > MAX_GIT_LFS_SIZE_KB {
+            return Err(BuildError::InvalidConfig {
+                application: app_id,
+                raw_error_message: format!(
+                    "GIT LFS files size are too big and are over the max allowed size of {MAX_GIT_LFS_SIZE_GB} GB"
+                ),
+            });
+        }
+
+        info!("fetching git-lfs files");
+        logger.send_progress("🗜️ Fetching git-lfs files for repository".to_string());
+        match git_lfs.checkout_files_for_commit(repository_root_path, &build.git_repository.commit_id, &cmd_killer) {
+            Ok(_) => {}
+            Err(GitLfsError::Aborted { .. }) => return Err(BuildError::Aborted { application: app_id }),
+            Err(GitLfsError::Timeout { .. }) => return Err(BuildError::Aborted { application: app_id }),
+            Err(GitLfsError::ExecutionError { raw_error }) => {
+                return Err(BuildError::IoError {
+                    application: app_id,
+                    action_description: "git lfs checkout".to_string(),
+                    raw_error,
+                })
+            }
+            Err(GitLfsError::ExitStatusError { .. }) => {
+                return Err(BuildError::IoError {
+                    application: app_id,
+                    action_description: "git lfs checkout".to_string(),
+                    raw_error: Error::new(ErrorKind::Other, "git lfs checkout failed"),
+                })
+            }
+        }
+    }
+
+    // Check that the build context is correct
+    let build_context_path = repository_root_path.join(&build.git_repository.root_path);
+    if !build_context_path.is_dir() {
+        return Err(BuildError::InvalidConfig {
+            application: app_id,
+            raw_error_message: format!(
+                "Specified build context path {:?} does not exist within the repository",
+                &build.git_repository.root_path
+            ),
+        });
+    }
+
+    // Safety check to ensure we can't go up in the directory
+    if !build_context_path
+        .canonicalize()
+        .unwrap_or_default()
+        .starts_with(repository_root_path.canonicalize().unwrap_or_default())
+    {
+        return Err(BuildError::InvalidConfig {
+            application: app_id,
+            raw_error_message: format!(
+                "Specified build context path {:?} tries to access directory outside of his git repository",
+                &build.git_repository.root_path,
+            ),
+        });
+    }
+
+    Ok(build_context_path)
+}
@@ -0,0 +1,72 @@
+use crate::cloud_provider::kubernetes::KubernetesVersion;
+use serde_derive::Serialize;
+
+/// AWS CSI snapshot controller addon, required to take volume snapshots with the EBS CSI driver
+/// https://docs.aws.amazon.com/eks/latest/userguide/csi-snapshot-controller.html
+#[derive(Debug, PartialEq, Serialize)]
+pub struct AwsSnapshotControllerAddon {
+    version: String,
+}
+
+impl AwsSnapshotControllerAddon {
+    pub fn new_from_k8s_version(k8s_version: KubernetesVersion) -> Self {
+        AwsSnapshotControllerAddon {
+            // Get current default build of a snapshot-controller add-on:
+            // aws eks describe-addon-versions --kubernetes-version 1.26 --addon-name snapshot-controller | jq -r '.addons[].addonVersions[] | select(.compatibilities[].defaultVersion == true) | .addonVersion'
+            version: match k8s_version {
+                KubernetesVersion::V1_23 { .. }
+                | KubernetesVersion::V1_24 { .. }
+                | KubernetesVersion::V1_25 { .. }
+                | KubernetesVersion::V1_26 { .. }
+                | KubernetesVersion::V1_27 { .. } => "v6.3.2-eksbuild.1",
+            }
+            .to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::aws::kubernetes::addons::aws_snapshot_controller_addon::AwsSnapshotControllerAddon;
+    use crate::cloud_provider::kubernetes::KubernetesVersion;
+
+    #[test]
+    fn aws_addon_snapshot_controller_new_test() {
+        // setup:
+        struct TestCase {
+            k8s_version: KubernetesVersion,
+            expected: AwsSnapshotControllerAddon,
+        }
+
+        let tests_cases = vec![
+            TestCase {
+                k8s_version: KubernetesVersion::V1_26 {
+                    prefix: None,
+                    patch: None,
+                    suffix: None,
+                },
+                expected: AwsSnapshotControllerAddon {
+                    version: "v6.3.2-eksbuild.1".to_string(),
+                },
+            },
+            TestCase {
+                k8s_version: KubernetesVersion::V1_27 {
+                    prefix: None,
+                    patch: None,
+                    suffix: None,
+                },
+                expected: AwsSnapshotControllerAddon {
+                    version: "v6.3.2-eksbuild.1".to_string(),
+                },
+            },
+        ];
+
+        for tc in tests_cases {
+            // execute:
+            let result = AwsSnapshotControllerAddon::new_from_k8s_version(tc.k8s_version);
+
+            // verify:
+            assert_eq!(tc.expected, result);
+        }
+    }
+}
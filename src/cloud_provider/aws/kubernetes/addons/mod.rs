@@ -1,3 +1,4 @@
 pub mod aws_ebs_csi_addon;
 pub mod aws_kube_proxy;
+pub mod aws_snapshot_controller_addon;
 pub mod aws_vpc_cni_addon;
@@ -91,6 +91,14 @@ impl EC2 {
 
         let aws_zones = kubernetes::aws_zones(zones, &region, &event_details)?;
         advanced_settings.validate(event_details.clone())?;
+        // nginx is bound to the host ports of the instance, there is no load balancer a gateway could use
+        if advanced_settings.gateway_api_enabled {
+            return Err(Box::new(EngineError::new_invalid_engine_payload(
+                event_details,
+                "gateway_api.enabled is not supported on EC2 clusters",
+                None,
+            )));
+        }
         let s3 = kubernetes::s3(&region, &*cloud_provider);
         match AwsInstancesType::from_str(instance.instance_type.as_str()) {
             Err(e) => {
@@ -8,6 +8,7 @@ use crate::cloud_provider::helm_charts::nginx_ingress_chart::NginxIngressChart;
 use crate::cloud_provider::helm_charts::qovery_shell_agent_chart::QoveryShellAgentChart;
 use crate::cloud_provider::helm_charts::qovery_storage_class_chart::{QoveryStorageClassChart, QoveryStorageType};
 use crate::cloud_provider::helm_charts::{HelmChartResources, HelmChartResourcesConstraintType, ToCommonHelmChart};
+use crate::cloud_provider::io::AcmeIssuer;
 use crate::cloud_provider::qovery::EngineLocation;
 use crate::cloud_provider::Kind;
 use crate::cmd::terraform::TerraformError;
@@ -81,6 +82,7 @@ pub struct Ec2ChartsConfigPrerequisites {
     pub external_dns_provider: String,
     pub lets_encrypt_config: LetsEncryptConfig,
     pub dns_provider_config: DnsProviderConfiguration,
+    pub cert_manager_acme_issuers: Vec<AcmeIssuer>,
     // qovery options form json input
     pub infra_options: Options,
 }
@@ -240,6 +242,7 @@ pub fn ec2_aws_helm_charts(
         UpdateStrategy::Recreate,
         false,
         HelmChartNamespaces::KubeSystem,
+        false,
     )
     .to_common_helm_chart()?;
 
@@ -304,6 +307,7 @@ pub fn ec2_aws_helm_charts(
         false,
         HelmChartNamespaces::CertManager,
         HelmChartNamespaces::KubeSystem,
+        false,
     )
     .to_common_helm_chart()?;
 
@@ -314,6 +318,8 @@ pub fn ec2_aws_helm_charts(
         &chart_config_prerequisites.dns_provider_config,
         chart_config_prerequisites.managed_dns_helm_format.to_string(),
         HelmChartNamespaces::CertManager,
+        &chart_config_prerequisites.cert_manager_acme_issuers,
+        false,
     )
     .to_common_helm_chart()?;
 
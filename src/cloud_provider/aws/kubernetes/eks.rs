@@ -790,6 +790,10 @@ impl NodeGroupsWithDesiredState {
             instance_type,
             disk_size_in_gib,
             instance_architecture: CpuArchitecture::AMD64,
+            capacity_type: crate::cloud_provider::models::NodeGroupCapacityType::OnDemand,
+            fallback_instance_types: vec![],
+            labels: std::collections::BTreeMap::new(),
+            taints: vec![],
         }
     }
 }
@@ -1,9 +1,10 @@
 use crate::cloud_provider::aws::kubernetes::Options;
 use crate::cloud_provider::helm::{
-    get_engine_helm_action_from_location, ChartInfo, ChartSetValue, CommonChart, HelmChart, HelmChartNamespaces,
-    UpdateStrategy,
+    get_engine_helm_action_from_location, ChartInfo, ChartSetValue, CommonChart, HelmAction, HelmChart,
+    HelmChartNamespaces, UpdateStrategy,
 };
 use crate::cloud_provider::helm_charts::coredns_config_chart::CoreDNSConfigChart;
+use crate::cloud_provider::helm_charts::envoy_gateway_chart::EnvoyGatewayChart;
 use crate::cloud_provider::helm_charts::nginx_ingress_chart::NginxIngressChart;
 use crate::cloud_provider::helm_charts::promtail_chart::PromtailChart;
 use crate::cloud_provider::helm_charts::qovery_shell_agent_chart::QoveryShellAgentChart;
@@ -14,7 +15,7 @@ use crate::cloud_provider::helm_charts::{
 };
 use crate::cloud_provider::io::ClusterAdvancedSettings;
 use crate::cloud_provider::models::{
-    CpuArchitecture, CustomerHelmChartsOverride, KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit,
+    CpuArchitecture, CustomerHelmChartsOverride, KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit, NodeGroups,
     VpcQoveryNetworkMode,
 };
 use crate::cloud_provider::qovery::EngineLocation;
@@ -29,6 +30,10 @@ use crate::cloud_provider::aws::kubernetes::helm_charts::aws_iam_eks_user_mapper
 use crate::cloud_provider::aws::kubernetes::helm_charts::aws_node_term_handler_chart::AwsNodeTermHandlerChart;
 use crate::cloud_provider::aws::kubernetes::helm_charts::aws_ui_view_chart::AwsUiViewChart;
 use crate::cloud_provider::aws::kubernetes::helm_charts::cluster_autoscaler_chart::ClusterAutoscalerChart;
+use crate::cloud_provider::aws::kubernetes::helm_charts::karpenter_chart::KarpenterChart;
+use crate::cloud_provider::aws::kubernetes::helm_charts::karpenter_configuration_chart::{
+    KarpenterConfigurationChart, KarpenterNodeClassConfig,
+};
 use crate::cloud_provider::aws::regions::AwsRegion;
 use crate::cloud_provider::helm_charts::cert_manager_chart::CertManagerChart;
 use crate::cloud_provider::helm_charts::cert_manager_config_chart::CertManagerConfigsChart;
@@ -43,15 +48,17 @@ use crate::cloud_provider::helm_charts::metrics_server_chart::MetricsServerChart
 use crate::cloud_provider::helm_charts::prometheus_adapter_chart::PrometheusAdapterChart;
 use crate::cloud_provider::helm_charts::qovery_cert_manager_webhook_chart::QoveryCertManagerWebhookChart;
 use crate::cloud_provider::helm_charts::qovery_cluster_agent_chart::QoveryClusterAgentChart;
+use crate::cloud_provider::helm_charts::qovery_gateway_chart::QoveryGatewayChart;
 use crate::engine_task::qovery_api::{EngineServiceType, QoveryApi};
 use crate::io_models::engine_request::{ChartValuesOverrideName, ChartValuesOverrideValues};
 use crate::io_models::QoveryIdentifier;
 use crate::models::aws::AwsStorageType;
+use crate::models::domain::Domain;
 use crate::models::third_parties::LetsEncryptConfig;
 use crate::models::ToCloudProviderFormat;
 use chrono::Duration;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::BufReader;
 use std::iter::FromIterator;
@@ -67,6 +74,17 @@ pub struct AwsEksQoveryTerraformConfig {
     pub aws_iam_loki_role_arn: String,
     pub aws_s3_loki_bucket_name: String,
     pub loki_storage_config_aws_s3: String,
+    /// Only set when karpenter is enabled
+    pub karpenter: Option<AwsEksKarpenterTerraformConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AwsEksKarpenterTerraformConfig {
+    pub aws_iam_karpenter_role_arn: String,
+    pub interruption_queue_name: String,
+    pub node_role_name: String,
+    pub subnet_ids: Vec<String>,
+    pub security_group_id: String,
 }
 
 pub struct EksChartsConfigPrerequisites {
@@ -77,6 +95,7 @@ pub struct EksChartsConfigPrerequisites {
     pub region: AwsRegion,
     pub cluster_name: String,
     pub cpu_architectures: Vec<CpuArchitecture>,
+    pub node_groups: Vec<NodeGroups>,
     pub cloud_provider: String,
     pub test_cluster: bool,
     pub aws_access_key_id: String,
@@ -231,7 +250,10 @@ pub fn eks_aws_helm_charts(
     .to_common_helm_chart()?;
 
     // Cluster autoscaler
-    let cluster_autoscaler = ClusterAutoscalerChart::new(
+    let karpenter_enabled = chart_config_prerequisites
+        .cluster_advanced_settings
+        .aws_eks_karpenter_enabled;
+    let mut cluster_autoscaler = ClusterAutoscalerChart::new(
         chart_prefix_path,
         chart_config_prerequisites.cloud_provider.to_string(),
         chart_config_prerequisites.region.clone(),
@@ -241,6 +263,55 @@ pub fn eks_aws_helm_charts(
         chart_config_prerequisites.ff_metrics_history_enabled,
     )
     .to_common_helm_chart()?;
+    // cluster-autoscaler is removed once karpenter is ready to take over pending pods
+    if karpenter_enabled {
+        cluster_autoscaler.chart_info.action = HelmAction::Destroy;
+    }
+
+    // Karpenter, node groups stay in place to host karpenter itself and the cluster system pods
+    let karpenter_terraform_config = match (karpenter_enabled, &qovery_terraform_config.karpenter) {
+        (true, None) => {
+            return Err(CommandError::new_from_safe_message(
+                "Karpenter is enabled but its configuration is missing from terraform outputs".to_string(),
+            ))
+        }
+        (_, karpenter_terraform_config) => karpenter_terraform_config.clone(),
+    };
+    let karpenter_action = match karpenter_enabled {
+        true => HelmAction::Deploy,
+        false => HelmAction::Destroy,
+    };
+    let karpenter = KarpenterChart::new(
+        chart_prefix_path,
+        chart_config_prerequisites.cluster_name.to_string(),
+        karpenter_terraform_config
+            .as_ref()
+            .map(|c| c.aws_iam_karpenter_role_arn.to_string())
+            .unwrap_or_default(),
+        karpenter_terraform_config
+            .as_ref()
+            .map(|c| c.interruption_queue_name.to_string())
+            .unwrap_or_default(),
+        chart_config_prerequisites.ff_metrics_history_enabled,
+        karpenter_action.clone(),
+    )
+    .to_common_helm_chart()?;
+    let karpenter_configuration = KarpenterConfigurationChart::new(
+        chart_prefix_path,
+        chart_config_prerequisites.cluster_name.to_string(),
+        chart_config_prerequisites.node_groups.clone(),
+        karpenter_terraform_config.map(|c| KarpenterNodeClassConfig {
+            node_role_name: c.node_role_name,
+            subnet_ids: c.subnet_ids,
+            security_group_id: c.security_group_id,
+            metadata_imds: chart_config_prerequisites
+                .cluster_advanced_settings
+                .aws_eks_ec2_metadata_imds
+                .clone(),
+        }),
+        karpenter_action,
+    )
+    .to_common_helm_chart()?;
 
     // CoreDNS config
     let coredns_config = CoreDNSConfigChart::new(
@@ -264,6 +335,7 @@ pub fn eks_aws_helm_charts(
         UpdateStrategy::RollingUpdate,
         true,
         HelmChartNamespaces::KubeSystem,
+        chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled,
     )
     .to_common_helm_chart()?;
 
@@ -423,6 +495,7 @@ pub fn eks_aws_helm_charts(
         true,
         HelmChartNamespaces::CertManager,
         HelmChartNamespaces::KubeSystem,
+        chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled,
     )
     .to_common_helm_chart()?;
 
@@ -433,9 +506,45 @@ pub fn eks_aws_helm_charts(
         &chart_config_prerequisites.dns_provider_config,
         chart_config_prerequisites.managed_dns_helm_format.to_string(),
         HelmChartNamespaces::CertManager,
+        &chart_config_prerequisites
+            .cluster_advanced_settings
+            .cert_manager_acme_issuers,
+        chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled,
     )
     .to_common_helm_chart()?;
 
+    // Gateway API, installed next to nginx which keeps serving existing ingresses
+    let envoy_gateway = match chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled {
+        false => None,
+        true => Some(
+            EnvoyGatewayChart::new(
+                chart_prefix_path,
+                HelmChartResourcesConstraintType::ChartDefault,
+                get_chart_overrride_fn.clone(),
+                HelmChartNamespaces::EnvoyGateway,
+            )
+            .to_common_helm_chart()?,
+        ),
+    };
+    let qovery_gateway = match chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled {
+        false => None,
+        true => Some(
+            QoveryGatewayChart::new(
+                chart_prefix_path,
+                Domain::new(chart_config_prerequisites.managed_dns_name.to_string())
+                    .wildcarded()
+                    .to_string(),
+                HelmChartNamespaces::CertManager,
+                BTreeMap::from([(
+                    "service.beta.kubernetes.io/aws-load-balancer-type".to_string(),
+                    "nlb".to_string(),
+                )]),
+                HelmChartNamespaces::EnvoyGateway,
+            )
+            .to_common_helm_chart()?,
+        ),
+    };
+
     // Nginx ingress
     let nginx_ingress = NginxIngressChart::new(
         chart_prefix_path,
@@ -634,19 +743,34 @@ pub fn eks_aws_helm_charts(
 
     let level_3: Vec<Box<dyn HelmChart>> = vec![Box::new(cert_manager)];
 
-    let mut level_4: Vec<Box<dyn HelmChart>> = vec![Box::new(cluster_autoscaler)];
+    let mut level_4: Vec<Box<dyn HelmChart>> = vec![];
 
     if let Some(qovery_webhook) = qovery_cert_manager_webhook {
         level_4.push(Box::new(qovery_webhook));
     }
 
-    let level_5: Vec<Box<dyn HelmChart>> = vec![
+    let mut level_5: Vec<Box<dyn HelmChart>> = vec![
         Box::new(metrics_server),
         Box::new(aws_node_term_handler),
         Box::new(external_dns),
     ];
 
-    let level_6: Vec<Box<dyn HelmChart>> = vec![Box::new(nginx_ingress)];
+    let mut level_6: Vec<Box<dyn HelmChart>> = vec![Box::new(nginx_ingress)];
+
+    // When switching between cluster-autoscaler and karpenter, the new node provisioner is ready before the old one is removed.
+    // Karpenter node pools are removed while karpenter is still running, so it can drain and delete its nodes.
+    match karpenter_enabled {
+        true => {
+            level_4.push(Box::new(karpenter));
+            level_5.push(Box::new(karpenter_configuration));
+            level_5.push(Box::new(cluster_autoscaler));
+        }
+        false => {
+            level_4.push(Box::new(cluster_autoscaler));
+            level_5.push(Box::new(karpenter_configuration));
+            level_6.push(Box::new(karpenter));
+        }
+    }
 
     let level_7: Vec<Box<dyn HelmChart>> = vec![
         Box::new(cert_manager_config),
@@ -675,6 +799,14 @@ pub fn eks_aws_helm_charts(
         level_2.push(Box::new(grafana_chart))
     }
 
+    // gateway API CRDs must exist before cert-manager starts watching gateways
+    if let Some(envoy_gateway_chart) = envoy_gateway {
+        level_2.push(Box::new(envoy_gateway_chart));
+    }
+    if let Some(qovery_gateway_chart) = qovery_gateway {
+        level_6.push(Box::new(qovery_gateway_chart));
+    }
+
     info!("charts configuration preparation finished");
     Ok(vec![level_1, level_2, level_3, level_4, level_5, level_6, level_7])
 }
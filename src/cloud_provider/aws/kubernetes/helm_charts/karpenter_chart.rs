@@ -0,0 +1,224 @@
+use crate::cloud_provider::helm::{
+    ChartInfo, ChartInstallationChecker, ChartSetValue, CommonChart, HelmAction, HelmChartError, HelmChartNamespaces,
+};
+use crate::cloud_provider::helm_charts::{
+    HelmChartDirectoryLocation, HelmChartPath, HelmChartValuesFilePath, ToCommonHelmChart,
+};
+use crate::errors::CommandError;
+use kube::Client;
+
+pub struct KarpenterChart {
+    chart_path: HelmChartPath,
+    chart_values_path: HelmChartValuesFilePath,
+    cluster_name: String,
+    aws_iam_karpenter_role_arn: String,
+    interruption_queue_name: String,
+    ff_metrics_history_enabled: bool,
+    action: HelmAction,
+}
+
+impl KarpenterChart {
+    pub fn new(
+        chart_prefix_path: Option<&str>,
+        cluster_name: String,
+        aws_iam_karpenter_role_arn: String,
+        interruption_queue_name: String,
+        ff_metrics_history_enabled: bool,
+        action: HelmAction,
+    ) -> Self {
+        KarpenterChart {
+            chart_path: HelmChartPath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CloudProviderFolder,
+                KarpenterChart::chart_name(),
+            ),
+            chart_values_path: HelmChartValuesFilePath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CloudProviderFolder,
+                KarpenterChart::chart_name(),
+            ),
+            cluster_name,
+            aws_iam_karpenter_role_arn,
+            interruption_queue_name,
+            ff_metrics_history_enabled,
+            action,
+        }
+    }
+
+    fn chart_name() -> String {
+        "karpenter".to_string()
+    }
+}
+
+impl ToCommonHelmChart for KarpenterChart {
+    fn to_common_helm_chart(&self) -> Result<CommonChart, HelmChartError> {
+        Ok(CommonChart {
+            chart_info: ChartInfo {
+                name: KarpenterChart::chart_name(),
+                path: self.chart_path.to_string(),
+                namespace: HelmChartNamespaces::KubeSystem,
+                action: self.action.clone(),
+                values_files: vec![self.chart_values_path.to_string()],
+                values: vec![
+                    ChartSetValue {
+                        key: "settings.clusterName".to_string(),
+                        value: self.cluster_name.to_string(),
+                    },
+                    ChartSetValue {
+                        key: "settings.interruptionQueue".to_string(),
+                        value: self.interruption_queue_name.to_string(),
+                    },
+                    ChartSetValue {
+                        // we use string templating (r"...") to escape dot in annotation's key
+                        key: r"serviceAccount.annotations.eks\.amazonaws\.com/role-arn".to_string(),
+                        value: self.aws_iam_karpenter_role_arn.to_string(),
+                    },
+                    // observability
+                    ChartSetValue {
+                        key: "serviceMonitor.enabled".to_string(),
+                        value: self.ff_metrics_history_enabled.to_string(),
+                    },
+                ],
+                ..Default::default()
+            },
+            chart_installation_checker: Some(Box::new(KarpenterChartChecker::new())),
+            vertical_pod_autoscaler: None,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct KarpenterChartChecker {}
+
+impl KarpenterChartChecker {
+    pub fn new() -> KarpenterChartChecker {
+        KarpenterChartChecker {}
+    }
+}
+
+impl Default for KarpenterChartChecker {
+    fn default() -> Self {
+        KarpenterChartChecker::new()
+    }
+}
+
+impl ChartInstallationChecker for KarpenterChartChecker {
+    fn verify_installation(&self, _kube_client: &Client) -> Result<(), CommandError> {
+        // TODO(ENG-1366): Implement chart install verification
+        Ok(())
+    }
+
+    fn clone_dyn(&self) -> Box<dyn ChartInstallationChecker> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::aws::kubernetes::helm_charts::karpenter_chart::KarpenterChart;
+    use crate::cloud_provider::helm::HelmAction;
+    use crate::cloud_provider::helm_charts::{
+        get_helm_path_kubernetes_provider_sub_folder_name, get_helm_values_set_in_code_but_absent_in_values_file,
+        HelmChartType, ToCommonHelmChart,
+    };
+    use crate::cloud_provider::kubernetes::Kind as KubernetesKind;
+    use std::env;
+
+    /// Makes sure chart directory containing all YAML files exists.
+    #[test]
+    fn karpenter_chart_directory_exists_test() {
+        // setup:
+        let chart = KarpenterChart::new(
+            None,
+            "whatever".to_string(),
+            "whatever".to_string(),
+            "whatever".to_string(),
+            true,
+            HelmAction::Deploy,
+        );
+
+        let current_directory = env::current_dir().expect("Impossible to get current directory");
+        let chart_path = format!(
+            "{}/lib/{}/bootstrap/charts/{}/Chart.yaml",
+            current_directory
+                .to_str()
+                .expect("Impossible to convert current directory to string"),
+            get_helm_path_kubernetes_provider_sub_folder_name(
+                chart.chart_path.helm_path(),
+                HelmChartType::CloudProviderSpecific(KubernetesKind::Eks)
+            ),
+            KarpenterChart::chart_name(),
+        );
+
+        // execute
+        let values_file = std::fs::File::open(&chart_path);
+
+        // verify:
+        assert!(values_file.is_ok(), "Chart directory should exist: `{chart_path}`");
+    }
+
+    /// Makes sure chart values file exists.
+    #[test]
+    fn karpenter_chart_values_file_exists_test() {
+        // setup:
+        let chart = KarpenterChart::new(
+            None,
+            "whatever".to_string(),
+            "whatever".to_string(),
+            "whatever".to_string(),
+            true,
+            HelmAction::Deploy,
+        );
+
+        let current_directory = env::current_dir().expect("Impossible to get current directory");
+        let chart_values_path = format!(
+            "{}/lib/{}/bootstrap/chart_values/{}.yaml",
+            current_directory
+                .to_str()
+                .expect("Impossible to convert current directory to string"),
+            get_helm_path_kubernetes_provider_sub_folder_name(
+                chart.chart_values_path.helm_path(),
+                HelmChartType::CloudProviderSpecific(KubernetesKind::Eks)
+            ),
+            KarpenterChart::chart_name(),
+        );
+
+        // execute
+        let values_file = std::fs::File::open(&chart_values_path);
+
+        // verify:
+        assert!(values_file.is_ok(), "Chart values file should exist: `{chart_values_path}`");
+    }
+
+    /// Make sure rust code doesn't set a value not declared inside values file.
+    /// All values should be declared / set in values file unless it needs to be injected via rust code.
+    #[test]
+    fn karpenter_chart_rust_overridden_values_exists_in_values_yaml_test() {
+        // setup:
+        let chart = KarpenterChart::new(
+            None,
+            "whatever".to_string(),
+            "whatever".to_string(),
+            "whatever".to_string(),
+            true,
+            HelmAction::Deploy,
+        );
+        let common_chart = chart.to_common_helm_chart().unwrap();
+
+        // execute:
+        let missing_fields = get_helm_values_set_in_code_but_absent_in_values_file(
+            common_chart,
+            format!(
+                "/lib/{}/bootstrap/chart_values/{}.yaml",
+                get_helm_path_kubernetes_provider_sub_folder_name(
+                    chart.chart_values_path.helm_path(),
+                    HelmChartType::CloudProviderSpecific(KubernetesKind::Eks)
+                ),
+                KarpenterChart::chart_name()
+            ),
+        );
+
+        // verify:
+        assert!(missing_fields.is_none(), "Some fields are missing in values file, add those (make sure they still exist in chart values), fields: {}", missing_fields.unwrap_or_default().join(","));
+    }
+}
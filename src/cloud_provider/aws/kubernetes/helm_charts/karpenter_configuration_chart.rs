@@ -0,0 +1,269 @@
+use crate::cloud_provider::helm::{
+    ChartInfo, ChartInstallationChecker, ChartValuesGenerated, CommonChart, HelmAction, HelmChartError,
+    HelmChartNamespaces,
+};
+use crate::cloud_provider::helm_charts::{HelmChartDirectoryLocation, HelmChartPath, ToCommonHelmChart};
+use crate::cloud_provider::io::AwsEc2MetadataImds;
+use crate::cloud_provider::models::{CpuArchitecture, NodeGroupTaint, NodeGroups};
+use crate::errors::CommandError;
+use kube::Client;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Karpenter network and IAM settings, created by the EKS terraform
+#[derive(Clone, Debug)]
+pub struct KarpenterNodeClassConfig {
+    pub node_role_name: String,
+    pub subnet_ids: Vec<String>,
+    pub security_group_id: String,
+    pub metadata_imds: AwsEc2MetadataImds,
+}
+
+/// NodePool (and its EC2NodeClass) generated from a cluster node group
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KarpenterNodePool {
+    pub name: String,
+    pub node_group_name: String,
+    pub architecture: &'static str,
+    pub capacity_types: Vec<&'static str>,
+    /// Karpenter picks the best fitting size among the families of the node group instance types
+    pub instance_families: Vec<String>,
+    pub labels: BTreeMap<String, String>,
+    pub taints: Vec<NodeGroupTaint>,
+    pub disk_size_in_gib: i32,
+}
+
+impl KarpenterNodePool {
+    pub fn new(node_group: &NodeGroups) -> Self {
+        let name: String = node_group
+            .name
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+
+        let mut instance_families: Vec<String> = Vec::with_capacity(node_group.fallback_instance_types.len() + 1);
+        for instance_type in std::iter::once(&node_group.instance_type).chain(&node_group.fallback_instance_types) {
+            let family = instance_type
+                .to_lowercase()
+                .split('.')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            if !family.is_empty() && !instance_families.contains(&family) {
+                instance_families.push(family);
+            }
+        }
+
+        KarpenterNodePool {
+            name: format!("qovery-{name}"),
+            node_group_name: node_group.name.to_string(),
+            architecture: match node_group.instance_architecture {
+                CpuArchitecture::AMD64 => "amd64",
+                CpuArchitecture::ARM64 => "arm64",
+            },
+            // spot node groups fall back on on-demand capacity when there is no spot capacity left
+            capacity_types: match node_group.capacity_type.is_interruptible() {
+                true => vec!["spot", "on-demand"],
+                false => vec!["on-demand"],
+            },
+            instance_families,
+            labels: node_group.labels.clone(),
+            taints: node_group.taints.clone(),
+            disk_size_in_gib: node_group.disk_size_in_gib,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KarpenterConfigurationValues<'a> {
+    cluster_name: &'a str,
+    node_role_name: &'a str,
+    subnet_ids: &'a [String],
+    security_group_id: &'a str,
+    metadata_http_tokens: &'a AwsEc2MetadataImds,
+    node_pools: Vec<KarpenterNodePool>,
+}
+
+pub struct KarpenterConfigurationChart {
+    chart_path: HelmChartPath,
+    cluster_name: String,
+    node_groups: Vec<NodeGroups>,
+    node_class_config: Option<KarpenterNodeClassConfig>,
+    action: HelmAction,
+}
+
+impl KarpenterConfigurationChart {
+    pub fn new(
+        chart_prefix_path: Option<&str>,
+        cluster_name: String,
+        node_groups: Vec<NodeGroups>,
+        node_class_config: Option<KarpenterNodeClassConfig>,
+        action: HelmAction,
+    ) -> Self {
+        KarpenterConfigurationChart {
+            chart_path: HelmChartPath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CloudProviderFolder,
+                KarpenterConfigurationChart::chart_name(),
+            ),
+            cluster_name,
+            node_groups,
+            node_class_config,
+            action,
+        }
+    }
+
+    fn chart_name() -> String {
+        "karpenter-configuration".to_string()
+    }
+
+    fn node_pools_yaml(&self, node_class_config: &KarpenterNodeClassConfig) -> Result<String, HelmChartError> {
+        let values = KarpenterConfigurationValues {
+            cluster_name: &self.cluster_name,
+            node_role_name: &node_class_config.node_role_name,
+            subnet_ids: &node_class_config.subnet_ids,
+            security_group_id: &node_class_config.security_group_id,
+            metadata_http_tokens: &node_class_config.metadata_imds,
+            node_pools: self.node_groups.iter().map(KarpenterNodePool::new).collect(),
+        };
+
+        serde_yaml::to_string(&values).map_err(|e| HelmChartError::CreateTemplateError {
+            chart_name: KarpenterConfigurationChart::chart_name(),
+            msg: e.to_string(),
+        })
+    }
+}
+
+impl ToCommonHelmChart for KarpenterConfigurationChart {
+    fn to_common_helm_chart(&self) -> Result<CommonChart, HelmChartError> {
+        Ok(CommonChart {
+            chart_info: ChartInfo {
+                name: KarpenterConfigurationChart::chart_name(),
+                path: self.chart_path.to_string(),
+                namespace: HelmChartNamespaces::KubeSystem,
+                action: self.action.clone(),
+                yaml_files_content: match &self.node_class_config {
+                    Some(node_class_config) => vec![ChartValuesGenerated {
+                        filename: "karpenter-configuration_generated.yaml".to_string(),
+                        yaml_content: self.node_pools_yaml(node_class_config)?,
+                    }],
+                    None => vec![],
+                },
+                ..Default::default()
+            },
+            chart_installation_checker: Some(Box::new(KarpenterConfigurationChartChecker::new())),
+            vertical_pod_autoscaler: None,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct KarpenterConfigurationChartChecker {}
+
+impl KarpenterConfigurationChartChecker {
+    pub fn new() -> KarpenterConfigurationChartChecker {
+        KarpenterConfigurationChartChecker {}
+    }
+}
+
+impl Default for KarpenterConfigurationChartChecker {
+    fn default() -> Self {
+        KarpenterConfigurationChartChecker::new()
+    }
+}
+
+impl ChartInstallationChecker for KarpenterConfigurationChartChecker {
+    fn verify_installation(&self, _kube_client: &Client) -> Result<(), CommandError> {
+        // TODO(ENG-1366): Implement chart install verification
+        Ok(())
+    }
+
+    fn clone_dyn(&self) -> Box<dyn ChartInstallationChecker> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::aws::kubernetes::helm_charts::karpenter_configuration_chart::{
+        KarpenterConfigurationChart, KarpenterNodePool,
+    };
+    use crate::cloud_provider::helm::HelmAction;
+    use crate::cloud_provider::helm_charts::{get_helm_path_kubernetes_provider_sub_folder_name, HelmChartType};
+    use crate::cloud_provider::kubernetes::Kind as KubernetesKind;
+    use crate::cloud_provider::models::{
+        CpuArchitecture, NodeGroupCapacityType, NodeGroupTaint, NodeGroups, TaintEffect,
+    };
+    use std::collections::BTreeMap;
+    use std::env;
+
+    /// Makes sure chart directory containing all YAML files exists.
+    #[test]
+    fn karpenter_configuration_chart_directory_exists_test() {
+        // setup:
+        let chart = KarpenterConfigurationChart::new(None, "whatever".to_string(), vec![], None, HelmAction::Deploy);
+
+        let current_directory = env::current_dir().expect("Impossible to get current directory");
+        let chart_path = format!(
+            "{}/lib/{}/bootstrap/charts/{}/Chart.yaml",
+            current_directory
+                .to_str()
+                .expect("Impossible to convert current directory to string"),
+            get_helm_path_kubernetes_provider_sub_folder_name(
+                chart.chart_path.helm_path(),
+                HelmChartType::CloudProviderSpecific(KubernetesKind::Eks)
+            ),
+            KarpenterConfigurationChart::chart_name(),
+        );
+
+        // execute
+        let values_file = std::fs::File::open(&chart_path);
+
+        // verify:
+        assert!(values_file.is_ok(), "Chart directory should exist: `{chart_path}`");
+    }
+
+    #[test]
+    fn karpenter_node_pool_from_node_group_test() {
+        // setup:
+        let node_group = NodeGroups {
+            name: "Spot_Workers".to_string(),
+            id: None,
+            min_nodes: 1,
+            max_nodes: 10,
+            desired_nodes: None,
+            instance_type: "t4g.large".to_string(),
+            disk_size_in_gib: 50,
+            instance_architecture: CpuArchitecture::ARM64,
+            capacity_type: NodeGroupCapacityType::Spot,
+            fallback_instance_types: vec!["t4g.xlarge".to_string(), "m6g.large".to_string()],
+            labels: BTreeMap::from([("workload".to_string(), "batch".to_string())]),
+            taints: vec![NodeGroupTaint {
+                key: "spot".to_string(),
+                value: "true".to_string(),
+                effect: TaintEffect::NoSchedule,
+            }],
+        };
+
+        // execute:
+        let node_pool = KarpenterNodePool::new(&node_group);
+
+        // verify:
+        assert_eq!(node_pool.name, "qovery-spot-workers");
+        assert_eq!(node_pool.architecture, "arm64");
+        assert_eq!(node_pool.capacity_types, vec!["spot", "on-demand"]);
+        assert_eq!(node_pool.instance_families, vec!["t4g".to_string(), "m6g".to_string()]);
+        assert_eq!(node_pool.labels, node_group.labels);
+        assert_eq!(node_pool.taints, node_group.taints);
+        assert_eq!(node_pool.disk_size_in_gib, 50);
+
+        let on_demand_node_pool = KarpenterNodePool::new(&NodeGroups {
+            capacity_type: NodeGroupCapacityType::OnDemand,
+            ..node_group
+        });
+        assert_eq!(on_demand_node_pool.capacity_types, vec!["on-demand"]);
+    }
+}
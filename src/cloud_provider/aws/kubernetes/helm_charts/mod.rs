@@ -2,3 +2,5 @@ pub mod aws_iam_eks_user_mapper_chart;
 pub mod aws_node_term_handler_chart;
 pub mod aws_ui_view_chart;
 pub mod cluster_autoscaler_chart;
+pub mod karpenter_chart;
+pub mod karpenter_configuration_chart;
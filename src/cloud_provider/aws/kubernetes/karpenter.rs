@@ -0,0 +1,91 @@
+use crate::errors::{CommandError, EngineError};
+use crate::events::EventDetails;
+use crate::runtime::block_on;
+use k8s_openapi::api::apps::v1::Deployment;
+use kube::api::{ApiResource, DeleteParams, DynamicObject, GroupVersionKind, ListParams};
+use kube::Api;
+use std::time::{Duration, Instant};
+
+const KARPENTER_API_GROUP: &str = "karpenter.sh";
+const KARPENTER_API_VERSION: &str = "v1beta1";
+const KARPENTER_NAMESPACE: &str = "kube-system";
+const KARPENTER_DEPLOYMENT_NAME: &str = "karpenter";
+
+/// Whether the Karpenter controller is still deployed on the cluster.
+/// While it is, its IAM role and interruption queue have to be kept so it can drain and terminate its nodes.
+pub fn is_karpenter_deployed(
+    kube_client: &kube::Client,
+    event_details: EventDetails,
+) -> Result<bool, Box<EngineError>> {
+    let deployments: Api<Deployment> = Api::namespaced(kube_client.clone(), KARPENTER_NAMESPACE);
+    block_on(deployments.get_opt(KARPENTER_DEPLOYMENT_NAME))
+        .map(|deployment| deployment.is_some())
+        .map_err(|e| {
+            Box::new(EngineError::new_k8s_get_deployment_error(
+                event_details,
+                CommandError::new_from_safe_message(format!("Cannot get Karpenter deployment: {e}")),
+            ))
+        })
+}
+
+/// Delete the Karpenter node pools and wait for Karpenter to drain and terminate their nodes.
+/// Those nodes are not part of the EKS node groups: they would keep running while the cluster is paused,
+/// and would be orphaned if Karpenter is uninstalled before them, preventing terraform from destroying the cluster.
+pub fn delete_karpenter_nodes(
+    kube_client: &kube::Client,
+    timeout: Duration,
+    event_details: EventDetails,
+) -> Result<(), Box<EngineError>> {
+    block_on(delete_node_pools_and_wait_for_node_claims(kube_client, timeout)).map_err(|e| {
+        Box::new(EngineError::new_karpenter_nodes_delete_error(
+            event_details,
+            CommandError::new_from_safe_message(e),
+        ))
+    })
+}
+
+async fn delete_node_pools_and_wait_for_node_claims(
+    kube_client: &kube::Client,
+    timeout: Duration,
+) -> Result<(), String> {
+    let karpenter_api = |kind: &str| -> Api<DynamicObject> {
+        Api::all_with(
+            kube_client.clone(),
+            &ApiResource::from_gvk(&GroupVersionKind::gvk(KARPENTER_API_GROUP, KARPENTER_API_VERSION, kind)),
+        )
+    };
+    let node_pools = karpenter_api("NodePool");
+    let node_claims = karpenter_api("NodeClaim");
+
+    match node_pools
+        .delete_collection(&DeleteParams::default(), &ListParams::default())
+        .await
+    {
+        Ok(_) => {}
+        // Karpenter CRDs are not installed, so there is no Karpenter node
+        Err(kube::Error::Api(e)) if e.code == 404 => return Ok(()),
+        Err(e) => return Err(format!("Cannot delete Karpenter node pools: {e}")),
+    }
+
+    // node claims are owned by their node pool, Karpenter releases them once their node is drained and terminated
+    let started_at = Instant::now();
+    loop {
+        let remaining_node_claims = node_claims
+            .list(&ListParams::default())
+            .await
+            .map_err(|e| format!("Cannot list Karpenter node claims: {e}"))?
+            .items
+            .len();
+        if remaining_node_claims == 0 {
+            return Ok(());
+        }
+        if started_at.elapsed() > timeout {
+            return Err(format!(
+                "{remaining_node_claims} Karpenter nodes are still running after {} minutes",
+                timeout.as_secs() / 60
+            ));
+        }
+
+        tokio::time::sleep(Duration::from_secs(10)).await;
+    }
+}
@@ -17,6 +17,7 @@ use serde::{Deserialize, Serialize};
 use tera::Context as TeraContext;
 
 use crate::cloud_provider::aws::kubernetes::addons::aws_ebs_csi_addon::AwsEbsCsiAddon;
+use crate::cloud_provider::aws::kubernetes::addons::aws_snapshot_controller_addon::AwsSnapshotControllerAddon;
 use crate::cloud_provider::aws::kubernetes::addons::aws_vpc_cni_addon::AwsVpcCniAddon;
 use crate::cloud_provider::aws::kubernetes::ec2_helm_charts::{
     ec2_aws_helm_charts, get_aws_ec2_qovery_terraform_config, Ec2ChartsConfigPrerequisites,
@@ -66,6 +67,7 @@ use tokio::time::Duration;
 use self::addons::aws_kube_proxy::AwsKubeProxyAddon;
 use self::ec2::EC2;
 use self::eks::{delete_eks_nodegroups, select_nodegroups_autoscaling_group_behavior, NodeGroupsDeletionType};
+use self::karpenter::{delete_karpenter_nodes, is_karpenter_deployed};
 use crate::cmd::command::CommandKiller;
 
 use super::models::QoveryAwsSdkConfigEks;
@@ -76,6 +78,7 @@ mod ec2_helm_charts;
 pub mod eks;
 pub mod eks_helm_charts;
 pub mod helm_charts;
+mod karpenter;
 pub mod node;
 
 static AWS_EKS_DEFAULT_UPGRADE_TIMEOUT_DURATION: Lazy<ChronoDuration> = Lazy::new(|| ChronoDuration::hours(1));
@@ -414,6 +417,7 @@ fn tera_context(
         "wildcard_managed_dns",
         &kubernetes.dns_provider().domain().wildcarded().to_string(),
     );
+    context.insert("gateway_api_enabled", &kubernetes.advanced_settings().gateway_api_enabled);
 
     // add specific DNS fields
     kubernetes.dns_provider().insert_into_teracontext(&mut context);
@@ -512,6 +516,11 @@ fn tera_context(
         );
     }
 
+    context.insert(
+        "aws_eks_karpenter_enabled",
+        &kubernetes.advanced_settings().aws_eks_karpenter_enabled,
+    );
+
     context.insert("cloudwatch_eks_log_group", &cloudwatch_eks_log_group);
     context.insert(
         "aws_cloudwatch_eks_logs_retention_days",
@@ -633,6 +642,11 @@ fn tera_context(
                 Some(overridden_version) => AwsEbsCsiAddon::new_with_overridden_version(overridden_version),
             }),
         );
+        // CSI snapshot controller
+        context.insert(
+            "eks_addon_snapshot_controller",
+            &AwsSnapshotControllerAddon::new_from_k8s_version(kubernetes.version()),
+        );
     }
 
     Ok(context)
@@ -906,7 +920,17 @@ fn create(
         None => return Err(Box::new(EngineError::new_aws_sdk_cannot_get_client(event_details))),
     };
 
-    let terraform_apply = |kubernetes_action: KubernetesClusterAction| {
+    // When Karpenter is disabled, its IAM role and interruption queue are kept by the first terraform apply,
+    // so Karpenter can still drain and terminate its nodes. They are removed by a second apply once it is uninstalled.
+    let karpenter_removal_pending = kubernetes.kind() == Kind::Eks
+        && !kubernetes.advanced_settings().aws_eks_karpenter_enabled
+        && !kubernetes.context().is_first_cluster_deployment()
+        && match kubernetes.kube_client() {
+            Ok(kube_client) => is_karpenter_deployed(&kube_client, event_details.clone())?,
+            Err(_) => false,
+        };
+
+    let terraform_apply = |kubernetes_action: KubernetesClusterAction, keep_karpenter_resources: bool| {
         let node_groups_with_desired_states = should_update_desired_nodes(
             event_details.clone(),
             kubernetes,
@@ -932,13 +956,16 @@ fn create(
         };
 
         // generate terraform files and copy them into temp dir
-        let context = tera_context(
+        let mut context = tera_context(
             kubernetes,
             aws_zones,
             &node_groups_with_desired_states,
             options,
             cluster_upgrade_timeout_in_min,
         )?;
+        if keep_karpenter_resources {
+            context.insert("aws_eks_karpenter_enabled", &true);
+        }
 
         if let Err(e) =
             crate::template::generate_and_copy_all_files_into_dir(template_directory, temp_dir.as_str(), context)
@@ -1104,7 +1131,7 @@ fn create(
     // upgrade cluster instead if required
     if kubernetes.context().is_first_cluster_deployment() {
         // terraform deployment dedicated to cloud resources
-        terraform_apply(KubernetesClusterAction::Bootstrap)?;
+        terraform_apply(KubernetesClusterAction::Bootstrap, false)?;
     } else {
         // on EKS, we need to check if there is no already deployed failed nodegroups to avoid future quota issues
         if kubernetes.kind() == Kind::Eks {
@@ -1174,7 +1201,7 @@ fn create(
     }
 
     // apply to generate tf_qovery_config.json
-    terraform_apply(KubernetesClusterAction::Update(None))?;
+    terraform_apply(KubernetesClusterAction::Update(None), karpenter_removal_pending)?;
 
     let kubeconfig_path = match kubernetes.kind() {
         Kind::Eks => {
@@ -1272,6 +1299,7 @@ fn create(
                 })?,
                 cluster_name: kubernetes.cluster_name(),
                 cpu_architectures,
+                node_groups: node_groups.to_vec(),
                 cloud_provider: "aws".to_string(),
                 test_cluster: kubernetes.context().is_test_cluster(),
                 aws_access_key_id: kubernetes.cloud_provider().access_key_id(),
@@ -1355,6 +1383,7 @@ fn create(
                     kubernetes.context().is_test_cluster(),
                 ),
                 dns_provider_config: kubernetes.dns_provider().provider_configuration(),
+                cert_manager_acme_issuers: kubernetes.advanced_settings().cert_manager_acme_issuers.clone(),
             };
             ec2_aws_helm_charts(
                 qovery_terraform_config_file.as_str(),
@@ -1410,7 +1439,22 @@ fn create(
         }
         .map_err(|e| Box::new(EngineError::new_helm_chart_error(event_details.clone(), e)))
     } else {
-        return deploy_charts_levels(
+        // Karpenter still has its IAM role, so it can terminate its nodes before being uninstalled with the charts
+        if karpenter_removal_pending {
+            kubernetes.logger().log(EngineEvent::Info(
+                event_details.clone(),
+                EventMessage::new_from_safe(
+                    "Deleting Karpenter node pools and waiting for their nodes to be terminated".to_string(),
+                ),
+            ));
+            delete_karpenter_nodes(
+                &kubernetes.kube_client()?,
+                Duration::from_secs(AWS_EKS_MAX_NODE_DRAIN_TIMEOUT_DURATION.num_seconds() as u64),
+                event_details.clone(),
+            )?;
+        }
+
+        deploy_charts_levels(
             &kubernetes.kube_client()?,
             kubeconfig_path,
             credentials_environment_variables
@@ -1421,7 +1465,17 @@ fn create(
             helm_charts_to_deploy,
             kubernetes.context().is_dry_run_deploy(),
         )
-        .map_err(|e| Box::new(EngineError::new_helm_chart_error(event_details.clone(), e)));
+        .map_err(|e| Box::new(EngineError::new_helm_chart_error(event_details.clone(), e)))?;
+
+        if karpenter_removal_pending {
+            kubernetes.logger().log(EngineEvent::Info(
+                event_details.clone(),
+                EventMessage::new_from_safe("Removing Karpenter IAM role and interruption queue".to_string()),
+            ));
+            terraform_apply(KubernetesClusterAction::Update(None), false)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -1633,6 +1687,22 @@ fn pause(
         }
     }
 
+    // Karpenter nodes are not part of the node groups, they are removed with their node pools
+    // node pools are created again by the karpenter-configuration chart when the cluster is resumed
+    if kubernetes.advanced_settings().aws_eks_karpenter_enabled {
+        kubernetes.logger().log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe(
+                "Deleting Karpenter node pools and waiting for their nodes to be drained".to_string(),
+            ),
+        ));
+        delete_karpenter_nodes(
+            &kubernetes.kube_client()?,
+            Duration::from_secs(cluster_upgrade_timeout_in_min.num_seconds() as u64),
+            event_details.clone(),
+        )?;
+    }
+
     kubernetes.logger().log(EngineEvent::Info(
         event_details.clone(),
         EventMessage::new_from_safe("Pausing cluster deployment.".to_string()),
@@ -2045,6 +2115,22 @@ fn delete(
             ));
         }
 
+        // Karpenter has to drain and terminate its nodes before being uninstalled with the other charts,
+        // otherwise they are orphaned and prevent terraform from destroying the cluster network
+        if kubernetes.advanced_settings().aws_eks_karpenter_enabled {
+            kubernetes.logger().log(EngineEvent::Info(
+                event_details.clone(),
+                EventMessage::new_from_safe(
+                    "Deleting Karpenter node pools and waiting for their nodes to be terminated".to_string(),
+                ),
+            ));
+            delete_karpenter_nodes(
+                &kubernetes.kube_client()?,
+                Duration::from_secs(AWS_EKS_MAX_NODE_DRAIN_TIMEOUT_DURATION.num_seconds() as u64),
+                event_details.clone(),
+            )?;
+        }
+
         kubernetes.logger().log(EngineEvent::Info(
             event_details.clone(),
             EventMessage::new_from_safe("Deleting Qovery managed helm charts".to_string()),
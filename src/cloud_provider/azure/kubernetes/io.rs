@@ -0,0 +1,96 @@
+use crate::cloud_provider::azure::kubernetes::AksOptions as AksOptionsModel;
+use crate::cloud_provider::qovery::EngineLocation;
+use crate::models::azure::AzureCredentials;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct AksOptions {
+    // Qovery
+    pub qovery_api_url: String,
+    pub qovery_grpc_url: String,
+    #[serde(default)]
+    pub qovery_engine_url: String,
+    pub jwt_token: String,
+    pub qovery_ssh_key: String,
+    #[serde(default)]
+    pub user_ssh_keys: Vec<String>,
+    pub grafana_admin_user: String,
+    pub grafana_admin_password: String,
+    pub qovery_engine_location: EngineLocation,
+
+    // Azure
+    pub azure_client_id: String,
+    pub azure_client_secret: String,
+    pub azure_tenant_id: String,
+    pub azure_subscription_id: String,
+
+    // Other
+    pub tls_email_report: String,
+}
+
+impl TryFrom<AksOptions> for AksOptionsModel {
+    type Error = String;
+
+    fn try_from(value: AksOptions) -> Result<AksOptionsModel, Self::Error> {
+        if value.azure_subscription_id.trim().is_empty() {
+            return Err("Azure subscription id cannot be empty".to_string());
+        }
+
+        Ok(AksOptionsModel::new(
+            value.qovery_api_url,
+            value.qovery_grpc_url,
+            value.qovery_engine_url,
+            value.jwt_token,
+            value.qovery_ssh_key,
+            value.user_ssh_keys,
+            value.grafana_admin_user,
+            value.grafana_admin_password,
+            value.qovery_engine_location,
+            AzureCredentials {
+                client_id: value.azure_client_id,
+                client_secret: value.azure_client_secret,
+                tenant_id: value.azure_tenant_id,
+                subscription_id: value.azure_subscription_id,
+            },
+            value.tls_email_report,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::azure::kubernetes::io::AksOptions;
+    use crate::cloud_provider::azure::kubernetes::AksOptions as AksOptionsModel;
+
+    #[test]
+    fn test_aks_options_deserialization() {
+        // setup:
+        let json = r#"{
+            "qovery_api_url": "https://api.qovery.com",
+            "qovery_grpc_url": "https://grpc.qovery.com",
+            "jwt_token": "jwt",
+            "qovery_ssh_key": "ssh",
+            "grafana_admin_user": "admin",
+            "grafana_admin_password": "password",
+            "qovery_engine_location": "ClientSide",
+            "azure_client_id": "client_id",
+            "azure_client_secret": "client_secret",
+            "azure_tenant_id": "tenant_id",
+            "azure_subscription_id": "subscription_id",
+            "tls_email_report": "tls@qovery.com"
+        }"#;
+
+        // execute:
+        let options: AksOptionsModel = serde_json::from_str::<AksOptions>(json)
+            .expect("Cannot deserialize AKS options")
+            .try_into()
+            .expect("Cannot convert AKS options");
+
+        // verify:
+        assert_eq!("client_id", options.azure_credentials.client_id);
+        assert_eq!("client_secret", options.azure_credentials.client_secret);
+        assert_eq!("tenant_id", options.azure_credentials.tenant_id);
+        assert_eq!("subscription_id", options.azure_credentials.subscription_id);
+        assert!(options.user_ssh_keys.is_empty());
+    }
+}
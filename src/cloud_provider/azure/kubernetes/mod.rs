@@ -0,0 +1,1652 @@
+pub mod helm_charts;
+pub mod io;
+
+use crate::cloud_provider::azure::locations::AzureLocation;
+use crate::cloud_provider::helm::{deploy_charts_levels, ChartInfo};
+use crate::cloud_provider::io::ClusterAdvancedSettings;
+use crate::cloud_provider::kubernetes::{
+    is_kubernetes_upgrade_required, send_progress_on_long_task, uninstall_cert_manager, Kind, Kubernetes,
+    KubernetesUpgradeStatus, KubernetesVersion, ProviderOptions,
+};
+use crate::cloud_provider::models::{CpuArchitecture, NodeGroups};
+use crate::cloud_provider::qovery::EngineLocation;
+use crate::cloud_provider::service::Action;
+use crate::cloud_provider::utilities::print_action;
+use crate::cloud_provider::vault::{ClusterSecrets, ClusterSecretsAzure};
+use crate::cloud_provider::CloudProvider;
+use crate::cmd::command::{CommandKiller, ExecutableCommand, QoveryCommand};
+use crate::cmd::helm::Helm;
+use crate::cmd::kubectl::{kubectl_exec_delete_namespace, kubectl_exec_get_all_namespaces, kubectl_exec_get_events};
+use crate::cmd::terraform::{terraform_init_validate_destroy, terraform_init_validate_plan_apply, TerraformError};
+use crate::deletion_utilities::{get_firsts_namespaces_to_delete, get_qovery_managed_namespaces};
+use crate::dns_provider::DnsProvider;
+use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
+use crate::events::Stage::Infrastructure;
+use crate::events::{EngineEvent, EventDetails, EventMessage, InfrastructureStep, Stage, Transmitter};
+use crate::io_models::context::{Context, Features};
+use crate::io_models::engine_request::{ChartValuesOverrideName, ChartValuesOverrideValues};
+use crate::io_models::QoveryIdentifier;
+use crate::logger::Logger;
+use crate::metrics_registry::MetricsRegistry;
+use crate::models::azure::AzureCredentials;
+use crate::models::domain::ToHelmString;
+use crate::models::third_parties::LetsEncryptConfig;
+use crate::models::ToCloudProviderFormat;
+use crate::object_storage::azure_object_storage::AzureOS;
+use crate::object_storage::{BucketDeleteStrategy, ObjectStorage};
+use crate::secret_manager;
+use crate::secret_manager::vault::QVaultClient;
+use crate::string::terraform_list_format;
+use base64::engine::general_purpose;
+use base64::Engine;
+use function_name::named;
+use itertools::Itertools;
+use serde_derive::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+use std::{env, fs};
+use tera::Context as TeraContext;
+use uuid::Uuid;
+
+// https://learn.microsoft.com/en-us/azure/azure-resource-manager/management/resource-name-rules
+const AZURE_STORAGE_ACCOUNT_NAME_MAX_LENGTH: usize = 24;
+const AZURE_CONTAINER_REGISTRY_NAME_MAX_LENGTH: usize = 50;
+const AKS_NODE_POOL_NAME_MAX_LENGTH: usize = 12;
+// https://learn.microsoft.com/en-us/azure/aks/spot-node-pool
+const AKS_SPOT_NODE_POOL_TAINT: &str = "kubernetes.azure.com/scalesetpriority=spot:NoSchedule";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AksOptions {
+    // Qovery
+    pub qovery_api_url: String,
+    pub qovery_grpc_url: String,
+    pub qovery_engine_url: String,
+    pub jwt_token: String,
+    pub qovery_ssh_key: String,
+    pub user_ssh_keys: Vec<String>,
+    pub grafana_admin_user: String,
+    pub grafana_admin_password: String,
+    pub qovery_engine_location: EngineLocation,
+
+    // Azure
+    pub azure_credentials: AzureCredentials,
+
+    // Other
+    pub tls_email_report: String,
+}
+
+impl AksOptions {
+    pub fn new(
+        qovery_api_url: String,
+        qovery_grpc_url: String,
+        qovery_engine_url: String,
+        jwt_token: String,
+        qovery_ssh_key: String,
+        user_ssh_keys: Vec<String>,
+        grafana_admin_user: String,
+        grafana_admin_password: String,
+        qovery_engine_location: EngineLocation,
+        azure_credentials: AzureCredentials,
+        tls_email_report: String,
+    ) -> Self {
+        AksOptions {
+            qovery_api_url,
+            qovery_grpc_url,
+            qovery_engine_url,
+            jwt_token,
+            qovery_ssh_key,
+            user_ssh_keys,
+            grafana_admin_user,
+            grafana_admin_password,
+            qovery_engine_location,
+            azure_credentials,
+            tls_email_report,
+        }
+    }
+}
+
+impl ProviderOptions for AksOptions {}
+
+/// Node pool as expected by AKS terraform templates, node pool names being restricted by Azure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AksNodePool {
+    pub name: String,
+    pub instance_type: String,
+    pub disk_size_in_gib: i32,
+    pub min_nodes: i32,
+    pub max_nodes: i32,
+    pub spot: bool,
+    pub labels: BTreeMap<String, String>,
+    /// Taints in Kubernetes format (`key=value:Effect`)
+    pub taints: Vec<String>,
+}
+
+impl AksNodePool {
+    fn new(index: usize, node_group: &NodeGroups) -> Self {
+        let spot = node_group.capacity_type.is_interruptible();
+        let mut taints: Vec<String> = node_group.taints.iter().map(|taint| taint.to_k8s_format()).collect();
+        if spot {
+            // taint added by Azure on spot node pools, declared to avoid terraform trying to remove it on each apply
+            taints.push(AKS_SPOT_NODE_POOL_TAINT.to_string());
+        }
+
+        AksNodePool {
+            name: aks_node_pool_name(node_group.name.as_str(), index),
+            instance_type: node_group.instance_type.to_string(),
+            disk_size_in_gib: node_group.disk_size_in_gib,
+            min_nodes: node_group.min_nodes,
+            max_nodes: node_group.max_nodes,
+            spot,
+            labels: node_group.labels.clone(),
+            taints,
+        }
+    }
+}
+
+/// Azure resources names such as storage accounts and container registries only accept lowercase alphanumeric
+/// characters and are globally unique, so they are derived from the cluster id.
+fn azure_alphanumeric_resource_name(prefix: &str, cluster_id: &str, max_length: usize) -> String {
+    format!("{prefix}{cluster_id}")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .take(max_length)
+        .collect()
+}
+
+/// Storage account hosting Qovery managed blob containers of the cluster
+pub fn azure_storage_account_name(cluster_id: &str) -> String {
+    azure_alphanumeric_resource_name("qovery", cluster_id, AZURE_STORAGE_ACCOUNT_NAME_MAX_LENGTH)
+}
+
+/// Azure container registry created along with the cluster
+pub fn azure_container_registry_name(cluster_id: &str) -> String {
+    azure_alphanumeric_resource_name("qovery", cluster_id, AZURE_CONTAINER_REGISTRY_NAME_MAX_LENGTH)
+}
+
+/// AKS node pool names must start with a lowercase letter and contain at most 12 lowercase alphanumeric characters
+fn aks_node_pool_name(node_group_name: &str, index: usize) -> String {
+    let sanitized_name: String = node_group_name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .skip_while(|c| !c.is_ascii_lowercase())
+        .collect();
+
+    let suffix = index.to_string();
+    match sanitized_name.is_empty() {
+        true => format!("pool{suffix}"),
+        false if index == 0 => sanitized_name.chars().take(AKS_NODE_POOL_NAME_MAX_LENGTH).collect(),
+        // suffix other node pools to make sure names are unique once truncated
+        false => format!(
+            "{}{suffix}",
+            sanitized_name
+                .chars()
+                .take(AKS_NODE_POOL_NAME_MAX_LENGTH - suffix.len())
+                .collect::<String>()
+        ),
+    }
+}
+
+/// Insert Azure specific values required by AKS terraform templates into tera context
+fn insert_aks_tera_context(
+    context: &mut TeraContext,
+    cluster_id: &str,
+    location: &AzureLocation,
+    credentials: &AzureCredentials,
+    nodes_groups: &[NodeGroups],
+) {
+    context.insert("azure_location", location.to_cloud_provider_format());
+    context.insert(
+        "azure_zones",
+        &location
+            .zones()
+            .iter()
+            .map(|z| z.to_cloud_provider_format())
+            .collect::<Vec<&str>>(),
+    );
+    context.insert("azure_client_id", credentials.client_id.as_str());
+    context.insert("azure_tenant_id", credentials.tenant_id.as_str());
+    context.insert("azure_subscription_id", credentials.subscription_id.as_str());
+    context.insert("azure_resource_group_name", &format!("qovery-{cluster_id}"));
+    context.insert("azure_storage_account_name", &azure_storage_account_name(cluster_id));
+    context.insert("azure_container_registry_name", &azure_container_registry_name(cluster_id));
+    context.insert(
+        "aks_node_pools",
+        &nodes_groups
+            .iter()
+            .enumerate()
+            .map(|(index, node_group)| AksNodePool::new(index, node_group))
+            .collect::<Vec<AksNodePool>>(),
+    );
+}
+
+pub struct Aks {
+    context: Context,
+    id: String,
+    long_id: Uuid,
+    name: String,
+    version: KubernetesVersion,
+    location: AzureLocation,
+    template_directory: String,
+    cloud_provider: Arc<dyn CloudProvider>,
+    dns_provider: Arc<dyn DnsProvider>,
+    object_storage: AzureOS,
+    options: AksOptions,
+    nodes_groups: Vec<NodeGroups>,
+    logger: Box<dyn Logger>,
+    metrics_registry: Box<dyn MetricsRegistry>,
+    advanced_settings: ClusterAdvancedSettings,
+    customer_helm_charts_override: Option<HashMap<ChartValuesOverrideName, ChartValuesOverrideValues>>,
+}
+
+impl Aks {
+    pub fn new(
+        context: Context,
+        id: &str,
+        long_id: Uuid,
+        name: &str,
+        version: KubernetesVersion,
+        location: AzureLocation,
+        cloud_provider: Arc<dyn CloudProvider>,
+        dns_provider: Arc<dyn DnsProvider>,
+        options: AksOptions,
+        nodes_groups: Vec<NodeGroups>,
+        logger: Box<dyn Logger>,
+        metrics_registry: Box<dyn MetricsRegistry>,
+        advanced_settings: ClusterAdvancedSettings,
+        customer_helm_charts_override: Option<HashMap<ChartValuesOverrideName, ChartValuesOverrideValues>>,
+    ) -> Result<Self, Box<EngineError>> {
+        let event_details = EventDetails::new(
+            Some(cloud_provider.kind()),
+            QoveryIdentifier::new(*context.organization_long_id()),
+            QoveryIdentifier::new(*context.cluster_long_id()),
+            context.borrow().execution_id().to_string(),
+            Infrastructure(InfrastructureStep::LoadConfiguration),
+            Transmitter::Kubernetes(long_id, name.to_string()),
+        );
+
+        // AKS requires a default node pool
+        if nodes_groups.is_empty() {
+            return Err(Box::new(EngineError::new_missing_nodegroup_information_error(
+                event_details,
+                "AKS cluster requires at least one node group".to_string(),
+            )));
+        }
+
+        // kubeconfig and logs containers are hosted in the storage account created by Terraform
+        let azure_object_storage = AzureOS::new(
+            id,
+            name,
+            &azure_storage_account_name(id),
+            options.azure_credentials.clone(),
+            location.clone(),
+        );
+
+        Ok(Self {
+            context: context.clone(),
+            id: id.to_string(),
+            long_id,
+            name: name.to_string(),
+            version,
+            location,
+            template_directory: format!("{}/azure/bootstrap", context.lib_root_dir()),
+            cloud_provider,
+            dns_provider,
+            object_storage: azure_object_storage,
+            options,
+            nodes_groups,
+            logger,
+            metrics_registry,
+            advanced_settings,
+            customer_helm_charts_override,
+        })
+    }
+
+    fn kubeconfig_bucket_name(&self) -> String {
+        self.get_bucket_name()
+    }
+
+    fn logs_bucket_name(&self) -> String {
+        format!("qovery-logs-{}", self.id)
+    }
+
+    fn resource_group_name(&self) -> String {
+        format!("qovery-{}", self.id)
+    }
+
+    fn get_engine_location(&self) -> EngineLocation {
+        self.options.qovery_engine_location.clone()
+    }
+
+    fn tera_context(&self) -> Result<TeraContext, Box<EngineError>> {
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::LoadConfiguration));
+        let mut context = TeraContext::new();
+
+        // Qovery
+        context.insert("organization_id", self.cloud_provider.organization_id());
+        context.insert("organization_long_id", &self.cloud_provider.organization_long_id().to_string());
+        context.insert("object_storage_kubeconfig_bucket", &self.kubeconfig_bucket_name());
+        context.insert("object_storage_logs_bucket", &self.logs_bucket_name());
+        // Qovery features
+        context.insert("log_history_enabled", &self.context.is_feature_enabled(&Features::LogsHistory));
+        context.insert(
+            "metrics_history_enabled",
+            &self.context.is_feature_enabled(&Features::MetricsHistory),
+        );
+
+        // Advanced settings
+        context.insert("resource_expiration_in_seconds", &self.advanced_settings().pleco_resources_ttl);
+
+        // Kubernetes
+        context.insert("test_cluster", &self.context.is_test_cluster());
+        context.insert("kubernetes_cluster_long_id", &self.long_id);
+        context.insert("kubernetes_cluster_id", self.id());
+        context.insert("kubernetes_cluster_name", self.cluster_name().as_str());
+        context.insert("kubernetes_cluster_version", &self.version.to_string());
+        context.insert("qovery_api_url", self.options.qovery_api_url.as_str());
+
+        // Azure
+        insert_aks_tera_context(
+            &mut context,
+            self.id(),
+            &self.location,
+            &self.options.azure_credentials,
+            &self.nodes_groups,
+        );
+
+        // AWS S3 tfstates storage
+        context.insert(
+            "aws_access_key_tfstates_account",
+            match self.cloud_provider().terraform_state_credentials() {
+                Some(x) => x.access_key_id.as_str(),
+                None => "",
+            },
+        );
+        context.insert(
+            "aws_secret_key_tfstates_account",
+            match self.cloud_provider().terraform_state_credentials() {
+                Some(x) => x.secret_access_key.as_str(),
+                None => "",
+            },
+        );
+        context.insert(
+            "aws_region_tfstates_account",
+            match self.cloud_provider().terraform_state_credentials() {
+                Some(x) => x.region.as_str(),
+                None => "",
+            },
+        );
+        context.insert("aws_terraform_backend_dynamodb_table", "qovery-terrafom-tfstates");
+        context.insert("aws_terraform_backend_bucket", "qovery-terrafom-tfstates");
+
+        // DNS
+        let managed_dns_list = vec![self.dns_provider.name()];
+        let managed_dns_domains_helm_format = vec![self.dns_provider.domain().to_string()];
+        let managed_dns_domains_root_helm_format = vec![self.dns_provider.domain().root_domain().to_string()];
+        let managed_dns_domains_terraform_format = terraform_list_format(vec![self.dns_provider.domain().to_string()]);
+        let managed_dns_domains_root_terraform_format =
+            terraform_list_format(vec![self.dns_provider.domain().root_domain().to_string()]);
+        let managed_dns_resolvers_terraform_format = terraform_list_format(
+            self.dns_provider()
+                .resolvers()
+                .iter()
+                .map(|x| x.clone().to_string())
+                .collect(),
+        );
+
+        context.insert("managed_dns", &managed_dns_list);
+        context.insert("managed_dns_domains_helm_format", &managed_dns_domains_helm_format);
+        context.insert("managed_dns_domains_root_helm_format", &managed_dns_domains_root_helm_format);
+        context.insert("managed_dns_domains_terraform_format", &managed_dns_domains_terraform_format);
+        context.insert(
+            "managed_dns_domains_root_terraform_format",
+            &managed_dns_domains_root_terraform_format,
+        );
+        context.insert(
+            "managed_dns_resolvers_terraform_format",
+            &managed_dns_resolvers_terraform_format,
+        );
+        context.insert("wildcard_managed_dns", &self.dns_provider().domain().wildcarded().to_string());
+        context.insert("gateway_api_enabled", &self.advanced_settings().gateway_api_enabled);
+
+        // add specific DNS fields
+        self.dns_provider().insert_into_teracontext(&mut context);
+
+        context.insert("dns_email_report", &self.options.tls_email_report);
+
+        // TLS
+        context.insert(
+            "acme_server_url",
+            LetsEncryptConfig::new(self.options.tls_email_report.to_string(), self.context.is_test_cluster())
+                .acme_url()
+                .as_str(),
+        );
+
+        // Vault
+        context.insert("vault_auth_method", "none");
+
+        if env::var_os("VAULT_ADDR").is_some() {
+            // select the correct used method
+            match env::var_os("VAULT_ROLE_ID") {
+                Some(role_id) => {
+                    context.insert("vault_auth_method", "app_role");
+                    context.insert("vault_role_id", role_id.to_str().unwrap());
+
+                    match env::var_os("VAULT_SECRET_ID") {
+                        Some(secret_id) => context.insert("vault_secret_id", secret_id.to_str().unwrap()),
+                        None => self.logger().log(EngineEvent::Error(
+                            EngineError::new_missing_required_env_variable(
+                                event_details.clone(),
+                                "VAULT_SECRET_ID".to_string(),
+                            ),
+                            None,
+                        )),
+                    }
+                }
+                None => {
+                    if env::var_os("VAULT_TOKEN").is_some() {
+                        context.insert("vault_auth_method", "token")
+                    }
+                }
+            }
+        };
+
+        // grafana credentials
+        context.insert("grafana_admin_user", self.options.grafana_admin_user.as_str());
+        context.insert("grafana_admin_password", self.options.grafana_admin_password.as_str());
+
+        Ok(context)
+    }
+
+    /// Render terraform templates with the given kubernetes version and apply them.
+    /// Used on creation as well as on upgrade, terraform taking care of the control plane and node pools versions.
+    fn apply_infrastructure(
+        &self,
+        event_details: EventDetails,
+        kubernetes_version: String,
+    ) -> Result<String, Box<EngineError>> {
+        let temp_dir = self.get_temp_dir(event_details.clone())?;
+
+        // generate terraform files and copy them into temp dir
+        let mut context = self.tera_context()?;
+        context.insert("kubernetes_cluster_version", &kubernetes_version);
+
+        if let Err(e) = crate::template::generate_and_copy_all_files_into_dir(
+            self.template_directory.as_str(),
+            temp_dir.as_str(),
+            context,
+        ) {
+            return Err(Box::new(EngineError::new_cannot_copy_files_from_one_directory_to_another(
+                event_details,
+                self.template_directory.to_string(),
+                temp_dir,
+                e,
+            )));
+        }
+
+        let dirs_to_be_copied_to = vec![
+            // copy lib/common/bootstrap/charts directory (and sub directory) into the lib/azure/bootstrap/common/charts directory.
+            // this is due to the required dependencies of lib/azure/bootstrap/*.tf files
+            (
+                format!("{}/common/bootstrap/charts", self.context.lib_root_dir()),
+                format!("{}/common/charts", temp_dir.as_str()),
+            ),
+            // copy lib/common/bootstrap/chart_values directory (and sub directory) into the lib/azure/bootstrap/common/chart_values directory.
+            (
+                format!("{}/common/bootstrap/chart_values", self.context.lib_root_dir()),
+                format!("{}/common/chart_values", temp_dir.as_str()),
+            ),
+        ];
+        for (source_dir, target_dir) in dirs_to_be_copied_to {
+            if let Err(e) = crate::template::copy_non_template_files(&source_dir, target_dir.as_str()) {
+                return Err(Box::new(EngineError::new_cannot_copy_files_from_one_directory_to_another(
+                    event_details,
+                    source_dir,
+                    target_dir,
+                    e,
+                )));
+            }
+        }
+
+        // Terraform deployment dedicated to cloud resources
+        if let Err(e) = terraform_init_validate_plan_apply(
+            temp_dir.as_str(),
+            self.context.is_dry_run_deploy(),
+            self.cloud_provider().credentials_environment_variables().as_slice(),
+        ) {
+            return Err(Box::new(EngineError::new_terraform_error(event_details, e)));
+        }
+
+        Ok(temp_dir)
+    }
+
+    fn create(&self) -> Result<(), Box<EngineError>> {
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Create));
+
+        self.logger.log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe("Preparing AKS cluster deployment.".to_string()),
+        ));
+
+        if !self.context().is_first_cluster_deployment() {
+            // a paused cluster has to be started before anything else
+            self.start_cluster_if_stopped(event_details.clone())?;
+
+            // upgrade cluster instead if required
+            match self.get_kubeconfig_file() {
+                Ok(path) => match is_kubernetes_upgrade_required(
+                    path,
+                    self.version.clone(),
+                    self.cloud_provider.credentials_environment_variables(),
+                    event_details.clone(),
+                    self.logger(),
+                ) {
+                    Ok(kubernetes_upgrade_status) => {
+                        if kubernetes_upgrade_status.required_upgrade_on.is_some() {
+                            return self.upgrade_with_status(kubernetes_upgrade_status);
+                        }
+
+                        self.logger().log(EngineEvent::Info(
+                            event_details.clone(),
+                            EventMessage::new_from_safe("Kubernetes cluster upgrade not required".to_string()),
+                        ))
+                    }
+                    Err(e) => {
+                        // Log a warning, this error is not blocking
+                        self.logger().log(EngineEvent::Warning(
+                            event_details.clone(),
+                            EventMessage::new(
+                                "Error detected, upgrade won't occurs, but standard deployment.".to_string(),
+                                Some(e.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)),
+                            ),
+                        ));
+                    }
+                },
+                Err(_) => self.logger().log(EngineEvent::Info(event_details.clone(), EventMessage::new_from_safe("Kubernetes cluster upgrade not required, config file is not found and cluster have certainly never been deployed before".to_string())))
+            };
+        }
+
+        self.logger().log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe("Deploying AKS cluster.".to_string()),
+        ));
+
+        let temp_dir = self.apply_infrastructure(event_details.clone(), self.version.to_string())?;
+        let qovery_terraform_config_file = format!("{}/qovery-tf-config.json", &temp_dir);
+
+        // Retrieve config generated via Terraform
+        let qovery_terraform_config: AksQoveryTerraformConfig = self
+            .get_aks_qovery_terraform_config(qovery_terraform_config_file.as_str())
+            .map_err(|e| EngineError::new_terraform_error(event_details.clone(), e))?;
+
+        // Storage account is created by Terraform, so buckets can only be created once it has been applied
+        self.logger().log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe("Create Qovery managed object storage buckets".to_string()),
+        ));
+        for bucket_name in [self.kubeconfig_bucket_name(), self.logs_bucket_name()] {
+            if self.object_storage.bucket_exists(bucket_name.as_str()) {
+                continue;
+            }
+
+            if let Err(e) =
+                self.object_storage
+                    .create_bucket(bucket_name.as_str(), self.advanced_settings.resource_ttl(), false)
+            {
+                let error = EngineError::new_object_storage_error(event_details, e);
+                self.logger().log(EngineEvent::Error(error.clone(), None));
+                return Err(Box::new(error));
+            }
+        }
+
+        // Push config file to object storage
+        let kubeconfig_path = &self.get_kubeconfig_file_path()?;
+        let kubeconfig_name = self.get_kubeconfig_filename();
+        if let Err(e) = self.object_storage.put_object(
+            self.kubeconfig_bucket_name().as_str(),
+            kubeconfig_name.as_str(),
+            kubeconfig_path,
+        ) {
+            let error = EngineError::new_object_storage_error(event_details, e);
+            self.logger().log(EngineEvent::Error(error.clone(), None));
+            return Err(Box::new(error));
+        }
+
+        // Ensure all nodes are ready on Kubernetes
+        match self.check_workers_on_create() {
+            Ok(_) => self.logger().log(EngineEvent::Info(
+                event_details.clone(),
+                EventMessage::new_from_safe("Kubernetes nodes have been successfully created".to_string()),
+            )),
+            Err(e) => {
+                return Err(Box::new(EngineError::new_k8s_node_not_ready(event_details, e)));
+            }
+        };
+
+        // Update cluster config to vault
+        let kubeconfig = fs::read_to_string(kubeconfig_path).map_err(|e| {
+            Box::new(EngineError::new_cannot_retrieve_cluster_config_file(
+                event_details.clone(),
+                CommandError::new_from_safe_message(format!(
+                    "Cannot read kubeconfig file {}: {e}",
+                    kubeconfig_path.to_str().unwrap_or_default()
+                )),
+            ))
+        })?;
+        let kubeconfig_b64 = general_purpose::STANDARD.encode(kubeconfig);
+        let cluster_secrets = ClusterSecrets::new_azure_aks(ClusterSecretsAzure::new(
+            self.options.azure_credentials.client_id.to_string(),
+            self.options.azure_credentials.client_secret.to_string(),
+            self.options.azure_credentials.tenant_id.to_string(),
+            self.options.azure_credentials.subscription_id.to_string(),
+            self.location.clone(),
+            Some(kubeconfig_b64),
+            Some(qovery_terraform_config.aks_cluster_public_hostname),
+            self.kind(),
+            self.cloud_provider().name().to_string(),
+            self.long_id().to_string(),
+            self.options.grafana_admin_user.clone(),
+            self.options.grafana_admin_password.clone(),
+            self.cloud_provider.organization_long_id().to_string(),
+            self.context().is_test_cluster(),
+        ));
+        // vault config is not blocking
+        if let Err(e) = self.update_aks_vault_config(event_details.clone(), cluster_secrets) {
+            self.logger.log(EngineEvent::Warning(
+                event_details.clone(),
+                EventMessage::new("Cannot push cluster config to Vault".to_string(), Some(e.to_string())),
+            ))
+        }
+
+        // kubernetes helm deployments on the cluster
+        let credentials_environment_variables: Vec<(String, String)> = self
+            .cloud_provider
+            .credentials_environment_variables()
+            .into_iter()
+            .map(|x| (x.0.to_string(), x.1.to_string()))
+            .collect();
+
+        self.logger().log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe("Preparing chart configuration to be deployed".to_string()),
+        ));
+
+        let charts_prerequisites = helm_charts::ChartsConfigPrerequisites::new(
+            self.cloud_provider.organization_id().to_string(),
+            self.cloud_provider.organization_long_id(),
+            self.id().to_string(),
+            self.long_id,
+            self.location.clone(),
+            self.cluster_name(),
+            self.cpu_architectures(),
+            "azure".to_string(),
+            self.context.is_test_cluster(),
+            self.options.qovery_engine_location.clone(),
+            self.context.is_feature_enabled(&Features::LogsHistory),
+            self.context.is_feature_enabled(&Features::MetricsHistory),
+            self.context.is_feature_enabled(&Features::Grafana),
+            self.dns_provider.domain().root_domain().to_string(),
+            self.dns_provider.domain().to_helm_format_string(),
+            terraform_list_format(
+                self.dns_provider()
+                    .resolvers()
+                    .iter()
+                    .map(|x| x.clone().to_string())
+                    .collect(),
+            ),
+            self.dns_provider.domain().root_domain().to_helm_format_string(),
+            self.dns_provider.provider_name().to_string(),
+            LetsEncryptConfig::new(self.options.tls_email_report.to_string(), self.context.is_test_cluster()),
+            self.dns_provider().provider_configuration(),
+            self.context.disable_pleco(),
+            self.options.clone(),
+            self.advanced_settings().clone(),
+        );
+
+        let helm_charts_to_deploy = helm_charts::aks_helm_charts(
+            qovery_terraform_config_file.as_str(),
+            &charts_prerequisites,
+            Some(&temp_dir),
+            kubeconfig_path,
+            &credentials_environment_variables,
+            &*self.context.qovery_api,
+            self.customer_helm_charts_override(),
+        )
+        .map_err(|e| EngineError::new_helm_charts_setup_error(event_details.clone(), e))?;
+
+        deploy_charts_levels(
+            &self.kube_client()?,
+            kubeconfig_path,
+            credentials_environment_variables
+                .iter()
+                .map(|(l, r)| (l.as_str(), r.as_str()))
+                .collect_vec()
+                .as_slice(),
+            helm_charts_to_deploy,
+            self.context.is_dry_run_deploy(),
+        )
+        .map_err(|e| Box::new(EngineError::new_helm_chart_error(event_details.clone(), e)))
+    }
+
+    fn create_error(&self) -> Result<(), Box<EngineError>> {
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Create));
+        let kubeconfig_path = self.get_kubeconfig_file()?;
+        let environment_variables: Vec<(&str, &str)> = self.cloud_provider.credentials_environment_variables();
+
+        self.logger().log(EngineEvent::Warning(
+            self.get_event_details(Infrastructure(InfrastructureStep::Create)),
+            EventMessage::new_from_safe("AKS.create_error() called.".to_string()),
+        ));
+
+        match kubectl_exec_get_events(kubeconfig_path, None, environment_variables) {
+            Ok(ok_line) => self
+                .logger()
+                .log(EngineEvent::Info(event_details, EventMessage::new_from_safe(ok_line))),
+            Err(err) => self.logger().log(EngineEvent::Warning(
+                event_details,
+                EventMessage::new(
+                    "Error trying to get kubernetes events".to_string(),
+                    Some(err.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)),
+                ),
+            )),
+        };
+
+        Ok(())
+    }
+
+    /// Run an `az` command authenticated with the cluster service principal and return its stdout lines.
+    fn az_exec(&self, args: &[&str]) -> Result<Vec<String>, CommandError> {
+        let envs = self.cloud_provider.credentials_environment_variables();
+
+        // credentials are read from environment variables to avoid leaking the secret in logged command arguments
+        QoveryCommand::new(
+            "bash",
+            &[
+                "-c",
+                "az login --service-principal --username \"$ARM_CLIENT_ID\" --password \"$ARM_CLIENT_SECRET\" --tenant \"$ARM_TENANT_ID\"",
+            ],
+            envs.as_slice(),
+        )
+        .exec_with_output(&mut |_| {}, &mut |_| {})?;
+
+        let mut az_args = args.to_vec();
+        az_args.extend([
+            "--subscription",
+            self.options.azure_credentials.subscription_id.as_str(),
+        ]);
+
+        let mut output = vec![];
+        QoveryCommand::new("az", &az_args, envs.as_slice())
+            .exec_with_output(&mut |line| output.push(line), &mut |_| {})?;
+
+        Ok(output)
+    }
+
+    fn aks_power_action(&self, event_details: EventDetails, action: &str) -> Result<(), Box<EngineError>> {
+        let resource_group_name = self.resource_group_name();
+        let cluster_name = self.cluster_name();
+
+        self.logger().log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe(format!("Requesting AKS cluster {action}.")),
+        ));
+
+        self.az_exec(&[
+            "aks",
+            action,
+            "--resource-group",
+            resource_group_name.as_str(),
+            "--name",
+            cluster_name.as_str(),
+        ])
+        .map_err(|e| Box::new(EngineError::new_cannot_change_cluster_power_state(event_details, action, e)))?;
+
+        Ok(())
+    }
+
+    fn start_cluster_if_stopped(&self, event_details: EventDetails) -> Result<(), Box<EngineError>> {
+        let resource_group_name = self.resource_group_name();
+        let cluster_name = self.cluster_name();
+
+        let power_state = self
+            .az_exec(&[
+                "aks",
+                "show",
+                "--resource-group",
+                resource_group_name.as_str(),
+                "--name",
+                cluster_name.as_str(),
+                "--query",
+                "powerState.code",
+                "--output",
+                "tsv",
+            ])
+            .map_err(|e| Box::new(EngineError::new_cannot_get_cluster_error(event_details.clone(), e)))?;
+
+        match power_state.iter().any(|line| line.trim() == "Stopped") {
+            true => self.aks_power_action(event_details, "start"),
+            false => Ok(()),
+        }
+    }
+
+    fn delete(&self) -> Result<(), Box<EngineError>> {
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Delete));
+        let skip_kubernetes_step = false;
+
+        self.logger().log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe("Preparing to delete cluster.".to_string()),
+        ));
+
+        let temp_dir = self.get_temp_dir(event_details.clone())?;
+
+        // generate terraform files and copy them into temp dir
+        let context = self.tera_context()?;
+
+        if let Err(e) = crate::template::generate_and_copy_all_files_into_dir(
+            self.template_directory.as_str(),
+            temp_dir.as_str(),
+            context,
+        ) {
+            return Err(Box::new(EngineError::new_cannot_copy_files_from_one_directory_to_another(
+                event_details,
+                self.template_directory.to_string(),
+                temp_dir,
+                e,
+            )));
+        }
+
+        // copy lib/common/bootstrap/charts directory (and sub directory) into the lib/azure/bootstrap/common/charts directory.
+        // this is due to the required dependencies of lib/azure/bootstrap/*.tf files
+        let bootstrap_charts_dir = format!("{}/common/bootstrap/charts", self.context.lib_root_dir());
+        let common_charts_temp_dir = format!("{}/common/charts", temp_dir.as_str());
+        if let Err(e) = crate::template::copy_non_template_files(&bootstrap_charts_dir, common_charts_temp_dir.as_str())
+        {
+            return Err(Box::new(EngineError::new_cannot_copy_files_from_one_directory_to_another(
+                event_details,
+                bootstrap_charts_dir,
+                common_charts_temp_dir,
+                e,
+            )));
+        }
+
+        // a stopped cluster cannot be reached, so it has to be started to be properly cleaned
+        if let Err(e) = self.start_cluster_if_stopped(event_details.clone()) {
+            self.logger().log(EngineEvent::Warning(
+                event_details.clone(),
+                EventMessage::new(
+                    "Cannot ensure AKS cluster is started, this is not blocking.".to_string(),
+                    Some(e.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)),
+                ),
+            ));
+        }
+
+        // should apply before destroy to be sure destroy will compute on all resources
+        // don't exit on failure, it can happen if we resume a destroy process
+        let message = format!(
+            "Ensuring everything is up to date before deleting cluster {}/{}",
+            self.name(),
+            self.id()
+        );
+        self.logger()
+            .log(EngineEvent::Info(event_details.clone(), EventMessage::new_from_safe(message)));
+
+        self.logger().log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe("Running Terraform apply before running a delete.".to_string()),
+        ));
+
+        if let Err(e) = terraform_init_validate_plan_apply(
+            temp_dir.as_str(),
+            false,
+            self.cloud_provider().credentials_environment_variables().as_slice(),
+        ) {
+            // An issue occurred during the apply before destroy of Terraform, it may be expected if you're resuming a destroy
+            self.logger().log(EngineEvent::Error(
+                EngineError::new_terraform_error(event_details.clone(), e),
+                None,
+            ));
+        };
+
+        let kubeconfig_path = &self.get_kubeconfig_file_path()?;
+        let kubeconfig_path = Path::new(kubeconfig_path);
+
+        if !skip_kubernetes_step {
+            // should make the diff between all namespaces and qovery managed namespaces
+            let message = format!(
+                "Deleting all non-Qovery deployed applications and dependencies for cluster {}/{}",
+                self.name(),
+                self.id()
+            );
+            self.logger()
+                .log(EngineEvent::Info(event_details.clone(), EventMessage::new_from_safe(message)));
+
+            let all_namespaces = kubectl_exec_get_all_namespaces(
+                kubeconfig_path,
+                self.cloud_provider().credentials_environment_variables(),
+            );
+
+            match all_namespaces {
+                Ok(namespace_vec) => {
+                    let namespaces_as_str = namespace_vec.iter().map(std::ops::Deref::deref).collect();
+                    let namespaces_to_delete = get_firsts_namespaces_to_delete(namespaces_as_str);
+
+                    self.logger().log(EngineEvent::Info(
+                        event_details.clone(),
+                        EventMessage::new_from_safe("Deleting non Qovery namespaces".to_string()),
+                    ));
+
+                    // Namespaces which are managed by AKS add-ons and cannot be deleted
+                    let undeletable_namespaces = ["gatekeeper-system", "calico-system", "tigera-operator"];
+                    for namespace_to_delete in namespaces_to_delete
+                        .into_iter()
+                        .filter(|ns| !undeletable_namespaces.contains(ns))
+                    {
+                        match kubectl_exec_delete_namespace(
+                            kubeconfig_path,
+                            namespace_to_delete,
+                            self.cloud_provider().credentials_environment_variables(),
+                        ) {
+                            Ok(_) => self.logger().log(EngineEvent::Info(
+                                event_details.clone(),
+                                EventMessage::new_from_safe(format!(
+                                    "Namespace `{namespace_to_delete}` deleted successfully."
+                                )),
+                            )),
+                            Err(e) => {
+                                if !(e.message(ErrorMessageVerbosity::FullDetails).contains("not found")) {
+                                    self.logger().log(EngineEvent::Warning(
+                                        event_details.clone(),
+                                        EventMessage::new_from_safe(format!(
+                                            "Can't delete the namespace `{namespace_to_delete}`"
+                                        )),
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    let message_safe = format!(
+                        "Error while getting all namespaces for Kubernetes cluster {}",
+                        self.name_with_id(),
+                    );
+                    self.logger().log(EngineEvent::Warning(
+                        event_details.clone(),
+                        EventMessage::new(
+                            message_safe,
+                            Some(e.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)),
+                        ),
+                    ));
+                }
+            }
+
+            let message = format!(
+                "Deleting all Qovery deployed elements and associated dependencies for cluster {}/{}",
+                self.name(),
+                self.id()
+            );
+            self.logger()
+                .log(EngineEvent::Info(event_details.clone(), EventMessage::new_from_safe(message)));
+
+            // delete custom metrics api to avoid stale namespaces on deletion
+            let helm = Helm::new(kubeconfig_path, &self.cloud_provider.credentials_environment_variables())
+                .map_err(|e| EngineError::new_helm_error(event_details.clone(), e))?;
+            let chart = ChartInfo::new_from_release_name("metrics-server", "kube-system");
+
+            if let Err(e) = helm.uninstall(&chart, &[], &CommandKiller::never(), &mut |_| {}, &mut |_| {}) {
+                // this error is not blocking
+                self.logger().log(EngineEvent::Warning(
+                    event_details.clone(),
+                    EventMessage::new_from_engine_error(EngineError::new_helm_error(event_details.clone(), e)),
+                ));
+            }
+
+            // required to avoid namespace stuck on deletion
+            if let Err(e) = uninstall_cert_manager(
+                kubeconfig_path,
+                self.cloud_provider().credentials_environment_variables(),
+                event_details.clone(),
+                self.logger(),
+            ) {
+                // this error is not blocking, logging a warning and move on
+                self.logger().log(EngineEvent::Warning(
+                    event_details.clone(),
+                    EventMessage::new(
+                        "An error occurred while trying to uninstall cert-manager. This is not blocking.".to_string(),
+                        Some(e.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)),
+                    ),
+                ));
+            }
+
+            self.logger().log(EngineEvent::Info(
+                event_details.clone(),
+                EventMessage::new_from_safe("Deleting Qovery managed helm charts".to_string()),
+            ));
+
+            let qovery_namespaces = get_qovery_managed_namespaces();
+            for qovery_namespace in qovery_namespaces.iter() {
+                let charts_to_delete = helm
+                    .list_release(Some(qovery_namespace), &[])
+                    .map_err(|e| EngineError::new_helm_error(event_details.clone(), e.clone()))?;
+
+                for chart in charts_to_delete {
+                    let chart_info = ChartInfo::new_from_release_name(&chart.name, &chart.namespace);
+                    match helm.uninstall(&chart_info, &[], &CommandKiller::never(), &mut |_| {}, &mut |_| {}) {
+                        Ok(_) => self.logger().log(EngineEvent::Info(
+                            event_details.clone(),
+                            EventMessage::new_from_safe(format!("Chart `{}` deleted", chart.name)),
+                        )),
+                        Err(e) => {
+                            let message_safe = format!("Can't delete chart `{}`", chart.name);
+                            self.logger().log(EngineEvent::Warning(
+                                event_details.clone(),
+                                EventMessage::new(message_safe, Some(e.to_string())),
+                            ))
+                        }
+                    }
+                }
+            }
+
+            self.logger().log(EngineEvent::Info(
+                event_details.clone(),
+                EventMessage::new_from_safe("Deleting Qovery managed namespaces".to_string()),
+            ));
+
+            for qovery_namespace in qovery_namespaces.iter() {
+                let deletion = kubectl_exec_delete_namespace(
+                    kubeconfig_path,
+                    qovery_namespace,
+                    self.cloud_provider().credentials_environment_variables(),
+                );
+                match deletion {
+                    Ok(_) => self.logger().log(EngineEvent::Info(
+                        event_details.clone(),
+                        EventMessage::new_from_safe(format!("Namespace {qovery_namespace} is fully deleted")),
+                    )),
+                    Err(e) => {
+                        if !(e.message(ErrorMessageVerbosity::FullDetails).contains("not found")) {
+                            self.logger().log(EngineEvent::Warning(
+                                event_details.clone(),
+                                EventMessage::new_from_safe(format!("Can't delete namespace {qovery_namespace}.")),
+                            ))
+                        }
+                    }
+                }
+            }
+
+            self.logger().log(EngineEvent::Info(
+                event_details.clone(),
+                EventMessage::new_from_safe("Delete all remaining deployed helm applications".to_string()),
+            ));
+
+            match helm.list_release(None, &[]) {
+                Ok(helm_charts) => {
+                    for chart in helm_charts {
+                        let chart_info = ChartInfo::new_from_release_name(&chart.name, &chart.namespace);
+                        match helm.uninstall(&chart_info, &[], &CommandKiller::never(), &mut |_| {}, &mut |_| {}) {
+                            Ok(_) => self.logger().log(EngineEvent::Info(
+                                event_details.clone(),
+                                EventMessage::new_from_safe(format!("Chart `{}` deleted", chart.name)),
+                            )),
+                            Err(e) => {
+                                let message_safe = format!("Error deleting chart `{}`", chart.name);
+                                self.logger().log(EngineEvent::Warning(
+                                    event_details.clone(),
+                                    EventMessage::new(message_safe, Some(e.to_string())),
+                                ))
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    let message_safe = "Unable to get helm list";
+                    self.logger().log(EngineEvent::Warning(
+                        event_details.clone(),
+                        EventMessage::new(message_safe.to_string(), Some(e.to_string())),
+                    ))
+                }
+            }
+        };
+
+        // delete object storages before the storage account is destroyed by terraform
+        if let Err(e) = self
+            .object_storage
+            .delete_bucket(&self.logs_bucket_name(), BucketDeleteStrategy::HardDelete)
+        {
+            self.logger.log(EngineEvent::Warning(
+                event_details.clone(),
+                EventMessage::new(
+                    format!("Cannot delete cluster logs object storage `{}`", &self.logs_bucket_name()),
+                    Some(e.to_string()),
+                ),
+            ))
+        }
+
+        let message = format!("Deleting Kubernetes cluster {}/{}", self.name(), self.id());
+        self.logger()
+            .log(EngineEvent::Info(event_details.clone(), EventMessage::new_from_safe(message)));
+
+        self.logger().log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe("Running Terraform destroy".to_string()),
+        ));
+
+        // storage account hosting kubeconfig container is removed along with the resource group
+        if let Err(err) = terraform_init_validate_destroy(
+            temp_dir.as_str(),
+            false,
+            self.cloud_provider().credentials_environment_variables().as_slice(),
+        ) {
+            return Err(Box::new(EngineError::new_terraform_error(event_details, err)));
+        }
+
+        // delete info on vault
+        let vault_conn = QVaultClient::new(event_details.clone());
+        if let Ok(vault_conn) = vault_conn {
+            let mount = secret_manager::vault::get_vault_mount_name(self.context().is_test_cluster());
+
+            // ignore on failure
+            if let Err(e) = vault_conn.delete_secret(mount.as_str(), self.long_id().to_string().as_str()) {
+                self.logger.log(EngineEvent::Warning(
+                    event_details.clone(),
+                    EventMessage::new("Cannot delete cluster config from Vault".to_string(), Some(e.to_string())),
+                ))
+            }
+        }
+
+        self.logger().log(EngineEvent::Info(
+            event_details,
+            EventMessage::new_from_safe("Kubernetes cluster successfully deleted".to_string()),
+        ));
+
+        Ok(())
+    }
+
+    fn pause(&self) -> Result<(), Box<EngineError>> {
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Pause));
+
+        self.logger().log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe("Preparing AKS cluster pause.".to_string()),
+        ));
+
+        // AKS stop deallocates all node pools while keeping cluster configuration
+        // https://learn.microsoft.com/en-us/azure/aks/start-stop-cluster
+        self.aks_power_action(event_details.clone(), "stop")?;
+
+        // avoid clippy yelling about `get_engine_location` not used
+        let _ = self.get_engine_location();
+
+        self.logger().log(EngineEvent::Info(
+            event_details,
+            EventMessage::new_from_safe("AKS cluster successfully paused.".to_string()),
+        ));
+
+        Ok(())
+    }
+
+    fn pause_error(&self) -> Result<(), Box<EngineError>> {
+        self.logger().log(EngineEvent::Warning(
+            self.get_event_details(Infrastructure(InfrastructureStep::Pause)),
+            EventMessage::new_from_safe("AKS.pause_error() called.".to_string()),
+        ));
+
+        Ok(())
+    }
+
+    fn get_aks_qovery_terraform_config(
+        &self,
+        qovery_terraform_config_file: &str,
+    ) -> Result<AksQoveryTerraformConfig, TerraformError> {
+        let content_file = match File::open(qovery_terraform_config_file) {
+            Ok(x) => x,
+            Err(e) => {
+                return Err(TerraformError::ConfigFileNotFound {
+                    path: qovery_terraform_config_file.to_string(),
+                    raw_message: e.to_string(),
+                });
+            }
+        };
+
+        let reader = BufReader::new(content_file);
+        match serde_json::from_reader(reader) {
+            Ok(config) => Ok(config),
+            Err(e) => Err(TerraformError::ConfigFileInvalidContent {
+                path: qovery_terraform_config_file.to_string(),
+                raw_message: e.to_string(),
+            }),
+        }
+    }
+
+    fn update_aks_vault_config(
+        &self,
+        event_details: EventDetails,
+        cluster_secrets: ClusterSecrets,
+    ) -> Result<(), Box<EngineError>> {
+        let vault_conn = match QVaultClient::new(event_details.clone()) {
+            Ok(x) => Some(x),
+            Err(_) => None,
+        };
+        if let Some(vault) = vault_conn {
+            let _ = cluster_secrets.create_or_update_secret(&vault, false, event_details.clone());
+        };
+
+        Ok(())
+    }
+}
+
+impl Kubernetes for Aks {
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Aks
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn long_id(&self) -> &Uuid {
+        &self.long_id
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn version(&self) -> KubernetesVersion {
+        self.version.clone()
+    }
+
+    fn region(&self) -> &str {
+        self.location.to_cloud_provider_format()
+    }
+
+    fn zones(&self) -> Option<Vec<&str>> {
+        let zones = self.location.zones();
+        if !zones.is_empty() {
+            return Some(zones.iter().map(|z| z.to_cloud_provider_format()).collect());
+        }
+        None
+    }
+
+    fn cloud_provider(&self) -> &dyn CloudProvider {
+        self.cloud_provider.as_ref()
+    }
+
+    fn dns_provider(&self) -> &dyn DnsProvider {
+        self.dns_provider.as_ref()
+    }
+
+    fn logger(&self) -> &dyn Logger {
+        self.logger.as_ref()
+    }
+
+    fn metrics_registry(&self) -> &dyn MetricsRegistry {
+        self.metrics_registry.as_ref()
+    }
+
+    fn config_file_store(&self) -> &dyn ObjectStorage {
+        &self.object_storage
+    }
+
+    fn is_valid(&self) -> Result<(), Box<EngineError>> {
+        Ok(())
+    }
+
+    fn is_network_managed_by_user(&self) -> bool {
+        false
+    }
+
+    fn cpu_architectures(&self) -> Vec<CpuArchitecture> {
+        self.nodes_groups
+            .iter()
+            .map(|node_group| node_group.instance_architecture)
+            .unique()
+            .collect()
+    }
+
+    #[named]
+    fn on_create(&self) -> Result<(), Box<EngineError>> {
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Create));
+        print_action(
+            self.cloud_provider.kind().to_string().to_lowercase().as_str(),
+            "kubernetes",
+            function_name!(),
+            self.name(),
+            event_details,
+            self.logger(),
+        );
+        send_progress_on_long_task(self, Action::Create, || self.create())
+    }
+
+    #[named]
+    fn on_create_error(&self) -> Result<(), Box<EngineError>> {
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Create));
+        print_action(
+            self.cloud_provider.kind().to_string().to_lowercase().as_str(),
+            "kubernetes",
+            function_name!(),
+            self.name(),
+            event_details,
+            self.logger(),
+        );
+        send_progress_on_long_task(self, Action::Create, || self.create_error())
+    }
+
+    fn upgrade_with_status(&self, kubernetes_upgrade_status: KubernetesUpgradeStatus) -> Result<(), Box<EngineError>> {
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Upgrade));
+        self.logger().log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe(format!(
+                "Start upgrading AKS cluster to Kubernetes {}",
+                kubernetes_upgrade_status.requested_version
+            )),
+        ));
+
+        // Terraform upgrades control plane first, then node pools
+        self.apply_infrastructure(event_details.clone(), kubernetes_upgrade_status.requested_version.to_string())?;
+
+        if let Err(e) = self.check_workers_on_upgrade(kubernetes_upgrade_status.requested_version.to_string()) {
+            return Err(Box::new(EngineError::new_k8s_node_not_ready_with_requested_version(
+                event_details,
+                kubernetes_upgrade_status.requested_version.to_string(),
+                e,
+            )));
+        }
+
+        self.logger().log(EngineEvent::Info(
+            event_details,
+            EventMessage::new_from_safe("Kubernetes nodes have been successfully upgraded".to_string()),
+        ));
+
+        Ok(())
+    }
+
+    #[named]
+    fn on_upgrade(&self) -> Result<(), Box<EngineError>> {
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Upgrade));
+        print_action(
+            self.cloud_provider.kind().to_string().to_lowercase().as_str(),
+            "kubernetes",
+            function_name!(),
+            self.name(),
+            event_details,
+            self.logger(),
+        );
+        send_progress_on_long_task(self, Action::Create, || self.upgrade())
+    }
+
+    fn on_upgrade_error(&self) -> Result<(), Box<EngineError>> {
+        self.logger().log(EngineEvent::Warning(
+            self.get_event_details(Infrastructure(InfrastructureStep::Upgrade)),
+            EventMessage::new_from_safe("AKS.upgrade_error() called.".to_string()),
+        ));
+
+        Ok(())
+    }
+
+    #[named]
+    fn on_pause(&self) -> Result<(), Box<EngineError>> {
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Pause));
+        print_action(
+            self.cloud_provider.kind().to_string().to_lowercase().as_str(),
+            "kubernetes",
+            function_name!(),
+            self.name(),
+            event_details,
+            self.logger(),
+        );
+        send_progress_on_long_task(self, Action::Pause, || self.pause())
+    }
+
+    #[named]
+    fn on_pause_error(&self) -> Result<(), Box<EngineError>> {
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Pause));
+        print_action(
+            self.cloud_provider.kind().to_string().to_lowercase().as_str(),
+            "kubernetes",
+            function_name!(),
+            self.name(),
+            event_details,
+            self.logger(),
+        );
+        send_progress_on_long_task(self, Action::Pause, || self.pause_error())
+    }
+
+    #[named]
+    fn on_delete(&self) -> Result<(), Box<EngineError>> {
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Delete));
+        print_action(
+            self.cloud_provider.kind().to_string().to_lowercase().as_str(),
+            "kubernetes",
+            function_name!(),
+            self.name(),
+            event_details,
+            self.logger(),
+        );
+        send_progress_on_long_task(self, Action::Delete, || self.delete())
+    }
+
+    fn on_delete_error(&self) -> Result<(), Box<EngineError>> {
+        self.logger().log(EngineEvent::Warning(
+            self.get_event_details(Stage::Infrastructure(InfrastructureStep::Delete)),
+            EventMessage::new_from_safe(format!(
+                "{}.delete_error() called.",
+                self.cloud_provider.kind().to_string().to_lowercase().as_str(),
+            )),
+        ));
+
+        Ok(())
+    }
+
+    fn update_vault_config(
+        &self,
+        event_details: EventDetails,
+        _qovery_terraform_config_file: String,
+        cluster_secrets: ClusterSecrets,
+        _kubeconfig_file_path: Option<&Path>,
+    ) -> Result<(), Box<EngineError>> {
+        self.update_aks_vault_config(event_details, cluster_secrets)
+    }
+
+    fn advanced_settings(&self) -> &ClusterAdvancedSettings {
+        &self.advanced_settings
+    }
+
+    fn customer_helm_charts_override(&self) -> Option<HashMap<ChartValuesOverrideName, ChartValuesOverrideValues>> {
+        self.customer_helm_charts_override.clone()
+    }
+
+    fn get_kubernetes_connection(&self) -> Option<String> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AksQoveryTerraformConfig {
+    pub aks_cluster_public_hostname: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cloud_provider::models::{NodeGroupCapacityType, NodeGroupTaint, TaintEffect};
+    use crate::template::generate_j2_template_files;
+
+    #[test]
+    fn test_aks_node_pool_name() {
+        struct TestCase<'a> {
+            node_group_name: &'a str,
+            index: usize,
+            expected: &'a str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                node_group_name: "default",
+                index: 0,
+                expected: "default",
+            },
+            TestCase {
+                node_group_name: "Default-Pool_With-A-Long-Name",
+                index: 0,
+                expected: "defaultpoolw",
+            },
+            TestCase {
+                node_group_name: "Default-Pool_With-A-Long-Name",
+                index: 12,
+                expected: "defaultpoo12",
+            },
+            TestCase {
+                node_group_name: "42-gpu",
+                index: 1,
+                expected: "gpu1",
+            },
+            TestCase {
+                node_group_name: "---",
+                index: 3,
+                expected: "pool3",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = aks_node_pool_name(tc.node_group_name, tc.index);
+
+            // verify:
+            assert_eq!(tc.expected, result);
+        }
+    }
+
+    #[test]
+    fn test_azure_alphanumeric_resource_name() {
+        assert_eq!(
+            "qoveryz1234567",
+            azure_alphanumeric_resource_name("qovery", "z1234567", AZURE_STORAGE_ACCOUNT_NAME_MAX_LENGTH)
+        );
+        assert_eq!(
+            "qoveryz123456789abcdef01",
+            azure_alphanumeric_resource_name(
+                "qovery",
+                "Z123-4567-89ab-cdef-0123",
+                AZURE_STORAGE_ACCOUNT_NAME_MAX_LENGTH
+            )
+        );
+    }
+
+    #[test]
+    fn test_aks_terraform_templates_rendering() {
+        // setup:
+        let template_directory = format!(
+            "{}/lib/azure/bootstrap",
+            env::current_dir()
+                .expect("Impossible to get current directory")
+                .to_str()
+                .expect("Impossible to convert current directory to string")
+        );
+        let node_group = |name: &str, instance_type: &str| NodeGroups {
+            name: name.to_string(),
+            id: None,
+            min_nodes: 3,
+            max_nodes: 10,
+            desired_nodes: None,
+            instance_type: instance_type.to_string(),
+            disk_size_in_gib: 50,
+            instance_architecture: CpuArchitecture::AMD64,
+            capacity_type: NodeGroupCapacityType::OnDemand,
+            fallback_instance_types: vec![],
+            labels: BTreeMap::new(),
+            taints: vec![],
+        };
+        let gpu_node_group = NodeGroups {
+            capacity_type: NodeGroupCapacityType::Spot,
+            labels: BTreeMap::from([("workload".to_string(), "gpu".to_string())]),
+            taints: vec![NodeGroupTaint {
+                key: "nvidia.com/gpu".to_string(),
+                value: "true".to_string(),
+                effect: TaintEffect::NoSchedule,
+            }],
+            ..node_group("gpu", "Standard_NC6s_v3")
+        };
+        let mut context = TeraContext::new();
+        for (key, value) in [
+            ("organization_id", "zorg1234"),
+            ("organization_long_id", "00000000-0000-0000-0000-000000000000"),
+            ("object_storage_kubeconfig_bucket", "qovery-kubeconfigs-z1234567"),
+            ("object_storage_logs_bucket", "qovery-logs-z1234567"),
+            ("kubernetes_cluster_long_id", "11111111-1111-1111-1111-111111111111"),
+            ("kubernetes_cluster_id", "z1234567"),
+            ("kubernetes_cluster_name", "qovery-z1234567"),
+            ("kubernetes_cluster_version", "1.28"),
+            ("aws_access_key_tfstates_account", "access_key"),
+            ("aws_secret_key_tfstates_account", "secret_key"),
+            ("aws_region_tfstates_account", "eu-west-3"),
+            ("aws_terraform_backend_dynamodb_table", "qovery-terrafom-tfstates"),
+            ("aws_terraform_backend_bucket", "qovery-terrafom-tfstates"),
+            ("wildcard_managed_dns", "*.z1234567.qovery.io"),
+            ("gateway_api_enabled", "false"),
+        ] {
+            context.insert(key, value);
+        }
+        context.insert("resource_expiration_in_seconds", &-1);
+        context.insert("test_cluster", &false);
+
+        // execute:
+        insert_aks_tera_context(
+            &mut context,
+            "z1234567",
+            &AzureLocation::WestEurope,
+            &AzureCredentials {
+                client_id: "client_id".to_string(),
+                client_secret: "client_secret".to_string(),
+                tenant_id: "tenant_id".to_string(),
+                subscription_id: "subscription_id".to_string(),
+            },
+            &[node_group("default", "Standard_D4s_v5"), gpu_node_group],
+        );
+        let rendered_templates =
+            generate_j2_template_files(template_directory, context).expect("Cannot render AKS templates");
+        let rendered = |file_name: &str| -> String {
+            rendered_templates
+                .iter()
+                .find(|t| t.file_name == file_name)
+                .unwrap_or_else(|| panic!("Template `{file_name}` should have been rendered"))
+                .content
+                .to_string()
+        };
+
+        // verify:
+        let vars = rendered("tf-default-vars.tf");
+        assert!(vars.contains(r#"default     = "westeurope""#));
+        assert!(vars.contains(r#"default     = ["1", "2", "3"]"#));
+        assert!(vars.contains(r#"default     = "qovery-z1234567""#));
+        assert!(vars.contains(r#"default     = "qoveryz1234567""#));
+        assert!(vars.contains(r#"default     = "1.28""#));
+        assert!(!vars.contains("resource_expiration_in_seconds"));
+
+        let cluster = rendered("cluster.tf");
+        assert!(cluster.contains(r#"name                         = "default""#));
+        assert!(cluster.contains(r#"vm_size                      = "Standard_D4s_v5""#));
+        assert!(cluster.contains(r#"resource "azurerm_kubernetes_cluster_node_pool" "gpu1""#));
+        assert!(cluster.contains(r#"vm_size               = "Standard_NC6s_v3""#));
+        assert!(!cluster.contains(r#"resource "azurerm_kubernetes_cluster_node_pool" "default""#));
+        assert!(cluster.contains(r#"priority              = "Spot""#));
+        assert!(cluster.contains(r#"node_labels           = { "workload" = "gpu" }"#));
+        assert!(cluster.contains(
+            r#"node_taints           = ["nvidia.com/gpu=true:NoSchedule", "kubernetes.azure.com/scalesetpriority=spot:NoSchedule"]"#
+        ));
+        assert!(cluster.contains(r#"sku_tier                  = "Standard""#));
+
+        // service principal secret must never be rendered into templates
+        assert!(rendered_templates.iter().all(|t| !t.content.contains("client_secret")));
+    }
+}
@@ -0,0 +1,327 @@
+use crate::models::ToCloudProviderFormat;
+use crate::object_storage::StorageRegion;
+use serde_derive::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use strum_macros::EnumIter;
+
+/// Azure availability zones are not named after their location, a location exposes zones `1`, `2` and `3`
+/// when it supports availability zones.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, EnumIter)]
+pub enum AzureZone {
+    Zone1,
+    Zone2,
+    Zone3,
+}
+
+impl ToCloudProviderFormat for AzureZone {
+    fn to_cloud_provider_format(&self) -> &str {
+        match self {
+            AzureZone::Zone1 => "1",
+            AzureZone::Zone2 => "2",
+            AzureZone::Zone3 => "3",
+        }
+    }
+}
+
+impl Display for AzureZone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.to_cloud_provider_format())
+    }
+}
+
+impl FromStr for AzureZone {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<AzureZone, ()> {
+        match s.trim() {
+            "1" => Ok(AzureZone::Zone1),
+            "2" => Ok(AzureZone::Zone2),
+            "3" => Ok(AzureZone::Zone3),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, EnumIter)]
+// Sync with Qovery Core team if you update this content
+// Locations can be retrieved with this command:
+// az account list-locations --query "[?metadata.regionType=='Physical'].name" -o tsv | sort
+pub enum AzureLocation {
+    AustraliaEast,
+    AustraliaSoutheast,
+    BrazilSouth,
+    CanadaCentral,
+    CanadaEast,
+    CentralIndia,
+    CentralUs,
+    EastAsia,
+    EastUs,
+    EastUs2,
+    FranceCentral,
+    GermanyWestCentral,
+    ItalyNorth,
+    JapanEast,
+    JapanWest,
+    KoreaCentral,
+    KoreaSouth,
+    NorthCentralUs,
+    NorthEurope,
+    NorwayEast,
+    PolandCentral,
+    SouthAfricaNorth,
+    SouthCentralUs,
+    SouthIndia,
+    SoutheastAsia,
+    SwedenCentral,
+    SwitzerlandNorth,
+    UaeNorth,
+    UkSouth,
+    UkWest,
+    WestCentralUs,
+    WestEurope,
+    WestUs,
+    WestUs2,
+    WestUs3,
+}
+
+impl AzureLocation {
+    /// Availability zones for the location, empty if the location doesn't support availability zones
+    pub fn zones(&self) -> &[AzureZone] {
+        match &self {
+            AzureLocation::AustraliaEast
+            | AzureLocation::BrazilSouth
+            | AzureLocation::CanadaCentral
+            | AzureLocation::CentralIndia
+            | AzureLocation::CentralUs
+            | AzureLocation::EastAsia
+            | AzureLocation::EastUs
+            | AzureLocation::EastUs2
+            | AzureLocation::FranceCentral
+            | AzureLocation::GermanyWestCentral
+            | AzureLocation::ItalyNorth
+            | AzureLocation::JapanEast
+            | AzureLocation::KoreaCentral
+            | AzureLocation::NorthEurope
+            | AzureLocation::NorwayEast
+            | AzureLocation::PolandCentral
+            | AzureLocation::SouthAfricaNorth
+            | AzureLocation::SouthCentralUs
+            | AzureLocation::SoutheastAsia
+            | AzureLocation::SwedenCentral
+            | AzureLocation::SwitzerlandNorth
+            | AzureLocation::UaeNorth
+            | AzureLocation::UkSouth
+            | AzureLocation::WestEurope
+            | AzureLocation::WestUs2
+            | AzureLocation::WestUs3 => &[AzureZone::Zone1, AzureZone::Zone2, AzureZone::Zone3],
+            AzureLocation::AustraliaSoutheast
+            | AzureLocation::CanadaEast
+            | AzureLocation::JapanWest
+            | AzureLocation::KoreaSouth
+            | AzureLocation::NorthCentralUs
+            | AzureLocation::SouthIndia
+            | AzureLocation::UkWest
+            | AzureLocation::WestCentralUs
+            | AzureLocation::WestUs => &[],
+        }
+    }
+}
+
+impl StorageRegion for AzureLocation {}
+
+impl ToCloudProviderFormat for AzureLocation {
+    fn to_cloud_provider_format(&self) -> &str {
+        match self {
+            AzureLocation::AustraliaEast => "australiaeast",
+            AzureLocation::AustraliaSoutheast => "australiasoutheast",
+            AzureLocation::BrazilSouth => "brazilsouth",
+            AzureLocation::CanadaCentral => "canadacentral",
+            AzureLocation::CanadaEast => "canadaeast",
+            AzureLocation::CentralIndia => "centralindia",
+            AzureLocation::CentralUs => "centralus",
+            AzureLocation::EastAsia => "eastasia",
+            AzureLocation::EastUs => "eastus",
+            AzureLocation::EastUs2 => "eastus2",
+            AzureLocation::FranceCentral => "francecentral",
+            AzureLocation::GermanyWestCentral => "germanywestcentral",
+            AzureLocation::ItalyNorth => "italynorth",
+            AzureLocation::JapanEast => "japaneast",
+            AzureLocation::JapanWest => "japanwest",
+            AzureLocation::KoreaCentral => "koreacentral",
+            AzureLocation::KoreaSouth => "koreasouth",
+            AzureLocation::NorthCentralUs => "northcentralus",
+            AzureLocation::NorthEurope => "northeurope",
+            AzureLocation::NorwayEast => "norwayeast",
+            AzureLocation::PolandCentral => "polandcentral",
+            AzureLocation::SouthAfricaNorth => "southafricanorth",
+            AzureLocation::SouthCentralUs => "southcentralus",
+            AzureLocation::SouthIndia => "southindia",
+            AzureLocation::SoutheastAsia => "southeastasia",
+            AzureLocation::SwedenCentral => "swedencentral",
+            AzureLocation::SwitzerlandNorth => "switzerlandnorth",
+            AzureLocation::UaeNorth => "uaenorth",
+            AzureLocation::UkSouth => "uksouth",
+            AzureLocation::UkWest => "ukwest",
+            AzureLocation::WestCentralUs => "westcentralus",
+            AzureLocation::WestEurope => "westeurope",
+            AzureLocation::WestUs => "westus",
+            AzureLocation::WestUs2 => "westus2",
+            AzureLocation::WestUs3 => "westus3",
+        }
+    }
+}
+
+impl Display for AzureLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.to_cloud_provider_format())
+    }
+}
+
+impl FromStr for AzureLocation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<AzureLocation, ()> {
+        let v: &str = &s.to_lowercase();
+        match v {
+            "australiaeast" => Ok(AzureLocation::AustraliaEast),
+            "australiasoutheast" => Ok(AzureLocation::AustraliaSoutheast),
+            "brazilsouth" => Ok(AzureLocation::BrazilSouth),
+            "canadacentral" => Ok(AzureLocation::CanadaCentral),
+            "canadaeast" => Ok(AzureLocation::CanadaEast),
+            "centralindia" => Ok(AzureLocation::CentralIndia),
+            "centralus" => Ok(AzureLocation::CentralUs),
+            "eastasia" => Ok(AzureLocation::EastAsia),
+            "eastus" => Ok(AzureLocation::EastUs),
+            "eastus2" => Ok(AzureLocation::EastUs2),
+            "francecentral" => Ok(AzureLocation::FranceCentral),
+            "germanywestcentral" => Ok(AzureLocation::GermanyWestCentral),
+            "italynorth" => Ok(AzureLocation::ItalyNorth),
+            "japaneast" => Ok(AzureLocation::JapanEast),
+            "japanwest" => Ok(AzureLocation::JapanWest),
+            "koreacentral" => Ok(AzureLocation::KoreaCentral),
+            "koreasouth" => Ok(AzureLocation::KoreaSouth),
+            "northcentralus" => Ok(AzureLocation::NorthCentralUs),
+            "northeurope" => Ok(AzureLocation::NorthEurope),
+            "norwayeast" => Ok(AzureLocation::NorwayEast),
+            "polandcentral" => Ok(AzureLocation::PolandCentral),
+            "southafricanorth" => Ok(AzureLocation::SouthAfricaNorth),
+            "southcentralus" => Ok(AzureLocation::SouthCentralUs),
+            "southindia" => Ok(AzureLocation::SouthIndia),
+            "southeastasia" => Ok(AzureLocation::SoutheastAsia),
+            "swedencentral" => Ok(AzureLocation::SwedenCentral),
+            "switzerlandnorth" => Ok(AzureLocation::SwitzerlandNorth),
+            "uaenorth" => Ok(AzureLocation::UaeNorth),
+            "uksouth" => Ok(AzureLocation::UkSouth),
+            "ukwest" => Ok(AzureLocation::UkWest),
+            "westcentralus" => Ok(AzureLocation::WestCentralUs),
+            "westeurope" => Ok(AzureLocation::WestEurope),
+            "westus" => Ok(AzureLocation::WestUs),
+            "westus2" => Ok(AzureLocation::WestUs2),
+            "westus3" => Ok(AzureLocation::WestUs3),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::azure::locations::{AzureLocation, AzureZone};
+    use crate::models::ToCloudProviderFormat;
+    use std::str::FromStr;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_azure_location_to_azure_format() {
+        for location in AzureLocation::iter() {
+            assert_eq!(
+                match location {
+                    AzureLocation::AustraliaEast => "australiaeast",
+                    AzureLocation::AustraliaSoutheast => "australiasoutheast",
+                    AzureLocation::BrazilSouth => "brazilsouth",
+                    AzureLocation::CanadaCentral => "canadacentral",
+                    AzureLocation::CanadaEast => "canadaeast",
+                    AzureLocation::CentralIndia => "centralindia",
+                    AzureLocation::CentralUs => "centralus",
+                    AzureLocation::EastAsia => "eastasia",
+                    AzureLocation::EastUs => "eastus",
+                    AzureLocation::EastUs2 => "eastus2",
+                    AzureLocation::FranceCentral => "francecentral",
+                    AzureLocation::GermanyWestCentral => "germanywestcentral",
+                    AzureLocation::ItalyNorth => "italynorth",
+                    AzureLocation::JapanEast => "japaneast",
+                    AzureLocation::JapanWest => "japanwest",
+                    AzureLocation::KoreaCentral => "koreacentral",
+                    AzureLocation::KoreaSouth => "koreasouth",
+                    AzureLocation::NorthCentralUs => "northcentralus",
+                    AzureLocation::NorthEurope => "northeurope",
+                    AzureLocation::NorwayEast => "norwayeast",
+                    AzureLocation::PolandCentral => "polandcentral",
+                    AzureLocation::SouthAfricaNorth => "southafricanorth",
+                    AzureLocation::SouthCentralUs => "southcentralus",
+                    AzureLocation::SouthIndia => "southindia",
+                    AzureLocation::SoutheastAsia => "southeastasia",
+                    AzureLocation::SwedenCentral => "swedencentral",
+                    AzureLocation::SwitzerlandNorth => "switzerlandnorth",
+                    AzureLocation::UaeNorth => "uaenorth",
+                    AzureLocation::UkSouth => "uksouth",
+                    AzureLocation::UkWest => "ukwest",
+                    AzureLocation::WestCentralUs => "westcentralus",
+                    AzureLocation::WestEurope => "westeurope",
+                    AzureLocation::WestUs => "westus",
+                    AzureLocation::WestUs2 => "westus2",
+                    AzureLocation::WestUs3 => "westus3",
+                },
+                location.to_cloud_provider_format()
+            );
+        }
+    }
+
+    #[test]
+    fn test_azure_location_zones() {
+        // setup:
+        let locations_without_zones = [
+            AzureLocation::AustraliaSoutheast,
+            AzureLocation::CanadaEast,
+            AzureLocation::JapanWest,
+            AzureLocation::KoreaSouth,
+            AzureLocation::NorthCentralUs,
+            AzureLocation::SouthIndia,
+            AzureLocation::UkWest,
+            AzureLocation::WestCentralUs,
+            AzureLocation::WestUs,
+        ];
+
+        for location in AzureLocation::iter() {
+            // execute:
+            let zones = location.zones();
+
+            // verify:
+            match locations_without_zones.contains(&location) {
+                true => assert!(zones.is_empty()),
+                false => assert_eq!(&[AzureZone::Zone1, AzureZone::Zone2, AzureZone::Zone3], zones),
+            }
+        }
+    }
+
+    #[test]
+    fn test_azure_location_from_str() {
+        // test all supported locations
+        for location in AzureLocation::iter() {
+            assert_eq!(location, AzureLocation::from_str(location.to_cloud_provider_format()).unwrap());
+            assert_eq!(location, AzureLocation::from_str(&location.to_string().to_uppercase()).unwrap());
+        }
+
+        // test unsupported location
+        assert!(AzureLocation::from_str("an-unsupported-location").is_err());
+    }
+
+    #[test]
+    fn test_azure_zone_from_str() {
+        for zone in AzureZone::iter() {
+            assert_eq!(zone, AzureZone::from_str(zone.to_cloud_provider_format()).unwrap());
+        }
+
+        assert!(AzureZone::from_str("4").is_err());
+    }
+}
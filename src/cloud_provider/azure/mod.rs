@@ -0,0 +1,156 @@
+pub mod kubernetes;
+pub mod locations;
+
+use crate::cloud_provider::azure::locations::AzureLocation;
+use crate::cloud_provider::{kubernetes::Kind as KubernetesKind, CloudProvider, Kind, TerraformStateCredentials};
+use crate::constants::{AZURE_CLIENT_ID, AZURE_CLIENT_SECRET, AZURE_SUBSCRIPTION_ID, AZURE_TENANT_ID};
+use crate::errors::EngineError;
+use crate::events::{EventDetails, Stage, Transmitter};
+use crate::io_models::context::Context;
+use crate::io_models::QoveryIdentifier;
+use crate::models::azure::AzureCredentials;
+use crate::models::ToCloudProviderFormat;
+use crate::utilities::to_short_id;
+use std::any::Any;
+use uuid::Uuid;
+
+pub struct Azure {
+    context: Context,
+    id: String,
+    long_id: Uuid,
+    name: String,
+    credentials: AzureCredentials,
+    location: AzureLocation,
+    terraform_state_credentials: TerraformStateCredentials,
+}
+
+impl Azure {
+    pub fn new(
+        context: Context,
+        long_id: Uuid,
+        name: &str,
+        credentials: AzureCredentials,
+        location: AzureLocation,
+        terraform_state_credentials: TerraformStateCredentials,
+    ) -> Azure {
+        Azure {
+            context,
+            id: to_short_id(&long_id),
+            long_id,
+            name: name.to_string(),
+            credentials,
+            location,
+            terraform_state_credentials,
+        }
+    }
+
+    pub fn credentials(&self) -> &AzureCredentials {
+        &self.credentials
+    }
+
+    pub fn location(&self) -> &AzureLocation {
+        &self.location
+    }
+}
+
+impl CloudProvider for Azure {
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Azure
+    }
+
+    fn kubernetes_kind(&self) -> KubernetesKind {
+        KubernetesKind::Aks
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn organization_id(&self) -> &str {
+        self.context.organization_short_id()
+    }
+
+    fn organization_long_id(&self) -> Uuid {
+        *self.context.organization_long_id()
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Azure service principal client id is used as access key
+    fn access_key_id(&self) -> String {
+        self.credentials.client_id.to_string()
+    }
+
+    fn secret_access_key(&self) -> String {
+        self.credentials.client_secret.to_string()
+    }
+
+    fn region(&self) -> String {
+        self.location.to_cloud_provider_format().to_string()
+    }
+
+    fn aws_sdk_client(&self) -> Option<aws_config::SdkConfig> {
+        None
+    }
+
+    fn is_valid(&self) -> Result<(), Box<EngineError>> {
+        Ok(())
+    }
+
+    fn zones(&self) -> Vec<String> {
+        self.location
+            .zones()
+            .iter()
+            .map(|z| z.to_cloud_provider_format().to_string())
+            .collect()
+    }
+
+    fn credentials_environment_variables(&self) -> Vec<(&str, &str)> {
+        vec![
+            (AZURE_CLIENT_ID, self.credentials.client_id.as_str()),
+            (AZURE_CLIENT_SECRET, self.credentials.client_secret.as_str()),
+            (AZURE_TENANT_ID, self.credentials.tenant_id.as_str()),
+            (AZURE_SUBSCRIPTION_ID, self.credentials.subscription_id.as_str()),
+        ]
+    }
+
+    fn tera_context_environment_variables(&self) -> Vec<(&str, &str)> {
+        vec![
+            ("azure_client_id", self.credentials.client_id.as_str()),
+            ("azure_client_secret", self.credentials.client_secret.as_str()),
+            ("azure_tenant_id", self.credentials.tenant_id.as_str()),
+            ("azure_subscription_id", self.credentials.subscription_id.as_str()),
+            ("azure_location", self.location.to_cloud_provider_format()),
+        ]
+    }
+
+    fn terraform_state_credentials(&self) -> Option<&TerraformStateCredentials> {
+        Some(&self.terraform_state_credentials)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_event_details(&self, stage: Stage) -> EventDetails {
+        let context = self.context();
+        EventDetails::new(
+            None,
+            QoveryIdentifier::new(*context.organization_long_id()),
+            QoveryIdentifier::new(*context.cluster_long_id()),
+            context.execution_id().to_string(),
+            stage,
+            self.to_transmitter(),
+        )
+    }
+
+    fn to_transmitter(&self) -> Transmitter {
+        Transmitter::CloudProvider(self.long_id, self.name.to_string())
+    }
+}
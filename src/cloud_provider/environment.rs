@@ -6,6 +6,7 @@ use crate::io_models::context::Context;
 use crate::models::application::ApplicationService;
 use crate::models::container::ContainerService;
 use crate::models::database::DatabaseService;
+use crate::models::dependency_graph::ServiceDependencyGraph;
 use crate::models::helm_chart::HelmChartService;
 use crate::models::job::JobService;
 use crate::models::router::RouterService;
@@ -31,6 +32,8 @@ pub struct Environment {
     pub databases: Vec<Box<dyn DatabaseService>>,
     pub jobs: Vec<Box<dyn JobService>>,
     pub helm_charts: Vec<Box<dyn HelmChartService>>,
+    pub dependencies: ServiceDependencyGraph,
+    pub rollback_on_failure: bool,
 }
 
 impl Environment {
@@ -50,6 +53,8 @@ impl Environment {
         databases: Vec<Box<dyn DatabaseService>>,
         jobs: Vec<Box<dyn JobService>>,
         helm_charts: Vec<Box<dyn HelmChartService>>,
+        dependencies: ServiceDependencyGraph,
+        rollback_on_failure: bool,
     ) -> Self {
         let project_id = to_short_id(&project_long_id);
         let env_id = to_short_id(&long_id);
@@ -77,6 +82,8 @@ impl Environment {
             databases,
             jobs,
             helm_charts,
+            dependencies,
+            rollback_on_failure,
         }
     }
 
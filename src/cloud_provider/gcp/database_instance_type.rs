@@ -0,0 +1,200 @@
+use crate::cloud_provider::service::DatabaseType;
+use crate::cloud_provider::Kind;
+use crate::models::database::{DatabaseError, DatabaseInstanceType};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use strum_macros::EnumIter;
+
+/// Cloud SQL machine tiers and Memorystore capacities available for managed databases
+/// https://cloud.google.com/sql/docs/postgres/instance-settings#machine-type-2ndgen
+/// https://cloud.google.com/memorystore/docs/redis/pricing#instance_pricing
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter)]
+#[allow(non_camel_case_types)]
+pub enum GcpDatabaseInstanceType {
+    DB_F1_MICRO,
+    DB_G1_SMALL,
+    DB_CUSTOM_1_3840,
+    DB_CUSTOM_2_7680,
+    DB_CUSTOM_4_15360,
+    DB_CUSTOM_8_30720,
+    DB_CUSTOM_16_61440,
+    REDIS_1GB,
+    REDIS_2GB,
+    REDIS_4GB,
+    REDIS_8GB,
+    REDIS_16GB,
+}
+
+impl GcpDatabaseInstanceType {
+    /// Memorystore instances are sized by memory only, returns None for Cloud SQL tiers
+    pub fn redis_memory_size_in_gib(&self) -> Option<u32> {
+        match self {
+            GcpDatabaseInstanceType::REDIS_1GB => Some(1),
+            GcpDatabaseInstanceType::REDIS_2GB => Some(2),
+            GcpDatabaseInstanceType::REDIS_4GB => Some(4),
+            GcpDatabaseInstanceType::REDIS_8GB => Some(8),
+            GcpDatabaseInstanceType::REDIS_16GB => Some(16),
+            _ => None,
+        }
+    }
+}
+
+impl DatabaseInstanceType for GcpDatabaseInstanceType {
+    fn cloud_provider(&self) -> Kind {
+        Kind::Gcp
+    }
+
+    fn to_cloud_provider_format(&self) -> String {
+        match self {
+            GcpDatabaseInstanceType::DB_F1_MICRO => "db-f1-micro",
+            GcpDatabaseInstanceType::DB_G1_SMALL => "db-g1-small",
+            GcpDatabaseInstanceType::DB_CUSTOM_1_3840 => "db-custom-1-3840",
+            GcpDatabaseInstanceType::DB_CUSTOM_2_7680 => "db-custom-2-7680",
+            GcpDatabaseInstanceType::DB_CUSTOM_4_15360 => "db-custom-4-15360",
+            GcpDatabaseInstanceType::DB_CUSTOM_8_30720 => "db-custom-8-30720",
+            GcpDatabaseInstanceType::DB_CUSTOM_16_61440 => "db-custom-16-61440",
+            GcpDatabaseInstanceType::REDIS_1GB => "redis-1gb",
+            GcpDatabaseInstanceType::REDIS_2GB => "redis-2gb",
+            GcpDatabaseInstanceType::REDIS_4GB => "redis-4gb",
+            GcpDatabaseInstanceType::REDIS_8GB => "redis-8gb",
+            GcpDatabaseInstanceType::REDIS_16GB => "redis-16gb",
+        }
+        .to_string()
+    }
+
+    fn is_instance_allowed(&self) -> bool {
+        match self {
+            GcpDatabaseInstanceType::DB_F1_MICRO => true,
+            GcpDatabaseInstanceType::DB_G1_SMALL => true,
+            GcpDatabaseInstanceType::DB_CUSTOM_1_3840 => true,
+            GcpDatabaseInstanceType::DB_CUSTOM_2_7680 => true,
+            GcpDatabaseInstanceType::DB_CUSTOM_4_15360 => true,
+            GcpDatabaseInstanceType::DB_CUSTOM_8_30720 => true,
+            GcpDatabaseInstanceType::DB_CUSTOM_16_61440 => true,
+            GcpDatabaseInstanceType::REDIS_1GB => true,
+            GcpDatabaseInstanceType::REDIS_2GB => true,
+            GcpDatabaseInstanceType::REDIS_4GB => true,
+            GcpDatabaseInstanceType::REDIS_8GB => true,
+            GcpDatabaseInstanceType::REDIS_16GB => true,
+        }
+    }
+
+    fn is_instance_compatible_with(&self, database_type: DatabaseType) -> bool {
+        match self {
+            GcpDatabaseInstanceType::DB_F1_MICRO
+            | GcpDatabaseInstanceType::DB_G1_SMALL
+            | GcpDatabaseInstanceType::DB_CUSTOM_1_3840
+            | GcpDatabaseInstanceType::DB_CUSTOM_2_7680
+            | GcpDatabaseInstanceType::DB_CUSTOM_4_15360
+            | GcpDatabaseInstanceType::DB_CUSTOM_8_30720
+            | GcpDatabaseInstanceType::DB_CUSTOM_16_61440 => {
+                matches!(database_type, DatabaseType::PostgreSQL | DatabaseType::MySQL)
+            }
+            GcpDatabaseInstanceType::REDIS_1GB
+            | GcpDatabaseInstanceType::REDIS_2GB
+            | GcpDatabaseInstanceType::REDIS_4GB
+            | GcpDatabaseInstanceType::REDIS_8GB
+            | GcpDatabaseInstanceType::REDIS_16GB => database_type == DatabaseType::Redis,
+        }
+    }
+}
+
+impl Display for GcpDatabaseInstanceType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_cloud_provider_format())
+    }
+}
+
+impl FromStr for GcpDatabaseInstanceType {
+    type Err = DatabaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "db-f1-micro" => Ok(GcpDatabaseInstanceType::DB_F1_MICRO),
+            "db-g1-small" => Ok(GcpDatabaseInstanceType::DB_G1_SMALL),
+            "db-custom-1-3840" => Ok(GcpDatabaseInstanceType::DB_CUSTOM_1_3840),
+            "db-custom-2-7680" => Ok(GcpDatabaseInstanceType::DB_CUSTOM_2_7680),
+            "db-custom-4-15360" => Ok(GcpDatabaseInstanceType::DB_CUSTOM_4_15360),
+            "db-custom-8-30720" => Ok(GcpDatabaseInstanceType::DB_CUSTOM_8_30720),
+            "db-custom-16-61440" => Ok(GcpDatabaseInstanceType::DB_CUSTOM_16_61440),
+            "redis-1gb" => Ok(GcpDatabaseInstanceType::REDIS_1GB),
+            "redis-2gb" => Ok(GcpDatabaseInstanceType::REDIS_2GB),
+            "redis-4gb" => Ok(GcpDatabaseInstanceType::REDIS_4GB),
+            "redis-8gb" => Ok(GcpDatabaseInstanceType::REDIS_8GB),
+            "redis-16gb" => Ok(GcpDatabaseInstanceType::REDIS_16GB),
+            _ => Err(DatabaseError::InvalidDatabaseInstance {
+                database_cloud_provider: Kind::Gcp,
+                requested_database_instance_type: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[rustfmt::skip]
+mod tests {
+    use crate::cloud_provider::gcp::database_instance_type::GcpDatabaseInstanceType;
+    use crate::cloud_provider::Kind;
+    use crate::models::database::{DatabaseError, DatabaseInstanceType};
+    use std::str::FromStr;
+    use strum::IntoEnumIterator;
+    use crate::cloud_provider::service::DatabaseType;
+
+    #[test]
+    fn test_gcp_database_instance_type_cloud_provider_kind() {
+        for instance_type in GcpDatabaseInstanceType::iter() {
+            // execute & verify:
+            assert_eq!(Kind::Gcp, instance_type.cloud_provider())
+        }
+    }
+
+    #[test]
+    fn test_gcp_database_instance_type_from_str() {
+        for instance_type in GcpDatabaseInstanceType::iter() {
+            // execute & verify:
+            // proper string: e.q `db-custom-1-3840`
+            assert_eq!(
+                Ok(instance_type.clone()),
+                GcpDatabaseInstanceType::from_str(&instance_type.to_cloud_provider_format())
+            );
+            // string with several casing: e.q `DB-CUSTOM-1-3840`
+            assert_eq!(
+                Ok(instance_type.clone()),
+                GcpDatabaseInstanceType::from_str(instance_type.to_cloud_provider_format().to_uppercase().as_str())
+            );
+            // string with leading and trailing spaces: e.q ` db-custom-1-3840   `
+            assert_eq!(
+                Ok(instance_type.clone()),
+                GcpDatabaseInstanceType::from_str(
+                    format!("  {}   ", &instance_type.to_cloud_provider_format()).as_str()
+                )
+            );
+        }
+
+        // unknown instance type
+        assert_eq!(
+            Err(DatabaseError::InvalidDatabaseInstance {
+                database_cloud_provider: Kind::Gcp,
+                requested_database_instance_type: "db-t3.micro".to_string(),
+            }),
+            GcpDatabaseInstanceType::from_str("db-t3.micro")
+        );
+    }
+
+    #[test]
+    fn test_gcp_database_instance_type_is_instance_compatible_with() {
+        for db_type in DatabaseType::iter() {
+            for instance_type in GcpDatabaseInstanceType::iter() {
+                // execute & verify:
+                assert_eq!(
+                    match db_type {
+                        DatabaseType::PostgreSQL | DatabaseType::MySQL => instance_type.redis_memory_size_in_gib().is_none(),
+                        DatabaseType::Redis => instance_type.redis_memory_size_in_gib().is_some(),
+                        DatabaseType::MongoDB => false,
+                    },
+                    instance_type.is_instance_compatible_with(db_type),
+                )
+            }
+        }
+    }
+}
@@ -6,10 +6,12 @@ use crate::cloud_provider::helm::{
 };
 use crate::cloud_provider::helm_charts::cert_manager_chart::CertManagerChart;
 use crate::cloud_provider::helm_charts::cert_manager_config_chart::CertManagerConfigsChart;
+use crate::cloud_provider::helm_charts::envoy_gateway_chart::EnvoyGatewayChart;
 use crate::cloud_provider::helm_charts::external_dns_chart::ExternalDNSChart;
 use crate::cloud_provider::helm_charts::nginx_ingress_chart::NginxIngressChart;
 use crate::cloud_provider::helm_charts::qovery_cert_manager_webhook_chart::QoveryCertManagerWebhookChart;
 use crate::cloud_provider::helm_charts::qovery_cluster_agent_chart::QoveryClusterAgentChart;
+use crate::cloud_provider::helm_charts::qovery_gateway_chart::QoveryGatewayChart;
 use crate::cloud_provider::helm_charts::qovery_shell_agent_chart::QoveryShellAgentChart;
 use crate::cloud_provider::helm_charts::qovery_storage_class_chart::{QoveryStorageClassChart, QoveryStorageType};
 use crate::cloud_provider::helm_charts::{HelmChartResources, HelmChartResourcesConstraintType, ToCommonHelmChart};
@@ -24,10 +26,11 @@ use crate::engine_task::qovery_api::{EngineServiceType, QoveryApi};
 use crate::errors::CommandError;
 use crate::io_models::engine_request::{ChartValuesOverrideName, ChartValuesOverrideValues};
 use crate::io_models::QoveryIdentifier;
+use crate::models::domain::Domain;
 use crate::models::gcp::JsonCredentials;
 use crate::models::third_parties::LetsEncryptConfig;
 use crate::models::ToCloudProviderFormat;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::path::Path;
 use std::sync::Arc;
@@ -175,6 +178,7 @@ pub fn gcp_helm_charts(
         UpdateStrategy::RollingUpdate,
         false,
         HelmChartNamespaces::Qovery,
+        chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled,
     )
     .to_common_helm_chart()?;
 
@@ -216,6 +220,7 @@ pub fn gcp_helm_charts(
         false,
         HelmChartNamespaces::Qovery,
         HelmChartNamespaces::Qovery, // Leader election defaults to kube-system which is not permitted on GKE autopilot
+        chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled,
     )
     .to_common_helm_chart()?;
 
@@ -226,6 +231,10 @@ pub fn gcp_helm_charts(
         &chart_config_prerequisites.dns_provider_config,
         chart_config_prerequisites.managed_dns_helm_format.to_string(),
         HelmChartNamespaces::Qovery,
+        &chart_config_prerequisites
+            .cluster_advanced_settings
+            .cert_manager_acme_issuers,
+        chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled,
     )
     .to_common_helm_chart()?;
 
@@ -245,6 +254,37 @@ pub fn gcp_helm_charts(
         ));
     }
 
+    // Gateway API, installed next to nginx which keeps serving existing ingresses
+    let envoy_gateway: Option<Box<dyn HelmChart>> =
+        match chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled {
+            false => None,
+            true => Some(Box::new(
+                EnvoyGatewayChart::new(
+                    chart_prefix_path,
+                    HelmChartResourcesConstraintType::ChartDefault,
+                    get_chart_overrride_fn.clone(),
+                    HelmChartNamespaces::EnvoyGateway,
+                )
+                .to_common_helm_chart()?,
+            )),
+        };
+    let qovery_gateway: Option<Box<dyn HelmChart>> =
+        match chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled {
+            false => None,
+            true => Some(Box::new(
+                QoveryGatewayChart::new(
+                    chart_prefix_path,
+                    Domain::new(chart_config_prerequisites.managed_dns_name.to_string())
+                        .wildcarded()
+                        .to_string(),
+                    HelmChartNamespaces::Qovery,
+                    BTreeMap::new(),
+                    HelmChartNamespaces::EnvoyGateway,
+                )
+                .to_common_helm_chart()?,
+            )),
+        };
+
     // Nginx ingress
     let nginx_ingress = NginxIngressChart::new(
         chart_prefix_path,
@@ -438,11 +478,12 @@ pub fn gcp_helm_charts(
     // chart deployment order matters!!!
     // Helm chart deployment order
     let level_1: Vec<Option<Box<dyn HelmChart>>> = vec![Some(Box::new(q_storage_class_chart))];
-    let level_2: Vec<Option<Box<dyn HelmChart>>> = vec![];
+    // gateway API CRDs must exist before cert-manager starts watching gateways
+    let level_2: Vec<Option<Box<dyn HelmChart>>> = vec![envoy_gateway];
     let level_3: Vec<Option<Box<dyn HelmChart>>> = vec![Some(Box::new(cert_manager))];
     let level_4: Vec<Option<Box<dyn HelmChart>>> = vec![qovery_cert_manager_webhook];
     let level_5: Vec<Option<Box<dyn HelmChart>>> = vec![Some(Box::new(external_dns_chart))];
-    let level_6: Vec<Option<Box<dyn HelmChart>>> = vec![Some(Box::new(nginx_ingress))];
+    let level_6: Vec<Option<Box<dyn HelmChart>>> = vec![Some(Box::new(nginx_ingress)), qovery_gateway];
     let level_7: Vec<Option<Box<dyn HelmChart>>> = vec![
         Some(Box::new(cert_manager_config)),
         Some(Box::new(qovery_cluster_agent)),
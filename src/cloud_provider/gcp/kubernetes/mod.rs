@@ -400,6 +400,7 @@ impl Gke {
             &managed_dns_resolvers_terraform_format,
         );
         context.insert("wildcard_managed_dns", &self.dns_provider().domain().wildcarded().to_string());
+        context.insert("gateway_api_enabled", &self.advanced_settings().gateway_api_enabled);
 
         // add specific DNS fields
         self.dns_provider().insert_into_teracontext(&mut context);
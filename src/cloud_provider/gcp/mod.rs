@@ -1,3 +1,4 @@
+pub mod database_instance_type;
 pub mod kubernetes;
 pub mod locations;
 
@@ -62,6 +62,7 @@ pub enum HelmChartNamespaces {
     Logging,
     CertManager,
     NginxIngress,
+    EnvoyGateway,
     Qovery,
     Custom,
 }
@@ -75,6 +76,7 @@ impl Display for HelmChartNamespaces {
             HelmChartNamespaces::Logging => "logging",
             HelmChartNamespaces::CertManager => "cert-manager",
             HelmChartNamespaces::NginxIngress => "nginx-ingress",
+            HelmChartNamespaces::EnvoyGateway => "envoy-gateway-system",
             HelmChartNamespaces::Qovery => "qovery",
         };
 
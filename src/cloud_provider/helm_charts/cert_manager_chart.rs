@@ -28,6 +28,7 @@ pub struct CertManagerChart {
     enable_vpa: bool,
     namespace: HelmChartNamespaces,
     leader_election_namespace: HelmChartNamespaces,
+    gateway_api_enabled: bool,
 }
 
 impl CertManagerChart {
@@ -42,6 +43,7 @@ impl CertManagerChart {
         enable_vpa: bool,
         namespace: HelmChartNamespaces,
         leader_election_namespace: HelmChartNamespaces,
+        gateway_api_enabled: bool,
     ) -> CertManagerChart {
         CertManagerChart {
             chart_prefix_path: chart_prefix_path.map(|s| s.to_string()),
@@ -88,6 +90,7 @@ impl CertManagerChart {
             enable_vpa,
             namespace,
             leader_election_namespace,
+            gateway_api_enabled,
         }
     }
 
@@ -178,6 +181,15 @@ impl ToCommonHelmChart for CertManagerChart {
                         key: "cainjector.resources.requests.memory".to_string(),
                         value: self.ca_injector_resources.request_memory.to_string(),
                     },
+                    // issue certificates of the gateway listeners annotated with a cluster issuer
+                    // https://cert-manager.io/docs/usage/gateway/
+                    ChartSetValue {
+                        key: "featureGates".to_string(),
+                        value: match self.gateway_api_enabled {
+                            true => "ExperimentalGatewayAPISupport=true".to_string(),
+                            false => "".to_string(),
+                        },
+                    },
                 ],
                 yaml_files_content: match self.customer_helm_chart_override.clone() {
                     Some(x) => vec![x.to_chart_values_generated()],
@@ -316,6 +328,7 @@ mod tests {
             false,
             HelmChartNamespaces::CertManager,
             HelmChartNamespaces::KubeSystem,
+            false,
         );
 
         let current_directory = env::current_dir().expect("Impossible to get current directory");
@@ -350,6 +363,7 @@ mod tests {
             false,
             HelmChartNamespaces::CertManager,
             HelmChartNamespaces::KubeSystem,
+            false,
         );
 
         let current_directory = env::current_dir().expect("Impossible to get current directory");
@@ -388,6 +402,7 @@ mod tests {
             false,
             HelmChartNamespaces::CertManager,
             HelmChartNamespaces::KubeSystem,
+            false,
         );
         let common_chart = chart.to_common_helm_chart().unwrap();
 
@@ -1,13 +1,45 @@
 use crate::cloud_provider::helm::{
-    ChartInfo, ChartInstallationChecker, ChartSetValue, CommonChart, HelmChartError, HelmChartNamespaces,
+    ChartInfo, ChartInstallationChecker, ChartSetValue, ChartValuesGenerated, CommonChart, HelmChartError,
+    HelmChartNamespaces,
 };
+use crate::cloud_provider::helm_charts::qovery_gateway_chart::QOVERY_GATEWAY_NAME;
 use crate::cloud_provider::helm_charts::{
     HelmChartDirectoryLocation, HelmChartPath, HelmChartValuesFilePath, ToCommonHelmChart,
 };
+use crate::cloud_provider::io::AcmeIssuer;
 use crate::dns_provider::DnsProviderConfiguration;
 use crate::errors::CommandError;
 use crate::models::third_parties::LetsEncryptConfig;
 use kube::Client;
+use serde::Serialize;
+
+// Name of the cluster issuer backed by Let's Encrypt, it can't be used by a custom ACME issuer
+const LETS_ENCRYPT_ISSUER_NAME: &str = "letsencrypt-qovery";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AcmeIssuerValues<'a> {
+    name: &'a str,
+    server: &'a str,
+    email: &'a str,
+    eab_key_id: Option<&'a str>,
+    eab_hmac_key: Option<&'a str>,
+    ca_bundle: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct GatewayParentValues {
+    name: String,
+    namespace: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CertManagerConfigsValues<'a> {
+    acme_issuers: Vec<AcmeIssuerValues<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gateway_http01_parent: Option<GatewayParentValues>,
+}
 
 pub struct CertManagerConfigsChart<'a> {
     chart_path: HelmChartPath,
@@ -16,6 +48,8 @@ pub struct CertManagerConfigsChart<'a> {
     dns_provider_configuration: &'a DnsProviderConfiguration,
     managed_dns_helm_format: String,
     namespace: HelmChartNamespaces,
+    acme_issuers: &'a [AcmeIssuer],
+    gateway_api_enabled: bool,
 }
 
 impl<'a> CertManagerConfigsChart<'a> {
@@ -25,6 +59,8 @@ impl<'a> CertManagerConfigsChart<'a> {
         dns_provider_configuration: &'a DnsProviderConfiguration,
         managed_dns_helm_format: String,
         namespace: HelmChartNamespaces,
+        acme_issuers: &'a [AcmeIssuer],
+        gateway_api_enabled: bool,
     ) -> Self {
         CertManagerConfigsChart {
             chart_path: HelmChartPath::new(
@@ -41,12 +77,53 @@ impl<'a> CertManagerConfigsChart<'a> {
             dns_provider_configuration,
             managed_dns_helm_format,
             namespace,
+            acme_issuers,
+            gateway_api_enabled,
         }
     }
 
     pub fn chart_name() -> String {
         "cert-manager-configs".to_string()
     }
+
+    fn values_yaml(&self) -> Result<String, HelmChartError> {
+        let mut acme_issuers = Vec::with_capacity(self.acme_issuers.len());
+        for issuer in self.acme_issuers {
+            if issuer.name == LETS_ENCRYPT_ISSUER_NAME {
+                return Err(HelmChartError::CreateTemplateError {
+                    chart_name: CertManagerConfigsChart::chart_name(),
+                    msg: format!("ACME issuer name `{LETS_ENCRYPT_ISSUER_NAME}` is reserved"),
+                });
+            }
+
+            acme_issuers.push(AcmeIssuerValues {
+                name: &issuer.name,
+                server: issuer.server.as_str(),
+                email: &issuer.email,
+                eab_key_id: issuer.eab_key_id.as_deref(),
+                eab_hmac_key: issuer.eab_hmac_key.as_deref(),
+                ca_bundle: issuer.ca_bundle.as_deref(),
+            });
+        }
+
+        // HTTP-01 challenges are answered through the cluster gateway, as nginx doesn't serve custom domains anymore
+        let gateway_http01_parent = match self.gateway_api_enabled {
+            true => Some(GatewayParentValues {
+                name: QOVERY_GATEWAY_NAME.to_string(),
+                namespace: HelmChartNamespaces::EnvoyGateway.to_string(),
+            }),
+            false => None,
+        };
+
+        serde_yaml::to_string(&CertManagerConfigsValues {
+            acme_issuers,
+            gateway_http01_parent,
+        })
+        .map_err(|e| HelmChartError::CreateTemplateError {
+            chart_name: CertManagerConfigsChart::chart_name(),
+            msg: e.to_string(),
+        })
+    }
 }
 
 impl ToCommonHelmChart for CertManagerConfigsChart<'_> {
@@ -59,6 +136,10 @@ impl ToCommonHelmChart for CertManagerConfigsChart<'_> {
                 // TODO: fix backup apply, it makes the chart deployment failed randomly
                 // backup_resources: Some(vec!["cert".to_string(), "issuer".to_string(), "clusterissuer".to_string()]),
                 values_files: vec![self.chart_values_path.to_string()],
+                yaml_files_content: vec![ChartValuesGenerated {
+                    filename: "cert-manager-configs_acme_issuers.yaml".to_string(),
+                    yaml_content: self.values_yaml()?,
+                }],
                 values: vec![
                     ChartSetValue {
                         key: "namespace".to_string(),
@@ -88,7 +169,7 @@ impl ToCommonHelmChart for CertManagerConfigsChart<'_> {
                             DnsProviderConfiguration::Cloudflare(cloudflare_config) => {
                                 cloudflare_config.cloudflare_api_token.to_string()
                             }
-                            DnsProviderConfiguration::QoveryDns(_) => "not-set".to_string(),
+                            _ => "not-set".to_string(),
                         },
                     },
                     ChartSetValue {
@@ -97,7 +178,7 @@ impl ToCommonHelmChart for CertManagerConfigsChart<'_> {
                             DnsProviderConfiguration::Cloudflare(cloudflare_config) => {
                                 cloudflare_config.cloudflare_email.to_string()
                             }
-                            DnsProviderConfiguration::QoveryDns(_) => "not-set".to_string(),
+                            _ => "not-set".to_string(),
                         },
                     },
                     // Qovery DNS
@@ -126,7 +207,7 @@ impl ToCommonHelmChart for CertManagerConfigsChart<'_> {
                                 // }
                                 format!("\"{}\"", qovery_dns_config.api_url_port)
                             }
-                            DnsProviderConfiguration::Cloudflare(_) => "no-set".to_string(),
+                            _ => "no-set".to_string(),
                         },
                     },
                     ChartSetValue {
@@ -135,7 +216,7 @@ impl ToCommonHelmChart for CertManagerConfigsChart<'_> {
                             DnsProviderConfiguration::QoveryDns(qovery_dns_config) => {
                                 qovery_dns_config.api_url_scheme_and_domain.to_string()
                             }
-                            DnsProviderConfiguration::Cloudflare(_) => "not-set".to_string(),
+                            _ => "not-set".to_string(),
                         },
                     },
                     ChartSetValue {
@@ -144,7 +225,62 @@ impl ToCommonHelmChart for CertManagerConfigsChart<'_> {
                             DnsProviderConfiguration::QoveryDns(qovery_dns_config) => {
                                 qovery_dns_config.api_key.to_string()
                             }
-                            DnsProviderConfiguration::Cloudflare(_) => "not-set".to_string(),
+                            _ => "not-set".to_string(),
+                        },
+                    },
+                    // Route53
+                    ChartSetValue {
+                        key: "provider.route53.accessKeyId".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Route53(config) => config.access_key_id.to_string(),
+                            _ => "not-set".to_string(),
+                        },
+                    },
+                    ChartSetValue {
+                        key: "provider.route53.secretAccessKey".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Route53(config) => config.secret_access_key.to_string(),
+                            _ => "not-set".to_string(),
+                        },
+                    },
+                    ChartSetValue {
+                        key: "provider.route53.region".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Route53(config) => config.region.to_string(),
+                            _ => "not-set".to_string(),
+                        },
+                    },
+                    // RFC2136
+                    ChartSetValue {
+                        key: "provider.rfc2136.nameserver".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Rfc2136(config) => {
+                                format!("{}:{}", config.nameserver, config.port)
+                            }
+                            _ => "not-set".to_string(),
+                        },
+                    },
+                    ChartSetValue {
+                        key: "provider.rfc2136.tsigKeyName".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Rfc2136(config) => config.tsig_key_name.to_string(),
+                            _ => "not-set".to_string(),
+                        },
+                    },
+                    ChartSetValue {
+                        key: "provider.rfc2136.tsigAlgorithm".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Rfc2136(config) => {
+                                config.tsig_algorithm.to_cert_manager_format().to_string()
+                            }
+                            _ => "not-set".to_string(),
+                        },
+                    },
+                    ChartSetValue {
+                        key: "provider.rfc2136.tsigSecret".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Rfc2136(config) => config.tsig_secret.to_string(),
+                            _ => "not-set".to_string(),
                         },
                     },
                 ],
@@ -190,6 +326,7 @@ mod tests {
         get_helm_path_kubernetes_provider_sub_folder_name, get_helm_values_set_in_code_but_absent_in_values_file,
         HelmChartType, ToCommonHelmChart,
     };
+    use crate::cloud_provider::io::AcmeIssuer;
     use crate::dns_provider::qoverydns::QoveryDnsConfig;
     use crate::dns_provider::DnsProviderConfiguration;
     use std::env;
@@ -212,6 +349,8 @@ mod tests {
             &dns_provider_config,
             "whatever".to_string(),
             HelmChartNamespaces::CertManager,
+            &[],
+            false,
         );
 
         let current_directory = env::current_dir().expect("Impossible to get current directory");
@@ -248,6 +387,8 @@ mod tests {
             &dns_provider_config,
             "whatever".to_string(),
             HelmChartNamespaces::CertManager,
+            &[],
+            false,
         );
 
         let current_directory = env::current_dir().expect("Impossible to get current directory");
@@ -288,6 +429,8 @@ mod tests {
             &dns_provider_config,
             "whatever".to_string(),
             HelmChartNamespaces::CertManager,
+            &[],
+            false,
         );
         let common_chart = chart.to_common_helm_chart().unwrap();
 
@@ -307,4 +450,99 @@ mod tests {
         // verify:
         assert!(missing_fields.is_none(), "Some fields are missing in values file, add those (make sure they still exist in chart values), fields: {}", missing_fields.unwrap_or_default().join(","));
     }
+
+    #[test]
+    fn cert_manager_configs_chart_acme_issuers_test() {
+        // setup:
+        let lets_encrypt_config = LetsEncryptConfig::new("whatever".to_string(), true);
+        let dns_provider_config = DnsProviderConfiguration::QoveryDns(QoveryDnsConfig {
+            api_key: "whatever".to_string(),
+            api_url: Url::parse("https://whatever.com").expect("Unable to parse URL"),
+            api_url_port: "whatever".to_string(),
+            api_url_scheme_and_domain: "whatever".to_string(),
+        });
+        let mut acme_issuers = vec![AcmeIssuer {
+            name: "zerossl".to_string(),
+            server: Url::parse("https://acme.zerossl.com/v2/DV90").expect("Unable to parse URL"),
+            email: "ops@example.com".to_string(),
+            eab_key_id: Some("key-id".to_string()),
+            eab_hmac_key: Some("hmac-key".to_string()),
+            ca_bundle: None,
+        }];
+
+        // execute:
+        let common_chart = CertManagerConfigsChart::new(
+            None,
+            &lets_encrypt_config,
+            &dns_provider_config,
+            "whatever".to_string(),
+            HelmChartNamespaces::CertManager,
+            &acme_issuers,
+            false,
+        )
+        .to_common_helm_chart()
+        .unwrap();
+
+        // verify:
+        assert_eq!(
+            common_chart.chart_info.yaml_files_content[0].yaml_content,
+            r#"acmeIssuers:
+- name: zerossl
+  server: https://acme.zerossl.com/v2/DV90
+  email: ops@example.com
+  eabKeyId: key-id
+  eabHmacKey: hmac-key
+  caBundle: null
+"#
+        );
+
+        // execute & verify:
+        acme_issuers[0].name = "letsencrypt-qovery".to_string();
+        assert!(CertManagerConfigsChart::new(
+            None,
+            &lets_encrypt_config,
+            &dns_provider_config,
+            "whatever".to_string(),
+            HelmChartNamespaces::CertManager,
+            &acme_issuers,
+            false,
+        )
+        .to_common_helm_chart()
+        .is_err());
+    }
+
+    #[test]
+    fn cert_manager_configs_chart_gateway_http01_parent_test() {
+        // setup:
+        let lets_encrypt_config = LetsEncryptConfig::new("whatever".to_string(), true);
+        let dns_provider_config = DnsProviderConfiguration::QoveryDns(QoveryDnsConfig {
+            api_key: "whatever".to_string(),
+            api_url: Url::parse("https://whatever.com").expect("Unable to parse URL"),
+            api_url_port: "whatever".to_string(),
+            api_url_scheme_and_domain: "whatever".to_string(),
+        });
+
+        // execute:
+        let common_chart = CertManagerConfigsChart::new(
+            None,
+            &lets_encrypt_config,
+            &dns_provider_config,
+            "whatever".to_string(),
+            HelmChartNamespaces::CertManager,
+            &[],
+            true,
+        )
+        .to_common_helm_chart()
+        .unwrap();
+
+        // verify:
+        assert_eq!(
+            common_chart.chart_info.yaml_files_content[0].yaml_content,
+            r#"acmeIssuers: []
+gatewayHttp01Parent:
+  name: qovery
+  namespace: envoy-gateway-system
+"#
+        );
+    }
 }
@@ -0,0 +1,238 @@
+use std::sync::Arc;
+
+use crate::cloud_provider::helm::{
+    ChartInfo, ChartInstallationChecker, ChartSetValue, CommonChart, HelmChartError, HelmChartNamespaces,
+};
+use crate::cloud_provider::helm_charts::{
+    HelmChartDirectoryLocation, HelmChartPath, HelmChartResources, HelmChartResourcesConstraintType,
+    HelmChartValuesFilePath, ToCommonHelmChart,
+};
+use crate::cloud_provider::models::{
+    CustomerHelmChartsOverride, KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit,
+};
+use crate::errors::CommandError;
+use kube::Client;
+
+/// Envoy Gateway controller, implementing the Gateway API when it replaces the nginx ingress controller.
+/// It also installs the Gateway API CRDs, so it has to be deployed before cert-manager watches them.
+pub struct EnvoyGatewayChart {
+    chart_path: HelmChartPath,
+    chart_values_path: HelmChartValuesFilePath,
+    chart_resources: HelmChartResources,
+    customer_helm_chart_override: Option<CustomerHelmChartsOverride>,
+    namespace: HelmChartNamespaces,
+}
+
+impl EnvoyGatewayChart {
+    pub fn new(
+        chart_prefix_path: Option<&str>,
+        chart_resources: HelmChartResourcesConstraintType,
+        customer_helm_chart_fn: Arc<dyn Fn(String) -> Option<CustomerHelmChartsOverride>>,
+        namespace: HelmChartNamespaces,
+    ) -> Self {
+        EnvoyGatewayChart {
+            chart_path: HelmChartPath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                EnvoyGatewayChart::chart_name(),
+            ),
+            chart_values_path: HelmChartValuesFilePath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                EnvoyGatewayChart::chart_name(),
+            ),
+            chart_resources: match chart_resources {
+                HelmChartResourcesConstraintType::ChartDefault => HelmChartResources {
+                    request_cpu: KubernetesCpuResourceUnit::MilliCpu(100),
+                    request_memory: KubernetesMemoryResourceUnit::MebiByte(256),
+                    limit_cpu: KubernetesCpuResourceUnit::MilliCpu(500),
+                    limit_memory: KubernetesMemoryResourceUnit::MebiByte(1024),
+                },
+                HelmChartResourcesConstraintType::Constrained(r) => r,
+            },
+            customer_helm_chart_override: customer_helm_chart_fn(Self::chart_name()),
+            namespace,
+        }
+    }
+
+    pub fn chart_name() -> String {
+        "envoy-gateway".to_string()
+    }
+}
+
+impl ToCommonHelmChart for EnvoyGatewayChart {
+    fn to_common_helm_chart(&self) -> Result<CommonChart, HelmChartError> {
+        Ok(CommonChart {
+            chart_info: ChartInfo {
+                name: EnvoyGatewayChart::chart_name(),
+                path: self.chart_path.to_string(),
+                namespace: self.namespace,
+                values_files: vec![self.chart_values_path.to_string()],
+                values: vec![
+                    ChartSetValue {
+                        key: "deployment.envoyGateway.resources.limits.cpu".to_string(),
+                        value: self.chart_resources.limit_cpu.to_string(),
+                    },
+                    ChartSetValue {
+                        key: "deployment.envoyGateway.resources.limits.memory".to_string(),
+                        value: self.chart_resources.limit_memory.to_string(),
+                    },
+                    ChartSetValue {
+                        key: "deployment.envoyGateway.resources.requests.cpu".to_string(),
+                        value: self.chart_resources.request_cpu.to_string(),
+                    },
+                    ChartSetValue {
+                        key: "deployment.envoyGateway.resources.requests.memory".to_string(),
+                        value: self.chart_resources.request_memory.to_string(),
+                    },
+                ],
+                yaml_files_content: match &self.customer_helm_chart_override {
+                    Some(customer_helm_chart_override) => {
+                        vec![customer_helm_chart_override.to_chart_values_generated()]
+                    }
+                    None => vec![],
+                },
+                ..Default::default()
+            },
+            chart_installation_checker: Some(Box::new(EnvoyGatewayChartChecker::new())),
+            vertical_pod_autoscaler: None,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct EnvoyGatewayChartChecker {}
+
+impl EnvoyGatewayChartChecker {
+    pub fn new() -> EnvoyGatewayChartChecker {
+        EnvoyGatewayChartChecker {}
+    }
+}
+
+impl Default for EnvoyGatewayChartChecker {
+    fn default() -> Self {
+        EnvoyGatewayChartChecker::new()
+    }
+}
+
+impl ChartInstallationChecker for EnvoyGatewayChartChecker {
+    fn verify_installation(&self, _kube_client: &Client) -> Result<(), CommandError> {
+        // TODO(ENG-1370): Implement chart install verification
+        Ok(())
+    }
+
+    fn clone_dyn(&self) -> Box<dyn ChartInstallationChecker> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::helm::HelmChartNamespaces;
+    use crate::cloud_provider::helm_charts::envoy_gateway_chart::EnvoyGatewayChart;
+    use crate::cloud_provider::helm_charts::{
+        get_helm_path_kubernetes_provider_sub_folder_name, get_helm_values_set_in_code_but_absent_in_values_file,
+        HelmChartResourcesConstraintType, HelmChartType, ToCommonHelmChart,
+    };
+    use crate::cloud_provider::models::CustomerHelmChartsOverride;
+    use std::env;
+    use std::sync::Arc;
+
+    fn get_envoy_gateway_chart_override() -> Arc<dyn Fn(String) -> Option<CustomerHelmChartsOverride>> {
+        Arc::new(|_chart_name: String| -> Option<CustomerHelmChartsOverride> {
+            Some(CustomerHelmChartsOverride {
+                chart_name: EnvoyGatewayChart::chart_name(),
+                chart_values: "".to_string(),
+            })
+        })
+    }
+
+    /// Makes sure chart directory containing all YAML files exists.
+    #[test]
+    fn envoy_gateway_chart_directory_exists_test() {
+        // setup:
+        let chart = EnvoyGatewayChart::new(
+            None,
+            HelmChartResourcesConstraintType::ChartDefault,
+            get_envoy_gateway_chart_override(),
+            HelmChartNamespaces::EnvoyGateway,
+        );
+
+        let current_directory = env::current_dir().expect("Impossible to get current directory");
+        let chart_path = format!(
+            "{}/lib/{}/bootstrap/charts/{}/Chart.yaml",
+            current_directory
+                .to_str()
+                .expect("Impossible to convert current directory to string"),
+            get_helm_path_kubernetes_provider_sub_folder_name(chart.chart_path.helm_path(), HelmChartType::Shared,),
+            EnvoyGatewayChart::chart_name(),
+        );
+
+        // execute
+        let values_file = std::fs::File::open(&chart_path);
+
+        // verify:
+        assert!(values_file.is_ok(), "Chart directory should exist: `{chart_path}`");
+    }
+
+    /// Makes sure chart values file exists.
+    #[test]
+    fn envoy_gateway_chart_values_file_exists_test() {
+        // setup:
+        let chart = EnvoyGatewayChart::new(
+            None,
+            HelmChartResourcesConstraintType::ChartDefault,
+            get_envoy_gateway_chart_override(),
+            HelmChartNamespaces::EnvoyGateway,
+        );
+
+        let current_directory = env::current_dir().expect("Impossible to get current directory");
+        let chart_values_path = format!(
+            "{}/lib/{}/bootstrap/chart_values/{}.yaml",
+            current_directory
+                .to_str()
+                .expect("Impossible to convert current directory to string"),
+            get_helm_path_kubernetes_provider_sub_folder_name(
+                chart.chart_values_path.helm_path(),
+                HelmChartType::Shared,
+            ),
+            EnvoyGatewayChart::chart_name(),
+        );
+
+        // execute
+        let values_file = std::fs::File::open(&chart_values_path);
+
+        // verify:
+        assert!(values_file.is_ok(), "Chart values file should exist: `{chart_values_path}`");
+    }
+
+    /// Make sure rust code doesn't set a value not declared inside values file.
+    /// All values should be declared / set in values file unless it needs to be injected via rust code.
+    #[test]
+    fn envoy_gateway_chart_rust_overridden_values_exists_in_values_yaml_test() {
+        // setup:
+        let chart = EnvoyGatewayChart::new(
+            None,
+            HelmChartResourcesConstraintType::ChartDefault,
+            get_envoy_gateway_chart_override(),
+            HelmChartNamespaces::EnvoyGateway,
+        );
+        let common_chart = chart.to_common_helm_chart().unwrap();
+
+        // execute:
+        let missing_fields = get_helm_values_set_in_code_but_absent_in_values_file(
+            common_chart,
+            format!(
+                "/lib/{}/bootstrap/chart_values/{}.yaml",
+                get_helm_path_kubernetes_provider_sub_folder_name(
+                    chart.chart_values_path.helm_path(),
+                    HelmChartType::Shared,
+                ),
+                EnvoyGatewayChart::chart_name()
+            ),
+        );
+
+        // verify:
+        assert!(missing_fields.is_none(), "Some fields are missing in values file, add those (make sure they still exist in chart values), fields: {}", missing_fields.unwrap_or_default().join(","));
+    }
+}
@@ -21,6 +21,7 @@ pub struct ExternalDNSChart {
     update_strategy: UpdateStrategy,
     enable_vpa: bool,
     namespace: HelmChartNamespaces,
+    gateway_api_enabled: bool,
 }
 
 impl ExternalDNSChart {
@@ -32,6 +33,7 @@ impl ExternalDNSChart {
         update_strategy: UpdateStrategy,
         enable_vpa: bool,
         namespace: HelmChartNamespaces,
+        gateway_api_enabled: bool,
     ) -> ExternalDNSChart {
         ExternalDNSChart {
             chart_prefix_path: chart_prefix_path.map(|s| s.to_string()),
@@ -51,6 +53,7 @@ impl ExternalDNSChart {
             update_strategy,
             enable_vpa,
             namespace,
+            gateway_api_enabled,
         }
     }
 
@@ -99,6 +102,14 @@ impl ToCommonHelmChart for ExternalDNSChart {
                         key: "txtPrefix".to_string(),
                         value: format!("qvy-{}-", self.cluster_id),
                     },
+                    // routes replace ingresses when the Gateway API is enabled
+                    ChartSetValue {
+                        key: "sources".to_string(),
+                        value: match self.gateway_api_enabled {
+                            true => "{service,ingress,gateway-httproute}".to_string(),
+                            false => "{service,ingress}".to_string(),
+                        },
+                    },
                     // Providers configuration
                     // Cloudflare
                     ChartSetValue {
@@ -144,6 +155,73 @@ impl ToCommonHelmChart for ExternalDNSChart {
                             _ => "".to_string(),
                         },
                     },
+                    // Route53
+                    ChartSetValue {
+                        key: "aws.credentials.accessKey".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Route53(config) => config.access_key_id.to_string(),
+                            _ => "".to_string(),
+                        },
+                    },
+                    ChartSetValue {
+                        key: "aws.credentials.secretKey".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Route53(config) => config.secret_access_key.to_string(),
+                            _ => "".to_string(),
+                        },
+                    },
+                    ChartSetValue {
+                        key: "aws.region".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Route53(config) => config.region.to_string(),
+                            _ => "".to_string(),
+                        },
+                    },
+                    // RFC2136
+                    ChartSetValue {
+                        key: "rfc2136.host".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Rfc2136(config) => config.nameserver.to_string(),
+                            _ => "".to_string(),
+                        },
+                    },
+                    ChartSetValue {
+                        key: "rfc2136.port".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Rfc2136(config) => config.port.to_string(),
+                            _ => "".to_string(),
+                        },
+                    },
+                    ChartSetValue {
+                        key: "rfc2136.zone".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Rfc2136(config) => config.zone.to_string(),
+                            _ => "".to_string(),
+                        },
+                    },
+                    ChartSetValue {
+                        key: "rfc2136.tsigKeyname".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Rfc2136(config) => config.tsig_key_name.to_string(),
+                            _ => "".to_string(),
+                        },
+                    },
+                    ChartSetValue {
+                        key: "rfc2136.tsigSecret".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Rfc2136(config) => config.tsig_secret.to_string(),
+                            _ => "".to_string(),
+                        },
+                    },
+                    ChartSetValue {
+                        key: "rfc2136.tsigSecretAlg".to_string(),
+                        value: match &self.dns_provider_configuration {
+                            DnsProviderConfiguration::Rfc2136(config) => {
+                                config.tsig_algorithm.to_external_dns_format().to_string()
+                            }
+                            _ => "".to_string(),
+                        },
+                    },
                 ],
                 ..Default::default()
             },
@@ -226,6 +304,7 @@ mod tests {
             UpdateStrategy::RollingUpdate,
             false,
             HelmChartNamespaces::KubeSystem,
+            false,
         );
 
         let current_directory = env::current_dir().expect("Impossible to get current directory");
@@ -261,6 +340,7 @@ mod tests {
             UpdateStrategy::RollingUpdate,
             false,
             HelmChartNamespaces::KubeSystem,
+            false,
         );
 
         let current_directory = env::current_dir().expect("Impossible to get current directory");
@@ -300,6 +380,7 @@ mod tests {
             UpdateStrategy::RollingUpdate,
             false,
             HelmChartNamespaces::KubeSystem,
+            false,
         );
         let common_chart = chart.to_common_helm_chart().unwrap();
 
@@ -11,6 +11,7 @@ use super::helm::HelmChartError;
 pub mod cert_manager_chart;
 pub mod cert_manager_config_chart;
 pub mod coredns_config_chart;
+pub mod envoy_gateway_chart;
 pub mod external_dns_chart;
 pub mod grafana_chart;
 pub mod kube_prometheus_stack_chart;
@@ -22,6 +23,7 @@ pub mod prometheus_adapter_chart;
 pub mod promtail_chart;
 pub mod qovery_cert_manager_webhook_chart;
 pub mod qovery_cluster_agent_chart;
+pub mod qovery_gateway_chart;
 pub mod qovery_shell_agent_chart;
 pub mod qovery_storage_class_chart;
 pub mod vertical_pod_autoscaler;
@@ -254,6 +256,7 @@ pub fn get_helm_path_kubernetes_provider_sub_folder_name(helm_path: &HelmPath, c
                 KubernetesKind::Ec2 => "aws-ec2",
                 KubernetesKind::ScwKapsule | Kind::ScwSelfManaged => "scaleway",
                 KubernetesKind::Gke | Kind::GkeSelfManaged => "gcp",
+                KubernetesKind::Aks => "azure",
             },
             true => "undefined-cloud-provider", // There is something weird
         },
@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
 use crate::cloud_provider::helm::{
-    ChartInfo, ChartInstallationChecker, ChartSetValue, CommonChart, CommonChartVpa, HelmChartError,
-    HelmChartNamespaces, VpaConfig, VpaContainerPolicy, VpaTargetRef, VpaTargetRefApiVersion, VpaTargetRefKind,
+    ChartInfo, ChartInstallationChecker, ChartSetValue, ChartValuesGenerated, CommonChart, CommonChartVpa,
+    HelmChartError, HelmChartNamespaces, VpaConfig, VpaContainerPolicy, VpaTargetRef, VpaTargetRefApiVersion,
+    VpaTargetRefKind,
 };
 use crate::cloud_provider::helm_charts::{
     HelmChartDirectoryLocation, HelmChartPath, HelmChartValuesFilePath, ToCommonHelmChart,
@@ -11,6 +12,7 @@ use crate::cloud_provider::models::{
     CustomerHelmChartsOverride, KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit,
 };
 use crate::errors::CommandError;
+use crate::models::horizontal_autoscaler::{CUSTOM_METRIC_PREFIX, REQUESTS_PER_SECOND_METRIC_NAME};
 use kube::Client;
 use semver::Version;
 
@@ -54,6 +56,39 @@ impl PrometheusAdapterChart {
     pub fn chart_name() -> String {
         "prometheus-adapter".to_string()
     }
+
+    /// Rules exposing the metrics services pod autoscalers can scale on (on top of the chart default ones):
+    /// requests per second received from nginx-ingress by each kubernetes service
+    /// and custom metrics queries recorded by services prometheus rules
+    fn autoscaler_rules_yaml() -> String {
+        format!(
+            r#"
+rules:
+  custom:
+    - seriesQuery: 'nginx_ingress_controller_requests{{exported_namespace!="",exported_service!=""}}'
+      resources:
+        overrides:
+          exported_namespace:
+            resource: namespace
+          exported_service:
+            resource: service
+      name:
+        matches: "^nginx_ingress_controller_requests$"
+        as: "{REQUESTS_PER_SECOND_METRIC_NAME}"
+      metricsQuery: 'sum(rate(<<.Series>>{{<<.LabelMatchers>>}}[2m])) by (<<.GroupBy>>)'
+  external:
+    - seriesQuery: '{{__name__=~"^{CUSTOM_METRIC_PREFIX}.*",namespace!=""}}'
+      resources:
+        overrides:
+          namespace:
+            resource: namespace
+      name:
+        matches: "^(.*)$"
+        as: "${{1}}"
+      metricsQuery: 'max(<<.Series>>{{<<.LabelMatchers>>}}) by (<<.GroupBy>>)'
+"#
+        )
+    }
 }
 
 impl ToCommonHelmChart for PrometheusAdapterChart {
@@ -69,9 +104,16 @@ impl ToCommonHelmChart for PrometheusAdapterChart {
                     key: "prometheus.url".to_string(),
                     value: self.prometheus_internal_url.clone(),
                 }],
-                yaml_files_content: match self.customer_helm_chart_override.clone() {
-                    Some(x) => vec![x.to_chart_values_generated()],
-                    None => vec![],
+                yaml_files_content: {
+                    let mut yaml_files_content = vec![ChartValuesGenerated {
+                        filename: "prometheus-adapter_generated.yaml".to_string(),
+                        yaml_content: Self::autoscaler_rules_yaml(),
+                    }];
+                    // customer override comes last so it can override generated rules
+                    if let Some(customer_override) = self.customer_helm_chart_override.clone() {
+                        yaml_files_content.push(customer_override.to_chart_values_generated());
+                    }
+                    yaml_files_content
                 },
                 ..Default::default()
             },
@@ -238,4 +280,27 @@ mod tests {
         // verify:
         assert!(missing_fields.is_none(), "Some fields are missing in values file, add those (make sure they still exist in chart values), fields: {}", missing_fields.unwrap_or_default().join(","));
     }
+
+    /// Makes sure generated autoscaler rules are valid yaml exposing the metrics services autoscalers are using.
+    #[test]
+    fn prometheus_adapter_chart_autoscaler_rules_test() {
+        // execute:
+        let rules: serde_yaml::Value = serde_yaml::from_str(&PrometheusAdapterChart::autoscaler_rules_yaml())
+            .expect("Generated rules should be valid yaml");
+
+        // verify:
+        assert_eq!(
+            rules["rules"]["custom"][0]["name"]["as"].as_str(),
+            Some("nginx_ingress_requests_per_second")
+        );
+        assert_eq!(
+            rules["rules"]["custom"][0]["seriesQuery"].as_str(),
+            Some("nginx_ingress_controller_requests{exported_namespace!=\"\",exported_service!=\"\"}")
+        );
+        assert_eq!(
+            rules["rules"]["external"][0]["seriesQuery"].as_str(),
+            Some("{__name__=~\"^qovery_hpa_.*\",namespace!=\"\"}")
+        );
+        assert_eq!(rules["rules"]["external"][0]["name"]["as"].as_str(), Some("${1}"));
+    }
 }
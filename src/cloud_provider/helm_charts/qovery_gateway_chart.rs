@@ -0,0 +1,217 @@
+use crate::cloud_provider::helm::{
+    ChartInfo, ChartInstallationChecker, ChartSetValue, ChartValuesGenerated, CommonChart, HelmChartError,
+    HelmChartNamespaces,
+};
+use crate::cloud_provider::helm_charts::{
+    HelmChartDirectoryLocation, HelmChartPath, HelmChartValuesFilePath, ToCommonHelmChart,
+};
+use crate::errors::CommandError;
+use kube::Client;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Name of the gateway class and of the cluster gateway routers attach their routes to
+pub const QOVERY_GATEWAY_NAME: &str = "qovery";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QoveryGatewayValues<'a> {
+    service_annotations: &'a BTreeMap<String, String>,
+}
+
+/// Gateway class and cluster gateway of the Gateway API, serving the managed DNS wildcard with its certificate.
+pub struct QoveryGatewayChart {
+    chart_path: HelmChartPath,
+    chart_values_path: HelmChartValuesFilePath,
+    wildcard_managed_dns: String,
+    certificate_namespace: HelmChartNamespaces,
+    service_annotations: BTreeMap<String, String>,
+    namespace: HelmChartNamespaces,
+}
+
+impl QoveryGatewayChart {
+    pub fn new(
+        chart_prefix_path: Option<&str>,
+        wildcard_managed_dns: String,
+        certificate_namespace: HelmChartNamespaces,
+        service_annotations: BTreeMap<String, String>,
+        namespace: HelmChartNamespaces,
+    ) -> Self {
+        QoveryGatewayChart {
+            chart_path: HelmChartPath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                QoveryGatewayChart::chart_name(),
+            ),
+            chart_values_path: HelmChartValuesFilePath::new(
+                chart_prefix_path,
+                HelmChartDirectoryLocation::CommonFolder,
+                QoveryGatewayChart::chart_name(),
+            ),
+            wildcard_managed_dns,
+            certificate_namespace,
+            service_annotations,
+            namespace,
+        }
+    }
+
+    pub fn chart_name() -> String {
+        "qovery-gateway".to_string()
+    }
+}
+
+impl ToCommonHelmChart for QoveryGatewayChart {
+    fn to_common_helm_chart(&self) -> Result<CommonChart, HelmChartError> {
+        // annotations keys contain dots, they can't be passed as set values
+        let service_annotations = serde_yaml::to_string(&QoveryGatewayValues {
+            service_annotations: &self.service_annotations,
+        })
+        .map_err(|e| HelmChartError::CreateTemplateError {
+            chart_name: QoveryGatewayChart::chart_name(),
+            msg: e.to_string(),
+        })?;
+
+        Ok(CommonChart {
+            chart_info: ChartInfo {
+                name: QoveryGatewayChart::chart_name(),
+                path: self.chart_path.to_string(),
+                namespace: self.namespace,
+                // load balancer can take some time to be provisioned
+                timeout_in_seconds: 300,
+                values_files: vec![self.chart_values_path.to_string()],
+                values: vec![
+                    ChartSetValue {
+                        key: "gatewayName".to_string(),
+                        value: QOVERY_GATEWAY_NAME.to_string(),
+                    },
+                    ChartSetValue {
+                        key: "wildcardDomain".to_string(),
+                        value: self.wildcard_managed_dns.to_string(),
+                    },
+                    ChartSetValue {
+                        key: "certificate.namespace".to_string(),
+                        value: self.certificate_namespace.to_string(),
+                    },
+                ],
+                yaml_files_content: vec![ChartValuesGenerated::new(
+                    "qovery_gateway_service_annotations".to_string(),
+                    service_annotations,
+                )],
+                ..Default::default()
+            },
+            chart_installation_checker: Some(Box::new(QoveryGatewayChartChecker::new())),
+            vertical_pod_autoscaler: None,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct QoveryGatewayChartChecker {}
+
+impl QoveryGatewayChartChecker {
+    pub fn new() -> QoveryGatewayChartChecker {
+        QoveryGatewayChartChecker {}
+    }
+}
+
+impl Default for QoveryGatewayChartChecker {
+    fn default() -> Self {
+        QoveryGatewayChartChecker::new()
+    }
+}
+
+impl ChartInstallationChecker for QoveryGatewayChartChecker {
+    fn verify_installation(&self, _kube_client: &Client) -> Result<(), CommandError> {
+        // TODO(ENG-1370): Implement chart install verification
+        Ok(())
+    }
+
+    fn clone_dyn(&self) -> Box<dyn ChartInstallationChecker> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::helm::HelmChartNamespaces;
+    use crate::cloud_provider::helm_charts::qovery_gateway_chart::QoveryGatewayChart;
+    use crate::cloud_provider::helm_charts::{
+        get_helm_path_kubernetes_provider_sub_folder_name, get_helm_values_set_in_code_but_absent_in_values_file,
+        HelmChartType, ToCommonHelmChart,
+    };
+    use std::collections::BTreeMap;
+    use std::env;
+
+    fn qovery_gateway_chart() -> QoveryGatewayChart {
+        QoveryGatewayChart::new(
+            None,
+            "*.z1234567.qovery.io".to_string(),
+            HelmChartNamespaces::CertManager,
+            BTreeMap::from([(
+                "service.beta.kubernetes.io/aws-load-balancer-type".to_string(),
+                "nlb".to_string(),
+            )]),
+            HelmChartNamespaces::EnvoyGateway,
+        )
+    }
+
+    /// Makes sure chart directory containing all YAML files exists.
+    #[test]
+    fn qovery_gateway_chart_directory_exists_test() {
+        // setup:
+        let chart = qovery_gateway_chart();
+
+        let current_directory = env::current_dir().expect("Impossible to get current directory");
+        let chart_path = format!(
+            "{}/lib/{}/bootstrap/charts/{}/Chart.yaml",
+            current_directory
+                .to_str()
+                .expect("Impossible to convert current directory to string"),
+            get_helm_path_kubernetes_provider_sub_folder_name(chart.chart_path.helm_path(), HelmChartType::Shared),
+            QoveryGatewayChart::chart_name(),
+        );
+
+        // execute
+        let values_file = std::fs::File::open(&chart_path);
+
+        // verify:
+        assert!(values_file.is_ok(), "Chart directory should exist: `{chart_path}`");
+    }
+
+    /// Make sure rust code doesn't set a value not declared inside values file.
+    /// All values should be declared / set in values file unless it needs to be injected via rust code.
+    #[test]
+    fn qovery_gateway_chart_rust_overridden_values_exists_in_values_yaml_test() {
+        // setup:
+        let chart = qovery_gateway_chart();
+        let common_chart = chart.to_common_helm_chart().unwrap();
+
+        // execute:
+        let missing_fields = get_helm_values_set_in_code_but_absent_in_values_file(
+            common_chart,
+            format!(
+                "/lib/{}/bootstrap/chart_values/{}.yaml",
+                get_helm_path_kubernetes_provider_sub_folder_name(
+                    chart.chart_values_path.helm_path(),
+                    HelmChartType::Shared,
+                ),
+                QoveryGatewayChart::chart_name()
+            ),
+        );
+
+        // verify:
+        assert!(missing_fields.is_none(), "Some fields are missing in values file, add those (make sure they still exist in chart values), fields: {}", missing_fields.unwrap_or_default().join(","));
+    }
+
+    #[test]
+    fn qovery_gateway_chart_service_annotations_test() {
+        // execute:
+        let common_chart = qovery_gateway_chart().to_common_helm_chart().unwrap();
+
+        // verify:
+        assert_eq!(
+            common_chart.chart_info.yaml_files_content[0].yaml_content,
+            "serviceAnnotations:\n  service.beta.kubernetes.io/aws-load-balancer-type: nlb\n"
+        );
+    }
+}
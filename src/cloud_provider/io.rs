@@ -1,3 +1,4 @@
+use crate::cmd::trivy::VulnerabilitySeverity;
 use crate::{cloud_provider::Kind as KindModel, errors::EngineError, events::EventDetails};
 use base64::engine::general_purpose;
 use base64::Engine;
@@ -5,6 +6,8 @@ use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::str;
 use std::time::Duration;
+use url::Url;
+use uuid::Uuid;
 
 pub const CLOUDWATCH_RETENTION_DAYS: &[u32] = &[
     0, 1, 3, 5, 7, 14, 30, 60, 90, 120, 150, 180, 365, 400, 545, 731, 1827, 2192, 2557, 2922, 3288, 3653,
@@ -21,6 +24,7 @@ pub enum Kind {
     Do,
     Scw,
     Gcp,
+    Azure,
     SelfManaged,
 }
 
@@ -30,6 +34,7 @@ impl From<KindModel> for Kind {
             KindModel::Aws => Kind::Aws,
             KindModel::Scw => Kind::Scw,
             KindModel::Gcp => Kind::Gcp,
+            KindModel::Azure => Kind::Azure,
             KindModel::SelfManaged => Kind::SelfManaged,
         }
     }
@@ -80,6 +85,9 @@ pub struct ClusterAdvancedSettings {
     pub aws_cloudwatch_eks_logs_retention_days: u32,
     #[serde(alias = "aws.eks.encrypt_secrets_kms_key_arn", default)]
     pub aws_eks_encrypt_secrets_kms_key_arn: String,
+    /// Nodes are provisioned by karpenter instead of cluster-autoscaler scaling the node groups
+    #[serde(alias = "aws.eks.karpenter.enabled")]
+    pub aws_eks_karpenter_enabled: bool,
     #[serde(alias = "cloud_provider.container_registry.tags")]
     pub cloud_provider_container_registry_tags: HashMap<String, String>,
     #[serde(alias = "database.postgresql.deny_public_access")]
@@ -116,6 +124,34 @@ pub struct ClusterAdvancedSettings {
     pub nginx_hpa_max_number_instances: u32,
     #[serde(alias = "scaleway.enable_private_network_migration")]
     pub scaleway_enable_private_network_migration: bool,
+    #[serde(alias = "image_scanning.enabled")]
+    pub image_scanning_enabled: bool,
+    #[serde(alias = "image_scanning.blocking_severity")]
+    pub image_scanning_blocking_severity: VulnerabilitySeverity,
+    #[serde(alias = "image_signing.enabled")]
+    pub image_signing_enabled: bool,
+    // public keys trusted to sign the images of a registry, by registry id
+    #[serde(alias = "registry.signature_verification_keys")]
+    pub registry_signature_verification_keys: HashMap<Uuid, Vec<String>>,
+    // ACME issuers custom domains can request their certificate from, in addition to Let's Encrypt
+    #[serde(alias = "cert_manager.acme_issuers")]
+    pub cert_manager_acme_issuers: Vec<AcmeIssuer>,
+    // route services through the Gateway API (Envoy Gateway) instead of the nginx ingress controller
+    #[serde(alias = "gateway_api.enabled")]
+    pub gateway_api_enabled: bool,
+}
+
+/// ACME server declared as a cert-manager cluster issuer, domains are validated with the HTTP-01 challenge
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AcmeIssuer {
+    pub name: String,
+    pub server: Url,
+    pub email: String,
+    // External account binding, required by some CAs to link the ACME account to an existing customer account
+    pub eab_key_id: Option<String>,
+    pub eab_hmac_key: Option<String>,
+    // PEM bundle of the CA the ACME server certificate is signed with, if not publicly trusted
+    pub ca_bundle: Option<String>,
 }
 
 impl Default for ClusterAdvancedSettings {
@@ -152,7 +188,14 @@ impl Default for ClusterAdvancedSettings {
             nginx_hpa_min_number_instances: 2,
             nginx_hpa_max_number_instances: 25,
             scaleway_enable_private_network_migration: false,
+            image_scanning_enabled: false,
+            image_scanning_blocking_severity: VulnerabilitySeverity::Critical,
+            image_signing_enabled: false,
+            registry_signature_verification_keys: HashMap::new(),
+            cert_manager_acme_issuers: vec![],
+            gateway_api_enabled: false,
             aws_eks_encrypt_secrets_kms_key_arn: "".to_string(),
+            aws_eks_karpenter_enabled: false,
         }
     }
 }
@@ -208,6 +251,7 @@ mod tests {
     use uuid::Uuid;
 
     use crate::cloud_provider::io::{ClusterAdvancedSettings, RegistryMirroringMode};
+    use crate::cmd::trivy::VulnerabilitySeverity;
     use crate::{
         cloud_provider::io::validate_aws_cloudwatch_eks_logs_retention_days,
         events::{EventDetails, Stage, Transmitter},
@@ -328,4 +372,90 @@ mod tests {
         assert_eq!(cluster_advanced_settings.nginx_hpa_min_number_instances, 2);
         assert_eq!(cluster_advanced_settings.nginx_hpa_max_number_instances, 25);
     }
+
+    #[test]
+    fn test_image_scanning_deserialization() {
+        let cluster_advanced_settings: ClusterAdvancedSettings = serde_json::from_str("{}").unwrap();
+        assert!(!cluster_advanced_settings.image_scanning_enabled);
+        assert_eq!(
+            cluster_advanced_settings.image_scanning_blocking_severity,
+            VulnerabilitySeverity::Critical
+        );
+
+        let data = r#"
+        {
+            "image_scanning.enabled": true,
+            "image_scanning.blocking_severity": "HIGH"
+        }"#;
+        let cluster_advanced_settings: ClusterAdvancedSettings = serde_json::from_str(data).unwrap();
+        assert!(cluster_advanced_settings.image_scanning_enabled);
+        assert_eq!(
+            cluster_advanced_settings.image_scanning_blocking_severity,
+            VulnerabilitySeverity::High
+        );
+    }
+
+    #[test]
+    fn test_registry_signature_verification_keys_deserialization() {
+        let data = r#"
+        {
+            "image_signing.enabled": true,
+            "registry.signature_verification_keys": {
+                "1f3c5f0e-3a0e-4e4b-9b8a-2e6f9c1d7a42": ["-----BEGIN PUBLIC KEY-----\nMFkw\n-----END PUBLIC KEY-----\n"]
+            }
+        }"#;
+        let cluster_advanced_settings: ClusterAdvancedSettings = serde_json::from_str(data).unwrap();
+        assert!(cluster_advanced_settings.image_signing_enabled);
+        assert_eq!(
+            cluster_advanced_settings
+                .registry_signature_verification_keys
+                .get(&Uuid::parse_str("1f3c5f0e-3a0e-4e4b-9b8a-2e6f9c1d7a42").unwrap()),
+            Some(&vec!["-----BEGIN PUBLIC KEY-----\nMFkw\n-----END PUBLIC KEY-----\n".to_string()])
+        );
+        assert!(ClusterAdvancedSettings::default()
+            .registry_signature_verification_keys
+            .is_empty());
+    }
+
+    #[test]
+    fn test_cert_manager_acme_issuers_deserialization() {
+        let data = r#"
+        {
+            "cert_manager.acme_issuers": [
+                {
+                    "name": "zerossl",
+                    "server": "https://acme.zerossl.com/v2/DV90",
+                    "email": "ops@example.com",
+                    "eab_key_id": "key-id",
+                    "eab_hmac_key": "hmac-key"
+                }
+            ]
+        }"#;
+        let cluster_advanced_settings: ClusterAdvancedSettings = serde_json::from_str(data).unwrap();
+        assert_eq!(
+            cluster_advanced_settings.cert_manager_acme_issuers,
+            vec![AcmeIssuer {
+                name: "zerossl".to_string(),
+                server: Url::parse("https://acme.zerossl.com/v2/DV90").unwrap(),
+                email: "ops@example.com".to_string(),
+                eab_key_id: Some("key-id".to_string()),
+                eab_hmac_key: Some("hmac-key".to_string()),
+                ca_bundle: None,
+            }]
+        );
+        assert!(ClusterAdvancedSettings::default().cert_manager_acme_issuers.is_empty());
+    }
+
+    #[test]
+    fn test_gateway_api_deserialization() {
+        let cluster_advanced_settings: ClusterAdvancedSettings = serde_json::from_str("{}").unwrap();
+        assert!(!cluster_advanced_settings.gateway_api_enabled);
+
+        let data = r#"
+        {
+            "gateway_api.enabled": true
+        }"#;
+        let cluster_advanced_settings: ClusterAdvancedSettings = serde_json::from_str(data).unwrap();
+        assert!(cluster_advanced_settings.gateway_api_enabled);
+    }
 }
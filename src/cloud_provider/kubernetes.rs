@@ -24,7 +24,7 @@ use uuid::Uuid;
 
 use crate::cloud_provider::environment::Environment;
 use crate::cloud_provider::io::ClusterAdvancedSettings;
-use crate::cloud_provider::models::{CpuArchitecture, CpuLimits, InstanceEc2, NodeGroups};
+use crate::cloud_provider::models::{CpuArchitecture, CpuLimits, InstanceEc2, NodeGroupCapacityType, NodeGroups};
 use crate::cloud_provider::service::Action;
 use crate::cloud_provider::CloudProvider;
 use crate::cloud_provider::Kind as CloudProviderKind;
@@ -877,6 +877,7 @@ pub enum Kind {
     Ec2,
     ScwKapsule,
     Gke,
+    Aks,
     EksSelfManaged,
     GkeSelfManaged,
     ScwSelfManaged,
@@ -888,6 +889,7 @@ impl Kind {
             Kind::Eks | Kind::EksSelfManaged | Kind::Ec2 => CloudProviderKind::Aws,
             Kind::ScwKapsule | Kind::ScwSelfManaged => CloudProviderKind::Scw,
             Kind::Gke | Kind::GkeSelfManaged => CloudProviderKind::Gcp,
+            Kind::Aks => CloudProviderKind::Azure,
         }
     }
 }
@@ -899,6 +901,7 @@ impl Display for Kind {
             Kind::Ec2 => "K3S",
             Kind::ScwKapsule => "ScwKapsule",
             Kind::Gke => "GKE",
+            Kind::Aks => "AKS",
             Kind::EksSelfManaged => "EKS Self Managed",
             Kind::GkeSelfManaged => "GKE Self Managed",
             Kind::ScwSelfManaged => "Scw Self Managed",
@@ -1019,6 +1022,10 @@ impl NodeGroupsWithDesiredState {
             instance_type: nodegroup.instance_type.clone(),
             disk_size_in_gib: nodegroup.disk_size_in_gib,
             instance_architecture: nodegroup.instance_architecture,
+            capacity_type: nodegroup.capacity_type,
+            fallback_instance_types: nodegroup.fallback_instance_types.clone(),
+            labels: nodegroup.labels.clone(),
+            taints: nodegroup.taints.clone(),
         }
     }
 }
@@ -1452,6 +1459,10 @@ impl NodeGroups {
             disk_size_in_gib,
             desired_nodes: None,
             instance_architecture,
+            capacity_type: NodeGroupCapacityType::OnDemand,
+            fallback_instance_types: vec![],
+            labels: BTreeMap::new(),
+            taints: vec![],
         })
     }
 
@@ -1507,7 +1518,7 @@ where
             "Infrastructure '{}' deletion is in progress...",
             kubernetes.name_with_id()
         )),
-        Action::Restart => None,
+        Action::Restart | Action::Restore => None,
     };
 
     send_progress_on_long_task_with_message(kubernetes, waiting_message, action, long_task)
@@ -1573,8 +1584,8 @@ where
                             event_message,
                         ));
                     }
-                    Action::Restart => {
-                        // restart is not implemented yet
+                    Action::Restart | Action::Restore => {
+                        // restart is not implemented yet, restore is not a cluster action
                     }
                 };
 
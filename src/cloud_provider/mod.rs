@@ -20,10 +20,12 @@ use crate::events::{EnvironmentStep, EventDetails, Stage, Transmitter};
 use crate::io_models::context::Context;
 use crate::logger::Logger;
 use crate::metrics_registry::MetricsRegistry;
+use crate::models::job_output::JobOutputs;
 use crate::runtime::block_on;
 use crate::utilities::create_kube_client;
 
 pub mod aws;
+pub mod azure;
 pub mod environment;
 pub mod gcp;
 pub mod helm;
@@ -73,6 +75,7 @@ pub enum Kind {
     Aws,
     Scw,
     Gcp,
+    Azure,
     SelfManaged,
 }
 
@@ -84,6 +87,7 @@ impl FromStr for Kind {
             "aws" | "amazon" => Ok(Kind::Aws),
             "scw" | "scaleway" => Ok(Kind::Scw),
             "gcp" | "google" => Ok(Kind::Gcp),
+            "azure" | "microsoft" => Ok(Kind::Azure),
             "self_managed" | "selfmanaged" => Ok(Kind::SelfManaged),
             _ => Err(()),
         }
@@ -96,6 +100,7 @@ impl Display for Kind {
             Kind::Aws => "AWS",
             Kind::Scw => "Scaleway",
             Kind::Gcp => "Google",
+            Kind::Azure => "Azure",
             Kind::SelfManaged => "SelfManaged",
         })
     }
@@ -134,6 +139,7 @@ pub struct DeploymentTarget<'a> {
     pub metrics_registry: Arc<dyn MetricsRegistry>,
     pub is_dry_run_deploy: bool,
     pub is_test_cluster: bool,
+    pub job_outputs: JobOutputs,
 }
 
 impl<'a> DeploymentTarget<'a> {
@@ -175,6 +181,7 @@ impl<'a> DeploymentTarget<'a> {
             logger: Arc::new(infra_ctx.kubernetes().logger().clone_dyn()),
             is_dry_run_deploy: kubernetes.context().is_dry_run_deploy(),
             is_test_cluster: kubernetes.context().is_test_cluster(),
+            job_outputs: JobOutputs::default(),
             metrics_registry: Arc::from(infra_ctx.kubernetes().metrics_registry().clone_dyn()),
         })
     }
@@ -215,6 +222,12 @@ mod tests {
             ("GCP ", Ok(Kind::Gcp)),
             ("Gcp", Ok(Kind::Gcp)),
             ("gcp_blabla", Err(())),
+            ("azure", Ok(Kind::Azure)),
+            ("microsoft", Ok(Kind::Azure)),
+            (" azure ", Ok(Kind::Azure)),
+            ("AZURE ", Ok(Kind::Azure)),
+            ("Azure", Ok(Kind::Azure)),
+            ("azure_blabla", Err(())),
         ];
 
         for tc in test_cases {
@@ -1,5 +1,7 @@
 use crate::cloud_provider::service::ServiceType;
+use derivative::Derivative;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::{self, Display, Formatter};
 use uuid::Uuid;
 
@@ -35,6 +37,7 @@ pub struct Storage<T> {
     pub size_in_gib: u32,
     pub mount_point: String,
     pub snapshot_retention_in_days: u16,
+    pub restore_from_snapshot: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -53,6 +56,23 @@ pub struct CustomDomain {
     pub domain: String,
     pub target_domain: String,
     pub generate_certificate: bool,
+    pub certificate_source: CertificateSource,
+}
+
+/// Where the certificate of a custom domain comes from, when one is generated for it
+#[derive(Clone, Derivative, Eq, PartialEq)]
+#[derivative(Debug)]
+pub enum CertificateSource {
+    /// Issued by Let's Encrypt along the other custom domains of the router
+    Generated,
+    /// PEM certificate chain and private key provided by the user, served as is
+    Uploaded {
+        certificate_chain: String,
+        #[derivative(Debug = "ignore")]
+        private_key: String,
+    },
+    /// Issued by the cert-manager cluster issuer with this name (i.e: an ACME server other than Let's Encrypt)
+    Issuer { name: String },
 }
 impl CustomDomain {
     const WILDCARD_PREFIX: &'static str = "*.";
@@ -66,11 +86,23 @@ impl CustomDomain {
     }
 }
 
-#[derive(Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
 pub struct CustomDomainDataTemplate {
     pub domain: String,
 }
 
+/// TLS certificate of a custom domain not generated along the other ones of its router
+#[derive(Serialize, Eq, PartialEq, Derivative)]
+#[derivative(Debug)]
+pub struct CustomCertificateDataTemplate {
+    pub secret_name: String,
+    pub issuer: Option<String>,
+    pub certificate_chain_b64: Option<String>,
+    #[derivative(Debug = "ignore")]
+    pub private_key_b64: Option<String>,
+    pub domains: Vec<CustomDomainDataTemplate>,
+}
+
 #[derive(Serialize, Eq, PartialEq)]
 pub struct HostDataTemplate {
     pub domain_name: String,
@@ -81,6 +113,13 @@ pub struct HostDataTemplate {
 pub struct Route {
     pub path: String,
     pub service_long_id: Uuid,
+    pub headers: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Eq, PartialEq, Debug)]
+pub struct RouteMatchDataTemplate {
+    pub path: String,
+    pub headers: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -117,6 +156,68 @@ pub struct NodeGroups {
     pub instance_type: String,
     pub disk_size_in_gib: i32,
     pub instance_architecture: CpuArchitecture,
+    #[serde(default)]
+    pub capacity_type: NodeGroupCapacityType,
+    /// Other instance types the group can use when the main one is not available (i.e: spot capacity shortage)
+    #[serde(default)]
+    pub fallback_instance_types: Vec<String>,
+    /// Kubernetes labels set on every node of the group
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Kubernetes taints set on every node of the group, only pods tolerating them are scheduled on the nodes
+    #[serde(default)]
+    pub taints: Vec<NodeGroupTaint>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Hash, Default)]
+pub enum NodeGroupCapacityType {
+    #[default]
+    OnDemand,
+    /// Spare capacity, cheaper but nodes can be reclaimed by the cloud provider at any time
+    Spot,
+    /// Same as spot, name used by GCP
+    Preemptible,
+}
+
+impl NodeGroupCapacityType {
+    pub fn is_interruptible(&self) -> bool {
+        match self {
+            NodeGroupCapacityType::OnDemand => false,
+            NodeGroupCapacityType::Spot | NodeGroupCapacityType::Preemptible => true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum TaintEffect {
+    NoSchedule,
+    PreferNoSchedule,
+    NoExecute,
+}
+
+impl Display for TaintEffect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TaintEffect::NoSchedule => write!(f, "NoSchedule"),
+            TaintEffect::PreferNoSchedule => write!(f, "PreferNoSchedule"),
+            TaintEffect::NoExecute => write!(f, "NoExecute"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Hash)]
+pub struct NodeGroupTaint {
+    pub key: String,
+    #[serde(default)]
+    pub value: String,
+    pub effect: TaintEffect,
+}
+
+impl NodeGroupTaint {
+    /// Kubernetes taint format: `key=value:Effect`
+    pub fn to_k8s_format(&self) -> String {
+        format!("{}={}:{}", self.key, self.value, self.effect)
+    }
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Hash)]
@@ -145,6 +246,10 @@ pub struct NodeGroupsWithDesiredState {
     pub instance_type: String,
     pub disk_size_in_gib: i32,
     pub instance_architecture: CpuArchitecture,
+    pub capacity_type: NodeGroupCapacityType,
+    pub fallback_instance_types: Vec<String>,
+    pub labels: BTreeMap<String, String>,
+    pub taints: Vec<NodeGroupTaint>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -255,7 +360,26 @@ impl CustomerHelmChartsOverride {
 
 #[cfg(test)]
 mod tests {
-    use crate::cloud_provider::models::{KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit};
+    use crate::cloud_provider::models::{
+        CertificateSource, KubernetesCpuResourceUnit, KubernetesMemoryResourceUnit, NodeGroupCapacityType,
+        NodeGroupTaint, NodeGroups, TaintEffect,
+    };
+
+    #[test]
+    fn test_certificate_source_debug_hides_private_key() {
+        // setup:
+        let certificate_source = CertificateSource::Uploaded {
+            certificate_chain: "certificate-chain".to_string(),
+            private_key: "private-key".to_string(),
+        };
+
+        // execute:
+        let debug = format!("{certificate_source:?}");
+
+        // verify:
+        assert!(debug.contains("certificate-chain"));
+        assert!(!debug.contains("private-key"));
+    }
 
     #[test]
     fn test_kubernetes_cpu_resource_unit_to_string() {
@@ -330,4 +454,35 @@ mod tests {
             assert_eq!(tc.output, tc.input.to_string());
         }
     }
+
+    #[test]
+    fn test_node_groups_deserialization() {
+        // setup:
+        let legacy_payload = r#"{"name": "default", "id": null, "min_nodes": 3, "max_nodes": 5, "desired_nodes": null, "instance_type": "t3a.large", "disk_size_in_gib": 100, "instance_architecture": "AMD64"}"#;
+        let spot_payload = r#"{"name": "batch", "id": null, "min_nodes": 0, "max_nodes": 10, "desired_nodes": null, "instance_type": "c6a.xlarge", "disk_size_in_gib": 100, "instance_architecture": "AMD64", "capacity_type": "Spot", "fallback_instance_types": ["c5a.xlarge"], "labels": {"workload": "batch"}, "taints": [{"key": "spot", "value": "true", "effect": "NoSchedule"}]}"#;
+
+        // execute:
+        let legacy_node_group: NodeGroups =
+            serde_json::from_str(legacy_payload).expect("Cannot deserialize node group");
+        let spot_node_group: NodeGroups = serde_json::from_str(spot_payload).expect("Cannot deserialize node group");
+
+        // verify:
+        assert_eq!(legacy_node_group.capacity_type, NodeGroupCapacityType::OnDemand);
+        assert!(legacy_node_group.fallback_instance_types.is_empty());
+        assert!(legacy_node_group.labels.is_empty());
+        assert!(legacy_node_group.taints.is_empty());
+
+        assert!(spot_node_group.capacity_type.is_interruptible());
+        assert_eq!(spot_node_group.fallback_instance_types, vec!["c5a.xlarge".to_string()]);
+        assert_eq!(spot_node_group.labels.get("workload"), Some(&"batch".to_string()));
+        assert_eq!(
+            spot_node_group.taints,
+            vec![NodeGroupTaint {
+                key: "spot".to_string(),
+                value: "true".to_string(),
+                effect: TaintEffect::NoSchedule,
+            }]
+        );
+        assert_eq!(spot_node_group.taints[0].to_k8s_format(), "spot=true:NoSchedule");
+    }
 }
@@ -2,6 +2,7 @@ use crate::cloud_provider::helm::{
     get_engine_helm_action_from_location, ChartInfo, ChartSetValue, CommonChart, HelmChart, HelmChartNamespaces,
     UpdateStrategy,
 };
+use crate::cloud_provider::helm_charts::envoy_gateway_chart::EnvoyGatewayChart;
 use crate::cloud_provider::helm_charts::nginx_ingress_chart::NginxIngressChart;
 use crate::cloud_provider::helm_charts::promtail_chart::PromtailChart;
 use crate::cloud_provider::helm_charts::qovery_shell_agent_chart::QoveryShellAgentChart;
@@ -21,6 +22,7 @@ use crate::cloud_provider::Kind;
 use crate::dns_provider::DnsProviderConfiguration;
 use crate::errors::CommandError;
 use crate::io_models::engine_request::{ChartValuesOverrideName, ChartValuesOverrideValues};
+use crate::models::domain::Domain;
 use crate::models::scaleway::{ScwRegion, ScwZone};
 
 use crate::cloud_provider::helm_charts::cert_manager_chart::CertManagerChart;
@@ -34,11 +36,12 @@ use crate::cloud_provider::helm_charts::loki_chart::{LokiChart, LokiS3BucketConf
 use crate::cloud_provider::helm_charts::prometheus_adapter_chart::PrometheusAdapterChart;
 use crate::cloud_provider::helm_charts::qovery_cert_manager_webhook_chart::QoveryCertManagerWebhookChart;
 use crate::cloud_provider::helm_charts::qovery_cluster_agent_chart::QoveryClusterAgentChart;
+use crate::cloud_provider::helm_charts::qovery_gateway_chart::QoveryGatewayChart;
 use crate::engine_task::qovery_api::{EngineServiceType, QoveryApi};
 use crate::io_models::QoveryIdentifier;
 use crate::models::third_parties::LetsEncryptConfig;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::BufReader;
 use std::iter::FromIterator;
@@ -232,6 +235,7 @@ pub fn scw_helm_charts(
         UpdateStrategy::RollingUpdate,
         true,
         HelmChartNamespaces::KubeSystem,
+        chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled,
     )
     .to_common_helm_chart()?;
 
@@ -365,6 +369,7 @@ pub fn scw_helm_charts(
         true,
         HelmChartNamespaces::CertManager,
         HelmChartNamespaces::KubeSystem,
+        chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled,
     )
     .to_common_helm_chart()?;
 
@@ -375,6 +380,10 @@ pub fn scw_helm_charts(
         &chart_config_prerequisites.dns_provider_config,
         chart_config_prerequisites.managed_dns_helm_format.to_string(),
         HelmChartNamespaces::CertManager,
+        &chart_config_prerequisites
+            .cluster_advanced_settings
+            .cert_manager_acme_issuers,
+        chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled,
     )
     .to_common_helm_chart()?;
 
@@ -393,6 +402,56 @@ pub fn scw_helm_charts(
         );
     }
 
+    // Gateway API, installed next to nginx which keeps serving existing ingresses
+    let envoy_gateway = match chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled {
+        false => None,
+        true => Some(
+            EnvoyGatewayChart::new(
+                chart_prefix_path,
+                HelmChartResourcesConstraintType::ChartDefault,
+                get_chart_overrride_fn.clone(),
+                HelmChartNamespaces::EnvoyGateway,
+            )
+            .to_common_helm_chart()?,
+        ),
+    };
+    let qovery_gateway = match chart_config_prerequisites.cluster_advanced_settings.gateway_api_enabled {
+        false => None,
+        true => Some(
+            QoveryGatewayChart::new(
+                chart_prefix_path,
+                Domain::new(chart_config_prerequisites.managed_dns_name.to_string())
+                    .wildcarded()
+                    .to_string(),
+                HelmChartNamespaces::CertManager,
+                // https://github.com/scaleway/scaleway-cloud-controller-manager/blob/master/docs/loadbalancer-annotations.md
+                BTreeMap::from([
+                    (
+                        "service.beta.kubernetes.io/scw-loadbalancer-forward-port-algorithm".to_string(),
+                        "leastconn".to_string(),
+                    ),
+                    (
+                        "service.beta.kubernetes.io/scw-loadbalancer-health-check-type".to_string(),
+                        "tcp".to_string(),
+                    ),
+                    (
+                        "service.beta.kubernetes.io/scw-loadbalancer-use-hostname".to_string(),
+                        "true".to_string(),
+                    ),
+                    (
+                        "service.beta.kubernetes.io/scw-loadbalancer-type".to_string(),
+                        chart_config_prerequisites
+                            .cluster_advanced_settings
+                            .load_balancer_size
+                            .to_string(),
+                    ),
+                ]),
+                HelmChartNamespaces::EnvoyGateway,
+            )
+            .to_common_helm_chart()?,
+        ),
+    };
+
     // Nginx ingress
     let nginx_ingress = NginxIngressChart::new(
         chart_prefix_path,
@@ -653,6 +712,14 @@ pub fn scw_helm_charts(
         level_6.push(Box::new(pleco_chart));
     }
 
+    // gateway API CRDs must exist before cert-manager starts watching gateways
+    if let Some(envoy_gateway_chart) = envoy_gateway {
+        level_2.push(Box::new(envoy_gateway_chart));
+    }
+    if let Some(qovery_gateway_chart) = qovery_gateway {
+        level_6.push(Box::new(qovery_gateway_chart));
+    }
+
     info!("charts configuration preparation finished");
     Ok(vec![level_1, level_2, level_3, level_4, level_5, level_6, level_7])
 }
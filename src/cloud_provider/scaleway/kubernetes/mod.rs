@@ -223,6 +223,18 @@ impl Kapsule {
             }
         }
 
+        // Kapsule pools are always on-demand
+        if let Some(node_group) = nodes_groups.iter().find(|ng| ng.capacity_type.is_interruptible()) {
+            let err = EngineError::new_unsupported_node_group_capacity_type(
+                event_details.clone(),
+                node_group.name.as_str(),
+                node_group.capacity_type,
+            );
+            logger.log(EngineEvent::Error(err.clone(), None));
+
+            return Err(Box::new(err));
+        }
+
         advanced_settings.validate(event_details)?;
 
         let object_storage = ScalewayOS::new(
@@ -498,6 +510,7 @@ impl Kapsule {
             &managed_dns_resolvers_terraform_format,
         );
         context.insert("wildcard_managed_dns", &self.dns_provider().domain().wildcarded().to_string());
+        context.insert("gateway_api_enabled", &self.advanced_settings().gateway_api_enabled);
 
         // add specific DNS fields
         self.dns_provider().insert_into_teracontext(&mut context);
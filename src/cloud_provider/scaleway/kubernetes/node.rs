@@ -388,7 +388,8 @@ impl ScwNodeGroup {
 mod tests {
     use crate::cloud_provider::scaleway::kubernetes::node::ScwInstancesType;
     use crate::cloud_provider::kubernetes::InstanceType;
-    use crate::cloud_provider::models::{CpuArchitecture, NodeGroups};
+    use crate::cloud_provider::models::{CpuArchitecture, NodeGroupCapacityType, NodeGroups};
+    use std::collections::BTreeMap;
     use std::str::FromStr;
     use strum::IntoEnumIterator;
 
@@ -471,6 +472,10 @@ mod tests {
                 disk_size_in_gib: 20,
                 desired_nodes: None,
                 instance_architecture: CpuArchitecture::AMD64,
+                capacity_type: NodeGroupCapacityType::OnDemand,
+                fallback_instance_types: vec![],
+                labels: BTreeMap::new(),
+                taints: vec![],
             }
         );
     }
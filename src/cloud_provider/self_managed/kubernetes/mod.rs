@@ -12,12 +12,17 @@ use crate::cloud_provider::qovery::EngineLocation;
 use crate::cloud_provider::CloudProvider;
 use crate::dns_provider::DnsProvider;
 use crate::errors::{CommandError, EngineError};
+use crate::events::InfrastructureStep;
+use crate::events::Stage::Infrastructure;
 use crate::io_models::context::Context;
 use crate::logger::Logger;
 use crate::metrics_registry::MetricsRegistry;
+use crate::object_storage::s3_compatible_object_storage::{S3Compatible, S3CompatibleRegion};
 use crate::object_storage::ObjectStorage;
 use crate::secret_manager::vault::QVaultClient;
+use derivative::Derivative;
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 pub struct SelfManaged {
     context: Context,
@@ -34,6 +39,7 @@ pub struct SelfManaged {
     metrics_registry: Box<dyn MetricsRegistry>,
     advanced_settings: ClusterAdvancedSettings,
     kubeconfig: Option<String>,
+    object_storage: Option<S3Compatible>,
 }
 
 impl SelfManaged {
@@ -51,6 +57,17 @@ impl SelfManaged {
         advanced_settings: ClusterAdvancedSettings,
         kubeconfig: Option<String>,
     ) -> Result<SelfManaged, Box<EngineError>> {
+        let object_storage = options.object_storage.as_ref().map(|object_storage| {
+            S3Compatible::new(
+                format!("s3-compatible-{}", id),
+                format!("s3-compatible-{}", name),
+                object_storage.endpoint.clone(),
+                object_storage.region.clone().map(S3CompatibleRegion),
+                object_storage.access_key_id.clone(),
+                object_storage.secret_access_key.clone(),
+                object_storage.ca_certificate.clone(),
+            )
+        });
         let self_managed = SelfManaged {
             context,
             id,
@@ -65,6 +82,7 @@ impl SelfManaged {
             metrics_registry,
             advanced_settings,
             kubeconfig,
+            object_storage,
         };
         // create kubeconfig file so it can be used later
         self_managed.create_kubeconfig_from_kubernetes_connection()?;
@@ -80,6 +98,24 @@ pub struct SelfManagedOptions {
     pub qovery_engine_url: String,
     pub jwt_token: String,
     pub qovery_engine_location: EngineLocation,
+    /// Where kubeconfigs and engine artifacts are stored, the cluster has no object storage of its own otherwise
+    #[serde(default)]
+    pub object_storage: Option<S3CompatibleObjectStorageOptions>,
+}
+
+#[derive(Derivative, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derivative(Debug)]
+pub struct S3CompatibleObjectStorageOptions {
+    pub endpoint: Url,
+    /// Location buckets are created in, the default one of the storage if not set
+    #[serde(default)]
+    pub region: Option<String>,
+    pub access_key_id: String,
+    #[derivative(Debug = "ignore")]
+    pub secret_access_key: String,
+    /// PEM encoded CA certificate, if the endpoint certificate is signed by a private CA
+    #[serde(default)]
+    pub ca_certificate: Option<String>,
 }
 
 impl Kubernetes for SelfManaged {
@@ -132,7 +168,9 @@ impl Kubernetes for SelfManaged {
     }
 
     fn config_file_store(&self) -> &dyn ObjectStorage {
-        todo!()
+        self.object_storage
+            .as_ref()
+            .expect("No object storage configured for this self managed cluster")
     }
 
     fn is_valid(&self) -> Result<(), Box<EngineError>> {
@@ -148,7 +186,18 @@ impl Kubernetes for SelfManaged {
     }
 
     fn on_create(&self) -> Result<(), Box<EngineError>> {
-        Ok(())
+        let (Some(object_storage), Some(_)) = (&self.object_storage, &self.kubeconfig) else {
+            return Ok(());
+        };
+
+        // keep the kubeconfig in the object storage, so it can be retrieved without the kubernetes connection
+        let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Create));
+        if let Err(e) =
+            object_storage.create_bucket(self.get_bucket_name().as_str(), self.advanced_settings.resource_ttl(), true)
+        {
+            return Err(Box::new(EngineError::new_object_storage_error(event_details, e)));
+        }
+        self.put_kubeconfig_file_to_object_storage(&self.get_kubeconfig_file_path()?)
     }
 
     fn on_create_error(&self) -> Result<(), Box<EngineError>> {
@@ -179,6 +228,9 @@ impl Kubernetes for SelfManaged {
     }
 
     fn on_delete(&self) -> Result<(), Box<EngineError>> {
+        if self.object_storage.is_some() {
+            self.delete_kubeconfig_from_object_storage()?;
+        }
         Ok(())
     }
 
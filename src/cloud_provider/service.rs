@@ -1,3 +1,4 @@
+use derivative::Derivative;
 use k8s_openapi::api::apps::v1::StatefulSet;
 use k8s_openapi::api::core::v1::PersistentVolumeClaim;
 use serde::{Deserialize, Serialize};
@@ -53,12 +54,13 @@ pub enum Action {
     Pause,
     Delete,
     Restart,
+    Restore,
 }
 
 impl Action {
     pub fn to_environment_step(&self) -> EnvironmentStep {
         match self {
-            Action::Create => EnvironmentStep::Deploy,
+            Action::Create | Action::Restore => EnvironmentStep::Deploy,
             Action::Pause => EnvironmentStep::Pause,
             Action::Delete => EnvironmentStep::Delete,
             Action::Restart => EnvironmentStep::Restart,
@@ -76,6 +78,7 @@ impl Display for Action {
                 Action::Pause => "Pause",
                 Action::Delete => "Deletion",
                 Action::Restart => "Restart",
+                Action::Restore => "Restore",
             },
         )
     }
@@ -154,7 +157,8 @@ pub fn default_tera_context(
     context
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Derivative, Clone, Serialize, Deserialize)]
+#[derivative(Debug)]
 pub struct DatabaseTerraformConfig {
     #[serde(rename = "database_target_id")]
     pub target_id: String,
@@ -164,6 +168,12 @@ pub struct DatabaseTerraformConfig {
     pub target_fqdn_id: String,
     #[serde(rename = "database_target_fqdn")]
     pub target_fqdn: String,
+    // only set when the managed service decides the port and password (i.e: Memorystore with AUTH and TLS)
+    #[serde(rename = "database_target_port", default)]
+    pub target_port: Option<u16>,
+    #[serde(rename = "database_target_password", default)]
+    #[derivative(Debug = "ignore")]
+    pub target_password: Option<String>,
 }
 
 pub fn get_database_terraform_config(
@@ -1,3 +1,4 @@
+use crate::cloud_provider::azure::locations::AzureLocation;
 use crate::cloud_provider::gcp::locations::{GcpRegion, GcpZone};
 use crate::cloud_provider::kubernetes::Kind;
 use crate::errors::{CommandError, EngineError};
@@ -15,6 +16,7 @@ pub enum ClusterSecrets {
     Ec2(ClusterSecretsAws),
     Scaleway(ClusterSecretsScaleway),
     Gke(ClusterSecretsGcp),
+    Aks(ClusterSecretsAzure),
     SelfManaged(ClusterSecretsSelfManaged),
 }
 
@@ -187,6 +189,66 @@ impl ClusterSecretsGcp {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ClusterSecretsAzure {
+    #[serde(rename = "ARM_CLIENT_ID")]
+    pub azure_client_id: String,
+    #[serde(rename = "ARM_CLIENT_SECRET")]
+    pub azure_client_secret: String,
+    #[serde(rename = "ARM_TENANT_ID")]
+    pub azure_tenant_id: String,
+    #[serde(rename = "ARM_SUBSCRIPTION_ID")]
+    pub azure_subscription_id: String,
+    pub azure_location: AzureLocation,
+    pub kubeconfig_b64: Option<String>,
+    pub k8s_cluster_endpoint: Option<String>,
+    pub cloud_provider: Kind,
+    pub cluster_name: String,
+    pub cluster_id: String,
+    pub grafana_login: String,
+    pub grafana_password: String,
+    pub organization_id: String,
+    pub test_cluster: bool,
+    pub vault_mount_name: String,
+}
+
+impl ClusterSecretsAzure {
+    pub fn new(
+        azure_client_id: String,
+        azure_client_secret: String,
+        azure_tenant_id: String,
+        azure_subscription_id: String,
+        azure_location: AzureLocation,
+        kubeconfig_b64: Option<String>,
+        k8s_cluster_endpoint: Option<String>,
+        cloud_provider: Kind,
+        cluster_name: String,
+        cluster_id: String,
+        grafana_login: String,
+        grafana_password: String,
+        organization_id: String,
+        test_cluster: bool,
+    ) -> Self {
+        Self {
+            azure_client_id,
+            azure_client_secret,
+            azure_tenant_id,
+            azure_subscription_id,
+            azure_location,
+            kubeconfig_b64,
+            k8s_cluster_endpoint,
+            cloud_provider,
+            cluster_name,
+            cluster_id,
+            grafana_login,
+            grafana_password,
+            organization_id,
+            test_cluster,
+            vault_mount_name: get_vault_mount_name(test_cluster),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct ClusterSecretsSelfManaged {
     pub kubeconfig_b64: Option<String>,
@@ -236,11 +298,16 @@ impl ClusterSecrets {
         ClusterSecrets::Gke(cluster_secrets)
     }
 
+    pub fn new_azure_aks(cluster_secrets: ClusterSecretsAzure) -> ClusterSecrets {
+        ClusterSecrets::Aks(cluster_secrets)
+    }
+
     pub fn get_vault_mount_name(&self) -> String {
         let is_test_cluster = match self {
             ClusterSecrets::Eks(aws) | ClusterSecrets::Ec2(aws) => aws.test_cluster,
             ClusterSecrets::Scaleway(scaleway) => scaleway.test_cluster,
             ClusterSecrets::Gke(gke) => gke.test_cluster,
+            ClusterSecrets::Aks(aks) => aks.test_cluster,
             ClusterSecrets::SelfManaged(selfmanaged) => selfmanaged.test_cluster,
         };
         get_vault_mount_name(is_test_cluster)
@@ -282,6 +349,10 @@ impl ClusterSecrets {
                 Ok(x) => Ok(ClusterSecrets::Gke(x)),
                 Err(e) => Err(err(e)),
             },
+            Kind::Aks => match qvault_client.get_secret(mount.as_str(), cluster_id) {
+                Ok(x) => Ok(ClusterSecrets::Aks(x)),
+                Err(e) => Err(err(e)),
+            },
         }
     }
 
@@ -291,6 +362,7 @@ impl ClusterSecrets {
             ClusterSecrets::Ec2(_) => Kind::Ec2,
             ClusterSecrets::Scaleway(_) => Kind::ScwKapsule,
             ClusterSecrets::Gke(_) => Kind::Gke,
+            ClusterSecrets::Aks(_) => Kind::Aks,
             ClusterSecrets::SelfManaged(_) => todo!(),
         }
     }
@@ -300,6 +372,7 @@ impl ClusterSecrets {
             ClusterSecrets::Eks(x) | ClusterSecrets::Ec2(x) => x.cluster_id.as_str(),
             ClusterSecrets::Scaleway(x) => x.cluster_id.as_str(),
             ClusterSecrets::Gke(x) => x.cluster_id.as_str(),
+            ClusterSecrets::Aks(x) => x.cluster_id.as_str(),
             ClusterSecrets::SelfManaged(x) => x.cluster_id.as_str(),
         }
     }
@@ -309,6 +382,7 @@ impl ClusterSecrets {
             ClusterSecrets::Eks(x) | ClusterSecrets::Ec2(x) => x.test_cluster,
             ClusterSecrets::Scaleway(x) => x.test_cluster,
             ClusterSecrets::Gke(x) => x.test_cluster,
+            ClusterSecrets::Aks(x) => x.test_cluster,
             ClusterSecrets::SelfManaged(x) => x.test_cluster,
         }
     }
@@ -318,6 +392,7 @@ impl ClusterSecrets {
             ClusterSecrets::Eks(x) | ClusterSecrets::Ec2(x) => x.k8s_cluster_endpoint = Some(k8s_cluster_endpoint),
             ClusterSecrets::Scaleway(x) => x.k8s_cluster_endpoint = Some(k8s_cluster_endpoint),
             ClusterSecrets::Gke(x) => x.k8s_cluster_endpoint = Some(k8s_cluster_endpoint),
+            ClusterSecrets::Aks(x) => x.k8s_cluster_endpoint = Some(k8s_cluster_endpoint),
             ClusterSecrets::SelfManaged(_) => {}
         }
     }
@@ -327,6 +402,7 @@ impl ClusterSecrets {
             ClusterSecrets::Eks(x) | ClusterSecrets::Ec2(x) => x.kubeconfig_b64 = Some(kubeconfig_b64),
             ClusterSecrets::Scaleway(x) => x.kubeconfig_b64 = Some(kubeconfig_b64),
             ClusterSecrets::Gke(x) => x.kubeconfig_b64 = Some(kubeconfig_b64),
+            ClusterSecrets::Aks(x) => x.kubeconfig_b64 = Some(kubeconfig_b64),
             ClusterSecrets::SelfManaged(x) => x.kubeconfig_b64 = Some(kubeconfig_b64),
         }
     }
@@ -336,6 +412,7 @@ impl ClusterSecrets {
             ClusterSecrets::Eks(x) | ClusterSecrets::Ec2(x) => x.organization_id = organization_id,
             ClusterSecrets::Scaleway(x) => x.organization_id = organization_id,
             ClusterSecrets::Gke(x) => x.organization_id = organization_id,
+            ClusterSecrets::Aks(x) => x.organization_id = organization_id,
             ClusterSecrets::SelfManaged(x) => x.organization_id = organization_id,
         }
     }
@@ -364,6 +441,7 @@ impl ClusterSecrets {
                     ClusterSecrets::Eks(ref mut x) | ClusterSecrets::Ec2(ref mut x) => x.kubeconfig_b64 = None,
                     ClusterSecrets::Scaleway(ref mut x) => x.kubeconfig_b64 = None,
                     ClusterSecrets::Gke(ref mut x) => x.kubeconfig_b64 = None,
+                    ClusterSecrets::Aks(ref mut x) => x.kubeconfig_b64 = None,
                     ClusterSecrets::SelfManaged(ref mut x) => x.kubeconfig_b64 = None,
                 }
                 let mut current_secret = x.clone();
@@ -371,6 +449,7 @@ impl ClusterSecrets {
                     ClusterSecrets::Eks(ref mut x) | ClusterSecrets::Ec2(ref mut x) => x.kubeconfig_b64 = None,
                     ClusterSecrets::Scaleway(ref mut x) => x.kubeconfig_b64 = None,
                     ClusterSecrets::Gke(ref mut x) => x.kubeconfig_b64 = None,
+                    ClusterSecrets::Aks(ref mut x) => x.kubeconfig_b64 = None,
                     ClusterSecrets::SelfManaged(ref mut x) => x.kubeconfig_b64 = None,
                 }
                 if x == current_secret {
@@ -390,6 +469,7 @@ impl ClusterSecrets {
             ClusterSecrets::Eks(x) | ClusterSecrets::Ec2(x) => (x.vault_mount_name.as_str(), x.cluster_id.as_str()),
             ClusterSecrets::Scaleway(x) => (x.vault_mount_name.as_str(), x.cluster_id.as_str()),
             ClusterSecrets::Gke(x) => (x.vault_mount_name.as_str(), x.cluster_id.as_str()),
+            ClusterSecrets::Aks(x) => (x.vault_mount_name.as_str(), x.cluster_id.as_str()),
             ClusterSecrets::SelfManaged(x) => (x.vault_mount_name.as_str(), x.cluster_id.as_str()),
         };
 
@@ -417,6 +497,7 @@ impl ClusterSecrets {
             ClusterSecrets::Eks(x) | ClusterSecrets::Ec2(x) => (x.vault_mount_name.as_str(), x.cluster_id.as_str()),
             ClusterSecrets::Scaleway(x) => (x.vault_mount_name.as_str(), x.cluster_id.as_str()),
             ClusterSecrets::Gke(x) => (x.vault_mount_name.as_str(), x.cluster_id.as_str()),
+            ClusterSecrets::Aks(x) => (x.vault_mount_name.as_str(), x.cluster_id.as_str()),
             ClusterSecrets::SelfManaged(x) => (x.vault_mount_name.as_str(), x.cluster_id.as_str()),
         };
 
@@ -0,0 +1,235 @@
+use crate::cmd::command::{CommandError, CommandKiller, ExecutableCommand, QoveryCommand};
+use derivative::Derivative;
+use serde_json::json;
+use std::fs;
+use std::io::{Error, Write};
+use std::process::ExitStatus;
+use tempfile::{NamedTempFile, TempDir};
+
+#[derive(thiserror::Error, Debug)]
+pub enum CosignError {
+    #[error("Cosign terminated with an unknown error: {raw_error:?}")]
+    ExecutionError { raw_error: Error },
+
+    #[error("Cosign terminated with a non success exit status code: {exit_status:?}: {stderr}")]
+    ExitStatusError { exit_status: ExitStatus, stderr: String },
+
+    #[error("Cosign aborted due to user cancel request: {raw_error_message:?}")]
+    Aborted { raw_error_message: String },
+
+    #[error("Cosign command terminated due to timeout: {raw_error_message:?}")]
+    Timeout { raw_error_message: String },
+
+    #[error("Image has no valid signature: {raw_error_message}")]
+    InvalidSignature { raw_error_message: String },
+
+    #[error("Cannot resolve the digest of the image: {raw_error_message}")]
+    DigestNotFound { raw_error_message: String },
+}
+
+impl From<Error> for CosignError {
+    fn from(value: Error) -> Self {
+        CosignError::ExecutionError { raw_error: value }
+    }
+}
+
+/// Key pair images are signed with, the private key is encrypted with the password
+#[derive(Clone, Derivative)]
+#[derivative(Debug)]
+pub struct CosignKeyPair {
+    #[derivative(Debug = "ignore")]
+    pub private_key: String,
+    pub public_key: String,
+    #[derivative(Debug = "ignore")]
+    pub password: String,
+}
+
+/// Key based signing and verification of container images.
+/// Registries are accessed with the given credentials, or the ones of the docker config if none.
+/// Signatures are not uploaded to a transparency log.
+#[derive(Debug, Default)]
+pub struct Cosign {}
+
+impl Cosign {
+    pub fn new() -> Self {
+        Cosign {}
+    }
+
+    pub fn generate_key_pair(&self, password: &str, cmd_killer: &CommandKiller) -> Result<CosignKeyPair, CosignError> {
+        // keys are written as cosign.key and cosign.pub in the current directory
+        let dir = tempfile::tempdir()?;
+        let mut cmd = QoveryCommand::new("cosign", &["generate-key-pair"], &[("COSIGN_PASSWORD", password)]);
+        cmd.set_current_dir(dir.path());
+        exec(cmd, cmd_killer)?;
+
+        Ok(CosignKeyPair {
+            private_key: fs::read_to_string(dir.path().join("cosign.key"))?,
+            public_key: fs::read_to_string(dir.path().join("cosign.pub"))?,
+            password: password.to_string(),
+        })
+    }
+
+    /// Signs the image the tag of `image_name` points to, by its digest as the tag can be moved meanwhile.
+    /// `registry_auth` is the registry host and its base64 encoded basic auth credentials.
+    /// Gives back the name of the signed image with its digest.
+    pub fn sign(
+        &self,
+        image_name: &str,
+        registry_auth: Option<(String, String)>,
+        key_pair: &CosignKeyPair,
+        cmd_killer: &CommandKiller,
+    ) -> Result<String, CosignError> {
+        let docker_config = registry_auth.map(docker_config_dir).transpose()?;
+        let docker_config_path = docker_config
+            .as_ref()
+            .map(|dir| dir.path().to_string_lossy().to_string());
+        let docker_config_envs = docker_config_path
+            .as_deref()
+            .map(|path| vec![("DOCKER_CONFIG", path)])
+            .unwrap_or_default();
+
+        let cmd = QoveryCommand::new("cosign", &["triangulate", "--type", "digest", image_name], &docker_config_envs);
+        let image_digest = exec(cmd, cmd_killer)?
+            .into_iter()
+            .rev()
+            .find(|line| line.contains("@sha256:"))
+            .ok_or_else(|| CosignError::DigestNotFound {
+                raw_error_message: format!("no digest found for image {image_name}"),
+            })?;
+
+        let key_file = key_file(&key_pair.private_key)?;
+        let cmd = QoveryCommand::new(
+            "cosign",
+            &[
+                "sign",
+                "--yes",
+                "--tlog-upload=false",
+                "--key",
+                key_file.path().to_string_lossy().as_ref(),
+                image_digest.as_str(),
+            ],
+            &[
+                &[("COSIGN_PASSWORD", key_pair.password.as_str())],
+                docker_config_envs.as_slice(),
+            ]
+            .concat(),
+        );
+        exec(cmd, cmd_killer)?;
+
+        Ok(image_digest)
+    }
+
+    /// Succeeds if the image has a signature made with the private key of `public_key`
+    pub fn verify(&self, image_name: &str, public_key: &str, cmd_killer: &CommandKiller) -> Result<(), CosignError> {
+        let key_file = key_file(public_key)?;
+        let cmd = QoveryCommand::new(
+            "cosign",
+            &[
+                "verify",
+                "--insecure-ignore-tlog=true",
+                "--key",
+                key_file.path().to_string_lossy().as_ref(),
+                image_name,
+            ],
+            &[],
+        );
+
+        match exec(cmd, cmd_killer) {
+            Ok(_) => Ok(()),
+            Err(CosignError::ExitStatusError { stderr, .. }) if is_signature_error(&stderr) => {
+                Err(CosignError::InvalidSignature {
+                    raw_error_message: stderr,
+                })
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn key_file(key: &str) -> Result<NamedTempFile, CosignError> {
+    let mut file = NamedTempFile::new()?;
+    file.write_all(key.as_bytes())?;
+    file.flush()?;
+
+    Ok(file)
+}
+
+/// Docker config directory holding only the credentials of the registry, cosign reads it from `DOCKER_CONFIG`
+fn docker_config_dir((registry_host, auth): (String, String)) -> Result<TempDir, CosignError> {
+    let dir = tempfile::tempdir()?;
+    fs::write(
+        dir.path().join("config.json"),
+        json!({ "auths": { registry_host: { "auth": auth } } }).to_string(),
+    )?;
+
+    Ok(dir)
+}
+
+fn is_signature_error(stderr: &str) -> bool {
+    stderr.contains("no signatures found")
+        || stderr.contains("no matching signatures")
+        || stderr.contains("invalid signature")
+}
+
+/// Runs the command and gives back the lines of its standard output
+fn exec(mut cmd: QoveryCommand, cmd_killer: &CommandKiller) -> Result<Vec<String>, CosignError> {
+    let mut stdout = Vec::new();
+    let mut stderr = String::new();
+    let ret = cmd.exec_with_abort(
+        &mut |line| {
+            info!("{}", line);
+            stdout.push(line);
+        },
+        &mut |line| {
+            warn!("{}", line);
+            stderr.push_str(&line);
+            stderr.push('\n');
+        },
+        cmd_killer,
+    );
+
+    match ret {
+        Ok(_) => Ok(stdout),
+        Err(CommandError::TimeoutError(msg)) => Err(CosignError::Timeout { raw_error_message: msg }),
+        Err(CommandError::Killed(msg)) => Err(CosignError::Aborted { raw_error_message: msg }),
+        Err(CommandError::ExitStatusError(err)) => Err(CosignError::ExitStatusError {
+            exit_status: err,
+            stderr,
+        }),
+        Err(CommandError::ExecutionError(err)) => Err(CosignError::ExecutionError { raw_error: err }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cmd::cosign::{docker_config_dir, is_signature_error};
+    use std::fs;
+
+    #[test]
+    fn test_is_signature_error() {
+        assert!(is_signature_error(
+            "Error: no signatures found for image\nmain.go:69: error during command execution: no signatures found"
+        ));
+        assert!(is_signature_error(
+            "Error: no matching signatures: invalid signature when validating ASN.1 encoded signature"
+        ));
+        assert!(!is_signature_error(
+            "Error: GET https://registry.qovery.com/v2/app-z1/manifests/v1: UNAUTHORIZED: authentication required"
+        ));
+    }
+
+    #[test]
+    fn test_docker_config_dir() {
+        // execute:
+        let dir = docker_config_dir(("registry.qovery.com".to_string(), "bG9naW46cGFzc3dvcmQ=".to_string()))
+            .expect("docker config should be written");
+
+        // verify:
+        let config: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join("config.json")).unwrap()).unwrap();
+        assert_eq!(
+            config,
+            serde_json::json!({ "auths": { "registry.qovery.com": { "auth": "bG9naW46cGFzc3dvcmQ=" } } })
+        );
+    }
+}
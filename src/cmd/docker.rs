@@ -165,6 +165,16 @@ pub struct BuilderHandle {
     builder_name: Option<String>,
 }
 
+impl BuilderHandle {
+    /// Handle for builds that don't run in a docker builder
+    pub fn without_builder(nb_builder: NonZeroUsize) -> BuilderHandle {
+        BuilderHandle {
+            nb_builder,
+            builder_name: None,
+        }
+    }
+}
+
 impl Drop for BuilderHandle {
     fn drop(&mut self) {
         if let Some(builder_name) = &self.builder_name {
@@ -5,11 +5,11 @@ use tracing::{error, info};
 
 use crate::cloud_provider::helm::ChartInfo;
 use crate::cmd::command::{CommandError, CommandKiller, ExecutableCommand, QoveryCommand};
-use crate::cmd::helm::HelmCommand::{FETCH, LIST, LOGIN, PULL, ROLLBACK, STATUS, UNINSTALL, UPGRADE};
+use crate::cmd::helm::HelmCommand::{FETCH, HISTORY, LIST, LOGIN, PULL, ROLLBACK, STATUS, UNINSTALL, UPGRADE};
 use crate::cmd::helm::HelmError::{
     CannotRollback, CmdError, InvalidKubeConfig, InvalidRepositoryConfig, ReleaseDoesNotExist,
 };
-use crate::cmd::structs::{HelmChart, HelmChartVersions, HelmListItem};
+use crate::cmd::structs::{HelmChart, HelmChartVersions, HelmHistoryRow, HelmListItem};
 use crate::errors;
 use crate::errors::EngineError;
 use crate::events::EventDetails;
@@ -77,6 +77,7 @@ pub struct Helm {
 #[derive(Debug, Clone, Copy)]
 pub enum HelmCommand {
     ROLLBACK,
+    HISTORY,
     STATUS,
     UPGRADE,
     UNINSTALL,
@@ -209,6 +210,94 @@ impl Helm {
         }
     }
 
+    /// Rollback a release to a specific revision, whatever the current revision is
+    pub fn rollback_to_revision(
+        &self,
+        chart: &ChartInfo,
+        revision: u16,
+        envs: &[(&str, &str)],
+    ) -> Result<(), HelmError> {
+        let timeout = format!("{}s", &chart.timeout_in_seconds);
+        let namespace = chart.get_namespace_string();
+        let revision = revision.to_string();
+        let args = vec![
+            "rollback",
+            &chart.name,
+            &revision,
+            "--kubeconfig",
+            self.kubernetes_config.to_str().unwrap_or_default(),
+            "--namespace",
+            &namespace,
+            "--timeout",
+            &timeout,
+            "--history-max",
+            HELM_MAX_HISTORY,
+            "--cleanup-on-fail",
+            "--force",
+            "--wait",
+        ];
+
+        let mut stderr = String::new();
+        match helm_exec_with_output(
+            &args,
+            &self.get_all_envs(envs),
+            &mut |_| {},
+            &mut |line| stderr.push_str(&line),
+            &CommandKiller::never(),
+        ) {
+            Err(_) if stderr.contains("release: not found") => Err(ReleaseDoesNotExist(chart.name.clone())),
+            Err(err) => {
+                stderr.push_str(err.to_string().as_str());
+                Err(CmdError(chart.name.clone(), ROLLBACK, err.into()))
+            }
+            Ok(_) => Ok(()),
+        }
+    }
+
+    /// List the revisions of a release, from the oldest to the newest
+    pub fn history(&self, chart: &ChartInfo, envs: &[(&str, &str)]) -> Result<Vec<HelmHistoryRow>, HelmError> {
+        let namespace = chart.get_namespace_string();
+        let args = vec![
+            "history",
+            &chart.name,
+            "--kubeconfig",
+            self.kubernetes_config.to_str().unwrap_or_default(),
+            "--namespace",
+            &namespace,
+            "--max",
+            HELM_MAX_HISTORY,
+            "-o",
+            "json",
+        ];
+
+        let mut stdout = String::new();
+        let mut stderr = String::new();
+        match helm_exec_with_output(
+            &args,
+            &self.get_all_envs(envs),
+            &mut |line| stdout.push_str(&line),
+            &mut |line| stderr.push_str(&line),
+            &CommandKiller::never(),
+        ) {
+            Err(_) if stderr.contains("release: not found") => Err(ReleaseDoesNotExist(chart.name.clone())),
+            Err(err) => {
+                stderr.push_str(err.to_string().as_str());
+                Err(CmdError(chart.name.clone(), HISTORY, err.into()))
+            }
+            Ok(_) => serde_json::from_str::<Vec<HelmHistoryRow>>(&stdout).map_err(|e| {
+                CmdError(
+                    chart.name.clone(),
+                    HISTORY,
+                    errors::CommandError::new(
+                        "Cannot parse helm history output".to_string(),
+                        Some(format!("{e}: {stdout}")),
+                        None,
+                    ),
+                )
+            }),
+        }
+    }
+
     pub fn uninstall<STDOUT, STDERR>(
         &self,
         chart: &ChartInfo,
@@ -756,9 +845,15 @@ impl Helm {
     }
 
     pub fn upgrade_diff(&self, chart: &ChartInfo, envs: &[(&str, &str)]) -> Result<(), HelmError> {
+        self.upgrade_diff_with_output(chart, envs).map(|_| ())
+    }
+
+    /// Same as upgrade_diff, but returns the diff between the rendered chart and the live release
+    pub fn upgrade_diff_with_output(&self, chart: &ChartInfo, envs: &[(&str, &str)]) -> Result<String, HelmError> {
         let mut args_string: Vec<String> = vec![
             "diff".to_string(),
             "upgrade".to_string(),
+            "--no-color".to_string(),
             "--kubeconfig".to_string(),
             self.kubernetes_config.to_str().unwrap_or_default().to_string(),
             "--install".to_string(),
@@ -806,12 +901,15 @@ impl Helm {
         args_string.push(chart.name.clone());
         args_string.push(chart.path.clone());
 
+        let mut stdout_msg = String::new();
         let mut stderr_msg = String::new();
         let helm_ret = helm_exec_with_output(
             &args_string.iter().map(|x| x.as_str()).collect::<Vec<&str>>(),
             &self.get_all_envs(envs),
             &mut |line| {
                 info!("{}", line);
+                stdout_msg.push_str(&line);
+                stdout_msg.push('\n');
             },
             &mut |line| {
                 stderr_msg.push_str(&line);
@@ -822,7 +920,7 @@ impl Helm {
 
         match helm_ret {
             // Ok is ok
-            Ok(_) => Ok(()),
+            Ok(_) => Ok(stdout_msg),
             Err(err) => {
                 error!("Helm error: {:?}", err);
                 Err(CmdError(chart.name.clone(), HelmCommand::DIFF, err.into()))
@@ -830,6 +928,53 @@ impl Helm {
         }
     }
 
+    /// Diff of a chart already present on disk against the live release, with raw helm arguments
+    pub fn upgrade_diff_raw(
+        &self,
+        release_name: &str,
+        chart_path: &Path,
+        namespace: &str,
+        args: &[&str],
+        envs: &[(&str, &str)],
+    ) -> Result<String, HelmError> {
+        let chart_path = chart_path.to_string_lossy();
+        let args: Vec<&str> = [
+            "diff",
+            "upgrade",
+            "--no-color",
+            release_name,
+            chart_path.as_ref(),
+            "--kubeconfig",
+            self.kubernetes_config.to_str().unwrap_or_default(),
+            "--install",
+            "-n",
+            namespace,
+        ]
+        .into_iter()
+        .chain(args.iter().copied())
+        .collect();
+
+        let mut stdout_msg = String::new();
+        let helm_ret = helm_exec_with_output(
+            &args,
+            &self.get_all_envs(envs),
+            &mut |line| {
+                stdout_msg.push_str(&line);
+                stdout_msg.push('\n');
+            },
+            &mut |line| warn!("chart {}: {}", release_name, line),
+            &CommandKiller::never(),
+        );
+
+        match helm_ret {
+            Ok(_) => Ok(stdout_msg),
+            Err(err) => {
+                error!("Helm error: {:?}", err);
+                Err(CmdError(release_name.to_string(), HelmCommand::DIFF, err.into()))
+            }
+        }
+    }
+
     pub fn upgrade(
         &self,
         chart: &ChartInfo,
@@ -1573,6 +1718,31 @@ mod tests {
         assert!(matches!(ret, Ok(())));
     }
 
+    #[test]
+    fn test_history_and_rollback_to_revision() {
+        let HelmTestCtx { ref helm, ref charts } = HelmTestCtx::new("test-history");
+
+        // check release does not exist yet
+        let ret = helm.history(&charts[0], &[]);
+        assert!(matches!(ret, Err(HelmError::ReleaseDoesNotExist(test)) if test == charts[0].name));
+
+        // install it twice
+        let ret = helm.upgrade(&charts[0], &[], &CommandKiller::never());
+        assert!(matches!(ret, Ok(())));
+        let ret = helm.upgrade(&charts[0], &[], &CommandKiller::never());
+        assert!(matches!(ret, Ok(())));
+
+        let history = helm.history(&charts[0], &[]).unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(history[1].is_successfully_deployed());
+
+        // Go back to the first revision
+        let ret = helm.rollback_to_revision(&charts[0], history[0].revision, &[]);
+        assert!(matches!(ret, Ok(())));
+        let history = helm.history(&charts[0], &[]).unwrap();
+        assert_eq!(history.len(), 3);
+    }
+
     #[test]
     fn test_upgrade() {
         let HelmTestCtx { ref helm, ref charts } = HelmTestCtx::new("test-upgrade");
@@ -1,4 +1,5 @@
 pub mod command;
+pub mod cosign;
 pub mod docker;
 pub mod git_lfs;
 pub mod helm;
@@ -7,3 +8,4 @@ pub mod kubectl;
 pub mod kubectl_utils;
 pub mod structs;
 pub mod terraform;
+pub mod trivy;
@@ -1302,6 +1302,13 @@ pub fn terraform_init_validate_plan_apply(
     )
 }
 
+pub fn terraform_init_validate_plan(root_dir: &str, envs: &[(&str, &str)]) -> Result<Vec<String>, TerraformError> {
+    // Terraform init & validate, then only plan without applying anything
+    // Only the plan output is returned, as it is the one describing the changes
+    terraform_run(TerraformAction::INIT | TerraformAction::VALIDATE, root_dir, false, envs)?;
+    terraform_plan(root_dir, envs)
+}
+
 pub fn terraform_init_validate(root_dir: &str, envs: &[(&str, &str)]) -> Result<Vec<String>, TerraformError> {
     // Terraform init & validate
     terraform_run(TerraformAction::INIT | TerraformAction::VALIDATE, root_dir, false, envs)
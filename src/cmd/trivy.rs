@@ -0,0 +1,320 @@
+use crate::cmd::command::{CommandError, CommandKiller, ExecutableCommand, QoveryCommand};
+use serde_derive::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::io::Error;
+use std::process::ExitStatus;
+use url::Url;
+
+#[derive(thiserror::Error, Debug)]
+pub enum TrivyError {
+    #[error("Trivy terminated with an unknown error: {raw_error:?}")]
+    ExecutionError { raw_error: Error },
+
+    #[error("Trivy terminated with a non success exit status code: {exit_status:?}: {stderr}")]
+    ExitStatusError { exit_status: ExitStatus, stderr: String },
+
+    #[error("Trivy aborted due to user cancel request: {raw_error_message:?}")]
+    Aborted { raw_error_message: String },
+
+    #[error("Trivy command terminated due to timeout: {raw_error_message:?}")]
+    Timeout { raw_error_message: String },
+
+    #[error("Cannot parse trivy report: {raw_error}")]
+    InvalidReport { raw_error: serde_json::Error },
+}
+
+impl From<Error> for TrivyError {
+    fn from(value: Error) -> Self {
+        TrivyError::ExecutionError { raw_error: value }
+    }
+}
+
+/// Severities as reported by trivy, ordered from the least to the most severe
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum VulnerabilitySeverity {
+    #[serde(alias = "unknown")]
+    Unknown,
+    #[serde(alias = "low")]
+    Low,
+    #[serde(alias = "medium")]
+    Medium,
+    #[serde(alias = "high")]
+    High,
+    #[serde(alias = "critical")]
+    Critical,
+}
+
+impl Display for VulnerabilitySeverity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            VulnerabilitySeverity::Unknown => "UNKNOWN",
+            VulnerabilitySeverity::Low => "LOW",
+            VulnerabilitySeverity::Medium => "MEDIUM",
+            VulnerabilitySeverity::High => "HIGH",
+            VulnerabilitySeverity::Critical => "CRITICAL",
+        })
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Vulnerability {
+    #[serde(rename = "VulnerabilityID")]
+    pub id: String,
+    #[serde(rename = "PkgName")]
+    pub package_name: String,
+    #[serde(rename = "InstalledVersion", default)]
+    pub installed_version: String,
+    #[serde(rename = "FixedVersion", default)]
+    pub fixed_version: Option<String>,
+    #[serde(rename = "Severity")]
+    pub severity: VulnerabilitySeverity,
+}
+
+impl Vulnerability {
+    /// Vulnerabilities at or above the blocking severity block the deployment, unless they are allowed
+    pub fn is_blocking(&self, blocking_severity: VulnerabilitySeverity, allowlist: &[String]) -> bool {
+        self.severity >= blocking_severity && !allowlist.iter().any(|id| id.eq_ignore_ascii_case(&self.id))
+    }
+}
+
+#[derive(Deserialize)]
+struct TrivyReport {
+    #[serde(rename = "Results", default)]
+    results: Vec<TrivyResult>,
+}
+
+#[derive(Deserialize)]
+struct TrivyResult {
+    #[serde(rename = "Vulnerabilities", default)]
+    vulnerabilities: Option<Vec<Vulnerability>>,
+}
+
+/// Vulnerability scanner of container images.
+/// The vulnerability database is looked up in the trivy cache directory (`TRIVY_CACHE_DIR`), and can be pre-seeded
+/// to scan without network access to the database repository by setting `TRIVY_SKIP_DB_UPDATE=true`.
+#[derive(Debug, Default)]
+pub struct Trivy {}
+
+impl Trivy {
+    pub fn new() -> Self {
+        Trivy {}
+    }
+
+    /// Vulnerabilities of an image pushed to a registry, credentials are taken from the registry url if any,
+    /// or from the docker config otherwise.
+    pub fn scan_image(
+        &self,
+        image_name: &str,
+        registry: &Url,
+        cmd_killer: &CommandKiller,
+    ) -> Result<Vec<Vulnerability>, TrivyError> {
+        let username = urlencoding::decode(registry.username()).unwrap_or_default().to_string();
+        let password = urlencoding::decode(registry.password().unwrap_or_default())
+            .unwrap_or_default()
+            .to_string();
+        let envs = match registry.password() {
+            Some(_) => vec![
+                ("TRIVY_USERNAME", username.as_str()),
+                ("TRIVY_PASSWORD", password.as_str()),
+            ],
+            None => vec![],
+        };
+
+        let mut report = String::new();
+        let mut stderr = String::new();
+        let ret = trivy_exec(
+            &[
+                "image",
+                "--quiet",
+                "--format",
+                "json",
+                "--scanners",
+                "vuln",
+                "--timeout",
+                "15m",
+                image_name,
+            ],
+            &envs,
+            &mut |line| report.push_str(&line),
+            &mut |line| {
+                stderr.push_str(&line);
+                stderr.push('\n');
+            },
+            cmd_killer,
+        );
+
+        match ret {
+            Ok(_) => parse_trivy_report(&report),
+            Err(TrivyError::ExitStatusError { exit_status, .. }) => {
+                Err(TrivyError::ExitStatusError { exit_status, stderr })
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn parse_trivy_report(report: &str) -> Result<Vec<Vulnerability>, TrivyError> {
+    let report: TrivyReport =
+        serde_json::from_str(report).map_err(|err| TrivyError::InvalidReport { raw_error: err })?;
+
+    Ok(report
+        .results
+        .into_iter()
+        .flat_map(|result| result.vulnerabilities.unwrap_or_default())
+        .collect())
+}
+
+fn trivy_exec<F, X>(
+    args: &[&str],
+    envs: &[(&str, &str)],
+    stdout_output: &mut F,
+    stderr_output: &mut X,
+    cmd_killer: &CommandKiller,
+) -> Result<(), TrivyError>
+where
+    F: FnMut(String),
+    X: FnMut(String),
+{
+    let mut cmd = QoveryCommand::new("trivy", args, envs);
+    let ret = cmd.exec_with_abort(stdout_output, stderr_output, cmd_killer);
+
+    match ret {
+        Ok(_) => Ok(()),
+        Err(CommandError::TimeoutError(msg)) => Err(TrivyError::Timeout { raw_error_message: msg }),
+        Err(CommandError::Killed(msg)) => Err(TrivyError::Aborted { raw_error_message: msg }),
+        Err(CommandError::ExitStatusError(err)) => Err(TrivyError::ExitStatusError {
+            exit_status: err,
+            stderr: String::new(),
+        }),
+        Err(CommandError::ExecutionError(err)) => Err(TrivyError::ExecutionError { raw_error: err }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cmd::trivy::{parse_trivy_report, Vulnerability, VulnerabilitySeverity};
+
+    #[test]
+    fn test_parse_trivy_report() {
+        // setup:
+        let report = r#"{
+          "SchemaVersion": 2,
+          "ArtifactName": "registry.qovery.com/app-z1:v1",
+          "ArtifactType": "container_image",
+          "Results": [
+            {
+              "Target": "registry.qovery.com/app-z1:v1 (alpine 3.17.0)",
+              "Class": "os-pkgs",
+              "Type": "alpine",
+              "Vulnerabilities": [
+                {
+                  "VulnerabilityID": "CVE-2023-0286",
+                  "PkgName": "libcrypto3",
+                  "InstalledVersion": "3.0.7-r0",
+                  "FixedVersion": "3.0.8-r0",
+                  "Severity": "HIGH",
+                  "Title": "openssl: X.400 address type confusion in X.509 GeneralName"
+                },
+                {
+                  "VulnerabilityID": "CVE-2022-48174",
+                  "PkgName": "busybox",
+                  "InstalledVersion": "1.35.0-r29",
+                  "Severity": "CRITICAL"
+                }
+              ]
+            },
+            {
+              "Target": "app/package-lock.json",
+              "Class": "lang-pkgs",
+              "Type": "npm"
+            }
+          ]
+        }"#;
+
+        // execute:
+        let vulnerabilities = parse_trivy_report(report).unwrap();
+
+        // verify:
+        assert_eq!(
+            vulnerabilities,
+            vec![
+                Vulnerability {
+                    id: "CVE-2023-0286".to_string(),
+                    package_name: "libcrypto3".to_string(),
+                    installed_version: "3.0.7-r0".to_string(),
+                    fixed_version: Some("3.0.8-r0".to_string()),
+                    severity: VulnerabilitySeverity::High,
+                },
+                Vulnerability {
+                    id: "CVE-2022-48174".to_string(),
+                    package_name: "busybox".to_string(),
+                    installed_version: "1.35.0-r29".to_string(),
+                    fixed_version: None,
+                    severity: VulnerabilitySeverity::Critical,
+                },
+            ]
+        );
+        assert!(parse_trivy_report("{}").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_vulnerability_is_blocking() {
+        // setup:
+        struct TestCase<'a> {
+            severity: VulnerabilitySeverity,
+            allowlist: Vec<String>,
+            expected: bool,
+            description: &'a str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                severity: VulnerabilitySeverity::Critical,
+                allowlist: vec![],
+                expected: true,
+                description: "above blocking severity",
+            },
+            TestCase {
+                severity: VulnerabilitySeverity::High,
+                allowlist: vec![],
+                expected: true,
+                description: "at blocking severity",
+            },
+            TestCase {
+                severity: VulnerabilitySeverity::Medium,
+                allowlist: vec![],
+                expected: false,
+                description: "below blocking severity",
+            },
+            TestCase {
+                severity: VulnerabilitySeverity::Critical,
+                allowlist: vec!["cve-2023-0286".to_string()],
+                expected: false,
+                description: "allowed",
+            },
+            TestCase {
+                severity: VulnerabilitySeverity::Critical,
+                allowlist: vec!["CVE-2022-48174".to_string()],
+                expected: true,
+                description: "another vulnerability allowed",
+            },
+        ];
+
+        for tc in test_cases {
+            let vulnerability = Vulnerability {
+                id: "CVE-2023-0286".to_string(),
+                package_name: "libcrypto3".to_string(),
+                installed_version: "3.0.7-r0".to_string(),
+                fixed_version: None,
+                severity: tc.severity,
+            };
+
+            // execute:
+            let result = vulnerability.is_blocking(VulnerabilitySeverity::High, &tc.allowlist);
+
+            // verify:
+            assert_eq!(tc.expected, result, "{}", tc.description);
+        }
+    }
+}
@@ -9,3 +9,7 @@ pub const SCW_DEFAULT_PROJECT_ID: &str = "SCW_DEFAULT_PROJECT_ID";
 pub const GCP_PROJECT: &str = "GOOGLE_PROJECT";
 pub const GCP_REGION: &str = "GOOGLE_REGION";
 pub const GCP_CREDENTIALS: &str = "GOOGLE_CREDENTIALS";
+pub const AZURE_CLIENT_ID: &str = "ARM_CLIENT_ID";
+pub const AZURE_CLIENT_SECRET: &str = "ARM_CLIENT_SECRET";
+pub const AZURE_TENANT_ID: &str = "ARM_TENANT_ID";
+pub const AZURE_SUBSCRIPTION_ID: &str = "ARM_SUBSCRIPTION_ID";
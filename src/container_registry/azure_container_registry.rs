@@ -0,0 +1,138 @@
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::build_platform::Image;
+use crate::container_registry::errors::ContainerRegistryError;
+use crate::container_registry::generic_cr::{GenericCr, GenericCrFlavor};
+use crate::container_registry::{ContainerRegistry, ContainerRegistryInfo, Kind, Repository, RepositoryInfo};
+use crate::io_models::context::Context;
+use crate::models::azure::AzureCredentials;
+
+/// Azure Container Registry, it implements the OCI distribution API so all image operations are delegated
+/// to the generic registry, authenticated with the cluster service principal.
+/// The registry itself is created alongside the AKS cluster by Terraform.
+pub struct AzureContainerRegistry {
+    registry: GenericCr,
+}
+
+impl AzureContainerRegistry {
+    pub fn new(
+        context: Context,
+        id: &str,
+        long_id: Uuid,
+        name: &str,
+        registry_name: &str,
+        credentials: &AzureCredentials,
+    ) -> Result<AzureContainerRegistry, ContainerRegistryError> {
+        let registry = GenericCr::new(
+            context,
+            id,
+            long_id,
+            name,
+            &Self::registry_url(registry_name),
+            None,
+            Some(&credentials.client_id),
+            Some(&credentials.client_secret),
+            GenericCrFlavor::Distribution,
+        )?;
+
+        Ok(AzureContainerRegistry { registry })
+    }
+
+    /// ACR login server is derived from the registry name, registry name being alphanumeric only
+    fn registry_url(registry_name: &str) -> String {
+        format!("https://{}.azurecr.io", registry_name.to_lowercase())
+    }
+}
+
+impl ContainerRegistry for AzureContainerRegistry {
+    fn context(&self) -> &Context {
+        self.registry.context()
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::AzureCr
+    }
+
+    fn id(&self) -> &str {
+        self.registry.id()
+    }
+
+    fn long_id(&self) -> &Uuid {
+        self.registry.long_id()
+    }
+
+    fn name(&self) -> &str {
+        self.registry.name()
+    }
+
+    fn registry_info(&self) -> &ContainerRegistryInfo {
+        self.registry.registry_info()
+    }
+
+    fn create_registry(&self) -> Result<(), ContainerRegistryError> {
+        // Nothing to do, the registry is created along with the cluster
+        Ok(())
+    }
+
+    fn create_repository(
+        &self,
+        repository_name: &str,
+        image_retention_time_in_seconds: u32,
+        resource_ttl: Option<Duration>,
+    ) -> Result<(Repository, RepositoryInfo), ContainerRegistryError> {
+        // ACR creates repositories on the fly on first push
+        self.registry
+            .create_repository(repository_name, image_retention_time_in_seconds, resource_ttl)
+    }
+
+    fn get_repository(&self, repository_name: &str) -> Result<Repository, ContainerRegistryError> {
+        self.registry.get_repository(repository_name)
+    }
+
+    fn delete_repository(&self, repository_name: &str) -> Result<(), ContainerRegistryError> {
+        // https://learn.microsoft.com/en-us/rest/api/containerregistry/repository/delete
+        let mut url = self.registry.base_url();
+        url.set_path(&format!("/acr/v1/{}", repository_name.trim_matches('/')));
+
+        let response =
+            self.registry
+                .send(Method::DELETE, url, None, &format!("repository:{repository_name}:delete"))?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(()),
+            status if status.is_success() => Ok(()),
+            status => Err(ContainerRegistryError::CannotDeleteRepository {
+                registry_name: self.name().to_string(),
+                repository_name: repository_name.to_string(),
+                raw_error_message: format!("Registry answered with status {status}"),
+            }),
+        }
+    }
+
+    fn delete_image(&self, image: &Image) -> Result<(), ContainerRegistryError> {
+        self.registry.delete_image(image)
+    }
+
+    fn image_exists(&self, image: &Image) -> bool {
+        self.registry.image_exists(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container_registry::azure_container_registry::AzureContainerRegistry;
+
+    #[test]
+    fn test_azure_container_registry_url() {
+        assert_eq!(
+            "https://qoveryz1234567.azurecr.io",
+            AzureContainerRegistry::registry_url("qoveryz1234567")
+        );
+        assert_eq!(
+            "https://qoveryz1234567.azurecr.io",
+            AzureContainerRegistry::registry_url("QoveryZ1234567")
+        );
+    }
+}
@@ -0,0 +1,744 @@
+use crate::build_platform::Image;
+use crate::container_registry::errors::ContainerRegistryError;
+use crate::container_registry::{ContainerRegistry, ContainerRegistryInfo, Kind, Repository, RepositoryInfo};
+use crate::io_models::context::Context;
+use base64::engine::general_purpose;
+use base64::Engine;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::{ACCEPT, WWW_AUTHENTICATE};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use url::Url;
+use uuid::Uuid;
+
+// Manifest media types we accept when looking up an image, the digest returned by the registry
+// depends on the media type negotiated, so we need to accept all of them to get the one that has been pushed
+const MANIFEST_MEDIA_TYPES: &str = "application/vnd.oci.image.index.v1+json, \
+application/vnd.oci.image.manifest.v1+json, \
+application/vnd.docker.distribution.manifest.list.v2+json, \
+application/vnd.docker.distribution.manifest.v2+json";
+const DOCKER_CONTENT_DIGEST_HEADER: &str = "Docker-Content-Digest";
+const HARBOR_API_PATH: &str = "/api/v2.0";
+
+/// Flavor of the registry behind the OCI distribution API, it drives how repositories are managed
+/// as the distribution API doesn't expose any repository creation nor deletion.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GenericCrFlavor {
+    /// Plain registry (registry:2, Docker Hub, GitLab, ...), repositories are created on first push
+    #[default]
+    Distribution,
+    /// Harbor, one project is created per repository
+    Harbor,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum AuthChallenge {
+    Basic,
+    Bearer {
+        realm: String,
+        service: Option<String>,
+        scope: Option<String>,
+    },
+}
+
+impl AuthChallenge {
+    /// Parse a `WWW-Authenticate` header as returned by the registry on 401
+    /// i.e: Bearer realm="https://auth.docker.io/token",service="registry.docker.io",scope="repository:qovery/app:pull"
+    fn from_header(header: &str) -> Option<AuthChallenge> {
+        let (scheme, params) = header.trim().split_once(' ').unwrap_or((header.trim(), ""));
+
+        if scheme.eq_ignore_ascii_case("basic") {
+            return Some(AuthChallenge::Basic);
+        }
+        if !scheme.eq_ignore_ascii_case("bearer") {
+            return None;
+        }
+
+        let mut params = Self::parse_params(params);
+        Some(AuthChallenge::Bearer {
+            realm: params.remove("realm")?,
+            service: params.remove("service"),
+            scope: params.remove("scope"),
+        })
+    }
+
+    // Values are comma separated, but scope values can contain commas as well (i.e: pull,push)
+    // so we have to take quotes into account
+    fn parse_params(params: &str) -> HashMap<String, String> {
+        let mut parsed = HashMap::new();
+        let mut rest = params.trim();
+
+        while let Some((key, value_and_rest)) = rest.split_once('=') {
+            let key = key.trim().trim_start_matches(',').trim().to_lowercase();
+            let (value, remaining) = match value_and_rest.strip_prefix('"') {
+                Some(quoted) => match quoted.split_once('"') {
+                    Some((value, remaining)) => (value, remaining),
+                    None => (quoted, ""),
+                },
+                None => value_and_rest.split_once(',').unwrap_or((value_and_rest, "")),
+            };
+            parsed.insert(key, value.to_string());
+            rest = remaining.trim_start_matches(',').trim();
+        }
+
+        parsed
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    token: Option<String>,
+    access_token: Option<String>,
+}
+
+pub struct GenericCr {
+    context: Context,
+    id: String,
+    long_id: Uuid,
+    name: String,
+    login: Option<String>,
+    password: Option<String>,
+    flavor: GenericCrFlavor,
+    http: Client,
+    registry_info: ContainerRegistryInfo,
+}
+
+impl GenericCr {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        context: Context,
+        id: &str,
+        long_id: Uuid,
+        name: &str,
+        registry_url: &str,
+        namespace: Option<&str>,
+        login: Option<&str>,
+        password: Option<&str>,
+        flavor: GenericCrFlavor,
+    ) -> Result<GenericCr, ContainerRegistryError> {
+        let mut registry = Url::parse(registry_url).map_err(|_e| ContainerRegistryError::InvalidRegistryUrl {
+            registry_url: registry_url.to_string(),
+        })?;
+        if registry.host_str().is_none() {
+            return Err(ContainerRegistryError::InvalidRegistryUrl {
+                registry_url: registry_url.to_string(),
+            });
+        }
+
+        // Anonymous registries (i.e: local registry:2) don't need any login
+        let mut registry_docker_json_config = None;
+        if let Some(login) = login {
+            let password = password.unwrap_or_default();
+            let _ = registry.set_username(login);
+            let _ = registry.set_password(Some(password));
+
+            if context.docker.login(&registry).is_err() {
+                return Err(ContainerRegistryError::InvalidCredentials);
+            }
+            registry_docker_json_config = Some(Self::get_docker_json_config_raw(
+                registry.host_str().unwrap_or_default(),
+                login,
+                password,
+            ));
+        }
+
+        let http = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| ContainerRegistryError::CannotInstantiateClient {
+                raw_error_message: e.to_string(),
+            })?;
+
+        let namespace = namespace.map(|ns| ns.trim_matches('/').to_string());
+        let registry_info = ContainerRegistryInfo {
+            endpoint: registry,
+            registry_name: name.to_string(),
+            registry_docker_json_config,
+            get_image_name: Box::new(move |img_name| Self::image_name(flavor, namespace.as_deref(), img_name)),
+            get_repository_name: Box::new(|repository_name| repository_name.to_string()),
+        };
+
+        Ok(GenericCr {
+            context,
+            id: id.to_string(),
+            long_id,
+            name: name.to_string(),
+            login: login.map(|l| l.to_string()),
+            password: password.map(|p| p.to_string()),
+            flavor,
+            http,
+            registry_info,
+        })
+    }
+
+    fn image_name(flavor: GenericCrFlavor, namespace: Option<&str>, img_name: &str) -> String {
+        match (flavor, namespace) {
+            // Harbor images have to live in a project, which is the repository we create
+            (GenericCrFlavor::Harbor, _) => format!("{img_name}/{img_name}"),
+            (GenericCrFlavor::Distribution, Some(namespace)) if !namespace.is_empty() => {
+                format!("{namespace}/{img_name}")
+            }
+            (GenericCrFlavor::Distribution, _) => img_name.to_string(),
+        }
+    }
+
+    fn get_docker_json_config_raw(registry_host: &str, login: &str, password: &str) -> String {
+        general_purpose::STANDARD.encode(
+            format!(
+                r#"{{"auths":{{"{}":{{"auth":"{}"}}}}}}"#,
+                registry_host,
+                general_purpose::STANDARD.encode(format!("{login}:{password}").as_bytes())
+            )
+            .as_bytes(),
+        )
+    }
+
+    /// Base url of the registry, without credentials
+    pub(crate) fn base_url(&self) -> Url {
+        let mut url = self.registry_info.endpoint.clone();
+        let _ = url.set_username("");
+        let _ = url.set_password(None);
+        url.set_path("");
+        url
+    }
+
+    fn manifest_url(base_url: &Url, image_name: &str, reference: &str) -> Url {
+        let mut url = base_url.clone();
+        url.set_path(&format!("/v2/{}/manifests/{}", image_name.trim_matches('/'), reference));
+        url
+    }
+
+    fn harbor_url(base_url: &Url, path: &str) -> Url {
+        let mut url = base_url.clone();
+        url.set_path(&format!("{HARBOR_API_PATH}{path}"));
+        url
+    }
+
+    fn with_basic_auth(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.login {
+            Some(login) => request.basic_auth(login, self.password.as_ref()),
+            None => request,
+        }
+    }
+
+    fn get_bearer_token(
+        &self,
+        realm: &str,
+        service: Option<&str>,
+        scope: &str,
+    ) -> Result<String, ContainerRegistryError> {
+        let mut token_url = Url::parse(realm).map_err(|_e| ContainerRegistryError::InvalidRegistryUrl {
+            registry_url: realm.to_string(),
+        })?;
+        if let Some(service) = service {
+            token_url.query_pairs_mut().append_pair("service", service);
+        }
+        token_url.query_pairs_mut().append_pair("scope", scope);
+
+        let response =
+            self.with_basic_auth(self.http.get(token_url))
+                .send()
+                .map_err(|e| ContainerRegistryError::Unknown {
+                    raw_error_message: e.to_string(),
+                })?;
+
+        match response.status() {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(ContainerRegistryError::InvalidCredentials),
+            status if !status.is_success() => Err(ContainerRegistryError::Unknown {
+                raw_error_message: format!("Cannot get registry token, auth server answered with status {status}"),
+            }),
+            _ => {
+                let token = response
+                    .json::<TokenResponse>()
+                    .map_err(|e| ContainerRegistryError::Unknown {
+                        raw_error_message: e.to_string(),
+                    })?;
+                token
+                    .token
+                    .or(token.access_token)
+                    .ok_or(ContainerRegistryError::CannotGetCredentials)
+            }
+        }
+    }
+
+    /// Send a request to the registry, handling the token auth flow of the distribution API:
+    /// the first anonymous call returns a 401 with a challenge telling us how to authenticate.
+    pub(crate) fn send(
+        &self,
+        method: Method,
+        url: Url,
+        accept: Option<&str>,
+        scope: &str,
+    ) -> Result<Response, ContainerRegistryError> {
+        let build_request = || {
+            let request = self.http.request(method.clone(), url.clone());
+            match accept {
+                Some(accept) => request.header(ACCEPT, accept),
+                None => request,
+            }
+        };
+        let to_error = |e: reqwest::Error| ContainerRegistryError::Unknown {
+            raw_error_message: e.to_string(),
+        };
+
+        let response = build_request().send().map_err(to_error)?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        let challenge = response
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|header| header.to_str().ok())
+            .and_then(AuthChallenge::from_header);
+
+        match challenge {
+            Some(AuthChallenge::Bearer {
+                realm,
+                service,
+                scope: challenge_scope,
+            }) => {
+                let token =
+                    self.get_bearer_token(&realm, service.as_deref(), challenge_scope.as_deref().unwrap_or(scope))?;
+                build_request().bearer_auth(token).send().map_err(to_error)
+            }
+            Some(AuthChallenge::Basic) | None => self.with_basic_auth(build_request()).send().map_err(to_error),
+        }
+    }
+
+    fn get_manifest_digest(&self, image: &Image) -> Result<Option<String>, ContainerRegistryError> {
+        let url = Self::manifest_url(&self.base_url(), &image.name, &image.tag);
+        let response = self.send(
+            Method::HEAD,
+            url,
+            Some(MANIFEST_MEDIA_TYPES),
+            &format!("repository:{}:pull", image.name),
+        )?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(ContainerRegistryError::InvalidCredentials),
+            status if status.is_success() => Ok(response
+                .headers()
+                .get(DOCKER_CONTENT_DIGEST_HEADER)
+                .and_then(|digest| digest.to_str().ok())
+                .map(|digest| digest.to_string())),
+            status => Err(ContainerRegistryError::Unknown {
+                raw_error_message: format!(
+                    "Cannot get manifest of image `{}`, status {}",
+                    image.name_with_tag(),
+                    status
+                ),
+            }),
+        }
+    }
+
+    fn get_harbor_project(&self, project_name: &str) -> Result<Option<Repository>, ContainerRegistryError> {
+        let url = Self::harbor_url(&self.base_url(), &format!("/projects/{project_name}"));
+        let response = self
+            .with_basic_auth(self.http.get(url).header("X-Is-Resource-Name", "true"))
+            .send()
+            .map_err(|e| ContainerRegistryError::CannotGetRepository {
+                registry_name: self.name.to_string(),
+                repository_name: project_name.to_string(),
+                raw_error_message: e.to_string(),
+            })?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(self.repository(project_name))),
+            status => Err(ContainerRegistryError::CannotGetRepository {
+                registry_name: self.name.to_string(),
+                repository_name: project_name.to_string(),
+                raw_error_message: format!("Harbor answered with status {status}"),
+            }),
+        }
+    }
+
+    fn create_harbor_project(&self, project_name: &str) -> Result<Repository, ContainerRegistryError> {
+        let url = Self::harbor_url(&self.base_url(), "/projects");
+        let body = serde_json::json!({ "project_name": project_name, "metadata": { "public": "false" } });
+        let response = self
+            .with_basic_auth(self.http.post(url).json(&body))
+            .send()
+            .map_err(|e| ContainerRegistryError::CannotCreateRepository {
+                registry_name: self.name.to_string(),
+                repository_name: project_name.to_string(),
+                raw_error_message: e.to_string(),
+            })?;
+
+        match response.status() {
+            // Project has been created in between by someone else, nothing to do
+            StatusCode::CONFLICT => Ok(self.repository(project_name)),
+            status if status.is_success() => Ok(self.repository(project_name)),
+            status => Err(ContainerRegistryError::CannotCreateRepository {
+                registry_name: self.name.to_string(),
+                repository_name: project_name.to_string(),
+                raw_error_message: format!(
+                    "Harbor answered with status {}: {}",
+                    status,
+                    response.text().unwrap_or_default()
+                ),
+            }),
+        }
+    }
+
+    fn delete_harbor_project(&self, project_name: &str) -> Result<(), ContainerRegistryError> {
+        let to_error = |raw_error_message: String| ContainerRegistryError::CannotDeleteRepository {
+            registry_name: self.name.to_string(),
+            repository_name: project_name.to_string(),
+            raw_error_message,
+        };
+
+        // Harbor refuses to delete a project that still contains repositories
+        let repository_url = Self::harbor_url(
+            &self.base_url(),
+            &format!("/projects/{project_name}/repositories/{project_name}"),
+        );
+        let response = self
+            .with_basic_auth(self.http.delete(repository_url).header("X-Is-Resource-Name", "true"))
+            .send()
+            .map_err(|e| to_error(e.to_string()))?;
+        if !response.status().is_success() && response.status() != StatusCode::NOT_FOUND {
+            return Err(to_error(format!("Harbor answered with status {}", response.status())));
+        }
+
+        let project_url = Self::harbor_url(&self.base_url(), &format!("/projects/{project_name}"));
+        let response = self
+            .with_basic_auth(self.http.delete(project_url).header("X-Is-Resource-Name", "true"))
+            .send()
+            .map_err(|e| to_error(e.to_string()))?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Err(ContainerRegistryError::RepositoryDoesntExistInRegistry {
+                registry_name: self.name.to_string(),
+                repository_name: project_name.to_string(),
+            }),
+            status if status.is_success() => Ok(()),
+            status => Err(to_error(format!("Harbor answered with status {status}"))),
+        }
+    }
+
+    fn repository(&self, repository_name: &str) -> Repository {
+        Repository {
+            registry_id: self.id.to_string(),
+            id: repository_name.to_string(),
+            name: repository_name.to_string(),
+            uri: Some(format!(
+                "{}/{}",
+                self.registry_info.endpoint.host_str().unwrap_or_default(),
+                repository_name
+            )),
+            ttl: None,
+            labels: None,
+        }
+    }
+}
+
+impl ContainerRegistry for GenericCr {
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::GenericCr
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn long_id(&self) -> &Uuid {
+        &self.long_id
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn registry_info(&self) -> &ContainerRegistryInfo {
+        &self.registry_info
+    }
+
+    fn create_registry(&self) -> Result<(), ContainerRegistryError> {
+        // Nothing to do, the registry is managed by the user
+        Ok(())
+    }
+
+    fn create_repository(
+        &self,
+        repository_name: &str,
+        _image_retention_time_in_seconds: u32,
+        _resource_ttl: Option<Duration>,
+    ) -> Result<(Repository, RepositoryInfo), ContainerRegistryError> {
+        match self.flavor {
+            // Distribution API creates repositories on the fly on first push
+            GenericCrFlavor::Distribution => Ok((self.repository(repository_name), RepositoryInfo { created: false })),
+            GenericCrFlavor::Harbor => {
+                info!("Get/Create harbor project for {}", repository_name);
+                if let Some(project) = self.get_harbor_project(repository_name)? {
+                    return Ok((project, RepositoryInfo { created: false }));
+                }
+
+                let project = self.create_harbor_project(repository_name)?;
+                Ok((project, RepositoryInfo { created: true }))
+            }
+        }
+    }
+
+    fn get_repository(&self, repository_name: &str) -> Result<Repository, ContainerRegistryError> {
+        match self.flavor {
+            GenericCrFlavor::Distribution => Ok(self.repository(repository_name)),
+            GenericCrFlavor::Harbor => self.get_harbor_project(repository_name)?.ok_or_else(|| {
+                ContainerRegistryError::RepositoryDoesntExistInRegistry {
+                    registry_name: self.name.to_string(),
+                    repository_name: repository_name.to_string(),
+                }
+            }),
+        }
+    }
+
+    fn delete_repository(&self, repository_name: &str) -> Result<(), ContainerRegistryError> {
+        match self.flavor {
+            // Distribution API doesn't allow to delete a repository, only its manifests
+            GenericCrFlavor::Distribution => Ok(()),
+            GenericCrFlavor::Harbor => self.delete_harbor_project(repository_name),
+        }
+    }
+
+    fn delete_image(&self, image: &Image) -> Result<(), ContainerRegistryError> {
+        let to_error = |raw_error_message: String| ContainerRegistryError::CannotDeleteImage {
+            registry_name: self.name.to_string(),
+            repository_name: image.repository_name().to_string(),
+            image_name: image.name_with_tag(),
+            raw_error_message,
+        };
+
+        // Manifests can only be deleted by digest, not by tag
+        let digest = match self.get_manifest_digest(image) {
+            Ok(Some(digest)) => digest,
+            Ok(None) => return Ok(()),
+            Err(err) => return Err(to_error(err.to_string())),
+        };
+
+        let url = Self::manifest_url(&self.base_url(), &image.name, &digest);
+        let response = self
+            .send(Method::DELETE, url, None, &format!("repository:{}:delete", image.name))
+            .map_err(|err| to_error(err.to_string()))?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(()),
+            StatusCode::METHOD_NOT_ALLOWED => Err(to_error(
+                "Deletion is disabled on this registry (i.e: REGISTRY_STORAGE_DELETE_ENABLED=false)".to_string(),
+            )),
+            status if status.is_success() => Ok(()),
+            status => Err(to_error(format!("Registry answered with status {status}"))),
+        }
+    }
+
+    fn image_exists(&self, image: &Image) -> bool {
+        matches!(self.get_manifest_digest(image), Ok(Some(_)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container_registry::generic_cr::{AuthChallenge, GenericCr, GenericCrFlavor};
+    use url::Url;
+
+    #[test]
+    fn test_generic_cr_auth_challenge_parsing() {
+        // setup:
+        struct TestCase<'a> {
+            input: &'a str,
+            expected: Option<AuthChallenge>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: r#"Basic realm="Registry Realm""#,
+                expected: Some(AuthChallenge::Basic),
+            },
+            TestCase {
+                input: r#"Bearer realm="https://auth.docker.io/token",service="registry.docker.io",scope="repository:qovery/app:pull,push""#,
+                expected: Some(AuthChallenge::Bearer {
+                    realm: "https://auth.docker.io/token".to_string(),
+                    service: Some("registry.docker.io".to_string()),
+                    scope: Some("repository:qovery/app:pull,push".to_string()),
+                }),
+            },
+            TestCase {
+                input: r#"bearer realm="https://gitlab.com/jwt/auth", service="container_registry""#,
+                expected: Some(AuthChallenge::Bearer {
+                    realm: "https://gitlab.com/jwt/auth".to_string(),
+                    service: Some("container_registry".to_string()),
+                    scope: None,
+                }),
+            },
+            TestCase {
+                input: r#"Bearer service="registry.docker.io""#,
+                expected: None,
+            },
+            TestCase {
+                input: r#"Negotiate abcdef"#,
+                expected: None,
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = AuthChallenge::from_header(tc.input);
+
+            // verify:
+            assert_eq!(tc.expected, result, "case: {}", tc.input);
+        }
+    }
+
+    #[test]
+    fn test_generic_cr_image_name() {
+        // setup:
+        struct TestCase<'a> {
+            flavor: GenericCrFlavor,
+            namespace: Option<&'a str>,
+            expected: &'a str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                flavor: GenericCrFlavor::Distribution,
+                namespace: None,
+                expected: "app",
+            },
+            TestCase {
+                flavor: GenericCrFlavor::Distribution,
+                namespace: Some(""),
+                expected: "app",
+            },
+            TestCase {
+                flavor: GenericCrFlavor::Distribution,
+                namespace: Some("qovery/team"),
+                expected: "qovery/team/app",
+            },
+            TestCase {
+                flavor: GenericCrFlavor::Harbor,
+                namespace: Some("qovery"),
+                expected: "app/app",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = GenericCr::image_name(tc.flavor, tc.namespace, "app");
+
+            // verify:
+            assert_eq!(tc.expected, result);
+        }
+    }
+
+    #[test]
+    fn test_generic_cr_urls() {
+        // setup:
+        let base_url = Url::parse("https://registry.qovery.io").unwrap();
+
+        // execute & verify:
+        assert_eq!(
+            GenericCr::manifest_url(&base_url, "qovery/app", "v1.0").as_str(),
+            "https://registry.qovery.io/v2/qovery/app/manifests/v1.0"
+        );
+        assert_eq!(
+            GenericCr::manifest_url(&base_url, "app", "sha256:abcdef").as_str(),
+            "https://registry.qovery.io/v2/app/manifests/sha256:abcdef"
+        );
+        assert_eq!(
+            GenericCr::harbor_url(&base_url, "/projects/app").as_str(),
+            "https://registry.qovery.io/api/v2.0/projects/app"
+        );
+    }
+
+    // start a local registry with deletion enabled to run this test
+    // docker run --rm -d -p 5000:5000 -e REGISTRY_STORAGE_DELETE_ENABLED=true --name registry registry:2
+    #[cfg(feature = "test-local-docker")]
+    #[test]
+    fn test_generic_cr_local_registry() {
+        use crate::build_platform::Image;
+        use crate::cmd::command::CommandKiller;
+        use crate::cmd::docker::{ContainerImage, Docker};
+        use crate::container_registry::ContainerRegistry;
+        use crate::engine_task::qovery_api::StaticQoveryApi;
+        use crate::events::{EnvironmentStep, EventDetails, Stage, Transmitter};
+        use crate::io_models::context::Context;
+        use crate::io_models::QoveryIdentifier;
+        use std::collections::HashMap;
+        use std::sync::Arc;
+        use uuid::Uuid;
+
+        // setup:
+        let docker = Arc::new(Docker::new(None).unwrap());
+        let context = Context::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4().to_string(),
+            "/tmp".to_string(),
+            "/tmp".to_string(),
+            true,
+            vec![],
+            None,
+            docker.clone(),
+            Arc::new(StaticQoveryApi {
+                versions: HashMap::new(),
+            }),
+            EventDetails::new(
+                None,
+                QoveryIdentifier::new_random(),
+                QoveryIdentifier::new_random(),
+                Uuid::new_v4().to_string(),
+                Stage::Environment(EnvironmentStep::Deploy),
+                Transmitter::TaskManager(Uuid::new_v4(), "".to_string()),
+            ),
+        );
+        let registry = GenericCr::new(
+            context,
+            "local",
+            Uuid::new_v4(),
+            "local",
+            "http://localhost:5000",
+            Some("qovery"),
+            None,
+            None,
+            GenericCrFlavor::Distribution,
+        )
+        .unwrap();
+
+        let source = ContainerImage::new(
+            Url::parse("https://public.ecr.aws").unwrap(),
+            "r3m4q3r9/pub-mirror-debian".to_string(),
+            vec!["11.6-ci".to_string()],
+        );
+        let image = Image {
+            name: (registry.registry_info().get_image_name)("debian"),
+            tag: Uuid::new_v4().to_string(),
+            registry_url: Url::parse("http://localhost:5000").unwrap(),
+            repository_name: "debian".to_string(),
+            ..Default::default()
+        };
+        let destination = ContainerImage::new(image.registry_url.clone(), image.name.clone(), vec![image.tag.clone()]);
+        docker
+            .pull(&source, &mut |_| {}, &mut |_| {}, &CommandKiller::never())
+            .unwrap();
+        docker
+            .tag(&source, &destination, &mut |_| {}, &mut |_| {}, &CommandKiller::never())
+            .unwrap();
+        docker
+            .push(&destination, &mut |_| {}, &mut |_| {}, &CommandKiller::never())
+            .unwrap();
+
+        // execute & verify:
+        assert!(registry.image_exists(&image));
+        assert!(registry.delete_image(&image).is_ok());
+        assert!(!registry.image_exists(&image));
+        // deleting an image already deleted should not fail
+        assert!(registry.delete_image(&image).is_ok());
+    }
+}
@@ -11,8 +11,10 @@ use crate::events::{EventDetails, Stage, Transmitter};
 use crate::io_models::context::Context;
 use crate::io_models::QoveryIdentifier;
 
+pub mod azure_container_registry;
 pub mod ecr;
 pub mod errors;
+pub mod generic_cr;
 pub mod google_artifact_registry;
 pub mod scaleway_container_registry;
 
@@ -108,6 +110,8 @@ pub enum Kind {
     Ecr,
     ScalewayCr,
     GcpArtifactRegistry,
+    GenericCr,
+    AzureCr,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -2,14 +2,19 @@ use crate::cloud_provider::helm::{ChartInfo, HelmAction, HelmChartNamespaces};
 use crate::cloud_provider::service::{Action, Service};
 use crate::cloud_provider::DeploymentTarget;
 use crate::deployment_action::deploy_helm::HelmDeployment;
+use crate::deployment_action::migrate_volume_storage_class::MigrateVolumeStorageClassAction;
 use crate::deployment_action::pause_service::PauseServiceAction;
+use crate::deployment_action::plan::{plan_image, plan_storage_resize, PlannedChange};
+use crate::deployment_action::progressive_rollout::ProgressiveRollout;
+use crate::deployment_action::restore_volume_snapshot::RestoreVolumeSnapshotAction;
 use crate::deployment_action::DeploymentAction;
 use crate::deployment_report::application::reporter::ApplicationDeploymentReporter;
 use crate::deployment_report::execute_long_deployment;
 use crate::errors::{CommandError, EngineError};
-use crate::events::{EngineEvent, EnvironmentStep, EventMessage, Stage};
+use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage};
 use crate::kubers_utils::{kube_delete_all_from_selector, KubeDeleteMode};
 use crate::models::application::{get_application_with_invalid_storage_size, Application, ApplicationService};
+use crate::models::deployment_strategy::DeploymentTeraContext;
 use crate::models::types::{CloudProvider, ToTeraContext};
 use crate::runtime::block_on;
 use k8s_openapi::api::core::v1::PersistentVolumeClaim;
@@ -26,7 +31,7 @@ where
     Application<T>: ToTeraContext,
 {
     fn on_create(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
-        let long_task = |_logger: &EnvProgressLogger| -> Result<(), Box<EngineError>> {
+        let long_task = |logger: &EnvProgressLogger| -> Result<(), Box<EngineError>> {
             let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
             // If the service have been paused, we must ensure we un-pause it first as hpa will not kick in
             let _ = PauseServiceAction::new(
@@ -37,6 +42,14 @@ where
             )
             .unpause_if_needed(target);
 
+            MigrateVolumeStorageClassAction::new(
+                self.kube_label_selector(),
+                self.volume_storage_classes(),
+                Duration::from_secs(5 * 60),
+                event_details.clone(),
+            )
+            .migrate(target, logger)?;
+
             match get_application_with_invalid_storage_size(
                 self,
                 &target.kube,
@@ -60,25 +73,26 @@ where
                 )),
             };
 
-            let chart = ChartInfo {
-                name: self.helm_release_name(),
-                path: self.workspace_directory().to_string(),
-                namespace: HelmChartNamespaces::Custom,
-                custom_namespace: Some(target.environment.namespace().to_string()),
-                timeout_in_seconds: self.startup_timeout().as_secs() as i64,
-                k8s_selector: Some(self.kube_label_selector()),
-                ..Default::default()
-            };
+            RestoreVolumeSnapshotAction::new(
+                self.kube_label_selector(),
+                self.volume_snapshot_restores(),
+                Duration::from_secs(5 * 60),
+                event_details.clone(),
+            )
+            .restore(target, logger)?;
 
-            let helm = HelmDeployment::new(
+            let rollout = ProgressiveRollout::new(
+                self.long_id(),
+                self.kube_name(),
+                self.min_instances(),
+                self.is_stateful(),
+                self.advanced_settings().to_container_advanced_settings(),
+                self.track_version(),
                 event_details.clone(),
-                self.to_tera_context(target)?,
-                PathBuf::from(self.helm_chart_dir()),
-                None,
-                chart,
             );
-
-            helm.on_create(target)?;
+            rollout.deploy(target, logger, &|deployment| {
+                helm_deployment(self, target, event_details.clone(), deployment)
+            })?;
 
             Ok(())
         };
@@ -86,6 +100,25 @@ where
         execute_long_deployment(ApplicationDeploymentReporter::new(self, target, Action::Create), long_task)
     }
 
+    fn on_plan(&self, target: &DeploymentTarget) -> Result<Vec<PlannedChange>, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Plan));
+        let mut changes = vec![plan_image(target.container_registry, &self.build().image)];
+
+        if let Ok(invalid_statefulset_storage) = get_application_with_invalid_storage_size(
+            self,
+            &target.kube,
+            target.environment.namespace(),
+            &event_details,
+        ) {
+            changes.extend(plan_storage_resize(invalid_statefulset_storage));
+        }
+
+        let helm = helm_deployment(self, target, event_details, &self.deployment_tera_context())?;
+        changes.push(helm.plan_release(target)?);
+
+        Ok(changes)
+    }
+
     fn on_pause(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
         execute_long_deployment(
             ApplicationDeploymentReporter::new(self, target, Action::Pause),
@@ -191,3 +224,27 @@ where
         )
     }
 }
+
+fn helm_deployment<T: CloudProvider>(
+    application: &Application<T>,
+    target: &DeploymentTarget,
+    event_details: EventDetails,
+    deployment: &DeploymentTeraContext,
+) -> Result<HelmDeployment, Box<EngineError>>
+where
+    Application<T>: ToTeraContext,
+{
+    let mut tera_context = application.to_tera_context(target)?;
+    tera_context.insert("deployment", deployment);
+
+    Ok(HelmDeployment::new_for_service(
+        event_details,
+        tera_context,
+        PathBuf::from(application.helm_chart_dir()),
+        application.helm_release_name(),
+        application.workspace_directory(),
+        target,
+        Some(application.startup_timeout()),
+        Some(application.kube_label_selector()),
+    ))
+}
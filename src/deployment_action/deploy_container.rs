@@ -2,14 +2,20 @@ use crate::cloud_provider::helm::{ChartInfo, HelmAction, HelmChartNamespaces};
 use crate::cloud_provider::service::{Action, Service};
 use crate::cloud_provider::DeploymentTarget;
 use crate::deployment_action::deploy_helm::HelmDeployment;
+use crate::deployment_action::migrate_volume_storage_class::MigrateVolumeStorageClassAction;
 use crate::deployment_action::pause_service::PauseServiceAction;
+use crate::deployment_action::plan::{plan_mirrored_image, plan_storage_resize, PlannedChange};
+use crate::deployment_action::progressive_rollout::ProgressiveRollout;
+use crate::deployment_action::restore_volume_snapshot::RestoreVolumeSnapshotAction;
+use crate::deployment_action::scan_image::scan_image_if_enabled;
 use crate::deployment_action::DeploymentAction;
 use crate::deployment_report::application::reporter::ApplicationDeploymentReporter;
 use crate::deployment_report::{execute_long_deployment, DeploymentTaskImpl};
 use crate::errors::{CommandError, EngineError};
-use crate::events::{EngineEvent, EnvironmentStep, EventMessage, Stage};
+use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage};
 use crate::kubers_utils::{kube_delete_all_from_selector, KubeDeleteMode};
 use crate::models::container::{get_container_with_invalid_storage_size, Container, ContainerService};
+use crate::models::deployment_strategy::DeploymentTeraContext;
 use crate::models::types::{CloudProvider, ToTeraContext};
 use crate::runtime::block_on;
 use k8s_openapi::api::core::v1::PersistentVolumeClaim;
@@ -36,7 +42,7 @@ where
 
         // We first mirror the image if needed
         let pre_task = |logger: &EnvProgressLogger| -> Result<TaskContext, Box<EngineError>> {
-            mirror_image_if_necessary(
+            let mirrored_image = mirror_image_if_necessary(
                 self.long_id(),
                 &self.source,
                 self.source.tag_for_mirror(self.long_id()),
@@ -45,6 +51,16 @@ where
                 event_details.clone(),
                 metrics_registry.clone(),
             )?;
+            scan_image_if_enabled(
+                &mirrored_image.image_name(),
+                &mirrored_image.registry,
+                &self.advanced_settings().security_vulnerability_allowlist,
+                target.kubernetes.advanced_settings(),
+                &|msg| logger.info(msg),
+                &|msg| logger.recap(msg),
+                event_details.clone(),
+                target.should_abort,
+            )?;
 
             let last_image = block_on(get_last_deployed_image(
                 target.kube.clone(),
@@ -62,7 +78,7 @@ where
             })
         };
 
-        let long_task = |_logger: &EnvProgressLogger, state: TaskContext| -> Result<TaskContext, Box<EngineError>> {
+        let long_task = |logger: &EnvProgressLogger, state: TaskContext| -> Result<TaskContext, Box<EngineError>> {
             // If the service have been paused, we must ensure we un-pause it first as hpa will not kick in
             let _ = PauseServiceAction::new(
                 self.kube_label_selector(),
@@ -72,6 +88,14 @@ where
             )
             .unpause_if_needed(target);
 
+            MigrateVolumeStorageClassAction::new(
+                self.kube_label_selector(),
+                self.volume_storage_classes(),
+                Duration::from_secs(5 * 60),
+                event_details.clone(),
+            )
+            .migrate(target, logger)?;
+
             match get_container_with_invalid_storage_size(
                 self,
                 &target.kube,
@@ -95,25 +119,26 @@ where
                 )),
             };
 
-            let chart = ChartInfo {
-                name: self.helm_release_name(),
-                path: self.workspace_directory().to_string(),
-                namespace: HelmChartNamespaces::Custom,
-                custom_namespace: Some(target.environment.namespace().to_string()),
-                timeout_in_seconds: self.startup_timeout().as_secs() as i64,
-                k8s_selector: Some(self.kube_label_selector()),
-                ..Default::default()
-            };
+            RestoreVolumeSnapshotAction::new(
+                self.kube_label_selector(),
+                self.volume_snapshot_restores(),
+                Duration::from_secs(5 * 60),
+                event_details.clone(),
+            )
+            .restore(target, logger)?;
 
-            let helm = HelmDeployment::new(
+            let rollout = ProgressiveRollout::new(
+                self.long_id(),
+                self.kube_name(),
+                self.min_instances(),
+                self.is_stateful(),
+                self.advanced_settings().clone(),
+                self.track_version(target),
                 event_details.clone(),
-                self.to_tera_context(target)?,
-                PathBuf::from(self.helm_chart_dir()),
-                None,
-                chart,
             );
-
-            helm.on_create(target)?;
+            rollout.deploy(target, logger, &|deployment| {
+                helm_deployment(self, target, event_details.clone(), deployment)
+            })?;
 
             Ok(state)
         };
@@ -145,6 +170,22 @@ where
         )
     }
 
+    fn on_plan(&self, target: &DeploymentTarget) -> Result<Vec<PlannedChange>, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Plan));
+        let mut changes = vec![plan_mirrored_image(self.long_id(), &self.source, target)];
+
+        if let Ok(invalid_statefulset_storage) =
+            get_container_with_invalid_storage_size(self, &target.kube, target.environment.namespace(), &event_details)
+        {
+            changes.extend(plan_storage_resize(invalid_statefulset_storage));
+        }
+
+        let helm = helm_deployment(self, target, event_details, &self.deployment_tera_context(target))?;
+        changes.push(helm.plan_release(target)?);
+
+        Ok(changes)
+    }
+
     fn on_pause(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
         execute_long_deployment(
             ApplicationDeploymentReporter::new_for_container(self, target, Action::Pause),
@@ -287,3 +328,27 @@ where
         )
     }
 }
+
+fn helm_deployment<T: CloudProvider>(
+    container: &Container<T>,
+    target: &DeploymentTarget,
+    event_details: EventDetails,
+    deployment: &DeploymentTeraContext,
+) -> Result<HelmDeployment, Box<EngineError>>
+where
+    Container<T>: ToTeraContext,
+{
+    let mut tera_context = container.to_tera_context(target)?;
+    tera_context.insert("deployment", deployment);
+
+    Ok(HelmDeployment::new_for_service(
+        event_details,
+        tera_context,
+        PathBuf::from(container.helm_chart_dir()),
+        container.helm_release_name(),
+        container.workspace_directory(),
+        target,
+        Some(container.startup_timeout()),
+        Some(container.kube_label_selector()),
+    ))
+}
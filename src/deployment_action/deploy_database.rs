@@ -1,25 +1,30 @@
 use crate::cloud_provider::helm::{ChartInfo, ChartSetValue, HelmAction, HelmChartNamespaces};
 use crate::cloud_provider::service::{get_database_terraform_config, Action, Service};
-use crate::cloud_provider::Kind::Aws;
+use crate::cloud_provider::Kind::{Aws, Gcp};
 use crate::cloud_provider::{service, DeploymentTarget};
 use crate::cmd;
-use crate::cmd::command::{ExecutableCommand, QoveryCommand};
-use crate::constants::AWS_DEFAULT_REGION;
+use crate::cmd::command::{CommandKiller, ExecutableCommand, QoveryCommand};
+use crate::constants::{AWS_DEFAULT_REGION, GCP_CREDENTIALS, GCP_PROJECT};
 use crate::deployment_action::check_dns::CheckDnsForDomains;
 use crate::deployment_action::deploy_helm::HelmDeployment;
 use crate::deployment_action::deploy_terraform::TerraformDeployment;
 use crate::deployment_action::pause_service::PauseServiceAction;
+use crate::deployment_action::plan::{plan_storage_resize, PlannedChange};
 use crate::deployment_action::DeploymentAction;
 use crate::deployment_report::database::reporter::DatabaseDeploymentReporter;
 use crate::deployment_report::{execute_long_deployment, DeploymentTaskImpl};
 use crate::errors::{CommandError, EngineError, Tag};
 use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage};
+use crate::io_models::database::{DatabaseBackup, DatabaseOptions};
 use crate::kubers_utils::{kube_delete_all_from_selector, KubeDeleteMode};
 use crate::models::database::{
     get_database_with_invalid_storage_size, Container, Database, DatabaseError, DatabaseService, DatabaseType, Managed,
 };
+use crate::models::database_backup::{backup_key_prefix, select_backup, DatabaseBackupTool};
+use crate::models::gcp::CLOUD_SQL_ACTIVATION_POLICY_STOPPED;
 use crate::models::types::{CloudProvider, ToTeraContext, VersionsNumber};
 use crate::runtime::block_on;
+use crate::services::gcp::managed_database_service::ManagedDatabaseService;
 use aws_types::SdkConfig;
 use k8s_openapi::api::core::v1::PersistentVolumeClaim;
 use semver::Version;
@@ -27,9 +32,14 @@ use serde::Deserialize;
 use std::collections::BTreeMap;
 
 use crate::cloud_provider::aws::models::QoveryAwsSdkConfigManagedDatabase;
+use crate::cloud_provider::aws::regions::AwsRegion;
 use crate::cloud_provider::utilities::{are_pvcs_bound, update_pvcs};
 use crate::deployment_action::restart_service::RestartServiceAction;
 use crate::deployment_report::logger::{EnvProgressLogger, EnvSuccessLogger};
+use crate::object_storage::errors::ObjectStorageError;
+use crate::object_storage::s3::S3;
+use crate::object_storage::s3_compatible_object_storage::{S3Compatible, S3CompatibleRegion};
+use crate::object_storage::ObjectStorage;
 use async_trait::async_trait;
 use aws_sdk_docdb::error::DescribeDBClustersError;
 use aws_sdk_docdb::output::DescribeDbClustersOutput;
@@ -39,8 +49,10 @@ use aws_sdk_elasticache::output::DescribeCacheClustersOutput;
 use aws_sdk_rds::error::DescribeDBInstancesError;
 use aws_sdk_rds::output::DescribeDbInstancesOutput;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::thread;
 use std::time::{Duration, Instant};
+use url::Url;
 
 const DB_READY_STATE: &str = "available";
 const DB_STOPPED_STATE: &str = "stopped";
@@ -293,9 +305,17 @@ where
 
     // Sending hostname to the core to update env variable with real hostname
     // useful when managed service requires TLS and using a CNAME is not possible due to certificate checks
+    // Port and password are sent as well when they are decided by the managed service
     {
+        let port = database_config.target_port.map(|port| port.to_string());
         let mut json: BTreeMap<&str, &str> = BTreeMap::new();
         json.insert("hostname", database_config.target_hostname.as_str());
+        if let Some(port) = &port {
+            json.insert("port", port.as_str());
+        }
+        if let Some(password) = &database_config.target_password {
+            json.insert("password", password.as_str());
+        }
         logger.core_configuration_for_database(
             format!(
                 "🪡 Retrieved database hostname {}, environment variables are going to be stitched with it",
@@ -365,6 +385,7 @@ where
     helm.on_create(target)?;
 
     // We don't manage START/PAUSE for managed database elsewhere than for AWS
+    // On GCP, terraform starts Cloud SQL instances itself with their activation policy
     if target.kubernetes.cloud_provider().kind() != Aws {
         return Ok(());
     }
@@ -445,6 +466,54 @@ impl QoveryAwsSdkConfigManagedDatabase for SdkConfig {
     }
 }
 
+fn on_pause_gcp_managed_impl<C: CloudProvider, T: DatabaseType<C, Managed>>(
+    db: &Database<C, Managed, T>,
+    event_details: EventDetails,
+    target: &DeploymentTarget,
+) -> Result<(), Box<EngineError>>
+where
+    Database<C, Managed, T>: DatabaseService,
+{
+    // Memorystore does not support being stopped/paused
+    if db.db_type() == service::DatabaseType::Redis {
+        return Ok(());
+    }
+
+    // Cloud SQL instances are stopped by applying the NEVER activation policy, data are kept
+    let mut tera_context = db.to_tera_context(target)?;
+    tera_context.insert("activation_policy", CLOUD_SQL_ACTIVATION_POLICY_STOPPED);
+
+    let terraform_deploy = TerraformDeployment::new(
+        tera_context,
+        PathBuf::from(db.terraform_common_resource_dir_path()),
+        PathBuf::from(db.terraform_resource_dir_path()),
+        PathBuf::from(db.workspace_directory()),
+        event_details,
+        target.is_dry_run_deploy,
+    );
+    terraform_deploy.on_create(target)
+}
+
+fn gcp_managed_database_exists(
+    db_type: service::DatabaseType,
+    db_id: &str,
+    event_details: &EventDetails,
+    target: &DeploymentTarget,
+) -> Result<bool, Box<EngineError>> {
+    let credentials = target.kubernetes.cloud_provider().credentials_environment_variables();
+    let credential = |name: &str| {
+        credentials
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+            .unwrap_or_default()
+    };
+
+    ManagedDatabaseService::new(credential(GCP_CREDENTIALS), credential(GCP_PROJECT), target.kubernetes.region())
+        .and_then(|db_service| db_service.database_exists(db_type, db_id))
+        .map_err(|e| Box::new(EngineError::new_cannot_get_managed_database(event_details.clone(), db_id, e)))
+}
+
 fn managed_database_exists(
     db_type: service::DatabaseType,
     db_id: &str,
@@ -582,12 +651,30 @@ where
         )
     }
 
+    fn on_plan(&self, target: &DeploymentTarget) -> Result<Vec<PlannedChange>, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Plan));
+        let terraform_plan = TerraformDeployment::new(
+            self.to_tera_context(target)?,
+            PathBuf::from(self.terraform_common_resource_dir_path()),
+            PathBuf::from(self.terraform_resource_dir_path()),
+            PathBuf::from(self.workspace_directory()),
+            event_details,
+            true,
+        );
+
+        Ok(vec![terraform_plan.plan(target)?])
+    }
+
     fn on_pause(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
         let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Pause));
         execute_long_deployment(
             DatabaseDeploymentReporter::new(self, target, Action::Pause),
             |_logger: &EnvProgressLogger| -> Result<(), Box<EngineError>> {
-                // We don't manage PAUSE for managed database elsewhere than for AWS
+                if target.kubernetes.cloud_provider().kind() == Gcp {
+                    return on_pause_gcp_managed_impl(self, event_details.clone(), target);
+                }
+
+                // We don't manage PAUSE for managed database elsewhere than for AWS and GCP
                 if target.kubernetes.cloud_provider().kind() != Aws {
                     return Ok(());
                 }
@@ -646,14 +733,18 @@ where
         execute_long_deployment(
             DatabaseDeploymentReporter::new(self, target, Action::Delete),
             |logger: &EnvProgressLogger| -> Result<(), Box<EngineError>> {
-                // First we must ensure the DB is created by looking for the k8s ExternalName service and AWS side
-                if !managed_database_exists(
-                    self.db_type(),
-                    &self.fqdn_id,
-                    &self.version,
-                    &event_details,
-                    target.cloud_provider.aws_sdk_client(),
-                )? {
+                // First we must ensure the DB is created by looking for the k8s ExternalName service and cloud provider side
+                let database_exists = match target.kubernetes.cloud_provider().kind() {
+                    Gcp => gcp_managed_database_exists(self.db_type(), &self.fqdn_id, &event_details, target)?,
+                    _ => managed_database_exists(
+                        self.db_type(),
+                        &self.fqdn_id,
+                        &self.version,
+                        &event_details,
+                        target.cloud_provider.aws_sdk_client(),
+                    )?,
+                };
+                if !database_exists {
                     // if db has never been deployed. No need to go further
                     info!("Managed database not found on cloud provider. Assuming it does not exist");
                     return Ok(());
@@ -710,7 +801,166 @@ where
 }
 
 // For Container database
-impl<C: CloudProvider, T: DatabaseType<C, Container>> DeploymentAction for Database<C, Container, T>
+fn container_database_helm_deployment<C: CloudProvider, T: DatabaseType<C, Container>>(
+    db: &Database<C, Container, T>,
+    target: &DeploymentTarget,
+    event_details: EventDetails,
+) -> Result<HelmDeployment, Box<EngineError>>
+where
+    Database<C, Container, T>: ToTeraContext,
+{
+    let mut helm = HelmDeployment::new_for_service(
+        event_details,
+        db.to_tera_context(target)?,
+        PathBuf::from(db.helm_chart_dir()),
+        db.helm_release_name(),
+        db.workspace_directory(),
+        target,
+        None,
+        Some(db.kube_label_selector()),
+    );
+    helm.render_custom_values_file =
+        Some(PathBuf::from(format!("{}/qovery-values.j2.yaml", db.helm_chart_values_dir())));
+    helm.helm_chart.values_files = vec![format!("{}/qovery-values.yaml", db.workspace_directory())];
+    // need to perform reinstall (but keep PVC) to update the statefulset
+    helm.helm_chart.reinstall_chart_if_installed_version_is_below_than = match T::db_type() {
+        service::DatabaseType::PostgreSQL => Some(Version::new(12, 5, 1)),
+        service::DatabaseType::MongoDB => Some(Version::new(13, 13, 1)),
+        service::DatabaseType::MySQL => Some(Version::new(9, 10, 1)),
+        service::DatabaseType::Redis => Some(Version::new(17, 11, 4)),
+    };
+
+    Ok(helm)
+}
+
+// AWS S3 by default, any S3 compatible storage (i.e: MinIO) with its own regions when an endpoint is set
+fn container_database_backup_storage(
+    backup: &DatabaseBackup,
+    event_details: &EventDetails,
+) -> Result<Box<dyn ObjectStorage>, Box<EngineError>> {
+    let to_engine_error = |raw_error_message: String| {
+        Box::new(EngineError::new_object_storage_error(
+            event_details.clone(),
+            ObjectStorageError::CannotInstantiateClient { raw_error_message },
+        ))
+    };
+
+    let (id, name) = ("database-backups".to_string(), "Database backups".to_string());
+    let (access_key_id, secret_access_key) = (
+        backup.storage.access_key_id.to_string(),
+        backup.storage.secret_access_key.to_string(),
+    );
+    Ok(match &backup.storage.endpoint {
+        Some(endpoint) => {
+            let endpoint = Url::parse(endpoint)
+                .map_err(|e| to_engine_error(format!("Invalid backup storage endpoint `{endpoint}`: {e}")))?;
+            Box::new(S3Compatible::new(
+                id,
+                name,
+                endpoint,
+                Some(S3CompatibleRegion(backup.storage.region.to_string())),
+                access_key_id,
+                secret_access_key,
+                None,
+            ))
+        }
+        None => {
+            let region = AwsRegion::from_str(&backup.storage.region)
+                .map_err(|_| to_engine_error(format!("Invalid backup storage region `{}`", backup.storage.region)))?;
+            Box::new(S3::new(id, name, access_key_id, secret_access_key, region))
+        }
+    })
+}
+
+// Backup cronjob of a container database, with the restore job if a backup has been requested to be restored.
+fn container_database_backup_helm_deployment<C: CloudProvider, T>(
+    db: &Database<C, Container, T>,
+    target: &DeploymentTarget,
+    event_details: EventDetails,
+) -> Result<Option<HelmDeployment>, Box<EngineError>>
+where
+    T: DatabaseType<C, Container, DatabaseOptions = DatabaseOptions>,
+{
+    let backup = match db.backup() {
+        Some(backup) => backup,
+        None => return Ok(None),
+    };
+    let bucket_name = backup.storage.bucket_name.as_str();
+    let to_engine_error =
+        |e: ObjectStorageError| Box::new(EngineError::new_object_storage_error(event_details.clone(), e));
+
+    let storage = container_database_backup_storage(backup, &event_details)?;
+    storage
+        .create_bucket(bucket_name, None, false)
+        .map_err(to_engine_error)?;
+    let backup_keys = storage
+        .list_objects(bucket_name, Some(&backup_key_prefix(&db.long_id)))
+        .map_err(to_engine_error)?;
+
+    let restore_backup_key = match db.restore() {
+        None => None,
+        Some(restore) => match select_backup(&backup_keys, restore) {
+            Some(backup_key) => Some(backup_key.as_str()),
+            None => {
+                let requested_backup = match (&restore.backup_key, &restore.point_in_time) {
+                    (Some(backup_key), _) => backup_key.to_string(),
+                    (None, Some(point_in_time)) => format!("before {}", point_in_time.to_rfc3339()),
+                    (None, None) => "latest".to_string(),
+                };
+                return Err(Box::new(EngineError::new_database_backup_not_found(
+                    event_details.clone(),
+                    db.id().to_string(),
+                    bucket_name,
+                    &requested_backup,
+                )));
+            }
+        },
+    };
+
+    let chart = ChartInfo {
+        name: db.backup_helm_release_name(),
+        path: format!("{}/backup", db.workspace_directory()),
+        namespace: HelmChartNamespaces::Custom,
+        custom_namespace: Some(target.environment.namespace().to_string()),
+        ..Default::default()
+    };
+
+    Ok(Some(HelmDeployment::new(
+        event_details,
+        db.to_backup_tera_context(target, backup, restore_backup_key)?,
+        PathBuf::from(db.backup_helm_chart_dir()),
+        None,
+        chart,
+    )))
+}
+
+fn uninstall_container_database_backup<C: CloudProvider, T: DatabaseType<C, Container>>(
+    db: &Database<C, Container, T>,
+    target: &DeploymentTarget,
+    event_details: EventDetails,
+) -> Result<(), Box<EngineError>> {
+    let chart = ChartInfo {
+        name: db.backup_helm_release_name(),
+        action: HelmAction::Destroy,
+        namespace: HelmChartNamespaces::Custom,
+        custom_namespace: Some(target.environment.namespace().to_string()),
+        ..Default::default()
+    };
+
+    target
+        .helm
+        .uninstall(
+            &chart,
+            &[],
+            &CommandKiller::from_cancelable(&target.should_abort),
+            &mut |_| {},
+            &mut |_| {},
+        )
+        .map_err(|e| Box::new(EngineError::new_helm_error(event_details, e)))
+}
+
+impl<C: CloudProvider, T: DatabaseType<C, Container, DatabaseOptions = DatabaseOptions>> DeploymentAction
+    for Database<C, Container, T>
 where
     Database<C, Container, T>: ToTeraContext,
 {
@@ -741,30 +991,24 @@ where
                 )),
             }
 
-            let chart = ChartInfo {
-                name: self.helm_release_name(),
-                path: self.workspace_directory().to_string(),
-                namespace: HelmChartNamespaces::Custom,
-                custom_namespace: Some(target.environment.namespace().to_string()),
-                k8s_selector: Some(self.kube_label_selector()),
-                values_files: vec![format!("{}/qovery-values.yaml", self.workspace_directory())],
-                // need to perform reinstall (but keep PVC) to update the statefulset
-                reinstall_chart_if_installed_version_is_below_than: match T::db_type() {
-                    service::DatabaseType::PostgreSQL => Some(Version::new(12, 5, 1)),
-                    service::DatabaseType::MongoDB => Some(Version::new(13, 13, 1)),
-                    service::DatabaseType::MySQL => Some(Version::new(9, 10, 1)),
-                    service::DatabaseType::Redis => Some(Version::new(17, 11, 4)),
-                },
-                ..Default::default()
-            };
-            let helm = HelmDeployment::new(
-                event_details.clone(),
-                self.to_tera_context(target)?,
-                PathBuf::from(self.helm_chart_dir()),
-                Some(PathBuf::from(format!("{}/qovery-values.j2.yaml", self.helm_chart_values_dir()))),
-                chart,
-            );
+            // A database restored into its volume has to be stopped during the restore, its chart starts it back
+            let backup_helm = container_database_backup_helm_deployment(self, target, event_details.clone())?;
+            let restore_into_volume =
+                self.restore().is_some() && DatabaseBackupTool::new(T::db_type()).restore_into_volume;
+            if let (Some(backup_helm), true) = (&backup_helm, restore_into_volume) {
+                logger.info("⏸️ Stopping the database to restore the backup into its volume".to_string());
+                let pause_service = PauseServiceAction::new(
+                    self.kube_label_selector(),
+                    true,
+                    Duration::from_secs(5 * 60),
+                    event_details.clone(),
+                );
+                pause_service.on_pause(target)?;
+                backup_helm.on_create(target)?;
+                pause_service.unpause_if_needed(target)?;
+            }
 
+            let helm = container_database_helm_deployment(self, target, event_details.clone())?;
             if let Err(e) = helm.on_create(target) {
                 return match e.tag() {
                     Tag::TaskCancellationRequested => Err(e),
@@ -783,6 +1027,17 @@ where
                 };
             };
 
+            match (&backup_helm, restore_into_volume) {
+                (Some(backup_helm), false) => {
+                    if self.restore().is_some() {
+                        logger.info("♻️ Restoring the backup into the database".to_string());
+                    }
+                    backup_helm.on_create(target)?;
+                }
+                (Some(_), true) => {}
+                (None, _) => uninstall_container_database_backup(self, target, event_details.clone())?,
+            }
+
             Ok(())
         };
 
@@ -810,6 +1065,22 @@ where
         )
     }
 
+    fn on_plan(&self, target: &DeploymentTarget) -> Result<Vec<PlannedChange>, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Plan));
+        let mut changes = vec![];
+
+        if let Ok(invalid_statefulset_storage) =
+            get_database_with_invalid_storage_size(self, &target.kube, target.environment.namespace(), &event_details)
+        {
+            changes.extend(plan_storage_resize(invalid_statefulset_storage));
+        }
+
+        let helm = container_database_helm_deployment(self, target, event_details)?;
+        changes.push(helm.plan_release(target)?);
+
+        Ok(changes)
+    }
+
     fn on_pause(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
         execute_long_deployment(
             DatabaseDeploymentReporter::new(self, target, Action::Pause),
@@ -846,6 +1117,7 @@ where
                     chart,
                 );
 
+                uninstall_container_database_backup(self, target, event_details.clone())?;
                 helm.on_delete(target)?;
 
                 // FIXME(ENG-1606): Remove this after kubernetes 1.23 is deployed, at it should be done by kubernetes
@@ -881,4 +1153,9 @@ where
             },
         )
     }
+
+    // The backup to restore is only set with the restore action, the deployment runs the restore job along the way
+    fn on_restore(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
+        self.on_create(target)
+    }
 }
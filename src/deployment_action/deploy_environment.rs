@@ -1,14 +1,21 @@
 use crate::cloud_provider::aws::load_balancers::clean_up_deleted_k8s_nlb;
 use crate::cloud_provider::environment::Environment;
+use crate::cloud_provider::helm::{get_latest_successful_deployment, ChartInfo, HelmChartNamespaces};
 use crate::cloud_provider::service::Action;
 use crate::cloud_provider::DeploymentTarget;
+use crate::cmd::helm::HelmError;
+use crate::cmd::structs::HelmHistoryRow;
+use crate::deployment_action::deploy_helm::default_helm_timeout;
 use crate::deployment_action::deploy_namespace::NamespaceDeployment;
+use crate::deployment_action::plan::{EnvironmentPlan, ServicePlan};
 use crate::deployment_action::DeploymentAction;
+use crate::deployment_report::logger::EnvLogger;
 use crate::engine::InfrastructureContext;
 use crate::errors::{CommandError, EngineError};
-use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage};
+use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage};
 use crate::logger::Logger;
 use crate::metrics_registry::{StepLabel, StepName, StepStatus};
+use crate::models::dependency_graph::ServiceDependencyGraph;
 use crate::models::router::RouterService;
 use crate::runtime::block_on;
 use itertools::Itertools;
@@ -16,7 +23,7 @@ use k8s_openapi::api::core::v1::Namespace;
 use kube::api::ListParams;
 use kube::Api;
 use std::cmp::{max, min};
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
 use std::thread;
@@ -24,6 +31,41 @@ use std::thread::ScopedJoinHandle;
 use std::time::Duration;
 use uuid::Uuid;
 
+/// State of a service helm release before the deployment started
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ReleaseState {
+    /// The release does not exist, the service is created by this deployment
+    Absent,
+    /// The release is deployed at the given revision
+    Deployed(u16),
+    /// We were not able to retrieve the release state, so no rollback can be attempted
+    Unknown(String),
+}
+
+impl ReleaseState {
+    fn from_history(history: Result<Vec<HelmHistoryRow>, HelmError>) -> ReleaseState {
+        match history {
+            Ok(history) if history.is_empty() => ReleaseState::Absent,
+            Ok(history) => match get_latest_successful_deployment(&history) {
+                Ok(revision) => ReleaseState::Deployed(revision.revision),
+                Err(err) => ReleaseState::Unknown(err.message_safe()),
+            },
+            Err(HelmError::ReleaseDoesNotExist(_)) => ReleaseState::Absent,
+            Err(err) => ReleaseState::Unknown(err.to_string()),
+        }
+    }
+}
+
+/// Snapshot of a service release taken before the deployment, used to restore it if the deployment fails
+struct ServiceRelease<'b> {
+    service_id: Uuid,
+    service_name: String,
+    event_details: EventDetails,
+    deployment_action: &'b dyn DeploymentAction,
+    chart: ChartInfo,
+    state: ReleaseState,
+}
+
 pub struct EnvironmentDeployment<'a> {
     pub deployed_services: Arc<Mutex<HashSet<Uuid>>>,
     deployment_target: DeploymentTarget<'a>,
@@ -131,7 +173,17 @@ impl<'a> EnvironmentDeployment<'a> {
         };
         ns.exec_action(target, target.environment.action)?;
 
-        let services_to_deploy = Self::services_without_routers_iter(target.environment);
+        // Record the current revision of services before touching them, to be able to restore them on failure
+        let services_releases = match target.environment.rollback_on_failure {
+            true => self.services_releases(),
+            false => vec![],
+        };
+
+        let services_to_deploy = Self::services_without_routers_iter(target.environment).collect_vec();
+        let services_dependencies = Self::services_dependencies(
+            &services_to_deploy.iter().map(|(id, _, _)| *id).collect_vec(),
+            &target.environment.dependencies,
+        );
         let parallel_deploys = max(target.environment.max_parallel_deploy as usize, 1);
 
         self.logger.log(EngineEvent::Info(
@@ -143,7 +195,7 @@ impl<'a> EnvironmentDeployment<'a> {
         ));
 
         let deployment_threads_pool = DeploymentThreadsPool::new();
-        deployment_threads_pool.run(
+        let deployment_ret = deployment_threads_pool.run(
             services_to_deploy
                 .into_iter()
                 .map(|(service_id, service, service_action)| {
@@ -167,10 +219,18 @@ impl<'a> EnvironmentDeployment<'a> {
                     }
                 })
                 .collect_vec(),
+            services_dependencies,
             || should_abort().is_err(),
             NonZeroUsize::new(parallel_deploys)
                 .unwrap_or(NonZeroUsize::new(1).expect("error trying to instantiate NonZeroUsize")),
-        )?;
+        );
+
+        if let Err(err) = deployment_ret {
+            if target.environment.rollback_on_failure {
+                self.rollback_services(&services_releases);
+            }
+            return Err(err);
+        }
 
         // clean up nlb
         clean_up_deleted_k8s_nlb(event_details.clone(), target)?;
@@ -189,7 +249,13 @@ impl<'a> EnvironmentDeployment<'a> {
         should_abort()?;
 
         // reverse order of the deployment
-        let services_to_pause = Self::services_without_routers_iter(target.environment).rev();
+        let services_to_pause = Self::services_without_routers_iter(target.environment)
+            .rev()
+            .collect_vec();
+        let services_dependencies = Self::services_dependencies(
+            &services_to_pause.iter().map(|(id, _, _)| *id).collect_vec(),
+            &target.environment.dependencies.reversed(),
+        );
         let parallel_deploys = max(target.environment.max_parallel_deploy as usize, 1);
 
         self.logger.log(EngineEvent::Info(
@@ -218,6 +284,7 @@ impl<'a> EnvironmentDeployment<'a> {
                     }
                 })
                 .collect_vec(),
+            services_dependencies,
             || should_abort().is_err(),
             NonZeroUsize::new(parallel_deploys)
                 .unwrap_or(NonZeroUsize::new(1).expect("error trying to instantiate NonZeroUsize")),
@@ -272,7 +339,13 @@ impl<'a> EnvironmentDeployment<'a> {
         should_abort()?;
 
         // reverse order of the deployment
-        let services_to_delete = Self::services_without_routers_iter(target.environment).rev();
+        let services_to_delete = Self::services_without_routers_iter(target.environment)
+            .rev()
+            .collect_vec();
+        let services_dependencies = Self::services_dependencies(
+            &services_to_delete.iter().map(|(id, _, _)| *id).collect_vec(),
+            &target.environment.dependencies.reversed(),
+        );
 
         let parallel_deploys = max(target.environment.max_parallel_deploy as usize, 1);
 
@@ -301,6 +374,7 @@ impl<'a> EnvironmentDeployment<'a> {
                     }
                 })
                 .collect_vec(),
+            services_dependencies,
             || should_abort().is_err(),
             NonZeroUsize::new(parallel_deploys)
                 .unwrap_or(NonZeroUsize::new(1).expect("error trying to instantiate NonZeroUsize")),
@@ -329,7 +403,11 @@ impl<'a> EnvironmentDeployment<'a> {
         let should_abort = Self::should_abort_wrapper(&target, &event_details);
         should_abort()?;
 
-        let services_to_restart = Self::services_without_routers_iter(target.environment);
+        let services_to_restart = Self::services_without_routers_iter(target.environment).collect_vec();
+        let services_dependencies = Self::services_dependencies(
+            &services_to_restart.iter().map(|(id, _, _)| *id).collect_vec(),
+            &target.environment.dependencies,
+        );
 
         let parallel_deploys = max(target.environment.max_parallel_deploy as usize, 1);
 
@@ -360,6 +438,7 @@ impl<'a> EnvironmentDeployment<'a> {
                     }
                 })
                 .collect_vec(),
+            services_dependencies,
             || should_abort().is_err(),
             NonZeroUsize::new(parallel_deploys)
                 .unwrap_or(NonZeroUsize::new(1).expect("error trying to instantiate NonZeroUsize")),
@@ -368,6 +447,217 @@ impl<'a> EnvironmentDeployment<'a> {
         Ok(())
     }
 
+    /// Compute what on_create would change on every service of the environment, without applying anything
+    pub fn on_plan(&self) -> Result<EnvironmentPlan, Box<EngineError>> {
+        let target = &self.deployment_target;
+        let environment = target.environment;
+        let event_details = environment.event_details_with_step(EnvironmentStep::Plan);
+
+        let should_abort = Self::should_abort_wrapper(target, &event_details);
+        should_abort()?;
+
+        self.logger.log(EngineEvent::Info(
+            event_details.clone(),
+            EventMessage::new_from_safe(
+                "🔎 Computing what the deployment would change, nothing will be applied".to_string(),
+            ),
+        ));
+
+        let services = std::iter::empty()
+            .chain(
+                environment
+                    .databases
+                    .iter()
+                    .map(|s| (s.as_service(), s.as_deployment_action())),
+            )
+            .chain(
+                environment
+                    .jobs
+                    .iter()
+                    .map(|s| (s.as_service(), s.as_deployment_action())),
+            )
+            .chain(
+                environment
+                    .containers
+                    .iter()
+                    .map(|s| (s.as_service(), s.as_deployment_action())),
+            )
+            .chain(
+                environment
+                    .applications
+                    .iter()
+                    .map(|s| (s.as_service(), s.as_deployment_action())),
+            )
+            .chain(
+                environment
+                    .helm_charts
+                    .iter()
+                    .map(|s| (s.as_service(), s.as_deployment_action())),
+            )
+            .chain(
+                environment
+                    .routers
+                    .iter()
+                    .map(|s| (s.as_service(), s.as_deployment_action())),
+            );
+
+        let mut services_plans = vec![];
+        for (service, deployment_action) in services {
+            should_abort()?;
+
+            // Only services being deployed render manifests, others are reported with their action only
+            let changes = match service.action() {
+                Action::Create => deployment_action.on_plan(target)?,
+                Action::Pause | Action::Delete | Action::Restart | Action::Restore => vec![],
+            };
+
+            let service_plan = ServicePlan::new(service, changes);
+            EnvLogger::new(service, EnvironmentStep::Plan, self.logger.clone()).send_success(service_plan.summary());
+            services_plans.push(service_plan);
+        }
+
+        Ok(EnvironmentPlan {
+            environment_id: environment.long_id,
+            namespace: environment.namespace().to_string(),
+            services: services_plans,
+        })
+    }
+
+    /// Returns the release state of every application, container and helm chart about to be deployed
+    fn services_releases(&self) -> Vec<ServiceRelease<'_>> {
+        let target = &self.deployment_target;
+        let environment = target.environment;
+
+        std::iter::empty()
+            .chain(environment.applications.iter().map(|s| {
+                (
+                    s.as_service(),
+                    s.as_deployment_action(),
+                    s.helm_release_name(),
+                    s.startup_timeout(),
+                )
+            }))
+            .chain(environment.containers.iter().map(|s| {
+                (
+                    s.as_service(),
+                    s.as_deployment_action(),
+                    s.helm_release_name(),
+                    s.startup_timeout(),
+                )
+            }))
+            .chain(environment.helm_charts.iter().map(|s| {
+                (
+                    s.as_service(),
+                    s.as_deployment_action(),
+                    s.helm_release_name(),
+                    default_helm_timeout(),
+                )
+            }))
+            .filter(|(service, _, _, _)| *service.action() == Action::Create)
+            .map(|(service, deployment_action, release_name, timeout)| {
+                let chart = ChartInfo {
+                    name: release_name,
+                    namespace: HelmChartNamespaces::Custom,
+                    custom_namespace: Some(environment.namespace().to_string()),
+                    timeout_in_seconds: timeout.as_secs() as i64,
+                    k8s_selector: Some(service.kube_label_selector()),
+                    ..Default::default()
+                };
+                let state = ReleaseState::from_history(target.helm.history(&chart, &[]));
+
+                ServiceRelease {
+                    service_id: *service.long_id(),
+                    service_name: service.name().to_string(),
+                    event_details: service.get_event_details(Stage::Environment(EnvironmentStep::Rollback)),
+                    deployment_action,
+                    chart,
+                    state,
+                }
+            })
+            .collect()
+    }
+
+    /// Restore every service touched by the failed deployment to its previous revision,
+    /// and delete the ones that did not exist before. Each outcome is reported as its own event.
+    fn rollback_services(&self, services_releases: &[ServiceRelease]) {
+        let target = &self.deployment_target;
+        let event_details = target.environment.event_details_with_step(EnvironmentStep::Rollback);
+
+        if (target.should_abort)() {
+            self.logger.log(EngineEvent::Info(
+                event_details,
+                EventMessage::new_from_safe("⏪ Deployment has been cancelled, skipping rollback".to_string()),
+            ));
+            return;
+        }
+
+        let deployed_services = self
+            .deployed_services
+            .lock()
+            .map(|services| services.clone())
+            .unwrap_or_default();
+        let releases_to_rollback = services_releases
+            .iter()
+            .rev()
+            .filter(|release| deployed_services.contains(&release.service_id))
+            .collect_vec();
+
+        self.logger.log(EngineEvent::Info(
+            event_details,
+            EventMessage::new_from_safe(format!(
+                "⏪ Deployment failed, rolling back {} service(s) to their previous version",
+                releases_to_rollback.len()
+            )),
+        ));
+
+        for release in releases_to_rollback {
+            let ret = match &release.state {
+                ReleaseState::Deployed(revision) => target
+                    .helm
+                    .rollback_to_revision(&release.chart, *revision, &[])
+                    .map(|_| format!("⏪ {} has been rolled back to revision {}", release.service_name, revision))
+                    .map_err(|err| EngineError::new_helm_error(release.event_details.clone(), err).to_string()),
+                ReleaseState::Absent => release
+                    .deployment_action
+                    .on_delete(target)
+                    .map(|_| {
+                        format!(
+                            "🗑️ {} did not exist before this deployment, it has been deleted",
+                            release.service_name
+                        )
+                    })
+                    .map_err(|err| err.to_string()),
+                ReleaseState::Unknown(reason) => Err(format!("previous revision is unknown: {reason}")),
+            };
+
+            match ret {
+                Ok(msg) => self.logger.log(EngineEvent::Info(
+                    release.event_details.clone(),
+                    EventMessage::new_from_safe(msg),
+                )),
+                Err(err) => self.logger.log(EngineEvent::Warning(
+                    release.event_details.clone(),
+                    EventMessage::new(format!("❌ Cannot rollback {}", release.service_name), Some(err)),
+                )),
+            }
+        }
+    }
+
+    /// For each service, returns the indexes of the services it must wait for before being processed
+    fn services_dependencies(services_ids: &[Uuid], dependencies: &ServiceDependencyGraph) -> Vec<Vec<usize>> {
+        let services_ix: HashMap<Uuid, usize> = services_ids.iter().enumerate().map(|(ix, id)| (*id, ix)).collect();
+
+        services_ids
+            .iter()
+            .map(|service_id| {
+                dependencies
+                    .dependencies_of(service_id)
+                    .filter_map(|dependency_id| services_ix.get(dependency_id).copied())
+                    .collect()
+            })
+            .collect()
+    }
+
     fn get_associated_router(routers: &'a [Box<dyn RouterService>], service_id: Uuid) -> Option<&'a dyn RouterService> {
         routers
             .iter()
@@ -383,9 +673,14 @@ impl DeploymentThreadsPool {
         Self {}
     }
 
+    /// Run the tasks in parallel, with at most `max_parallelism` tasks at the same time.
+    /// `dependencies[ix]` contains the indexes of the tasks that must succeed before the task `ix` can start.
+    /// Tasks without pending dependencies are started in the order they are given.
+    /// The dependencies must be acyclic, otherwise tasks being part of the cycle will never be started.
     pub fn run<Err, Task>(
         &self,
         tasks: Vec<Task>,
+        dependencies: Vec<Vec<usize>>,
         should_abort: impl Fn() -> bool + Send + Sync,
         max_parallelism: NonZeroUsize,
     ) -> Result<(), Err>
@@ -395,35 +690,43 @@ impl DeploymentThreadsPool {
     {
         let max_parallelism = min(max_parallelism.get(), tasks.len());
 
+        // Compute for each task the number of dependencies it is waiting for, and who is waiting for it
+        let mut remaining_dependencies: Vec<usize> = vec![0; tasks.len()];
+        let mut dependents: Vec<Vec<usize>> = vec![vec![]; tasks.len()];
+        for (ix, task_dependencies) in dependencies.iter().enumerate().take(tasks.len()) {
+            for dependency_ix in task_dependencies.iter().filter(|dep_ix| **dep_ix < tasks.len()) {
+                remaining_dependencies[ix] += 1;
+                dependents[*dependency_ix].push(ix);
+            }
+        }
+        let mut ready_tasks: VecDeque<usize> = (0..tasks.len()).filter(|ix| remaining_dependencies[*ix] == 0).collect();
+        let mut tasks: Vec<Option<Task>> = tasks.into_iter().map(Some).collect();
+
         // Launch our thread-pool
         let current_thread = thread::current();
         thread::scope(|scope| {
             let mut ret: Result<(), Err> = Ok(());
-            let mut active_threads: VecDeque<ScopedJoinHandle<Result<(), Err>>> =
+            let mut active_threads: VecDeque<(usize, ScopedJoinHandle<Result<(), Err>>)> =
                 VecDeque::with_capacity(max_parallelism);
 
             let handle_thread_result = |th_result: thread::Result<Result<(), Err>>, ret: &mut Result<(), Err>| {
                 match th_result {
-                    Ok(Ok(())) => {}
+                    Ok(Ok(())) => true,
                     Ok(Err(err)) => {
                         // We want to store only the first error
                         if ret.is_ok() {
                             *ret = Err(err);
                         }
+                        false
                     }
                     Err(err) => panic!("Deployment thread panicked: {err:?}"),
                 }
             };
 
-            let await_deployment_slot =
-                |active_threads: &mut VecDeque<ScopedJoinHandle<_>>| -> thread::Result<Result<(), Err>> {
-                    if active_threads.len() < max_parallelism {
-                        return Ok(Ok(()));
-                    }
-
-                    // There is no available deployment slot, so we wait for a thread to terminate
+            let await_terminated_thread =
+                |active_threads: &mut VecDeque<(usize, ScopedJoinHandle<_>)>| -> (usize, thread::Result<Result<(), Err>>) {
                     let terminated_thread_ix = loop {
-                        match active_threads.iter().position(|th| th.is_finished()) {
+                        match active_threads.iter().position(|(_, th)| th.is_finished()) {
                             // timeout is needed because we call unpark within the thread
                             // So it can happens that we got unparked but the thread is not marked as finished yet
                             None => thread::park_timeout(Duration::from_secs(10)),
@@ -431,39 +734,56 @@ impl DeploymentThreadsPool {
                         }
                     };
 
-                    active_threads.swap_remove_back(terminated_thread_ix).unwrap().join()
+                    let (task_ix, th) = active_threads.swap_remove_back(terminated_thread_ix).unwrap();
+                    (task_ix, th.join())
                 };
 
-            // Launch our deployment in parallel for each service
-            for (ix, mut task) in tasks.into_iter().enumerate() {
-                // Ensure we have a slot available to run a new thread
-                let thread_result = await_deployment_slot(&mut active_threads);
-                handle_thread_result(thread_result, &mut ret);
+            loop {
+                // Launch in parallel every task whose dependencies are satisfied, as long as we have a slot available
+                while active_threads.len() < max_parallelism {
+                    let Some(task_ix) = ready_tasks.pop_front() else {
+                        break;
+                    };
+
+                    // If an abort arises, we just stop executing next tasks
+                    if should_abort() || ret.is_err() {
+                        ready_tasks.clear();
+                        break;
+                    }
 
-                // If an abort arises, we just stop executing next tasks
-                if should_abort() || ret.is_err() {
+                    let Some(mut task) = tasks[task_ix].take() else {
+                        continue;
+                    };
+                    let th = thread::Builder::new()
+                        .name(format!("deployer-{}", task_ix))
+                        .spawn_scoped(scope, {
+                            let current_span = tracing::Span::current();
+                            let current_thread = &current_thread;
+
+                            move || {
+                                let _span = current_span.enter();
+                                let _guard = scopeguard::guard((), |_| current_thread.unpark());
+                                task()
+                            }
+                        });
+                    active_threads.push_back((task_ix, th.unwrap()));
+                }
+
+                // Nothing is running anymore, either everything is done or we stopped because of an error/abort
+                if active_threads.is_empty() {
                     break;
                 }
 
-                // We have a slot to run a new thread, so start a new deployment
-                let th = thread::Builder::new()
-                    .name(format!("deployer-{}", ix))
-                    .spawn_scoped(scope, {
-                        let current_span = tracing::Span::current();
-                        let current_thread = &current_thread;
-
-                        move || {
-                            let _span = current_span.enter();
-                            let _guard = scopeguard::guard((), |_| current_thread.unpark());
-                            task()
+                // Wait for a thread to terminate, and unlock the tasks that were waiting for it
+                let (task_ix, thread_result) = await_terminated_thread(&mut active_threads);
+                if handle_thread_result(thread_result, &mut ret) {
+                    for dependent_ix in &dependents[task_ix] {
+                        remaining_dependencies[*dependent_ix] -= 1;
+                        if remaining_dependencies[*dependent_ix] == 0 {
+                            ready_tasks.push_back(*dependent_ix);
                         }
-                    });
-                active_threads.push_back(th.unwrap());
-            }
-
-            // Wait for all threads to terminate
-            for th in active_threads {
-                handle_thread_result(th.join(), &mut ret);
+                    }
+                }
             }
 
             ret
@@ -499,7 +819,7 @@ mod test {
                 });
             }
 
-            let result = pool.run(tasks, || false, NonZeroUsize::new(tc).unwrap());
+            let result = pool.run(tasks, vec![], || false, NonZeroUsize::new(tc).unwrap());
 
             // verify:
             assert!(result.is_ok());
@@ -531,7 +851,7 @@ mod test {
                 });
             }
 
-            let result = pool.run(tasks, || false, NonZeroUsize::new(tc).unwrap());
+            let result = pool.run(tasks, vec![], || false, NonZeroUsize::new(tc).unwrap());
 
             // verify:
             assert!(result.is_ok());
@@ -564,7 +884,7 @@ mod test {
             });
         }
 
-        let ret = pool.run(tasks, || false, NonZeroUsize::new(MAX_PARALLEL_DEPLOYS).unwrap());
+        let ret = pool.run(tasks, vec![], || false, NonZeroUsize::new(MAX_PARALLEL_DEPLOYS).unwrap());
 
         // verify:
         assert!(ret.is_err());
@@ -572,4 +892,125 @@ mod test {
         // Avoiding flakiness, we test that not all tasks are being executed
         assert!(active_tasks.load(Ordering::Relaxed) < TASKS_COUNT);
     }
+
+    #[test]
+    fn test_deployment_thread_pool_respects_dependencies() {
+        // setup:
+        // 0 <- 1 <- 3
+        // 0 <- 2 <- 3
+        // 4 has no dependency
+        const MAX_PARALLEL_DEPLOYS: usize = 3;
+        let dependencies: Vec<Vec<usize>> = vec![vec![], vec![0], vec![0], vec![1, 2], vec![]];
+        let pool = DeploymentThreadsPool::new();
+
+        // execute:
+        let finished_tasks = Arc::new(Mutex::new(Vec::new()));
+        let mut tasks = Vec::new();
+        for i in 0..dependencies.len() {
+            let finished_tasks_local = finished_tasks.clone();
+            tasks.push(move || {
+                thread::sleep(Duration::from_millis(200));
+                finished_tasks_local.lock().unwrap().push(i);
+                Result::<(), ()>::Ok(())
+            });
+        }
+
+        let ret = pool.run(
+            tasks,
+            dependencies.clone(),
+            || false,
+            NonZeroUsize::new(MAX_PARALLEL_DEPLOYS).unwrap(),
+        );
+
+        // verify:
+        assert!(ret.is_ok());
+        let finished_tasks = finished_tasks.lock().unwrap();
+        assert_eq!(finished_tasks.len(), dependencies.len());
+        let position = |task: usize| finished_tasks.iter().position(|t| *t == task).unwrap();
+        for (task, task_dependencies) in dependencies.iter().enumerate() {
+            for dependency in task_dependencies {
+                assert!(position(*dependency) < position(task));
+            }
+        }
+    }
+
+    #[test]
+    fn test_deployment_thread_pool_failed_dependency_blocks_dependents() {
+        // setup:
+        const MAX_PARALLEL_DEPLOYS: usize = 2;
+        let dependencies: Vec<Vec<usize>> = vec![vec![], vec![0], vec![1]];
+        let pool = DeploymentThreadsPool::new();
+
+        // execute:
+        let executed_tasks = Arc::new(AtomicUsize::new(0));
+        let mut tasks = Vec::new();
+        for i in 0..dependencies.len() {
+            let executed_tasks_local = executed_tasks.clone();
+            tasks.push(move || {
+                executed_tasks_local.fetch_add(1, Ordering::Relaxed);
+                match i == 0 {
+                    true => Result::<(), ()>::Err(()),
+                    false => Result::<(), ()>::Ok(()),
+                }
+            });
+        }
+
+        let ret = pool.run(tasks, dependencies, || false, NonZeroUsize::new(MAX_PARALLEL_DEPLOYS).unwrap());
+
+        // verify:
+        assert!(ret.is_err());
+        assert_eq!(executed_tasks.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_services_dependencies_indexes() {
+        // setup:
+        let db = Uuid::new_v4();
+        let migration = Uuid::new_v4();
+        let api = Uuid::new_v4();
+        let graph = ServiceDependencyGraph::new(vec![(db, vec![]), (migration, vec![db]), (api, vec![migration])])
+            .expect("graph should be valid");
+
+        // execute:
+        let forward = EnvironmentDeployment::services_dependencies(&[db, migration, api], &graph);
+        let backward = EnvironmentDeployment::services_dependencies(&[api, migration, db], &graph.reversed());
+
+        // verify:
+        assert_eq!(forward, vec![vec![], vec![0], vec![1]]);
+        assert_eq!(backward, vec![vec![], vec![0], vec![1]]);
+    }
+
+    #[test]
+    fn test_release_state_from_history() {
+        // setup:
+        let history = |payload: &str| serde_json::from_str::<Vec<HelmHistoryRow>>(payload).unwrap();
+        let deployed = history(
+            r#"[
+                {"revision": 1, "updated": "2021-06-17T08:37:37", "status": "superseded", "chart": "q-container-0.1.0", "app_version": "0.1"},
+                {"revision": 2, "updated": "2021-06-17T12:34:08", "status": "deployed", "chart": "q-container-0.1.0", "app_version": "0.1"},
+                {"revision": 3, "updated": "2021-06-17T12:36:08", "status": "failed", "chart": "q-container-0.1.0", "app_version": "0.1"}
+            ]"#,
+        );
+        let never_deployed = history(
+            r#"[
+                {"revision": 1, "updated": "2021-06-17T08:37:37", "status": "failed", "chart": "q-container-0.1.0", "app_version": "0.1"}
+            ]"#,
+        );
+
+        // execute & verify:
+        assert_eq!(ReleaseState::from_history(Ok(deployed)), ReleaseState::Deployed(2));
+        assert_eq!(ReleaseState::from_history(Ok(vec![])), ReleaseState::Absent);
+        assert_eq!(
+            ReleaseState::from_history(Err(HelmError::ReleaseDoesNotExist("my-app".to_string()))),
+            ReleaseState::Absent
+        );
+        assert!(matches!(
+            ReleaseState::from_history(Ok(never_deployed)),
+            ReleaseState::Unknown(_)
+        ));
+        assert!(matches!(
+            ReleaseState::from_history(Err(HelmError::ReleaseLocked("my-app".to_string()))),
+            ReleaseState::Unknown(_)
+        ));
+    }
 }
@@ -1,6 +1,7 @@
-use crate::cloud_provider::helm::{ChartInfo, HelmChart, ServiceChart};
+use crate::cloud_provider::helm::{ChartInfo, HelmChart, HelmChartNamespaces, ServiceChart};
 use crate::cloud_provider::DeploymentTarget;
 use crate::cmd::command::CommandKiller;
+use crate::deployment_action::plan::{plan_helm_release, PlannedChange};
 use crate::deployment_action::DeploymentAction;
 use crate::errors::{CommandError, EngineError};
 use crate::events::{EnvironmentStep, EventDetails, Stage};
@@ -53,6 +54,32 @@ impl HelmDeployment {
         }
     }
 
+    /// Deployment of a service chart, released in the namespace of the environment being deployed
+    pub fn new_for_service(
+        event_details: EventDetails,
+        tera_context: TeraContext,
+        chart_orginal_dir: PathBuf,
+        release_name: String,
+        workspace_directory: &str,
+        target: &DeploymentTarget,
+        startup_timeout: Option<Duration>,
+        k8s_selector: Option<String>,
+    ) -> HelmDeployment {
+        let mut helm_chart = ChartInfo {
+            name: release_name,
+            path: workspace_directory.to_string(),
+            namespace: HelmChartNamespaces::Custom,
+            custom_namespace: Some(target.environment.namespace().to_string()),
+            k8s_selector,
+            ..Default::default()
+        };
+        if let Some(timeout) = startup_timeout {
+            helm_chart.timeout_in_seconds = timeout.as_secs() as i64;
+        }
+
+        HelmDeployment::new(event_details, tera_context, chart_orginal_dir, None, helm_chart)
+    }
+
     pub fn prepare_helm_chart(&self) -> Result<(), Box<EngineError>> {
         // Copy the root folder
         generate_and_copy_all_files_into_dir(&self.chart_orginal_dir, &self.helm_chart.path, self.tera_context.clone())
@@ -86,6 +113,14 @@ impl HelmDeployment {
 
         Ok(())
     }
+
+    /// Render the chart and diff it against the live release, without installing anything
+    pub fn plan_release(&self, target: &DeploymentTarget) -> Result<PlannedChange, Box<EngineError>> {
+        self.prepare_helm_chart()?;
+
+        plan_helm_release(target, &self.helm_chart)
+            .map_err(|e| Box::new(EngineError::new_helm_error(self.event_details.clone(), e)))
+    }
 }
 
 impl DeploymentAction for HelmDeployment {
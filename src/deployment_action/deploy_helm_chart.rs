@@ -5,6 +5,7 @@ use crate::cloud_provider::service::{Action, Service};
 use crate::cloud_provider::DeploymentTarget;
 use crate::cmd::command::CommandKiller;
 use crate::deployment_action::pause_service::PauseServiceAction;
+use crate::deployment_action::plan::{PlannedChange, ResourceChange};
 use crate::deployment_action::restart_service::RestartServiceAction;
 use crate::deployment_action::{DeploymentAction, K8sResourceType};
 use crate::deployment_report::helm_chart::reporter::HelmChartDeploymentReporter;
@@ -85,6 +86,47 @@ impl<T: CloudProvider> DeploymentAction for HelmChart<T> {
         execute_long_deployment(HelmChartDeploymentReporter::new(self, target, Action::Create), task)
     }
 
+    fn on_plan(&self, target: &DeploymentTarget) -> Result<Vec<PlannedChange>, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Plan));
+        let env_logger = target.env_logger(self, EnvironmentStep::Plan);
+        let logger = EnvProgressLogger::new(&env_logger);
+
+        let prepare_chart = || -> Result<(), Box<EngineError>> {
+            prepare_helm_chart_directory(self, target, event_details.clone(), &logger)?;
+            check_resources_are_allowed_to_install(self, target, event_details.clone(), &logger)
+        };
+        if let Err(err) = prepare_chart() {
+            env_logger.send_error(*err.clone());
+            return Err(err);
+        }
+        env_logger.send_success("📥 Helm chart is ready to be compared with the live release".to_string());
+
+        // Only values are given to the diff, other upgrade arguments (i.e: timeout) are not supported by helm diff
+        let args = self.helm_values_arguments().collect::<Vec<_>>();
+        let diff = target
+            .helm
+            .upgrade_diff_raw(
+                self.helm_release_name(),
+                self.chart_workspace_directory(),
+                target.environment.namespace(),
+                &args.iter().map(|x| x.as_ref()).collect::<Vec<_>>(),
+                &[],
+            )
+            .map_err(|err| {
+                Box::new(EngineError::new_helm_chart_error(
+                    event_details.clone(),
+                    HelmChartError::HelmError(err),
+                ))
+            })?;
+        let chart_info = ChartInfo::new_from_release_name(self.helm_release_name(), target.environment.namespace());
+
+        Ok(vec![PlannedChange::HelmRelease {
+            release_name: self.helm_release_name().to_string(),
+            change: ResourceChange::from_helm_release(target.helm.history(&chart_info, &[]), &diff),
+            diff,
+        }])
+    }
+
     fn on_pause(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
         let _event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Pause));
 
@@ -658,9 +700,9 @@ mod tests {
     #[test]
     fn test_replace_qovery_env_variables() {
         let envs = hashmap! {
-            "TOTO".to_string() => VariableInfo { value: "toto_var".to_string(), is_secret: false},
-            "LABEL_NAME".to_string() => VariableInfo {value: "toto_label".to_string(), is_secret: false},
-            "NGNIX_TAG".to_string() => VariableInfo {value: "42".to_string(), is_secret: false}
+            "TOTO".to_string() => VariableInfo { value: "toto_var".to_string(), is_secret: false, external_secret: None},
+            "LABEL_NAME".to_string() => VariableInfo {value: "toto_label".to_string(), is_secret: false, external_secret: None},
+            "NGNIX_TAG".to_string() => VariableInfo {value: "42".to_string(), is_secret: false, external_secret: None}
         };
 
         let ret = replace_qovery_env_variable(Cow::Borrowed("    tag: \"qovery.env.NGNIX_TAG\""), &envs);
@@ -769,8 +811,8 @@ controller:
         let env_id = Uuid::parse_str("11111111-1111-1111-1111-111111111111").unwrap();
         let project_id = Uuid::parse_str("22222222-2222-2222-2222-222222222222").unwrap();
         let envs = hashmap! {
-            "NGINX_TAG".to_string() => VariableInfo { value: "42".to_string(), is_secret: false},
-            "LABEL_NAME".to_string() => VariableInfo {value: "toto_label".to_string(), is_secret: false}
+            "NGINX_TAG".to_string() => VariableInfo { value: "42".to_string(), is_secret: false, external_secret: None},
+            "LABEL_NAME".to_string() => VariableInfo {value: "toto_label".to_string(), is_secret: false, external_secret: None}
         };
         let mut output: Vec<u8> = vec![];
 
@@ -1,10 +1,11 @@
 use super::utils::delete_cached_image;
-use crate::cloud_provider::helm::{ChartInfo, HelmChartNamespaces};
 use crate::cloud_provider::service::{Action, Service};
 use crate::cloud_provider::DeploymentTarget;
 use crate::cmd::kubectl::kubectl_get_job_pod_output;
 use crate::cmd::structs::KubernetesPodStatusPhase;
 use crate::deployment_action::deploy_helm::HelmDeployment;
+use crate::deployment_action::plan::{plan_image, plan_mirrored_image, PlannedChange};
+use crate::deployment_action::scan_image::scan_image_if_enabled;
 use crate::deployment_action::utils::{get_last_deployed_image, mirror_image_if_necessary, KubeObjectKind};
 use crate::deployment_action::DeploymentAction;
 use crate::deployment_report::job::reporter::JobDeploymentReporter;
@@ -15,18 +16,20 @@ use crate::events::EngineEvent;
 use crate::events::{EnvironmentStep, EventDetails, EventMessage, Stage};
 use crate::io_models::job::JobSchedule;
 use crate::models::job::{ImageSource, Job, JobService};
+use crate::models::job_output::{is_valid_job_output_name, job_output_secret, JobOutputVariable};
 use crate::models::types::{CloudProvider, ToTeraContext};
 use crate::runtime::block_on;
 use k8s_openapi::api::batch::v1::{CronJob, Job as K8sJob};
-use k8s_openapi::api::core::v1::Pod;
-use kube::api::{AttachParams, ListParams, PostParams};
+use k8s_openapi::api::core::v1::{Pod, Secret};
+use kube::api::{AttachParams, DeleteParams, ListParams, Patch, PatchParams, PostParams};
 use kube::runtime::wait::{await_condition, Condition};
 use kube::Api;
 use retry::{Error, OperationResult};
-use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use std::cmp::min;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 impl<T: CloudProvider> DeploymentAction for Job<T>
 where
@@ -43,7 +46,7 @@ where
         };
 
         match job_schedule {
-            JobSchedule::OnStart {} | JobSchedule::Cron { .. } => {
+            JobSchedule::OnStart {} | JobSchedule::Cron { .. } | JobSchedule::OnTrigger { .. } => {
                 let (pre_run, run, post_run) = run_job(self, target, &event_details);
                 let task = DeploymentTaskImpl {
                     pre_run: &pre_run,
@@ -62,10 +65,30 @@ where
         }
     }
 
+    fn on_plan(&self, target: &DeploymentTarget) -> Result<Vec<PlannedChange>, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Plan));
+
+        // Jobs only triggered on pause or delete are not deployed on create
+        if !self.should_force_trigger() && matches!(self.schedule(), JobSchedule::OnPause {} | JobSchedule::OnDelete {})
+        {
+            return Ok(vec![]);
+        }
+
+        let mut changes = vec![match &self.image_source {
+            ImageSource::Registry { source } => plan_mirrored_image(self.long_id(), source, target),
+            ImageSource::Build { source } => plan_image(target.container_registry, &source.image),
+        }];
+
+        let helm = helm_deployment(self, target, event_details)?;
+        changes.push(helm.plan_release(target)?);
+
+        Ok(changes)
+    }
+
     fn on_pause(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
         let event_details = self.get_event_details(Stage::Environment(self.action().to_environment_step()));
         match self.schedule() {
-            JobSchedule::Cron { .. } => {
+            JobSchedule::Cron { .. } | JobSchedule::OnTrigger { .. } => {
                 let (pre_run, run, post_run) = delete_job(self, target, &event_details);
                 let task = DeploymentTaskImpl {
                     pre_run: &pre_run,
@@ -111,7 +134,10 @@ where
                     task,
                 )
             }
-            JobSchedule::Cron { .. } | JobSchedule::OnStart {} | JobSchedule::OnPause {} => Ok(()),
+            JobSchedule::Cron { .. }
+            | JobSchedule::OnTrigger { .. }
+            | JobSchedule::OnStart {}
+            | JobSchedule::OnPause {} => Ok(()),
         }?;
 
         let (pre_run, run, post_run) = delete_job(self, target, &event_details);
@@ -138,6 +164,26 @@ struct TaskContext {
     last_deployed_image: Option<String>,
 }
 
+fn helm_deployment<T: CloudProvider>(
+    job: &Job<T>,
+    target: &DeploymentTarget,
+    event_details: EventDetails,
+) -> Result<HelmDeployment, Box<EngineError>>
+where
+    Job<T>: JobService,
+{
+    Ok(HelmDeployment::new_for_service(
+        event_details,
+        job.to_tera_context(target)?,
+        PathBuf::from(job.helm_chart_dir()),
+        job.helm_release_name(),
+        job.workspace_directory(),
+        target,
+        Some(job.startup_timeout()),
+        Some(job.kube_label_selector()),
+    ))
+}
+
 fn run_job<'a, T: CloudProvider>(
     job: &'a Job<T>,
     target: &'a DeploymentTarget,
@@ -155,7 +201,7 @@ where
         match &job.image_source {
             // If image come from a registry, we mirror it to the cluster registry in order to avoid losing access to it due to creds expiration
             ImageSource::Registry { source } => {
-                mirror_image_if_necessary(
+                let mirrored_image = mirror_image_if_necessary(
                     job.long_id(),
                     source,
                     source.tag_for_mirror(job.long_id()),
@@ -164,6 +210,16 @@ where
                     event_details.clone(),
                     metrics_registry.clone(),
                 )?;
+                scan_image_if_enabled(
+                    &mirrored_image.image_name(),
+                    &mirrored_image.registry,
+                    &job.advanced_settings().security_vulnerability_allowlist,
+                    target.kubernetes.advanced_settings(),
+                    &|msg| logger.info(msg),
+                    &|msg| logger.recap(msg),
+                    event_details.clone(),
+                    target.should_abort,
+                )?;
             }
             ImageSource::Build { .. } => {}
         }
@@ -185,147 +241,48 @@ where
     };
 
     let task = move |logger: &EnvProgressLogger, state: TaskContext| -> Result<TaskContext, Box<EngineError>> {
-        let chart = ChartInfo {
-            name: job.helm_release_name(),
-            path: job.workspace_directory().to_string(),
-            namespace: HelmChartNamespaces::Custom,
-            custom_namespace: Some(target.environment.namespace().to_string()),
-            timeout_in_seconds: job.startup_timeout().as_secs() as i64,
-            k8s_selector: Some(job.kube_label_selector()),
-            ..Default::default()
-        };
-
-        let helm = HelmDeployment::new(
-            event_details.clone(),
-            job.to_tera_context(target)?,
-            PathBuf::from(job.helm_chart_dir()),
-            None,
-            chart,
-        );
-
-        if job.schedule().is_job() {
-            // We first need to delete the old job, because job spec cannot be updated (due to be an immutable resources)
-            helm.on_delete(target)?;
-        }
+        let helm = helm_deployment(job, target, event_details.clone())?;
 
         // Wait for the job to terminate in order to have his status
         // For cronjob we dont care as we don't control when it is executed
         if job.schedule().is_job() {
-            // create job
-            helm.on_create(target)?;
-
-            // Get kube config file
-            let kubernetes_config_file_path = target.kubernetes.get_kubeconfig_file_path()?;
-            let job_pod_selector = format!("job-name={}", job.kube_name());
-            let kube_pod_api: Api<Pod> = Api::namespaced(target.kube.clone(), target.environment.namespace());
-
-            let job_max_nb_restart = job.max_nb_restart();
-            let mut job_creation_iterations = 0;
-            let mut set_of_pods_already_processed: HashSet<String> = HashSet::new();
-
+            let advanced_settings = job.advanced_settings();
+            let mut attempt = 0;
             loop {
-                // Wait for the pod to be started to get its name
-                let pod_name = get_active_job_pod_by_selector(
-                    kube_pod_api.clone(),
-                    &job_pod_selector,
-                    event_details,
-                    &set_of_pods_already_processed,
-                )?;
-                set_of_pods_already_processed.insert(pod_name.clone());
-
-                // Wait for the job container to be terminated
-                logger.info(format!("Waiting for the job container {} to be processed...", job.kube_name()));
-                let _ = block_on(await_condition(
-                    kube_pod_api.clone(),
-                    &pod_name,
-                    is_job_pod_container_terminated(job.kube_name()),
-                ));
-
-                // Get JSON output from shared volume
-                let result_json_output = kubectl_get_job_pod_output(
-                    kubernetes_config_file_path.clone(),
-                    target.cloud_provider.credentials_environment_variables(),
-                    target.environment.namespace(),
-                    &pod_name,
-                );
-                match result_json_output {
-                    Ok(json) => {
-                        let result_serde_json: Result<HashMap<String, JobOutputVariable>, serde_json::Error> =
-                            serialize_job_output(&json);
-                        match result_serde_json {
-                            Ok(deserialized_json_hashmap) => {
-                                let deserialized_json_hashmap_with_uppercase_keys: HashMap<String, JobOutputVariable> =
-                                    deserialized_json_hashmap
-                                        .iter()
-                                        .map(|(key, value)| (key.to_uppercase(), value.clone()))
-                                        .collect();
-                                logger.core_configuration_for_job(
-                                    "Job output succeeded. Environment variables will be synchronized.".to_string(),
-                                    serde_json::to_string(&deserialized_json_hashmap_with_uppercase_keys)
-                                        .unwrap_or_else(|_| "{}".to_string()),
-                                )
-                            }
-                            Err(err) => {
-                                logger.log(EngineEvent::Warning(
-                                    event_details.clone(),
-                                    EventMessage::new_from_engine_error(
-                                        EngineError::new_invalid_job_output_cannot_be_serialized(
-                                            event_details.clone(),
-                                            err,
-                                            &json,
-                                        ),
-                                    ),
-                                ));
-                            }
-                        }
-                    }
-                    Err(err) => {
-                        info!(
-                            "Cannot get JSON job output: {}",
-                            err.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)
+                let job_output = match run_job_to_completion(job, target, &helm, logger, event_details)? {
+                    JobRun::Succeeded { output } => output,
+                    JobRun::Failed(err) if attempt < advanced_settings.job_retry_max_attempts => {
+                        attempt += 1;
+                        let backoff = job_retry_backoff(
+                            attempt,
+                            Duration::from_secs(advanced_settings.job_retry_initial_backoff_seconds as u64),
+                            Duration::from_secs(advanced_settings.job_retry_max_backoff_seconds as u64),
                         );
+                        logger.warning(format!(
+                            "🔁 {} Retrying in {} seconds (attempt {}/{})",
+                            err.user_log_message(),
+                            backoff.as_secs(),
+                            attempt,
+                            advanced_settings.job_retry_max_attempts
+                        ));
+                        if !sleep_unless_aborted(backoff, target.should_abort) {
+                            return Err(Box::new(EngineError::new_task_cancellation_requested(event_details.clone())));
+                        }
+                        continue;
                     }
+                    JobRun::Failed(err) => return Err(err),
                 };
 
-                // Write file in shared volume to let the waiting container terminate
-                block_on(kube_pod_api.clone().exec(
-                    &pod_name,
-                    vec!["touch", "/qovery-output/terminate"],
-                    &AttachParams::default().container("qovery-wait-container-output"),
-                ))
-                .map_err(|_err| {
-                    EngineError::new_job_error(
-                        event_details.clone(),
-                        format!("Cannot create terminate file inside waiting container for pod {}", &pod_name),
-                    )
-                })?;
-
-                // wait for job to finish
-                let jobs: Api<K8sJob> = Api::namespaced(target.kube.clone(), target.environment.namespace());
-                let ret = block_on(await_condition(jobs, job.kube_name(), is_job_terminated())).map_err(|_err| {
-                    EngineError::new_job_error(
-                        event_details.clone(),
-                        format!("Cannot find job for terminated pod {}", &pod_name),
-                    )
-                })?;
-                let job_status_result = match job_status(&ret.as_ref()) {
-                    JobStatus::Success => return Ok(state),
-                    JobStatus::NotRunning | JobStatus::Running => unreachable!(),
-                    JobStatus::Failure { reason, message } => {
-                        let msg = format!("Job failed to correctly run due to {reason} {message}");
-                        Err(EngineError::new_job_error(event_details.clone(), msg))
-                    }
-                };
-
-                // If job has restarted the maximum time, then return the result that should be an Err
-                if job_creation_iterations == job_max_nb_restart {
-                    job_status_result?;
+                // Outputs of lifecycle jobs are injected into the services deployed after them
+                if let (Some(output), Action::Create) = (job_output, job.action()) {
+                    export_job_output(job, target, output, logger, event_details)?;
                 }
-                job_creation_iterations += 1;
+
+                return Ok(state);
             }
         }
 
-        // Cronjob will be installed
+        // Cronjob will be installed, triggered jobs are installed as a suspended cronjob
         if job.is_cron_job() && !job.is_force_trigger() {
             // create cronjob
             helm.on_create(target)?;
@@ -439,26 +396,23 @@ where
     };
 
     let task = move |_logger: &EnvProgressLogger, state: TaskContext| -> Result<TaskContext, Box<EngineError>> {
-        let chart = ChartInfo {
-            name: job.helm_release_name(),
-            path: job.workspace_directory().to_string(),
-            namespace: HelmChartNamespaces::Custom,
-            custom_namespace: Some(target.environment.namespace().to_string()),
-            timeout_in_seconds: job.startup_timeout().as_secs() as i64,
-            k8s_selector: Some(job.kube_label_selector()),
-            ..Default::default()
-        };
-
-        let helm = HelmDeployment::new(
-            event_details.clone(),
-            job.to_tera_context(target)?,
-            PathBuf::from(job.helm_chart_dir()),
-            None,
-            chart,
-        );
+        let helm = helm_deployment(job, target, event_details.clone())?;
 
         helm.on_delete(target)?;
 
+        // The output secret is not part of the chart
+        let secrets: Api<Secret> = Api::namespaced(target.kube.clone(), target.environment.namespace());
+        match block_on(secrets.delete(&job.output_secret_name(), &DeleteParams::default())) {
+            Ok(_) => {}
+            Err(kube::Error::Api(api_err)) if api_err.code == 404 => {}
+            Err(err) => {
+                return Err(Box::new(EngineError::new_job_error(
+                    event_details.clone(),
+                    format!("Cannot delete job output secret {}: {}", job.output_secret_name(), err),
+                )))
+            }
+        }
+
         Ok(state)
     };
 
@@ -497,6 +451,216 @@ where
     (pre_run, task, post_run)
 }
 
+enum JobRun {
+    Succeeded {
+        output: Option<HashMap<String, JobOutputVariable>>,
+    },
+    /// The job itself failed, once all its pods restarts have been consumed
+    Failed(Box<EngineError>),
+}
+
+/// (Re)create the job and wait for it to terminate, collecting the output of its pods
+fn run_job_to_completion<T: CloudProvider>(
+    job: &Job<T>,
+    target: &DeploymentTarget,
+    helm: &HelmDeployment,
+    logger: &EnvProgressLogger,
+    event_details: &EventDetails,
+) -> Result<JobRun, Box<EngineError>>
+where
+    Job<T>: JobService,
+{
+    // We first need to delete the old job, because job spec cannot be updated (due to be an immutable resources)
+    helm.on_delete(target)?;
+
+    // create job
+    helm.on_create(target)?;
+
+    // Get kube config file
+    let kubernetes_config_file_path = target.kubernetes.get_kubeconfig_file_path()?;
+    let job_pod_selector = format!("job-name={}", job.kube_name());
+    let kube_pod_api: Api<Pod> = Api::namespaced(target.kube.clone(), target.environment.namespace());
+
+    let job_max_nb_restart = job.max_nb_restart();
+    let mut job_creation_iterations = 0;
+    let mut set_of_pods_already_processed: HashSet<String> = HashSet::new();
+
+    loop {
+        // Wait for the pod to be started to get its name
+        let pod_name = get_active_job_pod_by_selector(
+            kube_pod_api.clone(),
+            &job_pod_selector,
+            event_details,
+            &set_of_pods_already_processed,
+        )?;
+        set_of_pods_already_processed.insert(pod_name.clone());
+
+        // Wait for the job container to be terminated
+        logger.info(format!("Waiting for the job container {} to be processed...", job.kube_name()));
+        let _ = block_on(await_condition(
+            kube_pod_api.clone(),
+            &pod_name,
+            is_job_pod_container_terminated(job.kube_name()),
+        ));
+
+        // Get JSON output from shared volume
+        let mut job_output = None;
+        let result_json_output = kubectl_get_job_pod_output(
+            kubernetes_config_file_path.clone(),
+            target.cloud_provider.credentials_environment_variables(),
+            target.environment.namespace(),
+            &pod_name,
+        );
+        match result_json_output {
+            Ok(json) => {
+                let result_serde_json: Result<HashMap<String, JobOutputVariable>, serde_json::Error> =
+                    serialize_job_output(&json);
+                match result_serde_json {
+                    Ok(deserialized_json_hashmap) => {
+                        let deserialized_json_hashmap_with_uppercase_keys: HashMap<String, JobOutputVariable> =
+                            deserialized_json_hashmap
+                                .iter()
+                                .map(|(key, value)| (key.to_uppercase(), value.clone()))
+                                .collect();
+                        logger.core_configuration_for_job(
+                            "Job output succeeded. Environment variables will be synchronized.".to_string(),
+                            serde_json::to_string(&deserialized_json_hashmap_with_uppercase_keys)
+                                .unwrap_or_else(|_| "{}".to_string()),
+                        );
+                        job_output = Some(deserialized_json_hashmap_with_uppercase_keys);
+                    }
+                    Err(err) => {
+                        logger.log(EngineEvent::Warning(
+                            event_details.clone(),
+                            EventMessage::new_from_engine_error(
+                                EngineError::new_invalid_job_output_cannot_be_serialized(
+                                    event_details.clone(),
+                                    err,
+                                    &json,
+                                ),
+                            ),
+                        ));
+                    }
+                }
+            }
+            Err(err) => {
+                info!(
+                    "Cannot get JSON job output: {}",
+                    err.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)
+                );
+            }
+        };
+
+        // Write file in shared volume to let the waiting container terminate
+        block_on(kube_pod_api.clone().exec(
+            &pod_name,
+            vec!["touch", "/qovery-output/terminate"],
+            &AttachParams::default().container("qovery-wait-container-output"),
+        ))
+        .map_err(|_err| {
+            EngineError::new_job_error(
+                event_details.clone(),
+                format!("Cannot create terminate file inside waiting container for pod {}", &pod_name),
+            )
+        })?;
+
+        // wait for job to finish
+        let jobs: Api<K8sJob> = Api::namespaced(target.kube.clone(), target.environment.namespace());
+        let ret = block_on(await_condition(jobs, job.kube_name(), is_job_terminated())).map_err(|_err| {
+            EngineError::new_job_error(
+                event_details.clone(),
+                format!("Cannot find job for terminated pod {}", &pod_name),
+            )
+        })?;
+        let job_status_result = match job_status(&ret.as_ref()) {
+            JobStatus::Success => return Ok(JobRun::Succeeded { output: job_output }),
+            JobStatus::NotRunning | JobStatus::Running => unreachable!(),
+            JobStatus::Failure { reason, message } => {
+                let msg = format!("Job failed to correctly run due to {reason} {message}");
+                EngineError::new_job_error(event_details.clone(), msg)
+            }
+        };
+
+        // If job has restarted the maximum time, then return the result that should be an Err
+        if job_creation_iterations == job_max_nb_restart {
+            return Ok(JobRun::Failed(Box::new(job_status_result)));
+        }
+        job_creation_iterations += 1;
+    }
+}
+
+/// Delay before running a failed job again, doubled at each attempt
+fn job_retry_backoff(attempt: u32, initial_backoff: Duration, max_backoff: Duration) -> Duration {
+    let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+    min(initial_backoff.saturating_mul(factor), max_backoff)
+}
+
+/// Sleep for the given duration in short steps, to stop waiting as soon as the deployment is aborted.
+/// Returns false if the deployment has been aborted
+fn sleep_unless_aborted(duration: Duration, should_abort: &(dyn Fn() -> bool + Send + Sync)) -> bool {
+    let deadline = Instant::now() + duration;
+    loop {
+        if should_abort() {
+            return false;
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        std::thread::sleep(min(deadline - now, Duration::from_secs(1)));
+    }
+}
+
+/// Store the sensitive outputs of the job in its output secret, and share its outputs with the services
+/// depending on it
+fn export_job_output<T: CloudProvider>(
+    job: &Job<T>,
+    target: &DeploymentTarget,
+    output: HashMap<String, JobOutputVariable>,
+    logger: &EnvProgressLogger,
+    event_details: &EventDetails,
+) -> Result<(), Box<EngineError>>
+where
+    Job<T>: JobService,
+{
+    let mut variables = BTreeMap::new();
+    for (key, variable) in output {
+        match is_valid_job_output_name(&key) {
+            true => {
+                variables.insert(key, variable);
+            }
+            false => logger.warning(format!(
+                "Job output `{key}` is not a valid environment variable name, it is not propagated to other services"
+            )),
+        }
+    }
+
+    let secret_name = job.output_secret_name();
+    let secret = job_output_secret(
+        &secret_name,
+        target.environment.namespace(),
+        BTreeMap::from([
+            ("qovery.com/service-id".to_string(), job.long_id().to_string()),
+            ("qovery.com/environment-id".to_string(), target.environment.long_id.to_string()),
+        ]),
+        &variables,
+    );
+    let secrets: Api<Secret> = Api::namespaced(target.kube.clone(), target.environment.namespace());
+    block_on(secrets.patch(&secret_name, &PatchParams::apply("qovery").force(), &Patch::Apply(&secret))).map_err(
+        |err| {
+            EngineError::new_job_error(
+                event_details.clone(),
+                format!("Cannot store job output in secret {secret_name}: {err}"),
+            )
+        },
+    )?;
+
+    target.job_outputs.insert(*job.long_id(), secret_name, variables);
+
+    Ok(())
+}
+
 enum JobStatus {
     NotRunning,
     Running,
@@ -655,23 +819,6 @@ fn is_job_pod_container_terminated(job_container_name: &str) -> impl Condition<P
     move |job_pod: Option<&Pod>| job_pod_container_status_is_terminated(&job_pod, job_container_name)
 }
 
-// Used to validate the job json output format with serde
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
-#[serde(default)]
-struct JobOutputVariable {
-    pub value: String,
-    pub sensitive: bool,
-}
-
-impl Default for JobOutputVariable {
-    fn default() -> Self {
-        JobOutputVariable {
-            value: String::new(),
-            sensitive: true,
-        }
-    }
-}
-
 fn serialize_job_output(json: &str) -> Result<HashMap<String, JobOutputVariable>, serde_json::Error> {
     let serde_hash_map: HashMap<&str, Value> = serde_json::from_str(json)?;
     let mut job_output_variables: HashMap<String, JobOutputVariable> = HashMap::new();
@@ -714,7 +861,10 @@ fn serialize_job_output(json: &str) -> Result<HashMap<String, JobOutputVariable>
 
 #[cfg(test)]
 mod test {
-    use crate::deployment_action::deploy_job::{serialize_job_output, JobOutputVariable};
+    use crate::deployment_action::deploy_job::{job_retry_backoff, serialize_job_output, sleep_unless_aborted};
+    use crate::models::job_output::JobOutputVariable;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::{Duration, Instant};
 
     #[test]
     fn should_serialize_json_to_job_output_variable_with_string_value() {
@@ -771,4 +921,33 @@ mod test {
         let json_final = serde_json::to_string(&hashmap).unwrap();
         println!("{json_final}");
     }
+
+    #[test]
+    fn test_job_retry_backoff() {
+        // setup:
+        let initial_backoff = Duration::from_secs(10);
+        let max_backoff = Duration::from_secs(60);
+
+        // execute & verify:
+        assert_eq!(job_retry_backoff(1, initial_backoff, max_backoff), Duration::from_secs(10));
+        assert_eq!(job_retry_backoff(2, initial_backoff, max_backoff), Duration::from_secs(20));
+        assert_eq!(job_retry_backoff(3, initial_backoff, max_backoff), Duration::from_secs(40));
+        assert_eq!(job_retry_backoff(4, initial_backoff, max_backoff), Duration::from_secs(60));
+        assert_eq!(job_retry_backoff(64, initial_backoff, max_backoff), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_sleep_unless_aborted() {
+        // setup:
+        let checks = AtomicU32::new(0);
+        let abort_after_two_checks = || checks.fetch_add(1, Ordering::SeqCst) >= 2;
+        let never_abort = || false;
+
+        // execute & verify:
+        let start = Instant::now();
+        assert!(!sleep_unless_aborted(Duration::from_secs(60), &abort_after_two_checks));
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        assert!(sleep_unless_aborted(Duration::from_millis(10), &never_abort));
+    }
 }
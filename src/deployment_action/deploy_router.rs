@@ -1,18 +1,21 @@
 use crate::cloud_provider::helm::{ChartInfo, HelmAction, HelmChartNamespaces};
-use crate::cloud_provider::models::CustomDomain;
+use crate::cloud_provider::models::{CertificateSource, CustomDomain};
 use crate::cloud_provider::service::{Action, Service};
 use crate::cloud_provider::DeploymentTarget;
 use crate::deployment_action::check_dns::CheckDnsForDomains;
 use crate::deployment_action::deploy_helm::HelmDeployment;
+use crate::deployment_action::plan::{PlannedChange, ResourceChange};
 use crate::deployment_action::DeploymentAction;
 use crate::deployment_report::router::reporter::RouterDeploymentReporter;
 use crate::deployment_report::{execute_long_deployment, DeploymentTaskImpl};
 use crate::errors::EngineError;
-use crate::events::{EnvironmentStep, Stage};
+use crate::events::{EnvironmentStep, EventDetails, Stage};
 use crate::models::router::Router;
+use crate::models::tls_certificate::TlsCertificate;
 use crate::models::types::{CloudProvider, ToTeraContext};
 
 use crate::deployment_report::logger::{EnvProgressLogger, EnvSuccessLogger};
+use chrono::Utc;
 use std::path::PathBuf;
 
 impl<T: CloudProvider> DeploymentAction for Router<T>
@@ -23,24 +26,30 @@ where
         let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
         let pre_run = |_: &EnvProgressLogger| -> Result<(), Box<EngineError>> { Ok(()) };
         let run = |logger: &EnvProgressLogger, _: ()| -> Result<(), Box<EngineError>> {
-            let chart = ChartInfo {
-                name: self.helm_release_name(),
-                path: self.workspace_directory().to_string(),
-                namespace: HelmChartNamespaces::Custom,
-                custom_namespace: Some(target.environment.namespace().to_string()),
-                ..Default::default()
-            };
-
-            let helm = HelmDeployment::new(
-                event_details.clone(),
-                self.to_tera_context(target)?,
-                PathBuf::from(self.helm_chart_dir()),
-                None,
-                chart,
-            );
-
+            let helm = helm_deployment(self, target, event_details.clone())?;
             helm.on_create(target)?;
 
+            // uploaded certificates are not renewed by us, warn before they expire
+            for custom_domain in self.custom_domains.iter().filter(|cd| cd.generate_certificate) {
+                if let CertificateSource::Uploaded {
+                    certificate_chain,
+                    private_key,
+                } = &custom_domain.certificate_source
+                {
+                    match TlsCertificate::from_pem(certificate_chain, private_key) {
+                        Ok(certificate) => {
+                            if let Some(msg) = certificate.expiry_warning(&custom_domain.domain, Utc::now()) {
+                                logger.warning(msg);
+                            }
+                        }
+                        Err(err) => logger.warning(format!(
+                            "🔒 Certificate of domain {} cannot be read: {err}",
+                            custom_domain.domain
+                        )),
+                    }
+                }
+            }
+
             // check non custom domains
             let custom_domains_to_check: Vec<CustomDomain> = if self.advanced_settings.custom_domain_check_enabled {
                 self.custom_domains.clone()
@@ -70,6 +79,27 @@ where
         )
     }
 
+    fn on_plan(&self, target: &DeploymentTarget) -> Result<Vec<PlannedChange>, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Plan));
+        let helm = helm_deployment(self, target, event_details)?;
+        let release = helm.plan_release(target)?;
+
+        // The router release only contains the ingress, so its change is the one of the ingress
+        let ingress_change = match &release {
+            PlannedChange::HelmRelease { change, .. } => *change,
+            _ => ResourceChange::Update,
+        };
+        let ingress = PlannedChange::Ingress {
+            name: self.kube_name().to_string(),
+            domains: std::iter::once(self.default_domain.clone())
+                .chain(self.custom_domains.iter().map(|domain| domain.domain.clone()))
+                .collect(),
+            change: ingress_change,
+        };
+
+        Ok(vec![ingress, release])
+    }
+
     fn on_pause(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
         execute_long_deployment(
             RouterDeploymentReporter::new(self, target, Action::Pause),
@@ -91,7 +121,7 @@ where
                 let helm = HelmDeployment::new(
                     self.get_event_details(Stage::Environment(EnvironmentStep::Delete)),
                     self.to_tera_context(target)?,
-                    PathBuf::from(self.helm_chart_dir().as_str()),
+                    PathBuf::from(self.helm_chart_dir(target).as_str()),
                     None,
                     chart,
                 );
@@ -109,3 +139,23 @@ where
         )
     }
 }
+
+fn helm_deployment<T: CloudProvider>(
+    router: &Router<T>,
+    target: &DeploymentTarget,
+    event_details: EventDetails,
+) -> Result<HelmDeployment, Box<EngineError>>
+where
+    Router<T>: ToTeraContext,
+{
+    Ok(HelmDeployment::new_for_service(
+        event_details,
+        router.to_tera_context(target)?,
+        PathBuf::from(router.helm_chart_dir(target)),
+        router.helm_release_name(),
+        router.workspace_directory(),
+        target,
+        None,
+        None,
+    ))
+}
@@ -2,6 +2,7 @@ use crate::cloud_provider::kubernetes::Kubernetes;
 use crate::cloud_provider::DeploymentTarget;
 use crate::cmd;
 use crate::cmd::kubectl::kubectl_exec_delete_secret;
+use crate::deployment_action::plan::PlannedChange;
 use crate::deployment_action::DeploymentAction;
 use crate::errors::{CommandError, EngineError};
 use crate::events::{EnvironmentStep, EventDetails, Stage};
@@ -72,6 +73,24 @@ impl TerraformDeployment {
         Ok(())
     }
 
+    /// Render the terraform files and only plan them, nothing is applied on the cloud provider side
+    pub fn plan(&self, target: &DeploymentTarget) -> Result<PlannedChange, Box<EngineError>> {
+        self.prepare_terraform_files()?;
+        let output = cmd::terraform::terraform_init_validate_plan(
+            &self.destination_folder.to_string_lossy(),
+            target
+                .kubernetes
+                .cloud_provider()
+                .credentials_environment_variables()
+                .as_slice(),
+        )
+        .map_err(|err| Box::new(EngineError::new_terraform_error(self.event_details.clone(), err)))?;
+
+        Ok(PlannedChange::CloudResources {
+            plan: output.join("\n"),
+        })
+    }
+
     pub fn delete_tfstate_secret(
         kubernetes: &dyn Kubernetes,
         namespace: &str,
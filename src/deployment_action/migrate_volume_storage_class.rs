@@ -0,0 +1,455 @@
+use crate::cloud_provider::DeploymentTarget;
+use crate::deployment_action::pause_service::PauseServiceAction;
+use crate::deployment_action::restore_volume_snapshot::set_reclaim_policy;
+use crate::deployment_action::DeploymentAction;
+use crate::deployment_report::logger::EnvProgressLogger;
+use crate::errors::{CommandError, EngineError};
+use crate::events::EventDetails;
+use crate::kubers_utils::{kube_delete_all_from_selector, KubeDeleteMode};
+use crate::models::volume_snapshot::VolumeStorageClass;
+use crate::runtime::block_on;
+use k8s_openapi::api::apps::v1::StatefulSet;
+use k8s_openapi::api::core::v1::{
+    AWSElasticBlockStoreVolumeSource, CSIPersistentVolumeSource, PersistentVolume, PersistentVolumeClaim,
+    PersistentVolumeClaimSpec, PersistentVolumeSpec,
+};
+use k8s_openapi::api::storage::v1::StorageClass;
+use kube::api::{DeleteParams, ListParams, ObjectMeta, PostParams};
+use kube::runtime::wait::{await_condition, conditions};
+use kube::Api;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+const EBS_CSI_PROVISIONER: &str = "ebs.csi.aws.com";
+const EBS_IN_TREE_PROVISIONER: &str = "kubernetes.io/aws-ebs";
+const PROVISIONED_BY_ANNOTATION: &str = "pv.kubernetes.io/provisioned-by";
+const DISK_TYPE_LABELS: [&str; 2] = ["qovery.com/disk-type", "diskType"];
+
+/// Moves the volumes of a stateful service to the storage class of their storage (i.e: from the in-tree
+/// aws-ebs provisioner to the EBS CSI driver when snapshots are enabled).
+/// The disk of each volume is kept and bound again under the new storage class, nothing is copied.
+/// The service is stopped meanwhile, and its statefulset removed so it is recreated with the new volume claim templates.
+pub struct MigrateVolumeStorageClassAction {
+    selector: String,
+    storage_classes: Vec<VolumeStorageClass>,
+    timeout: Duration,
+    event_details: EventDetails,
+}
+
+impl MigrateVolumeStorageClassAction {
+    pub fn new(
+        selector: String,
+        storage_classes: Vec<VolumeStorageClass>,
+        timeout: Duration,
+        event_details: EventDetails,
+    ) -> MigrateVolumeStorageClassAction {
+        MigrateVolumeStorageClassAction {
+            selector,
+            storage_classes,
+            timeout,
+            event_details,
+        }
+    }
+
+    pub fn migrate(&self, target: &DeploymentTarget, logger: &EnvProgressLogger) -> Result<(), Box<EngineError>> {
+        let namespace = target.environment.namespace();
+        let mut pvcs_to_migrate = Vec::new();
+        for storage_class in &self.storage_classes {
+            let pvcs: Api<PersistentVolumeClaim> = Api::namespaced(target.kube.clone(), namespace);
+            let pvcs = block_on(pvcs.list(&ListParams::default().labels(&storage_class.pvc_selector)))
+                .map_err(|e| self.to_engine_error(&storage_class.pvc_selector, &storage_class.storage_class, e))?;
+
+            for pvc in pvcs {
+                if pvc.spec.as_ref().and_then(|spec| spec.storage_class_name.as_deref())
+                    != Some(storage_class.storage_class.as_str())
+                {
+                    pvcs_to_migrate.push((pvc, storage_class.storage_class.as_str()));
+                }
+            }
+        }
+        if pvcs_to_migrate.is_empty() {
+            return Ok(());
+        }
+
+        logger.info("⏸️ Stopping the service to move its volumes to their new storage class".to_string());
+        let pause_service =
+            PauseServiceAction::new(self.selector.clone(), true, self.timeout, self.event_details.clone());
+        pause_service.on_pause(target)?;
+
+        for (pvc, storage_class) in pvcs_to_migrate {
+            let pvc_name = pvc.metadata.name.clone().unwrap_or_default();
+            logger.info(format!("🚚 Moving volume `{pvc_name}` to storage class `{storage_class}`"));
+            block_on(migrate_pvc(&target.kube, namespace, &pvc, storage_class, self.timeout)).map_err(|e| {
+                Box::new(EngineError::new_k8s_cannot_migrate_volume_storage_class(
+                    self.event_details.clone(),
+                    &pvc_name,
+                    storage_class,
+                    CommandError::new_from_safe_message(e),
+                ))
+            })?;
+        }
+
+        // volume claim templates are immutable, the statefulset is recreated by the deployment
+        block_on(kube_delete_all_from_selector::<StatefulSet>(
+            &target.kube,
+            &self.selector,
+            namespace,
+            KubeDeleteMode::Normal,
+        ))
+        .map_err(|e| {
+            let storage_classes = self
+                .storage_classes
+                .iter()
+                .map(|storage_class| storage_class.storage_class.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            self.to_engine_error(&self.selector, &storage_classes, e)
+        })?;
+
+        Ok(())
+    }
+
+    fn to_engine_error(&self, pvc_name: &str, storage_class: &str, e: kube::Error) -> Box<EngineError> {
+        Box::new(EngineError::new_k8s_cannot_migrate_volume_storage_class(
+            self.event_details.clone(),
+            pvc_name,
+            storage_class,
+            CommandError::new_from_safe_message(e.to_string()),
+        ))
+    }
+}
+
+/// The claim keeps its name, so the original claim and volume have to be deleted first.
+/// The disk is retained meanwhile, its id is given back if the new volume can't be created.
+async fn migrate_pvc(
+    kube: &kube::Client,
+    namespace: &str,
+    pvc: &PersistentVolumeClaim,
+    storage_class: &str,
+    timeout: Duration,
+) -> Result<(), String> {
+    let pvcs: Api<PersistentVolumeClaim> = Api::namespaced(kube.clone(), namespace);
+    let pvs: Api<PersistentVolume> = Api::all(kube.clone());
+    let name = pvc.metadata.name.clone().unwrap_or_default();
+    let uid = pvc.metadata.uid.clone().unwrap_or_default();
+
+    let provisioner = Api::<StorageClass>::all(kube.clone())
+        .get(storage_class)
+        .await
+        .map_err(|e| format!("Cannot get storage class `{storage_class}`: {e}"))?
+        .provisioner;
+
+    // nothing is deleted if the volume can't be moved
+    let volume = match pvc.spec.as_ref().and_then(|spec| spec.volume_name.as_deref()) {
+        Some(volume_name) => {
+            let pv = pvs
+                .get(volume_name)
+                .await
+                .map_err(|e| format!("Cannot get volume `{volume_name}`: {e}"))?;
+            Some((pv.clone(), migrated_pv(&pv, storage_class, &provisioner)?))
+        }
+        None => None,
+    };
+    let migrated_claim = migrated_pvc(
+        pvc,
+        storage_class,
+        volume
+            .as_ref()
+            .and_then(|(_, migrated)| migrated.metadata.name.as_deref()),
+    );
+
+    if let Some((pv, _)) = &volume {
+        set_reclaim_policy(&pvs, pv.metadata.name.as_deref().unwrap_or_default(), "Retain").await?;
+    }
+
+    // the claim is only removed once no pod uses it anymore
+    pvcs.delete(&name, &DeleteParams::default())
+        .await
+        .map_err(|e| format!("Cannot delete volume claim `{name}`: {e}"))?;
+    let is_deleted =
+        tokio::time::timeout(timeout, await_condition(pvcs.clone(), &name, conditions::is_deleted(&uid))).await;
+    if !matches!(is_deleted, Ok(Ok(_))) {
+        return Err(format!(
+            "Volume claim `{name}` is still used after {} seconds, its volume is retained",
+            timeout.as_secs()
+        ));
+    }
+
+    if let Some((pv, migrated)) = &volume {
+        let volume_name = pv.metadata.name.clone().unwrap_or_default();
+        let volume_uid = pv.metadata.uid.clone().unwrap_or_default();
+        let disk_retained =
+            |error: String| format!("{error}, disk `{}` is retained", volume_id(pv).unwrap_or_default());
+
+        pvs.delete(&volume_name, &DeleteParams::default())
+            .await
+            .map_err(|e| disk_retained(format!("Cannot delete volume `{volume_name}`: {e}")))?;
+        let is_deleted = tokio::time::timeout(
+            timeout,
+            await_condition(pvs.clone(), &volume_name, conditions::is_deleted(&volume_uid)),
+        )
+        .await;
+        if !matches!(is_deleted, Ok(Ok(_))) {
+            return Err(disk_retained(format!(
+                "Volume `{volume_name}` is still attached after {} seconds",
+                timeout.as_secs()
+            )));
+        }
+
+        pvs.create(&PostParams::default(), migrated)
+            .await
+            .map_err(|e| disk_retained(format!("Cannot create volume `{volume_name}`: {e}")))?;
+    }
+
+    pvcs.create(&PostParams::default(), &migrated_claim)
+        .await
+        .map_err(|e| format!("Cannot create volume claim `{name}`: {e}"))?;
+
+    Ok(())
+}
+
+/// Id of the EBS disk of a volume, provisioned by either the in-tree aws-ebs provisioner or the EBS CSI driver
+fn volume_id(pv: &PersistentVolume) -> Option<String> {
+    let spec = pv.spec.as_ref()?;
+    match (&spec.aws_elastic_block_store, &spec.csi) {
+        // in-tree volume ids can be prefixed by their zone, i.e: aws://eu-west-3a/vol-0123456789
+        (Some(ebs), _) => ebs.volume_id.rsplit('/').next().map(|id| id.to_string()),
+        (None, Some(csi)) if csi.driver == EBS_CSI_PROVISIONER => Some(csi.volume_handle.clone()),
+        _ => None,
+    }
+}
+
+/// Volume bound to the same EBS disk as `pv`, provisioned by the provisioner of the storage class.
+/// It keeps the reclaim policy and node affinity of `pv`.
+fn migrated_pv(pv: &PersistentVolume, storage_class: &str, provisioner: &str) -> Result<PersistentVolume, String> {
+    let name = pv.metadata.name.clone().unwrap_or_default();
+    let spec = pv.spec.clone().unwrap_or_default();
+    let volume_id = volume_id(pv).ok_or_else(|| format!("Volume `{name}` is not an EBS volume"))?;
+    let fs_type = spec
+        .aws_elastic_block_store
+        .as_ref()
+        .and_then(|ebs| ebs.fs_type.clone())
+        .or_else(|| spec.csi.as_ref().and_then(|csi| csi.fs_type.clone()));
+
+    let (aws_elastic_block_store, csi) = match provisioner {
+        EBS_CSI_PROVISIONER => (
+            None,
+            Some(CSIPersistentVolumeSource {
+                driver: EBS_CSI_PROVISIONER.to_string(),
+                volume_handle: volume_id,
+                fs_type,
+                ..Default::default()
+            }),
+        ),
+        EBS_IN_TREE_PROVISIONER => (
+            Some(AWSElasticBlockStoreVolumeSource {
+                volume_id,
+                fs_type,
+                ..Default::default()
+            }),
+            None,
+        ),
+        _ => {
+            return Err(format!(
+                "Volume `{name}` can't be moved to storage class `{storage_class}` provisioned by {provisioner}"
+            ))
+        }
+    };
+
+    Ok(PersistentVolume {
+        metadata: ObjectMeta {
+            name: Some(name),
+            labels: pv.metadata.labels.clone(),
+            // the provisioner only deletes the volumes it has provisioned
+            annotations: Some(BTreeMap::from([(
+                PROVISIONED_BY_ANNOTATION.to_string(),
+                provisioner.to_string(),
+            )])),
+            ..Default::default()
+        },
+        spec: Some(PersistentVolumeSpec {
+            access_modes: spec.access_modes,
+            capacity: spec.capacity,
+            mount_options: spec.mount_options,
+            node_affinity: spec.node_affinity,
+            persistent_volume_reclaim_policy: spec.persistent_volume_reclaim_policy,
+            storage_class_name: Some(storage_class.to_string()),
+            volume_mode: spec.volume_mode,
+            aws_elastic_block_store,
+            csi,
+            ..Default::default()
+        }),
+        status: None,
+    })
+}
+
+/// Claim replacing `pvc` with the storage class, bound to the migrated volume if it had one
+fn migrated_pvc(pvc: &PersistentVolumeClaim, storage_class: &str, volume_name: Option<&str>) -> PersistentVolumeClaim {
+    let spec = pvc.spec.clone().unwrap_or_default();
+    let labels = pvc.metadata.labels.clone().map(|mut labels| {
+        for label in DISK_TYPE_LABELS {
+            if let Some(disk_type) = labels.get_mut(label) {
+                *disk_type = storage_class.to_string();
+            }
+        }
+        labels
+    });
+
+    PersistentVolumeClaim {
+        metadata: ObjectMeta {
+            name: pvc.metadata.name.clone(),
+            namespace: pvc.metadata.namespace.clone(),
+            labels,
+            ..Default::default()
+        },
+        spec: Some(PersistentVolumeClaimSpec {
+            access_modes: spec.access_modes,
+            resources: spec.resources,
+            volume_mode: spec.volume_mode,
+            storage_class_name: Some(storage_class.to_string()),
+            volume_name: volume_name.map(|name| name.to_string()),
+            ..Default::default()
+        }),
+        status: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deployment_action::migrate_volume_storage_class::{migrated_pv, migrated_pvc};
+    use k8s_openapi::api::core::v1::{
+        AWSElasticBlockStoreVolumeSource, CSIPersistentVolumeSource, NFSVolumeSource, PersistentVolume,
+        PersistentVolumeClaim, PersistentVolumeClaimSpec, PersistentVolumeSpec,
+    };
+    use kube::api::ObjectMeta;
+    use std::collections::BTreeMap;
+
+    fn pv(spec: PersistentVolumeSpec) -> PersistentVolume {
+        PersistentVolume {
+            metadata: ObjectMeta {
+                name: Some("pvc-1234".to_string()),
+                annotations: Some(BTreeMap::from([(
+                    "pv.kubernetes.io/provisioned-by".to_string(),
+                    "kubernetes.io/aws-ebs".to_string(),
+                )])),
+                ..Default::default()
+            },
+            spec: Some(PersistentVolumeSpec {
+                persistent_volume_reclaim_policy: Some("Delete".to_string()),
+                storage_class_name: Some("aws-ebs-gp2-0".to_string()),
+                ..spec
+            }),
+            status: None,
+        }
+    }
+
+    #[test]
+    fn test_migrated_pv() {
+        // setup:
+        let in_tree = pv(PersistentVolumeSpec {
+            aws_elastic_block_store: Some(AWSElasticBlockStoreVolumeSource {
+                volume_id: "aws://eu-west-3a/vol-0123456789".to_string(),
+                fs_type: Some("ext4".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let csi = pv(PersistentVolumeSpec {
+            csi: Some(CSIPersistentVolumeSource {
+                driver: "ebs.csi.aws.com".to_string(),
+                volume_handle: "vol-0123456789".to_string(),
+                fs_type: Some("ext4".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let nfs = pv(PersistentVolumeSpec {
+            nfs: Some(NFSVolumeSource::default()),
+            ..Default::default()
+        });
+
+        // execute:
+        let to_csi = migrated_pv(&in_tree, "aws-ebs-csi-gp2-0", "ebs.csi.aws.com").expect("volume should be moved");
+        let to_in_tree = migrated_pv(&csi, "aws-ebs-gp2-0", "kubernetes.io/aws-ebs").expect("volume should be moved");
+
+        // verify:
+        let to_csi_spec = to_csi.spec.expect("volume should have a spec");
+        assert_eq!(to_csi_spec.aws_elastic_block_store, None);
+        assert_eq!(
+            to_csi_spec.csi,
+            Some(CSIPersistentVolumeSource {
+                driver: "ebs.csi.aws.com".to_string(),
+                volume_handle: "vol-0123456789".to_string(),
+                fs_type: Some("ext4".to_string()),
+                ..Default::default()
+            })
+        );
+        assert_eq!(to_csi_spec.storage_class_name.as_deref(), Some("aws-ebs-csi-gp2-0"));
+        assert_eq!(to_csi_spec.persistent_volume_reclaim_policy.as_deref(), Some("Delete"));
+        assert_eq!(to_csi.metadata.name.as_deref(), Some("pvc-1234"));
+        assert_eq!(
+            to_csi
+                .metadata
+                .annotations
+                .and_then(|annotations| annotations.get("pv.kubernetes.io/provisioned-by").cloned())
+                .as_deref(),
+            Some("ebs.csi.aws.com")
+        );
+
+        let to_in_tree_spec = to_in_tree.spec.expect("volume should have a spec");
+        assert_eq!(to_in_tree_spec.csi, None);
+        assert_eq!(
+            to_in_tree_spec.aws_elastic_block_store,
+            Some(AWSElasticBlockStoreVolumeSource {
+                volume_id: "vol-0123456789".to_string(),
+                fs_type: Some("ext4".to_string()),
+                ..Default::default()
+            })
+        );
+
+        assert!(migrated_pv(&nfs, "aws-ebs-csi-gp2-0", "ebs.csi.aws.com").is_err());
+        assert!(migrated_pv(&in_tree, "scw-sbv-ssd-0", "csi.scaleway.com").is_err());
+    }
+
+    #[test]
+    fn test_migrated_pvc() {
+        // setup:
+        let pvc = PersistentVolumeClaim {
+            metadata: ObjectMeta {
+                name: Some("data-app-0".to_string()),
+                namespace: Some("z1234".to_string()),
+                labels: Some(BTreeMap::from([
+                    ("qovery.com/disk-id".to_string(), "disk".to_string()),
+                    ("qovery.com/disk-type".to_string(), "aws-ebs-gp2-0".to_string()),
+                ])),
+                uid: Some("uid".to_string()),
+                ..Default::default()
+            },
+            spec: Some(PersistentVolumeClaimSpec {
+                access_modes: Some(vec!["ReadWriteOnce".to_string()]),
+                storage_class_name: Some("aws-ebs-gp2-0".to_string()),
+                volume_name: Some("pvc-1234".to_string()),
+                ..Default::default()
+            }),
+            status: None,
+        };
+
+        // execute:
+        let migrated = migrated_pvc(&pvc, "aws-ebs-csi-gp2-0", Some("pvc-1234"));
+
+        // verify:
+        assert_eq!(migrated.metadata.name.as_deref(), Some("data-app-0"));
+        assert_eq!(migrated.metadata.uid, None);
+        assert_eq!(
+            migrated.metadata.labels,
+            Some(BTreeMap::from([
+                ("qovery.com/disk-id".to_string(), "disk".to_string()),
+                ("qovery.com/disk-type".to_string(), "aws-ebs-csi-gp2-0".to_string()),
+            ]))
+        );
+        let spec = migrated.spec.expect("claim should have a spec");
+        assert_eq!(spec.storage_class_name.as_deref(), Some("aws-ebs-csi-gp2-0"));
+        assert_eq!(spec.volume_name.as_deref(), Some("pvc-1234"));
+        assert_eq!(spec.access_modes, Some(vec!["ReadWriteOnce".to_string()]));
+    }
+}
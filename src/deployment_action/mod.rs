@@ -1,6 +1,8 @@
 use crate::cloud_provider::service::Action;
 use crate::cloud_provider::DeploymentTarget;
+use crate::deployment_action::plan::PlannedChange;
 use crate::errors::EngineError;
+use crate::events::EnvironmentStep;
 
 mod check_dns;
 mod deploy_application;
@@ -13,8 +15,13 @@ mod deploy_job;
 pub mod deploy_namespace;
 mod deploy_router;
 mod deploy_terraform;
+mod migrate_volume_storage_class;
 mod pause_service;
+pub mod plan;
+mod progressive_rollout;
 mod restart_service;
+mod restore_volume_snapshot;
+pub mod scan_image;
 #[cfg(test)]
 mod test_utils;
 mod utils;
@@ -24,12 +31,26 @@ pub trait DeploymentAction: Send + Sync {
     fn on_pause(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>>;
     fn on_delete(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>>;
     fn on_restart(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>>;
+    /// Restore the service from one of its backups, only container databases have some
+    fn on_restore(&self, target: &DeploymentTarget) -> Result<(), Box<EngineError>> {
+        Err(Box::new(EngineError::new_invalid_engine_payload(
+            target.environment.event_details_with_step(EnvironmentStep::Deploy),
+            "only container databases can be restored",
+            None,
+        )))
+    }
+    /// Compute what on_create would change, without applying anything.
+    /// Nothing is reported by default
+    fn on_plan(&self, _target: &DeploymentTarget) -> Result<Vec<PlannedChange>, Box<EngineError>> {
+        Ok(vec![])
+    }
     fn exec_action(&self, deployment_target: &DeploymentTarget, action: Action) -> Result<(), Box<EngineError>> {
         match action {
             Action::Create => self.on_create(deployment_target),
             Action::Delete => self.on_delete(deployment_target),
             Action::Pause => self.on_pause(deployment_target),
             Action::Restart => self.on_restart(deployment_target),
+            Action::Restore => self.on_restore(deployment_target),
         }
     }
 }
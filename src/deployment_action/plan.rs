@@ -0,0 +1,431 @@
+use crate::build_platform::Image;
+use crate::cloud_provider::helm::ChartInfo;
+use crate::cloud_provider::models::InvalidStatefulsetStorage;
+use crate::cloud_provider::service::{Action, Service};
+use crate::cloud_provider::DeploymentTarget;
+use crate::cmd::helm::HelmError;
+use crate::cmd::structs::HelmHistoryRow;
+use crate::container_registry::ContainerRegistry;
+use crate::deployment_action::utils::{image_already_exist, mirror_destination_image};
+use crate::models::registry_image_source::RegistryImageSource;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// What will be done to a resource if the deployment is applied
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceChange {
+    Create,
+    Update,
+    Delete,
+    Unchanged,
+}
+
+impl ResourceChange {
+    /// Compute the change of an helm release from its history and the diff against the live release
+    pub fn from_helm_release(history: Result<Vec<HelmHistoryRow>, HelmError>, diff: &str) -> ResourceChange {
+        match history {
+            Ok(history) if history.is_empty() => ResourceChange::Create,
+            Err(HelmError::ReleaseDoesNotExist(_)) => ResourceChange::Create,
+            _ if diff.trim().is_empty() => ResourceChange::Unchanged,
+            _ => ResourceChange::Update,
+        }
+    }
+}
+
+/// What will be done to a container image if the deployment is applied
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageChange {
+    /// Image does not exist in the registry yet and will be built
+    Build,
+    /// Image already exists in the registry and will be reused as is
+    Reuse,
+    /// Image comes from an external registry and will be mirrored into the cluster registry
+    Mirror,
+}
+
+/// A single change a deployment would make on a service
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PlannedChange {
+    /// Kubernetes manifests rendered by the service chart, diffed against the live release
+    HelmRelease {
+        release_name: String,
+        change: ResourceChange,
+        diff: String,
+    },
+    /// Container image used by the service
+    Image { image_name: String, change: ImageChange },
+    /// Ingress exposing the domains of a router
+    Ingress {
+        name: String,
+        domains: Vec<String>,
+        change: ResourceChange,
+    },
+    /// Persistent volume claim of the service that would be resized
+    StorageResize {
+        pvc_name: String,
+        requested_size_in_gib: u32,
+    },
+    /// Cloud provider resources managed by terraform (i.e: managed databases)
+    CloudResources { plan: String },
+}
+
+impl PlannedChange {
+    pub fn is_noop(&self) -> bool {
+        match self {
+            PlannedChange::HelmRelease { change, .. } | PlannedChange::Ingress { change, .. } => {
+                *change == ResourceChange::Unchanged
+            }
+            PlannedChange::Image { change, .. } => *change == ImageChange::Reuse,
+            PlannedChange::StorageResize { .. } => false,
+            PlannedChange::CloudResources { plan } => plan.contains("No changes."),
+        }
+    }
+
+    fn summary(&self) -> String {
+        match self {
+            PlannedChange::HelmRelease {
+                release_name, change, ..
+            } => format!("helm release {release_name}: {}", change.as_str()),
+            PlannedChange::Image { image_name, change } => match change {
+                ImageChange::Build => format!("image {image_name}: build"),
+                ImageChange::Reuse => format!("image {image_name}: reuse"),
+                ImageChange::Mirror => format!("image {image_name}: mirror"),
+            },
+            PlannedChange::Ingress { name, domains, change } => {
+                format!("ingress {name} ({}): {}", domains.join(", "), change.as_str())
+            }
+            PlannedChange::StorageResize {
+                pvc_name,
+                requested_size_in_gib,
+            } => format!("volume {pvc_name}: resize to {requested_size_in_gib}Gi"),
+            PlannedChange::CloudResources { plan } => {
+                // terraform gives a summary line at the end of the plan
+                let summary = plan
+                    .lines()
+                    .find(|line| line.starts_with("Plan:") || line.starts_with("No changes."))
+                    .unwrap_or("see plan details");
+                format!("cloud resources: {}", summary.trim())
+            }
+        }
+    }
+}
+
+impl ResourceChange {
+    fn as_str(&self) -> &str {
+        match self {
+            ResourceChange::Create => "create",
+            ResourceChange::Update => "update",
+            ResourceChange::Delete => "delete",
+            ResourceChange::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// Action requested on a service, as exposed in the plan
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlannedAction {
+    Deploy,
+    Pause,
+    Delete,
+    Restart,
+    Restore,
+}
+
+impl From<Action> for PlannedAction {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Create => PlannedAction::Deploy,
+            Action::Pause => PlannedAction::Pause,
+            Action::Delete => PlannedAction::Delete,
+            Action::Restart => PlannedAction::Restart,
+            Action::Restore => PlannedAction::Restore,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ServicePlan {
+    pub service_id: Uuid,
+    pub service_name: String,
+    pub service_type: String,
+    pub action: PlannedAction,
+    pub changes: Vec<PlannedChange>,
+}
+
+impl ServicePlan {
+    pub fn new(service: &dyn Service, changes: Vec<PlannedChange>) -> ServicePlan {
+        ServicePlan {
+            service_id: *service.long_id(),
+            service_name: service.name().to_string(),
+            service_type: service.service_type().name(),
+            action: PlannedAction::from(*service.action()),
+            changes,
+        }
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.action != PlannedAction::Deploy || self.changes.iter().any(|change| !change.is_noop())
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = match self.action {
+            PlannedAction::Deploy if !self.has_changes() => {
+                format!("{} {}: no changes", self.service_type, self.service_name)
+            }
+            PlannedAction::Deploy => format!("{} {}: would be deployed", self.service_type, self.service_name),
+            PlannedAction::Pause => format!("{} {}: would be paused", self.service_type, self.service_name),
+            PlannedAction::Delete => format!("{} {}: would be deleted", self.service_type, self.service_name),
+            PlannedAction::Restart => format!("{} {}: would be restarted", self.service_type, self.service_name),
+            PlannedAction::Restore => {
+                format!("{} {}: would be restored from a backup", self.service_type, self.service_name)
+            }
+        };
+
+        for change in self.changes.iter().filter(|change| !change.is_noop()) {
+            summary.push_str(&format!("\n  - {}", change.summary()));
+        }
+
+        summary
+    }
+}
+
+/// Report of everything an environment deployment would change, computed without applying anything
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct EnvironmentPlan {
+    pub environment_id: Uuid,
+    pub namespace: String,
+    pub services: Vec<ServicePlan>,
+}
+
+impl EnvironmentPlan {
+    pub fn has_changes(&self) -> bool {
+        self.services.iter().any(|service| service.has_changes())
+    }
+
+    pub fn summary(&self) -> String {
+        if !self.has_changes() {
+            return "No changes, the environment is up to date".to_string();
+        }
+
+        self.services
+            .iter()
+            .filter(|service| service.has_changes())
+            .map(|service| service.summary())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+/// Compute the change of a helm release by diffing the chart already rendered in the workspace
+pub fn plan_helm_release(target: &DeploymentTarget, chart: &ChartInfo) -> Result<PlannedChange, HelmError> {
+    let diff = target.helm.upgrade_diff_with_output(chart, &[])?;
+
+    Ok(PlannedChange::HelmRelease {
+        release_name: chart.name.clone(),
+        change: ResourceChange::from_helm_release(target.helm.history(chart, &[]), &diff),
+        diff,
+    })
+}
+
+/// Check if the image of the service would have to be built, or if it can be reused from the registry
+pub fn plan_image(container_registry: &dyn ContainerRegistry, image: &Image) -> PlannedChange {
+    PlannedChange::Image {
+        image_name: image.full_image_name_with_tag(),
+        change: if container_registry.image_exists(image) {
+            ImageChange::Reuse
+        } else {
+            ImageChange::Build
+        },
+    }
+}
+
+/// Image of a service deployed from a registry, which is mirrored into the cluster registry before being used
+pub fn plan_mirrored_image(
+    service_id: &Uuid,
+    source: &RegistryImageSource,
+    target: &DeploymentTarget,
+) -> PlannedChange {
+    let dest_image = mirror_destination_image(service_id, source.tag_for_mirror(service_id), target);
+
+    PlannedChange::Image {
+        image_name: format!("{}:{}", source.image, source.tag),
+        change: if image_already_exist(&dest_image, target) {
+            ImageChange::Reuse
+        } else {
+            ImageChange::Mirror
+        },
+    }
+}
+
+/// Volumes of the service statefulset that would be resized
+pub fn plan_storage_resize(invalid_storage: Option<InvalidStatefulsetStorage>) -> Vec<PlannedChange> {
+    invalid_storage
+        .map(|storage| storage.invalid_pvcs)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|pvc| PlannedChange::StorageResize {
+            pvc_name: pvc.pvc_name,
+            requested_size_in_gib: pvc.required_disk_size_in_gib,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cmd::helm::HelmError;
+    use crate::cmd::structs::HelmHistoryRow;
+    use crate::deployment_action::plan::{
+        EnvironmentPlan, ImageChange, PlannedAction, PlannedChange, ResourceChange, ServicePlan,
+    };
+    use uuid::Uuid;
+
+    #[test]
+    fn test_resource_change_from_helm_release() {
+        // setup:
+        struct TestCase {
+            history: Result<Vec<HelmHistoryRow>, HelmError>,
+            diff: &'static str,
+            expected: ResourceChange,
+        }
+
+        let row = HelmHistoryRow {
+            revision: 1,
+            updated: "2023-09-05T10:00:00.000000000+00:00".to_string(),
+            status: "deployed".to_string(),
+            chart: "app".to_string(),
+            app_version: "1".to_string(),
+        };
+        let test_cases = vec![
+            TestCase {
+                history: Ok(vec![]),
+                diff: "+ kind: Deployment",
+                expected: ResourceChange::Create,
+            },
+            TestCase {
+                history: Err(HelmError::ReleaseDoesNotExist("app".to_string())),
+                diff: "+ kind: Deployment",
+                expected: ResourceChange::Create,
+            },
+            TestCase {
+                history: Ok(vec![row.clone()]),
+                diff: "",
+                expected: ResourceChange::Unchanged,
+            },
+            TestCase {
+                history: Ok(vec![row]),
+                diff: "-  replicas: 1\n+  replicas: 2",
+                expected: ResourceChange::Update,
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let change = ResourceChange::from_helm_release(tc.history, tc.diff);
+
+            // verify:
+            assert_eq!(tc.expected, change);
+        }
+    }
+
+    #[test]
+    fn test_environment_plan_serialization() {
+        // setup:
+        let service_id = Uuid::new_v4();
+        let plan = EnvironmentPlan {
+            environment_id: Uuid::new_v4(),
+            namespace: "z1234-z5678".to_string(),
+            services: vec![ServicePlan {
+                service_id,
+                service_name: "my-app".to_string(),
+                service_type: "Application".to_string(),
+                action: PlannedAction::Deploy,
+                changes: vec![
+                    PlannedChange::Image {
+                        image_name: "registry/app:1234".to_string(),
+                        change: ImageChange::Build,
+                    },
+                    PlannedChange::StorageResize {
+                        pvc_name: "data-app-0".to_string(),
+                        requested_size_in_gib: 20,
+                    },
+                ],
+            }],
+        };
+
+        // execute:
+        let json: serde_json::Value = serde_json::from_str(&plan.to_json().expect("Cannot serialize plan")).unwrap();
+
+        // verify:
+        assert_eq!(json["services"][0]["service_id"], service_id.to_string());
+        assert_eq!(json["services"][0]["action"], "deploy");
+        assert_eq!(json["services"][0]["changes"][0]["kind"], "image");
+        assert_eq!(json["services"][0]["changes"][0]["change"], "build");
+        assert_eq!(json["services"][0]["changes"][1]["kind"], "storage_resize");
+        assert_eq!(json["services"][0]["changes"][1]["requested_size_in_gib"], 20);
+    }
+
+    #[test]
+    fn test_environment_plan_summary() {
+        // setup:
+        let service = |name: &str, action: PlannedAction, changes: Vec<PlannedChange>| ServicePlan {
+            service_id: Uuid::new_v4(),
+            service_name: name.to_string(),
+            service_type: "Application".to_string(),
+            action,
+            changes,
+        };
+        let unchanged_release = PlannedChange::HelmRelease {
+            release_name: "application-a".to_string(),
+            change: ResourceChange::Unchanged,
+            diff: "".to_string(),
+        };
+        let mut plan = EnvironmentPlan {
+            environment_id: Uuid::new_v4(),
+            namespace: "z1234-z5678".to_string(),
+            services: vec![service("a", PlannedAction::Deploy, vec![unchanged_release.clone()])],
+        };
+
+        // execute & verify:
+        assert!(!plan.has_changes());
+        assert_eq!(plan.summary(), "No changes, the environment is up to date");
+
+        plan.services.push(service(
+            "b",
+            PlannedAction::Deploy,
+            vec![
+                unchanged_release,
+                PlannedChange::Image {
+                    image_name: "registry/b:1".to_string(),
+                    change: ImageChange::Build,
+                },
+            ],
+        ));
+        plan.services.push(service("c", PlannedAction::Delete, vec![]));
+
+        assert!(plan.has_changes());
+        assert_eq!(
+            plan.summary(),
+            "Application b: would be deployed\n  - image registry/b:1: build\nApplication c: would be deleted"
+        );
+
+        plan.services = vec![service(
+            "d",
+            PlannedAction::Deploy,
+            vec![PlannedChange::Image {
+                image_name: "debian:12".to_string(),
+                change: ImageChange::Mirror,
+            }],
+        )];
+
+        assert!(plan.has_changes());
+        assert_eq!(plan.summary(), "Application d: would be deployed\n  - image debian:12: mirror");
+    }
+}
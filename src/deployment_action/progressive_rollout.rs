@@ -0,0 +1,774 @@
+use crate::cloud_provider::helm::HelmChartNamespaces;
+use crate::cloud_provider::DeploymentTarget;
+use crate::deployment_action::deploy_helm::HelmDeployment;
+use crate::deployment_action::DeploymentAction;
+use crate::deployment_report::logger::EnvProgressLogger;
+use crate::errors::{CommandError, EngineError};
+use crate::events::EventDetails;
+use crate::io_models::container::ContainerAdvancedSettings;
+use crate::io_models::context::Features;
+use crate::io_models::UpdateStrategy;
+use crate::kubers_utils::{kube_get_resource_by_name, kube_get_resources_by_selector, kube_merge_patch_resource};
+use crate::models::deployment_strategy::{
+    canary_replicas, DeploymentTeraContext, DeploymentTrack, TrackVersion, DEPLOYMENT_TRACK_LABEL,
+};
+use crate::models::router::{HTTP_ROUTE_API_GROUP, HTTP_ROUTE_API_VERSION, HTTP_ROUTE_KIND};
+use crate::runtime::block_on;
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::{Pod, Service as K8sService};
+use k8s_openapi::api::networking::v1::Ingress;
+use kube::api::{ApiResource, DynamicObject, GroupVersionKind, ListParams, Patch, PatchParams};
+use kube::Api;
+use std::thread;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+const CANARY_WEIGHT_ANNOTATION: &str = "nginx.ingress.kubernetes.io/canary-weight";
+const CANARY_ANALYSIS_INTERVAL: Duration = Duration::from_secs(10);
+/// Window of the nginx requests the canary error rate is computed on
+const CANARY_ERROR_RATE_WINDOW: &str = "1m";
+/// Below this number of requests in the window, the error rate is not significant enough to abort the canary
+const CANARY_ERROR_RATE_MIN_REQUESTS: f64 = 10.0;
+
+/// Rollout of an application or a container following its update strategy.
+/// Rolling update and recreate are handled by Kubernetes itself, canary and blue/green are driven from here
+/// by rendering the q-container chart with the tracks of the service needed at each step.
+pub(super) struct ProgressiveRollout<'a> {
+    service_long_id: &'a Uuid,
+    kube_name: &'a str,
+    min_instances: u32,
+    is_stateful: bool,
+    advanced_settings: ContainerAdvancedSettings,
+    version: TrackVersion,
+    event_details: EventDetails,
+}
+
+impl<'a> ProgressiveRollout<'a> {
+    pub fn new(
+        service_long_id: &'a Uuid,
+        kube_name: &'a str,
+        min_instances: u32,
+        is_stateful: bool,
+        advanced_settings: ContainerAdvancedSettings,
+        version: TrackVersion,
+        event_details: EventDetails,
+    ) -> Self {
+        ProgressiveRollout {
+            service_long_id,
+            kube_name,
+            min_instances,
+            is_stateful,
+            advanced_settings,
+            version,
+            event_details,
+        }
+    }
+
+    pub fn deploy(
+        &self,
+        target: &DeploymentTarget,
+        logger: &EnvProgressLogger,
+        helm_deployment: &dyn Fn(&DeploymentTeraContext) -> Result<HelmDeployment, Box<EngineError>>,
+    ) -> Result<(), Box<EngineError>> {
+        let strategy = self.advanced_settings.deployment_update_strategy_type;
+
+        // Statefulsets have their own update strategy, canary and blue/green only apply to deployments
+        match strategy {
+            UpdateStrategy::Canary if !self.is_stateful => self.deploy_canary(target, logger, helm_deployment),
+            UpdateStrategy::BlueGreen if !self.is_stateful => self.deploy_blue_green(target, logger, helm_deployment),
+            _ => helm_deployment(&DeploymentTeraContext::new(
+                strategy,
+                self.kube_name,
+                self.version.clone(),
+                self.min_instances,
+            ))?
+            .on_create(target),
+        }
+    }
+
+    fn deploy_canary(
+        &self,
+        target: &DeploymentTarget,
+        logger: &EnvProgressLogger,
+        helm_deployment: &dyn Fn(&DeploymentTeraContext) -> Result<HelmDeployment, Box<EngineError>>,
+    ) -> Result<(), Box<EngineError>> {
+        let stable = match self.live_version(target, DeploymentTrack::Stable)? {
+            Some(stable) if stable.image_full != self.version.image_full => stable,
+            // Nothing to compare the new version with, it is deployed directly
+            _ => {
+                return helm_deployment(&DeploymentTeraContext::canary(self.kube_name, self.version.clone(), None))?
+                    .on_create(target)
+            }
+        };
+
+        let traffic_percent = self.advanced_settings.deployment_update_strategy_canary_traffic_percent;
+        let replicas = canary_replicas(self.min_instances, traffic_percent);
+        logger.info(format!(
+            "🐤 Deploying canary version `{}` next to stable version `{}`",
+            self.version.version, stable.version
+        ));
+
+        // Secrets and mounted files are shared between both tracks,
+        // so stable pods are restarted with the new configuration if it changed.
+        let canary =
+            DeploymentTeraContext::canary(self.kube_name, stable.clone(), Some((self.version.clone(), replicas)));
+        let analysis = helm_deployment(&canary)
+            .and_then(|helm| helm.on_create(target))
+            .and_then(|_| self.set_canary_weight(target, traffic_percent))
+            .and_then(|has_canary_ingress| {
+                match has_canary_ingress {
+                    true => logger.info(format!("🔀 Sending {traffic_percent}% of the traffic to the canary version")),
+                    false => logger.warning(
+                        "⚠️ No router canary ingress found, canary version will not receive any traffic".to_string(),
+                    ),
+                }
+                self.analyse_canary(target, logger)
+            });
+
+        if let Err(err) = analysis {
+            logger.warning(format!(
+                "⚠️ Aborting canary version, stable version `{}` is kept",
+                stable.version
+            ));
+            self.set_canary_weight(target, 0)?;
+            helm_deployment(&DeploymentTeraContext::canary(self.kube_name, stable, None))?.on_create(target)?;
+            return Err(err);
+        }
+
+        logger.info(format!("✅ Canary version `{}` is healthy, promoting it", self.version.version));
+        self.set_canary_weight(target, 0)?;
+        helm_deployment(&DeploymentTeraContext::canary(self.kube_name, self.version.clone(), None))?.on_create(target)
+    }
+
+    fn deploy_blue_green(
+        &self,
+        target: &DeploymentTarget,
+        logger: &EnvProgressLogger,
+        helm_deployment: &dyn Fn(&DeploymentTeraContext) -> Result<HelmDeployment, Box<EngineError>>,
+    ) -> Result<(), Box<EngineError>> {
+        let live_color = self.live_color(target)?;
+        let live = match self.live_version(target, live_color)? {
+            Some(live) => live,
+            // Nothing is serving traffic yet, the new version goes live directly
+            None => {
+                return helm_deployment(&DeploymentTeraContext::blue_green(
+                    self.kube_name,
+                    live_color,
+                    self.version.clone(),
+                    None,
+                    self.min_instances,
+                ))?
+                .on_create(target)
+            }
+        };
+
+        let next_color = live_color.next_track();
+        logger.info(format!(
+            "🚦 Deploying version `{}` on {} deployment, {} deployment keeps serving version `{}`",
+            self.version.version,
+            next_color.to_label(),
+            live_color.to_label(),
+            live.version
+        ));
+
+        let rollout = DeploymentTeraContext::blue_green(
+            self.kube_name,
+            live_color,
+            live.clone(),
+            Some(self.version.clone()),
+            self.min_instances,
+        );
+        if let Err(err) = helm_deployment(&rollout).and_then(|helm| helm.on_create(target)) {
+            logger.warning(format!(
+                "⚠️ New version is not ready, {} deployment is kept live",
+                live_color.to_label()
+            ));
+            helm_deployment(&DeploymentTeraContext::blue_green(
+                self.kube_name,
+                live_color,
+                live,
+                None,
+                self.min_instances,
+            ))?
+            .on_create(target)?;
+            return Err(err);
+        }
+
+        logger.info(format!(
+            "🔀 Switching traffic from {} to {} deployment",
+            live_color.to_label(),
+            next_color.to_label()
+        ));
+        self.switch_service_track(target, next_color)?;
+
+        // Previous color is not rendered anymore, so it is removed
+        helm_deployment(&DeploymentTeraContext::blue_green(
+            self.kube_name,
+            next_color,
+            self.version.clone(),
+            None,
+            self.min_instances,
+        ))?
+        .on_create(target)
+    }
+
+    /// Version running on the given track, if it is ready to serve traffic
+    fn live_version(
+        &self,
+        target: &DeploymentTarget,
+        track: DeploymentTrack,
+    ) -> Result<Option<TrackVersion>, Box<EngineError>> {
+        let deployment = block_on(kube_get_resource_by_name::<Deployment>(
+            &target.kube,
+            target.environment.namespace(),
+            &track.deployment_name(self.kube_name),
+        ))
+        .map_err(|e| EngineError::new_k8s_get_deployment_error(self.event_details.clone(), e))?;
+
+        let deployment = match deployment {
+            Some(deployment) => deployment,
+            None => return Ok(None),
+        };
+        let is_ready = deployment
+            .status
+            .as_ref()
+            .and_then(|status| status.ready_replicas)
+            .unwrap_or(0)
+            > 0;
+        let template = match (is_ready, deployment.spec) {
+            (true, Some(spec)) => spec.template,
+            _ => return Ok(None),
+        };
+        let image_full = template
+            .spec
+            .and_then(|spec| spec.containers.into_iter().next())
+            .and_then(|container| container.image);
+
+        Ok(image_full.map(|image_full| TrackVersion {
+            version: template
+                .metadata
+                .and_then(|metadata| metadata.annotations)
+                .and_then(|annotations| annotations.get("qovery.com/service-version").cloned())
+                .unwrap_or_else(|| image_full.clone()),
+            image_full,
+        }))
+    }
+
+    fn live_color(&self, target: &DeploymentTarget) -> Result<DeploymentTrack, Box<EngineError>> {
+        let selected_color = self
+            .get_service(target)?
+            .and_then(|service| service.spec)
+            .and_then(|spec| spec.selector)
+            .and_then(|selector| selector.get(DEPLOYMENT_TRACK_LABEL).cloned())
+            .and_then(|track| DeploymentTrack::from_label(&track));
+
+        match selected_color {
+            Some(color) => Ok(color),
+            // Service without any port: green deployment only exists when it is the live one
+            None => match self.live_version(target, DeploymentTrack::Green)? {
+                Some(_) => Ok(DeploymentTrack::Green),
+                None => Ok(DeploymentTrack::Blue),
+            },
+        }
+    }
+
+    fn get_service(&self, target: &DeploymentTarget) -> Result<Option<K8sService>, Box<EngineError>> {
+        block_on(kube_get_resource_by_name::<K8sService>(
+            &target.kube,
+            target.environment.namespace(),
+            self.kube_name,
+        ))
+        .map_err(|e| {
+            Box::new(EngineError::new_k8s_cannot_get_services(
+                self.event_details.clone(),
+                e,
+                &self.service_long_id.to_string(),
+            ))
+        })
+    }
+
+    /// Single patch of the service selector, so traffic moves from one color to the other at once
+    fn switch_service_track(&self, target: &DeploymentTarget, track: DeploymentTrack) -> Result<(), Box<EngineError>> {
+        if self.get_service(target)?.is_none() {
+            return Ok(());
+        }
+
+        let patch = serde_json::json!({
+            "spec": {
+                "selector": {
+                    DEPLOYMENT_TRACK_LABEL: track.to_label(),
+                }
+            }
+        });
+        block_on(kube_merge_patch_resource::<K8sService>(
+            &target.kube,
+            target.environment.namespace(),
+            self.kube_name,
+            &patch,
+        ))
+        .map_err(|e| {
+            Box::new(EngineError::new_k8s_cannot_switch_service_traffic(
+                self.event_details.clone(),
+                self.kube_name,
+                e,
+            ))
+        })
+    }
+
+    /// Set the weight of the router canary ingress, returns false if the router has no canary ingress
+    fn set_canary_weight(&self, target: &DeploymentTarget, weight: u32) -> Result<bool, Box<EngineError>> {
+        if target.kubernetes.advanced_settings().gateway_api_enabled {
+            return self.set_gateway_canary_weight(target, weight);
+        }
+
+        let selector = format!(
+            "qovery.com/associated-service-id={},{}={}",
+            self.service_long_id,
+            DEPLOYMENT_TRACK_LABEL,
+            DeploymentTrack::Canary.to_label()
+        );
+        let to_engine_error =
+            |e| EngineError::new_k8s_cannot_switch_service_traffic(self.event_details.clone(), self.kube_name, e);
+        let ingresses = block_on(kube_get_resources_by_selector::<Ingress>(
+            &target.kube,
+            target.environment.namespace(),
+            &selector,
+        ))
+        .map_err(to_engine_error)?;
+
+        let patch = serde_json::json!({
+            "metadata": {
+                "annotations": {
+                    CANARY_WEIGHT_ANNOTATION: weight.to_string(),
+                }
+            }
+        });
+        for ingress_name in ingresses
+            .items
+            .iter()
+            .filter_map(|ingress| ingress.metadata.name.as_ref())
+        {
+            block_on(kube_merge_patch_resource::<Ingress>(
+                &target.kube,
+                target.environment.namespace(),
+                ingress_name,
+                &patch,
+            ))
+            .map_err(to_engine_error)?;
+        }
+
+        Ok(!ingresses.items.is_empty())
+    }
+
+    /// Set the weight of the canary backends of the router HTTP routes, returns false if the router has none
+    fn set_gateway_canary_weight(&self, target: &DeploymentTarget, weight: u32) -> Result<bool, Box<EngineError>> {
+        let to_engine_error = |e: kube::Error| {
+            EngineError::new_k8s_cannot_switch_service_traffic(
+                self.event_details.clone(),
+                self.kube_name,
+                CommandError::new_from_safe_message(e.to_string()),
+            )
+        };
+        let api_resource = ApiResource::from_gvk(&GroupVersionKind::gvk(
+            HTTP_ROUTE_API_GROUP,
+            HTTP_ROUTE_API_VERSION,
+            HTTP_ROUTE_KIND,
+        ));
+        let routes: Api<DynamicObject> =
+            Api::namespaced_with(target.kube.clone(), target.environment.namespace(), &api_resource);
+        let selector = format!("qovery.com/associated-service-id={}", self.service_long_id);
+        let route_list = block_on(routes.list(&ListParams::default().labels(&selector))).map_err(to_engine_error)?;
+
+        let mut has_canary_backend = false;
+        for route in route_list.items {
+            let Some(route_name) = route.metadata.name.as_ref() else {
+                continue;
+            };
+            // backend refs are a list, so the whole rules have to be patched
+            let mut rules = route.data["spec"]["rules"].clone();
+            if !set_canary_backend_weight(&mut rules, weight) {
+                continue;
+            }
+
+            has_canary_backend = true;
+            let patch = serde_json::json!({ "spec": { "rules": rules } });
+            block_on(routes.patch(route_name, &PatchParams::default(), &Patch::Merge(&patch)))
+                .map_err(to_engine_error)?;
+        }
+
+        Ok(has_canary_backend)
+    }
+
+    /// Watch canary pods during the analysis window, any pod not ready or restarting aborts the canary.
+    /// The canary is aborted as well if the share of its requests answered with a 5xx exceeds the allowed error rate.
+    fn analyse_canary(&self, target: &DeploymentTarget, logger: &EnvProgressLogger) -> Result<(), Box<EngineError>> {
+        let analysis_duration = Duration::from_secs(
+            self.advanced_settings
+                .deployment_update_strategy_canary_analysis_duration_seconds as u64,
+        );
+        let max_container_restarts = self
+            .advanced_settings
+            .deployment_update_strategy_canary_max_container_restarts;
+        let max_error_rate_percent = self
+            .advanced_settings
+            .deployment_update_strategy_canary_max_error_rate_percent;
+        // requests metrics are scraped from nginx by prometheus, which is only deployed with metrics history
+        let mut is_error_rate_checked = !target.kubernetes.advanced_settings().gateway_api_enabled
+            && target
+                .kubernetes
+                .context()
+                .is_feature_enabled(&Features::MetricsHistory);
+        if !is_error_rate_checked {
+            logger.warning(
+                "⚠️ Canary error rate can't be checked, it requires nginx ingress and metrics history".to_string(),
+            );
+        }
+        let selector = format!(
+            "qovery.com/service-id={},{}={}",
+            self.service_long_id,
+            DEPLOYMENT_TRACK_LABEL,
+            DeploymentTrack::Canary.to_label()
+        );
+        logger.info(format!(
+            "🔎 Watching canary version for {} seconds",
+            analysis_duration.as_secs()
+        ));
+
+        let deadline = Instant::now() + analysis_duration;
+        loop {
+            if (target.should_abort)() {
+                return Err(Box::new(EngineError::new_task_cancellation_requested(
+                    self.event_details.clone(),
+                )));
+            }
+
+            let pods = block_on(kube_get_resources_by_selector::<Pod>(
+                &target.kube,
+                target.environment.namespace(),
+                &selector,
+            ))
+            .map_err(|e| EngineError::new_k8s_cannot_get_pods(self.event_details.clone(), e))?;
+            if let Some(reason) = canary_unhealthy_reason(&pods.items, max_container_restarts) {
+                return Err(Box::new(EngineError::new_canary_analysis_failed(
+                    self.event_details.clone(),
+                    self.kube_name,
+                    &reason,
+                )));
+            }
+
+            if is_error_rate_checked {
+                match self.canary_requests(target) {
+                    Ok((errors, total)) => {
+                        if let Some(reason) = canary_error_rate_reason(errors, total, max_error_rate_percent) {
+                            return Err(Box::new(EngineError::new_canary_analysis_failed(
+                                self.event_details.clone(),
+                                self.kube_name,
+                                &reason,
+                            )));
+                        }
+                    }
+                    // the canary is not aborted on a monitoring failure, pods health is still checked
+                    Err(e) => {
+                        logger.warning(format!("⚠️ Cannot get canary error rate from prometheus: {e}"));
+                        is_error_rate_checked = false;
+                    }
+                }
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(());
+            }
+            thread::sleep(remaining.min(CANARY_ANALYSIS_INTERVAL));
+        }
+    }
+
+    /// Requests answered with a 5xx and all requests nginx sent to the canary service during the error rate window
+    fn canary_requests(&self, target: &DeploymentTarget) -> Result<(f64, f64), String> {
+        let requests = |status_filter: &str| {
+            format!(
+                "sum(increase(nginx_ingress_controller_requests{{exported_namespace=\"{}\",exported_service=\"{}-{}\"{}}}[{}]))",
+                target.environment.namespace(),
+                self.kube_name,
+                DeploymentTrack::Canary.to_label(),
+                status_filter,
+                CANARY_ERROR_RATE_WINDOW
+            )
+        };
+        let errors = query_prometheus(&target.kube, &requests(",status=~\"5..\""))?;
+        let total = query_prometheus(&target.kube, &requests(""))?;
+
+        Ok((errors, total))
+    }
+}
+
+/// Instant query of a single value, through the API server proxy so it works wherever the engine runs
+fn query_prometheus(kube: &kube::Client, query: &str) -> Result<f64, String> {
+    let uri = format!(
+        "/api/v1/namespaces/{}/services/prometheus-operated:9090/proxy/api/v1/query?query={}",
+        HelmChartNamespaces::Prometheus,
+        urlencoding::encode(query)
+    );
+    let request = k8s_openapi::http::Request::get(uri)
+        .body(vec![])
+        .map_err(|e| e.to_string())?;
+    let response = block_on(kube.request_text(request)).map_err(|e| e.to_string())?;
+
+    parse_prometheus_value(&response)
+}
+
+/// Value of a vector result, an empty result means no sample (i.e: no request) and is 0
+fn parse_prometheus_value(response: &str) -> Result<f64, String> {
+    let response: serde_json::Value =
+        serde_json::from_str(response).map_err(|e| format!("invalid prometheus response: {e}"))?;
+    if response["status"] != "success" {
+        return Err(format!("prometheus query failed: {}", response["error"]));
+    }
+
+    match response["data"]["result"][0]["value"][1].as_str() {
+        Some(value) => value
+            .parse::<f64>()
+            .map_err(|e| format!("invalid prometheus value `{value}`: {e}")),
+        None => Ok(0.0),
+    }
+}
+
+fn canary_error_rate_reason(errors: f64, total: f64, max_error_rate_percent: u32) -> Option<String> {
+    if total.is_nan() || errors.is_nan() || total < CANARY_ERROR_RATE_MIN_REQUESTS {
+        return None;
+    }
+
+    let error_rate_percent = errors / total * 100.0;
+    match error_rate_percent > max_error_rate_percent as f64 {
+        true => Some(format!(
+            "{error_rate_percent:.1}% of the requests failed with a 5xx, above the {max_error_rate_percent}% allowed"
+        )),
+        false => None,
+    }
+}
+
+/// Canary backends of the rules get the weight, the stable ones the rest of the traffic.
+/// Returns false if there is no canary backend in the rules.
+fn set_canary_backend_weight(rules: &mut serde_json::Value, weight: u32) -> bool {
+    let canary_suffix = format!("-{}", DeploymentTrack::Canary.to_label());
+    let mut has_canary_backend = false;
+    for backend in rules
+        .as_array_mut()
+        .into_iter()
+        .flatten()
+        .filter_map(|rule| rule.get_mut("backendRefs").and_then(|backends| backends.as_array_mut()))
+        .flatten()
+    {
+        let is_canary = backend["name"]
+            .as_str()
+            .map(|name| name.ends_with(&canary_suffix))
+            .unwrap_or(false);
+        has_canary_backend |= is_canary;
+        backend["weight"] = match is_canary {
+            true => serde_json::json!(weight.min(100)),
+            false => serde_json::json!(100 - weight.min(100)),
+        };
+    }
+
+    has_canary_backend
+}
+
+fn canary_unhealthy_reason(pods: &[Pod], max_container_restarts: u32) -> Option<String> {
+    if pods.is_empty() {
+        return Some("no canary pod is running".to_string());
+    }
+
+    for pod in pods {
+        let pod_name = pod.metadata.name.as_deref().unwrap_or_default();
+        let status = match &pod.status {
+            Some(status) => status,
+            None => return Some(format!("pod `{pod_name}` has no status")),
+        };
+
+        let restarts: i32 = status
+            .container_statuses
+            .iter()
+            .flatten()
+            .map(|container| container.restart_count)
+            .sum();
+        if restarts > max_container_restarts as i32 {
+            return Some(format!("pod `{pod_name}` containers restarted {restarts} times"));
+        }
+
+        let is_ready = status
+            .conditions
+            .iter()
+            .flatten()
+            .any(|condition| condition.type_ == "Ready" && condition.status == "True");
+        if !is_ready {
+            return Some(format!("pod `{pod_name}` is not ready"));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deployment_action::progressive_rollout::{
+        canary_error_rate_reason, canary_unhealthy_reason, parse_prometheus_value, set_canary_backend_weight,
+    };
+    use k8s_openapi::api::core::v1::{ContainerStatus, Pod, PodCondition, PodStatus};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+    fn pod(restart_count: i32, ready: bool) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some("app-canary-1".to_string()),
+                ..Default::default()
+            },
+            status: Some(PodStatus {
+                container_statuses: Some(vec![ContainerStatus {
+                    restart_count,
+                    ..Default::default()
+                }]),
+                conditions: Some(vec![PodCondition {
+                    type_: "Ready".to_string(),
+                    status: if ready { "True" } else { "False" }.to_string(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_canary_unhealthy_reason() {
+        // setup:
+        struct TestCase {
+            pods: Vec<Pod>,
+            max_container_restarts: u32,
+            expected: Option<&'static str>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                pods: vec![],
+                max_container_restarts: 0,
+                expected: Some("no canary pod is running"),
+            },
+            TestCase {
+                pods: vec![pod(0, true)],
+                max_container_restarts: 0,
+                expected: None,
+            },
+            TestCase {
+                pods: vec![pod(1, true)],
+                max_container_restarts: 0,
+                expected: Some("pod `app-canary-1` containers restarted 1 times"),
+            },
+            TestCase {
+                pods: vec![pod(1, true)],
+                max_container_restarts: 2,
+                expected: None,
+            },
+            TestCase {
+                pods: vec![pod(0, false)],
+                max_container_restarts: 0,
+                expected: Some("pod `app-canary-1` is not ready"),
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = canary_unhealthy_reason(&tc.pods, tc.max_container_restarts);
+
+            // verify:
+            assert_eq!(result.as_deref(), tc.expected);
+        }
+    }
+
+    #[test]
+    fn test_canary_error_rate_reason() {
+        // setup:
+        struct TestCase {
+            errors: f64,
+            total: f64,
+            max_error_rate_percent: u32,
+            expected: Option<&'static str>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                errors: 0.0,
+                total: 0.0,
+                max_error_rate_percent: 5,
+                expected: None,
+            },
+            TestCase {
+                errors: 3.0,
+                total: 4.0,
+                max_error_rate_percent: 5,
+                expected: None,
+            },
+            TestCase {
+                errors: 5.0,
+                total: 100.0,
+                max_error_rate_percent: 5,
+                expected: None,
+            },
+            TestCase {
+                errors: 6.0,
+                total: 100.0,
+                max_error_rate_percent: 5,
+                expected: Some("6.0% of the requests failed with a 5xx, above the 5% allowed"),
+            },
+            TestCase {
+                errors: f64::NAN,
+                total: 100.0,
+                max_error_rate_percent: 5,
+                expected: None,
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = canary_error_rate_reason(tc.errors, tc.total, tc.max_error_rate_percent);
+
+            // verify:
+            assert_eq!(result.as_deref(), tc.expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_prometheus_value() {
+        // setup:
+        let with_value = r#"{"status":"success","data":{"resultType":"vector","result":[{"metric":{},"value":[1697500800.123,"12.5"]}]}}"#;
+        let without_value = r#"{"status":"success","data":{"resultType":"vector","result":[]}}"#;
+        let failed = r#"{"status":"error","errorType":"bad_data","error":"parse error"}"#;
+
+        // execute & verify:
+        assert_eq!(parse_prometheus_value(with_value), Ok(12.5));
+        assert_eq!(parse_prometheus_value(without_value), Ok(0.0));
+        assert!(parse_prometheus_value(failed).is_err());
+        assert!(parse_prometheus_value("not json").is_err());
+    }
+
+    #[test]
+    fn test_set_canary_backend_weight() {
+        // setup:
+        let mut canary_rules = serde_json::json!([{
+            "backendRefs": [
+                { "name": "app-z1", "port": 80, "weight": 100 },
+                { "name": "app-z1-canary", "port": 80, "weight": 0 },
+            ]
+        }]);
+        let mut stable_rules = serde_json::json!([{ "backendRefs": [{ "name": "app-z1", "port": 80 }] }]);
+
+        // execute:
+        let has_canary_backend = set_canary_backend_weight(&mut canary_rules, 20);
+        let has_stable_canary_backend = set_canary_backend_weight(&mut stable_rules, 20);
+
+        // verify:
+        assert!(has_canary_backend);
+        assert_eq!(canary_rules[0]["backendRefs"][0]["weight"], 80);
+        assert_eq!(canary_rules[0]["backendRefs"][1]["weight"], 20);
+        assert!(!has_stable_canary_backend);
+    }
+}
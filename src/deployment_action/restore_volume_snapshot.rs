@@ -0,0 +1,253 @@
+use crate::cloud_provider::DeploymentTarget;
+use crate::deployment_action::pause_service::PauseServiceAction;
+use crate::deployment_action::DeploymentAction;
+use crate::deployment_report::logger::EnvProgressLogger;
+use crate::errors::{CommandError, EngineError};
+use crate::events::EventDetails;
+use crate::models::volume_snapshot::{
+    is_restored_from, rebound_pvc, restored_pvc, VolumeSnapshotRestore, DISK_ID_LABEL, VOLUME_SNAPSHOT_API_GROUP,
+    VOLUME_SNAPSHOT_API_VERSION, VOLUME_SNAPSHOT_KIND,
+};
+use crate::runtime::block_on;
+use k8s_openapi::api::core::v1::{PersistentVolume, PersistentVolumeClaim};
+use kube::api::{ApiResource, DeleteParams, DynamicObject, GroupVersionKind, Patch, PatchParams, PostParams};
+use kube::runtime::wait::{await_condition, conditions};
+use kube::Api;
+use std::time::Duration;
+
+/// Recreates volumes of a stateful service from snapshots of its storages.
+/// The service is stopped while its volumes are replaced, and started back once done.
+pub struct RestoreVolumeSnapshotAction {
+    selector: String,
+    restores: Vec<VolumeSnapshotRestore>,
+    timeout: Duration,
+    event_details: EventDetails,
+}
+
+impl RestoreVolumeSnapshotAction {
+    pub fn new(
+        selector: String,
+        restores: Vec<VolumeSnapshotRestore>,
+        timeout: Duration,
+        event_details: EventDetails,
+    ) -> RestoreVolumeSnapshotAction {
+        RestoreVolumeSnapshotAction {
+            selector,
+            restores,
+            timeout,
+            event_details,
+        }
+    }
+
+    pub fn restore(&self, target: &DeploymentTarget, logger: &EnvProgressLogger) -> Result<(), Box<EngineError>> {
+        let namespace = target.environment.namespace();
+        let mut pvcs_to_restore = Vec::with_capacity(self.restores.len());
+        for restore in &self.restores {
+            if let Some(pvc) = self.pvc_to_restore(&target.kube, namespace, restore)? {
+                pvcs_to_restore.push((pvc, restore));
+            }
+        }
+        if pvcs_to_restore.is_empty() {
+            return Ok(());
+        }
+
+        logger.info("⏸️ Stopping the service to restore its volumes from snapshots".to_string());
+        let pause_service =
+            PauseServiceAction::new(self.selector.clone(), true, self.timeout, self.event_details.clone());
+        pause_service.on_pause(target)?;
+
+        for (pvc, restore) in pvcs_to_restore {
+            logger.info(format!(
+                "♻️ Recreating volume `{}` from snapshot `{}`",
+                pvc.metadata.name.as_deref().unwrap_or_default(),
+                restore.snapshot_name
+            ));
+            block_on(recreate_pvc(
+                &target.kube,
+                namespace,
+                &pvc,
+                &restore.snapshot_name,
+                self.timeout,
+            ))
+            .map_err(|e| {
+                Box::new(EngineError::new_k8s_cannot_restore_volume_snapshot(
+                    self.event_details.clone(),
+                    &restore.snapshot_name,
+                    CommandError::new_from_safe_message(e),
+                ))
+            })?;
+        }
+
+        pause_service.unpause_if_needed(target)
+    }
+
+    /// Claim the snapshot has been taken from, none if it has already been recreated from this snapshot
+    fn pvc_to_restore(
+        &self,
+        kube: &kube::Client,
+        namespace: &str,
+        restore: &VolumeSnapshotRestore,
+    ) -> Result<Option<PersistentVolumeClaim>, Box<EngineError>> {
+        let to_engine_error = |e: kube::Error| {
+            Box::new(EngineError::new_k8s_cannot_restore_volume_snapshot(
+                self.event_details.clone(),
+                &restore.snapshot_name,
+                CommandError::new_from_safe_message(e.to_string()),
+            ))
+        };
+        let not_found_error = || {
+            Box::new(EngineError::new_volume_snapshot_not_found(
+                self.event_details.clone(),
+                &restore.storage_long_id,
+                &restore.snapshot_name,
+            ))
+        };
+
+        let api_resource = ApiResource::from_gvk(&GroupVersionKind::gvk(
+            VOLUME_SNAPSHOT_API_GROUP,
+            VOLUME_SNAPSHOT_API_VERSION,
+            VOLUME_SNAPSHOT_KIND,
+        ));
+        let snapshots: Api<DynamicObject> = Api::namespaced_with(kube.clone(), namespace, &api_resource);
+        let snapshot = block_on(snapshots.get_opt(&restore.snapshot_name))
+            .map_err(to_engine_error)?
+            .ok_or_else(not_found_error)?;
+
+        // the snapshot must have been taken from this storage and be usable
+        let storage_long_id = restore.storage_long_id.to_string();
+        if snapshot
+            .metadata
+            .labels
+            .as_ref()
+            .and_then(|labels| labels.get(DISK_ID_LABEL))
+            != Some(&storage_long_id)
+            || snapshot.data["status"]["readyToUse"].as_bool() != Some(true)
+        {
+            return Err(not_found_error());
+        }
+        let pvc_name = snapshot.data["spec"]["source"]["persistentVolumeClaimName"]
+            .as_str()
+            .ok_or_else(not_found_error)?;
+
+        let pvcs: Api<PersistentVolumeClaim> = Api::namespaced(kube.clone(), namespace);
+        let pvc = block_on(pvcs.get_opt(pvc_name))
+            .map_err(to_engine_error)?
+            .ok_or_else(|| {
+                Box::new(EngineError::new_k8s_cannot_restore_volume_snapshot(
+                    self.event_details.clone(),
+                    &restore.snapshot_name,
+                    CommandError::new_from_safe_message(format!("Volume claim `{pvc_name}` doesn't exist anymore")),
+                ))
+            })?;
+
+        match is_restored_from(&pvc, &restore.snapshot_name) {
+            true => Ok(None),
+            false => Ok(Some(pvc)),
+        }
+    }
+}
+
+/// The restored claim takes the name of the original one, so the original claim has to be deleted first.
+/// Its volume is retained meanwhile, and bound again if the restored claim can't be created.
+async fn recreate_pvc(
+    kube: &kube::Client,
+    namespace: &str,
+    pvc: &PersistentVolumeClaim,
+    snapshot_name: &str,
+    timeout: Duration,
+) -> Result<(), String> {
+    let pvcs: Api<PersistentVolumeClaim> = Api::namespaced(kube.clone(), namespace);
+    let pvs: Api<PersistentVolume> = Api::all(kube.clone());
+    let name = pvc.metadata.name.clone().unwrap_or_default();
+    let uid = pvc.metadata.uid.clone().unwrap_or_default();
+    let restored = restored_pvc(pvc, snapshot_name);
+
+    // nothing is deleted if the restored claim would be rejected
+    let mut restored_check = restored.clone();
+    restored_check.metadata.name = Some(crate::string::cut(format!("{name}-restore"), 253));
+    pvcs.create(
+        &PostParams {
+            dry_run: true,
+            ..Default::default()
+        },
+        &restored_check,
+    )
+    .await
+    .map_err(|e| format!("Volume claim restored from the snapshot is invalid: {e}"))?;
+
+    let volume = match pvc.spec.as_ref().and_then(|spec| spec.volume_name.as_deref()) {
+        Some(volume_name) => {
+            let reclaim_policy = pvs
+                .get(volume_name)
+                .await
+                .map_err(|e| format!("Cannot get volume `{volume_name}`: {e}"))?
+                .spec
+                .and_then(|spec| spec.persistent_volume_reclaim_policy)
+                .unwrap_or_else(|| "Delete".to_string());
+            set_reclaim_policy(&pvs, volume_name, "Retain").await?;
+            Some((volume_name, reclaim_policy))
+        }
+        None => None,
+    };
+
+    // the claim is only removed once no pod uses it anymore
+    pvcs.delete(&name, &DeleteParams::default())
+        .await
+        .map_err(|e| format!("Cannot delete volume claim `{name}`: {e}"))?;
+    let is_deleted =
+        tokio::time::timeout(timeout, await_condition(pvcs.clone(), &name, conditions::is_deleted(&uid))).await;
+    if !matches!(is_deleted, Ok(Ok(_))) {
+        return Err(format!(
+            "Volume claim `{name}` is still used after {} seconds, its volume{} is retained",
+            timeout.as_secs(),
+            volume
+                .map(|(volume_name, _)| format!(" `{volume_name}`"))
+                .unwrap_or_default()
+        ));
+    }
+
+    if let Err(e) = pvcs.create(&PostParams::default(), &restored).await {
+        let error = format!("Cannot create volume claim `{name}` from the snapshot: {e}");
+        return match volume {
+            Some((volume_name, _)) => match rebind_volume(&pvcs, &pvs, pvc, volume_name).await {
+                Ok(()) => Err(format!("{error}, the original volume has been bound again")),
+                Err(rebind_error) => Err(format!("{error}, volume `{volume_name}` is retained: {rebind_error}")),
+            },
+            None => Err(error),
+        };
+    }
+
+    // the original volume is released, it is removed with its original reclaim policy
+    match volume {
+        Some((volume_name, reclaim_policy)) => set_reclaim_policy(&pvs, volume_name, &reclaim_policy).await,
+        None => Ok(()),
+    }
+}
+
+pub(super) async fn set_reclaim_policy(
+    pvs: &Api<PersistentVolume>,
+    volume_name: &str,
+    reclaim_policy: &str,
+) -> Result<(), String> {
+    let patch = serde_json::json!({ "spec": { "persistentVolumeReclaimPolicy": reclaim_policy } });
+    pvs.patch(volume_name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await
+        .map_err(|e| format!("Cannot set reclaim policy of volume `{volume_name}` to {reclaim_policy}: {e}"))?;
+
+    Ok(())
+}
+
+/// A released volume is only bound again once its claim reference is removed
+async fn rebind_volume(
+    pvcs: &Api<PersistentVolumeClaim>,
+    pvs: &Api<PersistentVolume>,
+    pvc: &PersistentVolumeClaim,
+    volume_name: &str,
+) -> Result<(), kube::Error> {
+    let patch = serde_json::json!({ "spec": { "claimRef": null } });
+    pvs.patch(volume_name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await?;
+    pvcs.create(&PostParams::default(), &rebound_pvc(pvc)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::cloud_provider::io::ClusterAdvancedSettings;
+use crate::cmd::command::CommandKiller;
+use crate::cmd::trivy::Trivy;
+use crate::deployment_report::recap_reporter::render_recap_vulnerabilities;
+use crate::errors::{CommandError, EngineError};
+use crate::events::EventDetails;
+use itertools::Itertools;
+use std::time::Duration;
+use url::Url;
+
+const IMAGE_SCAN_TIMEOUT: Duration = Duration::from_secs(20 * 60);
+
+/// Scans an image pushed to the cluster registry, if image scanning is enabled on the cluster.
+/// Found vulnerabilities are reported in the deployment recap, and the deployment is blocked
+/// if some of them are at or above the blocking severity and not allowed for the service.
+pub fn scan_image_if_enabled(
+    image_name: &str,
+    registry_url: &Url,
+    vulnerability_allowlist: &[String],
+    advanced_settings: &ClusterAdvancedSettings,
+    send_progress: &dyn Fn(String),
+    send_recap: &dyn Fn(String),
+    event_details: EventDetails,
+    should_abort: &dyn Fn() -> bool,
+) -> Result<(), Box<EngineError>> {
+    if !advanced_settings.image_scanning_enabled {
+        return Ok(());
+    }
+
+    send_progress(format!("🛡️ Scanning container image {image_name} for vulnerabilities"));
+    let vulnerabilities = Trivy::new()
+        .scan_image(image_name, registry_url, &CommandKiller::from(IMAGE_SCAN_TIMEOUT, should_abort))
+        .map_err(|err| {
+            Box::new(EngineError::new_image_scan_error(
+                event_details.clone(),
+                image_name,
+                CommandError::new_from_safe_message(err.to_string()),
+            ))
+        })?;
+
+    let blocking_severity = advanced_settings.image_scanning_blocking_severity;
+    match render_recap_vulnerabilities(image_name, &vulnerabilities, blocking_severity, vulnerability_allowlist) {
+        Ok(recap) => recap
+            .trim_end()
+            .split('\n')
+            .for_each(|line| send_recap(line.to_string())),
+        Err(err) => send_progress(format!("Cannot render vulnerability scan recap. Please contact us: {err}")),
+    }
+
+    let blocking_vulnerabilities = vulnerabilities
+        .iter()
+        .filter(|vulnerability| vulnerability.is_blocking(blocking_severity, vulnerability_allowlist))
+        .map(|vulnerability| vulnerability.id.as_str())
+        .unique()
+        .collect_vec();
+    if !blocking_vulnerabilities.is_empty() {
+        return Err(Box::new(EngineError::new_image_vulnerabilities_found(
+            event_details,
+            image_name,
+            blocking_severity,
+            &blocking_vulnerabilities,
+        )));
+    }
+
+    Ok(())
+}
@@ -2,10 +2,11 @@ use crate::build_platform::Image;
 use crate::cloud_provider::io::RegistryMirroringMode;
 use crate::cloud_provider::DeploymentTarget;
 use crate::cmd::command::CommandKiller;
+use crate::cmd::cosign::{Cosign, CosignError};
 use crate::cmd::docker::ContainerImage;
 use crate::container_registry::errors::ContainerRegistryError;
 use crate::deployment_report::logger::{EnvProgressLogger, EnvSuccessLogger};
-use crate::errors::EngineError;
+use crate::errors::{CommandError, EngineError};
 use crate::events::EventDetails;
 
 use crate::metrics_registry::{MetricsRegistry, StepLabel, StepName, StepStatus};
@@ -72,28 +73,18 @@ pub fn mirror_image_if_necessary(
     logger: &EnvProgressLogger,
     event_details: EventDetails,
     metrics_registry: Arc<dyn MetricsRegistry>,
-) -> Result<(), Box<EngineError>> {
+) -> Result<ContainerImage, Box<EngineError>> {
+    verify_image_signature_if_required(source, target, logger, event_details.clone())?;
     let mirror_record = metrics_registry.start_record(*service_id, StepLabel::Service, StepName::MirrorImage);
 
-    let registry_info = target.container_registry.registry_info();
-    let mirror_repo_name = get_mirror_repository_name(
-        service_id,
-        target.kubernetes.long_id(),
-        &target.kubernetes.advanced_settings().registry_mirroring_mode,
-    );
-    let dest_image = ContainerImage::new(
-        target.container_registry.registry_info().endpoint.clone(),
-        (registry_info.get_image_name)(&mirror_repo_name),
-        vec![tag_for_mirror],
-    );
-
+    let dest_image = mirror_destination_image(service_id, tag_for_mirror, target);
     if image_already_exist(&dest_image, target) {
         logger.info(format!(
             "🎯 Skipping image mirroring. Image {} already exists in the registry",
             source.image
         ));
         mirror_record.stop(StepStatus::Skip);
-        Ok(())
+        Ok(dest_image)
     } else {
         let result = mirror_image(service_id, source, &dest_image, target, logger, event_details.clone());
         mirror_record.stop(if result.is_ok() {
@@ -101,23 +92,40 @@ pub fn mirror_image_if_necessary(
         } else {
             StepStatus::Error
         });
-        result
+        result.map(|_| dest_image)
     }
 }
 
-fn image_already_exist(dest_image: &ContainerImage, target: &DeploymentTarget) -> bool {
+/// Image of the cluster registry where the image of the service is mirrored
+pub fn mirror_destination_image(
+    service_id: &Uuid,
+    tag_for_mirror: String,
+    target: &DeploymentTarget,
+) -> ContainerImage {
+    let registry_info = target.container_registry.registry_info();
+    let mirror_repo_name = get_mirror_repository_name(
+        service_id,
+        target.kubernetes.long_id(),
+        &target.kubernetes.advanced_settings().registry_mirroring_mode,
+    );
+
+    ContainerImage::new(
+        registry_info.endpoint.clone(),
+        (registry_info.get_image_name)(&mirror_repo_name),
+        vec![tag_for_mirror],
+    )
+}
+
+pub fn image_already_exist(dest_image: &ContainerImage, target: &DeploymentTarget) -> bool {
     matches!(target.docker.does_image_exist_remotely(dest_image), Ok(true))
 }
 
-fn mirror_image(
-    service_id: &Uuid,
+fn login_to_source_registry(
     source: &RegistryImageSource,
-    dest_image: &ContainerImage,
     target: &DeploymentTarget,
     logger: &EnvProgressLogger,
     event_details: EventDetails,
 ) -> Result<(), Box<EngineError>> {
-    // We need to login to the registry to get access to the image
     let url = source.registry.get_url_with_credentials();
     if url.password().is_some() {
         logger.info(format!(
@@ -144,6 +152,71 @@ fn mirror_image(
         }
     }
 
+    Ok(())
+}
+
+/// Third-party images must be signed by one of the keys trusted for their registry, if the registry has some.
+fn verify_image_signature_if_required(
+    source: &RegistryImageSource,
+    target: &DeploymentTarget,
+    logger: &EnvProgressLogger,
+    event_details: EventDetails,
+) -> Result<(), Box<EngineError>> {
+    let Some(public_keys) = target
+        .kubernetes
+        .advanced_settings()
+        .registry_signature_verification_keys
+        .get(source.registry.id())
+        .filter(|public_keys| !public_keys.is_empty())
+    else {
+        return Ok(());
+    };
+
+    let source_image = ContainerImage::new(
+        source.registry.url().clone(),
+        source.image.to_string(),
+        vec![source.tag.to_string()],
+    );
+    let image_name = source_image.image_name();
+    logger.info(format!("🔏 Verifying the signature of image {image_name}"));
+
+    // cosign accesses the registry with the credentials of the docker config
+    login_to_source_registry(source, target, logger, event_details.clone())?;
+    let cosign = Cosign::new();
+    let cmd_killer = CommandKiller::from(Duration::from_secs(5 * 60), target.should_abort);
+    let mut signature_errors = Vec::with_capacity(public_keys.len());
+    for public_key in public_keys {
+        match cosign.verify(&image_name, public_key, &cmd_killer) {
+            Ok(_) => return Ok(()),
+            Err(err @ CosignError::InvalidSignature { .. }) => signature_errors.push(err.to_string()),
+            Err(err) => {
+                return Err(Box::new(EngineError::new_image_signing_error(
+                    event_details,
+                    format!("Error while verifying the signature of image `{image_name}`."),
+                    CommandError::new_from_safe_message(err.to_string()),
+                )))
+            }
+        }
+    }
+
+    Err(Box::new(EngineError::new_image_signature_invalid(
+        event_details,
+        &image_name,
+        CommandError::new_from_safe_message(signature_errors.join("\n")),
+    )))
+}
+
+fn mirror_image(
+    service_id: &Uuid,
+    source: &RegistryImageSource,
+    dest_image: &ContainerImage,
+    target: &DeploymentTarget,
+    logger: &EnvProgressLogger,
+    event_details: EventDetails,
+) -> Result<(), Box<EngineError>> {
+    // We need to login to the registry to get access to the image
+    login_to_source_registry(source, target, logger, event_details.clone())?;
+
     // Once we are logged to the registry, we mirror the user image into our cluster private registry
     // This is required only to avoid to manage rotating credentials
     logger.info("🪞 Mirroring image to private cluster registry to ensure reproducibility".to_string());
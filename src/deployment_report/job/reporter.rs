@@ -29,6 +29,7 @@ use uuid::Uuid;
 pub(super) enum JobType {
     CronJob(String),
     Job(Action),
+    TriggeredJob,
 }
 
 impl Display for JobType {
@@ -36,6 +37,7 @@ impl Display for JobType {
         match self {
             JobType::CronJob(_) => f.write_str("cron-job"),
             JobType::Job(_) => f.write_str("job"),
+            JobType::TriggeredJob => f.write_str("triggered-job"),
         }
     }
 }
@@ -72,6 +74,7 @@ impl<T> JobDeploymentReporter<T> {
                 schedule,
                 timezone: _timezone,
             } => JobType::CronJob(schedule.to_string()),
+            JobSchedule::OnTrigger { .. } => JobType::TriggeredJob,
         };
 
         JobDeploymentReporter {
@@ -151,6 +154,11 @@ impl<T: Send + Sync> DeploymentReporter for JobDeploymentReporter<T> {
                     self.tag,
                     self.max_duration_human_str()
                 )),
+                JobType::TriggeredJob => self.logger.send_progress(format!(
+                    "🚀 Manual trigger of Job at tag {} is starting with a timeout/max duration of {}",
+                    self.tag,
+                    self.max_duration_human_str()
+                )),
             }
 
             return;
@@ -175,6 +183,10 @@ impl<T: Send + Sync> DeploymentReporter for JobDeploymentReporter<T> {
                 "🚀 Deployment of cronjob with schedule `{}` at tag {} is starting",
                 schedule, self.tag
             )),
+            JobType::TriggeredJob => self.logger.send_progress(format!(
+                "🚀 Deployment of triggered Job at tag {} is starting, it will run on its triggers",
+                self.tag
+            )),
         }
     }
 
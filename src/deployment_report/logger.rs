@@ -26,6 +26,7 @@ impl EnvLogger {
             EnvironmentStep::Delete => (EnvironmentStep::Delete, EnvironmentStep::Deleted),
             EnvironmentStep::Build => (EnvironmentStep::Build, EnvironmentStep::Built),
             EnvironmentStep::Restart => (EnvironmentStep::Restart, EnvironmentStep::Restarted),
+            EnvironmentStep::Plan => (EnvironmentStep::Plan, EnvironmentStep::Planned),
             _ => panic!("Invalid environment step for logger"),
         };
         let event_details_progress = service.get_event_details(Stage::Environment(progress_step));
@@ -169,6 +170,10 @@ impl<'a> EnvProgressLogger<'a> {
         self.logger.send_warning(msg);
     }
 
+    pub fn recap(&self, msg: String) {
+        self.logger.send_recap(msg);
+    }
+
     pub fn log(&self, engine_event: EngineEvent) {
         self.logger.log(engine_event);
     }
@@ -1,3 +1,4 @@
+use crate::cmd::trivy::{Vulnerability, VulnerabilitySeverity};
 use crate::deployment_report::utils::{get_tera_instance, EventRenderContext};
 use itertools::Itertools;
 use k8s_openapi::api::core::v1::Event;
@@ -60,9 +61,79 @@ pub(super) fn render_recap_events(warning_events: &[Event]) -> Result<String, te
     let ctx = tera::Context::from_serialize(render_ctx)?;
     get_tera_instance().render_str(RECAP_TEMPLATE, &ctx)
 }
+
+#[derive(Debug, Serialize)]
+pub struct VulnerabilityRenderContext {
+    pub id: String,
+    pub severity: String,
+    pub package_name: String,
+    pub installed_version: String,
+    pub fixed_version: Option<String>,
+    pub is_blocking: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VulnerabilityRecapRenderContext {
+    pub image_name: String,
+    pub summary: String,
+    pub vulnerabilities: Vec<VulnerabilityRenderContext>,
+}
+
+const VULNERABILITY_RECAP_TEMPLATE: &str = r#"
+┏━━ 🛡️ Vulnerability Scan Recap ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+┃   Image {{ image_name }}: {{ summary }}
+{%- for vulnerability in vulnerabilities %}
+┃   {% if vulnerability.is_blocking %}❌{% else %}🙈{% endif %} {{ vulnerability.severity }} {{ vulnerability.id }} in {{ vulnerability.package_name }} {{ vulnerability.installed_version }}{% if vulnerability.fixed_version %} (fixed in {{ vulnerability.fixed_version }}){% endif %}
+{%- endfor %}
+┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━"#;
+
+/// Recap of an image scan: the number of vulnerabilities by severity, and the details of the ones at or above
+/// the blocking severity, blocking or allowed.
+pub fn render_recap_vulnerabilities(
+    image_name: &str,
+    vulnerabilities: &[Vulnerability],
+    blocking_severity: VulnerabilitySeverity,
+    allowlist: &[String],
+) -> Result<String, tera::Error> {
+    let summary = match vulnerabilities.is_empty() {
+        true => "no vulnerability found".to_string(),
+        false => vulnerabilities
+            .iter()
+            .counts_by(|vulnerability| vulnerability.severity)
+            .into_iter()
+            .sorted_by(|a, b| Ord::cmp(&b.0, &a.0))
+            .map(|(severity, count)| format!("{count} {severity}"))
+            .join(", "),
+    };
+
+    let vulnerabilities_context = vulnerabilities
+        .iter()
+        .filter(|vulnerability| vulnerability.severity >= blocking_severity)
+        .sorted_by(|a, b| Ord::cmp(&b.severity, &a.severity).then_with(|| Ord::cmp(&a.id, &b.id)))
+        .map(|vulnerability| VulnerabilityRenderContext {
+            id: vulnerability.id.clone(),
+            severity: vulnerability.severity.to_string(),
+            package_name: vulnerability.package_name.clone(),
+            installed_version: vulnerability.installed_version.clone(),
+            fixed_version: vulnerability.fixed_version.clone(),
+            is_blocking: vulnerability.is_blocking(blocking_severity, allowlist),
+        })
+        .collect::<Vec<VulnerabilityRenderContext>>();
+
+    let render_ctx = VulnerabilityRecapRenderContext {
+        image_name: image_name.to_string(),
+        summary,
+        vulnerabilities: vulnerabilities_context,
+    };
+
+    let ctx = tera::Context::from_serialize(render_ctx)?;
+    get_tera_instance().render_str(VULNERABILITY_RECAP_TEMPLATE, &ctx)
+}
+
 #[cfg(test)]
 mod test {
-    use crate::deployment_report::recap_reporter::render_recap_events;
+    use crate::cmd::trivy::{Vulnerability, VulnerabilitySeverity};
+    use crate::deployment_report::recap_reporter::{render_recap_events, render_recap_vulnerabilities};
     use k8s_openapi::api::core::v1::Event;
 
     #[test]
@@ -99,4 +170,43 @@ mod test {
             assert_eq!(rendered_line.trim_end(), gold_line);
         }
     }
+
+    #[test]
+    fn test_vulnerability_recap_rendering() {
+        // setup:
+        let vulnerability = |id: &str, severity: VulnerabilitySeverity, fixed_version: Option<&str>| Vulnerability {
+            id: id.to_string(),
+            package_name: "libcrypto3".to_string(),
+            installed_version: "3.0.7-r0".to_string(),
+            fixed_version: fixed_version.map(str::to_string),
+            severity,
+        };
+        let vulnerabilities = vec![
+            vulnerability("CVE-2023-0464", VulnerabilitySeverity::Medium, None),
+            vulnerability("CVE-2023-0286", VulnerabilitySeverity::High, Some("3.0.8-r0")),
+            vulnerability("CVE-2022-48174", VulnerabilitySeverity::Critical, None),
+            vulnerability("CVE-2023-0465", VulnerabilitySeverity::Medium, None),
+        ];
+
+        // execute:
+        let rendered_report = render_recap_vulnerabilities(
+            "registry.qovery.com/app-z1:v1",
+            &vulnerabilities,
+            VulnerabilitySeverity::High,
+            &["CVE-2022-48174".to_string()],
+        )
+        .unwrap();
+
+        // verify:
+        let expected = r#"
+┏━━ 🛡️ Vulnerability Scan Recap ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
+┃   Image registry.qovery.com/app-z1:v1: 1 CRITICAL, 1 HIGH, 2 MEDIUM
+┃   🙈 CRITICAL CVE-2022-48174 in libcrypto3 3.0.7-r0
+┃   ❌ HIGH CVE-2023-0286 in libcrypto3 3.0.7-r0 (fixed in 3.0.8-r0)
+┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━"#;
+        assert_eq!(rendered_report.lines().count(), expected.lines().count());
+        for (rendered_line, gold_line) in rendered_report.lines().zip(expected.lines()) {
+            assert_eq!(rendered_line.trim_end(), gold_line);
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::errors::EngineError;
+use crate::errors::{CommandError, EngineError};
 use crate::events::EventDetails;
 use thiserror::Error;
 
@@ -8,6 +8,10 @@ pub enum DnsProviderError {
     InvalidCredentials,
     #[error("Invalid API url error.")]
     InvalidApiUrl,
+    #[error("DNS zone `{0}` not found error.")]
+    ZoneNotFound(String),
+    #[error("DNS provider API error: {0}")]
+    ApiError(String),
 }
 
 impl DnsProviderError {
@@ -17,6 +21,13 @@ impl DnsProviderError {
                 EngineError::new_error_on_dns_provider_invalid_credentials(event_details)
             }
             DnsProviderError::InvalidApiUrl => EngineError::new_error_on_dns_provider_invalid_api_url(event_details),
+            DnsProviderError::ZoneNotFound(zone) => {
+                EngineError::new_error_on_dns_provider_zone_not_found(event_details, zone.to_string())
+            }
+            DnsProviderError::ApiError(message) => EngineError::new_error_on_dns_provider_information(
+                event_details,
+                CommandError::new_from_safe_message(message.to_string()),
+            ),
         }
     }
 }
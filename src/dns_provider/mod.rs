@@ -3,6 +3,8 @@ use std::net::Ipv4Addr;
 use crate::dns_provider::cloudflare::CloudflareDnsConfig;
 use crate::dns_provider::errors::DnsProviderError;
 use crate::dns_provider::qoverydns::QoveryDnsConfig;
+use crate::dns_provider::rfc2136::Rfc2136DnsConfig;
+use crate::dns_provider::route53::Route53DnsConfig;
 use crate::events::{EventDetails, InfrastructureStep, Stage, Transmitter};
 use tera::Context as TeraContext;
 use uuid::Uuid;
@@ -15,17 +17,23 @@ pub mod cloudflare;
 pub mod errors;
 pub mod io;
 pub mod qoverydns;
+pub mod rfc2136;
+pub mod route53;
 
 #[derive(Clone, Debug)]
 pub enum Kind {
     Cloudflare,
     QoveryDns,
+    Route53,
+    Rfc2136,
 }
 
 #[derive(Clone, Debug)]
 pub enum DnsProviderConfiguration {
     Cloudflare(CloudflareDnsConfig),
     QoveryDns(QoveryDnsConfig),
+    Route53(Route53DnsConfig),
+    Rfc2136(Rfc2136DnsConfig),
 }
 
 impl DnsProviderConfiguration {
@@ -33,6 +41,8 @@ impl DnsProviderConfiguration {
         match self {
             DnsProviderConfiguration::Cloudflare(_) => "cloudflare",
             DnsProviderConfiguration::QoveryDns(_) => "pdns",
+            DnsProviderConfiguration::Route53(_) => "aws",
+            DnsProviderConfiguration::Rfc2136(_) => "rfc2136",
         }
         .to_string()
     }
@@ -0,0 +1,369 @@
+use base64::engine::general_purpose;
+use base64::Engine;
+use derivative::Derivative;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::str::FromStr;
+use std::time::Duration;
+use tera::Context as TeraContext;
+use trust_dns_client::client::{Client, SyncClient};
+use trust_dns_client::error::ClientErrorKind;
+use trust_dns_client::op::ResponseCode;
+use trust_dns_client::rr::dnssec::tsig::TSigner;
+use trust_dns_client::rr::rdata::tsig::TsigAlgorithm as DnsTsigAlgorithm;
+use trust_dns_client::rr::{DNSClass, Name, RecordType};
+use trust_dns_client::tcp::TcpClientConnection;
+use trust_dns_resolver::Resolver;
+use uuid::Uuid;
+
+use crate::dns_provider::errors::DnsProviderError;
+use crate::dns_provider::{DnsProvider, DnsProviderConfiguration, Kind};
+use crate::io_models::context::Context;
+use crate::models::domain::Domain;
+
+/// Algorithm the TSIG key signing dynamic updates has been generated with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TsigAlgorithm {
+    HmacSha1,
+    HmacSha256,
+    HmacSha512,
+}
+
+impl TsigAlgorithm {
+    pub fn to_external_dns_format(&self) -> &'static str {
+        match self {
+            TsigAlgorithm::HmacSha1 => "hmac-sha1",
+            TsigAlgorithm::HmacSha256 => "hmac-sha256",
+            TsigAlgorithm::HmacSha512 => "hmac-sha512",
+        }
+    }
+
+    pub fn to_cert_manager_format(&self) -> &'static str {
+        match self {
+            TsigAlgorithm::HmacSha1 => "HMACSHA1",
+            TsigAlgorithm::HmacSha256 => "HMACSHA256",
+            TsigAlgorithm::HmacSha512 => "HMACSHA512",
+        }
+    }
+
+    /// HMAC-SHA1 is not supported to sign messages, only its key name and secret format can be checked
+    fn to_signer_algorithm(self) -> Option<DnsTsigAlgorithm> {
+        match self {
+            TsigAlgorithm::HmacSha1 => None,
+            TsigAlgorithm::HmacSha256 => Some(DnsTsigAlgorithm::HmacSha256),
+            TsigAlgorithm::HmacSha512 => Some(DnsTsigAlgorithm::HmacSha512),
+        }
+    }
+}
+
+impl FromStr for TsigAlgorithm {
+    type Err = DnsProviderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "").as_str() {
+            "hmacsha1" => Ok(TsigAlgorithm::HmacSha1),
+            "hmacsha256" => Ok(TsigAlgorithm::HmacSha256),
+            "hmacsha512" => Ok(TsigAlgorithm::HmacSha512),
+            _ => Err(DnsProviderError::InvalidCredentials),
+        }
+    }
+}
+
+#[derive(Clone, Derivative)]
+#[derivative(Debug)]
+pub struct Rfc2136DnsConfig {
+    /// Authoritative name server accepting dynamic updates, ip or hostname
+    pub nameserver: String,
+    pub port: u16,
+    pub zone: String,
+    pub tsig_key_name: String,
+    /// Base64 encoded TSIG secret
+    #[derivative(Debug = "ignore")]
+    pub tsig_secret: String,
+    pub tsig_algorithm: TsigAlgorithm,
+}
+
+/// Any DNS server supporting RFC2136 dynamic updates authenticated with TSIG (i.e: BIND, PowerDNS, Knot)
+pub struct Rfc2136Dns {
+    context: Context,
+    long_id: Uuid,
+    name: String,
+    domain: Domain,
+    dns_config: Rfc2136DnsConfig,
+}
+
+impl Rfc2136Dns {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        context: Context,
+        long_id: Uuid,
+        name: &str,
+        domain: Domain,
+        nameserver: &str,
+        port: u16,
+        zone: Option<&str>,
+        tsig_key_name: &str,
+        tsig_secret: &str,
+        tsig_algorithm: TsigAlgorithm,
+    ) -> Self {
+        let zone = match zone {
+            Some(zone) => zone.trim_end_matches('.').to_string(),
+            None => domain.root_domain().to_string(),
+        };
+
+        Rfc2136Dns {
+            context,
+            long_id,
+            name: name.to_string(),
+            domain,
+            dns_config: Rfc2136DnsConfig {
+                nameserver: nameserver.to_string(),
+                port,
+                zone,
+                tsig_key_name: tsig_key_name.to_string(),
+                tsig_secret: tsig_secret.to_string(),
+                tsig_algorithm,
+            },
+        }
+    }
+}
+
+impl Rfc2136DnsConfig {
+    fn nameserver_ip(&self) -> Result<IpAddr, DnsProviderError> {
+        if let Ok(ip) = IpAddr::from_str(&self.nameserver) {
+            return Ok(ip);
+        }
+
+        let resolver = Resolver::from_system_conf().map_err(|_| DnsProviderError::InvalidApiUrl)?;
+        resolver
+            .lookup_ip(self.nameserver.as_str())
+            .ok()
+            .and_then(|ips| ips.iter().next())
+            .ok_or(DnsProviderError::InvalidApiUrl)
+    }
+
+    /// Checks the name server is authoritative for the zone and accepts the TSIG key, by sending it a signed SOA query
+    fn check_zone(&self) -> Result<(), DnsProviderError> {
+        let zone = Name::from_str(&format!("{}.", self.zone))
+            .map_err(|_| DnsProviderError::ZoneNotFound(self.zone.to_string()))?;
+        let connection = TcpClientConnection::with_timeout(
+            SocketAddr::new(self.nameserver_ip()?, self.port),
+            Duration::from_secs(10),
+        )
+        .map_err(|_| DnsProviderError::InvalidApiUrl)?;
+        let client = match self.tsig_algorithm.to_signer_algorithm() {
+            Some(algorithm) => {
+                let secret = general_purpose::STANDARD
+                    .decode(&self.tsig_secret)
+                    .map_err(|_| DnsProviderError::InvalidCredentials)?;
+                let key_name = Name::from_str(&self.tsig_key_name).map_err(|_| DnsProviderError::InvalidCredentials)?;
+                let signer =
+                    TSigner::new(secret, algorithm, key_name, 300).map_err(|_| DnsProviderError::InvalidCredentials)?;
+                SyncClient::with_tsigner(connection, signer)
+            }
+            None => SyncClient::new(connection),
+        };
+
+        // an unknown key or a bad signature is answered with NOTAUTH and an unsigned response, rejected by the client
+        let response = client
+            .query(&zone, DNSClass::IN, RecordType::SOA)
+            .map_err(|err| match err.kind() {
+                ClientErrorKind::Io(_) | ClientErrorKind::Timeout => DnsProviderError::InvalidApiUrl,
+                _ => DnsProviderError::InvalidCredentials,
+            })?;
+
+        match response.response_code() {
+            ResponseCode::NoError
+                if response.authoritative()
+                    && response
+                        .answers()
+                        .iter()
+                        .any(|record| record.record_type() == RecordType::SOA && record.name() == &zone) =>
+            {
+                Ok(())
+            }
+            ResponseCode::NotAuth => Err(DnsProviderError::InvalidCredentials),
+            _ => Err(DnsProviderError::ZoneNotFound(self.zone.to_string())),
+        }
+    }
+}
+
+impl DnsProvider for Rfc2136Dns {
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn provider_name(&self) -> &str {
+        "rfc2136"
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Rfc2136
+    }
+
+    fn long_id(&self) -> &Uuid {
+        &self.long_id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn insert_into_teracontext<'a>(&self, context: &'a mut TeraContext) -> &'a mut TeraContext {
+        context.insert("external_dns_provider", &self.provider_name());
+        context.insert("rfc2136_nameserver", &self.dns_config.nameserver);
+        context.insert("rfc2136_port", &self.dns_config.port);
+        context.insert("rfc2136_zone", &self.dns_config.zone);
+        context.insert("rfc2136_tsig_key_name", &self.dns_config.tsig_key_name);
+        context.insert("rfc2136_tsig_secret", &self.dns_config.tsig_secret);
+        context.insert(
+            "rfc2136_tsig_algorithm",
+            &self.dns_config.tsig_algorithm.to_external_dns_format(),
+        );
+        context
+    }
+
+    fn provider_configuration(&self) -> DnsProviderConfiguration {
+        DnsProviderConfiguration::Rfc2136(self.dns_config.clone())
+    }
+
+    fn domain(&self) -> &Domain {
+        &self.domain
+    }
+
+    fn resolvers(&self) -> Vec<Ipv4Addr> {
+        // records are only visible from the configured name server when the zone is private
+        match self.dns_config.nameserver_ip() {
+            Ok(IpAddr::V4(ip)) => vec![ip],
+            _ => vec![Ipv4Addr::new(8, 8, 8, 8), Ipv4Addr::new(8, 8, 4, 4)],
+        }
+    }
+
+    fn is_valid(&self) -> Result<(), DnsProviderError> {
+        if self.dns_config.tsig_key_name.is_empty()
+            || general_purpose::STANDARD
+                .decode(&self.dns_config.tsig_secret)
+                .map(|secret| secret.is_empty())
+                .unwrap_or(true)
+        {
+            return Err(DnsProviderError::InvalidCredentials);
+        }
+        if self.dns_config.nameserver.is_empty() || self.dns_config.port == 0 {
+            return Err(DnsProviderError::InvalidApiUrl);
+        }
+        if !self
+            .domain
+            .is_sub_domain_of(&Domain::new(self.dns_config.zone.to_string()))
+        {
+            return Err(DnsProviderError::ZoneNotFound(self.dns_config.zone.to_string()));
+        }
+
+        self.dns_config.check_zone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dns_provider::errors::DnsProviderError;
+    use crate::dns_provider::rfc2136::{Rfc2136DnsConfig, TsigAlgorithm};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_tsig_algorithm_from_str() {
+        // setup:
+        struct TestCase<'a> {
+            input: &'a str,
+            expected: Option<TsigAlgorithm>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: "hmac-sha256",
+                expected: Some(TsigAlgorithm::HmacSha256),
+            },
+            TestCase {
+                input: "HMACSHA512",
+                expected: Some(TsigAlgorithm::HmacSha512),
+            },
+            TestCase {
+                input: "hmac-sha1",
+                expected: Some(TsigAlgorithm::HmacSha1),
+            },
+            TestCase {
+                input: "hmac-md5",
+                expected: None,
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = TsigAlgorithm::from_str(tc.input).ok();
+
+            // verify:
+            assert_eq!(tc.expected, result, "case: {}", tc.input);
+        }
+    }
+
+    #[test]
+    fn test_tsig_algorithm_formats() {
+        assert_eq!(TsigAlgorithm::HmacSha256.to_external_dns_format(), "hmac-sha256");
+        assert_eq!(TsigAlgorithm::HmacSha256.to_cert_manager_format(), "HMACSHA256");
+    }
+
+    // Requires the BIND server configured in tests/bind, i.e:
+    // docker run --rm -p 5353:53/tcp -v $PWD/tests/bind:/etc/bind internetsystemsconsortium/bind9:9.18
+    #[ignore]
+    #[test]
+    fn test_check_zone_with_local_bind() {
+        // setup:
+        struct TestCase<'a> {
+            zone: &'a str,
+            tsig_key_name: &'a str,
+            tsig_secret: &'a str,
+            expected: Result<(), DnsProviderError>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                zone: "qovery.test",
+                tsig_key_name: "qovery",
+                tsig_secret: "cW92ZXJ5LXRlc3QtdHNpZy1zZWNyZXQtMTIzNDU2Nzg=",
+                expected: Ok(()),
+            },
+            TestCase {
+                zone: "qovery.test",
+                tsig_key_name: "qovery",
+                tsig_secret: "d3Jvbmctc2VjcmV0",
+                expected: Err(DnsProviderError::InvalidCredentials),
+            },
+            TestCase {
+                zone: "qovery.test",
+                tsig_key_name: "unknown",
+                tsig_secret: "cW92ZXJ5LXRlc3QtdHNpZy1zZWNyZXQtMTIzNDU2Nzg=",
+                expected: Err(DnsProviderError::InvalidCredentials),
+            },
+            TestCase {
+                zone: "unknown.test",
+                tsig_key_name: "qovery",
+                tsig_secret: "cW92ZXJ5LXRlc3QtdHNpZy1zZWNyZXQtMTIzNDU2Nzg=",
+                expected: Err(DnsProviderError::ZoneNotFound("unknown.test".to_string())),
+            },
+        ];
+
+        for tc in test_cases {
+            let config = Rfc2136DnsConfig {
+                nameserver: "127.0.0.1".to_string(),
+                port: 5353,
+                zone: tc.zone.to_string(),
+                tsig_key_name: tc.tsig_key_name.to_string(),
+                tsig_secret: tc.tsig_secret.to_string(),
+                tsig_algorithm: TsigAlgorithm::HmacSha256,
+            };
+
+            // execute:
+            let result = config.check_zone();
+
+            // verify:
+            assert_eq!(tc.expected, result, "case: {} {}", tc.zone, tc.tsig_key_name);
+        }
+    }
+}
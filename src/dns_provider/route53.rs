@@ -0,0 +1,195 @@
+use derivative::Derivative;
+use rusoto_core::{Client, HttpClient, Region, RusotoError};
+use rusoto_credential::StaticProvider;
+use rusoto_route53::{ListHostedZonesByNameError, ListHostedZonesByNameRequest, Route53, Route53Client};
+use std::net::Ipv4Addr;
+use tera::Context as TeraContext;
+use uuid::Uuid;
+
+use crate::dns_provider::errors::DnsProviderError;
+use crate::dns_provider::{DnsProvider, DnsProviderConfiguration, Kind};
+use crate::io_models::context::Context;
+use crate::models::domain::Domain;
+use crate::runtime::block_on;
+
+#[derive(Clone, Derivative)]
+#[derivative(Debug)]
+pub struct Route53DnsConfig {
+    pub access_key_id: String,
+    #[derivative(Debug = "ignore")]
+    pub secret_access_key: String,
+    pub region: String,
+}
+
+pub struct Route53Dns {
+    context: Context,
+    long_id: Uuid,
+    name: String,
+    domain: Domain,
+    dns_config: Route53DnsConfig,
+}
+
+impl Route53Dns {
+    pub fn new(
+        context: Context,
+        long_id: Uuid,
+        name: &str,
+        domain: Domain,
+        access_key_id: &str,
+        secret_access_key: &str,
+        region: &str,
+    ) -> Self {
+        Route53Dns {
+            context,
+            long_id,
+            name: name.to_string(),
+            domain,
+            dns_config: Route53DnsConfig {
+                access_key_id: access_key_id.to_string(),
+                secret_access_key: secret_access_key.to_string(),
+                region: region.to_string(),
+            },
+        }
+    }
+
+    fn client(&self) -> Result<Route53Client, DnsProviderError> {
+        let credentials = StaticProvider::new(
+            self.dns_config.access_key_id.to_string(),
+            self.dns_config.secret_access_key.to_string(),
+            None,
+            None,
+        );
+        let http_client = HttpClient::new().map_err(|_| DnsProviderError::InvalidApiUrl)?;
+
+        // Route53 is a global service, its API is only served from us-east-1
+        Ok(Route53Client::new_with_client(
+            Client::new_with(credentials, http_client),
+            Region::UsEast1,
+        ))
+    }
+}
+
+impl DnsProvider for Route53Dns {
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn provider_name(&self) -> &str {
+        "aws"
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Route53
+    }
+
+    fn long_id(&self) -> &Uuid {
+        &self.long_id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn insert_into_teracontext<'a>(&self, context: &'a mut TeraContext) -> &'a mut TeraContext {
+        context.insert("external_dns_provider", &self.provider_name());
+        context.insert("route53_access_key_id", &self.dns_config.access_key_id);
+        context.insert("route53_secret_access_key", &self.dns_config.secret_access_key);
+        context.insert("route53_region", &self.dns_config.region);
+        context
+    }
+
+    fn provider_configuration(&self) -> DnsProviderConfiguration {
+        DnsProviderConfiguration::Route53(self.dns_config.clone())
+    }
+
+    fn domain(&self) -> &Domain {
+        &self.domain
+    }
+
+    fn resolvers(&self) -> Vec<Ipv4Addr> {
+        vec![Ipv4Addr::new(8, 8, 8, 8), Ipv4Addr::new(8, 8, 4, 4)]
+    }
+
+    fn is_valid(&self) -> Result<(), DnsProviderError> {
+        if self.dns_config.access_key_id.is_empty() || self.dns_config.secret_access_key.is_empty() {
+            return Err(DnsProviderError::InvalidCredentials);
+        }
+
+        // hosted zones are listed in order starting from the root domain, the domain can be in the root domain zone
+        // or in a zone delegated for one of its parents (i.e: `cluster.example.com` for `qovery.cluster.example.com`)
+        let root_domain = self.domain.root_domain();
+        let hosted_zones = block_on(self.client()?.list_hosted_zones_by_name(ListHostedZonesByNameRequest {
+            dns_name: Some(format!("{root_domain}.")),
+            max_items: Some("100".to_string()),
+            ..Default::default()
+        }))
+        .map_err(|err| to_dns_provider_error(err, &root_domain))?;
+
+        match hosted_zones
+            .hosted_zones
+            .iter()
+            .any(|hosted_zone| self.domain.is_sub_domain_of(&Domain::new(hosted_zone.name.to_string())))
+        {
+            true => Ok(()),
+            false => Err(DnsProviderError::ZoneNotFound(root_domain.to_string())),
+        }
+    }
+}
+
+fn to_dns_provider_error(err: RusotoError<ListHostedZonesByNameError>, root_domain: &Domain) -> DnsProviderError {
+    match err {
+        RusotoError::Service(ListHostedZonesByNameError::InvalidDomainName(_)) => {
+            DnsProviderError::ZoneNotFound(root_domain.to_string())
+        }
+        RusotoError::Credentials(_) => DnsProviderError::InvalidCredentials,
+        // AWS answers 403 for unknown access keys, bad signatures and denied actions
+        RusotoError::Unknown(response) if response.status.as_u16() == 401 || response.status.as_u16() == 403 => {
+            DnsProviderError::InvalidCredentials
+        }
+        RusotoError::HttpDispatch(_) => DnsProviderError::InvalidApiUrl,
+        err => DnsProviderError::ApiError(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dns_provider::errors::DnsProviderError;
+    use crate::dns_provider::route53::to_dns_provider_error;
+    use crate::models::domain::Domain;
+    use rusoto_core::RusotoError;
+    use rusoto_credential::CredentialsError;
+    use rusoto_route53::ListHostedZonesByNameError;
+
+    #[test]
+    fn test_to_dns_provider_error() {
+        // setup:
+        struct TestCase {
+            input: RusotoError<ListHostedZonesByNameError>,
+            expected: DnsProviderError,
+        }
+
+        let root_domain = Domain::new("example.com".to_string());
+        let test_cases = vec![
+            TestCase {
+                input: RusotoError::Service(ListHostedZonesByNameError::InvalidDomainName("invalid".to_string())),
+                expected: DnsProviderError::ZoneNotFound("example.com".to_string()),
+            },
+            TestCase {
+                input: RusotoError::Credentials(CredentialsError::new("no credentials")),
+                expected: DnsProviderError::InvalidCredentials,
+            },
+            TestCase {
+                input: RusotoError::Validation("max items".to_string()),
+                expected: DnsProviderError::ApiError("max items".to_string()),
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = to_dns_provider_error(tc.input, &root_domain);
+
+            // verify:
+            assert_eq!(tc.expected, result);
+        }
+    }
+}
@@ -1,26 +1,34 @@
 use super::Task;
 use crate::build_platform;
-use crate::build_platform::{to_build_error, BuildError, BuildPlatform};
+use crate::build_platform::{to_build_error, Build, BuildError, BuildPlatform};
 use crate::cloud_provider::aws::regions::AwsRegion;
 use crate::cloud_provider::environment::Environment;
+use crate::cloud_provider::io::ClusterAdvancedSettings;
 use crate::cloud_provider::service;
 use crate::cloud_provider::service::Service;
 use crate::cmd::command::CommandKiller;
+use crate::cmd::cosign::{Cosign, CosignKeyPair};
 use crate::cmd::docker;
 use crate::cmd::docker::{BuilderHandle, Docker};
 use crate::container_registry::errors::ContainerRegistryError;
 use crate::container_registry::{to_engine_error, ContainerRegistry};
 use crate::deployment_action::deploy_environment::EnvironmentDeployment;
+use crate::deployment_action::scan_image::scan_image_if_enabled;
 use crate::deployment_report::logger::EnvLogger;
 use crate::engine::InfrastructureContext;
 use crate::engine_task::qovery_api::QoveryApi;
-use crate::errors::{EngineError, ErrorMessageVerbosity};
+use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
 use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage};
 use crate::io_models::context::Context;
 use crate::io_models::engine_request::EnvironmentEngineRequest;
+use crate::io_models::environment::EnvironmentRequest;
 use crate::io_models::Action;
 use crate::logger::Logger;
 use crate::metrics_registry::{MetricsRegistry, StepLabel, StepName, StepStatus};
+use crate::runtime::block_on;
+use crate::secret_manager::external_secret::{refresh_service_secret, ExternalSecretResolver};
+use crate::secret_manager::image_signing::get_or_create_image_signing_key;
+use crate::secret_manager::vault::QVaultClient;
 use crate::transaction::DeploymentOption;
 use base64::Engine;
 use itertools::Itertools;
@@ -101,15 +109,182 @@ impl EnvironmentTask {
 
     // FIXME: Remove EngineConfig type, there is no use for it
     // merge it with DeploymentTarget type
-    fn infrastructure_context(&self) -> Result<InfrastructureContext, Box<EngineError>> {
+    fn infrastructure_context(&self, logger: Box<dyn Logger>) -> Result<InfrastructureContext, Box<EngineError>> {
         self.request.engine(
             &self.info_context(),
             self.request.event_details(),
-            self.logger.clone(),
+            logger,
             self.metrics_registry.clone(),
         )
     }
 
+    /// Replace the values of the variables referencing an external secret by the secret, returns the resolved secrets
+    fn resolve_external_secrets(&self, environment: &mut EnvironmentRequest) -> Result<Vec<String>, Box<EngineError>> {
+        let services_variables = environment
+            .applications
+            .iter_mut()
+            .map(|x| &mut x.environment_vars_with_infos)
+            .chain(
+                environment
+                    .containers
+                    .iter_mut()
+                    .map(|x| &mut x.environment_vars_with_infos),
+            )
+            .chain(environment.jobs.iter_mut().map(|x| &mut x.environment_vars_with_infos))
+            .chain(environment.helms.iter_mut().map(|x| &mut x.environment_vars_with_infos))
+            .filter(|variables| variables.values().any(|v| v.external_secret.is_some()))
+            .collect::<Vec<_>>();
+        if services_variables.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let event_details = self.get_event_details(EnvironmentStep::LoadConfiguration);
+        let cloud_provider = self
+            .request
+            .cloud_provider
+            .to_engine_cloud_provider(
+                self.info_context(),
+                &self.request.kubernetes.region,
+                self.request.kubernetes.kind.clone(),
+            )
+            .ok_or_else(|| {
+                EngineError::new_error_on_cloud_provider_information(
+                    event_details.clone(),
+                    CommandError::new_from_safe_message("Invalid cloud provider information".to_string()),
+                )
+            })?;
+        // vault is only needed by vault references
+        let qvault_client = match services_variables
+            .iter()
+            .flat_map(|variables| variables.values())
+            .filter_map(|variable| variable.external_secret.as_ref())
+            .any(|external_secret| external_secret.reference.starts_with("vault://"))
+        {
+            true => Some(QVaultClient::new(event_details.clone())?),
+            false => None,
+        };
+        let resolver =
+            ExternalSecretResolver::new(qvault_client, self.request.organization_long_id, cloud_provider.as_ref());
+
+        let mut resolved_secrets = vec![];
+        for variables in services_variables {
+            resolved_secrets.extend(
+                resolver
+                    .resolve_variables(variables)
+                    .map_err(|e| EngineError::new_external_secret_cannot_be_resolved(event_details.clone(), e))?,
+            );
+        }
+
+        Ok(resolved_secrets)
+    }
+
+    /// Update the deployed services with their external secrets to refresh, pods are restarted when a secret changed
+    fn refresh_external_secrets(&self) {
+        let event_details = self.get_event_details(EnvironmentStep::Restart);
+        let refresh = || -> Result<Vec<Uuid>, Box<EngineError>> {
+            let environment = &self.request.target_environment;
+            let services_variables = environment
+                .applications
+                .iter()
+                .map(|x| (x.long_id, &x.environment_vars_with_infos))
+                .chain(
+                    environment
+                        .containers
+                        .iter()
+                        .map(|x| (x.long_id, &x.environment_vars_with_infos)),
+                )
+                .chain(
+                    environment
+                        .jobs
+                        .iter()
+                        .map(|x| (x.long_id, &x.environment_vars_with_infos)),
+                )
+                .filter(|(_, variables)| {
+                    variables
+                        .values()
+                        .any(|v| matches!(&v.external_secret, Some(external_secret) if external_secret.refresh))
+                })
+                .collect::<Vec<_>>();
+            if services_variables.is_empty() {
+                return Ok(vec![]);
+            }
+
+            let infra_ctx = self.infrastructure_context(self.logger.clone_dyn())?;
+            // vault is only needed by vault references
+            let qvault_client = match services_variables
+                .iter()
+                .flat_map(|(_, variables)| variables.values())
+                .filter_map(|variable| variable.external_secret.as_ref())
+                .any(|external_secret| external_secret.refresh && external_secret.reference.starts_with("vault://"))
+            {
+                true => Some(QVaultClient::new(event_details.clone())?),
+                false => None,
+            };
+            let resolver = ExternalSecretResolver::new(
+                qvault_client,
+                self.request.organization_long_id,
+                infra_ctx.cloud_provider(),
+            );
+            let namespace = environment
+                .to_environment_domain(
+                    infra_ctx.context(),
+                    infra_ctx.cloud_provider(),
+                    infra_ctx.container_registry(),
+                    infra_ctx.kubernetes(),
+                )
+                .map_err(|err| {
+                    EngineError::new_invalid_engine_payload(event_details.clone(), err.to_string().as_str(), None)
+                })?
+                .namespace()
+                .to_string();
+            let kube = infra_ctx.kubernetes().kube_client()?;
+
+            let mut restarted_services = vec![];
+            for (service_long_id, variables) in services_variables {
+                let Some(refreshed) = resolver
+                    .resolve_refreshed_variables(variables)
+                    .map_err(|e| EngineError::new_external_secret_cannot_be_resolved(event_details.clone(), e))?
+                else {
+                    continue;
+                };
+                let is_restarted = block_on(refresh_service_secret(&kube, &namespace, &service_long_id, &refreshed))
+                    .map_err(|e| {
+                        EngineError::new_external_secrets_cannot_be_refreshed(
+                            event_details.clone(),
+                            &service_long_id,
+                            CommandError::new_from_safe_message(e.to_string()),
+                        )
+                    })?;
+                if is_restarted {
+                    restarted_services.push(service_long_id);
+                }
+            }
+
+            Ok(restarted_services)
+        };
+
+        match refresh() {
+            Ok(restarted_services) if restarted_services.is_empty() => self.logger.log(EngineEvent::Info(
+                self.get_event_details(EnvironmentStep::Restarted),
+                EventMessage::new("🔐 External secrets are up to date".to_string(), None),
+            )),
+            Ok(restarted_services) => self.logger.log(EngineEvent::Info(
+                self.get_event_details(EnvironmentStep::Restarted),
+                EventMessage::new(
+                    format!(
+                        "🔐 External secrets changed, services restarted: {}",
+                        restarted_services.iter().join(", ")
+                    ),
+                    None,
+                ),
+            )),
+            Err(err) => self.logger.log(EngineEvent::Error(
+                err.clone_engine_error_with_stage(Stage::Environment(EnvironmentStep::RestartedError)),
+                None,
+            )),
+        }
+    }
+
     fn _is_canceled(&self) -> bool {
         self.cancel_requested.load(Ordering::Relaxed)
     }
@@ -150,6 +325,21 @@ impl EnvironmentTask {
             Some(srv) => srv,
         };
 
+        // Built images are signed with the key of the organization, kept in vault
+        let image_signing_key = match infra_ctx.kubernetes().advanced_settings().image_signing_enabled {
+            true => {
+                let event_details = first_service.get_event_details(Stage::Environment(EnvironmentStep::Build));
+                let qvault_client = QVaultClient::new(event_details.clone())?;
+                Some(get_or_create_image_signing_key(
+                    &qvault_client,
+                    infra_ctx.context().organization_long_id(),
+                    infra_ctx.context().is_test_cluster(),
+                    event_details,
+                )?)
+            }
+            false => None,
+        };
+
         let provision_builder =
             metrics_registry.start_record(environment_id, StepLabel::Environment, StepName::ProvisionBuilder);
         let builder_handle = match Self::provision_builder(
@@ -191,6 +381,7 @@ impl EnvironmentTask {
             .advanced_settings()
             .registry_image_retention_time_sec;
         let resource_ttl = infra_ctx.kubernetes().advanced_settings().resource_ttl();
+        let cluster_advanced_settings = infra_ctx.kubernetes().advanced_settings();
         let cr_registry = infra_ctx.container_registry();
         let build_platform = infra_ctx.build_platform();
 
@@ -210,6 +401,8 @@ impl EnvironmentTask {
                         build_platform,
                         img_retention_time_sec,
                         resource_ttl,
+                        cluster_advanced_settings,
+                        image_signing_key.as_ref(),
                         cr_to_engine_error,
                         &mk_logger,
                         metrics_registry.clone(),
@@ -232,6 +425,13 @@ impl EnvironmentTask {
         services: &Vec<&mut dyn Service>,
         first_service: &&mut dyn Service,
     ) -> Result<BuilderHandle, Box<EngineError>> {
+        // Each build runs in its own pod, there is no docker builder to provision and builds can run in parallel
+        if let build_platform::Kind::KubernetesPod = infra_ctx.build_platform().kind() {
+            let nb_builder = NonZeroUsize::new(max(min(max_build_in_parallel, services.len()), 1)).unwrap();
+            env_logger(format!("🧑‍🏭 Running up to {nb_builder} builds in parallel in kubernetes pods"));
+            return Ok(BuilderHandle::without_builder(nb_builder));
+        }
+
         let builder_handle = {
             let nb_builder = if build_needs_builpacks {
                 env_logger("⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️ ⚠️️️".to_string());
@@ -288,6 +488,8 @@ impl EnvironmentTask {
         build_platform: &dyn BuildPlatform,
         image_retention_time_sec: u32,
         resource_ttl: Option<Duration>,
+        cluster_advanced_settings: &ClusterAdvancedSettings,
+        image_signing_key: Option<&CosignKeyPair>,
         cr_to_engine_error: impl Fn(ContainerRegistryError) -> EngineError,
         mk_logger: impl Fn(&dyn Service) -> EnvLogger,
         metrics_registry: Arc<dyn MetricsRegistry>,
@@ -299,9 +501,60 @@ impl EnvironmentTask {
             None => return Ok(()), // this case should not happen as we filter on buildable services
         };
         let image_name = build.image.full_image_name_with_tag();
+        let registry_url = build.image.registry_url.clone();
+        // builds can run in a pod which never logs in to the registry, so cosign is given its credentials
+        let registry_auth = build.image.registry_auth();
+        let vulnerability_allowlist = build.vulnerability_allowlist.clone();
+        let scan_image = |image_name: &str, event_details: EventDetails| {
+            scan_image_if_enabled(
+                image_name,
+                &registry_url,
+                &vulnerability_allowlist,
+                cluster_advanced_settings,
+                &|msg| logger.send_progress(msg),
+                &|msg| logger.send_recap(msg),
+                event_details,
+                should_abort,
+            )
+            .map_err(|err| {
+                logger.send_error(*err.clone());
+                err
+            })
+        };
+        // Only newly built images are signed, the ones already in the registry have been signed when built
+        let sign_image = |image_name: &str, event_details: EventDetails| -> Result<(), Box<EngineError>> {
+            let Some(key_pair) = image_signing_key else {
+                return Ok(());
+            };
+
+            logger.send_progress(format!("🔏 Signing container image {image_name}"));
+            let signed_image = Cosign::new()
+                .sign(
+                    image_name,
+                    registry_auth.clone(),
+                    key_pair,
+                    &CommandKiller::from(Duration::from_secs(5 * 60), should_abort),
+                )
+                .map_err(|err| {
+                    let err = EngineError::new_image_signing_error(
+                        event_details,
+                        format!("Error while signing image `{image_name}`."),
+                        CommandError::new_from_safe_message(err.to_string()),
+                    );
+                    logger.send_error(err.clone());
+                    Box::new(err)
+                })?;
+            logger.send_progress(format!("🔏 Container image signed as {signed_image}"));
+
+            Ok(())
+        };
 
         // If image already exists in the registry, skip the build
         if !option.force_build && cr_registry.image_exists(&build.image) {
+            scan_image(
+                &image_name,
+                service.get_event_details(Stage::Environment(EnvironmentStep::BuiltError)),
+            )?;
             let msg = format!("✅ Container image {image_name} already exists and ready to use");
             logger.send_success(msg);
             return Ok(());
@@ -327,9 +580,19 @@ impl EnvironmentTask {
         }
 
         // Ok now everything is setup, we can try to build the app
-        let build_result = build_platform.build(build, &logger, metrics_registry.clone(), should_abort);
+        let (image_name, build_result) = build_image(build, |build| {
+            build_platform.build(build, &logger, metrics_registry.clone(), should_abort)
+        });
         match build_result {
             Ok(_) => {
+                scan_image(
+                    &image_name,
+                    service.get_event_details(Stage::Environment(EnvironmentStep::BuiltError)),
+                )?;
+                sign_image(
+                    &image_name,
+                    service.get_event_details(Stage::Environment(EnvironmentStep::BuiltError)),
+                )?;
                 let msg = format!("✅ Container image {} is built and ready to use", &image_name);
                 logger.send_success(msg);
                 Ok(())
@@ -420,7 +683,8 @@ impl EnvironmentTask {
 
             let mut env_deployment = EnvironmentDeployment::new(infra_ctx, &environment, should_abort, logger.clone())?;
             let deployment_ret = match environment.action {
-                service::Action::Create => env_deployment.on_create(),
+                // services are restored by their own action, the environment one is only a deployment
+                service::Action::Create | service::Action::Restore => env_deployment.on_create(),
                 service::Action::Pause => env_deployment.on_pause(),
                 service::Action::Delete => env_deployment.on_delete(),
                 service::Action::Restart => env_deployment.on_restart(),
@@ -461,6 +725,46 @@ impl EnvironmentTask {
         Err(deployment_err)
     }
 
+    /// Report what the deployment would change, without building nor deploying anything
+    fn plan_environment(&self, environment: &Environment, infra_ctx: &InfrastructureContext) {
+        if !matches!(self.request.action, Action::Create) {
+            self.logger.log(EngineEvent::Error(
+                EngineError::new_invalid_engine_payload(
+                    self.get_event_details(EnvironmentStep::PlannedError),
+                    "Dry run is only supported for environment deployments",
+                    None,
+                ),
+                None,
+            ));
+            return;
+        }
+
+        let logger = Arc::new(infra_ctx.kubernetes().logger().clone_dyn());
+        let should_abort = self.cancel_checker();
+        let plan_ret = EnvironmentDeployment::new(infra_ctx, environment, &should_abort, logger)
+            .and_then(|env_deployment| env_deployment.on_plan());
+
+        match plan_ret {
+            Ok(plan) => self.logger.log(EngineEvent::Info(
+                self.get_event_details(EnvironmentStep::Planned),
+                EventMessage::new(
+                    format!("📋 Dry run succeeded, nothing has been applied\n{}", plan.summary()),
+                    plan.to_json().ok(),
+                ),
+            )),
+            Err(err) if err.tag().is_cancel() => self.logger.log(EngineEvent::Info(
+                self.get_event_details(EnvironmentStep::Cancelled),
+                EventMessage::new("🚫 Dry run has been canceled at user request 🚫".to_string(), None),
+            )),
+            Err(err) => self.logger.log(EngineEvent::Error(
+                err.clone_engine_error_with_stage(Stage::Environment(EnvironmentStep::PlannedError)),
+                Some(EventMessage::new_from_safe(
+                    "💣 Dry run failed to compute what the deployment would change".to_string(),
+                )),
+            )),
+        }
+    }
+
     fn get_secrets(request: &EnvironmentEngineRequest) -> Vec<String> {
         let mut secrets = vec![];
         let services_secrets = request
@@ -543,7 +847,22 @@ impl Task for EnvironmentTask {
             let _ = is_terminated_tx.send(());
         });
 
-        let infra_context = match self.infrastructure_context() {
+        if self.request.refresh_external_secrets {
+            self.refresh_external_secrets();
+            return;
+        }
+
+        // External secrets are resolved before the services can log them, so they are obfuscated as the other secrets
+        let mut target_environment = self.request.target_environment.clone();
+        let resolved_secrets = match self.resolve_external_secrets(&mut target_environment) {
+            Ok(resolved_secrets) => resolved_secrets,
+            Err(err) => {
+                self.logger.log(EngineEvent::Error(*err, None));
+                return;
+            }
+        };
+
+        let infra_context = match self.infrastructure_context(self.logger.with_secrets(resolved_secrets)) {
             Ok(infra_ctx) => infra_ctx,
             Err(err) => {
                 self.logger.log(EngineEvent::Error(*err, None));
@@ -557,7 +876,7 @@ impl Task for EnvironmentTask {
             .to_service_action()
             .to_environment_step();
         let event_details = self.get_event_details(env_step);
-        let environment = match self.request.target_environment.to_environment_domain(
+        let environment = match target_environment.to_environment_domain(
             infra_context.context(),
             infra_context.cloud_provider(),
             infra_context.container_registry(),
@@ -573,6 +892,11 @@ impl Task for EnvironmentTask {
             }
         };
 
+        if self.request.dry_run {
+            self.plan_environment(&environment, &infra_context);
+            return;
+        }
+
         // run the actions
         let env_logger = |msg: String| {
             self.logger
@@ -582,7 +906,7 @@ impl Task for EnvironmentTask {
         let deployment_ret =
             EnvironmentTask::deploy_environment(environment, &infra_context, env_logger, &self.cancel_checker());
         match (&self.request.action, deployment_ret) {
-            (Action::Create, Ok(())) => self.logger.log(EngineEvent::Info(
+            (Action::Create | Action::Restore, Ok(())) => self.logger.log(EngineEvent::Info(
                 self.get_event_details(EnvironmentStep::Deployed),
                 EventMessage::new("❤️ Deployment succeeded ❤️".to_string(), None),
             )),
@@ -602,7 +926,7 @@ impl Task for EnvironmentTask {
                 self.get_event_details(EnvironmentStep::Cancelled),
                 EventMessage::new("🚫 Deployment has been canceled at user request 🚫".to_string(), None),
             )),
-            (Action::Create, Err(err)) => {
+            (Action::Create | Action::Restore, Err(err)) => {
                 self.logger.log(EngineEvent::Info(
                     self.get_event_details(EnvironmentStep::DeployedError),
                     EventMessage::new(
@@ -794,11 +1118,27 @@ impl BuilderThreadPool {
     }
 }
 
+/// Builds the image and gives back its name once built, with the result of the build.
+/// Build platforms recompute the tag of the image from the build arguments the dockerfile actually uses,
+/// so the name of the image taken before the build can point to another image.
+fn build_image(
+    build: &mut Build,
+    build_with_platform: impl FnOnce(&mut Build) -> Result<(), BuildError>,
+) -> (String, Result<(), BuildError>) {
+    let build_result = build_with_platform(build);
+
+    (build.image.full_image_name_with_tag(), build_result)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::build_platform::{BuildpacksSettings, GitRepository, Image};
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
     use std::sync::atomic::AtomicUsize;
     use std::time::Duration;
+    use url::Url;
 
     #[test]
     fn test_builder_thread_pool() {
@@ -863,4 +1203,44 @@ mod test {
         assert!(ret.is_err());
         assert_ne!(active_taks.load(Ordering::Relaxed), 10);
     }
+
+    #[test]
+    fn test_build_image_name_after_tag_change() {
+        // setup:
+        let mut build = Build {
+            git_repository: GitRepository {
+                url: Url::parse("https://github.com/Qovery/engine-testing.git").unwrap(),
+                get_credentials: None,
+                ssh_keys: vec![],
+                commit_id: "my_commit_id".to_string(),
+                dockerfile_path: Some(PathBuf::from("Dockerfile")),
+                root_path: PathBuf::from("/"),
+                buildpack_language: None,
+                buildpacks: BuildpacksSettings::default(),
+            },
+            image: Image {
+                name: "my-repository/my-app".to_string(),
+                tag: "tag-with-all-env-vars".to_string(),
+                registry_url: Url::parse("https://registry.qovery.com").unwrap(),
+                ..Default::default()
+            },
+            environment_variables: BTreeMap::new(),
+            disable_cache: false,
+            timeout: Duration::from_secs(60),
+            architectures: vec![],
+            max_cpu_in_milli: 1000,
+            max_ram_in_gib: 2,
+            vulnerability_allowlist: vec![],
+        };
+
+        // execute:
+        let (image_name, build_result) = build_image(&mut build, |build| {
+            build.image.tag = "tag-with-dockerfile-args".to_string();
+            Ok(())
+        });
+
+        // verify:
+        assert!(build_result.is_ok());
+        assert_eq!(image_name, "registry.qovery.com/my-repository/my-app:tag-with-dockerfile-args");
+    }
 }
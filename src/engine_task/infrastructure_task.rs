@@ -104,6 +104,7 @@ impl InfrastructureTask {
                 Action::Pause => InfrastructureStep::PauseError,
                 Action::Delete => InfrastructureStep::DeleteError,
                 Action::Restart => InfrastructureStep::RestartedError,
+                Action::Restore => InfrastructureStep::ValidateApiInput,
             };
             let event_message =
                 EventMessage::new_from_safe(format!("Kubernetes cluster failure {}", &infrastructure_step));
@@ -120,6 +121,7 @@ impl InfrastructureTask {
                 Action::Pause => InfrastructureStep::Paused,
                 Action::Delete => InfrastructureStep::Deleted,
                 Action::Restart => InfrastructureStep::RestartedError,
+                Action::Restore => InfrastructureStep::ValidateApiInput,
             };
             let event_message =
                 EventMessage::new_from_safe(format!("Kubernetes cluster successfully {}", &infrastructure_step));
@@ -204,6 +206,17 @@ impl Task for InfrastructureTask {
             Action::Pause => tx.pause_kubernetes(),
             Action::Delete => tx.delete_kubernetes(),
             Action::Restart => tx.restart_kubernetes(),
+            Action::Restore => {
+                self.send_infrastructure_progress(
+                    self.logger.clone(),
+                    Some(EngineError::new_invalid_engine_payload(
+                        self.get_event_details(InfrastructureStep::ValidateApiInput),
+                        "a cluster cannot be restored",
+                        None,
+                    )),
+                );
+                return;
+            }
         };
 
         self.handle_transaction_result(self.logger.clone(), tx.commit());
@@ -40,6 +40,8 @@ pub enum Tag {
     BuilderDockerCannotReadDockerfile,
     BuilderError,
     BuilderGetBuildError,
+    CanaryAnalysisFailed,
+    CannotChangeClusterPowerState,
     CannotConnectK8sCluster,
     CannotCopyFilesFromDirectoryToDirectory,
     CannotCreateFile,
@@ -55,6 +57,7 @@ pub enum Tag {
     CannotGetAnyAvailableVPC,
     CannotGetCluster,
     CannotGetClusterNodes,
+    CannotGetManagedDatabase,
     CannotGetNodeGroupInfo,
     CannotGetNodeGroupList,
     CannotGetOrCreateIamRole,
@@ -101,16 +104,20 @@ pub enum Tag {
     ContainerRegistryRepositoryDoesntExistInRegistry,
     ContainerRegistryRepositoryNameInvalid,
     ContainerRegistryUnknownError,
+    DatabaseBackupNotFound,
     DatabaseError,
     DatabaseFailedToStartAfterSeveralRetries,
     DeleteLocalKubeconfigFileError,
     DnsProviderInformationError,
     DnsProviderInvalidApiUrl,
     DnsProviderInvalidCredentials,
+    DnsProviderZoneNotFound,
     DoNotRespectCloudProviderBestPractices,
     DockerError,
     DockerPullImageError,
     DockerPushImageError,
+    ExternalSecretCannotBeResolved,
+    ExternalSecretsCannotBeRefreshed,
     HelmChartUninstallError,
     HelmChartsDeployError,
     HelmChartsSetupError,
@@ -119,6 +126,10 @@ pub enum Tag {
     HelmHistoryError,
     HelmReleaseDataNotFound,
     HelmSecretNotFound,
+    ImageScanError,
+    ImageSignatureInvalid,
+    ImageSigningError,
+    ImageVulnerabilitiesFound,
     InvalidEngineApiInputCannotBeDeserialized,
     InvalidEnginePayload,
     InvalidJobOutputCannotBeSerialized,
@@ -137,10 +148,13 @@ pub enum Tag {
     K8sCannotGetPods,
     K8sCannotGetServices,
     K8sCannotGetStatefulset,
+    K8sCannotMigrateVolumeStorageClass,
     K8sCannotOrphanDelete,
     K8sCannotPVCEdit,
     K8sCannotReachToApi,
+    K8sCannotRestoreVolumeSnapshot,
     K8sCannotRolloutRestartStatefulset,
+    K8sCannotSwitchServiceTraffic,
     K8sDeleteDeploymentError,
     K8sDeleteStatefulsetError,
     K8sDescribe,
@@ -180,6 +194,7 @@ pub enum Tag {
     ObjectStorageCannotDeleteFileIntoBucket,
     ObjectStorageCannotEmptyBucket,
     ObjectStorageCannotGetObjectFile,
+    ObjectStorageCannotListObjects,
     ObjectStorageCannotPutFileIntoBucket,
     ObjectStorageCannotTagBucket,
     ObjectStorageInvalidBucketName,
@@ -225,6 +240,7 @@ pub enum Tag {
     Unknown,
     UnsupportedClusterKind,
     UnsupportedInstanceType,
+    UnsupportedNodeGroupCapacityType,
     UnsupportedRegion,
     UnsupportedVersion,
     UnsupportedZone,
@@ -233,6 +249,7 @@ pub enum Tag {
     VaultSecretCouldNotBeDeleted,
     VaultSecretCouldNotBeRetrieved,
     VersionNumberParsingError,
+    VolumeSnapshotNotFound,
     RouterInvalidConfiguration,
     RouterBasicAuthEnvVarCannotDecodeBase64Error,
     RouterBasicAuthEnvVarNotFound,
@@ -245,6 +262,7 @@ impl From<errors::Tag> for Tag {
             errors::Tag::TerraformAccountBlockedByProvider => Tag::TerraformAccountBlockedByProvider,
             errors::Tag::InvalidEngineApiInputCannotBeDeserialized => Tag::InvalidEngineApiInputCannotBeDeserialized,
             errors::Tag::UnsupportedInstanceType => Tag::UnsupportedInstanceType,
+            errors::Tag::UnsupportedNodeGroupCapacityType => Tag::UnsupportedNodeGroupCapacityType,
             errors::Tag::CannotRetrieveClusterConfigFile => Tag::CannotRetrieveClusterConfigFile,
             errors::Tag::CannotCreateFile => Tag::CannotCreateFile,
             errors::Tag::CannotGetClusterNodes => Tag::CannotGetClusterNodes,
@@ -283,6 +301,8 @@ impl From<errors::Tag> for Tag {
             errors::Tag::K8sPodIsNotReady => Tag::K8sPodIsNotReady,
             errors::Tag::K8sGetPodError => Tag::K8sGetPodError,
             errors::Tag::K8sGetDeploymentError => Tag::K8sGetDeploymentError,
+            errors::Tag::K8sCannotSwitchServiceTraffic => Tag::K8sCannotSwitchServiceTraffic,
+            errors::Tag::CanaryAnalysisFailed => Tag::CanaryAnalysisFailed,
             errors::Tag::K8sDeleteDeploymentError => Tag::K8sDeleteDeploymentError,
             errors::Tag::K8sGetStatefulsetError => Tag::K8sGetStatefulsetError,
             errors::Tag::K8sDeleteStatefulsetError => Tag::K8sDeleteStatefulsetError,
@@ -308,6 +328,7 @@ impl From<errors::Tag> for Tag {
             errors::Tag::UnsupportedVersion => Tag::UnsupportedVersion,
             errors::Tag::CannotGetSupportedVersions => Tag::CannotGetSupportedVersions,
             errors::Tag::CannotGetCluster => Tag::CannotGetCluster,
+            errors::Tag::CannotChangeClusterPowerState => Tag::CannotChangeClusterPowerState,
             errors::Tag::ObjectStorageCannotCreateBucket => Tag::ObjectStorageCannotCreateBucket,
             errors::Tag::ObjectStorageCannotPutFileIntoBucket => Tag::ObjectStorageCannotPutFileIntoBucket,
             errors::Tag::UnsupportedRegion => Tag::UnsupportedRegion,
@@ -322,6 +343,7 @@ impl From<errors::Tag> for Tag {
             errors::Tag::ClientServiceFailedToStart => Tag::ClientServiceFailedToStart,
             errors::Tag::ClientServiceFailedToDeployBeforeStart => Tag::ClientServiceFailedToDeployBeforeStart,
             errors::Tag::DatabaseFailedToStartAfterSeveralRetries => Tag::DatabaseFailedToStartAfterSeveralRetries,
+            errors::Tag::DatabaseBackupNotFound => Tag::DatabaseBackupNotFound,
             errors::Tag::RouterFailedToDeploy => Tag::RouterFailedToDeploy,
             errors::Tag::CloudProviderClientInvalidCredentials => Tag::CloudProviderClientInvalidCredentials,
             errors::Tag::VersionNumberParsingError => Tag::VersionNumberParsingError,
@@ -339,6 +361,10 @@ impl From<errors::Tag> for Tag {
             errors::Tag::BuilderCloningRepositoryError => Tag::BuilderCloningRepositoryError,
             errors::Tag::DockerPushImageError => Tag::DockerPushImageError,
             errors::Tag::DockerPullImageError => Tag::DockerPullImageError,
+            errors::Tag::ImageScanError => Tag::ImageScanError,
+            errors::Tag::ImageVulnerabilitiesFound => Tag::ImageVulnerabilitiesFound,
+            errors::Tag::ImageSigningError => Tag::ImageSigningError,
+            errors::Tag::ImageSignatureInvalid => Tag::ImageSignatureInvalid,
             errors::Tag::ContainerRegistryCannotInstantiateClient => Tag::ContainerRegistryCannotInstantiateClient,
             errors::Tag::ContainerRegistryCannotCreateRepository => Tag::ContainerRegistryCannotCreateRepository,
             errors::Tag::ContainerRegistryCannotGetRepository => Tag::ContainerRegistryCannotGetRepository,
@@ -385,6 +411,8 @@ impl From<errors::Tag> for Tag {
             errors::Tag::DeleteLocalKubeconfigFileError => Tag::DeleteLocalKubeconfigFileError,
             errors::Tag::VaultConnectionError => Tag::VaultConnectionError,
             errors::Tag::VaultSecretCouldNotBeRetrieved => Tag::VaultSecretCouldNotBeRetrieved,
+            errors::Tag::ExternalSecretCannotBeResolved => Tag::ExternalSecretCannotBeResolved,
+            errors::Tag::ExternalSecretsCannotBeRefreshed => Tag::ExternalSecretsCannotBeRefreshed,
             errors::Tag::VaultSecretCouldNotBeCreatedOrUpdated => Tag::VaultSecretCouldNotBeCreatedOrUpdated,
             errors::Tag::JsonDeserializationError => Tag::JsonDeserializationError,
             errors::Tag::ClusterSecretsManipulationError => Tag::ClusterSecretsManipulationError,
@@ -400,6 +428,7 @@ impl From<errors::Tag> for Tag {
             errors::Tag::CloudProviderInformationError => Tag::CloudProviderInformationError,
             errors::Tag::DnsProviderInvalidCredentials => Tag::DnsProviderInvalidCredentials,
             errors::Tag::DnsProviderInvalidApiUrl => Tag::DnsProviderInvalidApiUrl,
+            errors::Tag::DnsProviderZoneNotFound => Tag::DnsProviderZoneNotFound,
             errors::Tag::K8sErrorCopySecret => Tag::K8sErrorCopySecret,
             errors::Tag::K8sCannotReachToApi => Tag::K8sCannotReachToApi,
             errors::Tag::TerraformUnknownError => Tag::TerraformUnknownError,
@@ -428,10 +457,12 @@ impl From<errors::Tag> for Tag {
             errors::Tag::TerraformManagedDatabaseError => Tag::TerraformManagedDatabaseError,
             errors::Tag::HelmDeployTimeout => Tag::HelmDeployTimeout,
             errors::Tag::CannotPauseManagedDatabase => Tag::CannotPauseManagedDatabase,
+            errors::Tag::CannotGetManagedDatabase => Tag::CannotGetManagedDatabase,
             errors::Tag::ObjectStorageCannotDeleteBucket => Tag::ObjectStorageCannotDeleteBucket,
             errors::Tag::ObjectStorageCannotGetBucket => Tag::ObjectStorageCannotGetBucket,
             errors::Tag::ObjectStorageQuotaExceeded => Tag::ObjectStorageQuotaExceeded,
             errors::Tag::ObjectStorageCannotGetObjectFile => Tag::ObjectStorageCannotGetObjectFile,
+            errors::Tag::ObjectStorageCannotListObjects => Tag::ObjectStorageCannotListObjects,
             errors::Tag::CloudProviderGetLoadBalancer => Tag::CloudProviderGetLoadBalancer,
             errors::Tag::CloudProviderGetLoadBalancerTags => Tag::CloudProviderGetLoadBalancerTags,
             errors::Tag::K8sCannotDeletePvc => Tag::K8sCannotDeletePvc,
@@ -446,6 +477,9 @@ impl From<errors::Tag> for Tag {
             errors::Tag::K8sCannotBoundPVC => Tag::K8sCannotBoundPVC,
             errors::Tag::K8sCannotOrphanDelete => Tag::K8sCannotOrphanDelete,
             errors::Tag::K8sCannotPVCEdit => Tag::K8sCannotPVCEdit,
+            errors::Tag::K8sCannotRestoreVolumeSnapshot => Tag::K8sCannotRestoreVolumeSnapshot,
+            errors::Tag::K8sCannotMigrateVolumeStorageClass => Tag::K8sCannotMigrateVolumeStorageClass,
+            errors::Tag::VolumeSnapshotNotFound => Tag::VolumeSnapshotNotFound,
             errors::Tag::K8sCannotGetStatefulset => Tag::K8sCannotGetStatefulset,
             errors::Tag::K8sCannotRolloutRestartStatefulset => Tag::K8sCannotRolloutRestartStatefulset,
             errors::Tag::K8sCannotApplyFromFile => Tag::K8sCannotApplyFromFile,
@@ -5,11 +5,13 @@ extern crate url;
 
 use crate::build_platform::BuildError;
 use crate::cloud_provider::helm::HelmChartError;
+use crate::cloud_provider::models::NodeGroupCapacityType;
 use crate::cloud_provider::service::DatabaseType;
 use crate::cloud_provider::Kind;
 use crate::cmd::docker::DockerError;
 use crate::cmd::helm::HelmError;
 use crate::cmd::terraform::{QuotaExceededError, TerraformError};
+use crate::cmd::trivy::VulnerabilitySeverity;
 use crate::container_registry::errors::ContainerRegistryError;
 
 use crate::cloud_provider::kubernetes::KubernetesError;
@@ -19,6 +21,8 @@ use crate::models::database::DatabaseError;
 use crate::models::router::RouterError;
 use crate::models::types::VersionsNumber;
 use crate::object_storage::errors::ObjectStorageError;
+use crate::secret_manager::external_secret::ExternalSecretError;
+use crate::services::gcp::managed_database_service::ManagedDatabaseServiceError;
 use aws_sdk_docdb::error::DescribeDBClustersError;
 use aws_sdk_docdb::types::SdkError as DocdbSdkError;
 use aws_sdk_ec2::error::{DescribeVolumesError, DetachVolumeError};
@@ -313,6 +317,14 @@ impl From<ObjectStorageError> for CommandError {
                 Some(raw_error_message),
                 None,
             ),
+            ObjectStorageError::CannotListObjects {
+                bucket_name,
+                raw_error_message,
+            } => CommandError::new(
+                format!("Object storage error, cannot list files from bucket: `{bucket_name}`"),
+                Some(raw_error_message),
+                None,
+            ),
         }
     }
 }
@@ -570,6 +582,14 @@ impl From<BuildError> for CommandError {
                 Some(raw_error.to_string()),
                 None,
             ),
+            BuildError::BuildPodError {
+                application,
+                raw_error_message,
+            } => CommandError::new(
+                format!("Build error, cannot build application `{application}` due to a build pod error"),
+                Some(raw_error_message),
+                None,
+            ),
         }
     }
 }
@@ -680,6 +700,8 @@ pub enum Tag {
     UnsupportedRegion,
     /// UnsupportedZone: represents an unsupported zone in region for the given cloud provider.
     UnsupportedZone,
+    /// UnsupportedNodeGroupCapacityType: represents a node group capacity type (i.e: spot) not available on the given cloud provider.
+    UnsupportedNodeGroupCapacityType,
     /// CannotRetrieveKubernetesConfigFile: represents an error while trying to retrieve Kubernetes config file.
     CannotRetrieveClusterConfigFile,
     /// CannotCreateFile: represents an error while trying to create a file.
@@ -716,6 +738,8 @@ pub enum Tag {
     CannotDetermineK8sKubeProxyVersion,
     /// CannotPauseManagedDatabase: as the title says
     CannotPauseManagedDatabase,
+    /// CannotGetManagedDatabase: represents an error when trying to get a managed database from the cloud provider
+    CannotGetManagedDatabase,
     /// CannotConnectK8sCluster: represents an error when trying to connect to the kubernetes cluster
     CannotConnectK8sCluster,
     /// CannotExecuteK8sApiCustomMetrics: represents an error when trying to get K8s API custom metrics.
@@ -781,6 +805,12 @@ pub enum Tag {
     K8sCannotOrphanDelete,
     /// K8sCannotPVCEdit: represents an error while to perform a PVC edit.
     K8sCannotPVCEdit,
+    /// K8sCannotRestoreVolumeSnapshot: represents an error while recreating a PVC from a volume snapshot.
+    K8sCannotRestoreVolumeSnapshot,
+    /// K8sCannotMigrateVolumeStorageClass: represents an error while moving a PVC to another storage class.
+    K8sCannotMigrateVolumeStorageClass,
+    /// VolumeSnapshotNotFound: represents an error where the volume snapshot requested to be restored doesn't exist.
+    VolumeSnapshotNotFound,
     /// K8sCannotRolloutRestartStatefulset: represents an error while to perform a rollout restart on a statefulset.
     K8sCannotRolloutRestartStatefulset,
     /// K8sCannotApplyFromFile: represents an error while to perform an apply from a file.
@@ -793,6 +823,10 @@ pub enum Tag {
     K8sGetPodError,
     /// K8sGetDeploymentError: Kubernetes get deployment error
     K8sGetDeploymentError,
+    /// K8sCannotSwitchServiceTraffic: represents an error while moving traffic between the deployments of a service.
+    K8sCannotSwitchServiceTraffic,
+    /// CanaryAnalysisFailed: represents a canary version found unhealthy during its analysis window.
+    CanaryAnalysisFailed,
     /// K8sDeleteDeploymentError: Kubernetes delete deployment error
     K8sDeleteDeploymentError,
     /// K8sGetStatefulsetError: Kubernetes get statefulset error
@@ -904,6 +938,8 @@ pub enum Tag {
     CannotListClusters,
     /// CannotGetCluster: represents an error where we cannot get cluster.
     CannotGetCluster,
+    /// CannotChangeClusterPowerState: represents an error while trying to start or stop a cluster on the cloud provider.
+    CannotChangeClusterPowerState,
     /// OnlyOneClusterExpected: represents an error where only one cluster was expected but several where found
     OnlyOneClusterExpected,
     /// ClientServiceFailedToStart: represent an error while trying to start a client's service.
@@ -912,6 +948,8 @@ pub enum Tag {
     ClientServiceFailedToDeployBeforeStart,
     /// DatabaseFailedToStartAfterSeveralRetries: represents an error while trying to start a database after several retries.
     DatabaseFailedToStartAfterSeveralRetries,
+    /// DatabaseBackupNotFound: represents an error where the backup requested to be restored doesn't exist.
+    DatabaseBackupNotFound,
     /// RouterFailedToDeploy: represents an error while trying to deploy a router.
     RouterFailedToDeploy,
     /// CloudProviderInformationError: represents an error when checking cloud provider information provided.
@@ -952,6 +990,14 @@ pub enum Tag {
     DockerPushImageError,
     /// DockerPullImageError: represents an error when trying to pull a docker image.
     DockerPullImageError,
+    /// ImageScanError: represents an error when trying to scan an image for vulnerabilities.
+    ImageScanError,
+    /// ImageVulnerabilitiesFound: represents an image blocked from being deployed because of its vulnerabilities.
+    ImageVulnerabilitiesFound,
+    /// ImageSigningError: represents an error when trying to sign an image or verify its signature.
+    ImageSigningError,
+    /// ImageSignatureInvalid: represents an image not signed by any signer trusted for its registry.
+    ImageSignatureInvalid,
     /// ContainerRegistryCannotCreateRepository: represents an error when trying to create a repository.
     ContainerRegistryCannotCreateRepository,
     /// ContainerRegistryCannotGetRepository: represents an error when trying to get a repository.
@@ -1006,6 +1052,10 @@ pub enum Tag {
     VaultSecretCouldNotBeCreatedOrUpdated,
     /// VaultSecretCouldNotBeDeleted, represent a vault secret deletion error
     VaultSecretCouldNotBeDeleted,
+    /// ExternalSecretCannotBeResolved: represents an environment variable referencing an external secret that can't be read
+    ExternalSecretCannotBeResolved,
+    /// ExternalSecretsCannotBeRefreshed: represents an error while updating a service with its refreshed external secrets
+    ExternalSecretsCannotBeRefreshed,
     /// JsonDeserializationError: represent a deserialization issue
     JsonDeserializationError,
     /// ClusterSecretsManipulationError: represent an error while trying to manipulate ClusterSecrets
@@ -1016,6 +1066,8 @@ pub enum Tag {
     DnsProviderInvalidCredentials,
     /// DnsProviderInvalidApiUrl: represent an error on invalid DNS provider api url.
     DnsProviderInvalidApiUrl,
+    /// DnsProviderZoneNotFound: represent an error on a DNS zone not managed by the DNS provider.
+    DnsProviderZoneNotFound,
     /// ObjectStorageCannotInstantiateClient: represents an error while trying to instantiate object storage client.
     ObjectStorageCannotInstantiateClient,
     /// ObjectStorageCannotCreateBucket: represents an error while trying to create a new object storage bucket.
@@ -1040,6 +1092,8 @@ pub enum Tag {
     ObjectStorageCannotTagBucket,
     /// ObjectStorageCannotGetObjectFile: represents an error while trying to get a file from object storage bucket.
     ObjectStorageCannotGetObjectFile,
+    /// ObjectStorageCannotListObjects: represents an error while trying to list files of an object storage bucket.
+    ObjectStorageCannotListObjects,
     /// JobFailure: represents an error while indicating that the job failed to terminate properly
     JobFailure,
     /// CannotParseString: represents an error while trying to parse a string
@@ -1563,6 +1617,35 @@ impl EngineError {
         )
     }
 
+    /// Creates new error for unsupported node group capacity type.
+    ///
+    /// Cloud provider doesn't offer the requested capacity type (i.e: spot) for its node pools.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `node_group_name`: Node group requesting the capacity type.
+    /// * `capacity_type`: Requested capacity type.
+    pub fn new_unsupported_node_group_capacity_type(
+        event_details: EventDetails,
+        node_group_name: &str,
+        capacity_type: NodeGroupCapacityType,
+    ) -> EngineError {
+        let message =
+            format!("Capacity type `{capacity_type:?}` requested by node group `{node_group_name}` is not supported.");
+        EngineError::new(
+            event_details,
+            Tag::UnsupportedNodeGroupCapacityType,
+            message.clone(),
+            Some(CommandError::new_from_safe_message(message)),
+            None,
+            Some(
+                "Selected capacity type is not supported by the cloud provider, please use on-demand nodes."
+                    .to_string(),
+            ),
+        )
+    }
+
     /// Creates new error: cannot get workspace directory.
     ///
     /// Error occurred while trying to get workspace directory.
@@ -1937,6 +2020,30 @@ impl EngineError {
         )
     }
 
+    /// Creates new error when a managed database can't be retrieved from the cloud provider
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `database_id`: Managed database identifier.
+    /// * `raw_error`: Raw error message.
+    pub fn new_cannot_get_managed_database(
+        event_details: EventDetails,
+        database_id: &str,
+        raw_error: ManagedDatabaseServiceError,
+    ) -> EngineError {
+        let message = format!("Unable to get managed database `{database_id}`: {raw_error}");
+
+        EngineError::new(
+            event_details,
+            Tag::CannotGetManagedDatabase,
+            message.clone(),
+            Some(CommandError::new_from_safe_message(message)),
+            None,
+            None,
+        )
+    }
+
     pub fn new_cannot_connect_to_k8s_cluster(event_details: EventDetails, kube_error: kube::Error) -> EngineError {
         let message = format!("Unable to connect to target k8s cluster: `{kube_error}`");
 
@@ -2117,6 +2224,80 @@ impl EngineError {
         EngineError::new(event_details, Tag::K8sCannotDeletePvc, message, Some(raw_k8s_error), None, None)
     }
 
+    /// Creates new error when the volume snapshot to restore a storage from cannot be found.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `storage_id`: Storage identifier.
+    /// * `snapshot_name`: Volume snapshot requested to be restored.
+    pub fn new_volume_snapshot_not_found(
+        event_details: EventDetails,
+        storage_id: &Uuid,
+        snapshot_name: &str,
+    ) -> EngineError {
+        let message = format!("Cannot find a ready volume snapshot `{snapshot_name}` of storage (id `{storage_id}`).");
+
+        EngineError::new(
+            event_details,
+            Tag::VolumeSnapshotNotFound,
+            message,
+            None,
+            None,
+            Some("Snapshots are taken on schedule, make sure the requested one belongs to this storage and has already been taken.".to_string()),
+        )
+    }
+
+    /// Creates new error for kubernetes not being able to recreate a pvc from a volume snapshot.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `snapshot_name`: Volume snapshot the pvc is recreated from.
+    /// * `raw_k8s_error`: Raw error message.
+    pub fn new_k8s_cannot_restore_volume_snapshot(
+        event_details: EventDetails,
+        snapshot_name: &str,
+        raw_k8s_error: CommandError,
+    ) -> EngineError {
+        let message = format!("Unable to recreate Kubernetes pvc from volume snapshot `{snapshot_name}`.");
+
+        EngineError::new(
+            event_details,
+            Tag::K8sCannotRestoreVolumeSnapshot,
+            message,
+            Some(raw_k8s_error),
+            None,
+            None,
+        )
+    }
+
+    /// Creates new error for kubernetes not being able to move a pvc to another storage class.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `pvc_name`: Volume claim moved to the storage class.
+    /// * `storage_class`: Storage class the volume claim is moved to.
+    /// * `raw_k8s_error`: Raw error message.
+    pub fn new_k8s_cannot_migrate_volume_storage_class(
+        event_details: EventDetails,
+        pvc_name: &str,
+        storage_class: &str,
+        raw_k8s_error: CommandError,
+    ) -> EngineError {
+        let message = format!("Unable to move Kubernetes pvc `{pvc_name}` to storage class `{storage_class}`.");
+
+        EngineError::new(
+            event_details,
+            Tag::K8sCannotMigrateVolumeStorageClass,
+            message,
+            Some(raw_k8s_error),
+            None,
+            None,
+        )
+    }
+
     /// Creates new error for kubernetes not being able to get crash looping pods.
     ///
     /// Arguments:
@@ -3308,6 +3489,50 @@ impl EngineError {
         EngineError::new(event_details, Tag::K8sGetPodError, error.to_string(), Some(error), None, None)
     }
 
+    /// Creates new error for traffic switch between the deployments of a service.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `service_name`: Kubernetes name of the service.
+    /// * `raw_error`: Raw error message.
+    pub fn new_k8s_cannot_switch_service_traffic(
+        event_details: EventDetails,
+        service_name: &str,
+        raw_error: CommandError,
+    ) -> EngineError {
+        let message = format!("Error while trying to switch traffic of `{service_name}` to its new version.");
+
+        EngineError::new(
+            event_details,
+            Tag::K8sCannotSwitchServiceTraffic,
+            message,
+            Some(raw_error),
+            None,
+            None,
+        )
+    }
+
+    /// Creates new error for a canary version found unhealthy, the canary has been removed and the stable version kept.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `service_name`: Kubernetes name of the service.
+    /// * `reason`: Why the canary has been aborted.
+    pub fn new_canary_analysis_failed(event_details: EventDetails, service_name: &str, reason: &str) -> EngineError {
+        let message = format!("Canary version of `{service_name}` has been aborted: {reason}");
+
+        EngineError::new(
+            event_details,
+            Tag::CanaryAnalysisFailed,
+            message,
+            None,
+            None,
+            Some("The previous version is still serving the traffic, check the logs of the canary pods.".to_string()),
+        )
+    }
+
     /// Creates new error from a command error
     ///
     /// Arguments:
@@ -3552,6 +3777,33 @@ impl EngineError {
         )
     }
 
+    /// Creates new error while trying to start or stop a cluster on the cloud provider.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `action`: Power action which failed (start, stop).
+    /// * `raw_error`: Raw error message.
+    pub fn new_cannot_change_cluster_power_state(
+        event_details: EventDetails,
+        action: &str,
+        raw_error: CommandError,
+    ) -> EngineError {
+        let message = format!("Error, cannot {action} cluster.");
+
+        EngineError::new(
+            event_details,
+            Tag::CannotChangeClusterPowerState,
+            message,
+            Some(raw_error),
+            None,
+            Some(
+                "Please retry later, if the issue persists, check the cluster state on your cloud provider console."
+                    .to_string(),
+            ),
+        )
+    }
+
     /// Creates new error while trying to start a client service.
     ///
     /// Arguments:
@@ -3634,6 +3886,34 @@ impl EngineError {
         )
     }
 
+    /// Creates new error when the backup to restore into a database cannot be found.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `service_id`: Database identifier.
+    /// * `bucket_name`: Bucket the backups are stored in.
+    /// * `requested_backup`: Backup key or point in time requested to be restored.
+    pub fn new_database_backup_not_found(
+        event_details: EventDetails,
+        service_id: String,
+        bucket_name: &str,
+        requested_backup: &str,
+    ) -> EngineError {
+        let message = format!(
+            "Cannot find backup `{requested_backup}` of database (id `{service_id}`) in bucket `{bucket_name}`."
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::DatabaseBackupNotFound,
+            message,
+            None,
+            None,
+            Some("Backups are taken on schedule, make sure one has already been taken before the requested point in time.".to_string()),
+        )
+    }
+
     /// Creates new error while trying to deploy a router.
     ///
     /// Arguments:
@@ -3767,6 +4047,25 @@ impl EngineError {
         EngineError::new(event_details, Tag::CannotDeleteNodeGroup, message, None, None, None)
     }
 
+    /// Can't delete the nodes launched by Karpenter
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `raw_error`: Raw error message.
+    pub fn new_karpenter_nodes_delete_error(event_details: EventDetails, raw_error: CommandError) -> EngineError {
+        let message = "Error, can't delete the nodes launched by Karpenter.";
+
+        EngineError::new(
+            event_details,
+            Tag::CannotDeleteNodeGroup,
+            message.to_string(),
+            Some(raw_error),
+            None,
+            Some("Check Karpenter controller logs, its nodes may be blocked by a pod disruption budget.".to_string()),
+        )
+    }
+
     /// No cluster found
     ///
     /// Arguments:
@@ -4171,6 +4470,96 @@ impl EngineError {
         EngineError::new(event_details, Tag::DockerPullImageError, message, Some(raw_error), None, None)
     }
 
+    /// Creates new error when trying to scan an image for vulnerabilities.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `image_name`: Scanned image name.
+    /// * `raw_error`: Raw error message.
+    pub fn new_image_scan_error(event_details: EventDetails, image_name: &str, raw_error: CommandError) -> EngineError {
+        let message = format!("Error while scanning image `{image_name}` for vulnerabilities.");
+
+        EngineError::new(
+            event_details,
+            Tag::ImageScanError,
+            message,
+            Some(raw_error),
+            None,
+            Some("Image scanning is enabled on the cluster, images are not deployed until they have been scanned. Please retry later.".to_string()),
+        )
+    }
+
+    /// Creates new error when an image has vulnerabilities blocking its deployment.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `image_name`: Scanned image name.
+    /// * `blocking_severity`: Severity from which vulnerabilities block the deployment.
+    /// * `vulnerability_ids`: Identifiers of the blocking vulnerabilities.
+    pub fn new_image_vulnerabilities_found(
+        event_details: EventDetails,
+        image_name: &str,
+        blocking_severity: VulnerabilitySeverity,
+        vulnerability_ids: &[&str],
+    ) -> EngineError {
+        let message = format!(
+            "Image `{image_name}` has {} vulnerabilities of severity {blocking_severity} or above: {}.",
+            vulnerability_ids.len(),
+            vulnerability_ids.join(", ")
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::ImageVulnerabilitiesFound,
+            message,
+            None,
+            None,
+            Some("Upgrade the vulnerable packages of the image, or allow those vulnerabilities with the `security.vulnerability_allowlist` advanced setting of the service.".to_string()),
+        )
+    }
+
+    /// Creates new error when trying to sign an image or verify its signature.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `message`: Error message.
+    /// * `raw_error`: Raw error message.
+    pub fn new_image_signing_error(
+        event_details: EventDetails,
+        message: String,
+        raw_error: CommandError,
+    ) -> EngineError {
+        EngineError::new(event_details, Tag::ImageSigningError, message, Some(raw_error), None, None)
+    }
+
+    /// Creates new error when an image is not signed by any signer trusted for its registry.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `image_name`: Verified image name.
+    /// * `raw_error`: Raw error message.
+    pub fn new_image_signature_invalid(
+        event_details: EventDetails,
+        image_name: &str,
+        raw_error: CommandError,
+    ) -> EngineError {
+        let message =
+            format!("Image `{image_name}` is not signed by a signer trusted for its registry, it cannot be deployed.");
+
+        EngineError::new(
+            event_details,
+            Tag::ImageSignatureInvalid,
+            message,
+            Some(raw_error),
+            None,
+            Some("Sign the image with one of the keys trusted for its registry, or update the `registry.signature_verification_keys` advanced setting of the cluster.".to_string()),
+        )
+    }
+
     /// Creates new error when trying to read Dockerfile content.
     ///
     /// Arguments:
@@ -4495,6 +4884,14 @@ impl EngineError {
                 None,
                 None,
             ),
+            ObjectStorageError::CannotListObjects { ref bucket_name, .. } => EngineError::new(
+                event_details,
+                Tag::ObjectStorageCannotListObjects,
+                format!("Error, cannot list files from object storage bucket `{bucket_name}`.",),
+                Some(object_storage_error.into()),
+                None,
+                None,
+            ),
         }
     }
 
@@ -4539,6 +4936,43 @@ impl EngineError {
         )
     }
 
+    /// Creates new error when an environment variable references an external secret that can't be read
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `raw_error`: Raw error message.
+    pub fn new_external_secret_cannot_be_resolved(
+        event_details: EventDetails,
+        raw_error: ExternalSecretError,
+    ) -> EngineError {
+        let message = format!("Environment variable external secret cannot be resolved: {raw_error}");
+
+        EngineError::new(
+            event_details,
+            Tag::ExternalSecretCannotBeResolved,
+            message.clone(),
+            Some(CommandError::new_from_safe_message(message)),
+            None,
+            Some("Make sure the secret exists and can be read with the credentials of the cluster.".to_string()),
+        )
+    }
+
+    pub fn new_external_secrets_cannot_be_refreshed(
+        event_details: EventDetails,
+        service_long_id: &Uuid,
+        raw_error: CommandError,
+    ) -> EngineError {
+        EngineError::new(
+            event_details,
+            Tag::ExternalSecretsCannotBeRefreshed,
+            format!("External secrets of service `{service_long_id}` cannot be refreshed."),
+            Some(raw_error),
+            None,
+            None,
+        )
+    }
+
     pub fn new_scaleway_cannot_fetch_private_networks(event_details: EventDetails, raw_error: String) -> EngineError {
         EngineError::new(
             event_details,
@@ -4709,6 +5143,25 @@ impl EngineError {
         )
     }
 
+    /// Creates new error when the DNS zone is not managed by the client DNS provider
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `zone`: DNS zone which has not been found.
+    pub fn new_error_on_dns_provider_zone_not_found(event_details: EventDetails, zone: String) -> EngineError {
+        let message_safe = format!("DNS zone `{zone}` is not managed by the DNS provider");
+
+        EngineError::new(
+            event_details,
+            Tag::DnsProviderZoneNotFound,
+            message_safe,
+            None,
+            None,
+            Some("Check the DNS zone exists in your DNS provider and credentials have access to it".to_string()),
+        )
+    }
+
     /// Creates new error to match Cloud Provider best practices
     ///
     /// Arguments:
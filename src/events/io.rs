@@ -204,6 +204,10 @@ pub enum EnvironmentStep {
     Restart,
     Restarted,
     RestartedError,
+    Rollback,
+    Plan,
+    Planned,
+    PlannedError,
 }
 
 impl From<events::EnvironmentStep> for EnvironmentStep {
@@ -237,6 +241,10 @@ impl From<events::EnvironmentStep> for EnvironmentStep {
             events::EnvironmentStep::JobOutput => EnvironmentStep::JobOutput,
             events::EnvironmentStep::DatabaseOutput => EnvironmentStep::DatabaseOutput,
             events::EnvironmentStep::Recap => EnvironmentStep::Recap,
+            events::EnvironmentStep::Rollback => EnvironmentStep::Rollback,
+            events::EnvironmentStep::Plan => EnvironmentStep::Plan,
+            events::EnvironmentStep::Planned => EnvironmentStep::Planned,
+            events::EnvironmentStep::PlannedError => EnvironmentStep::PlannedError,
         }
     }
 }
@@ -426,6 +426,14 @@ pub enum EnvironmentStep {
     Restarted,
     /// RestartedError: Error on restarting service pods
     RestartedError,
+    /// Rollback: Restore services to their previous version after a failed deployment
+    Rollback,
+    /// Plan: compute what a deployment would change, without applying anything
+    Plan,
+    /// Planned: the deployment plan has been computed
+    Planned,
+    /// PlannedError: Terminal error on computing the deployment plan
+    PlannedError,
 
     // Transfer data to core
     /// JobOutput: contains the environment variables to upsert
@@ -445,6 +453,7 @@ impl EnvironmentStep {
                 | EnvironmentStep::PausedError
                 | EnvironmentStep::DeletedError
                 | EnvironmentStep::RestartedError
+                | EnvironmentStep::PlannedError
         )
     }
 
@@ -487,6 +496,10 @@ impl Display for EnvironmentStep {
                 EnvironmentStep::JobOutput => "job-output",
                 EnvironmentStep::DatabaseOutput => "database-output",
                 EnvironmentStep::Recap => "recap",
+                EnvironmentStep::Rollback => "rollback",
+                EnvironmentStep::Plan => "plan",
+                EnvironmentStep::Planned => "planned",
+                EnvironmentStep::PlannedError => "planned-error",
             },
         )
     }
@@ -643,6 +656,7 @@ impl EventDetails {
                 EnvironmentStep::Restart | EnvironmentStep::Restarted => {
                     Stage::Environment(EnvironmentStep::RestartedError)
                 }
+                EnvironmentStep::Plan | EnvironmentStep::Planned => Stage::Environment(EnvironmentStep::PlannedError),
                 EnvironmentStep::LoadConfiguration
                 | EnvironmentStep::ValidateApiInput
                 | EnvironmentStep::ValidateSystemRequirements
@@ -658,8 +672,10 @@ impl EventDetails {
                 | EnvironmentStep::PausedError
                 | EnvironmentStep::DeletedError
                 | EnvironmentStep::RestartedError
+                | EnvironmentStep::PlannedError
                 | EnvironmentStep::JobOutput
                 | EnvironmentStep::Recap
+                | EnvironmentStep::Rollback
                 | EnvironmentStep::DatabaseOutput => return,
             },
         };
@@ -1,4 +1,4 @@
-use crate::build_platform::{Build, GitRepository, Image, SshKey};
+use crate::build_platform::{Build, BuildpacksSettings, GitRepository, Image, SshKey};
 use crate::cloud_provider::kubernetes::Kind as KubernetesKind;
 use crate::cloud_provider::models::{CpuArchitecture, EnvironmentVariable};
 use crate::cloud_provider::service::ServiceType;
@@ -16,10 +16,11 @@ use crate::models;
 use crate::models::application::{ApplicationError, ApplicationService};
 use crate::models::aws::{AwsAppExtraSettings, AwsStorageType};
 use crate::models::aws_ec2::{AwsEc2AppExtraSettings, AwsEc2StorageType};
+use crate::models::azure::{AzureAppExtraSettings, AzureStorageType};
 use crate::models::gcp::{GcpAppExtraSettings, GcpStorageType};
 use crate::models::scaleway::{ScwAppExtraSettings, ScwStorageType};
 use crate::models::selfmanaged::SelfManagedAppExtraSettings;
-use crate::models::types::{AWSEc2, SelfManaged, AWS, GCP, SCW};
+use crate::models::types::{AWSEc2, SelfManaged, AWS, AZURE, GCP, SCW};
 use crate::utilities::to_short_id;
 use base64::engine::general_purpose;
 use base64::Engine;
@@ -32,7 +33,7 @@ use std::time::Duration;
 use url::Url;
 use uuid::Uuid;
 
-use super::{PodAntiAffinity, UpdateStrategy};
+use super::{HpaCustomMetric, ModSecurityProfile, PodAntiAffinity, PodToleration, UpdateStrategy};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Protocol {
@@ -70,6 +71,24 @@ pub fn to_environment_variable(env_vars: BTreeMap<String, VariableInfo>) -> Vec<
         .collect()
 }
 
+/// Images built with buildpacks listen on `$PORT` (dynamic port binding).
+/// It is set to the application default port, unless the user already defined it.
+fn buildpacks_port_variable(
+    ports: &[Port],
+    environment_variables: &[EnvironmentVariable],
+) -> Option<EnvironmentVariable> {
+    if environment_variables.iter().any(|v| v.key == "PORT") {
+        return None;
+    }
+
+    let port = ports.iter().find(|p| p.is_default).or_else(|| ports.first())?;
+    Some(EnvironmentVariable {
+        key: "PORT".to_string(),
+        value: general_purpose::STANDARD.encode(port.port.to_string()),
+        is_secret: false,
+    })
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
 pub struct GitCredentials {
     pub login: String,
@@ -85,6 +104,8 @@ pub struct ApplicationAdvancedSettings {
     pub security_service_account_name: String,
     #[serde(alias = "security.read_only_root_filesystem")]
     pub security_read_only_root_filesystem: bool,
+    #[serde(alias = "security.vulnerability_allowlist")]
+    pub security_vulnerability_allowlist: Vec<String>,
 
     // Deployment
     #[serde(alias = "deployment.termination_grace_period_seconds")]
@@ -97,8 +118,21 @@ pub struct ApplicationAdvancedSettings {
     pub deployment_update_strategy_rolling_update_max_unavailable_percent: u32,
     #[serde(alias = "deployment.update_strategy.rolling_update.max_surge_percent")]
     pub deployment_update_strategy_rolling_update_max_surge_percent: u32,
+    #[serde(alias = "deployment.update_strategy.canary.traffic_percent")]
+    pub deployment_update_strategy_canary_traffic_percent: u32,
+    #[serde(alias = "deployment.update_strategy.canary.analysis_duration_seconds")]
+    pub deployment_update_strategy_canary_analysis_duration_seconds: u32,
+    #[serde(alias = "deployment.update_strategy.canary.max_container_restarts")]
+    pub deployment_update_strategy_canary_max_container_restarts: u32,
+    // share of the canary requests answered with a 5xx above which the canary is aborted
+    #[serde(alias = "deployment.update_strategy.canary.max_error_rate_percent")]
+    pub deployment_update_strategy_canary_max_error_rate_percent: u32,
     #[serde(alias = "deployment.affinity.node.required")]
     pub deployment_affinity_node_required: BTreeMap<String, String>,
+    #[serde(alias = "deployment.node_selector")]
+    pub deployment_node_selector: BTreeMap<String, String>,
+    #[serde(alias = "deployment.tolerations")]
+    pub deployment_tolerations: Vec<PodToleration>,
     #[serde(alias = "deployment.antiaffinity.pod")]
     pub deployment_antiaffinity_pod: PodAntiAffinity,
 
@@ -149,6 +183,20 @@ pub struct ApplicationAdvancedSettings {
     pub network_ingress_denylist_source_range: String,
     #[serde(alias = "network.ingress.basic_auth_env_var")]
     pub network_ingress_basic_auth_env_var: String,
+    #[serde(alias = "network.ingress.limit_rps")]
+    pub network_ingress_limit_rps: Option<u32>,
+    #[serde(alias = "network.ingress.limit_connections")]
+    pub network_ingress_limit_connections: Option<u32>,
+    #[serde(alias = "network.ingress.limit_burst_multiplier")]
+    pub network_ingress_limit_burst_multiplier: u32,
+    #[serde(alias = "network.ingress.modsecurity_profile")]
+    pub network_ingress_modsecurity_profile: Option<ModSecurityProfile>,
+    #[serde(alias = "network.ingress.auth_url")]
+    pub network_ingress_auth_url: String,
+    #[serde(alias = "network.ingress.auth_signin")]
+    pub network_ingress_auth_signin: String,
+    #[serde(alias = "network.ingress.auth_response_headers")]
+    pub network_ingress_auth_response_headers: String,
 
     #[serde(alias = "network.ingress.grpc_send_timeout_seconds")]
     pub network_ingress_grpc_send_timeout_seconds: u32,
@@ -158,6 +206,20 @@ pub struct ApplicationAdvancedSettings {
     // Pod autoscaler
     #[serde(alias = "hpa.cpu.average_utilization_percent")]
     pub hpa_cpu_average_utilization_percent: u8,
+    #[serde(alias = "hpa.memory.average_utilization_percent")]
+    pub hpa_memory_average_utilization_percent: Option<u8>,
+    #[serde(alias = "hpa.requests_per_second.average")]
+    pub hpa_requests_per_second_average: Option<u32>,
+    #[serde(alias = "hpa.custom_metrics")]
+    pub hpa_custom_metrics: Vec<HpaCustomMetric>,
+    #[serde(alias = "hpa.scale_up.stabilization_window_seconds")]
+    pub hpa_scale_up_stabilization_window_seconds: u32,
+    #[serde(alias = "hpa.scale_up.max_percent")]
+    pub hpa_scale_up_max_percent: u32,
+    #[serde(alias = "hpa.scale_down.stabilization_window_seconds")]
+    pub hpa_scale_down_stabilization_window_seconds: u32,
+    #[serde(alias = "hpa.scale_down.max_percent")]
+    pub hpa_scale_down_max_percent: u32,
 }
 
 impl Default for ApplicationAdvancedSettings {
@@ -165,12 +227,19 @@ impl Default for ApplicationAdvancedSettings {
         ApplicationAdvancedSettings {
             security_service_account_name: "".to_string(),
             security_read_only_root_filesystem: false,
+            security_vulnerability_allowlist: vec![],
             deployment_termination_grace_period_seconds: 60,
             deployment_custom_domain_check_enabled: true,
             deployment_update_strategy_type: UpdateStrategy::RollingUpdate,
             deployment_update_strategy_rolling_update_max_unavailable_percent: 25,
             deployment_update_strategy_rolling_update_max_surge_percent: 25,
+            deployment_update_strategy_canary_traffic_percent: 10,
+            deployment_update_strategy_canary_analysis_duration_seconds: 5 * 60,
+            deployment_update_strategy_canary_max_container_restarts: 0,
+            deployment_update_strategy_canary_max_error_rate_percent: 5,
             deployment_affinity_node_required: BTreeMap::new(),
+            deployment_node_selector: BTreeMap::new(),
+            deployment_tolerations: vec![],
             deployment_antiaffinity_pod: PodAntiAffinity::Preferred,
             build_timeout_max_sec: 30 * 60,
             build_cpu_max_in_milli: 4000,
@@ -194,9 +263,23 @@ impl Default for ApplicationAdvancedSettings {
             network_ingress_whitelist_source_range: "0.0.0.0/0".to_string(),
             network_ingress_denylist_source_range: "".to_string(),
             network_ingress_basic_auth_env_var: "".to_string(),
+            network_ingress_limit_rps: None,
+            network_ingress_limit_connections: None,
+            network_ingress_limit_burst_multiplier: 5,
+            network_ingress_modsecurity_profile: None,
+            network_ingress_auth_url: "".to_string(),
+            network_ingress_auth_signin: "".to_string(),
+            network_ingress_auth_response_headers: "".to_string(),
             network_ingress_grpc_send_timeout_seconds: 60,
             network_ingress_grpc_read_timeout_seconds: 60,
             hpa_cpu_average_utilization_percent: 60,
+            hpa_memory_average_utilization_percent: None,
+            hpa_requests_per_second_average: None,
+            hpa_custom_metrics: vec![],
+            hpa_scale_up_stabilization_window_seconds: 0,
+            hpa_scale_up_max_percent: 100,
+            hpa_scale_down_stabilization_window_seconds: 300,
+            hpa_scale_down_max_percent: 100,
         }
     }
 }
@@ -206,6 +289,7 @@ impl ApplicationAdvancedSettings {
         ContainerAdvancedSettings {
             security_service_account_name: self.security_service_account_name.clone(),
             security_read_only_root_filesystem: self.security_read_only_root_filesystem,
+            security_vulnerability_allowlist: self.security_vulnerability_allowlist.clone(),
             deployment_custom_domain_check_enabled: self.deployment_custom_domain_check_enabled,
             deployment_termination_grace_period_seconds: self.deployment_termination_grace_period_seconds,
             deployment_update_strategy_type: self.deployment_update_strategy_type,
@@ -213,7 +297,16 @@ impl ApplicationAdvancedSettings {
                 .deployment_update_strategy_rolling_update_max_unavailable_percent,
             deployment_update_strategy_rolling_update_max_surge_percent: self
                 .deployment_update_strategy_rolling_update_max_surge_percent,
+            deployment_update_strategy_canary_traffic_percent: self.deployment_update_strategy_canary_traffic_percent,
+            deployment_update_strategy_canary_analysis_duration_seconds: self
+                .deployment_update_strategy_canary_analysis_duration_seconds,
+            deployment_update_strategy_canary_max_container_restarts: self
+                .deployment_update_strategy_canary_max_container_restarts,
+            deployment_update_strategy_canary_max_error_rate_percent: self
+                .deployment_update_strategy_canary_max_error_rate_percent,
             deployment_affinity_node_required: self.deployment_affinity_node_required.clone(),
+            deployment_node_selector: self.deployment_node_selector.clone(),
+            deployment_tolerations: self.deployment_tolerations.clone(),
             deployment_antiaffinity_pod: self.deployment_antiaffinity_pod.clone(),
             network_ingress_proxy_body_size_mb: self.network_ingress_proxy_body_size_mb,
             network_ingress_cors_enable: self.network_ingress_cors_enable,
@@ -234,9 +327,23 @@ impl ApplicationAdvancedSettings {
             network_ingress_whitelist_source_range: self.network_ingress_whitelist_source_range.clone(),
             network_ingress_denylist_source_range: self.network_ingress_denylist_source_range.clone(),
             network_ingress_basic_auth_env_var: self.network_ingress_basic_auth_env_var.clone(),
+            network_ingress_limit_rps: self.network_ingress_limit_rps,
+            network_ingress_limit_connections: self.network_ingress_limit_connections,
+            network_ingress_limit_burst_multiplier: self.network_ingress_limit_burst_multiplier,
+            network_ingress_modsecurity_profile: self.network_ingress_modsecurity_profile,
+            network_ingress_auth_url: self.network_ingress_auth_url.clone(),
+            network_ingress_auth_signin: self.network_ingress_auth_signin.clone(),
+            network_ingress_auth_response_headers: self.network_ingress_auth_response_headers.clone(),
             network_ingress_grpc_send_timeout_seconds: self.network_ingress_grpc_send_timeout_seconds,
             network_ingress_grpc_read_timeout_seconds: self.network_ingress_grpc_read_timeout_seconds,
             hpa_cpu_average_utilization_percent: self.hpa_cpu_average_utilization_percent,
+            hpa_memory_average_utilization_percent: self.hpa_memory_average_utilization_percent,
+            hpa_requests_per_second_average: self.hpa_requests_per_second_average,
+            hpa_custom_metrics: self.hpa_custom_metrics.clone(),
+            hpa_scale_up_stabilization_window_seconds: self.hpa_scale_up_stabilization_window_seconds,
+            hpa_scale_up_max_percent: self.hpa_scale_up_max_percent,
+            hpa_scale_down_stabilization_window_seconds: self.hpa_scale_down_stabilization_window_seconds,
+            hpa_scale_down_max_percent: self.hpa_scale_down_max_percent,
         }
     }
 }
@@ -255,6 +362,8 @@ pub struct Application {
     pub command_args: Vec<String>,
     pub entrypoint: Option<String>,
     pub buildpack_language: Option<String>,
+    #[serde(default)]
+    pub buildpacks: BuildpacksSettings,
     #[serde(default = "default_root_path_value")]
     pub root_path: String,
     pub public_domain: String,
@@ -275,6 +384,8 @@ pub struct Application {
     pub liveness_probe: Option<Probe>,
     #[serde(default)]
     pub advanced_settings: ApplicationAdvancedSettings,
+    #[serde(default)]
+    pub depends_on: Vec<Uuid>,
 }
 
 fn default_root_path_value() -> String {
@@ -288,7 +399,10 @@ impl Application {
         build: Build,
         cloud_provider: &dyn CloudProvider,
     ) -> Result<Box<dyn ApplicationService>, ApplicationError> {
-        let environment_variables = to_environment_variable(self.environment_vars_with_infos);
+        let mut environment_variables = to_environment_variable(self.environment_vars_with_infos);
+        if build.use_buildpacks() {
+            environment_variables.extend(buildpacks_port_variable(&self.ports, &environment_variables));
+        }
 
         match cloud_provider.kind() {
             CPKind::Aws => {
@@ -411,6 +525,34 @@ impl Application {
                 GcpAppExtraSettings {},
                 |transmitter| context.get_event_details(transmitter),
             )?)),
+            CPKind::Azure => Ok(Box::new(models::application::Application::<AZURE>::new(
+                context,
+                self.long_id,
+                self.action.to_service_action(),
+                self.name.as_str(),
+                self.kube_name,
+                self.public_domain,
+                self.ports,
+                self.total_cpus,
+                self.cpu_burst,
+                self.total_ram_in_mib,
+                self.min_instances,
+                self.max_instances,
+                build,
+                self.command_args,
+                self.entrypoint,
+                self.storage.iter().map(|s| s.to_azure_storage()).collect::<Vec<_>>(),
+                environment_variables,
+                self.mounted_files
+                    .iter()
+                    .map(|e| e.to_domain())
+                    .collect::<BTreeSet<_>>(),
+                self.readiness_probe.map(|p| p.to_domain()),
+                self.liveness_probe.map(|p| p.to_domain()),
+                self.advanced_settings,
+                AzureAppExtraSettings {},
+                |transmitter| context.get_event_details(transmitter),
+            )?)),
             CPKind::SelfManaged => Ok(Box::new(models::application::Application::<SelfManaged>::new(
                 context,
                 self.long_id,
@@ -487,6 +629,7 @@ impl Application {
                 dockerfile_path,
                 root_path,
                 buildpack_language: self.buildpack_language.clone(),
+                buildpacks: self.buildpacks.clone(),
             },
             image: self.to_image(registry_url),
             environment_variables: self
@@ -513,6 +656,7 @@ impl Application {
             architectures,
             max_cpu_in_milli: self.advanced_settings.build_cpu_max_in_milli,
             max_ram_in_gib: self.advanced_settings.build_ram_max_in_gib,
+            vulnerability_allowlist: self.advanced_settings.security_vulnerability_allowlist.clone(),
         };
 
         build.compute_image_tag();
@@ -529,6 +673,9 @@ pub struct Storage {
     pub size_in_gib: u32,
     pub mount_point: String,
     pub snapshot_retention_in_days: u16,
+    /// Volume snapshot of this storage its volume is recreated from during the deployment
+    #[serde(default)]
+    pub restore_from_snapshot: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
@@ -555,6 +702,7 @@ impl Storage {
             size_in_gib: self.size_in_gib,
             mount_point: self.mount_point.clone(),
             snapshot_retention_in_days: self.snapshot_retention_in_days,
+            restore_from_snapshot: self.restore_from_snapshot.clone(),
         }
     }
 
@@ -572,6 +720,7 @@ impl Storage {
             size_in_gib: self.size_in_gib,
             mount_point: self.mount_point.clone(),
             snapshot_retention_in_days: self.snapshot_retention_in_days,
+            restore_from_snapshot: self.restore_from_snapshot.clone(),
         }
     }
 
@@ -584,6 +733,7 @@ impl Storage {
             size_in_gib: self.size_in_gib,
             mount_point: self.mount_point.clone(),
             snapshot_retention_in_days: self.snapshot_retention_in_days,
+            restore_from_snapshot: self.restore_from_snapshot.clone(),
         }
     }
 
@@ -602,6 +752,24 @@ impl Storage {
             size_in_gib: self.size_in_gib,
             mount_point: self.mount_point.clone(),
             snapshot_retention_in_days: self.snapshot_retention_in_days,
+            restore_from_snapshot: self.restore_from_snapshot.clone(),
+        }
+    }
+
+    pub fn to_azure_storage(&self) -> crate::cloud_provider::models::Storage<AzureStorageType> {
+        crate::cloud_provider::models::Storage {
+            id: self.id.clone(),
+            long_id: self.long_id,
+            name: self.name.clone(),
+            storage_type: match self.storage_type {
+                StorageType::SlowHdd | StorageType::Hdd => AzureStorageType::StandardHDD,
+                StorageType::Ssd => AzureStorageType::StandardSSD,
+                StorageType::FastSsd => AzureStorageType::PremiumSSD,
+            },
+            size_in_gib: self.size_in_gib,
+            mount_point: self.mount_point.clone(),
+            snapshot_retention_in_days: self.snapshot_retention_in_days,
+            restore_from_snapshot: self.restore_from_snapshot.clone(),
         }
     }
 }
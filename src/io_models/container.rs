@@ -10,12 +10,13 @@ use crate::io_models::{Action, MountedFile};
 use crate::models;
 use crate::models::aws::AwsAppExtraSettings;
 use crate::models::aws_ec2::AwsEc2AppExtraSettings;
+use crate::models::azure::AzureAppExtraSettings;
 use crate::models::container::{ContainerError, ContainerService};
 use crate::models::gcp::GcpAppExtraSettings;
 use crate::models::registry_image_source::RegistryImageSource;
 use crate::models::scaleway::ScwAppExtraSettings;
 use crate::models::selfmanaged::SelfManagedAppExtraSettings;
-use crate::models::types::{AWSEc2, SelfManaged, AWS, GCP, SCW};
+use crate::models::types::{AWSEc2, SelfManaged, AWS, AZURE, GCP, SCW};
 use rusoto_core::{Client, HttpClient, Region};
 use rusoto_credential::StaticProvider;
 use rusoto_ecr::EcrClient;
@@ -25,7 +26,7 @@ use std::str::FromStr;
 use url::Url;
 use uuid::Uuid;
 
-use super::{PodAntiAffinity, UpdateStrategy};
+use super::{HpaCustomMetric, ModSecurityProfile, PodAntiAffinity, PodToleration, UpdateStrategy};
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
 pub struct Credentials {
@@ -210,6 +211,8 @@ pub struct ContainerAdvancedSettings {
     pub security_service_account_name: String,
     #[serde(alias = "security.read_only_root_filesystem")]
     pub security_read_only_root_filesystem: bool,
+    #[serde(alias = "security.vulnerability_allowlist")]
+    pub security_vulnerability_allowlist: Vec<String>,
 
     // Deployment
     #[serde(alias = "deployment.custom_domain_check_enabled")]
@@ -222,8 +225,21 @@ pub struct ContainerAdvancedSettings {
     pub deployment_update_strategy_rolling_update_max_unavailable_percent: u32,
     #[serde(alias = "deployment.update_strategy.rolling_update.max_surge_percent")]
     pub deployment_update_strategy_rolling_update_max_surge_percent: u32,
+    #[serde(alias = "deployment.update_strategy.canary.traffic_percent")]
+    pub deployment_update_strategy_canary_traffic_percent: u32,
+    #[serde(alias = "deployment.update_strategy.canary.analysis_duration_seconds")]
+    pub deployment_update_strategy_canary_analysis_duration_seconds: u32,
+    #[serde(alias = "deployment.update_strategy.canary.max_container_restarts")]
+    pub deployment_update_strategy_canary_max_container_restarts: u32,
+    // share of the canary requests answered with a 5xx above which the canary is aborted
+    #[serde(alias = "deployment.update_strategy.canary.max_error_rate_percent")]
+    pub deployment_update_strategy_canary_max_error_rate_percent: u32,
     #[serde(alias = "deployment.affinity.node.required")]
     pub deployment_affinity_node_required: BTreeMap<String, String>,
+    #[serde(alias = "deployment.node_selector")]
+    pub deployment_node_selector: BTreeMap<String, String>,
+    #[serde(alias = "deployment.tolerations")]
+    pub deployment_tolerations: Vec<PodToleration>,
     #[serde(alias = "deployment.antiaffinity.pod")]
     pub deployment_antiaffinity_pod: PodAntiAffinity,
 
@@ -266,6 +282,20 @@ pub struct ContainerAdvancedSettings {
     pub network_ingress_denylist_source_range: String,
     #[serde(alias = "network.ingress.basic_auth_env_var")]
     pub network_ingress_basic_auth_env_var: String,
+    #[serde(alias = "network.ingress.limit_rps")]
+    pub network_ingress_limit_rps: Option<u32>,
+    #[serde(alias = "network.ingress.limit_connections")]
+    pub network_ingress_limit_connections: Option<u32>,
+    #[serde(alias = "network.ingress.limit_burst_multiplier")]
+    pub network_ingress_limit_burst_multiplier: u32,
+    #[serde(alias = "network.ingress.modsecurity_profile")]
+    pub network_ingress_modsecurity_profile: Option<ModSecurityProfile>,
+    #[serde(alias = "network.ingress.auth_url")]
+    pub network_ingress_auth_url: String,
+    #[serde(alias = "network.ingress.auth_signin")]
+    pub network_ingress_auth_signin: String,
+    #[serde(alias = "network.ingress.auth_response_headers")]
+    pub network_ingress_auth_response_headers: String,
 
     #[serde(alias = "network.ingress.grpc_send_timeout_seconds")]
     pub network_ingress_grpc_send_timeout_seconds: u32,
@@ -275,6 +305,20 @@ pub struct ContainerAdvancedSettings {
     // Pod autoscaler
     #[serde(alias = "hpa.cpu.average_utilization_percent")]
     pub hpa_cpu_average_utilization_percent: u8,
+    #[serde(alias = "hpa.memory.average_utilization_percent")]
+    pub hpa_memory_average_utilization_percent: Option<u8>,
+    #[serde(alias = "hpa.requests_per_second.average")]
+    pub hpa_requests_per_second_average: Option<u32>,
+    #[serde(alias = "hpa.custom_metrics")]
+    pub hpa_custom_metrics: Vec<HpaCustomMetric>,
+    #[serde(alias = "hpa.scale_up.stabilization_window_seconds")]
+    pub hpa_scale_up_stabilization_window_seconds: u32,
+    #[serde(alias = "hpa.scale_up.max_percent")]
+    pub hpa_scale_up_max_percent: u32,
+    #[serde(alias = "hpa.scale_down.stabilization_window_seconds")]
+    pub hpa_scale_down_stabilization_window_seconds: u32,
+    #[serde(alias = "hpa.scale_down.max_percent")]
+    pub hpa_scale_down_max_percent: u32,
 }
 
 impl Default for ContainerAdvancedSettings {
@@ -282,12 +326,19 @@ impl Default for ContainerAdvancedSettings {
         ContainerAdvancedSettings {
             security_service_account_name: "".to_string(),
             security_read_only_root_filesystem: false,
+            security_vulnerability_allowlist: vec![],
             deployment_termination_grace_period_seconds: 60,
             deployment_custom_domain_check_enabled: true,
             deployment_update_strategy_type: UpdateStrategy::RollingUpdate,
             deployment_update_strategy_rolling_update_max_unavailable_percent: 25,
             deployment_update_strategy_rolling_update_max_surge_percent: 25,
+            deployment_update_strategy_canary_traffic_percent: 10,
+            deployment_update_strategy_canary_analysis_duration_seconds: 5 * 60,
+            deployment_update_strategy_canary_max_container_restarts: 0,
+            deployment_update_strategy_canary_max_error_rate_percent: 5,
             deployment_affinity_node_required: BTreeMap::new(),
+            deployment_node_selector: BTreeMap::new(),
+            deployment_tolerations: vec![],
             deployment_antiaffinity_pod: PodAntiAffinity::Preferred,
             network_ingress_proxy_body_size_mb: 100,
             network_ingress_cors_enable: false,
@@ -308,9 +359,23 @@ impl Default for ContainerAdvancedSettings {
             network_ingress_whitelist_source_range: "0.0.0.0/0".to_string(),
             network_ingress_denylist_source_range: "".to_string(),
             network_ingress_basic_auth_env_var: "".to_string(),
+            network_ingress_limit_rps: None,
+            network_ingress_limit_connections: None,
+            network_ingress_limit_burst_multiplier: 5,
+            network_ingress_modsecurity_profile: None,
+            network_ingress_auth_url: "".to_string(),
+            network_ingress_auth_signin: "".to_string(),
+            network_ingress_auth_response_headers: "".to_string(),
             network_ingress_grpc_send_timeout_seconds: 60,
             network_ingress_grpc_read_timeout_seconds: 60,
             hpa_cpu_average_utilization_percent: 60,
+            hpa_memory_average_utilization_percent: None,
+            hpa_requests_per_second_average: None,
+            hpa_custom_metrics: vec![],
+            hpa_scale_up_stabilization_window_seconds: 0,
+            hpa_scale_up_max_percent: 100,
+            hpa_scale_down_stabilization_window_seconds: 300,
+            hpa_scale_down_max_percent: 100,
         }
     }
 }
@@ -345,6 +410,8 @@ pub struct Container {
     pub liveness_probe: Option<Probe>,
     #[serde(default)]
     pub advanced_settings: ContainerAdvancedSettings,
+    #[serde(default)]
+    pub depends_on: Vec<Uuid>,
 }
 
 impl Container {
@@ -492,6 +559,35 @@ impl Container {
                 GcpAppExtraSettings {},
                 |transmitter| context.get_event_details(transmitter),
             )?),
+            CPKind::Azure => Box::new(models::container::Container::<AZURE>::new(
+                context,
+                self.long_id,
+                self.name,
+                self.kube_name,
+                self.action.to_service_action(),
+                image_source,
+                self.command_args,
+                self.entrypoint,
+                self.cpu_request_in_mili,
+                self.cpu_limit_in_mili,
+                self.ram_request_in_mib,
+                self.ram_limit_in_mib,
+                self.min_instances,
+                self.max_instances,
+                self.public_domain,
+                self.ports,
+                self.storages.iter().map(|s| s.to_azure_storage()).collect::<Vec<_>>(),
+                environment_variables,
+                self.mounted_files
+                    .iter()
+                    .map(|e| e.to_domain())
+                    .collect::<BTreeSet<_>>(),
+                self.readiness_probe.map(|p| p.to_domain()),
+                self.liveness_probe.map(|p| p.to_domain()),
+                self.advanced_settings,
+                AzureAppExtraSettings {},
+                |transmitter| context.get_event_details(transmitter),
+            )?),
             CPKind::SelfManaged => Box::new(models::container::Container::<SelfManaged>::new(
                 context,
                 self.long_id,
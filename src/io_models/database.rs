@@ -1,4 +1,5 @@
 use crate::cloud_provider::aws::database_instance_type::AwsDatabaseInstanceType;
+use crate::cloud_provider::gcp::database_instance_type::GcpDatabaseInstanceType;
 use crate::cloud_provider::kubernetes::Kind as KubernetesKind;
 use crate::cloud_provider::scaleway::database_instance_type::ScwDatabaseInstanceType;
 use crate::cloud_provider::{service, CloudProvider, Kind as CPKind, Kind};
@@ -9,10 +10,11 @@ use crate::models::database::{
     Container, DatabaseError, DatabaseInstanceType, DatabaseService, Managed, MongoDB, MySQL, PostgresSQL, Redis,
 };
 use crate::models::types::{AWSEc2, VersionsNumber, AWS, SCW};
-use crate::models::types::{CloudProvider as CloudProviderTrait, GCP};
+use crate::models::types::{CloudProvider as CloudProviderTrait, AZURE, GCP};
 use chrono::{DateTime, Utc};
 use core::result::Result;
 use core::result::Result::{Err, Ok};
+use derivative::Derivative;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use uuid::Uuid;
@@ -49,6 +51,13 @@ pub struct Database {
     pub activate_backups: bool,
     pub publicly_accessible: bool,
     pub mode: DatabaseMode,
+    #[serde(default)]
+    pub depends_on: Vec<Uuid>,
+    #[serde(default)] // => no backups if not present in input
+    pub backup: Option<DatabaseBackup>,
+    /// Backup to restore with the `RESTORE` action, the latest one if not set
+    #[serde(default)]
+    pub restore: Option<DatabaseRestore>,
 }
 
 impl Database {
@@ -69,8 +78,25 @@ impl Database {
             activate_high_availability: self.activate_high_availability,
             activate_backups: self.activate_backups,
             publicly_accessible: self.publicly_accessible,
+            backup: self.backup.clone(),
+            restore: match self.action {
+                Action::Restore => Some(self.restore.clone().unwrap_or_default()),
+                _ => None,
+            },
         };
 
+        // Managed databases backups are handled by the cloud provider with `activate_backups`
+        if self.mode == DatabaseMode::MANAGED && (self.backup.is_some() || self.action == Action::Restore) {
+            return Err(DatabaseError::InvalidConfig(
+                "Backup and restore are only available for container databases".to_string(),
+            ));
+        }
+        if self.action == Action::Restore && self.backup.is_none() {
+            return Err(DatabaseError::InvalidConfig(
+                "Cannot restore a database without a backup storage".to_string(),
+            ));
+        }
+
         let version = VersionsNumber::from_str(self.version.as_str())
             .map_err(|_| DatabaseError::InvalidConfig(format!("Bad version number: {}", self.version)))?;
 
@@ -87,7 +113,21 @@ impl Database {
                     Ok(t) => Some(Box::new(t)),
                     Err(e) => return Err(e),
                 },
-                Kind::Gcp => todo!(), // TODO(benjaminch): GKE integration
+                Kind::Gcp => match GcpDatabaseInstanceType::from_str(database_instance_type_raw_str) {
+                    // Cloud SQL tiers and Memorystore capacities can't be used interchangeably
+                    Ok(t)
+                        if self.mode == DatabaseMode::MANAGED
+                            && !t.is_instance_compatible_with(self.kind.database_type()) =>
+                    {
+                        return Err(DatabaseError::DatabaseInstanceTypeMismatchDatabaseType {
+                            database_instance_type_str: t.to_cloud_provider_format(),
+                            database_type: self.kind.database_type(),
+                        })
+                    }
+                    Ok(t) => Some(Box::new(t)),
+                    Err(e) => return Err(e),
+                },
+                Kind::Azure => None, // Managed databases are not supported on Azure yet
                 Kind::SelfManaged => None,
             },
         };
@@ -726,21 +766,196 @@ impl Database {
 
                 Ok(Box::new(db))
             }
-            (CPKind::Gcp, DatabaseKind::Mysql, DatabaseMode::MANAGED) => Err(DatabaseError::UnsupportedManagedMode(
-                service::DatabaseType::MySQL,
-                GCP::full_name().to_string(),
-            )),
-            (CPKind::Gcp, DatabaseKind::Postgresql, DatabaseMode::MANAGED) => Err(
-                DatabaseError::UnsupportedManagedMode(service::DatabaseType::PostgreSQL, GCP::full_name().to_string()),
-            ),
-            (CPKind::Gcp, DatabaseKind::Redis, DatabaseMode::MANAGED) => Err(DatabaseError::UnsupportedManagedMode(
-                service::DatabaseType::Redis,
-                GCP::full_name().to_string(),
-            )),
+            (CPKind::Gcp, DatabaseKind::Postgresql, DatabaseMode::MANAGED) => {
+                let db = models::database::Database::<GCP, Managed, PostgresSQL>::new(
+                    context,
+                    self.long_id,
+                    self.action.to_service_action(),
+                    self.name.as_str(),
+                    self.kube_name.clone(),
+                    version,
+                    self.created_at,
+                    self.fqdn.as_str(),
+                    self.fqdn_id.as_str(),
+                    self.total_cpus.clone(),
+                    self.total_ram_in_mib,
+                    database_options.disk_size_in_gib,
+                    database_instance_type,
+                    database_options.publicly_accessible,
+                    database_options.port,
+                    database_options,
+                    |transmitter| context.get_event_details(transmitter),
+                )?;
+
+                Ok(Box::new(db))
+            }
+            (CPKind::Gcp, DatabaseKind::Mysql, DatabaseMode::MANAGED) => {
+                let db = models::database::Database::<GCP, Managed, MySQL>::new(
+                    context,
+                    self.long_id,
+                    self.action.to_service_action(),
+                    self.name.as_str(),
+                    self.kube_name.clone(),
+                    version,
+                    self.created_at,
+                    self.fqdn.as_str(),
+                    self.fqdn_id.as_str(),
+                    self.total_cpus.clone(),
+                    self.total_ram_in_mib,
+                    database_options.disk_size_in_gib,
+                    database_instance_type,
+                    database_options.publicly_accessible,
+                    database_options.port,
+                    database_options,
+                    |transmitter| context.get_event_details(transmitter),
+                )?;
+
+                Ok(Box::new(db))
+            }
+            (CPKind::Gcp, DatabaseKind::Redis, DatabaseMode::MANAGED) => {
+                // Memorystore instances only have a private IP in the cluster VPC
+                if database_options.publicly_accessible {
+                    return Err(DatabaseError::InvalidConfig(
+                        "Managed Redis cannot be publicly accessible on GCP".to_string(),
+                    ));
+                }
+
+                let db = models::database::Database::<GCP, Managed, Redis>::new(
+                    context,
+                    self.long_id,
+                    self.action.to_service_action(),
+                    self.name.as_str(),
+                    self.kube_name.clone(),
+                    version,
+                    self.created_at,
+                    self.fqdn.as_str(),
+                    self.fqdn_id.as_str(),
+                    self.total_cpus.clone(),
+                    self.total_ram_in_mib,
+                    database_options.disk_size_in_gib,
+                    database_instance_type,
+                    database_options.publicly_accessible,
+                    database_options.port,
+                    database_options,
+                    |transmitter| context.get_event_details(transmitter),
+                )?;
+
+                Ok(Box::new(db))
+            }
             (CPKind::Gcp, DatabaseKind::Mongodb, DatabaseMode::MANAGED) => Err(DatabaseError::UnsupportedManagedMode(
                 service::DatabaseType::MongoDB,
                 GCP::full_name().to_string(),
             )),
+
+            (CPKind::Azure, DatabaseKind::Postgresql, DatabaseMode::CONTAINER) => {
+                let db = models::database::Database::<AZURE, Container, PostgresSQL>::new(
+                    context,
+                    self.long_id,
+                    self.action.to_service_action(),
+                    self.name.as_str(),
+                    self.kube_name.clone(),
+                    version,
+                    self.created_at,
+                    self.fqdn.as_str(),
+                    self.fqdn_id.as_str(),
+                    self.total_cpus.clone(),
+                    self.total_ram_in_mib,
+                    database_options.disk_size_in_gib,
+                    None,
+                    database_options.publicly_accessible,
+                    database_options.port,
+                    database_options,
+                    |transmitter| context.get_event_details(transmitter),
+                )?;
+
+                Ok(Box::new(db))
+            }
+            (CPKind::Azure, DatabaseKind::Mysql, DatabaseMode::CONTAINER) => {
+                let db = models::database::Database::<AZURE, Container, MySQL>::new(
+                    context,
+                    self.long_id,
+                    self.action.to_service_action(),
+                    self.name.as_str(),
+                    self.kube_name.clone(),
+                    version,
+                    self.created_at,
+                    self.fqdn.as_str(),
+                    self.fqdn_id.as_str(),
+                    self.total_cpus.clone(),
+                    self.total_ram_in_mib,
+                    database_options.disk_size_in_gib,
+                    None,
+                    database_options.publicly_accessible,
+                    database_options.port,
+                    database_options,
+                    |transmitter| context.get_event_details(transmitter),
+                )?;
+
+                Ok(Box::new(db))
+            }
+            (CPKind::Azure, DatabaseKind::Redis, DatabaseMode::CONTAINER) => {
+                let db = models::database::Database::<AZURE, Container, Redis>::new(
+                    context,
+                    self.long_id,
+                    self.action.to_service_action(),
+                    self.name.as_str(),
+                    self.kube_name.clone(),
+                    version,
+                    self.created_at,
+                    self.fqdn.as_str(),
+                    self.fqdn_id.as_str(),
+                    self.total_cpus.clone(),
+                    self.total_ram_in_mib,
+                    database_options.disk_size_in_gib,
+                    None,
+                    database_options.publicly_accessible,
+                    database_options.port,
+                    database_options,
+                    |transmitter| context.get_event_details(transmitter),
+                )?;
+
+                Ok(Box::new(db))
+            }
+            (CPKind::Azure, DatabaseKind::Mongodb, DatabaseMode::CONTAINER) => {
+                let db = models::database::Database::<AZURE, Container, MongoDB>::new(
+                    context,
+                    self.long_id,
+                    self.action.to_service_action(),
+                    self.name.as_str(),
+                    self.kube_name.clone(),
+                    version,
+                    self.created_at,
+                    self.fqdn.as_str(),
+                    self.fqdn_id.as_str(),
+                    self.total_cpus.clone(),
+                    self.total_ram_in_mib,
+                    database_options.disk_size_in_gib,
+                    None,
+                    database_options.publicly_accessible,
+                    database_options.port,
+                    database_options,
+                    |transmitter| context.get_event_details(transmitter),
+                )?;
+
+                Ok(Box::new(db))
+            }
+            (CPKind::Azure, DatabaseKind::Mysql, DatabaseMode::MANAGED) => Err(DatabaseError::UnsupportedManagedMode(
+                service::DatabaseType::MySQL,
+                AZURE::full_name().to_string(),
+            )),
+            (CPKind::Azure, DatabaseKind::Postgresql, DatabaseMode::MANAGED) => {
+                Err(DatabaseError::UnsupportedManagedMode(
+                    service::DatabaseType::PostgreSQL,
+                    AZURE::full_name().to_string(),
+                ))
+            }
+            (CPKind::Azure, DatabaseKind::Redis, DatabaseMode::MANAGED) => Err(DatabaseError::UnsupportedManagedMode(
+                service::DatabaseType::Redis,
+                AZURE::full_name().to_string(),
+            )),
+            (CPKind::Azure, DatabaseKind::Mongodb, DatabaseMode::MANAGED) => Err(
+                DatabaseError::UnsupportedManagedMode(service::DatabaseType::MongoDB, AZURE::full_name().to_string()),
+            ),
         }
     }
 }
@@ -763,6 +978,15 @@ impl DatabaseKind {
             DatabaseKind::Redis => "redis",
         }
     }
+
+    pub fn database_type(&self) -> service::DatabaseType {
+        match self {
+            DatabaseKind::Mongodb => service::DatabaseType::MongoDB,
+            DatabaseKind::Mysql => service::DatabaseType::MySQL,
+            DatabaseKind::Postgresql => service::DatabaseType::PostgreSQL,
+            DatabaseKind::Redis => service::DatabaseType::Redis,
+        }
+    }
 }
 
 #[derive(Eq, PartialEq)]
@@ -778,4 +1002,40 @@ pub struct DatabaseOptions {
     pub activate_high_availability: bool,
     pub activate_backups: bool,
     pub publicly_accessible: bool,
+    pub backup: Option<DatabaseBackup>,
+    pub restore: Option<DatabaseRestore>,
+}
+
+/// Scheduled backups of a container database, uploaded to an S3 compatible object storage
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct DatabaseBackup {
+    /// Cron expression, i.e: `0 3 * * *`
+    pub schedule: String,
+    /// Older backups are removed from the object storage by the backup job, once the new backup is uploaded
+    pub retention_in_days: u32,
+    pub storage: DatabaseBackupStorage,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Derivative)]
+#[derivative(Debug)]
+pub struct DatabaseBackupStorage {
+    /// Endpoint of an S3 compatible storage (i.e: MinIO), AWS S3 is used if not set
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// AWS region, or any region accepted by the S3 compatible storage
+    pub region: String,
+    pub bucket_name: String,
+    pub access_key_id: String,
+    #[derivative(Debug = "ignore")]
+    pub secret_access_key: String,
+}
+
+/// Backup to restore into the database with the `RESTORE` action.
+/// If no backup key is set, the latest backup taken before `point_in_time` (or the latest one) is restored.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct DatabaseRestore {
+    #[serde(default)]
+    pub backup_key: Option<String>,
+    #[serde(default)]
+    pub point_in_time: Option<DateTime<Utc>>,
 }
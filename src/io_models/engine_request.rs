@@ -2,10 +2,14 @@ use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
+use crate::build_platform::kubernetes_pod::KubernetesPod;
 use crate::build_platform::local_docker::LocalDocker;
 use crate::cloud_provider::aws::kubernetes::{ec2::EC2, eks::EKS};
 use crate::cloud_provider::aws::regions::AwsRegion;
 use crate::cloud_provider::aws::AWS;
+use crate::cloud_provider::azure::kubernetes::{azure_container_registry_name, Aks};
+use crate::cloud_provider::azure::locations::AzureLocation;
+use crate::cloud_provider::azure::Azure;
 use crate::cloud_provider::gcp::kubernetes::Gke;
 use crate::cloud_provider::gcp::locations::GcpRegion;
 use crate::cloud_provider::gcp::Google;
@@ -15,12 +19,16 @@ use crate::cloud_provider::models::NodeGroups;
 use crate::cloud_provider::scaleway::kubernetes::Kapsule;
 use crate::cloud_provider::scaleway::Scaleway;
 use crate::cloud_provider::self_managed::SelfManaged;
+use crate::container_registry::azure_container_registry::AzureContainerRegistry;
 use crate::container_registry::ecr::ECR;
+use crate::container_registry::generic_cr::{GenericCr, GenericCrFlavor};
 use crate::container_registry::google_artifact_registry::GoogleArtifactRegistry;
 use crate::container_registry::scaleway_container_registry::ScalewayCR;
 use crate::dns_provider::cloudflare::Cloudflare;
 use crate::dns_provider::io::Kind;
 use crate::dns_provider::qoverydns::QoveryDns;
+use crate::dns_provider::rfc2136::{Rfc2136Dns, TsigAlgorithm};
+use crate::dns_provider::route53::Route53Dns;
 use crate::engine::InfrastructureContext;
 use crate::errors::{CommandError, EngineError as IoEngineError, EngineError};
 use crate::events::{EventDetails, InfrastructureStep, Stage, Transmitter};
@@ -29,6 +37,7 @@ use crate::io_models::environment::EnvironmentRequest;
 use crate::io_models::{Action, QoveryIdentifier};
 use crate::logger::Logger;
 use crate::metrics_registry::MetricsRegistry;
+use crate::models::azure::AzureCredentials;
 use crate::models::domain::Domain;
 use crate::models::gcp::io::JsonCredentials as JsonCredentialsIo;
 use crate::models::gcp::JsonCredentials;
@@ -65,6 +74,13 @@ pub struct EngineRequest<T> {
     pub target_environment: T,
     pub metadata: Option<Metadata>,
     pub archive: Option<Archive>,
+    /// Only compute and report what an environment deployment would change, nothing is applied
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Only resolve again the external secrets to refresh of the deployed environment, and restart the pods of the
+    /// services whose secrets changed. Requested periodically for the environments having secrets to refresh.
+    #[serde(default)]
+    pub refresh_external_secrets: bool,
 }
 
 impl<T> EngineRequest<T> {
@@ -122,9 +138,15 @@ impl<T> EngineRequest<T> {
             .flat_map(|v| v.as_str())
             .collect();
 
+        let dns_domain = Domain::try_new(self.dns_provider.domain.to_string()).map_err(|e| {
+            IoEngineError::new_error_on_dns_provider_information(
+                event_details.clone(),
+                CommandError::new_from_safe_message(format!("Invalid DNS provider domain: {e}")),
+            )
+        })?;
         let dns_provider = self
             .dns_provider
-            .to_engine_dns_provider(context.clone(), cluster_jwt_token)
+            .to_engine_dns_provider(context.clone(), dns_domain, cluster_jwt_token)
             .ok_or_else(|| {
                 IoEngineError::new_error_on_dns_provider_information(
                     event_details,
@@ -170,6 +192,7 @@ impl InfrastructureEngineRequest {
             Action::Pause => Stage::Infrastructure(InfrastructureStep::Pause),
             Action::Delete => Stage::Infrastructure(InfrastructureStep::Delete),
             Action::Restart => Stage::Infrastructure(InfrastructureStep::Restart),
+            Action::Restore => Stage::Infrastructure(InfrastructureStep::ValidateApiInput),
         };
 
         EventDetails::new(
@@ -198,6 +221,9 @@ impl EnvironmentEngineRequest {
     }
 }
 
+/// Namespace build pods are run into, when not set in the build platform options
+const DEFAULT_BUILD_NAMESPACE: &str = "qovery";
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct BuildPlatform {
     pub kind: build_platform::Kind,
@@ -209,12 +235,24 @@ pub struct BuildPlatform {
 
 impl BuildPlatform {
     pub fn to_engine_build_platform(&self, context: &Context) -> Box<dyn build_platform::BuildPlatform> {
-        Box::new(match self.kind {
+        // FIXME: Remove the unwraps by propagating errors above
+        match self.kind {
             build_platform::Kind::LocalDocker => {
-                // FIXME: Remove the unwrap by propagating errors above
-                LocalDocker::new(context.clone(), self.long_id, self.name.as_str()).unwrap()
+                Box::new(LocalDocker::new(context.clone(), self.long_id, self.name.as_str()).unwrap())
             }
-        })
+            build_platform::Kind::KubernetesPod => Box::new(
+                KubernetesPod::new(
+                    context.clone(),
+                    self.long_id,
+                    self.name.as_str(),
+                    self.options
+                        .build_namespace
+                        .as_deref()
+                        .unwrap_or(DEFAULT_BUILD_NAMESPACE),
+                )
+                .unwrap(),
+            ),
+        }
     }
 }
 
@@ -285,6 +323,20 @@ impl CloudProvider {
                     terraform_state_credentials,
                 )))
             }
+            cloud_provider::Kind::Azure => {
+                let location = match AzureLocation::from_str(region) {
+                    Ok(l) => l,
+                    Err(_e) => return None,
+                };
+                Some(Box::new(Azure::new(
+                    context,
+                    self.long_id,
+                    self.name.as_str(),
+                    self.options.azure_credentials()?,
+                    location,
+                    terraform_state_credentials,
+                )))
+            }
             cloud_provider::Kind::SelfManaged => Some(Box::new(SelfManaged::new(
                 context,
                 self.clone().long_id,
@@ -475,6 +527,40 @@ impl Kubernetes {
                     Err(e) => Err(e),
                 }
             }
+            cloud_provider::kubernetes::Kind::Aks => {
+                let options =
+                    serde_json::from_value::<cloud_provider::azure::kubernetes::io::AksOptions>(self.options.clone())
+                        .expect("What's wronnnnng -- JSON Options payload for Azure is not the expected one")
+                        .try_into()
+                        .map_err(|e: String| {
+                            Box::new(EngineError::new_invalid_engine_payload(event_details.clone(), e.as_str(), None))
+                        })?;
+                match Aks::new(
+                    context.clone(),
+                    &self.id,
+                    self.long_id,
+                    &self.name,
+                    KubernetesVersion::from_str(&self.version)
+                        .unwrap_or_else(|_| panic!("Kubernetes version `{}` is not supported", &self.version)),
+                    AzureLocation::from_str(self.region.as_str()).unwrap_or_else(|_| {
+                        panic!(
+                            "cannot parse `{}`, it doesn't seem to be a valid Azure location",
+                            self.region.as_str()
+                        )
+                    }),
+                    cloud_provider,
+                    dns_provider,
+                    options,
+                    self.nodes_groups.clone(),
+                    logger,
+                    metrics_registry,
+                    self.advanced_settings.clone(),
+                    decoded_helm_charts_override,
+                ) {
+                    Ok(res) => Ok(Box::new(res)),
+                    Err(e) => Err(e),
+                }
+            }
             cloud_provider::kubernetes::Kind::EksSelfManaged
             | cloud_provider::kubernetes::Kind::GkeSelfManaged
             | cloud_provider::kubernetes::Kind::ScwSelfManaged => {
@@ -587,6 +673,34 @@ impl ContainerRegistry {
                     .ok()?,
                 ))
             }
+            container_registry::Kind::GenericCr => Some(Box::new(
+                GenericCr::new(
+                    context,
+                    self.id.as_str(),
+                    self.long_id,
+                    self.name.as_str(),
+                    self.options.registry_url.as_ref()?.as_str(),
+                    self.options.registry_namespace.as_deref(),
+                    self.options.login.as_deref(),
+                    self.options.password.as_deref(),
+                    self.options.registry_flavor,
+                )
+                .ok()?,
+            )),
+            container_registry::Kind::AzureCr => {
+                let registry_name = azure_container_registry_name(context.cluster_short_id());
+                Some(Box::new(
+                    AzureContainerRegistry::new(
+                        context,
+                        self.id.as_str(),
+                        self.long_id,
+                        self.name.as_str(),
+                        registry_name.as_str(),
+                        &self.options.azure_credentials()?,
+                    )
+                    .ok()?,
+                ))
+            }
         }
     }
 }
@@ -604,6 +718,7 @@ impl DnsProvider {
     pub fn to_engine_dns_provider(
         &self,
         context: Context,
+        domain: Domain,
         cluster_jwt_token: String,
     ) -> Option<Box<dyn dns_provider::DnsProvider>> {
         match self.kind {
@@ -620,7 +735,7 @@ impl DnsProvider {
                     context,
                     self.long_id,
                     self.name.as_str(),
-                    Domain::new(self.domain.clone()),
+                    domain,
                     token.as_str(),
                     email.as_str(),
                     proxied,
@@ -636,12 +751,57 @@ impl DnsProvider {
                         api_url,
                         &cluster_jwt_token,
                         self.name.as_str(),
-                        Domain::new(self.domain.clone()),
+                        domain,
                     )));
                 }
 
                 None
             }
+            Kind::Route53 => {
+                let access_key_id = self.options.get("aws_access_key_id")?;
+                let secret_access_key = self.options.get("aws_secret_access_key")?;
+                let region = self
+                    .options
+                    .get("aws_region")
+                    .map(|s| s.as_str())
+                    .unwrap_or("us-east-1");
+
+                Some(Box::new(Route53Dns::new(
+                    context,
+                    self.long_id,
+                    self.name.as_str(),
+                    domain,
+                    access_key_id.as_str(),
+                    secret_access_key.as_str(),
+                    region,
+                )))
+            }
+            Kind::Rfc2136 => {
+                let nameserver = self.options.get("rfc2136_nameserver")?;
+                let port: u16 = match self.options.get("rfc2136_port") {
+                    Some(port) => port.parse().ok()?,
+                    None => 53,
+                };
+                let tsig_key_name = self.options.get("rfc2136_tsig_key_name")?;
+                let tsig_secret = self.options.get("rfc2136_tsig_secret")?;
+                let tsig_algorithm = match self.options.get("rfc2136_tsig_algorithm") {
+                    Some(algorithm) => TsigAlgorithm::from_str(algorithm).ok()?,
+                    None => TsigAlgorithm::HmacSha256,
+                };
+
+                Some(Box::new(Rfc2136Dns::new(
+                    context,
+                    self.long_id,
+                    self.name.as_str(),
+                    domain,
+                    nameserver.as_str(),
+                    port,
+                    self.options.get("rfc2136_zone").map(|s| s.as_str()),
+                    tsig_key_name.as_str(),
+                    tsig_secret.as_str(),
+                    tsig_algorithm,
+                )))
+            }
         }
     }
 }
@@ -671,6 +831,34 @@ pub struct Options {
     #[derivative(Debug = "ignore")]
     pub token: Option<String>,
     region: Option<String>,
+    registry_url: Option<String>,
+    registry_namespace: Option<String>,
+    #[serde(default)]
+    registry_flavor: GenericCrFlavor,
+    #[serde(default)]
+    azure_client_id: Option<String>,
+    #[derivative(Debug = "ignore")]
+    #[serde(default)]
+    pub azure_client_secret: Option<String>,
+    #[serde(default)]
+    azure_tenant_id: Option<String>,
+    #[serde(default)]
+    azure_subscription_id: Option<String>,
+    /// Namespace of the build pods, for the kubernetes pod build platform
+    #[serde(default)]
+    build_namespace: Option<String>,
+}
+
+impl Options {
+    /// Azure service principal credentials, None if any of them is missing
+    fn azure_credentials(&self) -> Option<AzureCredentials> {
+        Some(AzureCredentials {
+            client_id: self.azure_client_id.clone()?,
+            client_secret: self.azure_client_secret.clone()?,
+            tenant_id: self.azure_tenant_id.clone()?,
+            subscription_id: self.azure_subscription_id.clone()?,
+        })
+    }
 }
 
 /// Allow to properly deserialize JSON credentials from string, making sure to escape \n from keys strings
@@ -12,9 +12,10 @@ use crate::io_models::Action;
 use crate::models::application::{ApplicationError, ApplicationService};
 use crate::models::container::{ContainerError, ContainerService};
 use crate::models::database::{DatabaseError, DatabaseService};
+use crate::models::dependency_graph::{DependencyGraphError, ServiceDependencyGraph};
 use crate::models::helm_chart::{HelmChartError, HelmChartService};
 use crate::models::job::{JobError, JobService};
-use crate::models::router::RouterError;
+use crate::models::router::{RouterError, RouterExternalAuth, RouterRateLimit};
 use crate::utilities::base64_replace_comma_to_new_line;
 use crate::{cloud_provider::environment::Environment, models::router::RouterAdvancedSettings};
 use serde::{Deserialize, Serialize};
@@ -40,6 +41,9 @@ pub struct EnvironmentRequest {
     pub databases: Vec<Database>,
     #[serde(default)]
     pub helms: Vec<HelmChart>,
+    /// When a deployment fails, restore touched services to their previous version
+    #[serde(default)]
+    pub rollback_on_failure: bool,
 }
 
 fn default_max_parallel_build() -> u32 {
@@ -64,6 +68,8 @@ pub enum DomainError {
     JobError(#[from] JobError),
     #[error("Invalid helm chart: {0}")]
     HelmChartError(#[from] HelmChartError),
+    #[error("Invalid services dependencies: {0}")]
+    DependencyGraphError(#[from] DependencyGraphError),
 }
 
 impl EnvironmentRequest {
@@ -74,6 +80,8 @@ impl EnvironmentRequest {
         container_registry: &dyn ContainerRegistry,
         cluster: &dyn Kubernetes,
     ) -> Result<Environment, DomainError> {
+        let dependencies = self.to_dependency_graph()?;
+
         let applications: Result<Vec<Box<dyn ApplicationService>>, ApplicationError> = self
             .applications
             .iter()
@@ -120,6 +128,19 @@ impl EnvironmentRequest {
                             router_advanced_settings.denylist_source_range =
                                 Some(app.advanced_settings.network_ingress_denylist_source_range.clone());
                         }
+                        // rate limiting, WAF and external auth
+                        router_advanced_settings.rate_limit = RouterRateLimit::new(
+                            app.advanced_settings.network_ingress_limit_rps,
+                            app.advanced_settings.network_ingress_limit_connections,
+                            app.advanced_settings.network_ingress_limit_burst_multiplier,
+                        );
+                        router_advanced_settings.modsecurity_profile =
+                            app.advanced_settings.network_ingress_modsecurity_profile;
+                        router_advanced_settings.external_auth = RouterExternalAuth::new(
+                            &app.advanced_settings.network_ingress_auth_url,
+                            &app.advanced_settings.network_ingress_auth_signin,
+                            &app.advanced_settings.network_ingress_auth_response_headers,
+                        );
                         // basic auth
                         if app.advanced_settings.network_ingress_basic_auth_env_var != *"" {
                             match app
@@ -180,6 +201,19 @@ impl EnvironmentRequest {
                                     .clone(),
                             );
                         }
+                        // rate limiting, WAF and external auth
+                        router_advanced_settings.rate_limit = RouterRateLimit::new(
+                            container.advanced_settings.network_ingress_limit_rps,
+                            container.advanced_settings.network_ingress_limit_connections,
+                            container.advanced_settings.network_ingress_limit_burst_multiplier,
+                        );
+                        router_advanced_settings.modsecurity_profile =
+                            container.advanced_settings.network_ingress_modsecurity_profile;
+                        router_advanced_settings.external_auth = RouterExternalAuth::new(
+                            &container.advanced_settings.network_ingress_auth_url,
+                            &container.advanced_settings.network_ingress_auth_signin,
+                            &container.advanced_settings.network_ingress_auth_response_headers,
+                        );
                         // basic auth
                         if container.advanced_settings.network_ingress_basic_auth_env_var != *"" {
                             match container
@@ -233,6 +267,19 @@ impl EnvironmentRequest {
                             router_advanced_settings.denylist_source_range =
                                 Some(helm.advanced_settings.network_ingress_denylist_source_range.clone());
                         }
+                        // rate limiting, WAF and external auth
+                        router_advanced_settings.rate_limit = RouterRateLimit::new(
+                            helm.advanced_settings.network_ingress_limit_rps,
+                            helm.advanced_settings.network_ingress_limit_connections,
+                            helm.advanced_settings.network_ingress_limit_burst_multiplier,
+                        );
+                        router_advanced_settings.modsecurity_profile =
+                            helm.advanced_settings.network_ingress_modsecurity_profile;
+                        router_advanced_settings.external_auth = RouterExternalAuth::new(
+                            &helm.advanced_settings.network_ingress_auth_url,
+                            &helm.advanced_settings.network_ingress_auth_signin,
+                            &helm.advanced_settings.network_ingress_auth_response_headers,
+                        );
                         // basic auth
                         if helm.advanced_settings.network_ingress_basic_auth_env_var != *"" {
                             match helm
@@ -314,6 +361,19 @@ impl EnvironmentRequest {
             databases,
             jobs,
             helm_charts,
+            dependencies,
+            self.rollback_on_failure,
         ))
     }
+
+    pub fn to_dependency_graph(&self) -> Result<ServiceDependencyGraph, DependencyGraphError> {
+        ServiceDependencyGraph::new(
+            std::iter::empty()
+                .chain(self.databases.iter().map(|s| (s.long_id, s.depends_on.clone())))
+                .chain(self.jobs.iter().map(|s| (s.long_id, s.depends_on.clone())))
+                .chain(self.containers.iter().map(|s| (s.long_id, s.depends_on.clone())))
+                .chain(self.applications.iter().map(|s| (s.long_id, s.depends_on.clone())))
+                .chain(self.helms.iter().map(|s| (s.long_id, s.depends_on.clone()))),
+        )
+    }
 }
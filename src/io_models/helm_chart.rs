@@ -6,14 +6,15 @@ use crate::io_models::application::{GitCredentials, Port};
 use crate::io_models::container::Registry;
 use crate::io_models::context::Context;
 use crate::io_models::variable_utils::{default_environment_vars_with_info, VariableInfo};
-use crate::io_models::{fetch_git_token, ssh_keys_from_env_vars, Action};
+use crate::io_models::{fetch_git_token, ssh_keys_from_env_vars, Action, ModSecurityProfile};
 use crate::models;
 use crate::models::aws::AwsAppExtraSettings;
 use crate::models::aws_ec2::AwsEc2AppExtraSettings;
+use crate::models::azure::AzureAppExtraSettings;
 use crate::models::gcp::GcpAppExtraSettings;
 use crate::models::helm_chart::{HelmChartError, HelmChartService};
 use crate::models::scaleway::ScwAppExtraSettings;
-use crate::models::types::{AWSEc2, AWS, GCP, SCW};
+use crate::models::types::{AWSEc2, AWS, AZURE, GCP, SCW};
 use base64::Engine;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
@@ -74,6 +75,20 @@ pub struct HelmChartAdvancedSettings {
     pub network_ingress_denylist_source_range: String,
     #[serde(alias = "network.ingress.basic_auth_env_var")]
     pub network_ingress_basic_auth_env_var: String,
+    #[serde(alias = "network.ingress.limit_rps")]
+    pub network_ingress_limit_rps: Option<u32>,
+    #[serde(alias = "network.ingress.limit_connections")]
+    pub network_ingress_limit_connections: Option<u32>,
+    #[serde(alias = "network.ingress.limit_burst_multiplier")]
+    pub network_ingress_limit_burst_multiplier: u32,
+    #[serde(alias = "network.ingress.modsecurity_profile")]
+    pub network_ingress_modsecurity_profile: Option<ModSecurityProfile>,
+    #[serde(alias = "network.ingress.auth_url")]
+    pub network_ingress_auth_url: String,
+    #[serde(alias = "network.ingress.auth_signin")]
+    pub network_ingress_auth_signin: String,
+    #[serde(alias = "network.ingress.auth_response_headers")]
+    pub network_ingress_auth_response_headers: String,
 
     #[serde(alias = "network.ingress.grpc_send_timeout_seconds")]
     pub network_ingress_grpc_send_timeout_seconds: u32,
@@ -105,6 +120,13 @@ impl Default for HelmChartAdvancedSettings {
             network_ingress_whitelist_source_range: "0.0.0.0/0".to_string(),
             network_ingress_denylist_source_range: "".to_string(),
             network_ingress_basic_auth_env_var: "".to_string(),
+            network_ingress_limit_rps: None,
+            network_ingress_limit_connections: None,
+            network_ingress_limit_burst_multiplier: 5,
+            network_ingress_modsecurity_profile: None,
+            network_ingress_auth_url: "".to_string(),
+            network_ingress_auth_signin: "".to_string(),
+            network_ingress_auth_response_headers: "".to_string(),
             network_ingress_grpc_send_timeout_seconds: 60,
             network_ingress_grpc_read_timeout_seconds: 60,
         }
@@ -169,6 +191,8 @@ pub struct HelmChart {
     pub environment_vars_with_infos: BTreeMap<String, VariableInfo>,
     pub advanced_settings: HelmChartAdvancedSettings,
     pub ports: Vec<Port>,
+    #[serde(default)]
+    pub depends_on: Vec<Uuid>,
 }
 
 impl HelmChart {
@@ -364,6 +388,31 @@ impl HelmChart {
                     self.ports,
                 )?)
             }
+            kubernetes::Kind::Aks => Box::new(models::helm_chart::HelmChart::<AZURE>::new(
+                context,
+                self.long_id,
+                self.name,
+                self.kube_name,
+                self.action.to_service_action(),
+                Self::to_chart_source_domain(
+                    self.chart_source.clone(),
+                    &ssh_keys,
+                    context.qovery_api.clone(),
+                    self.long_id,
+                ),
+                Self::to_chart_value_domain(self.chart_values, &ssh_keys, context.qovery_api.clone(), self.long_id),
+                self.set_values,
+                self.set_string_values,
+                self.set_json_values,
+                self.command_args,
+                std::time::Duration::from_secs(self.timeout_sec),
+                self.allow_cluster_wide_resources,
+                environment_variables_with_info,
+                self.advanced_settings,
+                AzureAppExtraSettings {},
+                |transmitter| context.get_event_details(transmitter),
+                self.ports,
+            )?),
         };
 
         Ok(service)
@@ -467,6 +516,7 @@ fn test_helm_deserialization_with_env_variables_with_infos() {
         &VariableInfo {
             value: "value".to_string(),
             is_secret: false,
+            external_secret: None,
         }
     );
     assert_eq!(
@@ -474,6 +524,7 @@ fn test_helm_deserialization_with_env_variables_with_infos() {
         &VariableInfo {
             value: "my password".to_string(),
             is_secret: true,
+            external_secret: None,
         }
     );
 }
@@ -1,4 +1,4 @@
-use crate::build_platform::{Build, GitRepository, Image, SshKey};
+use crate::build_platform::{Build, BuildpacksSettings, GitRepository, Image, SshKey};
 use crate::cloud_provider::kubernetes::{Kind as KubernetesKind, Kubernetes};
 use crate::cloud_provider::models::CpuArchitecture;
 use crate::cloud_provider::service::ServiceType;
@@ -11,17 +11,18 @@ use crate::io_models::context::Context;
 use crate::io_models::probe::Probe;
 use crate::io_models::variable_utils::{default_environment_vars_with_info, VariableInfo};
 use crate::io_models::{
-    fetch_git_token, normalize_root_and_dockerfile_path, ssh_keys_from_env_vars, Action, MountedFile,
+    fetch_git_token, normalize_root_and_dockerfile_path, ssh_keys_from_env_vars, Action, MountedFile, PodToleration,
 };
 use crate::models;
 use crate::models::aws::AwsAppExtraSettings;
 use crate::models::aws_ec2::AwsEc2AppExtraSettings;
+use crate::models::azure::AzureAppExtraSettings;
 use crate::models::gcp::GcpAppExtraSettings;
 use crate::models::job::{ImageSource, JobError, JobService};
 use crate::models::registry_image_source::RegistryImageSource;
 use crate::models::scaleway::ScwAppExtraSettings;
 use crate::models::selfmanaged::SelfManagedAppExtraSettings;
-use crate::models::types::{AWSEc2, SelfManaged, AWS, GCP, SCW};
+use crate::models::types::{AWSEc2, SelfManaged, AWS, AZURE, GCP, SCW};
 use crate::utilities::to_short_id;
 use base64::engine::general_purpose;
 use base64::Engine;
@@ -38,6 +39,12 @@ pub struct JobAdvancedSettings {
     // Job specific
     #[serde(alias = "job.delete_ttl_seconds_after_finished")]
     pub job_delete_ttl_seconds_after_finished: Option<u32>,
+    #[serde(alias = "job.retry_max_attempts")]
+    pub job_retry_max_attempts: u32,
+    #[serde(alias = "job.retry_initial_backoff_seconds")]
+    pub job_retry_initial_backoff_seconds: u32,
+    #[serde(alias = "job.retry_max_backoff_seconds")]
+    pub job_retry_max_backoff_seconds: u32,
 
     #[serde(alias = "cronjob.concurrency_policy")]
     pub cronjob_concurrency_policy: String,
@@ -51,6 +58,10 @@ pub struct JobAdvancedSettings {
     pub deployment_termination_grace_period_seconds: u32,
     #[serde(alias = "deployment.affinity.node.required")]
     pub deployment_affinity_node_required: BTreeMap<String, String>,
+    #[serde(alias = "deployment.node_selector")]
+    pub deployment_node_selector: BTreeMap<String, String>,
+    #[serde(alias = "deployment.tolerations")]
+    pub deployment_tolerations: Vec<PodToleration>,
 
     // Build
     #[serde(alias = "build.timeout_max_sec")]
@@ -64,14 +75,21 @@ pub struct JobAdvancedSettings {
     pub security_service_account_name: String,
     #[serde(alias = "security.read_only_root_filesystem")]
     pub security_read_only_root_filesystem: bool,
+    #[serde(alias = "security.vulnerability_allowlist")]
+    pub security_vulnerability_allowlist: Vec<String>,
 }
 
 impl Default for JobAdvancedSettings {
     fn default() -> Self {
         Self {
             job_delete_ttl_seconds_after_finished: None,
+            job_retry_max_attempts: 0,
+            job_retry_initial_backoff_seconds: 10,
+            job_retry_max_backoff_seconds: 5 * 60, // 5 minutes
             deployment_termination_grace_period_seconds: 60,
             deployment_affinity_node_required: BTreeMap::new(),
+            deployment_node_selector: BTreeMap::new(),
+            deployment_tolerations: vec![],
             cronjob_concurrency_policy: "Forbid".to_string(),
             cronjob_failed_jobs_history_limit: 1,
             cronjob_success_jobs_history_limit: 1,
@@ -80,6 +98,7 @@ impl Default for JobAdvancedSettings {
             build_ram_max_in_gib: 8,
             security_service_account_name: "".to_string(),
             security_read_only_root_filesystem: false,
+            security_vulnerability_allowlist: vec![],
         }
     }
 }
@@ -90,11 +109,21 @@ pub enum JobSchedule {
     OnStart {},
     OnPause {},
     OnDelete {},
-    Cron { schedule: String, timezone: String },
+    Cron {
+        schedule: String,
+        timezone: String,
+    },
+    /// Deployed dormant, a job is created from its template each time one of its triggers fires.
+    /// It can always be triggered manually with a force trigger.
+    OnTrigger {
+        webhook: Option<JobWebhookTrigger>,
+        queue: Option<JobQueueTrigger>,
+    },
 }
 impl JobSchedule {
+    /// Deployed as a kubernetes CronJob, either scheduled or suspended and waiting for its triggers
     pub fn is_cronjob(&self) -> bool {
-        matches!(self, JobSchedule::Cron { .. })
+        matches!(self, JobSchedule::Cron { .. } | JobSchedule::OnTrigger { .. })
     }
 
     pub fn is_job(&self) -> bool {
@@ -102,6 +131,24 @@ impl JobSchedule {
     }
 }
 
+/// HTTP endpoint exposed through the environment router, callers must present the token as a bearer token
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+pub struct JobWebhookTrigger {
+    pub token: String,
+}
+
+/// Queue polled for messages, each message received triggers the job with the message as payload
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobQueueTrigger {
+    Sqs {
+        queue_url: String,
+        region: String,
+        access_key_id: String,
+        secret_access_key: String,
+    },
+}
+
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum JobSource {
@@ -149,6 +196,8 @@ pub struct Job {
     pub liveness_probe: Option<Probe>,
     #[serde(default)]
     pub advanced_settings: JobAdvancedSettings,
+    #[serde(default)]
+    pub depends_on: Vec<Uuid>,
 }
 
 impl Job {
@@ -197,6 +246,7 @@ impl Job {
                 dockerfile_path,
                 root_path,
                 buildpack_language: None,
+                buildpacks: BuildpacksSettings::default(),
             },
             image: self.to_image(commit_id.to_string(), registry_url),
             environment_variables: self
@@ -223,6 +273,7 @@ impl Job {
             architectures,
             max_cpu_in_milli: self.advanced_settings.build_cpu_max_in_milli,
             max_ram_in_gib: self.advanced_settings.build_ram_max_in_gib,
+            vulnerability_allowlist: self.advanced_settings.security_vulnerability_allowlist.clone(),
         };
 
         build.compute_image_tag();
@@ -412,6 +463,35 @@ impl Job {
                 GcpAppExtraSettings {},
                 |transmitter| context.get_event_details(transmitter),
             )?),
+            Kind::Azure => Box::new(models::job::Job::<AZURE>::new(
+                context,
+                self.long_id,
+                self.name,
+                self.kube_name,
+                self.action.to_service_action(),
+                image_source,
+                self.schedule,
+                self.max_nb_restart,
+                Duration::from_secs(self.max_duration_in_sec),
+                self.default_port,
+                self.command_args,
+                self.entrypoint,
+                self.force_trigger,
+                self.cpu_request_in_milli,
+                self.cpu_limit_in_milli,
+                self.ram_request_in_mib,
+                self.ram_limit_in_mib,
+                environment_variables,
+                self.mounted_files
+                    .iter()
+                    .map(|e| e.to_domain())
+                    .collect::<BTreeSet<_>>(),
+                self.advanced_settings,
+                self.readiness_probe.map(|p| p.to_domain()),
+                self.liveness_probe.map(|p| p.to_domain()),
+                AzureAppExtraSettings {},
+                |transmitter| context.get_event_details(transmitter),
+            )?),
             Kind::SelfManaged => Box::new(models::job::Job::<SelfManaged>::new(
                 context,
                 self.long_id,
@@ -446,3 +526,42 @@ impl Job {
         Ok(service)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::io_models::job::{JobQueueTrigger, JobSchedule};
+
+    #[test]
+    fn test_on_trigger_schedule_deserialization() {
+        let data = r#"
+        {
+          "on_trigger": {
+            "webhook": { "token": "my-token" },
+            "queue": {
+              "type": "sqs",
+              "queue_url": "https://sqs.eu-west-3.amazonaws.com/123456789012/my-queue",
+              "region": "eu-west-3",
+              "access_key_id": "AKIA",
+              "secret_access_key": "secret"
+            }
+          }
+        }"#;
+
+        let schedule: JobSchedule = serde_json::from_str(data).unwrap();
+        assert!(schedule.is_cronjob());
+        let JobSchedule::OnTrigger { webhook, queue } = schedule else {
+            panic!("schedule should be on_trigger");
+        };
+        assert_eq!(webhook.map(|webhook| webhook.token).as_deref(), Some("my-token"));
+        assert!(matches!(queue, Some(JobQueueTrigger::Sqs { region, .. }) if region == "eu-west-3"));
+
+        let manual_only: JobSchedule = serde_json::from_str(r#"{ "on_trigger": {} }"#).unwrap();
+        assert!(matches!(
+            manual_only,
+            JobSchedule::OnTrigger {
+                webhook: None,
+                queue: None
+            }
+        ));
+    }
+}
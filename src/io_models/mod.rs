@@ -1,5 +1,6 @@
 use crate::build_platform::{Credentials, SshKey};
 use crate::cloud_provider;
+use crate::cloud_provider::models::TaintEffect;
 use crate::cloud_provider::service;
 use crate::cloud_provider::service::ServiceType;
 use crate::engine_task::qovery_api::QoveryApi;
@@ -30,6 +31,71 @@ pub enum UpdateStrategy {
     #[default]
     RollingUpdate,
     Recreate,
+    /// Deploy the new version next to the stable one, send it a share of the router traffic
+    /// and promote it once it stayed healthy during the analysis window
+    Canary,
+    /// Deploy the new version next to the live one and switch the service selector once it is ready
+    BlueGreen,
+}
+
+impl UpdateStrategy {
+    /// Kubernetes deployment strategy used to roll out each track of the service
+    pub fn to_k8s_deployment_strategy(&self) -> &'static str {
+        match self {
+            UpdateStrategy::Recreate => "Recreate",
+            UpdateStrategy::RollingUpdate | UpdateStrategy::Canary | UpdateStrategy::BlueGreen => "RollingUpdate",
+        }
+    }
+}
+
+/// How the value of a custom autoscaling metric is compared to its target
+#[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Hash, Debug, Default)]
+pub enum HpaMetricTargetType {
+    /// Metric value divided by the number of pods (i.e: queue length per pod)
+    #[default]
+    AverageValue,
+    /// Metric value as returned by the query
+    Value,
+}
+
+impl HpaMetricTargetType {
+    pub fn to_k8s_target_type(&self) -> &'static str {
+        match self {
+            HpaMetricTargetType::AverageValue => "AverageValue",
+            HpaMetricTargetType::Value => "Value",
+        }
+    }
+}
+
+/// Prometheus query the pod autoscaler scales on, exposed as an external metric through prometheus-adapter
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct HpaCustomMetric {
+    pub name: String,
+    pub query: String,
+    #[serde(default)]
+    pub target_type: HpaMetricTargetType,
+    /// Kubernetes quantity (i.e: 30, 500m)
+    pub target_value: String,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Hash, Debug, Default)]
+pub enum TolerationOperator {
+    #[default]
+    Equal,
+    Exists,
+}
+
+/// Allows pods to be scheduled on nodes having a matching taint (i.e: spot node groups)
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PodToleration {
+    pub key: String,
+    #[serde(default)]
+    pub operator: TolerationOperator,
+    #[serde(default)]
+    pub value: Option<String>,
+    /// All effects are tolerated when not set
+    #[serde(default)]
+    pub effect: Option<TaintEffect>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug, Default)]
@@ -39,6 +105,24 @@ pub enum PodAntiAffinity {
     Required,
 }
 
+/// OWASP core rule set behavior of the ModSecurity web application firewall in front of a service
+#[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Hash, Debug)]
+pub enum ModSecurityProfile {
+    /// Matching requests are only logged
+    DetectionOnly,
+    /// Matching requests are rejected with a 403
+    Blocking,
+}
+
+impl ModSecurityProfile {
+    pub fn to_modsecurity_rule_engine(&self) -> &'static str {
+        match self {
+            ModSecurityProfile::DetectionOnly => "DetectionOnly",
+            ModSecurityProfile::Blocking => "On",
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct QoveryIdentifier {
     long_id: Uuid,
@@ -85,6 +169,8 @@ pub enum Action {
     Pause,
     Delete,
     Restart,
+    /// Restore a container database from one of its backups
+    Restore,
 }
 
 impl Action {
@@ -94,6 +180,7 @@ impl Action {
             Action::Pause => service::Action::Pause,
             Action::Delete => service::Action::Delete,
             Action::Restart => service::Action::Restart,
+            Action::Restore => service::Action::Restore,
         }
     }
 }
@@ -5,12 +5,16 @@ use crate::io_models::Action;
 use crate::models;
 use crate::models::aws::AwsRouterExtraSettings;
 use crate::models::aws_ec2::AwsEc2RouterExtraSettings;
+use crate::models::azure::AzureRouterExtraSettings;
+use crate::models::domain::Domain;
 use crate::models::gcp::GcpRouterExtraSettings;
 use crate::models::router::{RouterAdvancedSettings, RouterError, RouterService};
 use crate::models::scaleway::ScwRouterExtraSettings;
 use crate::models::selfmanaged::SelfManagedRouterExtraSettings;
-use crate::models::types::{AWSEc2, SelfManaged, AWS, GCP, SCW};
+use crate::models::tls_certificate::TlsCertificate;
+use crate::models::types::{AWSEc2, SelfManaged, AWS, AZURE, GCP, SCW};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 fn default_generate_certificate() -> bool {
@@ -35,12 +39,49 @@ pub struct CustomDomain {
     pub target_domain: String,
     #[serde(default = "default_generate_certificate")]
     pub generate_certificate: bool,
+    #[serde(default)]
+    pub certificate_source: CertificateSource,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Default)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CertificateSource {
+    #[default]
+    Generated,
+    Uploaded {
+        certificate_chain: String,
+        private_key: String,
+    },
+    Issuer {
+        name: String,
+    },
+}
+
+impl CertificateSource {
+    fn to_domain(&self) -> crate::cloud_provider::models::CertificateSource {
+        match self {
+            CertificateSource::Generated => crate::cloud_provider::models::CertificateSource::Generated,
+            CertificateSource::Uploaded {
+                certificate_chain,
+                private_key,
+            } => crate::cloud_provider::models::CertificateSource::Uploaded {
+                certificate_chain: certificate_chain.clone(),
+                private_key: private_key.clone(),
+            },
+            CertificateSource::Issuer { name } => {
+                crate::cloud_provider::models::CertificateSource::Issuer { name: name.clone() }
+            }
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
 pub struct Route {
     pub path: String,
     pub service_long_id: Uuid,
+    // only matched when the cluster routes through the Gateway API, nginx ingresses can't match on headers
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
 }
 
 impl Router {
@@ -50,6 +91,21 @@ impl Router {
         advanced_settings: RouterAdvancedSettings,
         cloud_provider: &dyn CloudProvider,
     ) -> Result<Box<dyn RouterService>, RouterError> {
+        // uploaded certificates are checked here, as once deployed an invalid one is silently replaced by the default one
+        for custom_domain in &self.custom_domains {
+            Domain::try_new(custom_domain.domain.to_string())
+                .map_err(|e| RouterError::InvalidConfig(format!("Invalid custom domain: {e}")))?;
+            if let CertificateSource::Uploaded {
+                certificate_chain,
+                private_key,
+            } = &custom_domain.certificate_source
+            {
+                TlsCertificate::from_pem(certificate_chain, private_key).map_err(|e| {
+                    RouterError::InvalidConfig(format!("Custom domain `{}` certificate: {}", custom_domain.domain, e))
+                })?;
+            }
+        }
+
         let custom_domains = self
             .custom_domains
             .iter()
@@ -57,6 +113,7 @@ impl Router {
                 domain: x.domain.clone(),
                 target_domain: x.target_domain.clone(),
                 generate_certificate: x.generate_certificate,
+                certificate_source: x.certificate_source.to_domain(),
             })
             .collect::<Vec<_>>();
 
@@ -66,6 +123,7 @@ impl Router {
             .map(|x| crate::cloud_provider::models::Route {
                 path: x.path.clone(),
                 service_long_id: x.service_long_id,
+                headers: x.headers.clone(),
             })
             .collect::<Vec<_>>();
 
@@ -133,6 +191,19 @@ impl Router {
                 advanced_settings,
                 |transmitter| context.get_event_details(transmitter),
             )?)),
+            CPKind::Azure => Ok(Box::new(models::router::Router::<AZURE>::new(
+                context,
+                self.long_id,
+                self.name.as_str(),
+                self.kube_name.to_string(),
+                self.action.to_service_action(),
+                self.default_domain.as_str(),
+                custom_domains,
+                routes,
+                AzureRouterExtraSettings {},
+                advanced_settings,
+                |transmitter| context.get_event_details(transmitter),
+            )?)),
             CPKind::SelfManaged => {
                 let router = Box::new(models::router::Router::<SelfManaged>::new(
                     context,
@@ -5,6 +5,20 @@ use std::collections::BTreeMap;
 pub struct VariableInfo {
     pub value: String,
     pub is_secret: bool,
+    #[serde(default)]
+    pub external_secret: Option<ExternalSecret>,
+}
+
+/// Secret kept outside of Qovery, the engine resolves it into the variable value at deployment time
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ExternalSecret {
+    /// `vault://external-secrets/<organization long id>/<path>#<key>`, `aws-sm://<secret arn>#<key>` or `gcp-sm://projects/<project>/secrets/<secret>/versions/<version>`.
+    /// Vault secrets can only be read from the `external-secrets` kv v2 mount, under the long id of the organization.
+    pub reference: String,
+    /// Restart the pods of the application, container or job when the version of the upstream secret changes,
+    /// checked between deployments by the external secrets refresh requests
+    #[serde(default)]
+    pub refresh: bool,
 }
 
 pub fn default_environment_vars_with_info() -> BTreeMap<String, VariableInfo> {
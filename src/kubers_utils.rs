@@ -143,3 +143,45 @@ pub async fn kube_rollout_restart_statefulset(
 
     Ok(())
 }
+
+pub async fn kube_get_resource_by_name<K>(
+    client: &kube::Client,
+    namespace: &str,
+    name: &str,
+) -> Result<Option<K>, CommandError>
+where
+    K: Clone + DeserializeOwned + Debug + Resource<Scope = NamespaceResourceScope>,
+    <K as Resource>::DynamicType: Default,
+{
+    let obj_name = K::kind(&K::DynamicType::default()).to_string();
+    info!("Getting k8s {} {} in {}", obj_name, name, namespace);
+
+    let api: Api<K> = Api::namespaced(client.clone(), namespace);
+    let resource = api
+        .get_opt(name)
+        .await
+        .map_err(|e| CommandError::new(format!("Unable to get {obj_name} {name}."), Some(e.to_string()), None))?;
+
+    Ok(resource)
+}
+
+pub async fn kube_merge_patch_resource<K>(
+    client: &kube::Client,
+    namespace: &str,
+    name: &str,
+    patch: &serde_json::Value,
+) -> Result<(), CommandError>
+where
+    K: Clone + DeserializeOwned + Debug + Resource<Scope = NamespaceResourceScope>,
+    <K as Resource>::DynamicType: Default,
+{
+    let obj_name = K::kind(&K::DynamicType::default()).to_string();
+    info!("Patching k8s {} {} in {}", obj_name, name, namespace);
+
+    let api: Api<K> = Api::namespaced(client.clone(), namespace);
+    api.patch(name, &PatchParams::default(), &Patch::Merge(patch))
+        .await
+        .map_err(|e| CommandError::new(format!("Unable to patch {obj_name} {name}."), Some(e.to_string()), None))?;
+
+    Ok(())
+}
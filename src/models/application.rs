@@ -17,8 +17,13 @@ use crate::kubers_utils::kube_get_resources_by_selector;
 use crate::models::container::{
     to_public_l4_ports, ClusterTeraContext, ContainerTeraContext, RegistryTeraContext, ServiceTeraContext,
 };
+use crate::models::deployment_strategy::{DeploymentTeraContext, TrackVersion};
+use crate::models::horizontal_autoscaler::HorizontalAutoscalerTeraContext;
 use crate::models::probe::Probe;
 use crate::models::types::{CloudProvider, ToTeraContext};
+use crate::models::volume_snapshot::{
+    volume_snapshot_restores, VolumeSnapshotRestore, VolumeSnapshotTeraContext, VolumeStorageClass,
+};
 use crate::runtime::block_on;
 use crate::unit_conversion::extract_volume_size;
 use crate::utilities::to_short_id;
@@ -147,6 +152,11 @@ impl<T: CloudProvider> Application<T> {
         let environment = &target.environment;
         let kubernetes = &target.kubernetes;
         let registry_info = target.container_registry.registry_info();
+        // outputs of the jobs this service depends on have been recorded when those jobs ran
+        let (environment_variables, job_output_secrets) = target.job_outputs.service_environment_variables(
+            &self.environment_variables,
+            environment.dependencies.dependencies_of(&self.long_id),
+        );
         let ctx = ContainerTeraContext {
             organization_long_id: environment.organization_long_id,
             project_long_id: environment.project_long_id,
@@ -204,19 +214,65 @@ impl<T: CloudProvider> Application<T> {
                     secret_name: format!("{}-registry", self.kube_name()),
                     docker_json_config: Some(docker_json.to_string()),
                 }),
-            environment_variables: self.environment_variables.clone(),
+            environment_variables,
+            job_output_secrets,
             mounted_files: self.mounted_files.clone().into_iter().collect::<Vec<_>>(),
             resource_expiration_in_seconds: Some(kubernetes.advanced_settings().pleco_resources_ttl),
             loadbalancer_l4_annotations: T::loadbalancer_l4_annotations(),
+            deployment: self.deployment_tera_context(),
+            autoscaler: HorizontalAutoscalerTeraContext::new(
+                self.long_id,
+                self.kube_name(),
+                &self.advanced_settings.to_container_advanced_settings(),
+            ),
+            volume_snapshot: VolumeSnapshotTeraContext::new(
+                self.kube_name(),
+                &self.storage,
+                T::volume_snapshot_class(),
+            ),
         };
 
         ctx
     }
 
+    /// Version of the application being deployed
+    pub(crate) fn track_version(&self) -> TrackVersion {
+        TrackVersion {
+            image_full: self.build.image.full_image_name_with_tag(),
+            version: self.version(),
+        }
+    }
+
+    /// Deployment of the application once its rollout is over
+    pub(crate) fn deployment_tera_context(&self) -> DeploymentTeraContext {
+        DeploymentTeraContext::new(
+            self.advanced_settings.deployment_update_strategy_type,
+            self.kube_name(),
+            self.track_version(),
+            self.min_instances,
+        )
+    }
+
     pub fn is_stateful(&self) -> bool {
         !self.storage.is_empty()
     }
 
+    /// Storages requested to be recreated from one of their snapshots
+    pub fn volume_snapshot_restores(&self) -> Vec<VolumeSnapshotRestore> {
+        volume_snapshot_restores(&self.storage)
+    }
+
+    /// StorageClass of the volume claims of each storage, labelled by the legacy volume claim template
+    pub fn volume_storage_classes(&self) -> Vec<VolumeStorageClass> {
+        self.storage
+            .iter()
+            .map(|storage| VolumeStorageClass {
+                pvc_selector: format!("diskId={}", storage.id),
+                storage_class: T::storage_class(storage),
+            })
+            .collect()
+    }
+
     pub fn service_type(&self) -> ServiceType {
         ServiceType::Application
     }
@@ -370,6 +426,7 @@ pub trait ApplicationService: Service + DeploymentAction + ToTeraContext + Send
     fn public_ports(&self) -> Vec<&Port>;
     fn advanced_settings(&self) -> &ApplicationAdvancedSettings;
     fn startup_timeout(&self) -> Duration;
+    fn helm_release_name(&self) -> String;
     fn as_deployment_action(&self) -> &dyn DeploymentAction;
 }
 
@@ -411,6 +468,10 @@ where
         Duration::from_secs(startup_timeout as u64)
     }
 
+    fn helm_release_name(&self) -> String {
+        Application::helm_release_name(self)
+    }
+
     fn as_deployment_action(&self) -> &dyn DeploymentAction {
         self
     }
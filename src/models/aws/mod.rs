@@ -5,6 +5,7 @@ mod database_utils;
 mod job;
 mod router;
 
+use crate::cloud_provider::models::Storage;
 use crate::cloud_provider::Kind;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -49,6 +50,19 @@ impl CloudProvider for AWS {
     fn loadbalancer_l4_annotations() -> &'static [(&'static str, &'static str)] {
         &[("service.beta.kubernetes.io/aws-load-balancer-type", "nlb")]
     }
+
+    fn volume_snapshot_class() -> Option<&'static str> {
+        Some("aws-ebs-snapshot")
+    }
+
+    // Volumes of the in-tree aws-ebs storage classes cannot be snapshotted by the EBS CSI driver,
+    // so storages with snapshots are provisioned with the EBS CSI storage classes
+    fn storage_class(storage: &Storage<Self::StorageTypes>) -> String {
+        match storage.snapshot_retention_in_days > 0 {
+            true => storage.storage_type.to_k8s_csi_storage_class(),
+            false => storage.storage_type.to_k8s_storage_class(),
+        }
+    }
 }
 
 impl AWS {}
@@ -82,4 +96,14 @@ impl AwsStorageType {
         }
         .to_string()
     }
+
+    pub fn to_k8s_csi_storage_class(&self) -> String {
+        match self {
+            AwsStorageType::SC1 => "aws-ebs-csi-sc1-0",
+            AwsStorageType::ST1 => "aws-ebs-csi-st1-0",
+            AwsStorageType::GP2 => "aws-ebs-csi-gp2-0",
+            AwsStorageType::IO1 => "aws-ebs-csi-io1-0",
+        }
+        .to_string()
+    }
 }
@@ -5,6 +5,7 @@ mod database_utils;
 mod job;
 mod router;
 
+use crate::cloud_provider::models::Storage;
 use crate::cloud_provider::Kind;
 use std::fmt::{Display, Formatter};
 
@@ -47,6 +48,14 @@ impl CloudProvider for AWSEc2 {
     fn loadbalancer_l4_annotations() -> &'static [(&'static str, &'static str)] {
         &[("service.beta.kubernetes.io/aws-load-balancer-type", "nlb")]
     }
+
+    fn volume_snapshot_class() -> Option<&'static str> {
+        None
+    }
+
+    fn storage_class(storage: &Storage<Self::StorageTypes>) -> String {
+        storage.storage_type.to_k8s_storage_class()
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Hash)]
@@ -0,0 +1,97 @@
+mod application;
+mod container;
+mod database;
+mod job;
+mod router;
+
+use crate::cloud_provider::models::Storage;
+use crate::cloud_provider::Kind;
+use crate::models::types::{CloudProvider, AZURE};
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct AzureCredentials {
+    pub client_id: String,
+    pub client_secret: String,
+    pub tenant_id: String,
+    pub subscription_id: String,
+}
+
+// https://learn.microsoft.com/en-us/azure/aks/azure-disk-csi
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub enum AzureStorageType {
+    StandardSSD,
+    PremiumSSD,
+    StandardHDD,
+    UltraSSD,
+}
+
+impl Display for AzureStorageType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AzureStorageType::StandardSSD => write!(f, "StandardSSD_LRS"),
+            AzureStorageType::PremiumSSD => write!(f, "Premium_LRS"),
+            AzureStorageType::StandardHDD => write!(f, "Standard_LRS"),
+            AzureStorageType::UltraSSD => write!(f, "UltraSSD_LRS"),
+        }
+    }
+}
+
+impl AzureStorageType {
+    pub fn to_k8s_storage_class(&self) -> String {
+        match self {
+            AzureStorageType::StandardSSD => "azure-standard-ssd",
+            AzureStorageType::PremiumSSD => "azure-premium-ssd",
+            AzureStorageType::StandardHDD => "azure-standard-hdd",
+            AzureStorageType::UltraSSD => "azure-ultra-ssd",
+        }
+        .to_string()
+    }
+}
+
+pub struct AzureAppExtraSettings {}
+pub struct AzureDbExtraSettings {}
+pub struct AzureRouterExtraSettings {}
+
+impl CloudProvider for AZURE {
+    type AppExtraSettings = AzureAppExtraSettings;
+    type DbExtraSettings = AzureDbExtraSettings;
+    type RouterExtraSettings = AzureRouterExtraSettings;
+    type StorageTypes = AzureStorageType;
+
+    fn cloud_provider() -> Kind {
+        Kind::Azure
+    }
+
+    fn short_name() -> &'static str {
+        "Azure"
+    }
+
+    fn full_name() -> &'static str {
+        "Microsoft Azure"
+    }
+
+    fn registry_short_name() -> &'static str {
+        "ACR"
+    }
+
+    fn registry_full_name() -> &'static str {
+        "Azure Container Registry"
+    }
+
+    fn lib_directory_name() -> &'static str {
+        "azure"
+    }
+
+    fn loadbalancer_l4_annotations() -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    fn volume_snapshot_class() -> Option<&'static str> {
+        Some("azure-disk-snapshot")
+    }
+
+    fn storage_class(storage: &Storage<Self::StorageTypes>) -> String {
+        storage.storage_type.to_k8s_storage_class()
+    }
+}
@@ -13,9 +13,15 @@ use crate::io_models::application::{Port, Protocol};
 use crate::io_models::container::{ContainerAdvancedSettings, Registry};
 use crate::io_models::context::Context;
 use crate::kubers_utils::kube_get_resources_by_selector;
+use crate::models::deployment_strategy::{DeploymentTeraContext, TrackVersion};
+use crate::models::horizontal_autoscaler::HorizontalAutoscalerTeraContext;
+use crate::models::job_output::JobOutputSecretRef;
 use crate::models::probe::Probe;
 use crate::models::registry_image_source::RegistryImageSource;
 use crate::models::types::{CloudProvider, ToTeraContext};
+use crate::models::volume_snapshot::{
+    volume_snapshot_restores, VolumeSnapshotRestore, VolumeSnapshotTeraContext, VolumeStorageClass, DISK_ID_LABEL,
+};
 use crate::runtime::block_on;
 use crate::unit_conversion::extract_volume_size;
 use crate::utilities::to_short_id;
@@ -222,6 +228,11 @@ impl<T: CloudProvider> Container<T> {
         let environment = &target.environment;
         let kubernetes = &target.kubernetes;
         let registry_info = target.container_registry.registry_info();
+        // outputs of the jobs this service depends on have been recorded when those jobs ran
+        let (environment_variables, job_output_secrets) = target.job_outputs.service_environment_variables(
+            &self.environment_variables,
+            environment.dependencies.dependencies_of(&self.long_id),
+        );
         let ctx = ContainerTeraContext {
             organization_long_id: environment.organization_long_id,
             project_long_id: environment.project_long_id,
@@ -240,17 +251,7 @@ impl<T: CloudProvider> Container<T> {
                 r#type: "container",
                 name: self.kube_name().to_string(),
                 user_unsafe_name: self.name.clone(),
-                // FIXME: We mirror images to cluster private registry
-                image_full: format!(
-                    "{}/{}:{}",
-                    registry_info.endpoint.host_str().unwrap_or_default(),
-                    (registry_info.get_image_name)(&get_mirror_repository_name(
-                        self.long_id(),
-                        kubernetes.long_id(),
-                        &kubernetes.advanced_settings().registry_mirroring_mode,
-                    )),
-                    self.source.tag_for_mirror(&self.long_id)
-                ),
+                image_full: self.image_full(target),
                 image_tag: self.source.tag_for_mirror(&self.long_id),
                 version: self.service_version(),
                 command_args: self.command_args.clone(),
@@ -289,19 +290,77 @@ impl<T: CloudProvider> Container<T> {
                     secret_name: format!("{}-registry", self.kube_name()),
                     docker_json_config: Some(docker_json.to_string()),
                 }),
-            environment_variables: self.environment_variables.clone(),
+            environment_variables,
+            job_output_secrets,
             mounted_files: self.mounted_files.clone().into_iter().collect::<Vec<_>>(),
             resource_expiration_in_seconds: Some(kubernetes.advanced_settings().pleco_resources_ttl),
             loadbalancer_l4_annotations: T::loadbalancer_l4_annotations(),
+            deployment: self.deployment_tera_context(target),
+            autoscaler: HorizontalAutoscalerTeraContext::new(self.long_id, self.kube_name(), &self.advanced_settings),
+            volume_snapshot: VolumeSnapshotTeraContext::new(
+                self.kube_name(),
+                &self.storages,
+                T::volume_snapshot_class(),
+            ),
         };
 
         ctx
     }
 
+    fn image_full(&self, target: &DeploymentTarget) -> String {
+        let kubernetes = &target.kubernetes;
+        let registry_info = target.container_registry.registry_info();
+        // FIXME: We mirror images to cluster private registry
+        format!(
+            "{}/{}:{}",
+            registry_info.endpoint.host_str().unwrap_or_default(),
+            (registry_info.get_image_name)(&get_mirror_repository_name(
+                self.long_id(),
+                kubernetes.long_id(),
+                &kubernetes.advanced_settings().registry_mirroring_mode,
+            )),
+            self.source.tag_for_mirror(&self.long_id)
+        )
+    }
+
+    /// Version of the container being deployed
+    pub(crate) fn track_version(&self, target: &DeploymentTarget) -> TrackVersion {
+        TrackVersion {
+            image_full: self.image_full(target),
+            version: self.service_version(),
+        }
+    }
+
+    /// Deployment of the container once its rollout is over
+    pub(crate) fn deployment_tera_context(&self, target: &DeploymentTarget) -> DeploymentTeraContext {
+        DeploymentTeraContext::new(
+            self.advanced_settings.deployment_update_strategy_type,
+            self.kube_name(),
+            self.track_version(target),
+            self.min_instances,
+        )
+    }
+
     pub fn is_stateful(&self) -> bool {
         !self.storages.is_empty()
     }
 
+    /// Storages requested to be recreated from one of their snapshots
+    pub fn volume_snapshot_restores(&self) -> Vec<VolumeSnapshotRestore> {
+        volume_snapshot_restores(&self.storages)
+    }
+
+    /// StorageClass of the volume claims of each storage
+    pub fn volume_storage_classes(&self) -> Vec<VolumeStorageClass> {
+        self.storages
+            .iter()
+            .map(|storage| VolumeStorageClass {
+                pvc_selector: format!("{DISK_ID_LABEL}={}", storage.long_id),
+                storage_class: T::storage_class(storage),
+            })
+            .collect()
+    }
+
     pub fn service_type(&self) -> ServiceType {
         ServiceType::Container
     }
@@ -318,6 +377,10 @@ impl<T: CloudProvider> Container<T> {
         &self.action
     }
 
+    pub fn min_instances(&self) -> u32 {
+        self.min_instances
+    }
+
     pub fn publicly_accessible(&self) -> bool {
         self.public_ports().count() > 0
     }
@@ -416,6 +479,7 @@ pub trait ContainerService: Service + DeploymentAction + ToTeraContext + Send {
     fn advanced_settings(&self) -> &ContainerAdvancedSettings;
     fn image_full(&self) -> String;
     fn startup_timeout(&self) -> Duration;
+    fn helm_release_name(&self) -> String;
     fn as_deployment_action(&self) -> &dyn DeploymentAction;
 }
 
@@ -458,6 +522,10 @@ where
         Duration::from_secs(startup_timeout as u64)
     }
 
+    fn helm_release_name(&self) -> String {
+        Container::helm_release_name(self)
+    }
+
     fn as_deployment_action(&self) -> &dyn DeploymentAction {
         self
     }
@@ -526,9 +594,13 @@ pub(super) struct ContainerTeraContext {
     pub(super) service: ServiceTeraContext,
     pub(super) registry: Option<RegistryTeraContext>,
     pub(super) environment_variables: Vec<EnvironmentVariable>,
+    pub(super) job_output_secrets: Vec<JobOutputSecretRef>,
     pub(super) mounted_files: Vec<MountedFile>,
     pub(super) resource_expiration_in_seconds: Option<i32>,
     pub(super) loadbalancer_l4_annotations: &'static [(&'static str, &'static str)],
+    pub(super) deployment: DeploymentTeraContext,
+    pub(super) autoscaler: HorizontalAutoscalerTeraContext,
+    pub(super) volume_snapshot: Option<VolumeSnapshotTeraContext>,
 }
 
 pub fn get_container_with_invalid_storage_size<T: CloudProvider>(
@@ -9,8 +9,9 @@ use crate::deployment_action::DeploymentAction;
 use crate::errors::{CommandError, EngineError};
 use crate::events::{EnvironmentStep, EventDetails, Stage, Transmitter};
 use crate::io_models::context::Context;
-use crate::io_models::database::DatabaseOptions;
+use crate::io_models::database::{DatabaseBackup, DatabaseOptions, DatabaseRestore};
 use crate::kubers_utils::kube_get_resources_by_selector;
+use crate::models::database_backup::{BackedUpDatabase, DatabaseBackupTeraContext, DatabaseRestoreTeraContext};
 use crate::models::database_utils::{
     is_allowed_containered_mongodb_version, is_allowed_containered_mysql_version,
     is_allowed_containered_postgres_version, is_allowed_containered_redis_version,
@@ -26,6 +27,8 @@ use std::sync::Arc;
 use tera::Context as TeraContext;
 use uuid::Uuid;
 
+const CONTAINER_DATABASE_REGISTRY_NAME: &str = "public.ecr.aws";
+
 /////////////////////////////////////////////////////////////////
 // Database mode
 pub trait DatabaseInstanceType: Send + Sync {
@@ -356,8 +359,8 @@ impl<C: CloudProvider, T: DatabaseType<C, Container>> Database<C, Container, T>
         let container_database_publicly_accessible = !cluster_denied_public_access && self.publicly_accessible;
 
         // repository and image location
-        let registry_name = "public.ecr.aws";
-        let repository_name = format!("r3m4q3r9/pub-mirror-{}", T::db_type().to_string().to_lowercase());
+        let registry_name = CONTAINER_DATABASE_REGISTRY_NAME;
+        let repository_name = Self::container_repository_name();
         let repository_name_minideb = "r3m4q3r9/pub-mirror-minideb".to_string();
         let repository_name_bitnami_shell = "r3m4q3r9/pub-mirror-bitnami-shell".to_string();
         context.insert("registry_name", registry_name);
@@ -411,6 +414,10 @@ impl<C: CloudProvider, T: DatabaseType<C, Container>> Database<C, Container, T>
         Ok(context)
     }
 
+    fn container_repository_name() -> String {
+        format!("r3m4q3r9/pub-mirror-{}", T::db_type().to_string().to_lowercase())
+    }
+
     fn get_version(&self, event_details: EventDetails) -> Result<ServiceVersionCheckResult, Box<EngineError>> {
         let fn_version = match T::db_type() {
             service::DatabaseType::PostgreSQL => is_allowed_containered_postgres_version,
@@ -429,6 +436,67 @@ impl<C: CloudProvider, T: DatabaseType<C, Container>> Database<C, Container, T>
     }
 }
 
+// Backups of container databases
+impl<C: CloudProvider, T: DatabaseType<C, Container, DatabaseOptions = DatabaseOptions>> Database<C, Container, T> {
+    pub fn backup(&self) -> Option<&DatabaseBackup> {
+        self.options.backup.as_ref()
+    }
+
+    pub fn restore(&self) -> Option<&DatabaseRestore> {
+        self.options.restore.as_ref()
+    }
+
+    pub fn backup_helm_release_name(&self) -> String {
+        format!("{}-backup-{}", T::lib_directory_name(), self.id)
+    }
+
+    pub fn backup_helm_chart_dir(&self) -> String {
+        format!("{}/common/charts/q-database-backup", self.lib_root_directory)
+    }
+
+    /// Tera context of the backup chart, with the restore job if a backup has to be restored during this deployment
+    pub fn to_backup_tera_context(
+        &self,
+        target: &DeploymentTarget,
+        backup: &DatabaseBackup,
+        restore_backup_key: Option<&str>,
+    ) -> Result<TeraContext, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::LoadConfiguration));
+        let mut context = self.to_tera_context_for_container(target, &self.options)?;
+        let version = self.get_version(event_details)?.matched_version().to_string();
+
+        let database = BackedUpDatabase {
+            image: format!(
+                "{}/{}:{}",
+                CONTAINER_DATABASE_REGISTRY_NAME,
+                Self::container_repository_name(),
+                version
+            ),
+            host: format!("{}.{}.svc.cluster.local", self.fqdn_id, target.environment.namespace()),
+            port: self.private_port,
+            // mysql chart creates the database with the kube name
+            name: match T::db_type() {
+                service::DatabaseType::MySQL => self.kube_name.to_string(),
+                _ => self.name.to_string(),
+            },
+        };
+        let mut backup_context =
+            DatabaseBackupTeraContext::new(T::db_type(), &self.long_id, &self.kube_name, database, backup);
+        backup_context.restore = restore_backup_key.map(|backup_key| DatabaseRestoreTeraContext {
+            job_name: format!("{}-restore", self.kube_name),
+            backup_key: backup_key.to_string(),
+            // only redis is restored into its volume, claim name is the one of the redis chart statefulset
+            volume_claim_name: match backup_context.tool.restore_into_volume {
+                true => Some(format!("redis-data-{}-master-0", self.kube_name)),
+                false => None,
+            },
+        });
+        context.insert("backup", &backup_context);
+
+        Ok(context)
+    }
+}
+
 // methods for all Managed databases
 impl<C: CloudProvider, T: DatabaseType<C, Managed>> Database<C, Managed, T> {
     pub fn helm_chart_external_name_service_dir(&self) -> String {
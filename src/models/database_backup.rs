@@ -0,0 +1,245 @@
+use crate::cloud_provider::service::DatabaseType;
+use crate::io_models::database::{DatabaseBackup, DatabaseRestore};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use derivative::Derivative;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Backups are stored as `<database long id>/<timestamp>.<extension>`, timestamps sort in chronological order
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Image of the containers uploading backups and pruning the ones older than the retention, or downloading the backup to restore
+const BACKUP_STORAGE_CLIENT_IMAGE: &str = "public.ecr.aws/aws-cli/aws-cli:2.13.25";
+
+/// How a database is dumped and restored.
+/// Commands run in the database image, with `DATABASE_*` env vars and `BACKUP_FILE` set.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseBackupTool {
+    /// User created by the database chart with all privileges
+    pub admin_user: &'static str,
+    pub file_extension: &'static str,
+    pub backup_command: &'static str,
+    pub restore_command: &'static str,
+    /// Restore is done by copying the dump into the database volume, the database has to be stopped
+    pub restore_into_volume: bool,
+}
+
+impl DatabaseBackupTool {
+    pub fn new(db_type: DatabaseType) -> Self {
+        match db_type {
+            DatabaseType::PostgreSQL => DatabaseBackupTool {
+                admin_user: "postgres",
+                file_extension: "dump",
+                backup_command: "PGPASSWORD=\"$DATABASE_PASSWORD\" pg_dump -h \"$DATABASE_HOST\" -p \"$DATABASE_PORT\" -U \"$DATABASE_USER\" -Fc -d \"$DATABASE_NAME\" -f \"$BACKUP_FILE\"",
+                restore_command: "PGPASSWORD=\"$DATABASE_PASSWORD\" pg_restore -h \"$DATABASE_HOST\" -p \"$DATABASE_PORT\" -U \"$DATABASE_USER\" --clean --if-exists --no-owner -d \"$DATABASE_NAME\" \"$BACKUP_FILE\"",
+                restore_into_volume: false,
+            },
+            DatabaseType::MySQL => DatabaseBackupTool {
+                admin_user: "root",
+                file_extension: "sql",
+                backup_command: "mysqldump -h \"$DATABASE_HOST\" -P \"$DATABASE_PORT\" -u \"$DATABASE_USER\" -p\"$DATABASE_PASSWORD\" --single-transaction --routines --triggers --databases \"$DATABASE_NAME\" > \"$BACKUP_FILE\"",
+                restore_command: "mysql -h \"$DATABASE_HOST\" -P \"$DATABASE_PORT\" -u \"$DATABASE_USER\" -p\"$DATABASE_PASSWORD\" < \"$BACKUP_FILE\"",
+                restore_into_volume: false,
+            },
+            // credentials are percent-encoded in the connection string, they may contain `@`, `:` or `/`
+            DatabaseType::MongoDB => DatabaseBackupTool {
+                admin_user: "root",
+                file_extension: "archive.gz",
+                backup_command: "urlencode() { local LC_ALL=C s=\"$1\" c i; for ((i = 0; i < ${#s}; i++)); do c=\"${s:i:1}\"; case \"$c\" in [a-zA-Z0-9.~_-]) printf '%s' \"$c\" ;; *) printf '%%%02X' \"'$c\" ;; esac; done; }; mongodump --uri \"mongodb://$(urlencode \"$DATABASE_USER\"):$(urlencode \"$DATABASE_PASSWORD\")@$DATABASE_HOST:$DATABASE_PORT/?authSource=admin\" --gzip --archive=\"$BACKUP_FILE\"",
+                restore_command: "urlencode() { local LC_ALL=C s=\"$1\" c i; for ((i = 0; i < ${#s}; i++)); do c=\"${s:i:1}\"; case \"$c\" in [a-zA-Z0-9.~_-]) printf '%s' \"$c\" ;; *) printf '%%%02X' \"'$c\" ;; esac; done; }; mongorestore --uri \"mongodb://$(urlencode \"$DATABASE_USER\"):$(urlencode \"$DATABASE_PASSWORD\")@$DATABASE_HOST:$DATABASE_PORT/?authSource=admin\" --drop --gzip --archive=\"$BACKUP_FILE\"",
+                restore_into_volume: false,
+            },
+            // --rdb triggers a BGSAVE on the server and streams the resulting RDB file.
+            // An RDB file cannot be loaded remotely, so it is copied into the data volume and loaded at startup
+            // (append only files are removed, otherwise they would take precedence over the RDB file).
+            DatabaseType::Redis => DatabaseBackupTool {
+                admin_user: "default",
+                file_extension: "rdb",
+                backup_command: "REDISCLI_AUTH=\"$DATABASE_PASSWORD\" redis-cli -h \"$DATABASE_HOST\" -p \"$DATABASE_PORT\" --rdb \"$BACKUP_FILE\"",
+                restore_command: "rm -rf \"$DATABASE_DATA_DIR\"/appendonlydir \"$DATABASE_DATA_DIR\"/appendonly.aof && cp \"$BACKUP_FILE\" \"$DATABASE_DATA_DIR\"/dump.rdb",
+                restore_into_volume: true,
+            },
+        }
+    }
+}
+
+pub fn backup_key_prefix(database_long_id: &Uuid) -> String {
+    format!("{database_long_id}/")
+}
+
+/// Time a backup has been taken at, from its object key
+pub fn backup_time(backup_key: &str) -> Option<DateTime<Utc>> {
+    let file_name = backup_key.rsplit('/').next()?;
+    let timestamp = file_name.split('.').next()?;
+
+    NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT)
+        .ok()
+        .map(|t| Utc.from_utc_datetime(&t))
+}
+
+/// Backup to restore among the existing ones: the requested key, or the latest backup taken before the point in time
+pub fn select_backup<'a>(backup_keys: &'a [String], restore: &DatabaseRestore) -> Option<&'a String> {
+    if let Some(backup_key) = &restore.backup_key {
+        return backup_keys.iter().find(|key| *key == backup_key);
+    }
+
+    backup_keys
+        .iter()
+        .filter_map(|key| backup_time(key).map(|time| (time, key)))
+        .filter(|(time, _)| restore.point_in_time.map(|pit| *time <= pit).unwrap_or(true))
+        .max_by_key(|(time, _)| *time)
+        .map(|(_, key)| key)
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseRestoreTeraContext {
+    pub job_name: String,
+    pub backup_key: String,
+    /// Claim of the database volume, only set when the dump is restored into the volume
+    pub volume_claim_name: Option<String>,
+}
+
+/// Database the backups are taken from, reached through its in-cluster service
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct BackedUpDatabase {
+    pub image: String,
+    pub host: String,
+    pub port: u16,
+    pub name: String,
+}
+
+/// Backup cronjob (and restore job) of a container database, rendered by the q-database-backup chart
+#[derive(Serialize, Derivative, Clone, PartialEq, Eq)]
+#[derivative(Debug)]
+pub struct DatabaseBackupTeraContext {
+    pub name: String,
+    pub schedule: String,
+    pub timestamp_format: &'static str,
+    pub storage_client_image: &'static str,
+    pub storage_endpoint: Option<String>,
+    pub storage_region: String,
+    pub storage_access_key_id: String,
+    #[derivative(Debug = "ignore")]
+    pub storage_secret_access_key: String,
+    pub bucket_name: String,
+    pub key_prefix: String,
+    pub retention_in_days: u32,
+    pub database: BackedUpDatabase,
+    pub tool: DatabaseBackupTool,
+    pub restore: Option<DatabaseRestoreTeraContext>,
+}
+
+impl DatabaseBackupTeraContext {
+    pub fn new(
+        db_type: DatabaseType,
+        database_long_id: &Uuid,
+        kube_name: &str,
+        database: BackedUpDatabase,
+        backup: &DatabaseBackup,
+    ) -> Self {
+        DatabaseBackupTeraContext {
+            name: format!("{kube_name}-backup"),
+            schedule: backup.schedule.to_string(),
+            timestamp_format: BACKUP_TIMESTAMP_FORMAT,
+            storage_client_image: BACKUP_STORAGE_CLIENT_IMAGE,
+            storage_endpoint: backup.storage.endpoint.clone(),
+            storage_region: backup.storage.region.to_string(),
+            storage_access_key_id: backup.storage.access_key_id.to_string(),
+            storage_secret_access_key: backup.storage.secret_access_key.to_string(),
+            bucket_name: backup.storage.bucket_name.to_string(),
+            key_prefix: backup_key_prefix(database_long_id),
+            retention_in_days: backup.retention_in_days,
+            database,
+            tool: DatabaseBackupTool::new(db_type),
+            restore: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io_models::database::DatabaseRestore;
+    use crate::models::database_backup::{backup_time, select_backup};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_backup_time() {
+        assert_eq!(
+            backup_time("5d1f7d7a-7d4f-4a60-9bd5-0a1e1ef3f0a4/20231012T030000Z.dump"),
+            Some(Utc.with_ymd_and_hms(2023, 10, 12, 3, 0, 0).unwrap())
+        );
+        assert_eq!(
+            backup_time("db/20231012T030000Z.archive.gz"),
+            Some(Utc.with_ymd_and_hms(2023, 10, 12, 3, 0, 0).unwrap())
+        );
+        assert_eq!(backup_time("db/latest.dump"), None);
+    }
+
+    #[test]
+    fn test_select_backup() {
+        // setup:
+        struct TestCase<'a> {
+            restore: DatabaseRestore,
+            expected: Option<&'a str>,
+            description: &'a str,
+        }
+
+        let backup_keys = vec![
+            "db/20231010T030000Z.dump".to_string(),
+            "db/20231012T030000Z.dump".to_string(),
+            "db/20231011T030000Z.dump".to_string(),
+            "db/not-a-backup.txt".to_string(),
+        ];
+
+        let test_cases = vec![
+            TestCase {
+                restore: DatabaseRestore {
+                    backup_key: None,
+                    point_in_time: None,
+                },
+                expected: Some("db/20231012T030000Z.dump"),
+                description: "latest backup",
+            },
+            TestCase {
+                restore: DatabaseRestore {
+                    backup_key: None,
+                    point_in_time: Some(Utc.with_ymd_and_hms(2023, 10, 11, 12, 0, 0).unwrap()),
+                },
+                expected: Some("db/20231011T030000Z.dump"),
+                description: "latest backup before point in time",
+            },
+            TestCase {
+                restore: DatabaseRestore {
+                    backup_key: None,
+                    point_in_time: Some(Utc.with_ymd_and_hms(2023, 10, 1, 0, 0, 0).unwrap()),
+                },
+                expected: None,
+                description: "no backup before point in time",
+            },
+            TestCase {
+                restore: DatabaseRestore {
+                    backup_key: Some("db/20231010T030000Z.dump".to_string()),
+                    point_in_time: None,
+                },
+                expected: Some("db/20231010T030000Z.dump"),
+                description: "requested backup",
+            },
+            TestCase {
+                restore: DatabaseRestore {
+                    backup_key: Some("db/20220101T030000Z.dump".to_string()),
+                    point_in_time: None,
+                },
+                expected: None,
+                description: "requested backup doesn't exist",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = select_backup(&backup_keys, &tc.restore);
+
+            // verify:
+            assert_eq!(tc.expected, result.map(|k| k.as_str()), "case: {}", tc.description);
+        }
+    }
+}
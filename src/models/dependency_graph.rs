@@ -0,0 +1,218 @@
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use uuid::Uuid;
+
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+pub enum DependencyGraphError {
+    #[error("Dependency cycle detected between services: {}", cycle.iter().join(" -> "))]
+    CycleDetected { cycle: Vec<Uuid> },
+}
+
+/// Directed graph of the dependencies between the services of an environment.
+/// An edge `A -> B` means that service `A` must wait for service `B` to be deployed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ServiceDependencyGraph {
+    dependencies: BTreeMap<Uuid, BTreeSet<Uuid>>,
+}
+
+impl ServiceDependencyGraph {
+    /// Build the graph from a list of (service_id, depends_on) and validate it is acyclic.
+    /// Dependencies toward services that are not part of the graph are ignored, as those services
+    /// are not deployed during this run.
+    pub fn new(services: impl IntoIterator<Item = (Uuid, Vec<Uuid>)>) -> Result<Self, DependencyGraphError> {
+        let services: Vec<(Uuid, Vec<Uuid>)> = services.into_iter().collect();
+        let known_services: BTreeSet<Uuid> = services.iter().map(|(id, _)| *id).collect();
+
+        let mut dependencies: BTreeMap<Uuid, BTreeSet<Uuid>> = BTreeMap::new();
+        for (service_id, depends_on) in services {
+            dependencies.entry(service_id).or_default().extend(
+                depends_on
+                    .into_iter()
+                    .filter(|dependency_id| known_services.contains(dependency_id)),
+            );
+        }
+
+        let graph = ServiceDependencyGraph { dependencies };
+        match graph.find_cycle() {
+            Some(cycle) => Err(DependencyGraphError::CycleDetected { cycle }),
+            None => Ok(graph),
+        }
+    }
+
+    /// Services that must be processed before the given one
+    pub fn dependencies_of<'a>(&'a self, service_id: &Uuid) -> impl Iterator<Item = &'a Uuid> {
+        self.dependencies.get(service_id).into_iter().flatten()
+    }
+
+    /// Returns the graph with all edges inverted, used to tear down services (pause/delete)
+    /// in the opposite order of their deployment.
+    pub fn reversed(&self) -> ServiceDependencyGraph {
+        let mut dependencies: BTreeMap<Uuid, BTreeSet<Uuid>> =
+            self.dependencies.keys().map(|id| (*id, BTreeSet::new())).collect();
+        for (service_id, depends_on) in &self.dependencies {
+            for dependency_id in depends_on {
+                dependencies.entry(*dependency_id).or_default().insert(*service_id);
+            }
+        }
+
+        ServiceDependencyGraph { dependencies }
+    }
+
+    fn find_cycle(&self) -> Option<Vec<Uuid>> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum VisitState {
+            InProgress,
+            Done,
+        }
+
+        fn visit(
+            graph: &ServiceDependencyGraph,
+            service_id: Uuid,
+            states: &mut HashMap<Uuid, VisitState>,
+            path: &mut Vec<Uuid>,
+        ) -> Option<Vec<Uuid>> {
+            match states.get(&service_id) {
+                Some(VisitState::Done) => return None,
+                Some(VisitState::InProgress) => {
+                    let cycle_start = path.iter().position(|id| *id == service_id).unwrap_or(0);
+                    let mut cycle = path[cycle_start..].to_vec();
+                    cycle.push(service_id);
+                    return Some(cycle);
+                }
+                None => {}
+            }
+
+            states.insert(service_id, VisitState::InProgress);
+            path.push(service_id);
+            for dependency_id in graph.dependencies_of(&service_id) {
+                if let Some(cycle) = visit(graph, *dependency_id, states, path) {
+                    return Some(cycle);
+                }
+            }
+            path.pop();
+            states.insert(service_id, VisitState::Done);
+
+            None
+        }
+
+        let mut states: HashMap<Uuid, VisitState> = HashMap::with_capacity(self.dependencies.len());
+        let mut path: Vec<Uuid> = Vec::new();
+        self.dependencies
+            .keys()
+            .find_map(|service_id| visit(self, *service_id, &mut states, &mut path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has_no_dependency(graph: &ServiceDependencyGraph) -> bool {
+        graph.dependencies.values().all(|deps| deps.is_empty())
+    }
+
+    /// All services ordered such as every service comes after its dependencies.
+    /// Services without any relation keep their id ordering to be deterministic.
+    fn topological_order(graph: &ServiceDependencyGraph) -> Vec<Uuid> {
+        let mut remaining_dependencies: BTreeMap<Uuid, usize> =
+            graph.dependencies.iter().map(|(id, deps)| (*id, deps.len())).collect();
+        let reversed = graph.reversed();
+        let mut order = Vec::with_capacity(graph.dependencies.len());
+
+        while let Some(service_id) = remaining_dependencies
+            .iter()
+            .find(|(_, nb_deps)| **nb_deps == 0)
+            .map(|(id, _)| *id)
+        {
+            remaining_dependencies.remove(&service_id);
+            for dependent_id in reversed.dependencies_of(&service_id) {
+                if let Some(nb_deps) = remaining_dependencies.get_mut(dependent_id) {
+                    *nb_deps -= 1;
+                }
+            }
+            order.push(service_id);
+        }
+
+        order
+    }
+
+    #[test]
+    fn test_dependency_graph_ignores_unknown_services() {
+        // setup:
+        let api = Uuid::new_v4();
+        let unknown = Uuid::new_v4();
+
+        // execute:
+        let graph = ServiceDependencyGraph::new(vec![(api, vec![unknown])]).expect("graph should be valid");
+
+        // verify:
+        assert_eq!(graph.dependencies_of(&api).count(), 0);
+        assert!(has_no_dependency(&graph));
+    }
+
+    #[test]
+    fn test_dependency_graph_detects_cycles() {
+        // setup:
+        let db = Uuid::new_v4();
+        let migration = Uuid::new_v4();
+        let api = Uuid::new_v4();
+
+        struct TestCase {
+            services: Vec<(Uuid, Vec<Uuid>)>,
+            expect_cycle: bool,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                services: vec![(db, vec![]), (migration, vec![db]), (api, vec![migration, db])],
+                expect_cycle: false,
+            },
+            TestCase {
+                services: vec![(db, vec![db])],
+                expect_cycle: true,
+            },
+            TestCase {
+                services: vec![(db, vec![api]), (migration, vec![db]), (api, vec![migration])],
+                expect_cycle: true,
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = ServiceDependencyGraph::new(tc.services);
+
+            // verify:
+            match result {
+                Err(DependencyGraphError::CycleDetected { cycle }) => {
+                    assert!(tc.expect_cycle);
+                    assert_eq!(cycle.first(), cycle.last());
+                }
+                Ok(_) => assert!(!tc.expect_cycle),
+            }
+        }
+    }
+
+    #[test]
+    fn test_dependency_graph_topological_order() {
+        // setup:
+        let db = Uuid::new_v4();
+        let migration = Uuid::new_v4();
+        let api = Uuid::new_v4();
+        let front = Uuid::new_v4();
+        let graph = ServiceDependencyGraph::new(vec![
+            (front, vec![api]),
+            (api, vec![migration, db]),
+            (migration, vec![db]),
+            (db, vec![]),
+        ])
+        .expect("graph should be valid");
+
+        // execute:
+        let order = topological_order(&graph);
+        let reversed_order = topological_order(&graph.reversed());
+
+        // verify:
+        assert_eq!(order, vec![db, migration, api, front]);
+        assert_eq!(reversed_order, vec![front, api, migration, db]);
+    }
+}
@@ -0,0 +1,297 @@
+use crate::io_models::UpdateStrategy;
+use serde::Serialize;
+
+/// Kubernetes label set on pods (and on the router canary ingress) to tell apart the tracks of a service
+pub const DEPLOYMENT_TRACK_LABEL: &str = "qovery.com/deployment-track";
+
+/// A track is one Kubernetes deployment of a service.
+/// Services only have one track, except while a canary or blue/green rollout is in progress.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DeploymentTrack {
+    Stable,
+    Canary,
+    Blue,
+    Green,
+}
+
+impl DeploymentTrack {
+    pub fn from_label(value: &str) -> Option<DeploymentTrack> {
+        match value {
+            "stable" => Some(DeploymentTrack::Stable),
+            "canary" => Some(DeploymentTrack::Canary),
+            "blue" => Some(DeploymentTrack::Blue),
+            "green" => Some(DeploymentTrack::Green),
+            _ => None,
+        }
+    }
+
+    pub fn to_label(&self) -> &'static str {
+        match self {
+            DeploymentTrack::Stable => "stable",
+            DeploymentTrack::Canary => "canary",
+            DeploymentTrack::Blue => "blue",
+            DeploymentTrack::Green => "green",
+        }
+    }
+
+    /// Stable and blue tracks keep the service kube name, so switching an existing service to canary or blue/green
+    /// does not recreate its deployment (deployment selector being immutable).
+    pub fn deployment_name(&self, kube_name: &str) -> String {
+        match self {
+            DeploymentTrack::Stable | DeploymentTrack::Blue => kube_name.to_string(),
+            DeploymentTrack::Canary | DeploymentTrack::Green => format!("{}-{}", kube_name, self.to_label()),
+        }
+    }
+
+    /// The track a rollout is deploying the new version to
+    pub fn next_track(&self) -> DeploymentTrack {
+        match self {
+            DeploymentTrack::Blue => DeploymentTrack::Green,
+            DeploymentTrack::Green => DeploymentTrack::Blue,
+            DeploymentTrack::Stable => DeploymentTrack::Canary,
+            DeploymentTrack::Canary => DeploymentTrack::Stable,
+        }
+    }
+
+    /// Deployments created by a rollout get the track in their selector, while stable/blue ones keep the historical one
+    fn has_dedicated_selector(&self) -> bool {
+        matches!(self, DeploymentTrack::Canary | DeploymentTrack::Green)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrackVersion {
+    pub image_full: String,
+    pub version: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DeploymentTrackTeraContext {
+    pub name: String,
+    pub track: Option<DeploymentTrack>,
+    pub dedicated_selector: bool,
+    pub image_full: String,
+    pub version: String,
+    pub replicas: Option<u32>,
+}
+
+impl DeploymentTrackTeraContext {
+    fn new(kube_name: &str, track: Option<DeploymentTrack>, version: TrackVersion, replicas: Option<u32>) -> Self {
+        DeploymentTrackTeraContext {
+            name: track
+                .map(|t| t.deployment_name(kube_name))
+                .unwrap_or_else(|| kube_name.to_string()),
+            track,
+            dedicated_selector: track.map(|t| t.has_dedicated_selector()).unwrap_or(false),
+            image_full: version.image_full,
+            version: version.version,
+            replicas,
+        }
+    }
+}
+
+/// Deployments of a service rendered by the q-container chart
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DeploymentTeraContext {
+    /// Kubernetes strategy used to update each deployment
+    pub strategy: &'static str,
+    pub tracks: Vec<DeploymentTrackTeraContext>,
+    /// Track the main service is selecting, all pods of the service are selected when not set
+    pub service_track: Option<DeploymentTrack>,
+    /// Render the service the router canary ingress is sending traffic to
+    pub canary_service: bool,
+    /// Deployment scaled by the horizontal pod autoscaler
+    pub autoscaled_deployment_name: String,
+}
+
+impl DeploymentTeraContext {
+    /// Deployment of the service once no rollout is in progress
+    pub fn new(strategy: UpdateStrategy, kube_name: &str, version: TrackVersion, min_instances: u32) -> Self {
+        match strategy {
+            UpdateStrategy::RollingUpdate | UpdateStrategy::Recreate => DeploymentTeraContext {
+                strategy: strategy.to_k8s_deployment_strategy(),
+                tracks: vec![DeploymentTrackTeraContext::new(kube_name, None, version, None)],
+                service_track: None,
+                canary_service: false,
+                autoscaled_deployment_name: kube_name.to_string(),
+            },
+            UpdateStrategy::Canary => Self::canary(kube_name, version, None),
+            UpdateStrategy::BlueGreen => {
+                Self::blue_green(kube_name, DeploymentTrack::Blue, version, None, min_instances)
+            }
+        }
+    }
+
+    /// Canary deployment: the stable version keeps receiving the traffic, the canary one only receives the share
+    /// the router canary ingress is configured with
+    pub fn canary(kube_name: &str, stable: TrackVersion, canary: Option<(TrackVersion, u32)>) -> Self {
+        let mut tracks = vec![DeploymentTrackTeraContext::new(
+            kube_name,
+            Some(DeploymentTrack::Stable),
+            stable,
+            None,
+        )];
+        if let Some((canary, replicas)) = canary {
+            tracks.push(DeploymentTrackTeraContext::new(
+                kube_name,
+                Some(DeploymentTrack::Canary),
+                canary,
+                Some(replicas),
+            ));
+        }
+
+        DeploymentTeraContext {
+            strategy: UpdateStrategy::Canary.to_k8s_deployment_strategy(),
+            tracks,
+            service_track: Some(DeploymentTrack::Stable),
+            canary_service: true,
+            autoscaled_deployment_name: DeploymentTrack::Stable.deployment_name(kube_name),
+        }
+    }
+
+    /// Blue/green deployment: the live color receives all the traffic, the other one (if any) is waiting for the switch
+    pub fn blue_green(
+        kube_name: &str,
+        live_color: DeploymentTrack,
+        live: TrackVersion,
+        next: Option<TrackVersion>,
+        min_instances: u32,
+    ) -> Self {
+        // Replicas are always set for blue/green, otherwise the next color would start with a single pod
+        let mut tracks = vec![DeploymentTrackTeraContext::new(
+            kube_name,
+            Some(live_color),
+            live,
+            Some(min_instances),
+        )];
+        if let Some(next) = next {
+            tracks.push(DeploymentTrackTeraContext::new(
+                kube_name,
+                Some(live_color.next_track()),
+                next,
+                Some(min_instances),
+            ));
+        }
+
+        DeploymentTeraContext {
+            strategy: UpdateStrategy::BlueGreen.to_k8s_deployment_strategy(),
+            tracks,
+            service_track: Some(live_color),
+            canary_service: false,
+            autoscaled_deployment_name: live_color.deployment_name(kube_name),
+        }
+    }
+}
+
+/// Number of canary pods needed to hold the canary traffic share, there is always at least one
+pub fn canary_replicas(min_instances: u32, traffic_percent: u32) -> u32 {
+    (min_instances * traffic_percent.min(100)).div_ceil(100).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io_models::UpdateStrategy;
+    use crate::models::deployment_strategy::{
+        canary_replicas, DeploymentTeraContext, DeploymentTrack, DeploymentTrackTeraContext, TrackVersion,
+    };
+
+    fn version(tag: &str) -> TrackVersion {
+        TrackVersion {
+            image_full: format!("registry/app:{tag}"),
+            version: tag.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_deployment_track_names() {
+        assert_eq!(DeploymentTrack::Stable.deployment_name("app-z1"), "app-z1");
+        assert_eq!(DeploymentTrack::Canary.deployment_name("app-z1"), "app-z1-canary");
+        assert_eq!(DeploymentTrack::Blue.deployment_name("app-z1"), "app-z1");
+        assert_eq!(DeploymentTrack::Green.deployment_name("app-z1"), "app-z1-green");
+        assert_eq!(DeploymentTrack::Blue.next_track(), DeploymentTrack::Green);
+        assert_eq!(DeploymentTrack::Green.next_track(), DeploymentTrack::Blue);
+        assert_eq!(DeploymentTrack::from_label("green"), Some(DeploymentTrack::Green));
+        assert_eq!(DeploymentTrack::from_label("purple"), None);
+    }
+
+    #[test]
+    fn test_canary_replicas() {
+        assert_eq!(canary_replicas(1, 10), 1);
+        assert_eq!(canary_replicas(10, 10), 1);
+        assert_eq!(canary_replicas(10, 25), 3);
+        assert_eq!(canary_replicas(4, 50), 2);
+        assert_eq!(canary_replicas(4, 150), 4);
+        assert_eq!(canary_replicas(3, 0), 1);
+    }
+
+    #[test]
+    fn test_deployment_tera_context() {
+        // setup:
+        struct TestCase {
+            input: DeploymentTeraContext,
+            expected_tracks: Vec<DeploymentTrackTeraContext>,
+            expected_service_track: Option<DeploymentTrack>,
+            expected_autoscaled_deployment_name: &'static str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: DeploymentTeraContext::new(UpdateStrategy::RollingUpdate, "app", version("v2"), 2),
+                expected_tracks: vec![DeploymentTrackTeraContext {
+                    name: "app".to_string(),
+                    track: None,
+                    dedicated_selector: false,
+                    image_full: "registry/app:v2".to_string(),
+                    version: "v2".to_string(),
+                    replicas: None,
+                }],
+                expected_service_track: None,
+                expected_autoscaled_deployment_name: "app",
+            },
+            TestCase {
+                input: DeploymentTeraContext::canary("app", version("v1"), Some((version("v2"), 1))),
+                expected_tracks: vec![
+                    DeploymentTrackTeraContext {
+                        name: "app".to_string(),
+                        track: Some(DeploymentTrack::Stable),
+                        dedicated_selector: false,
+                        image_full: "registry/app:v1".to_string(),
+                        version: "v1".to_string(),
+                        replicas: None,
+                    },
+                    DeploymentTrackTeraContext {
+                        name: "app-canary".to_string(),
+                        track: Some(DeploymentTrack::Canary),
+                        dedicated_selector: true,
+                        image_full: "registry/app:v2".to_string(),
+                        version: "v2".to_string(),
+                        replicas: Some(1),
+                    },
+                ],
+                expected_service_track: Some(DeploymentTrack::Stable),
+                expected_autoscaled_deployment_name: "app",
+            },
+            TestCase {
+                input: DeploymentTeraContext::blue_green("app", DeploymentTrack::Green, version("v2"), None, 2),
+                expected_tracks: vec![DeploymentTrackTeraContext {
+                    name: "app-green".to_string(),
+                    track: Some(DeploymentTrack::Green),
+                    dedicated_selector: true,
+                    image_full: "registry/app:v2".to_string(),
+                    version: "v2".to_string(),
+                    replicas: Some(2),
+                }],
+                expected_service_track: Some(DeploymentTrack::Green),
+                expected_autoscaled_deployment_name: "app-green",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute & verify:
+            assert_eq!(tc.input.tracks, tc.expected_tracks);
+            assert_eq!(tc.input.service_track, tc.expected_service_track);
+            assert_eq!(tc.input.autoscaled_deployment_name, tc.expected_autoscaled_deployment_name);
+        }
+    }
+}
@@ -1,4 +1,3 @@
-use itertools::Itertools;
 use std::fmt::{Display, Formatter};
 use std::net::Ipv4Addr;
 
@@ -13,8 +12,26 @@ pub trait ToHelmString {
     fn to_helm_format_string(&self) -> String;
 }
 
+/// Maximum length of a domain name, without its trailing dot (RFC 1035)
+const DOMAIN_MAX_LENGTH: usize = 253;
+/// Maximum length of a single label of a domain name (RFC 1035)
+const DOMAIN_LABEL_MAX_LENGTH: usize = 63;
+
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+pub enum DomainError {
+    #[error("Domain is empty")]
+    Empty,
+    #[error("Domain `{domain}` is longer than {DOMAIN_MAX_LENGTH} characters")]
+    TooLong { domain: String },
+    #[error("Domain `{domain}` is not a valid internationalized domain name")]
+    InvalidInternationalizedDomain { domain: String },
+    #[error("Domain `{domain}` has an invalid label `{label}`")]
+    InvalidLabel { domain: String, label: String },
+}
+
 /// Represents a domain, just plain domain, no protocol.
 /// eq. `test.com`, `sub.test.com`
+/// Internationalized domains are stored in their ASCII (punycode) form, eq. `café.fr` is `xn--caf-dma.fr`.
 #[derive(Clone)]
 pub struct Domain {
     raw: String,
@@ -22,21 +39,50 @@ pub struct Domain {
 }
 
 impl Domain {
+    /// Lenient version of `try_new`, a malformed domain is kept as is and is its own root domain
     pub fn new(raw: String) -> Self {
-        // TODO(benjaminch): This is very basic solution which doesn't take into account
-        // some edge cases such as: "test.co.uk" domains
-        let sep: &str = ".";
-        let items: Vec<String> = raw.split(sep).map(|e| e.to_string()).collect();
-        let items_count = raw.matches(sep).count() + 1;
-        let top_domain: String = match items_count > 2 {
-            true => items.iter().skip(items_count - 2).join("."),
-            false => items.iter().join("."),
+        Domain::try_new(raw.to_string()).unwrap_or(Domain {
+            root_domain: raw.to_string(),
+            raw,
+        })
+    }
+
+    /// Normalizes (lowercase, punycode, no trailing dot) and validates the domain.
+    /// Root domain is the registrable domain according to the Public Suffix List (private section included),
+    /// eq. `test.co.uk` for `sub.test.co.uk` and `qovery.github.io` for `www.qovery.github.io`.
+    pub fn try_new(raw: String) -> Result<Self, DomainError> {
+        let domain = raw.trim().trim_end_matches('.');
+        let (wildcard, domain) = match domain.strip_prefix("*.") {
+            Some(domain) => (true, domain),
+            None => (false, domain),
         };
+        if domain.is_empty() {
+            return Err(DomainError::Empty);
+        }
 
-        Domain {
-            root_domain: top_domain,
-            raw,
+        let ascii_domain = idna::domain_to_ascii(domain).map_err(|_| DomainError::InvalidInternationalizedDomain {
+            domain: raw.to_string(),
+        })?;
+        if ascii_domain.len() > DOMAIN_MAX_LENGTH {
+            return Err(DomainError::TooLong { domain: raw });
+        }
+        if let Some(label) = ascii_domain.split('.').find(|label| !is_valid_label(label)) {
+            return Err(DomainError::InvalidLabel {
+                label: label.to_string(),
+                domain: raw,
+            });
         }
+
+        // a domain being itself a public suffix (or a single label) has no registrable part, it is its own root
+        let root_domain = psl::domain_str(&ascii_domain).unwrap_or(&ascii_domain).to_string();
+
+        Ok(Domain {
+            raw: match wildcard {
+                true => format!("*.{ascii_domain}"),
+                false => ascii_domain,
+            },
+            root_domain,
+        })
     }
 
     pub fn new_with_subdomain(raw: String, sub_domain: String) -> Self {
@@ -65,6 +111,20 @@ impl Domain {
     fn is_wildcarded(&self) -> bool {
         self.raw.starts_with('*')
     }
+
+    /// Whether the domain is this one or one of its sub domains, labels are compared as a whole
+    /// eq. `api.test.com` is a sub domain of `test.com` but `mytest.com` is not.
+    pub fn is_sub_domain_of(&self, other: &Domain) -> bool {
+        self.raw == other.raw || self.raw.ends_with(&format!(".{}", other.raw))
+    }
+}
+
+fn is_valid_label(label: &str) -> bool {
+    !label.is_empty()
+        && label.len() <= DOMAIN_LABEL_MAX_LENGTH
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
 }
 
 impl Display for Domain {
@@ -93,7 +153,7 @@ impl ToTerraformString for Ipv4Addr {
 
 #[cfg(test)]
 mod tests {
-    use crate::models::domain::Domain;
+    use crate::models::domain::{Domain, DomainError};
 
     #[test]
     fn test_domain_new() {
@@ -126,9 +186,33 @@ mod tests {
             },
             TestCase {
                 input: "test.co.uk".to_string(),
-                expected_root_domain_output: "co.uk".to_string(), // TODO(benjamin) => Should be test.co.uk in the future
+                expected_root_domain_output: "test.co.uk".to_string(),
                 expected_wildcarded_output: "*.test.co.uk".to_string(),
-                description: "broken edge case domain with special tld input",
+                description: "domain with multi labels public suffix input",
+            },
+            TestCase {
+                input: "sub.test.com.au".to_string(),
+                expected_root_domain_output: "test.com.au".to_string(),
+                expected_wildcarded_output: "*.sub.test.com.au".to_string(),
+                description: "sub domain with multi labels public suffix input",
+            },
+            TestCase {
+                input: "www.qovery.github.io".to_string(),
+                expected_root_domain_output: "qovery.github.io".to_string(),
+                expected_wildcarded_output: "*.www.qovery.github.io".to_string(),
+                description: "sub domain with private public suffix input",
+            },
+            TestCase {
+                input: "co.uk".to_string(),
+                expected_root_domain_output: "co.uk".to_string(),
+                expected_wildcarded_output: "*.co.uk".to_string(),
+                description: "public suffix input",
+            },
+            TestCase {
+                input: "Sub.Café.fr.".to_string(),
+                expected_root_domain_output: "xn--caf-dma.fr".to_string(),
+                expected_wildcarded_output: "*.sub.xn--caf-dma.fr".to_string(),
+                description: "internationalized domain input with uppercase and trailing dot",
             },
             TestCase {
                 input: "test".to_string(),
@@ -177,4 +261,75 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_domain_try_new_errors() {
+        // setup:
+        struct TestCase<'a> {
+            input: &'a str,
+            expected: DomainError,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: " ",
+                expected: DomainError::Empty,
+            },
+            TestCase {
+                input: "test..com",
+                expected: DomainError::InvalidLabel {
+                    domain: "test..com".to_string(),
+                    label: "".to_string(),
+                },
+            },
+            TestCase {
+                input: "-test.com",
+                expected: DomainError::InvalidLabel {
+                    domain: "-test.com".to_string(),
+                    label: "-test".to_string(),
+                },
+            },
+            TestCase {
+                input: "sub.*.test.com",
+                expected: DomainError::InvalidLabel {
+                    domain: "sub.*.test.com".to_string(),
+                    label: "*".to_string(),
+                },
+            },
+            TestCase {
+                input: "https://test.com",
+                expected: DomainError::InvalidLabel {
+                    domain: "https://test.com".to_string(),
+                    label: "https://test".to_string(),
+                },
+            },
+            TestCase {
+                input: &format!("{}.com", "a".repeat(64)),
+                expected: DomainError::InvalidLabel {
+                    domain: format!("{}.com", "a".repeat(64)),
+                    label: "a".repeat(64),
+                },
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = Domain::try_new(tc.input.to_string());
+
+            // verify:
+            assert_eq!(Some(tc.expected), result.err(), "case: '{}'", tc.input);
+        }
+    }
+
+    #[test]
+    fn test_domain_is_sub_domain_of() {
+        // setup:
+        let cluster_domain = Domain::new("cluster.qovery.io".to_string());
+
+        // execute & verify:
+        assert!(Domain::new("cluster.qovery.io".to_string()).is_sub_domain_of(&cluster_domain));
+        assert!(Domain::new("app.cluster.qovery.io".to_string()).is_sub_domain_of(&cluster_domain));
+        assert!(!Domain::new("mycluster.qovery.io".to_string()).is_sub_domain_of(&cluster_domain));
+        assert!(!Domain::new("qovery.io".to_string()).is_sub_domain_of(&cluster_domain));
+    }
 }
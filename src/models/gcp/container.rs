@@ -2,7 +2,7 @@ use crate::cloud_provider::models::StorageDataTemplate;
 use crate::cloud_provider::DeploymentTarget;
 use crate::errors::EngineError;
 use crate::models::container::Container;
-use crate::models::types::{ToTeraContext, GCP};
+use crate::models::types::{CloudProvider, ToTeraContext, GCP};
 use tera::Context as TeraContext;
 
 impl ToTeraContext for Container<GCP> {
@@ -15,7 +15,7 @@ impl ToTeraContext for Container<GCP> {
                 id: s.id.clone(),
                 long_id: s.long_id,
                 name: s.name.clone(),
-                storage_type: s.storage_type.to_k8s_storage_class(),
+                storage_type: GCP::storage_class(s),
                 size_in_gib: s.size_in_gib,
                 mount_point: s.mount_point.clone(),
                 snapshot_retention_in_days: s.snapshot_retention_in_days,
@@ -1,12 +1,26 @@
 #![allow(clippy::redundant_closure)]
 
+use crate::cloud_provider::gcp::database_instance_type::GcpDatabaseInstanceType;
+use crate::cloud_provider::service::{
+    check_service_version, default_tera_context, get_tfstate_name, get_tfstate_suffix, Service,
+    ServiceVersionCheckResult,
+};
 use crate::cloud_provider::{service, DeploymentTarget};
-use crate::errors::EngineError;
-use crate::models::database::{Container, Database, DatabaseType, MongoDB, MySQL, PostgresSQL, Redis};
+use crate::errors::{CommandError, EngineError};
+use crate::events::{EventDetails, Stage};
+use crate::models::database::{
+    Container, Database, DatabaseError, DatabaseType, Managed, MongoDB, MySQL, PostgresSQL, Redis,
+};
 
 use crate::io_models::database::DatabaseOptions;
-use crate::models::types::{ToTeraContext, GCP};
+use crate::models::gcp::database_utils::{
+    is_allowed_managed_mysql_version, is_allowed_managed_postgres_version, is_allowed_managed_redis_version,
+    to_gcp_database_version,
+};
+use crate::models::gcp::CLOUD_SQL_ACTIVATION_POLICY_RUNNING;
+use crate::models::types::{CloudProvider, ToTeraContext, VersionsNumber, GCP};
 use crate::unit_conversion::cpu_string_to_float;
+use std::str::FromStr;
 use tera::Context as TeraContext;
 
 /////////////////////////////////////////////////////////////////
@@ -159,8 +173,152 @@ impl DatabaseType<GCP, Container> for MongoDB {
     }
 }
 
+/////////////////////////////////////////////////////////////////
+// MANAGED
+impl DatabaseType<GCP, Managed> for PostgresSQL {
+    type DatabaseOptions = DatabaseOptions;
+
+    fn short_name() -> &'static str {
+        "Postgres Managed"
+    }
+    fn lib_directory_name() -> &'static str {
+        "postgresql"
+    }
+    fn db_type() -> service::DatabaseType {
+        service::DatabaseType::PostgreSQL
+    }
+}
+
+impl DatabaseType<GCP, Managed> for MySQL {
+    type DatabaseOptions = DatabaseOptions;
+
+    fn short_name() -> &'static str {
+        "MySQL Managed"
+    }
+    fn lib_directory_name() -> &'static str {
+        "mysql"
+    }
+    fn db_type() -> service::DatabaseType {
+        service::DatabaseType::MySQL
+    }
+}
+
+impl DatabaseType<GCP, Managed> for Redis {
+    type DatabaseOptions = DatabaseOptions;
+
+    fn short_name() -> &'static str {
+        "Redis Managed"
+    }
+    fn lib_directory_name() -> &'static str {
+        "redis"
+    }
+    fn db_type() -> service::DatabaseType {
+        service::DatabaseType::Redis
+    }
+}
+
+// MongoDB is not supported as managed db
+
+impl<T: DatabaseType<GCP, Managed>> Database<GCP, Managed, T>
+where
+    Database<GCP, Managed, T>: Service,
+{
+    fn get_version_gcp_managed(
+        &self,
+        event_details: EventDetails,
+    ) -> Result<ServiceVersionCheckResult, Box<EngineError>> {
+        let fn_version = match T::db_type() {
+            service::DatabaseType::PostgreSQL => is_allowed_managed_postgres_version,
+            service::DatabaseType::MySQL => is_allowed_managed_mysql_version,
+            service::DatabaseType::Redis => is_allowed_managed_redis_version,
+            service::DatabaseType::MongoDB => |_: &VersionsNumber| {
+                Err(DatabaseError::UnsupportedManagedMode(
+                    service::DatabaseType::MongoDB,
+                    GCP::full_name().to_string(),
+                ))
+            },
+        };
+
+        check_service_version(
+            fn_version(&self.version)
+                .map(|_| self.version.to_string())
+                .map_err(CommandError::from),
+            self,
+            event_details,
+        )
+    }
+
+    fn to_tera_context_for_gcp_managed(
+        &self,
+        target: &DeploymentTarget,
+        options: &DatabaseOptions,
+    ) -> Result<TeraContext, Box<EngineError>> {
+        let event_details = self.get_event_details(Stage::Environment(self.action.to_environment_step()));
+        let kubernetes = target.kubernetes;
+        let environment = target.environment;
+        let mut context = default_tera_context(self, kubernetes, environment);
+
+        // we need the kubernetes config file to store tfstates file in kube secrets
+        let kube_config_file_path = kubernetes.get_kubeconfig_file_path()?;
+        context.insert("kubeconfig_path", &kube_config_file_path);
+        context.insert("namespace", environment.namespace());
+
+        let version = self.get_version_gcp_managed(event_details.clone())?.matched_version();
+        context.insert("version", &version.to_string());
+        context.insert("database_version", &to_gcp_database_version(T::db_type(), &version));
+
+        for (k, v) in kubernetes.cloud_provider().tera_context_environment_variables() {
+            context.insert(k, v);
+        }
+
+        // instances get a private IP in the cluster VPC, the network is retrieved from the GKE cluster
+        context.insert("kubernetes_cluster_id", kubernetes.id());
+        context.insert("kubernetes_cluster_name", &kubernetes.cluster_name());
+        context.insert("fqdn_id", self.fqdn_id.as_str());
+        context.insert("fqdn", self.fqdn(target, &self.fqdn).as_str());
+        context.insert("service_name", self.fqdn_id.as_str());
+        context.insert("database_name", self.kube_name());
+        context.insert("database_login", options.login.as_str());
+        context.insert("database_password", options.password.as_str());
+        context.insert("database_port", &self.private_port);
+        context.insert("database_disk_size_in_gib", &options.disk_size_in_gib);
+        if let Some(i) = &self.database_instance_type {
+            context.insert("database_instance_type", i.to_cloud_provider_format().as_str());
+            // Memorystore instances are sized by memory and not by machine type
+            if let Some(memory_size) = GcpDatabaseInstanceType::from_str(&i.to_cloud_provider_format())
+                .ok()
+                .and_then(|i| i.redis_memory_size_in_gib())
+            {
+                context.insert("database_memory_size_in_gib", &memory_size);
+            }
+        }
+        context.insert("database_id", &self.id());
+        context.insert("tfstate_suffix_name", &get_tfstate_suffix(self));
+        context.insert("tfstate_name", &get_tfstate_name(self));
+        context.insert("publicly_accessible", &options.publicly_accessible);
+        context.insert("activate_high_availability", &options.activate_high_availability);
+        context.insert("activate_backups", &options.activate_backups);
+        context.insert("activation_policy", CLOUD_SQL_ACTIVATION_POLICY_RUNNING);
+        context.insert(
+            "resource_expiration_in_seconds",
+            &kubernetes.advanced_settings().pleco_resources_ttl,
+        );
+
+        Ok(context)
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////:
 // POSTGRES SQL
+impl ToTeraContext for Database<GCP, Managed, PostgresSQL>
+where
+    PostgresSQL: DatabaseType<GCP, Managed>,
+{
+    fn to_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, Box<EngineError>> {
+        self.to_tera_context_for_gcp_managed(target, &self.options)
+    }
+}
+
 impl ToTeraContext for Database<GCP, Container, PostgresSQL>
 where
     PostgresSQL: DatabaseType<GCP, Container>,
@@ -172,6 +330,15 @@ where
 
 ////////////////////////////////////////////////////////////////////////:
 // MySQL
+impl ToTeraContext for Database<GCP, Managed, MySQL>
+where
+    MySQL: DatabaseType<GCP, Managed>,
+{
+    fn to_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, Box<EngineError>> {
+        self.to_tera_context_for_gcp_managed(target, &self.options)
+    }
+}
+
 impl ToTeraContext for Database<GCP, Container, MySQL>
 where
     MySQL: DatabaseType<GCP, Container>,
@@ -194,6 +361,15 @@ where
 
 ////////////////////////////////////////////////////////////////////////:
 // Redis
+impl ToTeraContext for Database<GCP, Managed, Redis>
+where
+    Redis: DatabaseType<GCP, Managed>,
+{
+    fn to_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, Box<EngineError>> {
+        self.to_tera_context_for_gcp_managed(target, &self.options)
+    }
+}
+
 impl ToTeraContext for Database<GCP, Container, Redis>
 where
     Redis: DatabaseType<GCP, Container>,
@@ -0,0 +1,204 @@
+use crate::cloud_provider::service::DatabaseType;
+use crate::models::database::DatabaseError;
+use crate::models::types::VersionsNumber;
+use std::sync::Arc;
+
+pub(super) fn is_allowed_managed_postgres_version(requested_version: &VersionsNumber) -> Result<(), DatabaseError> {
+    // https://cloud.google.com/sql/docs/postgres/db-versions
+
+    // Allow only major from 11 to 16
+    if !&["11", "12", "13", "14", "15", "16"].contains(&requested_version.major.as_str()) {
+        return Err(DatabaseError::UnsupportedDatabaseVersion {
+            database_type: DatabaseType::PostgreSQL,
+            database_version: Arc::from(requested_version.to_string()),
+        });
+    }
+
+    // If we want to filter out some versions, we should filter those out here
+    // <-
+
+    Ok(())
+}
+
+pub(super) fn is_allowed_managed_mysql_version(requested_version: &VersionsNumber) -> Result<(), DatabaseError> {
+    // https://cloud.google.com/sql/docs/mysql/db-versions
+
+    // Allow only major 5 and 8
+    if !&["5", "8"].contains(&requested_version.major.as_str()) {
+        return Err(DatabaseError::UnsupportedDatabaseVersion {
+            database_type: DatabaseType::MySQL,
+            database_version: Arc::from(requested_version.to_string()),
+        });
+    }
+
+    // If we want to filter out some versions, we should filter those out here
+    // <-
+
+    Ok(())
+}
+
+pub(super) fn is_allowed_managed_redis_version(requested_version: &VersionsNumber) -> Result<(), DatabaseError> {
+    // https://cloud.google.com/memorystore/docs/redis/supported-versions
+
+    // Allow only major 6 and 7
+    if !&["6", "7"].contains(&requested_version.major.as_str()) {
+        return Err(DatabaseError::UnsupportedDatabaseVersion {
+            database_type: DatabaseType::Redis,
+            database_version: Arc::from(requested_version.to_string()),
+        });
+    }
+
+    // If we want to filter out some versions, we should filter those out here
+    // <-
+
+    Ok(())
+}
+
+/// Cloud SQL and Memorystore identify their engine versions with enums, e.q `POSTGRES_15` or `REDIS_7_0`.
+/// Requested version is expected to be allowed already.
+pub(super) fn to_gcp_database_version(database_type: DatabaseType, version: &VersionsNumber) -> String {
+    match database_type {
+        DatabaseType::PostgreSQL => format!("POSTGRES_{}", version.major),
+        // only the latest minor of each major is available
+        DatabaseType::MySQL => match version.major.as_str() {
+            "5" => "MYSQL_5_7".to_string(),
+            _ => "MYSQL_8_0".to_string(),
+        },
+        DatabaseType::Redis => match (version.major.as_str(), version.minor.as_deref()) {
+            ("6", _) => "REDIS_6_X".to_string(),
+            ("7", Some("2")) => "REDIS_7_2".to_string(),
+            _ => "REDIS_7_0".to_string(),
+        },
+        DatabaseType::MongoDB => format!("MONGODB_{}", version.major), // not available as a managed database
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::service::DatabaseType;
+    use crate::models::database::DatabaseError;
+    use crate::models::gcp::database_utils::{
+        is_allowed_managed_mysql_version, is_allowed_managed_postgres_version, is_allowed_managed_redis_version,
+        to_gcp_database_version,
+    };
+    use crate::models::types::VersionsNumberBuilder;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_gcp_is_allowed_managed_postgres_versions() {
+        for major in 11..=16 {
+            assert!(is_allowed_managed_postgres_version(&VersionsNumberBuilder::new().major(major).build()).is_ok());
+            assert!(
+                is_allowed_managed_postgres_version(&VersionsNumberBuilder::new().major(major).minor(2).build())
+                    .is_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn test_gcp_is_allowed_managed_postgres_unsupported_versions() {
+        // unsupported versions
+        // <- unsupported versions to be added here
+        assert_eq!(
+            is_allowed_managed_postgres_version(&VersionsNumberBuilder::new().major(10).build()).unwrap_err(),
+            DatabaseError::UnsupportedDatabaseVersion {
+                database_type: DatabaseType::PostgreSQL,
+                database_version: Arc::from("10"),
+            }
+        );
+        assert_eq!(
+            is_allowed_managed_postgres_version(&VersionsNumberBuilder::new().major(17).build()).unwrap_err(),
+            DatabaseError::UnsupportedDatabaseVersion {
+                database_type: DatabaseType::PostgreSQL,
+                database_version: Arc::from("17"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_gcp_is_allowed_managed_mysql_versions() {
+        // v5
+        assert!(is_allowed_managed_mysql_version(&VersionsNumberBuilder::new().major(5).build()).is_ok());
+        assert!(is_allowed_managed_mysql_version(&VersionsNumberBuilder::new().major(5).minor(7).build()).is_ok());
+
+        // v8
+        assert!(is_allowed_managed_mysql_version(&VersionsNumberBuilder::new().major(8).build()).is_ok());
+        assert!(is_allowed_managed_mysql_version(&VersionsNumberBuilder::new().major(8).minor(0).build()).is_ok());
+
+        // unsupported versions
+        assert_eq!(
+            is_allowed_managed_mysql_version(&VersionsNumberBuilder::new().major(9).build()).unwrap_err(),
+            DatabaseError::UnsupportedDatabaseVersion {
+                database_type: DatabaseType::MySQL,
+                database_version: Arc::from("9"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_gcp_is_allowed_managed_redis_versions() {
+        // v6
+        assert!(is_allowed_managed_redis_version(&VersionsNumberBuilder::new().major(6).build()).is_ok());
+        assert!(is_allowed_managed_redis_version(&VersionsNumberBuilder::new().major(6).minor(2).build()).is_ok());
+
+        // v7
+        assert!(is_allowed_managed_redis_version(&VersionsNumberBuilder::new().major(7).build()).is_ok());
+        assert!(is_allowed_managed_redis_version(&VersionsNumberBuilder::new().major(7).minor(2).build()).is_ok());
+
+        // unsupported versions
+        assert_eq!(
+            is_allowed_managed_redis_version(&VersionsNumberBuilder::new().major(5).build()).unwrap_err(),
+            DatabaseError::UnsupportedDatabaseVersion {
+                database_type: DatabaseType::Redis,
+                database_version: Arc::from("5"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_to_gcp_database_version() {
+        struct TestCase {
+            database_type: DatabaseType,
+            version: VersionsNumberBuilder,
+            expected: &'static str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                database_type: DatabaseType::PostgreSQL,
+                version: VersionsNumberBuilder::new().major(15).minor(4),
+                expected: "POSTGRES_15",
+            },
+            TestCase {
+                database_type: DatabaseType::MySQL,
+                version: VersionsNumberBuilder::new().major(5).minor(7),
+                expected: "MYSQL_5_7",
+            },
+            TestCase {
+                database_type: DatabaseType::MySQL,
+                version: VersionsNumberBuilder::new().major(8),
+                expected: "MYSQL_8_0",
+            },
+            TestCase {
+                database_type: DatabaseType::Redis,
+                version: VersionsNumberBuilder::new().major(6).minor(2),
+                expected: "REDIS_6_X",
+            },
+            TestCase {
+                database_type: DatabaseType::Redis,
+                version: VersionsNumberBuilder::new().major(7),
+                expected: "REDIS_7_0",
+            },
+            TestCase {
+                database_type: DatabaseType::Redis,
+                version: VersionsNumberBuilder::new().major(7).minor(2),
+                expected: "REDIS_7_2",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute & verify:
+            assert_eq!(tc.expected, to_gcp_database_version(tc.database_type, &tc.version.build()));
+        }
+    }
+}
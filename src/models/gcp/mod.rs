@@ -1,16 +1,22 @@
 mod application;
 mod container;
 mod database;
+mod database_utils;
 pub mod io;
 mod job;
 mod router;
 
+use crate::cloud_provider::models::Storage;
 use crate::cloud_provider::Kind;
 use crate::models::types::{CloudProvider, GCP};
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
 use url::Url;
 
+/// Cloud SQL activation policies, a stopped instance keeps its data and is started again on next deployment
+pub const CLOUD_SQL_ACTIVATION_POLICY_RUNNING: &str = "ALWAYS";
+pub const CLOUD_SQL_ACTIVATION_POLICY_STOPPED: &str = "NEVER";
+
 #[derive(Clone, Error, Debug, PartialEq, Eq)]
 pub enum CredentialsError {
     #[error("Cannot create credentials: {raw_error_message:?}.")]
@@ -115,4 +121,12 @@ impl CloudProvider for GCP {
     fn loadbalancer_l4_annotations() -> &'static [(&'static str, &'static str)] {
         &[]
     }
+
+    fn volume_snapshot_class() -> Option<&'static str> {
+        Some("gcp-pd-snapshot")
+    }
+
+    fn storage_class(storage: &Storage<Self::StorageTypes>) -> String {
+        storage.storage_type.to_k8s_storage_class()
+    }
 }
@@ -201,7 +201,7 @@ impl<T: CloudProvider> HelmChart<T> {
         self.timeout
     }
 
-    fn helm_values_arguments(&self) -> impl Iterator<Item = Cow<'_, str>> {
+    pub fn helm_values_arguments(&self) -> impl Iterator<Item = Cow<'_, str>> {
         let chart_dir = self.chart_workspace_directory();
         let values: Vec<Cow<'_, str>> = match &self.chart_values {
             HelmValueSource::Raw { values, .. } => values
@@ -340,6 +340,7 @@ impl<T: CloudProvider> Service for HelmChart<T> {
 pub trait HelmChartService: Service + DeploymentAction + Send {
     fn public_ports(&self) -> Vec<&Port>;
     fn advanced_settings(&self) -> &HelmChartAdvancedSettings;
+    fn helm_release_name(&self) -> String;
     fn as_deployment_action(&self) -> &dyn DeploymentAction;
 }
 
@@ -353,6 +354,9 @@ where
     fn advanced_settings(&self) -> &HelmChartAdvancedSettings {
         &self.advanced_settings
     }
+    fn helm_release_name(&self) -> String {
+        HelmChart::helm_release_name(self).to_string()
+    }
     fn as_deployment_action(&self) -> &dyn DeploymentAction {
         self
     }
@@ -0,0 +1,225 @@
+use crate::io_models::container::ContainerAdvancedSettings;
+use crate::io_models::HpaCustomMetric;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Requests per second nginx-ingress is sending to a kubernetes service, exposed by prometheus-adapter as a custom metric
+pub const REQUESTS_PER_SECOND_METRIC_NAME: &str = "nginx_ingress_requests_per_second";
+
+/// Custom metrics queries are recorded by prometheus under this prefix, prometheus-adapter exposes them as external metrics
+pub const CUSTOM_METRIC_PREFIX: &str = "qovery_hpa_";
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum HorizontalAutoscalerMetric {
+    /// Pods resource usage, as a percentage of their requests
+    Resource {
+        name: &'static str,
+        average_utilization: u8,
+    },
+    /// Metric describing a kubernetes service, divided by the number of pods
+    Object {
+        service_name: String,
+        metric_name: &'static str,
+        average_value: String,
+    },
+    /// Metric not related to any kubernetes object (i.e: a queue length)
+    External {
+        metric_name: String,
+        service_id: Uuid,
+        target_type: &'static str,
+        value: String,
+    },
+}
+
+/// Prometheus recording rule a custom metric query is stored as
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RecordingRuleTeraContext {
+    pub record: String,
+    pub expr: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ScalingRulesTeraContext {
+    pub stabilization_window_seconds: u32,
+    pub max_percent: u32,
+}
+
+/// Horizontal pod autoscaler (and recording rules of its custom metrics) rendered by the q-container chart
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct HorizontalAutoscalerTeraContext {
+    pub metrics: Vec<HorizontalAutoscalerMetric>,
+    pub scale_up: ScalingRulesTeraContext,
+    pub scale_down: ScalingRulesTeraContext,
+    pub recording_rules: Vec<RecordingRuleTeraContext>,
+}
+
+impl HorizontalAutoscalerTeraContext {
+    pub fn new(service_long_id: Uuid, kube_name: &str, advanced_settings: &ContainerAdvancedSettings) -> Self {
+        let mut metrics = vec![HorizontalAutoscalerMetric::Resource {
+            name: "cpu",
+            average_utilization: advanced_settings.hpa_cpu_average_utilization_percent,
+        }];
+        if let Some(memory_percent) = advanced_settings.hpa_memory_average_utilization_percent {
+            metrics.push(HorizontalAutoscalerMetric::Resource {
+                name: "memory",
+                average_utilization: memory_percent,
+            });
+        }
+        if let Some(requests_per_second) = advanced_settings.hpa_requests_per_second_average {
+            metrics.push(HorizontalAutoscalerMetric::Object {
+                service_name: kube_name.to_string(),
+                metric_name: REQUESTS_PER_SECOND_METRIC_NAME,
+                average_value: requests_per_second.to_string(),
+            });
+        }
+
+        let mut recording_rules = Vec::with_capacity(advanced_settings.hpa_custom_metrics.len());
+        for custom_metric in &advanced_settings.hpa_custom_metrics {
+            let metric_name = custom_metric_name(custom_metric);
+            metrics.push(HorizontalAutoscalerMetric::External {
+                metric_name: metric_name.clone(),
+                service_id: service_long_id,
+                target_type: custom_metric.target_type.to_k8s_target_type(),
+                value: custom_metric.target_value.to_string(),
+            });
+            recording_rules.push(RecordingRuleTeraContext {
+                record: metric_name,
+                expr: custom_metric.query.to_string(),
+            });
+        }
+
+        HorizontalAutoscalerTeraContext {
+            metrics,
+            scale_up: ScalingRulesTeraContext {
+                stabilization_window_seconds: advanced_settings.hpa_scale_up_stabilization_window_seconds,
+                max_percent: advanced_settings.hpa_scale_up_max_percent,
+            },
+            scale_down: ScalingRulesTeraContext {
+                stabilization_window_seconds: advanced_settings.hpa_scale_down_stabilization_window_seconds,
+                max_percent: advanced_settings.hpa_scale_down_max_percent,
+            },
+            recording_rules,
+        }
+    }
+}
+
+/// Name of the recorded series, prometheus metric names only allow [a-zA-Z0-9_:]
+pub fn custom_metric_name(custom_metric: &HpaCustomMetric) -> String {
+    let name: String = custom_metric
+        .name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    format!("{CUSTOM_METRIC_PREFIX}{name}")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io_models::container::ContainerAdvancedSettings;
+    use crate::io_models::{HpaCustomMetric, HpaMetricTargetType};
+    use crate::models::horizontal_autoscaler::{
+        custom_metric_name, HorizontalAutoscalerMetric, HorizontalAutoscalerTeraContext, RecordingRuleTeraContext,
+    };
+    use uuid::Uuid;
+
+    #[test]
+    fn test_custom_metric_name() {
+        // setup:
+        struct TestCase<'a> {
+            input: &'a str,
+            expected: &'a str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: "queue_length",
+                expected: "qovery_hpa_queue_length",
+            },
+            TestCase {
+                input: "Jobs-Pending.count",
+                expected: "qovery_hpa_jobs_pending_count",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = custom_metric_name(&HpaCustomMetric {
+                name: tc.input.to_string(),
+                query: "".to_string(),
+                target_type: HpaMetricTargetType::AverageValue,
+                target_value: "1".to_string(),
+            });
+
+            // verify:
+            assert_eq!(tc.expected, result);
+        }
+    }
+
+    #[test]
+    fn test_horizontal_autoscaler_tera_context() {
+        // setup:
+        let service_id = Uuid::new_v4();
+        let default_settings = ContainerAdvancedSettings::default();
+        let settings = ContainerAdvancedSettings {
+            hpa_memory_average_utilization_percent: Some(80),
+            hpa_requests_per_second_average: Some(50),
+            hpa_custom_metrics: vec![HpaCustomMetric {
+                name: "queue_length".to_string(),
+                query: "sum(rabbitmq_queue_messages{queue=\"tasks\"})".to_string(),
+                target_type: HpaMetricTargetType::AverageValue,
+                target_value: "30".to_string(),
+            }],
+            ..ContainerAdvancedSettings::default()
+        };
+
+        // execute:
+        let default_autoscaler = HorizontalAutoscalerTeraContext::new(service_id, "app-z1", &default_settings);
+        let autoscaler = HorizontalAutoscalerTeraContext::new(service_id, "app-z1", &settings);
+
+        // verify:
+        assert_eq!(
+            default_autoscaler.metrics,
+            vec![HorizontalAutoscalerMetric::Resource {
+                name: "cpu",
+                average_utilization: 60,
+            }]
+        );
+        assert!(default_autoscaler.recording_rules.is_empty());
+        assert_eq!(default_autoscaler.scale_down.stabilization_window_seconds, 300);
+
+        assert_eq!(
+            autoscaler.metrics,
+            vec![
+                HorizontalAutoscalerMetric::Resource {
+                    name: "cpu",
+                    average_utilization: 60,
+                },
+                HorizontalAutoscalerMetric::Resource {
+                    name: "memory",
+                    average_utilization: 80,
+                },
+                HorizontalAutoscalerMetric::Object {
+                    service_name: "app-z1".to_string(),
+                    metric_name: "nginx_ingress_requests_per_second",
+                    average_value: "50".to_string(),
+                },
+                HorizontalAutoscalerMetric::External {
+                    metric_name: "qovery_hpa_queue_length".to_string(),
+                    service_id,
+                    target_type: "AverageValue",
+                    value: "30".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            autoscaler.recording_rules,
+            vec![RecordingRuleTeraContext {
+                record: "qovery_hpa_queue_length".to_string(),
+                expr: "sum(rabbitmq_queue_messages{queue=\"tasks\"})".to_string(),
+            }]
+        );
+    }
+}
@@ -4,8 +4,9 @@ use crate::cloud_provider::service::{Action, Service, ServiceType};
 use crate::cloud_provider::DeploymentTarget;
 use crate::deployment_action::DeploymentAction;
 use crate::events::{EventDetails, Stage, Transmitter};
+use crate::io_models::application::{Port, Protocol};
 use crate::io_models::context::Context;
-use crate::io_models::job::{JobAdvancedSettings, JobSchedule};
+use crate::io_models::job::{JobAdvancedSettings, JobQueueTrigger, JobSchedule};
 use crate::models;
 use crate::models::container::RegistryTeraContext;
 use crate::models::probe::Probe;
@@ -18,6 +19,14 @@ use std::marker::PhantomData;
 use std::time::Duration;
 use uuid::Uuid;
 
+/// Triggered jobs are deployed as a suspended CronJob, its schedule never fires anyway (February 31st)
+const TRIGGERED_JOB_SCHEDULE: &str = "0 0 31 2 *";
+
+/// Image of the receiver creating a job from the suspended cronjob each time the webhook is called or a message is
+/// received from the queue, built from images/job-trigger-receiver
+const JOB_TRIGGER_RECEIVER_IMAGE: &str = "public.ecr.aws/r3m4q3r9/job-trigger-receiver:v0.1.0";
+const JOB_TRIGGER_RECEIVER_PORT: u16 = 8080;
+
 #[derive(thiserror::Error, Debug)]
 pub enum JobError {
     #[error("Job invalid configuration: {0}")]
@@ -168,6 +177,11 @@ impl<T: CloudProvider> Job<T> {
         self.max_nb_restart
     }
 
+    /// Secret the sensitive outputs of the job are stored in
+    pub fn output_secret_name(&self) -> String {
+        format!("{}-output", self.kube_name)
+    }
+
     pub(super) fn default_tera_context(&self, target: &DeploymentTarget) -> JobTeraContext {
         let environment = &target.environment;
         let kubernetes = &target.kubernetes;
@@ -224,10 +238,38 @@ impl<T: CloudProvider> Job<T> {
                 cronjob_schedule: match &self.schedule {
                     JobSchedule::OnStart {} | JobSchedule::OnPause {} | JobSchedule::OnDelete {} => None,
                     JobSchedule::Cron { schedule, .. } => Some(schedule.clone()),
+                    JobSchedule::OnTrigger { .. } => Some(TRIGGERED_JOB_SCHEDULE.to_string()),
                 },
                 cronjob_timezone: match &self.schedule {
                     JobSchedule::OnStart {} | JobSchedule::OnPause {} | JobSchedule::OnDelete {} => None,
                     JobSchedule::Cron { timezone, .. } => Some(timezone.clone()),
+                    JobSchedule::OnTrigger { .. } => Some("Etc/UTC".to_string()),
+                },
+                trigger: match &self.schedule {
+                    JobSchedule::OnTrigger { webhook, queue } => Some(JobTriggerTeraContext {
+                        name: self.trigger_receiver_name(),
+                        receiver_image: JOB_TRIGGER_RECEIVER_IMAGE,
+                        port: JOB_TRIGGER_RECEIVER_PORT,
+                        webhook_token: webhook.as_ref().map(|webhook| webhook.token.clone()),
+                        sqs: match queue {
+                            Some(JobQueueTrigger::Sqs {
+                                queue_url,
+                                region,
+                                access_key_id,
+                                secret_access_key,
+                            }) => Some(SqsTriggerTeraContext {
+                                queue_url: queue_url.clone(),
+                                region: region.clone(),
+                                access_key_id: access_key_id.clone(),
+                                secret_access_key: secret_access_key.clone(),
+                            }),
+                            None => None,
+                        },
+                    }),
+                    JobSchedule::OnStart {}
+                    | JobSchedule::OnPause {}
+                    | JobSchedule::OnDelete {}
+                    | JobSchedule::Cron { .. } => None,
                 },
                 readiness_probe: self.readiness_probe.clone(),
                 liveness_probe: self.liveness_probe.clone(),
@@ -281,7 +323,28 @@ impl<T: CloudProvider> Job<T> {
     }
 
     pub fn is_cron_job(&self) -> bool {
-        matches!(self.schedule, JobSchedule::Cron { .. })
+        self.schedule.is_cronjob()
+    }
+
+    /// Port the webhook trigger receiver is listening on, to be exposed by the environment router
+    pub fn webhook_trigger_port(&self) -> Option<Port> {
+        match &self.schedule {
+            JobSchedule::OnTrigger { webhook: Some(_), .. } => Some(Port {
+                long_id: self.long_id,
+                port: JOB_TRIGGER_RECEIVER_PORT,
+                is_default: true,
+                name: "webhook".to_string(),
+                publicly_accessible: true,
+                protocol: Protocol::HTTP,
+                service_name: Some(self.trigger_receiver_name()),
+                namespace: None,
+            }),
+            _ => None,
+        }
+    }
+
+    fn trigger_receiver_name(&self) -> String {
+        format!("{}-trigger", self.kube_name)
     }
 
     pub fn kube_label_selector(&self) -> String {
@@ -347,6 +410,7 @@ impl<T: CloudProvider> Service for Job<T> {
                 JobSchedule::OnPause { .. } if self.action == Action::Pause => Some(build),
                 JobSchedule::OnDelete { .. } if self.action == Action::Delete => Some(build),
                 JobSchedule::Cron { .. } if self.action == Action::Create => Some(build),
+                JobSchedule::OnTrigger { .. } if self.action == Action::Create => Some(build),
                 _ => None,
             },
         }
@@ -365,6 +429,7 @@ impl<T: CloudProvider> Service for Job<T> {
                     JobSchedule::OnPause { .. } if self.action == Action::Pause => Some(build),
                     JobSchedule::OnDelete { .. } if self.action == Action::Delete => Some(build),
                     JobSchedule::Cron { .. } if self.action == Action::Create => Some(build),
+                    JobSchedule::OnTrigger { .. } if self.action == Action::Create => Some(build),
                     _ => None,
                 }
             }
@@ -400,6 +465,7 @@ pub trait JobService: Service + DeploymentAction + ToTeraContext + Send {
     fn max_duration(&self) -> &Duration;
     fn max_restarts(&self) -> u32;
     fn is_force_trigger(&self) -> bool;
+    fn webhook_trigger_port(&self) -> Option<Port>;
 }
 
 impl<T: CloudProvider> JobService for Job<T>
@@ -461,6 +527,10 @@ where
     fn is_force_trigger(&self) -> bool {
         self.force_trigger
     }
+
+    fn webhook_trigger_port(&self) -> Option<Port> {
+        Job::webhook_trigger_port(self)
+    }
 }
 
 pub enum ImageSource {
@@ -496,11 +566,30 @@ pub(super) struct ServiceTeraContext {
     pub(super) max_duration_in_sec: u64,
     pub(super) cronjob_schedule: Option<String>,
     pub(super) cronjob_timezone: Option<String>,
+    pub(super) trigger: Option<JobTriggerTeraContext>,
     pub(super) readiness_probe: Option<Probe>,
     pub(super) liveness_probe: Option<Probe>,
     pub(super) advanced_settings: JobAdvancedSettings,
 }
 
+#[derive(Serialize, Debug, Clone)]
+pub(super) struct SqsTriggerTeraContext {
+    pub(super) queue_url: String,
+    pub(super) region: String,
+    pub(super) access_key_id: String,
+    pub(super) secret_access_key: String,
+}
+
+/// Receiver creating a job from the suspended CronJob each time a trigger fires
+#[derive(Serialize, Debug, Clone)]
+pub(super) struct JobTriggerTeraContext {
+    pub(super) name: String,
+    pub(super) receiver_image: &'static str,
+    pub(super) port: u16,
+    pub(super) webhook_token: Option<String>,
+    pub(super) sqs: Option<SqsTriggerTeraContext>,
+}
+
 #[derive(Serialize, Debug, Clone)]
 pub(super) struct JobTeraContext {
     pub(super) organization_long_id: Uuid,
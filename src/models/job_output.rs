@@ -0,0 +1,257 @@
+use crate::cloud_provider::models::EnvironmentVariable;
+use base64::engine::general_purpose;
+use base64::Engine;
+use k8s_openapi::api::core::v1::Secret;
+use k8s_openapi::ByteString;
+use kube::api::ObjectMeta;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+// Used to validate the job json output format with serde
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[serde(default)]
+pub struct JobOutputVariable {
+    pub value: String,
+    pub sensitive: bool,
+}
+
+impl Default for JobOutputVariable {
+    fn default() -> Self {
+        JobOutputVariable {
+            value: String::new(),
+            sensitive: true,
+        }
+    }
+}
+
+/// Sensitive job output, read by the service from the output secret of the job
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct JobOutputSecretRef {
+    pub key: String,
+    pub secret_name: String,
+}
+
+struct JobOutput {
+    secret_name: String,
+    variables: BTreeMap<String, JobOutputVariable>,
+}
+
+/// Outputs of the lifecycle jobs run during a deployment.
+/// They are injected into the applications and containers depending on those jobs, deployed afterward in the same run.
+#[derive(Default)]
+pub struct JobOutputs {
+    outputs: Mutex<BTreeMap<Uuid, JobOutput>>,
+}
+
+impl JobOutputs {
+    pub fn insert(&self, job_long_id: Uuid, secret_name: String, variables: BTreeMap<String, JobOutputVariable>) {
+        self.outputs
+            .lock()
+            .unwrap()
+            .insert(job_long_id, JobOutput { secret_name, variables });
+    }
+
+    /// Environment variables of a service, extended with the non-sensitive outputs of the jobs it depends on,
+    /// along with the references to their sensitive outputs.
+    /// Variables defined on the service take precedence over the outputs, and the first job defining an output wins.
+    pub fn service_environment_variables<'a>(
+        &self,
+        environment_variables: &[EnvironmentVariable],
+        dependencies: impl Iterator<Item = &'a Uuid>,
+    ) -> (Vec<EnvironmentVariable>, Vec<JobOutputSecretRef>) {
+        let outputs = self.outputs.lock().unwrap();
+        let mut keys: HashSet<String> = environment_variables.iter().map(|ev| ev.key.clone()).collect();
+        let mut environment_variables = environment_variables.to_vec();
+        let mut secret_refs = vec![];
+
+        for output in dependencies.filter_map(|job_long_id| outputs.get(job_long_id)) {
+            for (key, variable) in &output.variables {
+                if !keys.insert(key.clone()) {
+                    continue;
+                }
+
+                match variable.sensitive {
+                    true => secret_refs.push(JobOutputSecretRef {
+                        key: key.clone(),
+                        secret_name: output.secret_name.clone(),
+                    }),
+                    false => environment_variables.push(EnvironmentVariable {
+                        key: key.clone(),
+                        value: general_purpose::STANDARD.encode(&variable.value),
+                        is_secret: false,
+                    }),
+                }
+            }
+        }
+
+        (environment_variables, secret_refs)
+    }
+}
+
+/// Output names must be usable as environment variable names and secret keys
+pub fn is_valid_job_output_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Secret holding the sensitive outputs of a job
+pub fn job_output_secret(
+    name: &str,
+    namespace: &str,
+    labels: BTreeMap<String, String>,
+    variables: &BTreeMap<String, JobOutputVariable>,
+) -> Secret {
+    Secret {
+        metadata: ObjectMeta {
+            name: Some(name.to_string()),
+            namespace: Some(namespace.to_string()),
+            labels: Some(labels),
+            ..Default::default()
+        },
+        type_: Some("Opaque".to_string()),
+        data: Some(
+            variables
+                .iter()
+                .filter(|(_, variable)| variable.sensitive)
+                .map(|(key, variable)| (key.clone(), ByteString(variable.value.as_bytes().to_vec())))
+                .collect(),
+        ),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::models::EnvironmentVariable;
+    use crate::models::job_output::{
+        is_valid_job_output_name, job_output_secret, JobOutputSecretRef, JobOutputVariable, JobOutputs,
+    };
+    use k8s_openapi::ByteString;
+    use std::collections::BTreeMap;
+    use uuid::Uuid;
+
+    fn variable(value: &str, sensitive: bool) -> JobOutputVariable {
+        JobOutputVariable {
+            value: value.to_string(),
+            sensitive,
+        }
+    }
+
+    #[test]
+    fn test_service_environment_variables() {
+        // setup:
+        let migration = Uuid::new_v4();
+        let seed = Uuid::new_v4();
+        let not_a_dependency = Uuid::new_v4();
+        let job_outputs = JobOutputs::default();
+        job_outputs.insert(
+            migration,
+            "migration-output".to_string(),
+            BTreeMap::from([
+                ("DB_SCHEMA".to_string(), variable("v42", false)),
+                ("DB_PASSWORD".to_string(), variable("s3cr3t", true)),
+                ("PORT".to_string(), variable("8080", false)),
+            ]),
+        );
+        job_outputs.insert(
+            seed,
+            "seed-output".to_string(),
+            BTreeMap::from([("DB_SCHEMA".to_string(), variable("v1", false))]),
+        );
+        job_outputs.insert(
+            not_a_dependency,
+            "other-output".to_string(),
+            BTreeMap::from([("OTHER".to_string(), variable("other", false))]),
+        );
+        let service_variables = vec![EnvironmentVariable {
+            key: "PORT".to_string(),
+            value: "ODA=".to_string(),
+            is_secret: false,
+        }];
+
+        // execute:
+        let (environment_variables, secret_refs) =
+            job_outputs.service_environment_variables(&service_variables, [migration, seed].iter());
+
+        // verify:
+        assert_eq!(
+            environment_variables
+                .iter()
+                .map(|ev| (ev.key.as_str(), ev.value.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("PORT", "ODA="), ("DB_SCHEMA", "djQy")]
+        );
+        assert_eq!(
+            secret_refs,
+            vec![JobOutputSecretRef {
+                key: "DB_PASSWORD".to_string(),
+                secret_name: "migration-output".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_is_valid_job_output_name() {
+        // setup:
+        struct TestCase<'a> {
+            input: &'a str,
+            expected: bool,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                input: "DATABASE_URL",
+                expected: true,
+            },
+            TestCase {
+                input: "_PRIVATE2",
+                expected: true,
+            },
+            TestCase {
+                input: "2FA_KEY",
+                expected: false,
+            },
+            TestCase {
+                input: "API-KEY",
+                expected: false,
+            },
+            TestCase {
+                input: "",
+                expected: false,
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = is_valid_job_output_name(tc.input);
+
+            // verify:
+            assert_eq!(tc.expected, result, "{}", tc.input);
+        }
+    }
+
+    #[test]
+    fn test_job_output_secret_only_holds_sensitive_outputs() {
+        // setup:
+        let variables = BTreeMap::from([
+            ("DB_SCHEMA".to_string(), variable("v42", false)),
+            ("DB_PASSWORD".to_string(), variable("s3cr3t", true)),
+        ]);
+
+        // execute:
+        let secret = job_output_secret("migration-output", "z1-env", BTreeMap::new(), &variables);
+
+        // verify:
+        assert_eq!(secret.metadata.name.as_deref(), Some("migration-output"));
+        assert_eq!(
+            secret.data,
+            Some(BTreeMap::from([(
+                "DB_PASSWORD".to_string(),
+                ByteString("s3cr3t".as_bytes().to_vec())
+            )]))
+        );
+    }
+}
@@ -1,13 +1,19 @@
 pub mod application;
 pub mod aws;
 pub mod aws_ec2;
+pub mod azure;
 pub mod container;
 pub mod database;
+pub mod database_backup;
 pub(crate) mod database_utils;
+pub mod dependency_graph;
+pub mod deployment_strategy;
 pub mod domain;
 pub mod gcp;
 pub mod helm_chart;
+pub mod horizontal_autoscaler;
 pub mod job;
+pub mod job_output;
 pub mod kubernetes;
 pub mod probe;
 pub mod registry_image_source;
@@ -15,7 +21,9 @@ pub mod router;
 pub mod scaleway;
 pub mod selfmanaged;
 pub mod third_parties;
+pub mod tls_certificate;
 pub mod types;
+pub mod volume_snapshot;
 
 pub trait ToCloudProviderFormat {
     /// Returns cloud provider string representation.
@@ -1,6 +1,9 @@
 use crate::build_platform::Build;
+use crate::cloud_provider::helm::HelmChartNamespaces;
+use crate::cloud_provider::helm_charts::qovery_gateway_chart::QOVERY_GATEWAY_NAME;
 use crate::cloud_provider::models::{
-    CustomDomain, CustomDomainDataTemplate, EnvironmentVariable, HostDataTemplate, Route,
+    CertificateSource, CustomCertificateDataTemplate, CustomDomain, CustomDomainDataTemplate, EnvironmentVariable,
+    HostDataTemplate, Route, RouteMatchDataTemplate,
 };
 use crate::cloud_provider::service::{default_tera_context, Action, Service, ServiceType};
 use crate::cloud_provider::DeploymentTarget;
@@ -8,16 +11,27 @@ use crate::deployment_action::DeploymentAction;
 use crate::errors::EngineError;
 use crate::events::{EnvironmentStep, EventDetails, Stage, Transmitter};
 use crate::io_models::application::{Port, Protocol};
+use crate::io_models::container::ContainerAdvancedSettings;
 use crate::io_models::context::Context;
+use crate::io_models::{ModSecurityProfile, UpdateStrategy};
+use crate::models::domain::Domain;
 use crate::models::types::CloudProvider;
 use crate::models::types::ToTeraContext;
 use crate::utilities::to_short_id;
+use base64::engine::general_purpose;
+use base64::Engine;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::iter;
 use std::marker::PhantomData;
 use tera::Context as TeraContext;
+use url::Url;
 use uuid::Uuid;
 
+pub const HTTP_ROUTE_API_GROUP: &str = "gateway.networking.k8s.io";
+pub const HTTP_ROUTE_API_VERSION: &str = "v1";
+pub const HTTP_ROUTE_KIND: &str = "HTTPRoute";
+
 #[derive(thiserror::Error, Debug)]
 pub enum RouterError {
     #[error("Router invalid configuration: {0}")]
@@ -36,6 +50,9 @@ pub struct RouterAdvancedSettings {
     pub whitelist_source_range: Option<String>,
     pub denylist_source_range: Option<String>,
     pub basic_auth: Option<String>,
+    pub rate_limit: Option<RouterRateLimit>,
+    pub modsecurity_profile: Option<ModSecurityProfile>,
+    pub external_auth: Option<RouterExternalAuth>,
 }
 
 impl Default for RouterAdvancedSettings {
@@ -45,7 +62,58 @@ impl Default for RouterAdvancedSettings {
             whitelist_source_range: None,
             denylist_source_range: None,
             basic_auth: None,
+            rate_limit: None,
+            modsecurity_profile: None,
+            external_auth: None,
+        }
+    }
+}
+
+/// Limits applied by nginx to each client IP
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct RouterRateLimit {
+    pub requests_per_second: Option<u32>,
+    pub connections: Option<u32>,
+    /// Requests above the rate accepted in bursts, as a multiple of `requests_per_second`
+    pub burst_multiplier: u32,
+}
+
+impl RouterRateLimit {
+    pub fn new(requests_per_second: Option<u32>, connections: Option<u32>, burst_multiplier: u32) -> Option<Self> {
+        if requests_per_second.is_none() && connections.is_none() {
+            return None;
+        }
+
+        Some(RouterRateLimit {
+            requests_per_second,
+            connections,
+            burst_multiplier,
+        })
+    }
+}
+
+/// Requests are forwarded to the service only if `url` answers them with a 2xx (i.e: oauth2-proxy)
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct RouterExternalAuth {
+    pub url: String,
+    /// Where clients are redirected to when `url` answers with a 401
+    pub signin_url: Option<String>,
+    /// Comma separated headers of the `url` response passed to the service
+    pub response_headers: Option<String>,
+}
+
+impl RouterExternalAuth {
+    pub fn new(url: &str, signin_url: &str, response_headers: &str) -> Option<Self> {
+        if url.is_empty() && signin_url.is_empty() && response_headers.is_empty() {
+            return None;
         }
+
+        let non_empty = |value: &str| Some(value.to_string()).filter(|value| !value.is_empty());
+        Some(RouterExternalAuth {
+            url: url.to_string(),
+            signin_url: non_empty(signin_url),
+            response_headers: non_empty(response_headers),
+        })
     }
 }
 
@@ -63,12 +131,95 @@ impl RouterAdvancedSettings {
             whitelist_source_range: definitive_whitelist,
             denylist_source_range,
             basic_auth,
+            rate_limit: None,
+            modsecurity_profile: None,
+            external_auth: None,
         }
     }
 
     pub fn whitelist_source_range_default_value() -> String {
         "0.0.0.0/0".to_string()
     }
+
+    /// Access restrictions are implemented with nginx annotations, Gateway API routes can't enforce them
+    pub fn validate_for_gateway_api(&self) -> Result<(), RouterError> {
+        let unsupported_settings = [
+            ("whitelist source range", self.whitelist_source_range.is_some()),
+            ("denylist source range", self.denylist_source_range.is_some()),
+            ("basic auth", self.basic_auth.is_some()),
+            ("external auth", self.external_auth.is_some()),
+            ("rate limit", self.rate_limit.is_some()),
+            ("ModSecurity", self.modsecurity_profile.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(name, is_set)| if is_set { Some(name) } else { None })
+        .collect::<Vec<_>>();
+
+        if !unsupported_settings.is_empty() {
+            return Err(RouterError::InvalidConfig(format!(
+                "{} can't be enforced on clusters routing through the Gateway API",
+                unsupported_settings.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), RouterError> {
+        if let Some(rate_limit) = &self.rate_limit {
+            if rate_limit.requests_per_second == Some(0) || rate_limit.connections == Some(0) {
+                return Err(RouterError::InvalidConfig(
+                    "Rate limit requests per second and connections must be greater than 0".to_string(),
+                ));
+            }
+            if rate_limit.burst_multiplier == 0 {
+                return Err(RouterError::InvalidConfig(
+                    "Rate limit burst multiplier must be greater than 0".to_string(),
+                ));
+            }
+        }
+
+        if let Some(external_auth) = &self.external_auth {
+            // both are implemented with the nginx auth annotations
+            if self.basic_auth.is_some() {
+                return Err(RouterError::InvalidConfig(
+                    "Basic auth and external auth can't be enabled together".to_string(),
+                ));
+            }
+            if !is_http_url(&external_auth.url) {
+                return Err(RouterError::InvalidConfig(format!(
+                    "External auth url `{}` must be an http(s) url",
+                    external_auth.url
+                )));
+            }
+            if let Some(signin_url) = &external_auth.signin_url {
+                if !is_http_url(signin_url) {
+                    return Err(RouterError::InvalidConfig(format!(
+                        "External auth signin url `{signin_url}` must be an http(s) url"
+                    )));
+                }
+            }
+            if let Some(response_headers) = &external_auth.response_headers {
+                let is_header_name =
+                    |name: &str| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+                if !response_headers.split(',').map(str::trim).all(is_header_name) {
+                    return Err(RouterError::InvalidConfig(format!(
+                        "External auth response headers `{response_headers}` must be a comma separated list of header names"
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// urls are rendered as is in quoted annotations
+fn is_http_url(url: &str) -> bool {
+    !url.chars().any(|c| c == '"' || c.is_whitespace())
+        && Url::parse(url)
+            .map(|url| (url.scheme() == "http" || url.scheme() == "https") && url.has_host())
+            .unwrap_or(false)
 }
 
 pub struct Router<T: CloudProvider> {
@@ -102,6 +253,8 @@ impl<T: CloudProvider> Router<T> {
         advanced_settings: RouterAdvancedSettings,
         mk_event_details: impl Fn(Transmitter) -> EventDetails,
     ) -> Result<Self, RouterError> {
+        advanced_settings.validate()?;
+
         let workspace_directory = crate::fs::workspace_directory(
             context.workspace_root_dir(),
             context.execution_id(),
@@ -154,6 +307,20 @@ impl<T: CloudProvider> Router<T> {
             .ok_or_else(|| EngineError::new_router_failed_to_deploy(event_details.clone()))?
             .service_long_id;
 
+        // refuse to deploy rather than exposing the service without the restrictions it asked for
+        if kubernetes.advanced_settings().gateway_api_enabled {
+            self.advanced_settings
+                .validate_for_gateway_api()
+                .map_err(|e| EngineError::new_router_error(event_details.clone(), e))?;
+        }
+
+        // Jobs are only routed through the receiver of their webhook trigger
+        let job_webhook_port = environment
+            .jobs
+            .iter()
+            .find(|job| job.long_id() == &service_id)
+            .and_then(|job| job.webhook_trigger_port().map(|port| (job.kube_name(), port)));
+
         // Check if the service is an application
         let (service_name, ports) =
             if let Some(application) = &environment.applications.iter().find(|app| app.long_id() == &service_id) {
@@ -161,6 +328,10 @@ impl<T: CloudProvider> Router<T> {
                 context.insert("advanced_settings", &application.advanced_settings());
                 context.insert("associated_service_long_id", &service_id);
                 context.insert("associated_service_type", "application");
+                context.insert(
+                    "canary_enabled",
+                    &(application.advanced_settings().deployment_update_strategy_type == UpdateStrategy::Canary),
+                );
 
                 (application.kube_name(), application.public_ports())
             } else if let Some(container) = &environment
@@ -172,8 +343,20 @@ impl<T: CloudProvider> Router<T> {
                 context.insert("advanced_settings", &container.advanced_settings());
                 context.insert("associated_service_long_id", &service_id);
                 context.insert("associated_service_type", "container");
+                context.insert(
+                    "canary_enabled",
+                    &(container.advanced_settings().deployment_update_strategy_type == UpdateStrategy::Canary),
+                );
 
                 (container.kube_name(), container.public_ports())
+            } else if let Some((job_kube_name, webhook_port)) = &job_webhook_port {
+                // the receiver has no ingress settings of its own
+                context.insert("advanced_settings", &ContainerAdvancedSettings::default());
+                context.insert("associated_service_long_id", &service_id);
+                context.insert("associated_service_type", "job");
+                context.insert("canary_enabled", &false);
+
+                (*job_kube_name, vec![webhook_port])
             } else {
                 let helm_chart = environment
                     .helm_charts
@@ -185,6 +368,7 @@ impl<T: CloudProvider> Router<T> {
                 context.insert("advanced_settings", &helm_chart.advanced_settings());
                 context.insert("associated_service_long_id", &service_id);
                 context.insert("associated_service_type", "helm");
+                context.insert("canary_enabled", &false);
 
                 (helm_chart.kube_name(), helm_chart.public_ports())
             };
@@ -192,6 +376,17 @@ impl<T: CloudProvider> Router<T> {
         // inject basic auth data
         context.insert("basic_auth_htaccess", &self.advanced_settings.basic_auth);
 
+        // inject rate limiting, WAF and external auth settings
+        context.insert("rate_limit", &self.advanced_settings.rate_limit);
+        context.insert(
+            "modsecurity_rule_engine",
+            &self
+                .advanced_settings
+                .modsecurity_profile
+                .map(|profile| profile.to_modsecurity_rule_engine()),
+        );
+        context.insert("external_auth", &self.advanced_settings.external_auth);
+
         // Get the alternative names we need to generate for the certificate
         // For custom domain, we need to generate a subdomain for each port. p80.mydomain.com, p443.mydomain.com
         let cluster_domain = kubernetes.dns_provider().domain().to_string();
@@ -231,6 +426,24 @@ impl<T: CloudProvider> Router<T> {
         context.insert("http_hosts_per_namespace", &http_hosts_per_namespace);
         context.insert("grpc_hosts_per_namespace", &grpc_hosts_per_namespace);
 
+        // Gateway API routes attach to the cluster gateway, and can match on the path and headers of the routes
+        context.insert("gateway_name", QOVERY_GATEWAY_NAME);
+        context.insert("gateway_namespace", &HelmChartNamespaces::EnvoyGateway.to_string());
+        context.insert("route_matches", &to_route_matches(&self.routes, &service_id));
+
+        // Uploaded and issuer certificates are attached to the hosts in every namespace the router has ingresses in
+        let mut certificate_namespaces = http_hosts_per_namespace
+            .keys()
+            .chain(grpc_hosts_per_namespace.keys())
+            .collect::<Vec<_>>();
+        certificate_namespaces.sort();
+        certificate_namespaces.dedup();
+        context.insert("certificate_namespaces", &certificate_namespaces);
+        context.insert(
+            "custom_certificates",
+            &generate_custom_certificates(&self.custom_domains, &self.id, &ports),
+        );
+
         let lets_encrypt_url = match target.is_test_cluster {
             true => "https://acme-staging-v02.api.letsencrypt.org/directory",
             false => "https://acme-v02.api.letsencrypt.org/directory",
@@ -244,9 +457,28 @@ impl<T: CloudProvider> Router<T> {
         crate::string::cut(format!("router-{}", self.id), 50)
     }
 
-    pub fn helm_chart_dir(&self) -> String {
-        format!("{}/common/charts/q-ingress-tls", self.lib_root_directory,)
+    pub fn helm_chart_dir(&self, target: &DeploymentTarget) -> String {
+        match target.kubernetes.advanced_settings().gateway_api_enabled {
+            true => format!("{}/common/charts/q-gateway", self.lib_root_directory),
+            false => format!("{}/common/charts/q-ingress-tls", self.lib_root_directory,),
+        }
+    }
+}
+
+// Gateway API path matches must be absolute, a route without path matches everything
+// Only the routes of the service are matched, requests matching the routes of another service must not reach it
+fn to_route_matches(routes: &[Route], service_long_id: &Uuid) -> Vec<RouteMatchDataTemplate> {
+    let mut route_matches: Vec<RouteMatchDataTemplate> = Vec::with_capacity(routes.len());
+    for route in routes.iter().filter(|route| &route.service_long_id == service_long_id) {
+        let route_match = RouteMatchDataTemplate {
+            path: format!("/{}", route.path.trim_start_matches('/')),
+            headers: route.headers.clone(),
+        };
+        if !route_matches.contains(&route_match) {
+            route_matches.push(route_match);
+        }
     }
+    route_matches
 }
 
 fn to_host_data_template(
@@ -381,37 +613,88 @@ fn generate_certificate_alternative_names(
         return vec![];
     }
 
+    let cluster_domain = Domain::new(cluster_domain.to_string());
     custom_domains
         .iter()
         // we filter out domain that belongs to our cluster, we dont need to create certificate for them
         // we keep wildcard domains, as we will need to create certificate for them
         .filter(|domain| {
-            (domain.is_wildcard() || !domain.domain.ends_with(&cluster_domain)) && domain.generate_certificate
+            (domain.is_wildcard() || !Domain::new(domain.domain.to_string()).is_sub_domain_of(&cluster_domain))
+                && domain.generate_certificate
+                && domain.certificate_source == CertificateSource::Generated
         })
-        .flat_map(|cd| {
-            // We always want the root domain to be in the certificate (I.e: example.com, or if *.example.com -> example.com)
-            let default_domain = CustomDomainDataTemplate {
-                domain: cd.domain_without_wildcard().to_string(),
-            };
+        .flat_map(|cd| certificate_names(cd, ports))
+        .collect::<Vec<_>>()
+}
 
-            // If it is a wildcard domain, we want to generate the wildcard certificate (*.example.com)
-            // if there is a single public port, we can use only the default domain and don't generate subdomains for each port. (to avoid migration for clients)
-            iter::once(default_domain).chain(if cd.is_wildcard() {
-                vec![CustomDomainDataTemplate {
-                    domain: cd.domain.to_string(),
-                }]
-            } else if ports.len() == 1 {
-                vec![]
-            } else {
-                ports
-                    .iter()
-                    .map(|port| CustomDomainDataTemplate {
-                        domain: format!("{}.{}", port.name, cd.domain),
-                    })
-                    .collect()
-            })
+fn certificate_names(custom_domain: &CustomDomain, ports: &[&Port]) -> Vec<CustomDomainDataTemplate> {
+    // Certificates only accept ASCII names, internationalized domains are converted to punycode
+    let domain = Domain::new(custom_domain.domain_without_wildcard().to_string());
+
+    // We always want the root domain to be in the certificate (I.e: example.com, or if *.example.com -> example.com)
+    let default_domain = CustomDomainDataTemplate {
+        domain: domain.to_string(),
+    };
+
+    // If it is a wildcard domain, we want to generate the wildcard certificate (*.example.com)
+    // if there is a single public port, we can use only the default domain and don't generate subdomains for each port. (to avoid migration for clients)
+    iter::once(default_domain)
+        .chain(if custom_domain.is_wildcard() {
+            vec![CustomDomainDataTemplate {
+                domain: domain.wildcarded().to_string(),
+            }]
+        } else if ports.len() == 1 {
+            vec![]
+        } else {
+            ports
+                .iter()
+                .map(|port| CustomDomainDataTemplate {
+                    domain: domain.with_sub_domain(port.name.to_string()).to_string(),
+                })
+                .collect()
         })
-        .collect::<Vec<_>>()
+        .collect()
+}
+
+// Certificates not issued by Let's Encrypt get their own secret, attached to the hosts of their domain by a dedicated
+// ingress without routing rules. Sharing the ingress of the router would make cert-manager manage them too.
+fn generate_custom_certificates(
+    custom_domains: &[CustomDomain],
+    router_id: &str,
+    ports: &[&Port],
+) -> Vec<CustomCertificateDataTemplate> {
+    if ports.is_empty() {
+        return vec![];
+    }
+
+    custom_domains
+        .iter()
+        .enumerate()
+        .filter(|(_, cd)| cd.generate_certificate)
+        .filter_map(|(idx, cd)| {
+            let secret_name = format!("router-tls-{router_id}-{idx}");
+            match &cd.certificate_source {
+                CertificateSource::Generated => None,
+                CertificateSource::Uploaded {
+                    certificate_chain,
+                    private_key,
+                } => Some(CustomCertificateDataTemplate {
+                    secret_name,
+                    issuer: None,
+                    certificate_chain_b64: Some(general_purpose::STANDARD.encode(certificate_chain)),
+                    private_key_b64: Some(general_purpose::STANDARD.encode(private_key)),
+                    domains: certificate_names(cd, ports),
+                }),
+                CertificateSource::Issuer { name } => Some(CustomCertificateDataTemplate {
+                    secret_name,
+                    issuer: Some(name.clone()),
+                    certificate_chain_b64: None,
+                    private_key_b64: None,
+                    domains: certificate_names(cd, ports),
+                }),
+            }
+        })
+        .collect()
 }
 
 impl<T: CloudProvider> Service for Router<T> {
@@ -504,10 +787,17 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::RouterAdvancedSettings;
-    use crate::cloud_provider::models::{CustomDomain, CustomDomainDataTemplate, HostDataTemplate};
+    use super::{RouterAdvancedSettings, RouterExternalAuth, RouterRateLimit};
+    use crate::cloud_provider::models::{
+        CertificateSource, CustomCertificateDataTemplate, CustomDomain, CustomDomainDataTemplate, HostDataTemplate,
+        Route, RouteMatchDataTemplate,
+    };
     use crate::io_models::application::{Port, Protocol};
-    use crate::models::router::{generate_certificate_alternative_names, to_host_data_template};
+    use crate::io_models::ModSecurityProfile;
+    use crate::models::router::{
+        generate_certificate_alternative_names, generate_custom_certificates, to_host_data_template, to_route_matches,
+    };
+    use std::collections::BTreeMap;
 
     #[test]
     pub fn test_router_advanced_settings() {
@@ -516,6 +806,149 @@ mod tests {
         assert!(router_advanced_settings_defaults.custom_domain_check_enabled);
     }
 
+    #[test]
+    pub fn test_router_advanced_settings_validate() {
+        struct TestCase {
+            settings: RouterAdvancedSettings,
+            is_valid: bool,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                settings: RouterAdvancedSettings::default(),
+                is_valid: true,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings {
+                    rate_limit: RouterRateLimit::new(Some(10), Some(20), 5),
+                    external_auth: RouterExternalAuth::new(
+                        "https://oauth2-proxy.example.com/oauth2/auth",
+                        "https://oauth2-proxy.example.com/oauth2/start?rd=$escaped_request_uri",
+                        "X-Auth-Request-User, X-Auth-Request-Email",
+                    ),
+                    ..Default::default()
+                },
+                is_valid: true,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings {
+                    rate_limit: RouterRateLimit::new(Some(0), None, 5),
+                    ..Default::default()
+                },
+                is_valid: false,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings {
+                    rate_limit: RouterRateLimit::new(None, Some(10), 0),
+                    ..Default::default()
+                },
+                is_valid: false,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings {
+                    external_auth: RouterExternalAuth::new("", "https://oauth2-proxy.example.com/oauth2/start", ""),
+                    ..Default::default()
+                },
+                is_valid: false,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings {
+                    external_auth: RouterExternalAuth::new("oauth2-proxy:4180/oauth2/auth", "", ""),
+                    ..Default::default()
+                },
+                is_valid: false,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings {
+                    external_auth: RouterExternalAuth::new("https://auth.example.com", "", "X-User;rm"),
+                    ..Default::default()
+                },
+                is_valid: false,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings {
+                    basic_auth: Some("user:password".to_string()),
+                    external_auth: RouterExternalAuth::new("https://auth.example.com", "", ""),
+                    ..Default::default()
+                },
+                is_valid: false,
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = tc.settings.validate();
+
+            // verify:
+            assert_eq!(result.is_ok(), tc.is_valid, "{:?}", result.err());
+        }
+    }
+
+    #[test]
+    pub fn test_router_advanced_settings_validate_for_gateway_api() {
+        struct TestCase {
+            settings: RouterAdvancedSettings,
+            is_valid: bool,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                settings: RouterAdvancedSettings::default(),
+                is_valid: true,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings::new(
+                    false,
+                    Some(RouterAdvancedSettings::whitelist_source_range_default_value()),
+                    None,
+                    None,
+                ),
+                is_valid: true,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings::new(false, Some("10.0.0.0/8".to_string()), None, None),
+                is_valid: false,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings::new(false, None, Some("10.0.0.0/8".to_string()), None),
+                is_valid: false,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings::new(false, None, None, Some("user:password".to_string())),
+                is_valid: false,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings {
+                    external_auth: RouterExternalAuth::new("https://auth.example.com", "", ""),
+                    ..Default::default()
+                },
+                is_valid: false,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings {
+                    rate_limit: RouterRateLimit::new(Some(10), None, 5),
+                    ..Default::default()
+                },
+                is_valid: false,
+            },
+            TestCase {
+                settings: RouterAdvancedSettings {
+                    modsecurity_profile: Some(ModSecurityProfile::DetectionOnly),
+                    ..Default::default()
+                },
+                is_valid: false,
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = tc.settings.validate_for_gateway_api();
+
+            // verify:
+            assert_eq!(result.is_ok(), tc.is_valid, "{:?}", result.err());
+        }
+    }
+
     #[test]
     pub fn test_certificate_alternative_names() {
         let custom_domains = vec![
@@ -523,16 +956,19 @@ mod tests {
                 domain: "toto.com".to_string(),
                 target_domain: "".to_string(),
                 generate_certificate: true,
+                certificate_source: CertificateSource::Generated,
             },
             CustomDomain {
                 domain: "cluster.com".to_string(),
                 target_domain: "".to_string(),
                 generate_certificate: true,
+                certificate_source: CertificateSource::Generated,
             },
             CustomDomain {
                 domain: "titi.com".to_string(),
                 target_domain: "".to_string(),
                 generate_certificate: false,
+                certificate_source: CertificateSource::Generated,
             },
         ];
 
@@ -587,6 +1023,7 @@ mod tests {
             domain: "*.toto.cluster.com".to_string(),
             target_domain: "".to_string(),
             generate_certificate: true,
+            certificate_source: CertificateSource::Generated,
         }];
         let port2 = Port {
             long_id: Default::default(),
@@ -608,6 +1045,32 @@ mod tests {
         assert!(certificate_names.contains(&CustomDomainDataTemplate {
             domain: "*.toto.cluster.com".to_string()
         }));
+
+        // Internationalized domains are converted to punycode, domains only sharing a suffix with the cluster domain are kept
+        let custom_domains = vec![
+            CustomDomain {
+                domain: "café.fr".to_string(),
+                target_domain: "".to_string(),
+                generate_certificate: true,
+                certificate_source: CertificateSource::Generated,
+            },
+            CustomDomain {
+                domain: "mycluster.com".to_string(),
+                target_domain: "".to_string(),
+                generate_certificate: true,
+                certificate_source: CertificateSource::Generated,
+            },
+        ];
+        let ports = vec![&port];
+
+        let certificate_names = generate_certificate_alternative_names(&custom_domains, "cluster.com", &ports);
+        assert_eq!(certificate_names.len(), 2);
+        assert!(certificate_names.contains(&CustomDomainDataTemplate {
+            domain: "xn--caf-dma.fr".to_string()
+        }));
+        assert!(certificate_names.contains(&CustomDomainDataTemplate {
+            domain: "mycluster.com".to_string()
+        }));
     }
 
     #[test]
@@ -636,6 +1099,7 @@ mod tests {
             domain: "*.toto.mydomain.com".to_string(),
             target_domain: "".to_string(),
             generate_certificate: true,
+            certificate_source: CertificateSource::Generated,
         }];
 
         let namespace = "env_namespace";
@@ -692,11 +1156,13 @@ mod tests {
                 domain: "super.mydomain.com".to_string(),
                 target_domain: "".to_string(),
                 generate_certificate: true,
+                certificate_source: CertificateSource::Generated,
             },
             CustomDomain {
                 domain: "*.toto.mydomain.com".to_string(),
                 target_domain: "".to_string(),
                 generate_certificate: true,
+                certificate_source: CertificateSource::Generated,
             },
         ];
 
@@ -780,6 +1246,7 @@ mod tests {
             domain: "toto.cluster.com".to_string(),
             target_domain: "".to_string(),
             generate_certificate: true,
+            certificate_source: CertificateSource::Generated,
         }];
 
         let namespace = "namespace1";
@@ -831,6 +1298,7 @@ mod tests {
             domain: "*.toto.mydomain.com".to_string(),
             target_domain: "".to_string(),
             generate_certificate: true,
+            certificate_source: CertificateSource::Generated,
         }];
 
         let namespace = "env_namespace";
@@ -893,6 +1361,7 @@ mod tests {
             domain: "*.toto.mydomain.com".to_string(),
             target_domain: "".to_string(),
             generate_certificate: true,
+            certificate_source: CertificateSource::Generated,
         }];
 
         let namespace = "env_namespace";
@@ -930,4 +1399,140 @@ mod tests {
             service_port: 8080,
         }));
     }
+
+    #[test]
+    pub fn test_custom_certificates() {
+        // setup:
+        let custom_domains = vec![
+            CustomDomain {
+                domain: "generated.com".to_string(),
+                target_domain: "".to_string(),
+                generate_certificate: true,
+                certificate_source: CertificateSource::Generated,
+            },
+            CustomDomain {
+                domain: "uploaded.com".to_string(),
+                target_domain: "".to_string(),
+                generate_certificate: true,
+                certificate_source: CertificateSource::Uploaded {
+                    certificate_chain: "chain".to_string(),
+                    private_key: "key".to_string(),
+                },
+            },
+            CustomDomain {
+                domain: "*.corporate.com".to_string(),
+                target_domain: "".to_string(),
+                generate_certificate: true,
+                certificate_source: CertificateSource::Issuer {
+                    name: "step-ca".to_string(),
+                },
+            },
+            CustomDomain {
+                domain: "disabled.com".to_string(),
+                target_domain: "".to_string(),
+                generate_certificate: false,
+                certificate_source: CertificateSource::Issuer {
+                    name: "step-ca".to_string(),
+                },
+            },
+        ];
+        let port = Port {
+            long_id: Default::default(),
+            name: "http".to_string(),
+            publicly_accessible: true,
+            port: 80,
+            is_default: true,
+            protocol: Protocol::HTTP,
+            service_name: None,
+            namespace: None,
+        };
+        let ports = vec![&port];
+
+        // execute:
+        let certificate_names = generate_certificate_alternative_names(&custom_domains, "cluster.com", &ports);
+        let custom_certificates = generate_custom_certificates(&custom_domains, "z1234", &ports);
+
+        // verify:
+        assert_eq!(
+            certificate_names,
+            vec![CustomDomainDataTemplate {
+                domain: "generated.com".to_string()
+            }]
+        );
+        assert_eq!(
+            custom_certificates,
+            vec![
+                CustomCertificateDataTemplate {
+                    secret_name: "router-tls-z1234-1".to_string(),
+                    issuer: None,
+                    certificate_chain_b64: Some("Y2hhaW4=".to_string()),
+                    private_key_b64: Some("a2V5".to_string()),
+                    domains: vec![CustomDomainDataTemplate {
+                        domain: "uploaded.com".to_string()
+                    }],
+                },
+                CustomCertificateDataTemplate {
+                    secret_name: "router-tls-z1234-2".to_string(),
+                    issuer: Some("step-ca".to_string()),
+                    certificate_chain_b64: None,
+                    private_key_b64: None,
+                    domains: vec![
+                        CustomDomainDataTemplate {
+                            domain: "corporate.com".to_string()
+                        },
+                        CustomDomainDataTemplate {
+                            domain: "*.corporate.com".to_string()
+                        },
+                    ],
+                },
+            ]
+        );
+        assert!(generate_custom_certificates(&custom_domains, "z1234", &[]).is_empty());
+    }
+
+    #[test]
+    pub fn test_route_matches() {
+        // setup:
+        let service_long_id = uuid::Uuid::new_v4();
+        let routes = vec![
+            Route {
+                path: "/".to_string(),
+                service_long_id,
+                headers: BTreeMap::new(),
+            },
+            Route {
+                path: "".to_string(),
+                service_long_id,
+                headers: BTreeMap::new(),
+            },
+            Route {
+                path: "api".to_string(),
+                service_long_id,
+                headers: BTreeMap::from([("x-version".to_string(), "v2".to_string())]),
+            },
+            Route {
+                path: "/admin".to_string(),
+                service_long_id: uuid::Uuid::new_v4(),
+                headers: BTreeMap::new(),
+            },
+        ];
+
+        // execute:
+        let route_matches = to_route_matches(&routes, &service_long_id);
+
+        // verify:
+        assert_eq!(
+            route_matches,
+            vec![
+                RouteMatchDataTemplate {
+                    path: "/".to_string(),
+                    headers: BTreeMap::new(),
+                },
+                RouteMatchDataTemplate {
+                    path: "/api".to_string(),
+                    headers: BTreeMap::from([("x-version".to_string(), "v2".to_string())]),
+                },
+            ]
+        );
+    }
 }
@@ -2,8 +2,7 @@ use crate::cloud_provider::models::StorageDataTemplate;
 use crate::cloud_provider::DeploymentTarget;
 use crate::errors::EngineError;
 use crate::models::application::Application;
-use crate::models::scaleway::ScwStorageType;
-use crate::models::types::{ToTeraContext, SCW};
+use crate::models::types::{CloudProvider, ToTeraContext, SCW};
 use tera::Context as TeraContext;
 
 impl ToTeraContext for Application<SCW> {
@@ -16,14 +15,7 @@ impl ToTeraContext for Application<SCW> {
                 id: s.id.clone(),
                 long_id: self.long_id,
                 name: s.name.clone(),
-                storage_type: match s.storage_type {
-                    // TODO(benjaminch): Switch to proper storage class
-                    // Note: Seems volume storage type are not supported, only blocked storage for the time being
-                    // https://github.com/scaleway/scaleway-csi/tree/master/examples/kubernetes#different-storageclass
-                    ScwStorageType::BlockSsd => "scw-sbv-ssd-0", // "b_ssd",
-                    ScwStorageType::LocalSsd => "l_ssd",
-                }
-                .to_string(),
+                storage_type: SCW::storage_class(s),
                 size_in_gib: s.size_in_gib,
                 mount_point: s.mount_point.clone(),
                 snapshot_retention_in_days: s.snapshot_retention_in_days,
@@ -5,6 +5,7 @@ mod database_utils;
 mod job;
 mod router;
 
+use crate::cloud_provider::models::Storage;
 use crate::cloud_provider::Kind;
 use crate::errors::CommandError;
 use crate::models::types::CloudProvider;
@@ -64,6 +65,14 @@ impl CloudProvider for SCW {
             ("service.beta.kubernetes.io/scw-loadbalancer-use-hostname", "false"),
         ]
     }
+
+    fn volume_snapshot_class() -> Option<&'static str> {
+        Some("scw-sbv-snapshot")
+    }
+
+    fn storage_class(storage: &Storage<Self::StorageTypes>) -> String {
+        storage.storage_type.to_k8s_storage_class()
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, serde_derive::Serialize, serde_derive::Deserialize)]
@@ -74,6 +83,19 @@ pub enum ScwStorageType {
     LocalSsd,
 }
 
+impl ScwStorageType {
+    pub fn to_k8s_storage_class(&self) -> String {
+        match self {
+            // TODO(benjaminch): Switch to proper storage class
+            // Note: Seems volume storage type are not supported, only blocked storage for the time being
+            // https://github.com/scaleway/scaleway-csi/tree/master/examples/kubernetes#different-storageclass
+            ScwStorageType::BlockSsd => "scw-sbv-ssd-0", // "b_ssd",
+            ScwStorageType::LocalSsd => "l_ssd",
+        }
+        .to_string()
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub enum ScwRegion {
     Paris,
@@ -1,3 +1,4 @@
+use crate::cloud_provider::models::Storage;
 use crate::cloud_provider::Kind;
 use crate::models::types::CloudProvider;
 use crate::models::types::SelfManaged;
@@ -43,6 +44,14 @@ impl CloudProvider for SelfManaged {
     fn loadbalancer_l4_annotations() -> &'static [(&'static str, &'static str)] {
         &[]
     }
+
+    fn volume_snapshot_class() -> Option<&'static str> {
+        None
+    }
+
+    fn storage_class(storage: &Storage<Self::StorageTypes>) -> String {
+        match storage.storage_type {}
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, serde_derive::Serialize, serde_derive::Deserialize)]
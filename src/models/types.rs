@@ -4,6 +4,7 @@ use std::fmt::Write;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use crate::cloud_provider::models::Storage;
 use crate::cloud_provider::{DeploymentTarget, Kind};
 use crate::errors::{CommandError, EngineError};
 use tera::Context as TeraContext;
@@ -14,6 +15,7 @@ pub struct AWSEc2 {}
 pub struct SCW {}
 pub struct SelfManaged {}
 pub struct GCP {}
+pub struct AZURE {}
 
 // CloudProvider trait allows to derive all the custom type we need per provider,
 // with our marker type defined above to be able to select the correct one
@@ -30,6 +32,10 @@ pub trait CloudProvider: Send + Sync {
     fn registry_full_name() -> &'static str;
     fn lib_directory_name() -> &'static str;
     fn loadbalancer_l4_annotations() -> &'static [(&'static str, &'static str)];
+    /// VolumeSnapshotClass deployed with the cluster, none if volumes cannot be snapshotted
+    fn volume_snapshot_class() -> Option<&'static str>;
+    /// StorageClass the volumes of a storage are provisioned with
+    fn storage_class(storage: &Storage<Self::StorageTypes>) -> String;
 }
 
 pub trait ToTeraContext {
@@ -0,0 +1,260 @@
+use crate::cloud_provider::models::Storage;
+use k8s_openapi::api::core::v1::{PersistentVolumeClaim, PersistentVolumeClaimSpec, TypedLocalObjectReference};
+use kube::api::ObjectMeta;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Snapshots of all the storages of a service are taken every night, expired ones are removed at the same time
+const VOLUME_SNAPSHOT_SCHEDULE: &str = "0 2 * * *";
+
+/// Image used to create and prune volume snapshots
+const VOLUME_SNAPSHOT_CLIENT_IMAGE: &str = "public.ecr.aws/bitnami/kubectl:1.27.7";
+
+pub const VOLUME_SNAPSHOT_API_GROUP: &str = "snapshot.storage.k8s.io";
+pub const VOLUME_SNAPSHOT_API_VERSION: &str = "v1";
+pub const VOLUME_SNAPSHOT_KIND: &str = "VolumeSnapshot";
+
+/// Label set on snapshots (and volumes) with the long id of the storage they belong to
+pub const DISK_ID_LABEL: &str = "qovery.com/disk-id";
+
+/// Snapshot cronjob of a stateful service, rendered by the q-container chart.
+/// Only storages with a snapshot retention are snapshotted.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct VolumeSnapshotTeraContext {
+    pub name: String,
+    pub schedule: &'static str,
+    pub client_image: &'static str,
+    pub volume_snapshot_class: &'static str,
+}
+
+impl VolumeSnapshotTeraContext {
+    /// None if the cluster cannot snapshot volumes or if no storage has a snapshot retention
+    pub fn new<T>(
+        kube_name: &str,
+        storages: &[Storage<T>],
+        volume_snapshot_class: Option<&'static str>,
+    ) -> Option<Self> {
+        let volume_snapshot_class = volume_snapshot_class?;
+        if !storages.iter().any(|storage| storage.snapshot_retention_in_days > 0) {
+            return None;
+        }
+
+        Some(VolumeSnapshotTeraContext {
+            name: format!("{kube_name}-snapshot"),
+            schedule: VOLUME_SNAPSHOT_SCHEDULE,
+            client_image: VOLUME_SNAPSHOT_CLIENT_IMAGE,
+            volume_snapshot_class,
+        })
+    }
+}
+
+/// Snapshot a storage volume has been requested to be recreated from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolumeSnapshotRestore {
+    pub storage_long_id: Uuid,
+    pub snapshot_name: String,
+}
+
+pub fn volume_snapshot_restores<T>(storages: &[Storage<T>]) -> Vec<VolumeSnapshotRestore> {
+    storages
+        .iter()
+        .filter_map(|storage| {
+            storage
+                .restore_from_snapshot
+                .as_ref()
+                .map(|snapshot_name| VolumeSnapshotRestore {
+                    storage_long_id: storage.long_id,
+                    snapshot_name: snapshot_name.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// StorageClass the volume claims of a storage must be provisioned with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolumeStorageClass {
+    pub pvc_selector: String,
+    pub storage_class: String,
+}
+
+/// Volume claim replacing `pvc`, provisioned from the snapshot.
+/// Name and labels are kept, so the statefulset picks it up when its pods are recreated.
+pub fn restored_pvc(pvc: &PersistentVolumeClaim, snapshot_name: &str) -> PersistentVolumeClaim {
+    let spec = pvc.spec.clone().unwrap_or_default();
+
+    PersistentVolumeClaim {
+        metadata: ObjectMeta {
+            name: pvc.metadata.name.clone(),
+            namespace: pvc.metadata.namespace.clone(),
+            labels: pvc.metadata.labels.clone(),
+            ..Default::default()
+        },
+        spec: Some(PersistentVolumeClaimSpec {
+            access_modes: spec.access_modes,
+            storage_class_name: spec.storage_class_name,
+            resources: spec.resources,
+            volume_mode: spec.volume_mode,
+            data_source: Some(TypedLocalObjectReference {
+                api_group: Some(VOLUME_SNAPSHOT_API_GROUP.to_string()),
+                kind: VOLUME_SNAPSHOT_KIND.to_string(),
+                name: snapshot_name.to_string(),
+            }),
+            ..Default::default()
+        }),
+        status: None,
+    }
+}
+
+/// Claim bound again to the volume of `pvc`, when it has been deleted but its volume retained
+pub fn rebound_pvc(pvc: &PersistentVolumeClaim) -> PersistentVolumeClaim {
+    PersistentVolumeClaim {
+        metadata: ObjectMeta {
+            name: pvc.metadata.name.clone(),
+            namespace: pvc.metadata.namespace.clone(),
+            labels: pvc.metadata.labels.clone(),
+            ..Default::default()
+        },
+        spec: pvc.spec.clone(),
+        status: None,
+    }
+}
+
+/// A claim keeps the snapshot it has been provisioned from, it doesn't have to be restored twice
+pub fn is_restored_from(pvc: &PersistentVolumeClaim, snapshot_name: &str) -> bool {
+    pvc.spec
+        .as_ref()
+        .and_then(|spec| spec.data_source.as_ref())
+        .map(|data_source| data_source.kind == VOLUME_SNAPSHOT_KIND && data_source.name == snapshot_name)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cloud_provider::models::Storage;
+    use crate::models::aws::AwsStorageType;
+    use crate::models::volume_snapshot::{
+        is_restored_from, rebound_pvc, restored_pvc, volume_snapshot_restores, VolumeSnapshotRestore,
+        VolumeSnapshotTeraContext,
+    };
+    use k8s_openapi::api::core::v1::{PersistentVolumeClaim, PersistentVolumeClaimSpec, ResourceRequirements};
+    use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+    use kube::api::ObjectMeta;
+    use std::collections::BTreeMap;
+    use uuid::Uuid;
+
+    fn storage(snapshot_retention_in_days: u16, restore_from_snapshot: Option<&str>) -> Storage<AwsStorageType> {
+        Storage {
+            id: "z1".to_string(),
+            long_id: Uuid::new_v4(),
+            name: "data".to_string(),
+            storage_type: AwsStorageType::GP2,
+            size_in_gib: 10,
+            mount_point: "/data".to_string(),
+            snapshot_retention_in_days,
+            restore_from_snapshot: restore_from_snapshot.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_volume_snapshot_tera_context() {
+        // setup:
+        let storages = vec![storage(0, None), storage(7, None)];
+        let storages_without_retention = vec![storage(0, None)];
+
+        // execute & verify:
+        assert_eq!(
+            VolumeSnapshotTeraContext::new("app-z1", &storages, Some("aws-ebs-snapshot"))
+                .map(|ctx| (ctx.name, ctx.volume_snapshot_class)),
+            Some(("app-z1-snapshot".to_string(), "aws-ebs-snapshot"))
+        );
+        assert_eq!(VolumeSnapshotTeraContext::new("app-z1", &storages, None), None);
+        assert_eq!(
+            VolumeSnapshotTeraContext::new("app-z1", &storages_without_retention, Some("aws-ebs-snapshot")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_volume_snapshot_restores() {
+        // setup:
+        let storages = vec![storage(7, None), storage(7, Some("data-app-z1-0-20231012020000"))];
+
+        // execute:
+        let restores = volume_snapshot_restores(&storages);
+
+        // verify:
+        assert_eq!(
+            restores,
+            vec![VolumeSnapshotRestore {
+                storage_long_id: storages[1].long_id,
+                snapshot_name: "data-app-z1-0-20231012020000".to_string(),
+            }]
+        );
+    }
+
+    fn bound_pvc() -> PersistentVolumeClaim {
+        PersistentVolumeClaim {
+            metadata: ObjectMeta {
+                name: Some("data-app-z1-0".to_string()),
+                namespace: Some("z1-env".to_string()),
+                labels: Some(BTreeMap::from([(
+                    "qovery.com/disk-id".to_string(),
+                    "8d3b1c1e-6bb1-4b4f-8a0e-6cbd4a4e1f2a".to_string(),
+                )])),
+                uid: Some("7c4f0a1e".to_string()),
+                resource_version: Some("42".to_string()),
+                ..Default::default()
+            },
+            spec: Some(PersistentVolumeClaimSpec {
+                access_modes: Some(vec!["ReadWriteOnce".to_string()]),
+                storage_class_name: Some("aws-ebs-gp2-0".to_string()),
+                resources: Some(ResourceRequirements {
+                    requests: Some(BTreeMap::from([("storage".to_string(), Quantity("10Gi".to_string()))])),
+                    ..Default::default()
+                }),
+                volume_name: Some("pvc-7c4f0a1e".to_string()),
+                ..Default::default()
+            }),
+            status: None,
+        }
+    }
+
+    #[test]
+    fn test_restored_pvc() {
+        // setup:
+        let pvc = bound_pvc();
+
+        // execute:
+        let restored = restored_pvc(&pvc, "data-app-z1-0-20231012020000");
+
+        // verify:
+        assert_eq!(restored.metadata.name, pvc.metadata.name);
+        assert_eq!(restored.metadata.labels, pvc.metadata.labels);
+        assert_eq!(restored.metadata.uid, None);
+        assert_eq!(restored.metadata.resource_version, None);
+        let spec = restored.spec.as_ref().unwrap();
+        assert_eq!(spec.storage_class_name.as_deref(), Some("aws-ebs-gp2-0"));
+        assert_eq!(spec.resources, pvc.spec.as_ref().unwrap().resources);
+        // a new volume is provisioned
+        assert_eq!(spec.volume_name, None);
+        assert!(is_restored_from(&restored, "data-app-z1-0-20231012020000"));
+        assert!(!is_restored_from(&restored, "data-app-z1-0-20231011020000"));
+        assert!(!is_restored_from(&pvc, "data-app-z1-0-20231012020000"));
+    }
+    #[test]
+    fn test_rebound_pvc() {
+        // setup:
+        let pvc = bound_pvc();
+
+        // execute:
+        let rebound = rebound_pvc(&pvc);
+
+        // verify:
+        assert_eq!(rebound.metadata.name, pvc.metadata.name);
+        assert_eq!(rebound.metadata.labels, pvc.metadata.labels);
+        assert_eq!(rebound.metadata.uid, None);
+        assert_eq!(rebound.metadata.resource_version, None);
+        // the retained volume is bound again
+        assert_eq!(rebound.spec, pvc.spec);
+    }
+}
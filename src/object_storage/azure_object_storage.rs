@@ -0,0 +1,515 @@
+use chrono::{DateTime, Utc};
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::{Method, StatusCode};
+use serde_derive::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::cloud_provider::azure::locations::AzureLocation;
+use crate::models::azure::AzureCredentials;
+use crate::object_storage::errors::ObjectStorageError;
+use crate::object_storage::{Bucket, BucketDeleteStrategy, BucketObject, BucketRegion, Kind, ObjectStorage};
+
+const AZURE_STORAGE_API_VERSION: &str = "2021-08-06";
+const AZURE_STORAGE_OAUTH_SCOPE: &str = "https://storage.azure.com/.default";
+const METADATA_HEADER_PREFIX: &str = "x-ms-meta-";
+
+#[derive(Deserialize)]
+struct AccessTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+struct AccessToken {
+    value: String,
+    expires_at: Instant,
+}
+
+// doc: https://learn.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api
+// Buckets are mapped to blob containers of a storage account, the storage account itself is managed by Terraform
+// and the service principal is expected to have the `Storage Blob Data Contributor` role on it.
+pub struct AzureOS {
+    id: String,
+    name: String,
+    storage_account_name: String,
+    credentials: AzureCredentials,
+    location: AzureLocation,
+    http: Client,
+    access_token: Mutex<Option<AccessToken>>,
+}
+
+impl AzureOS {
+    pub fn new(
+        id: &str,
+        name: &str,
+        storage_account_name: &str,
+        credentials: AzureCredentials,
+        location: AzureLocation,
+    ) -> AzureOS {
+        AzureOS {
+            id: id.to_string(),
+            name: name.to_string(),
+            storage_account_name: storage_account_name.to_string(),
+            credentials,
+            location,
+            http: Client::new(),
+            access_token: Mutex::new(None),
+        }
+    }
+
+    fn container_url(&self, bucket_name: &str) -> String {
+        format!(
+            "https://{}.blob.core.windows.net/{}?restype=container",
+            self.storage_account_name, bucket_name
+        )
+    }
+
+    fn blob_url(&self, bucket_name: &str, object_key: &str) -> String {
+        format!(
+            "https://{}.blob.core.windows.net/{}/{}",
+            self.storage_account_name,
+            bucket_name,
+            urlencoding::encode(object_key).replace("%2F", "/")
+        )
+    }
+
+    fn get_access_token(&self) -> Result<String, String> {
+        let mut access_token = self.access_token.lock().map_err(|e| e.to_string())?;
+        if let Some(token) = access_token.as_ref() {
+            // keep a safety margin to avoid using a token expiring in the middle of a request
+            if token.expires_at > Instant::now() + Duration::from_secs(60) {
+                return Ok(token.value.clone());
+            }
+        }
+
+        let response = self
+            .http
+            .post(format!(
+                "https://login.microsoftonline.com/{}/oauth2/v2.0/token",
+                self.credentials.tenant_id
+            ))
+            .form(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", self.credentials.client_id.as_str()),
+                ("client_secret", self.credentials.client_secret.as_str()),
+                ("scope", AZURE_STORAGE_OAUTH_SCOPE),
+            ])
+            .send()
+            .map_err(|e| format!("Cannot get Azure access token: {e}"))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "Cannot get Azure access token, status `{}`: {}",
+                response.status(),
+                response.text().unwrap_or_default()
+            ));
+        }
+        let token: AccessTokenResponse = response
+            .json()
+            .map_err(|e| format!("Cannot parse Azure access token: {e}"))?;
+
+        *access_token = Some(AccessToken {
+            value: token.access_token.to_string(),
+            expires_at: Instant::now() + Duration::from_secs(token.expires_in),
+        });
+
+        Ok(token.access_token)
+    }
+
+    fn request(&self, method: Method, url: &str) -> Result<RequestBuilder, String> {
+        Ok(self
+            .http
+            .request(method, url)
+            .bearer_auth(self.get_access_token()?)
+            .header("x-ms-version", AZURE_STORAGE_API_VERSION)
+            .header("x-ms-date", Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string()))
+    }
+
+    fn send(&self, request: Result<RequestBuilder, String>) -> Result<Response, String> {
+        request?.send().map_err(|e| e.to_string())
+    }
+
+    fn is_bucket_name_valid(bucket_name: &str) -> Result<(), ObjectStorageError> {
+        // https://learn.microsoft.com/en-us/rest/api/storageservices/naming-and-referencing-containers--blobs--and-metadata#container-names
+        let error = |message: &str| ObjectStorageError::InvalidBucketName {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: message.to_string(),
+        };
+
+        if bucket_name.len() < 3 || bucket_name.len() > 63 {
+            return Err(error("bucket name should be between 3 and 63 characters long"));
+        }
+        if !bucket_name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err(error("bucket name can only contain lowercase letters, numbers and '-'"));
+        }
+        if bucket_name.starts_with('-') || bucket_name.ends_with('-') || bucket_name.contains("--") {
+            return Err(error(
+                "bucket name should start and end with a letter or a number and cannot contain consecutive '-'",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn list_blobs(&self, bucket_name: &str) -> Result<Vec<String>, String> {
+        let response =
+            self.send(self.request(Method::GET, format!("{}&comp=list", self.container_url(bucket_name)).as_str()))?;
+        if !response.status().is_success() {
+            return Err(format!("Cannot list blobs, status `{}`", response.status()));
+        }
+
+        Ok(parse_blob_names(response.text().map_err(|e| e.to_string())?.as_str()))
+    }
+
+    fn empty_bucket(&self, bucket_name: &str) -> Result<(), ObjectStorageError> {
+        let blobs = self
+            .list_blobs(bucket_name)
+            .map_err(|e| ObjectStorageError::CannotEmptyBucket {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e,
+            })?;
+
+        for blob in blobs {
+            self.delete_object(bucket_name, &blob)
+                .map_err(|e| ObjectStorageError::CannotEmptyBucket {
+                    bucket_name: bucket_name.to_string(),
+                    raw_error_message: e.to_string(),
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Extract blob names from a `List Blobs` XML response
+/// https://learn.microsoft.com/en-us/rest/api/storageservices/list-blobs#response-body
+fn parse_blob_names(list_blobs_response: &str) -> Vec<String> {
+    list_blobs_response
+        .split("<Blob>")
+        .skip(1)
+        .filter_map(|blob| {
+            let start = blob.find("<Name>")? + "<Name>".len();
+            let end = blob[start..].find("</Name>")? + start;
+            Some(
+                blob[start..end]
+                    .replace("&lt;", "<")
+                    .replace("&gt;", ">")
+                    .replace("&quot;", "\"")
+                    .replace("&apos;", "'")
+                    .replace("&amp;", "&"),
+            )
+        })
+        .collect()
+}
+
+impl ObjectStorage for AzureOS {
+    fn kind(&self) -> Kind {
+        Kind::AzureOs
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn is_valid(&self) -> Result<(), ObjectStorageError> {
+        Ok(())
+    }
+
+    fn bucket_exists(&self, bucket_name: &str) -> bool {
+        match self.send(self.request(Method::HEAD, &self.container_url(bucket_name))) {
+            Ok(response) => response.status().is_success(),
+            Err(_) => false,
+        }
+    }
+
+    fn create_bucket(
+        &self,
+        bucket_name: &str,
+        bucket_ttl: Option<Duration>,
+        bucket_versioning_activated: bool,
+    ) -> Result<Bucket, ObjectStorageError> {
+        AzureOS::is_bucket_name_valid(bucket_name)?;
+
+        // check if bucket already exists, if so, no need to recreate it
+        if let Ok(existing_bucket) = self.get_bucket(bucket_name) {
+            return Ok(existing_bucket);
+        }
+
+        // Note: blob containers don't support TTL nor versioning, those are kept as metadata
+        let creation_date: DateTime<Utc> = Utc::now();
+        let request = self.request(Method::PUT, &self.container_url(bucket_name)).map(|r| {
+            r.header(format!("{METADATA_HEADER_PREFIX}creationdate"), creation_date.to_rfc3339())
+                .header(
+                    format!("{METADATA_HEADER_PREFIX}ttl"),
+                    bucket_ttl.map(|ttl| ttl.as_secs()).unwrap_or(0).to_string(),
+                )
+                .header(
+                    format!("{METADATA_HEADER_PREFIX}versioning"),
+                    bucket_versioning_activated.to_string(),
+                )
+                .header(reqwest::header::CONTENT_LENGTH, 0)
+        });
+        match self.send(request) {
+            Ok(response) if response.status().is_success() || response.status() == StatusCode::CONFLICT => {}
+            Ok(response) => {
+                return Err(ObjectStorageError::CannotCreateBucket {
+                    bucket_name: bucket_name.to_string(),
+                    raw_error_message: format!(
+                        "status `{}`: {}",
+                        response.status(),
+                        response.text().unwrap_or_default()
+                    ),
+                })
+            }
+            Err(e) => {
+                return Err(ObjectStorageError::CannotCreateBucket {
+                    bucket_name: bucket_name.to_string(),
+                    raw_error_message: e,
+                })
+            }
+        }
+
+        self.get_bucket(bucket_name)
+    }
+
+    fn get_bucket(&self, bucket_name: &str) -> Result<Bucket, ObjectStorageError> {
+        let response = self
+            .send(self.request(Method::HEAD, &self.container_url(bucket_name)))
+            .map_err(|e| ObjectStorageError::CannotGetBucket {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e,
+            })?;
+        if !response.status().is_success() {
+            return Err(ObjectStorageError::CannotGetBucket {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: format!("Bucket `{}` doesn't exist", bucket_name),
+            });
+        }
+
+        let labels: HashMap<String, String> = response
+            .headers()
+            .iter()
+            .filter_map(|(key, value)| {
+                key.as_str()
+                    .strip_prefix(METADATA_HEADER_PREFIX)
+                    .map(|k| (k.to_string(), value.to_str().unwrap_or_default().to_string()))
+            })
+            .collect();
+
+        Ok(Bucket {
+            name: bucket_name.to_string(),
+            ttl: labels
+                .get("ttl")
+                .and_then(|ttl| ttl.parse::<u64>().ok())
+                .filter(|ttl| *ttl > 0)
+                .map(Duration::from_secs),
+            versioning_activated: labels.get("versioning").map(|v| v == "true").unwrap_or(false),
+            location: BucketRegion::AzureRegion(self.location.clone()),
+            labels: Some(labels),
+        })
+    }
+
+    fn delete_bucket(
+        &self,
+        bucket_name: &str,
+        bucket_delete_strategy: BucketDeleteStrategy,
+    ) -> Result<(), ObjectStorageError> {
+        AzureOS::is_bucket_name_valid(bucket_name)?;
+
+        if !self.bucket_exists(bucket_name) {
+            return Ok(());
+        }
+
+        match bucket_delete_strategy {
+            // deleting a container deletes all the blobs it contains
+            BucketDeleteStrategy::HardDelete => {
+                match self.send(self.request(Method::DELETE, &self.container_url(bucket_name))) {
+                    Ok(response) if response.status().is_success() || response.status() == StatusCode::NOT_FOUND => {
+                        Ok(())
+                    }
+                    Ok(response) => Err(ObjectStorageError::CannotDeleteBucket {
+                        bucket_name: bucket_name.to_string(),
+                        raw_error_message: format!("status `{}`", response.status()),
+                    }),
+                    Err(e) => Err(ObjectStorageError::CannotDeleteBucket {
+                        bucket_name: bucket_name.to_string(),
+                        raw_error_message: e,
+                    }),
+                }
+            }
+            BucketDeleteStrategy::Empty => self.empty_bucket(bucket_name),
+        }
+    }
+
+    fn get_object(&self, bucket_name: &str, object_key: &str) -> Result<BucketObject, ObjectStorageError> {
+        let error = |raw_error_message: String| ObjectStorageError::CannotGetObjectFile {
+            bucket_name: bucket_name.to_string(),
+            object_name: object_key.to_string(),
+            raw_error_message,
+        };
+
+        let response = self
+            .send(self.request(Method::GET, &self.blob_url(bucket_name, object_key)))
+            .map_err(error)?;
+        if !response.status().is_success() {
+            return Err(error(format!("status `{}`", response.status())));
+        }
+
+        Ok(BucketObject {
+            bucket_name: bucket_name.to_string(),
+            key: object_key.to_string(),
+            value: response
+                .bytes()
+                .map_err(|e| error(format!("Cannot read response body: {e}")))?
+                .to_vec(),
+        })
+    }
+
+    fn put_object(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        file_path: &Path,
+    ) -> Result<BucketObject, ObjectStorageError> {
+        let error = |raw_error_message: String| ObjectStorageError::CannotUploadFile {
+            bucket_name: bucket_name.to_string(),
+            object_name: object_key.to_string(),
+            raw_error_message,
+        };
+
+        let file_content = std::fs::read(file_path).map_err(|e| error(e.to_string()))?;
+        let request = self
+            .request(Method::PUT, &self.blob_url(bucket_name, object_key))
+            .map(|r| r.header("x-ms-blob-type", "BlockBlob").body(file_content.clone()));
+        let response = self.send(request).map_err(error)?;
+        if !response.status().is_success() {
+            return Err(error(format!(
+                "status `{}`: {}",
+                response.status(),
+                response.text().unwrap_or_default()
+            )));
+        }
+
+        Ok(BucketObject {
+            bucket_name: bucket_name.to_string(),
+            key: object_key.to_string(),
+            value: file_content,
+        })
+    }
+
+    fn delete_object(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError> {
+        let error = |raw_error_message: String| ObjectStorageError::CannotDeleteFile {
+            bucket_name: bucket_name.to_string(),
+            object_name: object_key.to_string(),
+            raw_error_message,
+        };
+
+        let response = self
+            .send(self.request(Method::DELETE, &self.blob_url(bucket_name, object_key)))
+            .map_err(error)?;
+        match response.status() {
+            s if s.is_success() || s == StatusCode::NOT_FOUND => Ok(()),
+            s => Err(error(format!("status `{s}`"))),
+        }
+    }
+
+    fn list_objects(&self, bucket_name: &str, prefix: Option<&str>) -> Result<Vec<String>, ObjectStorageError> {
+        let blobs = self
+            .list_blobs(bucket_name)
+            .map_err(|e| ObjectStorageError::CannotListObjects {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e,
+            })?;
+
+        Ok(blobs
+            .into_iter()
+            .filter(|blob| prefix.map(|prefix| blob.starts_with(prefix)).unwrap_or(true))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::object_storage::azure_object_storage::{parse_blob_names, AzureOS};
+    use crate::object_storage::errors::ObjectStorageError;
+
+    #[test]
+    fn test_is_bucket_name_valid() {
+        // setup:
+        struct TestCase<'a> {
+            bucket_name_input: &'a str,
+            expected_output: Result<(), ObjectStorageError>,
+            description: &'a str,
+        }
+
+        let invalid = |bucket_name: &str, raw_error_message: &str| ObjectStorageError::InvalidBucketName {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: raw_error_message.to_string(),
+        };
+
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                bucket_name_input: "",
+                expected_output: Err(invalid("", "bucket name should be between 3 and 63 characters long")),
+                description: "bucket name is empty",
+            },
+            TestCase {
+                bucket_name_input: "Qovery-Kubeconfigs",
+                expected_output: Err(invalid(
+                    "Qovery-Kubeconfigs",
+                    "bucket name can only contain lowercase letters, numbers and '-'",
+                )),
+                description: "bucket name contains uppercase letters",
+            },
+            TestCase {
+                bucket_name_input: "qovery--kubeconfigs",
+                expected_output: Err(invalid(
+                    "qovery--kubeconfigs",
+                    "bucket name should start and end with a letter or a number and cannot contain consecutive '-'",
+                )),
+                description: "bucket name contains consecutive '-'",
+            },
+            TestCase {
+                bucket_name_input: "qovery-kubeconfigs-z1234567",
+                expected_output: Ok(()),
+                description: "bucket name is valid",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = AzureOS::is_bucket_name_valid(tc.bucket_name_input);
+
+            // verify:
+            assert_eq!(tc.expected_output, result, "case: {}", tc.description);
+        }
+    }
+
+    #[test]
+    fn test_parse_blob_names() {
+        // setup:
+        let list_blobs_response = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://qovery.blob.core.windows.net/" ContainerName="qovery-kubeconfigs">
+  <Blobs>
+    <Blob><Name>z1234567.yaml</Name><Properties><Content-Length>12</Content-Length></Properties></Blob>
+    <Blob><Name>logs/a&amp;b.log</Name><Properties><Content-Length>42</Content-Length></Properties></Blob>
+  </Blobs>
+  <NextMarker />
+</EnumerationResults>"#;
+
+        // execute:
+        let blob_names = parse_blob_names(list_blobs_response);
+
+        // verify:
+        assert_eq!(vec!["z1234567.yaml".to_string(), "logs/a&b.log".to_string()], blob_names);
+        assert!(parse_blob_names("<EnumerationResults><Blobs /></EnumerationResults>").is_empty());
+    }
+}
@@ -56,6 +56,11 @@ pub enum ObjectStorageError {
         object_name: String,
         raw_error_message: String,
     },
+    #[error("Cannot list objects error for `{bucket_name:?}`: {raw_error_message:?}.")]
+    CannotListObjects {
+        bucket_name: String,
+        raw_error_message: String,
+    },
     #[error("Cannot delete object `{object_name:?}` error for `{bucket_name:?}`: {raw_error_message:?}.")]
     CannotDeleteFile {
         bucket_name: String,
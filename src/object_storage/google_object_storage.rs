@@ -179,6 +179,15 @@ impl ObjectStorage for GoogleOS {
                 raw_error_message: e.to_string(),
             })
     }
+
+    fn list_objects(&self, bucket_name: &str, prefix: Option<&str>) -> Result<Vec<String>, ObjectStorageError> {
+        self.service
+            .list_objects_keys_only(bucket_name, prefix)
+            .map_err(|e| ObjectStorageError::CannotListObjects {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            })
+    }
 }
 
 #[cfg(test)]
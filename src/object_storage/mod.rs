@@ -4,15 +4,19 @@ use std::path::Path;
 use std::time::Duration;
 
 use crate::cloud_provider::aws::regions::AwsRegion;
+use crate::cloud_provider::azure::locations::AzureLocation;
 use crate::models::scaleway::ScwZone;
 use crate::models::ToCloudProviderFormat;
 use crate::object_storage::errors::ObjectStorageError;
+use crate::object_storage::s3_compatible_object_storage::S3CompatibleRegion;
 use crate::services::gcp::object_storage_regions::GcpStorageRegion;
 use enum_dispatch::enum_dispatch;
 
+pub mod azure_object_storage;
 pub mod errors;
 pub mod google_object_storage;
 pub mod s3;
+pub mod s3_compatible_object_storage;
 pub mod scaleway_object_storage;
 
 #[derive(Clone)]
@@ -27,6 +31,8 @@ pub enum BucketRegion {
     AwsRegion(AwsRegion),
     ScwRegion(ScwZone),
     GcpRegion(GcpStorageRegion),
+    AzureRegion(AzureLocation),
+    S3CompatibleRegion(S3CompatibleRegion),
 }
 
 #[enum_dispatch(StorageRegion)]
@@ -64,6 +70,7 @@ pub trait ObjectStorage {
         file_path: &Path,
     ) -> Result<BucketObject, ObjectStorageError>;
     fn delete_object(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError>;
+    fn list_objects(&self, bucket_name: &str, prefix: Option<&str>) -> Result<Vec<String>, ObjectStorageError>;
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -73,6 +80,8 @@ pub enum Kind {
     Spaces,
     ScalewayOs,
     GcpOs,
+    AzureOs,
+    S3Compatible,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -12,8 +12,9 @@ use rusoto_core::{Client, HttpClient, Region as RusotoRegion};
 use rusoto_s3::{
     CreateBucketConfiguration, CreateBucketRequest, Delete, DeleteBucketRequest, DeleteObjectRequest,
     DeleteObjectsRequest, GetBucketLifecycleRequest, GetBucketTaggingRequest, GetBucketVersioningRequest,
-    GetObjectRequest, HeadBucketRequest, ListObjectsRequest, ObjectIdentifier, PutBucketTaggingRequest,
-    PutBucketVersioningRequest, PutObjectRequest, S3Client, StreamingBody, Tag, Tagging, S3 as RusotoS3,
+    GetObjectRequest, HeadBucketRequest, ListObjectsRequest, ListObjectsV2Request, ObjectIdentifier,
+    PutBucketTaggingRequest, PutBucketVersioningRequest, PutObjectRequest, S3Client, StreamingBody, Tag, Tagging,
+    S3 as RusotoS3,
 };
 
 use crate::models::ToCloudProviderFormat;
@@ -401,6 +402,35 @@ impl ObjectStorage for S3 {
             }),
         }
     }
+
+    fn list_objects(&self, bucket_name: &str, prefix: Option<&str>) -> Result<Vec<String>, ObjectStorageError> {
+        S3::is_bucket_name_valid(bucket_name)?;
+
+        let s3_client = self.get_s3_client();
+        let mut keys = vec![];
+        let mut continuation_token: Option<String> = None;
+
+        loop {
+            let res = block_on(s3_client.list_objects_v2(ListObjectsV2Request {
+                bucket: bucket_name.to_string(),
+                prefix: prefix.map(str::to_string),
+                continuation_token,
+                ..Default::default()
+            }))
+            .map_err(|e| ObjectStorageError::CannotListObjects {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            })?;
+
+            keys.extend(res.contents.unwrap_or_default().into_iter().filter_map(|o| o.key));
+            continuation_token = res.next_continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(keys)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,670 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+
+use hyper::client::HttpConnector;
+use hyper_tls::HttpsConnector;
+use rusoto_core::{Client, HttpClient, Region as RusotoRegion};
+use rusoto_credential::StaticProvider;
+use rusoto_s3::{
+    CreateBucketConfiguration, CreateBucketRequest, Delete, DeleteBucketRequest, DeleteObjectRequest,
+    DeleteObjectsRequest, GetBucketTaggingRequest, GetBucketVersioningRequest, GetObjectRequest, HeadBucketRequest,
+    ListObjectVersionsRequest, ListObjectsV2Request, ObjectIdentifier, PutBucketTaggingRequest,
+    PutBucketVersioningRequest, PutObjectRequest, S3Client, StreamingBody, Tag, Tagging, VersioningConfiguration, S3,
+};
+use url::Url;
+
+use crate::models::ToCloudProviderFormat;
+use crate::object_storage::errors::ObjectStorageError;
+use crate::object_storage::{
+    Bucket, BucketDeleteStrategy, BucketObject, BucketRegion, Kind, ObjectStorage, StorageRegion,
+};
+use crate::runtime::block_on;
+
+/// Region as configured on the S3 compatible storage, most of them accept any value (i.e: `us-east-1`, `auto` for R2)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct S3CompatibleRegion(pub String);
+
+/// Region requests are signed with when none is configured, the default one of MinIO and Ceph RGW
+const DEFAULT_SIGNING_REGION: &str = "us-east-1";
+
+impl StorageRegion for S3CompatibleRegion {}
+
+impl ToCloudProviderFormat for S3CompatibleRegion {
+    fn to_cloud_provider_format(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+/// Object storage speaking the S3 API on a custom endpoint (i.e: MinIO, Ceph RGW, Wasabi, Cloudflare R2).
+/// Buckets are addressed path-style (`https://endpoint/bucket/key`), so no wildcard DNS record nor
+/// wildcard certificate is required on the endpoint.
+pub struct S3Compatible {
+    id: String,
+    name: String,
+    endpoint: Url,
+    region: Option<S3CompatibleRegion>,
+    access_key_id: String,
+    secret_access_key: String,
+    ca_certificate: Option<String>,
+}
+
+impl S3Compatible {
+    /// `ca_certificate` is a PEM encoded certificate trusted in addition to the system ones,
+    /// for endpoints served with a certificate signed by a private CA.
+    /// Without `region`, buckets are created in the default location of the storage.
+    pub fn new(
+        id: String,
+        name: String,
+        endpoint: Url,
+        region: Option<S3CompatibleRegion>,
+        access_key_id: String,
+        secret_access_key: String,
+        ca_certificate: Option<String>,
+    ) -> Self {
+        S3Compatible {
+            id,
+            name,
+            endpoint,
+            region,
+            access_key_id,
+            secret_access_key,
+            ca_certificate,
+        }
+    }
+
+    fn signing_region(&self) -> S3CompatibleRegion {
+        self.region
+            .clone()
+            .unwrap_or_else(|| S3CompatibleRegion(DEFAULT_SIGNING_REGION.to_string()))
+    }
+
+    fn get_credentials(&self) -> StaticProvider {
+        StaticProvider::new(self.access_key_id.clone(), self.secret_access_key.clone(), None, None)
+    }
+
+    fn get_http_client(&self) -> Result<HttpClient<HttpsConnector<HttpConnector>>, ObjectStorageError> {
+        let mut tls_connector = native_tls::TlsConnector::builder();
+        if let Some(ca_certificate) = &self.ca_certificate {
+            let certificate = native_tls::Certificate::from_pem(ca_certificate.as_bytes()).map_err(|e| {
+                ObjectStorageError::CannotInstantiateClient {
+                    raw_error_message: format!("Invalid CA certificate: {}", e),
+                }
+            })?;
+            tls_connector.add_root_certificate(certificate);
+        }
+        let tls_connector = tls_connector
+            .build()
+            .map_err(|e| ObjectStorageError::CannotInstantiateClient {
+                raw_error_message: e.to_string(),
+            })?;
+
+        let mut http_connector = HttpConnector::new();
+        http_connector.enforce_http(false);
+
+        Ok(HttpClient::from_connector(HttpsConnector::from((
+            http_connector,
+            tokio_native_tls::TlsConnector::from(tls_connector),
+        ))))
+    }
+
+    fn get_s3_client(&self) -> Result<S3Client, ObjectStorageError> {
+        // rusoto always addresses buckets path-style on custom regions
+        let region = RusotoRegion::Custom {
+            name: self.signing_region().to_cloud_provider_format().to_string(),
+            endpoint: self.endpoint.as_str().trim_end_matches('/').to_string(),
+        };
+        let client = Client::new_with(self.get_credentials(), self.get_http_client()?);
+
+        Ok(S3Client::new_with_client(client, region))
+    }
+
+    fn is_bucket_name_valid(bucket_name: &str) -> Result<(), ObjectStorageError> {
+        if bucket_name.is_empty() {
+            return Err(ObjectStorageError::InvalidBucketName {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: "bucket name cannot be empty".to_string(),
+            });
+        }
+        // Path-style addressing allows names not being valid DNS labels on AWS, but MinIO and Ceph RGW
+        // reject them, so enforcing the common rules
+        if bucket_name.len() < 3 || bucket_name.len() > 63 {
+            return Err(ObjectStorageError::InvalidBucketName {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: "bucket name should be between 3 and 63 characters long".to_string(),
+            });
+        }
+        if !bucket_name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '.')
+        {
+            return Err(ObjectStorageError::InvalidBucketName {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: "bucket name can only contain lowercase letters, numbers, '.' and '-'".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn empty_bucket(&self, bucket_name: &str) -> Result<(), ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        let s3_client = self.get_s3_client()?;
+
+        // listing versions rather than objects, otherwise older versions and delete markers of a versioned bucket
+        // would remain and prevent its deletion
+        loop {
+            let versions = block_on(s3_client.list_object_versions(ListObjectVersionsRequest {
+                bucket: bucket_name.to_string(),
+                ..Default::default()
+            }))
+            .map_err(|e| ObjectStorageError::CannotEmptyBucket {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            })?;
+
+            let objects_to_be_deleted: Vec<ObjectIdentifier> = versions
+                .versions
+                .unwrap_or_default()
+                .into_iter()
+                .map(|v| (v.key, v.version_id))
+                .chain(
+                    versions
+                        .delete_markers
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|m| (m.key, m.version_id)),
+                )
+                .filter_map(|(key, version_id)| key.map(|key| ObjectIdentifier { key, version_id }))
+                .collect();
+
+            if objects_to_be_deleted.is_empty() {
+                break;
+            }
+
+            if let Err(e) = block_on(s3_client.delete_objects(DeleteObjectsRequest {
+                bucket: bucket_name.to_string(),
+                delete: Delete {
+                    objects: objects_to_be_deleted,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })) {
+                return Err(ObjectStorageError::CannotEmptyBucket {
+                    bucket_name: bucket_name.to_string(),
+                    raw_error_message: e.to_string(),
+                });
+            }
+
+            if !versions.is_truncated.unwrap_or(false) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ObjectStorage for S3Compatible {
+    fn kind(&self) -> Kind {
+        Kind::S3Compatible
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn is_valid(&self) -> Result<(), ObjectStorageError> {
+        // listing buckets checks the endpoint is reachable and the credentials are accepted
+        block_on(self.get_s3_client()?.list_buckets()).map(|_| ()).map_err(|e| {
+            ObjectStorageError::CannotInstantiateClient {
+                raw_error_message: format!("Cannot list buckets on `{}`: {}", self.endpoint, e),
+            }
+        })
+    }
+
+    fn bucket_exists(&self, bucket_name: &str) -> bool {
+        let s3_client = match self.get_s3_client() {
+            Ok(client) => client,
+            Err(_) => return false,
+        };
+
+        block_on(s3_client.head_bucket(HeadBucketRequest {
+            bucket: bucket_name.to_string(),
+            expected_bucket_owner: None,
+        }))
+        .is_ok()
+    }
+
+    fn create_bucket(
+        &self,
+        bucket_name: &str,
+        bucket_ttl: Option<Duration>,
+        bucket_versioning_activated: bool,
+    ) -> Result<Bucket, ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        let s3_client = self.get_s3_client()?;
+
+        // check if bucket already exists, if so, no need to recreate it
+        if let Ok(existing_bucket) = self.get_bucket(bucket_name) {
+            return Ok(existing_bucket);
+        }
+
+        if let Err(e) = block_on(s3_client.create_bucket(CreateBucketRequest {
+            bucket: bucket_name.to_string(),
+            // Ceph RGW rejects location constraints not matching one of its zone groups
+            create_bucket_configuration: self.region.as_ref().map(|region| CreateBucketConfiguration {
+                location_constraint: Some(region.to_cloud_provider_format().to_string()),
+            }),
+            ..Default::default()
+        })) {
+            return Err(ObjectStorageError::CannotCreateBucket {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            });
+        }
+
+        let creation_date: DateTime<Utc> = Utc::now();
+        if let Err(e) = block_on(s3_client.put_bucket_tagging(PutBucketTaggingRequest {
+            bucket: bucket_name.to_string(),
+            expected_bucket_owner: None,
+            tagging: Tagging {
+                tag_set: vec![
+                    Tag {
+                        key: "CreationDate".to_string(),
+                        value: creation_date.to_rfc3339(),
+                    },
+                    Tag {
+                        key: "Ttl".to_string(),
+                        value: format!("{}", bucket_ttl.map(|ttl| ttl.as_secs()).unwrap_or(0)),
+                    },
+                ],
+            },
+            ..Default::default()
+        })) {
+            return Err(ObjectStorageError::CannotTagBucket {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            });
+        }
+
+        if bucket_versioning_activated {
+            if let Err(e) = block_on(s3_client.put_bucket_versioning(PutBucketVersioningRequest {
+                bucket: bucket_name.to_string(),
+                versioning_configuration: VersioningConfiguration {
+                    status: Some("Enabled".to_string()),
+                    mfa_delete: None,
+                },
+                ..Default::default()
+            })) {
+                return Err(ObjectStorageError::CannotActivateBucketVersioning {
+                    bucket_name: bucket_name.to_string(),
+                    raw_error_message: e.to_string(),
+                });
+            }
+        }
+
+        self.get_bucket(bucket_name)
+    }
+
+    fn get_bucket(&self, bucket_name: &str) -> Result<Bucket, ObjectStorageError> {
+        // if bucket doesn't exist, then return an error
+        if !self.bucket_exists(bucket_name) {
+            return Err(ObjectStorageError::CannotGetBucket {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: format!("Bucket `{}` doesn't exist", bucket_name),
+            });
+        }
+
+        let s3_client = self.get_s3_client()?;
+
+        // Get versioning
+        let mut versioning_activated = false;
+        if let Ok(versioning) = block_on(s3_client.get_bucket_versioning(GetBucketVersioningRequest {
+            bucket: bucket_name.to_string(),
+            expected_bucket_owner: None,
+        })) {
+            if let Some(status) = versioning.status.map(|s| s.to_lowercase()) {
+                if status == "enabled" {
+                    versioning_activated = true;
+                }
+            }
+        }
+
+        // Get labels, TTL is not set as a lifecycle rule since it would expire bucket objects, it's kept in the tags
+        let mut labels: Option<HashMap<String, String>> = None;
+        if let Ok(tagging) = block_on(s3_client.get_bucket_tagging(GetBucketTaggingRequest {
+            bucket: bucket_name.to_string(),
+            expected_bucket_owner: None,
+        })) {
+            labels = Some(HashMap::from_iter(tagging.tag_set.into_iter().map(|t| (t.key, t.value))));
+        }
+        let ttl = labels.as_ref().and_then(ttl_from_labels);
+
+        Ok(Bucket {
+            name: bucket_name.to_string(),
+            ttl,
+            versioning_activated,
+            location: BucketRegion::S3CompatibleRegion(self.signing_region()),
+            labels,
+        })
+    }
+
+    fn delete_bucket(
+        &self,
+        bucket_name: &str,
+        bucket_delete_strategy: BucketDeleteStrategy,
+    ) -> Result<(), ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        let s3_client = self.get_s3_client()?;
+
+        // make sure to delete all bucket content before trying to delete the bucket
+        self.empty_bucket(bucket_name)?;
+
+        match bucket_delete_strategy {
+            BucketDeleteStrategy::HardDelete => match block_on(s3_client.delete_bucket(DeleteBucketRequest {
+                bucket: bucket_name.to_string(),
+                expected_bucket_owner: None,
+            })) {
+                Ok(_) => Ok(()),
+                Err(e) => Err(ObjectStorageError::CannotDeleteBucket {
+                    bucket_name: bucket_name.to_string(),
+                    raw_error_message: e.to_string(),
+                }),
+            },
+            BucketDeleteStrategy::Empty => Ok(()),
+        }
+    }
+
+    fn get_object(&self, bucket_name: &str, object_key: &str) -> Result<BucketObject, ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        let s3_client = self.get_s3_client()?;
+
+        match block_on(s3_client.get_object(GetObjectRequest {
+            bucket: bucket_name.to_string(),
+            key: object_key.to_string(),
+            ..Default::default()
+        })) {
+            Ok(res) => {
+                let mut stream = match res.body {
+                    Some(b) => b.into_blocking_read(),
+                    None => {
+                        return Err(ObjectStorageError::CannotGetObjectFile {
+                            bucket_name: bucket_name.to_string(),
+                            object_name: object_key.to_string(),
+                            raw_error_message: "Cannot get response body".to_string(),
+                        })
+                    }
+                };
+                let mut body = Vec::new();
+                stream
+                    .read_to_end(&mut body)
+                    .map_err(|e| ObjectStorageError::CannotGetObjectFile {
+                        bucket_name: bucket_name.to_string(),
+                        object_name: object_key.to_string(),
+                        raw_error_message: format!("Cannot read response body: {}", e),
+                    })?;
+
+                Ok(BucketObject {
+                    bucket_name: bucket_name.to_string(),
+                    key: object_key.to_string(),
+                    value: body,
+                })
+            }
+            Err(e) => Err(ObjectStorageError::CannotGetObjectFile {
+                bucket_name: bucket_name.to_string(),
+                object_name: object_key.to_string(),
+                raw_error_message: e.to_string(),
+            }),
+        }
+    }
+
+    fn put_object(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        file_path: &Path,
+    ) -> Result<BucketObject, ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        let s3_client = self.get_s3_client()?;
+
+        let file_content = std::fs::read(file_path).map_err(|e| ObjectStorageError::CannotUploadFile {
+            bucket_name: bucket_name.to_string(),
+            object_name: object_key.to_string(),
+            raw_error_message: e.to_string(),
+        })?;
+
+        match block_on(s3_client.put_object(PutObjectRequest {
+            bucket: bucket_name.to_string(),
+            key: object_key.to_string(),
+            body: Some(StreamingBody::from(file_content.clone())),
+            ..Default::default()
+        })) {
+            Ok(_) => Ok(BucketObject {
+                bucket_name: bucket_name.to_string(),
+                key: object_key.to_string(),
+                value: file_content,
+            }),
+            Err(e) => Err(ObjectStorageError::CannotUploadFile {
+                bucket_name: bucket_name.to_string(),
+                object_name: object_key.to_string(),
+                raw_error_message: e.to_string(),
+            }),
+        }
+    }
+
+    fn delete_object(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError> {
+        if S3Compatible::is_bucket_name_valid(bucket_name).is_err() {
+            // bucket is missing it's ok as file can't be present
+            return Ok(());
+        };
+
+        // check if file already exists
+        if self.get_object(bucket_name, object_key).is_err() {
+            return Ok(());
+        };
+
+        let s3_client = self.get_s3_client()?;
+
+        match block_on(s3_client.delete_object(DeleteObjectRequest {
+            bucket: bucket_name.to_string(),
+            key: object_key.to_string(),
+            ..Default::default()
+        })) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(ObjectStorageError::CannotDeleteFile {
+                bucket_name: bucket_name.to_string(),
+                object_name: object_key.to_string(),
+                raw_error_message: e.to_string(),
+            }),
+        }
+    }
+
+    fn list_objects(&self, bucket_name: &str, prefix: Option<&str>) -> Result<Vec<String>, ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        let s3_client = self.get_s3_client()?;
+        let mut keys = vec![];
+        let mut continuation_token: Option<String> = None;
+
+        loop {
+            let res = block_on(s3_client.list_objects_v2(ListObjectsV2Request {
+                bucket: bucket_name.to_string(),
+                prefix: prefix.map(str::to_string),
+                continuation_token,
+                ..Default::default()
+            }))
+            .map_err(|e| ObjectStorageError::CannotListObjects {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            })?;
+
+            keys.extend(res.contents.unwrap_or_default().into_iter().filter_map(|o| o.key));
+            continuation_token = res.next_continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(keys)
+    }
+}
+
+fn ttl_from_labels(labels: &HashMap<String, String>) -> Option<Duration> {
+    labels
+        .get("Ttl")
+        .and_then(|ttl| ttl.parse::<u64>().ok())
+        .filter(|ttl| *ttl > 0)
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_bucket_name_valid() {
+        // setup:
+        struct TestCase<'a> {
+            bucket_name_input: &'a str,
+            expected_output: Result<(), ObjectStorageError>,
+            description: &'a str,
+        }
+
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                bucket_name_input: "",
+                expected_output: Err(ObjectStorageError::InvalidBucketName {
+                    bucket_name: "".to_string(),
+                    raw_error_message: "bucket name cannot be empty".to_string(),
+                }),
+                description: "bucket name is empty",
+            },
+            TestCase {
+                bucket_name_input: "ab",
+                expected_output: Err(ObjectStorageError::InvalidBucketName {
+                    bucket_name: "ab".to_string(),
+                    raw_error_message: "bucket name should be between 3 and 63 characters long".to_string(),
+                }),
+                description: "bucket name is too short",
+            },
+            TestCase {
+                bucket_name_input: "Qovery_Kubeconfigs",
+                expected_output: Err(ObjectStorageError::InvalidBucketName {
+                    bucket_name: "Qovery_Kubeconfigs".to_string(),
+                    raw_error_message: "bucket name can only contain lowercase letters, numbers, '.' and '-'"
+                        .to_string(),
+                }),
+                description: "bucket name contains forbidden characters",
+            },
+            TestCase {
+                bucket_name_input: "qovery-kubeconfigs-z1234.test",
+                expected_output: Ok(()),
+                description: "bucket name is valid",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = S3Compatible::is_bucket_name_valid(tc.bucket_name_input);
+
+            // verify:
+            assert_eq!(tc.expected_output, result, "{}", tc.description);
+        }
+    }
+
+    #[test]
+    fn test_invalid_ca_certificate() {
+        // setup:
+        let object_storage = S3Compatible::new(
+            "id".to_string(),
+            "minio".to_string(),
+            Url::parse("https://minio.local:9000").unwrap(),
+            None,
+            "access_key".to_string(),
+            "secret_key".to_string(),
+            Some("not a certificate".to_string()),
+        );
+
+        // execute:
+        let result = object_storage.is_valid();
+
+        // verify:
+        assert!(matches!(result, Err(ObjectStorageError::CannotInstantiateClient { .. })));
+    }
+
+    #[test]
+    fn test_ttl_from_labels() {
+        assert_eq!(
+            ttl_from_labels(&HashMap::from([("Ttl".to_string(), "3600".to_string())])),
+            Some(Duration::from_secs(3600))
+        );
+        assert_eq!(ttl_from_labels(&HashMap::from([("Ttl".to_string(), "0".to_string())])), None);
+        assert_eq!(ttl_from_labels(&HashMap::new()), None);
+    }
+
+    // Requires a local MinIO, i.e:
+    // docker run --rm -p 9000:9000 quay.io/minio/minio:RELEASE.2023-10-16T04-13-43Z server /data
+    #[ignore]
+    #[test]
+    fn test_object_storage_with_local_minio() {
+        // setup:
+        let object_storage = S3Compatible::new(
+            "id".to_string(),
+            "minio".to_string(),
+            Url::parse("http://127.0.0.1:9000").unwrap(),
+            None,
+            "minioadmin".to_string(),
+            "minioadmin".to_string(),
+            None,
+        );
+        let bucket_name = format!("qovery-test-{}", uuid::Uuid::new_v4());
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "content").unwrap();
+
+        // execute & verify:
+        assert!(object_storage.is_valid().is_ok());
+        assert!(object_storage.create_bucket(&bucket_name, None, true).is_ok());
+        assert!(object_storage.put_object(&bucket_name, "dir/file", file.path()).is_ok());
+        assert_eq!(
+            object_storage.list_objects(&bucket_name, Some("dir/")),
+            Ok(vec!["dir/file".to_string()])
+        );
+        assert_eq!(
+            object_storage
+                .get_object(&bucket_name, "dir/file")
+                .map(|object| object.value),
+            Ok(b"content".to_vec())
+        );
+        assert!(object_storage
+            .delete_bucket(&bucket_name, BucketDeleteStrategy::HardDelete)
+            .is_ok());
+        assert!(!object_storage.bucket_exists(&bucket_name));
+
+        // invalid credentials are reported
+        let invalid_object_storage = S3Compatible::new(
+            "id".to_string(),
+            "minio".to_string(),
+            Url::parse("http://127.0.0.1:9000").unwrap(),
+            None,
+            "minioadmin".to_string(),
+            "wrong".to_string(),
+            None,
+        );
+        assert!(matches!(
+            invalid_object_storage.is_valid(),
+            Err(ObjectStorageError::CannotInstantiateClient { .. })
+        ));
+    }
+}
@@ -14,8 +14,8 @@ use rusoto_credential::StaticProvider;
 use rusoto_s3::{
     CreateBucketConfiguration, CreateBucketRequest, Delete, DeleteBucketRequest, DeleteObjectRequest,
     DeleteObjectsRequest, GetBucketLifecycleRequest, GetBucketTaggingRequest, GetBucketVersioningRequest,
-    GetObjectRequest, HeadBucketRequest, ListObjectsRequest, ObjectIdentifier, PutBucketTaggingRequest,
-    PutBucketVersioningRequest, PutObjectRequest, S3Client, StreamingBody, Tag, Tagging, S3,
+    GetObjectRequest, HeadBucketRequest, ListObjectsRequest, ListObjectsV2Request, ObjectIdentifier,
+    PutBucketTaggingRequest, PutBucketVersioningRequest, PutObjectRequest, S3Client, StreamingBody, Tag, Tagging, S3,
 };
 
 // doc: https://www.scaleway.com/en/docs/object-storage-feature/
@@ -421,6 +421,35 @@ impl ObjectStorage for ScalewayOS {
             }),
         }
     }
+
+    fn list_objects(&self, bucket_name: &str, prefix: Option<&str>) -> Result<Vec<String>, ObjectStorageError> {
+        ScalewayOS::is_bucket_name_valid(bucket_name)?;
+
+        let s3_client = self.get_s3_client();
+        let mut keys = vec![];
+        let mut continuation_token: Option<String> = None;
+
+        loop {
+            let res = block_on(s3_client.list_objects_v2(ListObjectsV2Request {
+                bucket: bucket_name.to_string(),
+                prefix: prefix.map(str::to_string),
+                continuation_token,
+                ..Default::default()
+            }))
+            .map_err(|e| ObjectStorageError::CannotListObjects {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            })?;
+
+            keys.extend(res.contents.unwrap_or_default().into_iter().filter_map(|o| o.key));
+            continuation_token = res.next_continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(keys)
+    }
 }
 
 struct ScalewayObjectStorageErrorManager {}
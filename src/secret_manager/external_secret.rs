@@ -0,0 +1,775 @@
+use crate::cloud_provider::{CloudProvider, Kind};
+use crate::io_models::variable_utils::VariableInfo;
+use crate::runtime::block_on;
+use crate::secret_manager::vault::QVaultClient;
+use crate::services::gcp::google_cloud_sdk_types::new_gcp_access_token;
+use crate::utilities::calculate_hash;
+use aws_types::SdkConfig;
+use base64::engine::general_purpose;
+use base64::Engine;
+use derivative::Derivative;
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::batch::v1::CronJob;
+use k8s_openapi::api::core::v1::Secret;
+use k8s_openapi::ByteString;
+use kube::api::{ListParams, Patch, PatchParams};
+use kube::Api;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Vault kv v2 mount of the secrets organizations can reference, an organization only reads under its long id
+pub const EXTERNAL_SECRETS_VAULT_MOUNT: &str = "external-secrets";
+
+/// Annotation of the service secret and pod template, hash of the versions of the refreshed external secrets
+pub const EXTERNAL_SECRETS_VERSION_ANNOTATION: &str = "qovery.com/external-secrets-version";
+
+const GCP_SECRET_MANAGER_URL: &str = "https://secretmanager.googleapis.com/v1";
+
+#[derive(Clone, Error, Debug, PartialEq, Eq)]
+pub enum ExternalSecretError {
+    #[error("Invalid external secret reference `{reference}`: {reason}")]
+    InvalidReference { reference: String, reason: String },
+    #[error("External secret `{reference}` is not allowed: {reason}")]
+    ForbiddenReference { reference: String, reason: String },
+    #[error("External secret `{reference}` is not available on this cluster: {reason}")]
+    UnsupportedProvider { reference: String, reason: String },
+    #[error("Cannot retrieve external secret `{reference}`: {raw_error_message}")]
+    CannotRetrieveSecret {
+        reference: String,
+        raw_error_message: String,
+    },
+    #[error("Key `{key}` not found in external secret `{reference}`")]
+    KeyNotFound { reference: String, key: String },
+}
+
+/// Where an external secret is kept, parsed from its reference
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExternalSecretReference {
+    /// `vault://<mount>/<path>#<key>`, read from a kv v2 secret engine.
+    /// Only `vault://external-secrets/<organization long id>/<path>#<key>` can be resolved.
+    Vault { mount: String, path: String, key: String },
+    /// `aws-sm://<secret arn or name>#<key>`, the whole secret string is used when there is no key
+    AwsSecretsManager { secret_id: String, key: Option<String> },
+    /// `gcp-sm://projects/<project>/secrets/<secret>/versions/<version>`, latest version when there is none
+    GcpSecretManager { version_name: String },
+}
+
+impl FromStr for ExternalSecretReference {
+    type Err = ExternalSecretError;
+
+    fn from_str(reference: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| ExternalSecretError::InvalidReference {
+            reference: reference.to_string(),
+            reason: reason.to_string(),
+        };
+        let (scheme, location) = reference
+            .split_once("://")
+            .ok_or_else(|| invalid("expected <scheme>://<location>"))?;
+        let (location, key) = match location.split_once('#') {
+            Some((location, key)) if !key.is_empty() => (location, Some(key.to_string())),
+            Some((location, _)) => (location, None),
+            None => (location, None),
+        };
+        if location.is_empty() {
+            return Err(invalid("location is empty"));
+        }
+
+        match scheme {
+            "vault" => {
+                let (mount, path) = location
+                    .split_once('/')
+                    .filter(|(mount, path)| !mount.is_empty() && !path.is_empty())
+                    .ok_or_else(|| invalid("expected vault://<mount>/<path>#<key>"))?;
+                Ok(ExternalSecretReference::Vault {
+                    mount: mount.to_string(),
+                    path: path.to_string(),
+                    key: key.ok_or_else(|| invalid("vault secrets need a #<key>"))?,
+                })
+            }
+            "aws-sm" => Ok(ExternalSecretReference::AwsSecretsManager {
+                secret_id: location.to_string(),
+                key,
+            }),
+            "gcp-sm" => {
+                if !location.starts_with("projects/") || !location.contains("/secrets/") {
+                    return Err(invalid("expected gcp-sm://projects/<project>/secrets/<secret>"));
+                }
+                Ok(ExternalSecretReference::GcpSecretManager {
+                    version_name: match location.contains("/versions/") {
+                        true => location.to_string(),
+                        false => format!("{location}/versions/latest"),
+                    },
+                })
+            }
+            _ => Err(invalid("scheme should be one of vault, aws-sm or gcp-sm")),
+        }
+    }
+}
+
+#[derive(Clone, Derivative, PartialEq, Eq)]
+#[derivative(Debug)]
+pub struct ResolvedExternalSecret {
+    #[derivative(Debug = "ignore")]
+    pub value: String,
+    pub version: String,
+}
+
+/// Refreshed external secrets of a service, the pods of the service are restarted when their versions change
+#[derive(Clone, Derivative, PartialEq, Eq)]
+#[derivative(Debug)]
+pub struct RefreshedExternalSecrets {
+    /// Resolved value of each refreshed variable
+    #[derivative(Debug = "ignore")]
+    pub values: BTreeMap<String, String>,
+    pub versions_hash: String,
+}
+
+#[derive(Deserialize)]
+struct GcpAccessSecretVersionResponse {
+    name: String,
+    payload: GcpSecretPayload,
+}
+
+#[derive(Deserialize)]
+struct GcpSecretPayload {
+    data: String,
+}
+
+/// Resolves external secrets with the vault of the engine and the credentials of the cluster cloud provider
+pub struct ExternalSecretResolver {
+    qvault_client: Option<QVaultClient>,
+    organization_long_id: Uuid,
+    aws_sdk_config: Option<SdkConfig>,
+    gcp_credentials: Option<String>,
+}
+
+impl ExternalSecretResolver {
+    pub fn new(
+        qvault_client: Option<QVaultClient>,
+        organization_long_id: Uuid,
+        cloud_provider: &dyn CloudProvider,
+    ) -> Self {
+        ExternalSecretResolver {
+            qvault_client,
+            organization_long_id,
+            aws_sdk_config: match cloud_provider.kind() {
+                Kind::Aws => cloud_provider.aws_sdk_client(),
+                _ => None,
+            },
+            gcp_credentials: match cloud_provider.kind() {
+                // GCP credentials JSON is given as secret access key
+                Kind::Gcp => Some(cloud_provider.secret_access_key()),
+                _ => None,
+            },
+        }
+    }
+
+    pub fn resolve(&self, reference: &str) -> Result<ResolvedExternalSecret, ExternalSecretError> {
+        let cannot_retrieve = |raw_error_message: String| ExternalSecretError::CannotRetrieveSecret {
+            reference: reference.to_string(),
+            raw_error_message,
+        };
+        let unsupported = |reason: &str| ExternalSecretError::UnsupportedProvider {
+            reference: reference.to_string(),
+            reason: reason.to_string(),
+        };
+
+        match ExternalSecretReference::from_str(reference)? {
+            ExternalSecretReference::Vault { mount, path, key } => {
+                // the engine vault also holds Qovery and other organizations secrets, never read outside of the organization path
+                if !is_organization_vault_path(&mount, &path, &self.organization_long_id) {
+                    return Err(ExternalSecretError::ForbiddenReference {
+                        reference: reference.to_string(),
+                        reason: format!(
+                            "vault secrets should be under vault://{EXTERNAL_SECRETS_VAULT_MOUNT}/{}/",
+                            self.organization_long_id
+                        ),
+                    });
+                }
+                let qvault_client = self
+                    .qvault_client
+                    .as_ref()
+                    .ok_or_else(|| unsupported("vault is not configured"))?;
+                let secret = qvault_client
+                    .get_secret::<HashMap<String, serde_json::Value>>(&mount, &path)
+                    .map_err(|e| cannot_retrieve(e.to_string()))?;
+                let version = qvault_client
+                    .get_secret_current_version(&mount, &path)
+                    .map_err(|e| cannot_retrieve(e.to_string()))?;
+
+                Ok(ResolvedExternalSecret {
+                    value: secret_key_value(&secret, &key).ok_or_else(|| ExternalSecretError::KeyNotFound {
+                        reference: reference.to_string(),
+                        key,
+                    })?,
+                    version: version.to_string(),
+                })
+            }
+            ExternalSecretReference::AwsSecretsManager { secret_id, key } => {
+                let sdk_config = self
+                    .aws_sdk_config
+                    .as_ref()
+                    .ok_or_else(|| unsupported("AWS Secrets Manager is only available on AWS clusters"))?;
+                let client = aws_sdk_secretsmanager::Client::new(sdk_config);
+                let output = block_on(client.get_secret_value().secret_id(&secret_id).send())
+                    .map_err(|e| cannot_retrieve(e.to_string()))?;
+                let secret_string = output
+                    .secret_string()
+                    .ok_or_else(|| cannot_retrieve("secret has no string value".to_string()))?;
+
+                let value = match key {
+                    None => secret_string.to_string(),
+                    Some(key) => serde_json::from_str::<HashMap<String, serde_json::Value>>(secret_string)
+                        .ok()
+                        .and_then(|secret| secret_key_value(&secret, &key))
+                        .ok_or_else(|| ExternalSecretError::KeyNotFound {
+                            reference: reference.to_string(),
+                            key,
+                        })?,
+                };
+                Ok(ResolvedExternalSecret {
+                    value,
+                    version: output.version_id().unwrap_or_default().to_string(),
+                })
+            }
+            ExternalSecretReference::GcpSecretManager { version_name } => {
+                let credentials = self
+                    .gcp_credentials
+                    .as_ref()
+                    .ok_or_else(|| unsupported("GCP Secret Manager is only available on GCP clusters"))?;
+                let response =
+                    block_on(access_gcp_secret_version(credentials, &version_name)).map_err(cannot_retrieve)?;
+                let data = general_purpose::STANDARD
+                    .decode(&response.payload.data)
+                    .map_err(|e| cannot_retrieve(e.to_string()))?;
+
+                Ok(ResolvedExternalSecret {
+                    value: String::from_utf8(data).map_err(|e| cannot_retrieve(e.to_string()))?,
+                    // name of the accessed version, with its number even when latest was requested
+                    version: response.name,
+                })
+            }
+        }
+    }
+
+    /// Resolve the external secrets of a service variables into secret values, returns the resolved values
+    pub fn resolve_variables(
+        &self,
+        variables: &mut BTreeMap<String, VariableInfo>,
+    ) -> Result<Vec<String>, ExternalSecretError> {
+        resolve_variables_with(variables, |reference| self.resolve(reference).map(|resolved| resolved.value))
+    }
+
+    /// Resolve the external secrets to refresh of a service variables, none if the service has none
+    pub fn resolve_refreshed_variables(
+        &self,
+        variables: &BTreeMap<String, VariableInfo>,
+    ) -> Result<Option<RefreshedExternalSecrets>, ExternalSecretError> {
+        resolve_refreshed_variables_with(variables, |reference| self.resolve(reference))
+    }
+}
+
+fn resolve_variables_with(
+    variables: &mut BTreeMap<String, VariableInfo>,
+    resolve: impl Fn(&str) -> Result<String, ExternalSecretError>,
+) -> Result<Vec<String>, ExternalSecretError> {
+    let mut resolved_values = vec![];
+    for variable in variables.values_mut() {
+        let Some(external_secret) = &variable.external_secret else {
+            continue;
+        };
+
+        let value = resolve(&external_secret.reference)?;
+        variable.value = general_purpose::STANDARD.encode(&value);
+        variable.is_secret = true;
+        resolved_values.push(value);
+    }
+
+    Ok(resolved_values)
+}
+
+fn resolve_refreshed_variables_with(
+    variables: &BTreeMap<String, VariableInfo>,
+    resolve: impl Fn(&str) -> Result<ResolvedExternalSecret, ExternalSecretError>,
+) -> Result<Option<RefreshedExternalSecrets>, ExternalSecretError> {
+    let mut values = BTreeMap::new();
+    let mut versions = BTreeMap::new();
+    for (name, variable) in variables {
+        let Some(external_secret) = variable.external_secret.as_ref().filter(|secret| secret.refresh) else {
+            continue;
+        };
+
+        let resolved = resolve(&external_secret.reference)?;
+        values.insert(name.clone(), resolved.value);
+        versions.insert(name.clone(), resolved.version);
+    }
+
+    if values.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(RefreshedExternalSecrets {
+        values,
+        versions_hash: calculate_hash(&versions).to_string(),
+    }))
+}
+
+/// Updates the secret of a deployed service with its refreshed external secrets, and restarts its pods when
+/// a value changed by setting the hash of the secrets versions in their template.
+/// Returns whether the pods have been restarted.
+pub async fn refresh_service_secret(
+    kube: &kube::Client,
+    namespace: &str,
+    service_long_id: &Uuid,
+    refreshed: &RefreshedExternalSecrets,
+) -> Result<bool, kube::Error> {
+    let selector = format!("qovery.com/service-id={service_long_id}");
+    let list_params = ListParams::default().labels(&selector);
+    let secrets: Api<Secret> = Api::namespaced(kube.clone(), namespace);
+    // the service may not be deployed, or deployed without any variable
+    let Some(secret) = secrets
+        .list(&list_params.clone().fields("type=Opaque"))
+        .await?
+        .items
+        .into_iter()
+        .next()
+    else {
+        return Ok(false);
+    };
+    if secret
+        .metadata
+        .annotations
+        .as_ref()
+        .and_then(|annotations| annotations.get(EXTERNAL_SECRETS_VERSION_ANNOTATION))
+        == Some(&refreshed.versions_hash)
+    {
+        return Ok(false);
+    }
+
+    let changed_values = changed_secret_values(secret.data.as_ref(), &refreshed.values);
+    let secret_patch = serde_json::json!({
+        "metadata": { "annotations": { EXTERNAL_SECRETS_VERSION_ANNOTATION: &refreshed.versions_hash } },
+        "data": &changed_values,
+    });
+    secrets
+        .patch(
+            secret.metadata.name.as_deref().unwrap_or_default(),
+            &PatchParams::default(),
+            &Patch::Merge(&secret_patch),
+        )
+        .await?;
+    // the deployment already gave the pods the current values, only the versions are recorded
+    if changed_values.is_empty() {
+        return Ok(false);
+    }
+
+    let template_annotations = serde_json::json!({
+        "metadata": { "annotations": { EXTERNAL_SECRETS_VERSION_ANNOTATION: &refreshed.versions_hash } }
+    });
+    let workload_patch = serde_json::json!({ "spec": { "template": &template_annotations } });
+    let deployments: Api<Deployment> = Api::namespaced(kube.clone(), namespace);
+    for deployment in deployments.list(&list_params).await? {
+        let name = deployment.metadata.name.unwrap_or_default();
+        deployments
+            .patch(&name, &PatchParams::default(), &Patch::Merge(&workload_patch))
+            .await?;
+    }
+    let statefulsets: Api<StatefulSet> = Api::namespaced(kube.clone(), namespace);
+    for statefulset in statefulsets.list(&list_params).await? {
+        let name = statefulset.metadata.name.unwrap_or_default();
+        statefulsets
+            .patch(&name, &PatchParams::default(), &Patch::Merge(&workload_patch))
+            .await?;
+    }
+    // jobs already running keep their values, the next scheduled ones get the new values
+    let cronjob_patch =
+        serde_json::json!({ "spec": { "jobTemplate": { "spec": { "template": &template_annotations } } } });
+    let cronjobs: Api<CronJob> = Api::namespaced(kube.clone(), namespace);
+    for cronjob in cronjobs.list(&list_params).await? {
+        let name = cronjob.metadata.name.unwrap_or_default();
+        cronjobs
+            .patch(&name, &PatchParams::default(), &Patch::Merge(&cronjob_patch))
+            .await?;
+    }
+
+    Ok(true)
+}
+
+/// Values of the secret data which differ from the refreshed ones
+fn changed_secret_values(
+    data: Option<&BTreeMap<String, ByteString>>,
+    values: &BTreeMap<String, String>,
+) -> BTreeMap<String, ByteString> {
+    values
+        .iter()
+        .filter(|(name, value)| {
+            data.and_then(|data| data.get(*name))
+                .map(|current| current.0.as_slice())
+                != Some(value.as_bytes())
+        })
+        .map(|(name, value)| (name.clone(), ByteString(value.as_bytes().to_vec())))
+        .collect()
+}
+
+fn is_organization_vault_path(mount: &str, path: &str, organization_long_id: &Uuid) -> bool {
+    match path.split_once('/') {
+        Some((organization_id, secret_path)) => {
+            mount == EXTERNAL_SECRETS_VAULT_MOUNT
+                && organization_id == organization_long_id.to_string()
+                && secret_path
+                    .split('/')
+                    .all(|segment| !matches!(segment, "" | "." | ".."))
+        }
+        None => false,
+    }
+}
+
+fn secret_key_value(secret: &HashMap<String, serde_json::Value>, key: &str) -> Option<String> {
+    secret.get(key).map(|value| match value {
+        serde_json::Value::String(value) => value.clone(),
+        value => value.to_string(),
+    })
+}
+
+async fn access_gcp_secret_version(
+    credentials: &str,
+    version_name: &str,
+) -> Result<GcpAccessSecretVersionResponse, String> {
+    let access_token = new_gcp_access_token(credentials).await.map_err(|e| e.to_string())?;
+
+    reqwest::Client::new()
+        .get(format!("{GCP_SECRET_MANAGER_URL}/{version_name}:access"))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?
+        .json::<GcpAccessSecretVersionResponse>()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        changed_secret_values, is_organization_vault_path, resolve_refreshed_variables_with, resolve_variables_with,
+        ExternalSecretError, ExternalSecretReference, ExternalSecretResolver, ResolvedExternalSecret,
+    };
+    use crate::io_models::variable_utils::{ExternalSecret, VariableInfo};
+    use base64::engine::general_purpose;
+    use base64::Engine;
+    use k8s_openapi::ByteString;
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    fn variable(value: &str, reference: Option<&str>) -> VariableInfo {
+        VariableInfo {
+            value: value.to_string(),
+            is_secret: false,
+            external_secret: reference.map(|reference| ExternalSecret {
+                reference: reference.to_string(),
+                refresh: false,
+            }),
+        }
+    }
+
+    fn refreshed_variable(reference: &str) -> VariableInfo {
+        VariableInfo {
+            value: "".to_string(),
+            is_secret: false,
+            external_secret: Some(ExternalSecret {
+                reference: reference.to_string(),
+                refresh: true,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_external_secret_reference_from_str() {
+        // setup:
+        struct TestCase<'a> {
+            reference: &'a str,
+            expected: Result<ExternalSecretReference, ()>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                reference: "vault://secret/my-app/database#password",
+                expected: Ok(ExternalSecretReference::Vault {
+                    mount: "secret".to_string(),
+                    path: "my-app/database".to_string(),
+                    key: "password".to_string(),
+                }),
+            },
+            TestCase {
+                reference: "vault://secret/my-app/database",
+                expected: Err(()),
+            },
+            TestCase {
+                reference: "aws-sm://arn:aws:secretsmanager:eu-west-3:123456789012:secret:my-app-AbCdEf#password",
+                expected: Ok(ExternalSecretReference::AwsSecretsManager {
+                    secret_id: "arn:aws:secretsmanager:eu-west-3:123456789012:secret:my-app-AbCdEf".to_string(),
+                    key: Some("password".to_string()),
+                }),
+            },
+            TestCase {
+                reference: "aws-sm://my-app",
+                expected: Ok(ExternalSecretReference::AwsSecretsManager {
+                    secret_id: "my-app".to_string(),
+                    key: None,
+                }),
+            },
+            TestCase {
+                reference: "gcp-sm://projects/my-project/secrets/my-app/versions/3",
+                expected: Ok(ExternalSecretReference::GcpSecretManager {
+                    version_name: "projects/my-project/secrets/my-app/versions/3".to_string(),
+                }),
+            },
+            TestCase {
+                reference: "gcp-sm://projects/my-project/secrets/my-app",
+                expected: Ok(ExternalSecretReference::GcpSecretManager {
+                    version_name: "projects/my-project/secrets/my-app/versions/latest".to_string(),
+                }),
+            },
+            TestCase {
+                reference: "gcp-sm://my-app",
+                expected: Err(()),
+            },
+            TestCase {
+                reference: "azure-kv://my-vault/my-app",
+                expected: Err(()),
+            },
+            TestCase {
+                reference: "my-app",
+                expected: Err(()),
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = ExternalSecretReference::from_str(tc.reference);
+
+            // verify:
+            match tc.expected {
+                Ok(expected) => assert_eq!(result, Ok(expected), "reference: {}", tc.reference),
+                Err(()) => assert!(
+                    matches!(result, Err(ExternalSecretError::InvalidReference { .. })),
+                    "reference: {}",
+                    tc.reference
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_organization_vault_path() {
+        // setup:
+        let organization_long_id = Uuid::new_v4();
+        struct TestCase {
+            mount: &'static str,
+            path: String,
+            expected: bool,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                mount: "external-secrets",
+                path: format!("{organization_long_id}/my-app/database"),
+                expected: true,
+            },
+            TestCase {
+                mount: "secret",
+                path: format!("{organization_long_id}/my-app/database"),
+                expected: false,
+            },
+            TestCase {
+                mount: "external-secrets",
+                path: format!("{}/my-app/database", Uuid::new_v4()),
+                expected: false,
+            },
+            TestCase {
+                mount: "external-secrets",
+                path: organization_long_id.to_string(),
+                expected: false,
+            },
+            TestCase {
+                mount: "external-secrets",
+                path: format!("{organization_long_id}/"),
+                expected: false,
+            },
+            TestCase {
+                mount: "external-secrets",
+                path: format!("{organization_long_id}/../{}/my-app", Uuid::new_v4()),
+                expected: false,
+            },
+            TestCase {
+                mount: "external-secrets",
+                path: format!("{organization_long_id}//my-app"),
+                expected: false,
+            },
+        ];
+
+        for tc in test_cases {
+            // execute & verify:
+            assert_eq!(
+                tc.expected,
+                is_organization_vault_path(tc.mount, &tc.path, &organization_long_id),
+                "vault://{}/{}",
+                tc.mount,
+                tc.path
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolver_rejects_vault_references_outside_of_organization() {
+        // setup:
+        let organization_long_id = Uuid::new_v4();
+        let resolver = ExternalSecretResolver {
+            qvault_client: None,
+            organization_long_id,
+            aws_sdk_config: None,
+            gcp_credentials: None,
+        };
+
+        // execute & verify:
+        assert!(matches!(
+            resolver.resolve(&format!("vault://secret/image-signing-{organization_long_id}#private_key")),
+            Err(ExternalSecretError::ForbiddenReference { .. })
+        ));
+        assert!(matches!(
+            resolver.resolve(&format!("vault://external-secrets/{}/my-app#password", Uuid::new_v4())),
+            Err(ExternalSecretError::ForbiddenReference { .. })
+        ));
+        // allowed path, but there is no vault to read it from
+        assert!(matches!(
+            resolver.resolve(&format!("vault://external-secrets/{organization_long_id}/my-app#password")),
+            Err(ExternalSecretError::UnsupportedProvider { .. })
+        ));
+        assert!(matches!(
+            resolver.resolve("aws-sm://my-app"),
+            Err(ExternalSecretError::UnsupportedProvider { .. })
+        ));
+    }
+
+    #[test]
+    fn test_resolve_variables() {
+        // setup:
+        let mut variables = BTreeMap::from([
+            ("PLAIN".to_string(), variable("cGxhaW4=", None)),
+            ("DATABASE_PASSWORD".to_string(), variable("", Some("aws-sm://my-app#password"))),
+            ("API_KEY".to_string(), variable("", Some("gcp-sm://projects/p/secrets/api-key"))),
+        ]);
+
+        // execute:
+        let resolved = resolve_variables_with(&mut variables, |reference| match reference {
+            "aws-sm://my-app#password" => Ok("p4ssw0rd".to_string()),
+            _ => Ok("k3y".to_string()),
+        });
+
+        // verify:
+        assert_eq!(Ok(vec!["k3y".to_string(), "p4ssw0rd".to_string()]), resolved);
+        assert_eq!(variable("cGxhaW4=", None), variables["PLAIN"]);
+        for (name, value) in [("DATABASE_PASSWORD", "p4ssw0rd"), ("API_KEY", "k3y")] {
+            assert_eq!(general_purpose::STANDARD.encode(value), variables[name].value);
+            assert!(variables[name].is_secret);
+            assert!(variables[name].external_secret.is_some());
+        }
+    }
+
+    #[test]
+    fn test_resolve_variables_fails_when_a_secret_cannot_be_resolved() {
+        // setup:
+        let mut variables = BTreeMap::from([
+            ("PLAIN".to_string(), variable("cGxhaW4=", None)),
+            ("DATABASE_PASSWORD".to_string(), variable("", Some("aws-sm://my-app#password"))),
+        ]);
+        let error = ExternalSecretError::KeyNotFound {
+            reference: "aws-sm://my-app#password".to_string(),
+            key: "password".to_string(),
+        };
+
+        // execute:
+        let resolved = resolve_variables_with(&mut variables, |_reference| Err(error.clone()));
+
+        // verify:
+        assert_eq!(Err(error), resolved);
+        assert_eq!(variable("", Some("aws-sm://my-app#password")), variables["DATABASE_PASSWORD"]);
+    }
+
+    #[test]
+    fn test_resolve_refreshed_variables() {
+        // setup:
+        let variables = BTreeMap::from([
+            ("PLAIN".to_string(), variable("cGxhaW4=", None)),
+            ("API_KEY".to_string(), variable("", Some("gcp-sm://projects/p/secrets/api-key"))),
+            ("DATABASE_PASSWORD".to_string(), refreshed_variable("aws-sm://my-app#password")),
+        ]);
+        let resolve = |version: &'static str| {
+            move |reference: &str| {
+                assert_eq!(
+                    "aws-sm://my-app#password", reference,
+                    "only refreshed secrets should be resolved"
+                );
+                Ok::<_, ExternalSecretError>(ResolvedExternalSecret {
+                    value: "p4ssw0rd".to_string(),
+                    version: version.to_string(),
+                })
+            }
+        };
+
+        // execute:
+        let refreshed = resolve_refreshed_variables_with(&variables, resolve("v1"))
+            .unwrap()
+            .expect("service should have refreshed secrets");
+        let same_version = resolve_refreshed_variables_with(&variables, resolve("v1"))
+            .unwrap()
+            .unwrap();
+        let new_version = resolve_refreshed_variables_with(&variables, resolve("v2"))
+            .unwrap()
+            .unwrap();
+        let not_refreshed = resolve_refreshed_variables_with(
+            &BTreeMap::from([("PLAIN".to_string(), variable("", None))]),
+            resolve("v1"),
+        );
+
+        // verify:
+        assert_eq!(
+            BTreeMap::from([("DATABASE_PASSWORD".to_string(), "p4ssw0rd".to_string())]),
+            refreshed.values
+        );
+        assert_eq!(refreshed.versions_hash, same_version.versions_hash);
+        assert_ne!(refreshed.versions_hash, new_version.versions_hash);
+        assert_eq!(Ok(None), not_refreshed);
+    }
+
+    #[test]
+    fn test_changed_secret_values() {
+        // setup:
+        let data = BTreeMap::from([
+            ("API_KEY".to_string(), ByteString(b"k3y".to_vec())),
+            ("DATABASE_PASSWORD".to_string(), ByteString(b"old-p4ssw0rd".to_vec())),
+        ]);
+        let values = BTreeMap::from([
+            ("API_KEY".to_string(), "k3y".to_string()),
+            ("DATABASE_PASSWORD".to_string(), "p4ssw0rd".to_string()),
+            ("NEW_SECRET".to_string(), "s3cr3t".to_string()),
+        ]);
+
+        // execute:
+        let changed = changed_secret_values(Some(&data), &values);
+
+        // verify:
+        assert_eq!(
+            BTreeMap::from([
+                ("DATABASE_PASSWORD".to_string(), ByteString(b"p4ssw0rd".to_vec())),
+                ("NEW_SECRET".to_string(), ByteString(b"s3cr3t".to_vec())),
+            ]),
+            changed
+        );
+        assert_eq!(3, changed_secret_values(None, &values).len());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::cmd::command::CommandKiller;
+use crate::cmd::cosign::{Cosign, CosignKeyPair};
+use crate::errors::{CommandError, EngineError};
+use crate::events::EventDetails;
+use crate::secret_manager::vault::{get_vault_mount_name, QVaultClient};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use uuid::Uuid;
+use vaultrs::error::ClientError;
+
+#[derive(Serialize, Deserialize)]
+struct ImageSigningKeySecret {
+    private_key: String,
+    public_key: String,
+    password: String,
+}
+
+fn image_signing_key_secret_name(organization_long_id: &Uuid) -> String {
+    format!("image-signing-{organization_long_id}")
+}
+
+/// Key pair the images built for an organization are signed with.
+/// It is generated on first use and kept in vault, so every cluster of the organization signs with the same key.
+pub fn get_or_create_image_signing_key(
+    qvault_client: &QVaultClient,
+    organization_long_id: &Uuid,
+    is_test_cluster: bool,
+    event_details: EventDetails,
+) -> Result<CosignKeyPair, Box<EngineError>> {
+    let mount = get_vault_mount_name(is_test_cluster);
+    let secret_name = image_signing_key_secret_name(organization_long_id);
+
+    if let Some(key_pair) = get_image_signing_key(qvault_client, &mount, &secret_name, &event_details)? {
+        return Ok(key_pair);
+    }
+
+    let password = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect::<String>();
+    let key_pair = Cosign::new()
+        .generate_key_pair(&password, &CommandKiller::from_timeout(Duration::from_secs(60)))
+        .map_err(|e| {
+            Box::new(EngineError::new_image_signing_error(
+                event_details.clone(),
+                "Cannot generate the image signing key.".to_string(),
+                CommandError::new_from_safe_message(e.to_string()),
+            ))
+        })?;
+
+    let secret = ImageSigningKeySecret {
+        private_key: key_pair.private_key.clone(),
+        public_key: key_pair.public_key.clone(),
+        password: key_pair.password.clone(),
+    };
+    match qvault_client.create_secret_if_not_exists(&mount, &secret_name, &secret) {
+        Ok(_) => Ok(key_pair),
+        // another deployment of the organization created the key in the meantime, it must be used instead
+        Err(ClientError::APIError { code: 400, .. }) => {
+            get_image_signing_key(qvault_client, &mount, &secret_name, &event_details)?.ok_or_else(|| {
+                Box::new(EngineError::new_vault_secret_could_not_be_retrieved(
+                    event_details,
+                    CommandError::new_from_safe_message(format!(
+                        "Vault secret couldn't be retrieved after a concurrent creation ({secret_name})"
+                    )),
+                ))
+            })
+        }
+        Err(e) => Err(Box::new(EngineError::new_vault_secret_could_not_be_created_or_updated(
+            event_details,
+            CommandError::new(
+                "Vault secret couldn't be created or updated".to_string(),
+                Some(format!("{e:?}")),
+                None,
+            ),
+        ))),
+    }
+}
+
+fn get_image_signing_key(
+    qvault_client: &QVaultClient,
+    mount: &str,
+    secret_name: &str,
+    event_details: &EventDetails,
+) -> Result<Option<CosignKeyPair>, Box<EngineError>> {
+    match qvault_client.get_secret::<ImageSigningKeySecret>(mount, secret_name) {
+        Ok(secret) => Ok(Some(CosignKeyPair {
+            private_key: secret.private_key,
+            public_key: secret.public_key,
+            password: secret.password,
+        })),
+        Err(ClientError::APIError { code: 404, .. }) => Ok(None),
+        Err(e) => Err(Box::new(EngineError::new_vault_secret_could_not_be_retrieved(
+            event_details.clone(),
+            CommandError::new(
+                format!("Vault secret couldn't be retrieved ({secret_name})"),
+                Some(format!("{e}")),
+                None,
+            ),
+        ))),
+    }
+}
@@ -1 +1,3 @@
+pub mod external_secret;
+pub mod image_signing;
 pub mod vault;
@@ -5,6 +5,7 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::env;
 use uuid::Uuid;
+use vaultrs::api::kv2::requests::SetSecretRequestOptions;
 use vaultrs::api::kv2::responses::SecretVersionMetadata;
 use vaultrs::client::{VaultClient, VaultClientSettingsBuilder};
 use vaultrs::error::ClientError;
@@ -28,6 +29,10 @@ impl QVaultClient {
         block_on(kv2::read(&self.connection, mount, secret_name_path))
     }
 
+    pub fn get_secret_current_version(&self, mount: &str, secret_name_path: &str) -> Result<u64, ClientError> {
+        block_on(kv2::read_metadata(&self.connection, mount, secret_name_path)).map(|metadata| metadata.current_version)
+    }
+
     pub fn crate_update_secret<T: Serialize>(
         &self,
         mount: &str,
@@ -37,6 +42,22 @@ impl QVaultClient {
         block_on(kv2::set(&self.connection, mount, secret_name, secret_content))
     }
 
+    /// Creates the secret only if it doesn't exist yet, a concurrent creation is rejected by vault with a 400
+    pub fn create_secret_if_not_exists<T: Serialize>(
+        &self,
+        mount: &str,
+        secret_name: &str,
+        secret_content: &T,
+    ) -> Result<SecretVersionMetadata, ClientError> {
+        block_on(kv2::set_with_options(
+            &self.connection,
+            mount,
+            secret_name,
+            secret_content,
+            SetSecretRequestOptions { cas: 0 },
+        ))
+    }
+
     pub fn delete_secret(&self, mount: &str, secret_name: &str) -> Result<(), ClientError> {
         block_on(kv2::delete_metadata(&self.connection, mount, secret_name))
     }
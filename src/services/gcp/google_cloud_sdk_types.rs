@@ -7,6 +7,8 @@ use crate::object_storage::{Bucket, BucketRegion};
 use crate::runtime::block_on;
 use crate::services::gcp::object_storage_regions::GcpStorageRegion;
 use google_cloud_auth::credentials::CredentialsFile;
+use google_cloud_auth::project::{create_token_source_from_credentials, Config};
+use google_cloud_auth::token_source::TokenSource;
 use google_cloud_googleapis::devtools::artifact_registry::v1::{
     DockerImage as GcpDockerImage, Package as GcpPackage, Repository as GcpRepository,
 };
@@ -16,6 +18,8 @@ use regex::Regex;
 use std::str::FromStr;
 use std::time::Duration;
 
+const GCP_CLOUD_PLATFORM_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
 /// Handle conversion and deal with external types for Google cloud
 /// defined here https://github.com/yoshidan/google-cloud-rust
 /// Keeping it isolated prevent from high coupling with third party crate
@@ -36,6 +40,31 @@ pub fn new_gcp_credentials_file_from_credentials(
     })
 }
 
+/// OAuth access token of the credentials, to call Google APIs not covered by the SDK crates
+pub async fn new_gcp_access_token(credentials: &str) -> Result<String, CredentialsError> {
+    let token = async {
+        let credentials_file = CredentialsFile::new_from_str(credentials)
+            .await
+            .map_err(|e| e.to_string())?;
+        let token_source = create_token_source_from_credentials(
+            &credentials_file,
+            &Config {
+                audience: None,
+                scopes: Some(&[GCP_CLOUD_PLATFORM_SCOPE]),
+                sub: None,
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        token_source.token().await.map_err(|e| e.to_string())
+    };
+
+    token
+        .await
+        .map(|token| token.access_token)
+        .map_err(|raw_error_message| CredentialsError::CannotCreateCredentials { raw_error_message })
+}
+
 impl TryFrom<GcpBucket> for Bucket {
     type Error = String;
 
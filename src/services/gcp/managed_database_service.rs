@@ -0,0 +1,79 @@
+use crate::cloud_provider::service::DatabaseType;
+use crate::runtime::block_on;
+use crate::services::gcp::google_cloud_sdk_types::new_gcp_access_token;
+use reqwest::StatusCode;
+use thiserror::Error;
+
+const CLOUD_SQL_ADMIN_URL: &str = "https://sqladmin.googleapis.com/v1";
+const MEMORYSTORE_URL: &str = "https://redis.googleapis.com/v1";
+
+#[derive(Clone, Error, Debug, PartialEq, Eq)]
+pub enum ManagedDatabaseServiceError {
+    #[error("Cannot create managed database service: {raw_error_message:?}")]
+    CannotCreateService { raw_error_message: String },
+    #[error("Cannot get managed database `{database_name}`: {raw_error_message:?}")]
+    CannotGetDatabase {
+        database_name: String,
+        raw_error_message: String,
+    },
+}
+
+/// Cloud SQL and Memorystore instances, those APIs are not covered by the SDK crates
+pub struct ManagedDatabaseService {
+    access_token: String,
+    project_id: String,
+    region: String,
+}
+
+impl ManagedDatabaseService {
+    pub fn new(
+        google_credentials_json: &str,
+        project_id: &str,
+        region: &str,
+    ) -> Result<Self, ManagedDatabaseServiceError> {
+        Ok(Self {
+            access_token: block_on(new_gcp_access_token(google_credentials_json)).map_err(|e| {
+                ManagedDatabaseServiceError::CannotCreateService {
+                    raw_error_message: e.to_string(),
+                }
+            })?,
+            project_id: project_id.to_string(),
+            region: region.to_string(),
+        })
+    }
+
+    pub fn database_exists(
+        &self,
+        database_type: DatabaseType,
+        database_name: &str,
+    ) -> Result<bool, ManagedDatabaseServiceError> {
+        let url = match database_type {
+            DatabaseType::PostgreSQL | DatabaseType::MySQL => {
+                format!("{CLOUD_SQL_ADMIN_URL}/projects/{}/instances/{database_name}", self.project_id)
+            }
+            DatabaseType::Redis => format!(
+                "{MEMORYSTORE_URL}/projects/{}/locations/{}/instances/{database_name}",
+                self.project_id, self.region
+            ),
+            // not available as a managed database
+            DatabaseType::MongoDB => return Ok(false),
+        };
+
+        let response =
+            block_on(reqwest::Client::new().get(url).bearer_auth(&self.access_token).send()).map_err(|e| {
+                ManagedDatabaseServiceError::CannotGetDatabase {
+                    database_name: database_name.to_string(),
+                    raw_error_message: e.to_string(),
+                }
+            })?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(false),
+            status if status.is_success() => Ok(true),
+            status => Err(ManagedDatabaseServiceError::CannotGetDatabase {
+                database_name: database_name.to_string(),
+                raw_error_message: format!("unexpected response status `{status}`"),
+            }),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod artifact_registry_service;
-mod google_cloud_sdk_types;
+pub(crate) mod google_cloud_sdk_types;
+pub mod managed_database_service;
 pub mod object_storage_regions;
 pub mod object_storage_service;
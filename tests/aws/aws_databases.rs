@@ -288,6 +288,9 @@ fn postgresql_deploy_a_working_environment_and_redeploy() {
             publicly_accessible: false,
             mode: CONTAINER,
             database_instance_type: None,
+            depends_on: vec![],
+            backup: None,
+            restore: None,
         }];
         environment.applications = environment
             .applications
@@ -306,11 +309,11 @@ fn postgresql_deploy_a_working_environment_and_redeploy() {
                     namespace: None,
                 }];
                 app.environment_vars_with_infos = btreemap! {
-                     "PG_DBNAME".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode(database_db_name.clone()), is_secret: false},
-                     "PG_HOST".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode(database_host.clone()), is_secret: false},
-                     "PG_PORT".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode(database_port.to_string()), is_secret: false},
-                     "PG_USERNAME".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode(database_username.clone()), is_secret: false},
-                     "PG_PASSWORD".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode(database_password.clone()), is_secret: false},
+                     "PG_DBNAME".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode(database_db_name.clone()), is_secret: false, external_secret: None},
+                     "PG_HOST".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode(database_host.clone()), is_secret: false, external_secret: None},
+                     "PG_PORT".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode(database_port.to_string()), is_secret: false, external_secret: None},
+                     "PG_USERNAME".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode(database_username.clone()), is_secret: false, external_secret: None},
+                     "PG_PASSWORD".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode(database_password.clone()), is_secret: false, external_secret: None},
                 };
                 app.readiness_probe = Some(Probe {
                     r#type: ProbeType::Tcp { host: None },
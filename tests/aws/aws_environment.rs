@@ -23,7 +23,7 @@ use qovery_engine::io_models::context::CloneForTest;
 use qovery_engine::io_models::helm_chart::{HelmChart, HelmChartSource, HelmRawValues, HelmValueSource};
 use qovery_engine::io_models::job::{Job, JobSchedule, JobSource};
 use qovery_engine::io_models::probe::{Probe, ProbeType};
-use qovery_engine::io_models::router::{CustomDomain, Route, Router};
+use qovery_engine::io_models::router::{CertificateSource, CustomDomain, Route, Router};
 use qovery_engine::io_models::variable_utils::VariableInfo;
 use qovery_engine::io_models::{Action, MountedFile, QoveryIdentifier};
 use qovery_engine::metrics_registry::{StepLabel, StepName, StepStatus};
@@ -591,6 +591,7 @@ fn deploy_a_working_environment_with_custom_domain_and_disable_check_on_custom_d
                 domain: format!("fake-custom-domain-{idx}.qovery.io"),
                 target_domain: format!("validation-domain-{idx}"),
                 generate_certificate: true,
+                certificate_source: CertificateSource::Generated,
             };
 
             router.custom_domains = vec![cd];
@@ -674,6 +675,7 @@ fn deploy_a_working_environment_with_storage_on_aws_eks() {
                     size_in_gib: storage_size,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }];
                 app
             })
@@ -838,6 +840,7 @@ fn redeploy_same_app_with_ebs() {
                     size_in_gib: storage_size,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }];
                 app
             })
@@ -1513,7 +1516,7 @@ fn deploy_container_with_no_router_and_affinitiy_on_aws_eks() {
                 },
             ],
             storages: vec![],
-            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo { value: general_purpose::STANDARD.encode("my_value"), is_secret: false}},
+            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo { value: general_purpose::STANDARD.encode("my_value"), is_secret: false, external_secret: None}},
             mounted_files: vec![],
             readiness_probe: Some(Probe {
                 r#type: ProbeType::Tcp { host: None },
@@ -1534,6 +1537,7 @@ fn deploy_container_with_no_router_and_affinitiy_on_aws_eks() {
                 failure_threshold: 5,
             }),
             advanced_settings: Default::default(),
+            depends_on: vec![],
         }];
 
         let ret = environment.deploy_environment(&environment, &infra_ctx);
@@ -1707,7 +1711,7 @@ fn deploy_container_with_no_router_on_aws_eks() {
                 },
             ],
             storages: vec![],
-            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo { value: general_purpose::STANDARD.encode("my_value"), is_secret: false} },
+            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo { value: general_purpose::STANDARD.encode("my_value"), is_secret: false, external_secret: None} },
             mounted_files: vec![],
             readiness_probe: Some(Probe {
                 r#type: ProbeType::Tcp { host: None },
@@ -1728,6 +1732,7 @@ fn deploy_container_with_no_router_on_aws_eks() {
                 failure_threshold: 5,
             }),
             advanced_settings: Default::default(),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -1845,6 +1850,7 @@ fn deploy_container_with_storages_on_aws_eks() {
                     size_in_gib: 10,
                     mount_point: "/mnt/photos1".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 },
                 Storage {
                     id: to_short_id(&storage_id_2),
@@ -1854,11 +1860,13 @@ fn deploy_container_with_storages_on_aws_eks() {
                     size_in_gib: 10,
                     mount_point: "/mnt/photos2".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 },
             ],
             environment_vars_with_infos: BTreeMap::default(),
             mounted_files: vec![],
             advanced_settings: Default::default(),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -1984,9 +1992,10 @@ fn deploy_container_on_aws_eks_with_mounted_files_as_volume() {
                 failure_threshold: 5,
             }),
             storages: vec![],
-            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret: false} },
+            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret: false, external_secret: None} },
             mounted_files: vec![mounted_file.clone()],
             advanced_settings: Default::default(),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -2125,9 +2134,10 @@ fn deploy_container_with_router_on_aws_eks() {
                 failure_threshold: 5,
             }),
             storages: vec![],
-            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret:false} },
+            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret:false, external_secret: None} },
             mounted_files: vec![],
             advanced_settings: Default::default(),
+            depends_on: vec![],
         }];
 
         environment.routers = vec![Router {
@@ -2141,6 +2151,7 @@ fn deploy_container_with_router_on_aws_eks() {
             routes: vec![Route {
                 path: "/".to_string(),
                 service_long_id: environment.containers[0].long_id,
+                headers: BTreeMap::new(),
             }],
         }];
 
@@ -2237,6 +2248,7 @@ fn deploy_job_on_aws_eks() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -2331,6 +2343,7 @@ fn deploy_cronjob_on_aws_eks() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -2427,6 +2440,7 @@ fn deploy_cronjob_force_trigger_on_aws_eks() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -2546,6 +2560,7 @@ fn build_and_deploy_job_on_aws_eks() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -2662,8 +2677,9 @@ fn test_restart_deployment() {
             }),
             storages: vec![],
             mounted_files: vec![],
-            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret: false} },
+            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret: false, external_secret: None} },
             advanced_settings: Default::default(),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -2743,6 +2759,7 @@ fn test_restart_statefulset() {
                 size_in_gib: 10,
                 storage_type: StorageType::FastSsd,
                 snapshot_retention_in_days: 1,
+                restore_from_snapshot: None,
             }],
             mounted_files: vec![],
             entrypoint: None,
@@ -2793,8 +2810,9 @@ fn test_restart_statefulset() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
-            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret: false} },
+            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret: false, external_secret: None} },
             advanced_settings: Default::default(),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -2904,6 +2922,7 @@ fn build_and_deploy_job_on_aws_eks_with_mounted_files_as_volume() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -2995,6 +3014,7 @@ fn deploy_a_working_environment_with_multiple_resized_storage_on_aws_eks() {
                         size_in_gib: initial_storage_size,
                         mount_point: "/mnt/photos_1".to_string(),
                         snapshot_retention_in_days: 0,
+                        restore_from_snapshot: None,
                     },
                     Storage {
                         id: to_short_id(&id_2),
@@ -3004,6 +3024,7 @@ fn deploy_a_working_environment_with_multiple_resized_storage_on_aws_eks() {
                         size_in_gib: initial_storage_size,
                         mount_point: "/mnt/photos_2".to_string(),
                         snapshot_retention_in_days: 0,
+                        restore_from_snapshot: None,
                     },
                 ];
                 app
@@ -3188,7 +3209,7 @@ fn deploy_container_with_udp_tcp_public_ports() {
                 },
             ],
             storages: vec![],
-            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret: false} },
+            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{value: general_purpose::STANDARD.encode("my_value"), is_secret: false, external_secret: None} },
             mounted_files: vec![],
             readiness_probe: Some(Probe {
                 r#type: ProbeType::Tcp { host: None },
@@ -3209,6 +3230,7 @@ fn deploy_container_with_udp_tcp_public_ports() {
                 failure_threshold: 5,
             }),
             advanced_settings: Default::default(),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -3328,9 +3350,10 @@ fn deploy_helm_chart() {
             command_args: vec!["--install".to_string()],
             timeout_sec: 60,
             allow_cluster_wide_resources: false,
-            environment_vars_with_infos: btreemap! { "TOTO".to_string() => VariableInfo {value: "Salut".to_string(), is_secret: false} },
+            environment_vars_with_infos: btreemap! { "TOTO".to_string() => VariableInfo {value: "Salut".to_string(), is_secret: false, external_secret: None} },
             advanced_settings: Default::default(),
             ports: vec![],
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -3411,9 +3434,10 @@ fn deploy_helm_chart_and_pause_it() {
                 command_args: vec!["--install".to_string()],
                 timeout_sec: 60,
                 allow_cluster_wide_resources,
-                environment_vars_with_infos: btreemap! { "TOTO".to_string() => VariableInfo {value: "Salut".to_string(), is_secret: false} },
+                environment_vars_with_infos: btreemap! { "TOTO".to_string() => VariableInfo {value: "Salut".to_string(), is_secret: false, external_secret: None} },
                 advanced_settings: Default::default(),
                 ports: vec![],
+                depends_on: vec![],
             }];
 
             let mut environment_for_delete = environment.clone();
@@ -3518,9 +3542,10 @@ fn deploy_helm_chart_and_restart_it() {
                 command_args: vec!["--install".to_string()],
                 timeout_sec: 60,
                 allow_cluster_wide_resources,
-                environment_vars_with_infos: btreemap! { "TOTO".to_string() => VariableInfo {value: "Salut".to_string(), is_secret: false} },
+                environment_vars_with_infos: btreemap! { "TOTO".to_string() => VariableInfo {value: "Salut".to_string(), is_secret: false, external_secret: None} },
                 advanced_settings: Default::default(),
                 ports: vec![],
+                depends_on: vec![],
             }];
 
             let mut environment_for_delete = environment.clone();
@@ -3604,7 +3629,7 @@ fn deploy_helm_chart_with_router() {
             command_args: vec![],
             timeout_sec: 60,
             allow_cluster_wide_resources: true,
-            environment_vars_with_infos: btreemap! { "TOTO".to_string() => VariableInfo {value: "Salut".to_string(), is_secret: false} },
+            environment_vars_with_infos: btreemap! { "TOTO".to_string() => VariableInfo {value: "Salut".to_string(), is_secret: false, external_secret: None} },
             advanced_settings: Default::default(),
             ports: vec![
                 Port {
@@ -3628,6 +3653,7 @@ fn deploy_helm_chart_with_router() {
                     service_name: Some("outside-namespace-service2".to_string()),
                 },
             ],
+            depends_on: vec![],
         }];
         environment.routers = vec![Router {
             long_id: Uuid::new_v4(),
@@ -3640,6 +3666,7 @@ fn deploy_helm_chart_with_router() {
             routes: vec![Route {
                 path: "/".to_string(),
                 service_long_id: environment.helms[0].long_id,
+                headers: BTreeMap::new(),
             }],
         }];
 
@@ -222,9 +222,10 @@ fn deploy_container_on_aws_ec2_with_mounted_files_as_volume() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
-            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo { value:  general_purpose::STANDARD.encode("my_value"), is_secret: false} },
+            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo { value:  general_purpose::STANDARD.encode("my_value"), is_secret: false, external_secret: None} },
             mounted_files: vec![mounted_file.clone()],
             advanced_settings: Default::default(),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -359,6 +360,7 @@ fn build_and_deploy_job_on_aws_ec2_with_mounted_files_as_volume() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -122,6 +122,49 @@ fn q_ingress_test() {
     assert!(!resources.is_empty());
 }
 
+#[cfg(feature = "test-local-kube")]
+#[test]
+fn q_gateway_test() {
+    let test_info = router_context();
+    let chart_name = "q-gateway";
+    let uuid = test_info.service_id;
+    let chart = CommonChart {
+        chart_info: ChartInfo {
+            name: chart_name.to_string(),
+            path: chart_path(&test_info.temp_dir, &test_info.service_folder_type, &uuid, chart_name),
+            namespace: HelmChartNamespaces::KubeSystem,
+            custom_namespace: None,
+            action: HelmAction::Deploy,
+            atomic: false,
+            force_upgrade: false,
+            recreate_pods: false,
+            reinstall_chart_if_installed_version_is_below_than: None,
+            timeout_in_seconds: 0,
+            dry_run: false,
+            wait: false,
+            values: vec![],
+            values_string: vec![],
+            values_files: vec![],
+            yaml_files_content: vec![],
+            parse_stderr_for_error: false,
+            k8s_selector: None,
+            backup_resources: None,
+            crds_update: None,
+        },
+        chart_installation_checker: None,
+        vertical_pod_autoscaler: None,
+    };
+
+    let resources = get_kube_resources(
+        format!("{}/common/charts/{}", lib_dir(), chart_name).as_str(),
+        chart.chart_info,
+        None,
+        &test_info,
+        &uuid,
+    );
+    assert!(!resources.is_empty());
+}
+
 #[cfg(feature = "test-local-kube")]
 #[test]
 fn q_container_test() {
@@ -4,7 +4,7 @@ use crate::helpers::utilities::{context_for_cluster, logger, metrics_registry, F
 use base64::engine::general_purpose;
 use base64::Engine;
 use chrono::Utc;
-use qovery_engine::build_platform::{Build, GitRepository, Image, SshKey};
+use qovery_engine::build_platform::{Build, BuildpacksSettings, GitRepository, Image, SshKey};
 use qovery_engine::cloud_provider::aws::database_instance_type::AwsDatabaseInstanceType;
 use qovery_engine::cloud_provider::aws::{
     kubernetes::eks::EKS,
@@ -15,7 +15,7 @@ use qovery_engine::cloud_provider::environment::Environment;
 use qovery_engine::cloud_provider::io::{ClusterAdvancedSettings, RegistryMirroringMode};
 use qovery_engine::cloud_provider::kubernetes::{Kind::Eks, Kubernetes, KubernetesVersion};
 use qovery_engine::cloud_provider::models::{
-    CpuArchitecture, CustomDomain, EnvironmentVariable, MountedFile, Route, Storage,
+    CertificateSource, CpuArchitecture, CustomDomain, EnvironmentVariable, MountedFile, Route, Storage,
 };
 use qovery_engine::cloud_provider::qovery::EngineLocation;
 use qovery_engine::cloud_provider::service::{Action, Service};
@@ -31,6 +31,7 @@ use qovery_engine::models::application::Application;
 use qovery_engine::models::aws::{AwsAppExtraSettings, AwsRouterExtraSettings, AwsStorageType};
 use qovery_engine::models::container::Container;
 use qovery_engine::models::database::{Container as ContainerDB, Database, Managed, PostgresSQL};
+use qovery_engine::models::dependency_graph::ServiceDependencyGraph;
 use qovery_engine::models::job::{ImageSource, Job};
 use qovery_engine::models::probe::{Probe, ProbeType};
 use qovery_engine::models::registry_image_source::RegistryImageSource;
@@ -159,6 +160,8 @@ fn test_environment(kube: &dyn Kubernetes) -> Environment {
         ],
         vec![Box::new(test_job(kube))],
         vec![], // TODO (helm): add helm charts test
+        ServiceDependencyGraph::default(),
+        false,
     )
 }
 
@@ -184,6 +187,7 @@ fn test_storage() -> Storage<AwsStorageType> {
         size_in_gib: 1,
         mount_point: "my_mount_point".to_string(),
         snapshot_retention_in_days: 2,
+        restore_from_snapshot: None,
     }
 }
 
@@ -214,6 +218,7 @@ fn test_custom_domain() -> CustomDomain {
         domain: "my_custom_domain".to_string(),
         target_domain: "my_target_domain".to_string(),
         generate_certificate: true,
+        certificate_source: CertificateSource::Generated,
     }
 }
 
@@ -221,6 +226,7 @@ fn test_route(uuid: Uuid) -> Route {
     Route {
         path: "my_route_path".to_string(),
         service_long_id: uuid,
+        headers: BTreeMap::new(),
     }
 }
 
@@ -253,6 +259,7 @@ pub fn test_application(test_kube: &dyn Kubernetes) -> Application<AWSType> {
                 dockerfile_path: Some(PathBuf::from("my_dockerfile_path")),
                 root_path: PathBuf::from("my_root_path"),
                 buildpack_language: Some("my_language".to_string()),
+                buildpacks: BuildpacksSettings::default(),
             },
             image: Image {
                 service_id: "my_application_id".to_string(),
@@ -272,6 +279,7 @@ pub fn test_application(test_kube: &dyn Kubernetes) -> Application<AWSType> {
             architectures: test_kube.cpu_architectures(),
             max_cpu_in_milli: 2000,
             max_ram_in_gib: 4,
+            vulnerability_allowlist: vec![],
         },
         vec![],
         None,
@@ -302,11 +310,16 @@ pub fn test_application(test_kube: &dyn Kubernetes) -> Application<AWSType> {
         ApplicationAdvancedSettings {
             security_service_account_name: "".to_string(),
             security_read_only_root_filesystem: false,
+            security_vulnerability_allowlist: vec![],
             deployment_termination_grace_period_seconds: 60,
             deployment_custom_domain_check_enabled: true,
             deployment_update_strategy_type: UpdateStrategy::RollingUpdate,
             deployment_update_strategy_rolling_update_max_unavailable_percent: 25,
             deployment_update_strategy_rolling_update_max_surge_percent: 25,
+            deployment_update_strategy_canary_traffic_percent: 10,
+            deployment_update_strategy_canary_analysis_duration_seconds: 300,
+            deployment_update_strategy_canary_max_container_restarts: 0,
+            deployment_update_strategy_canary_max_error_rate_percent: 5,
             build_timeout_max_sec: 2,
             build_cpu_max_in_milli: 2000,
             build_ram_max_in_gib: 4,
@@ -329,10 +342,26 @@ pub fn test_application(test_kube: &dyn Kubernetes) -> Application<AWSType> {
             network_ingress_whitelist_source_range: "my_network_ingress_whitelist_source_range".to_string(),
             network_ingress_denylist_source_range: "".to_string(),
             network_ingress_basic_auth_env_var: "".to_string(),
+            network_ingress_limit_rps: None,
+            network_ingress_limit_connections: None,
+            network_ingress_limit_burst_multiplier: 5,
+            network_ingress_modsecurity_profile: None,
+            network_ingress_auth_url: "".to_string(),
+            network_ingress_auth_signin: "".to_string(),
+            network_ingress_auth_response_headers: "".to_string(),
             network_ingress_grpc_send_timeout_seconds: 60,
             network_ingress_grpc_read_timeout_seconds: 60,
             hpa_cpu_average_utilization_percent: 31,
+            hpa_memory_average_utilization_percent: None,
+            hpa_requests_per_second_average: None,
+            hpa_custom_metrics: vec![],
+            hpa_scale_up_stabilization_window_seconds: 0,
+            hpa_scale_up_max_percent: 100,
+            hpa_scale_down_stabilization_window_seconds: 300,
+            hpa_scale_down_max_percent: 100,
             deployment_affinity_node_required: BTreeMap::new(),
+            deployment_node_selector: BTreeMap::new(),
+            deployment_tolerations: vec![],
             deployment_antiaffinity_pod: PodAntiAffinity::Preferred,
         },
         AwsAppExtraSettings {},
@@ -399,7 +428,13 @@ pub fn test_container(test_kube: &dyn Kubernetes) -> Container<AWSType> {
             deployment_update_strategy_type: UpdateStrategy::RollingUpdate,
             deployment_update_strategy_rolling_update_max_unavailable_percent: 25,
             deployment_update_strategy_rolling_update_max_surge_percent: 25,
+            deployment_update_strategy_canary_traffic_percent: 10,
+            deployment_update_strategy_canary_analysis_duration_seconds: 300,
+            deployment_update_strategy_canary_max_container_restarts: 0,
+            deployment_update_strategy_canary_max_error_rate_percent: 5,
             deployment_affinity_node_required: BTreeMap::new(),
+            deployment_node_selector: BTreeMap::new(),
+            deployment_tolerations: vec![],
             deployment_antiaffinity_pod: PodAntiAffinity::Preferred,
             network_ingress_proxy_body_size_mb: 11,
             network_ingress_cors_enable: true,
@@ -420,11 +455,26 @@ pub fn test_container(test_kube: &dyn Kubernetes) -> Container<AWSType> {
             network_ingress_whitelist_source_range: "my_network_ingress_whitelist_source_range".to_string(),
             network_ingress_denylist_source_range: "".to_string(),
             network_ingress_basic_auth_env_var: "".to_string(),
+            network_ingress_limit_rps: None,
+            network_ingress_limit_connections: None,
+            network_ingress_limit_burst_multiplier: 5,
+            network_ingress_modsecurity_profile: None,
+            network_ingress_auth_url: "".to_string(),
+            network_ingress_auth_signin: "".to_string(),
+            network_ingress_auth_response_headers: "".to_string(),
             network_ingress_grpc_send_timeout_seconds: 60,
             network_ingress_grpc_read_timeout_seconds: 60,
             hpa_cpu_average_utilization_percent: 41,
+            hpa_memory_average_utilization_percent: None,
+            hpa_requests_per_second_average: None,
+            hpa_custom_metrics: vec![],
+            hpa_scale_up_stabilization_window_seconds: 0,
+            hpa_scale_up_max_percent: 100,
+            hpa_scale_down_stabilization_window_seconds: 300,
+            hpa_scale_down_max_percent: 100,
             security_service_account_name: "".to_string(),
             security_read_only_root_filesystem: false,
+            security_vulnerability_allowlist: vec![],
         },
         AwsAppExtraSettings {},
         |transmitter| test_kube.context().get_event_details(transmitter),
@@ -461,6 +511,8 @@ pub fn test_managed_database(test_kube: &dyn Kubernetes) -> Database<AWSType, Ma
             activate_high_availability: true,
             activate_backups: true,
             publicly_accessible: true,
+            backup: None,
+            restore: None,
         },
         |transmitter| test_kube.context().get_event_details(transmitter),
     )
@@ -496,6 +548,8 @@ pub fn test_container_database(test_kube: &dyn Kubernetes) -> Database<AWSType,
             activate_high_availability: true,
             activate_backups: true,
             publicly_accessible: true,
+            backup: None,
+            restore: None,
         },
         |transmitter| test_kube.context().get_event_details(transmitter),
     )
@@ -519,6 +573,9 @@ pub fn test_router(test_kube: &dyn Kubernetes, app_id: Uuid) -> Router<AWSType>
             whitelist_source_range: None,
             denylist_source_range: None,
             basic_auth: None,
+            rate_limit: None,
+            modsecurity_profile: None,
+            external_auth: None,
         },
         |transmitter| test_kube.context().get_event_details(transmitter),
     )
@@ -562,8 +619,13 @@ fn test_job(test_kube: &dyn Kubernetes) -> Job<AWSType> {
         btreeset![test_mounted_file()],
         JobAdvancedSettings {
             job_delete_ttl_seconds_after_finished: Some(8),
+            job_retry_max_attempts: 0,
+            job_retry_initial_backoff_seconds: 10,
+            job_retry_max_backoff_seconds: 300,
             deployment_termination_grace_period_seconds: 60,
             deployment_affinity_node_required: BTreeMap::new(),
+            deployment_node_selector: BTreeMap::new(),
+            deployment_tolerations: vec![],
             cronjob_concurrency_policy: "my_cronjob_concurrency_policy".to_string(),
             cronjob_failed_jobs_history_limit: 9,
             cronjob_success_jobs_history_limit: 10,
@@ -572,6 +634,7 @@ fn test_job(test_kube: &dyn Kubernetes) -> Job<AWSType> {
             build_ram_max_in_gib: 4,
             security_service_account_name: "".to_string(),
             security_read_only_root_filesystem: false,
+            security_vulnerability_allowlist: vec![],
         },
         Some(Probe {
             r#type: ProbeType::Http {
@@ -11,6 +11,7 @@ use core::default::Default;
 use core::option::Option;
 use core::option::Option::{None, Some};
 use core::result::Result::{Err, Ok};
+use qovery_engine::build_platform::BuildpacksSettings;
 use qovery_engine::cloud_provider::aws::AWS;
 use qovery_engine::cloud_provider::environment::Environment;
 use qovery_engine::cloud_provider::kubernetes::Kind as KubernetesKind;
@@ -254,16 +255,17 @@ pub fn environment_3_apps_3_databases(
                 command_args: vec![],
                 entrypoint: None,
                 buildpack_language: None,
+                buildpacks: BuildpacksSettings::default(),
                 root_path: "/".to_string(),
                 action: Action::Create,
                 git_credentials: None,
                 storage: vec![],
                 environment_vars_with_infos: btreemap! {
-                     "PG_DBNAME".to_string() => VariableInfo{value: general_purpose::STANDARD.encode(database_name.clone()), is_secret: false},
-                     "PG_HOST".to_string() => VariableInfo{value: general_purpose::STANDARD.encode(fqdn.clone()),is_secret: false},
-                     "PG_PORT".to_string() => VariableInfo{value: general_purpose::STANDARD.encode(database_port.to_string()), is_secret: false},
-                     "PG_USERNAME".to_string() => VariableInfo{value: general_purpose::STANDARD.encode(database_username.clone()), is_secret: false},
-                     "PG_PASSWORD".to_string() => VariableInfo{value: general_purpose::STANDARD.encode(database_password.clone()), is_secret: false},
+                     "PG_DBNAME".to_string() => VariableInfo{value: general_purpose::STANDARD.encode(database_name.clone()), is_secret: false, external_secret: None},
+                     "PG_HOST".to_string() => VariableInfo{value: general_purpose::STANDARD.encode(fqdn.clone()),is_secret: false, external_secret: None},
+                     "PG_PORT".to_string() => VariableInfo{value: general_purpose::STANDARD.encode(database_port.to_string()), is_secret: false, external_secret: None},
+                     "PG_USERNAME".to_string() => VariableInfo{value: general_purpose::STANDARD.encode(database_username.clone()), is_secret: false, external_secret: None},
+                     "PG_PASSWORD".to_string() => VariableInfo{value: general_purpose::STANDARD.encode(database_password.clone()), is_secret: false, external_secret: None},
                 },
                 mounted_files: vec![],
                 public_domain: format!("{}.example.com", app_id),
@@ -304,6 +306,7 @@ pub fn environment_3_apps_3_databases(
                     success_threshold: 1,
                     failure_threshold: 5,
                 }),
+                depends_on: vec![],
             },
             Application {
                 long_id: Uuid::new_v4(),
@@ -316,16 +319,17 @@ pub fn environment_3_apps_3_databases(
                 command_args: vec![],
                 entrypoint: None,
                 buildpack_language: None,
+                buildpacks: BuildpacksSettings::default(),
                 root_path: String::from("/"),
                 action: Action::Create,
                 git_credentials: None,
                 storage: vec![],
                 environment_vars_with_infos: btreemap! {
-                     "PG_DBNAME".to_string() => VariableInfo {value: general_purpose::STANDARD.encode(database_name_2.clone()), is_secret: false },
-                     "PG_HOST".to_string() =>VariableInfo {value: general_purpose::STANDARD.encode(fqdn_2.clone()), is_secret: false },
-                     "PG_PORT".to_string() => VariableInfo {value:general_purpose::STANDARD.encode(database_port.to_string()), is_secret: false },
-                     "PG_USERNAME".to_string() =>VariableInfo {value: general_purpose::STANDARD.encode(database_username_2.clone()), is_secret: false },
-                     "PG_PASSWORD".to_string() => VariableInfo {value:general_purpose::STANDARD.encode(database_password.clone()), is_secret: false },
+                     "PG_DBNAME".to_string() => VariableInfo {value: general_purpose::STANDARD.encode(database_name_2.clone()), is_secret: false, external_secret: None },
+                     "PG_HOST".to_string() =>VariableInfo {value: general_purpose::STANDARD.encode(fqdn_2.clone()), is_secret: false, external_secret: None },
+                     "PG_PORT".to_string() => VariableInfo {value:general_purpose::STANDARD.encode(database_port.to_string()), is_secret: false, external_secret: None },
+                     "PG_USERNAME".to_string() =>VariableInfo {value: general_purpose::STANDARD.encode(database_username_2.clone()), is_secret: false, external_secret: None },
+                     "PG_PASSWORD".to_string() => VariableInfo {value:general_purpose::STANDARD.encode(database_password.clone()), is_secret: false, external_secret: None },
                 },
                 mounted_files: vec![],
                 ports: vec![Port {
@@ -366,6 +370,7 @@ pub fn environment_3_apps_3_databases(
                     failure_threshold: 5,
                 }),
                 public_domain: format!("{}.example.com", app_id),
+                depends_on: vec![],
             },
             Application {
                 long_id: Uuid::new_v4(),
@@ -378,6 +383,7 @@ pub fn environment_3_apps_3_databases(
                 command_args: vec![],
                 entrypoint: None,
                 buildpack_language: None,
+                buildpacks: BuildpacksSettings::default(),
                 action: Action::Create,
                 root_path: String::from("/"),
                 git_credentials: None,
@@ -422,6 +428,7 @@ pub fn environment_3_apps_3_databases(
                     success_threshold: 1,
                     failure_threshold: 5,
                 }),
+                depends_on: vec![],
             },
         ],
         containers: vec![],
@@ -451,6 +458,9 @@ pub fn environment_3_apps_3_databases(
                 activate_backups: false,
                 publicly_accessible: false,
                 mode: CONTAINER,
+                depends_on: vec![],
+                backup: None,
+                restore: None,
             },
             Database {
                 kind: DatabaseKind::Postgresql,
@@ -475,6 +485,9 @@ pub fn environment_3_apps_3_databases(
                 activate_backups: false,
                 publicly_accessible: false,
                 mode: CONTAINER,
+                depends_on: vec![],
+                backup: None,
+                restore: None,
             },
             Database {
                 kind: DatabaseKind::Mongodb,
@@ -499,9 +512,13 @@ pub fn environment_3_apps_3_databases(
                 activate_backups: false,
                 publicly_accessible: false,
                 mode: CONTAINER,
+                depends_on: vec![],
+                backup: None,
+                restore: None,
             },
         ],
         helms: vec![],
+        rollback_on_failure: false,
     }
 }
 
@@ -531,6 +548,7 @@ pub fn database_test_environment(context: &Context) -> EnvironmentRequest {
             command_args: vec![],
             entrypoint: None,
             buildpack_language: None,
+            buildpacks: BuildpacksSettings::default(),
             root_path: String::from("/"),
             action: Action::Create,
             git_credentials: None,
@@ -547,12 +565,14 @@ pub fn database_test_environment(context: &Context) -> EnvironmentRequest {
             readiness_probe: None,
             liveness_probe: None,
             public_domain: format!("{}.example.com", Uuid::new_v4()),
+            depends_on: vec![],
         }],
         containers: vec![],
         jobs: vec![],
         routers: vec![],
         databases: vec![],
         helms: vec![],
+        rollback_on_failure: false,
     }
 }
 
@@ -581,6 +601,7 @@ pub fn database_test_environment_on_upgrade(context: &Context) -> EnvironmentReq
             command_args: vec![],
             entrypoint: None,
             buildpack_language: None,
+            buildpacks: BuildpacksSettings::default(),
             root_path: String::from("/"),
             action: Action::Create,
             git_credentials: None,
@@ -598,12 +619,14 @@ pub fn database_test_environment_on_upgrade(context: &Context) -> EnvironmentReq
             advanced_settings: Default::default(),
             readiness_probe: None,
             liveness_probe: None,
+            depends_on: vec![],
         }],
         containers: vec![],
         jobs: vec![],
         routers: vec![],
         databases: vec![],
         helms: vec![],
+        rollback_on_failure: false,
     }
 }
 
@@ -712,6 +735,9 @@ pub fn test_db(
         activate_backups: false,
         publicly_accessible: is_public,
         mode: database_mode.clone(),
+        depends_on: vec![],
+        backup: None,
+        restore: None,
     };
 
     environment.databases = vec![db.clone()];
@@ -757,6 +783,7 @@ pub fn test_db(
         KubernetesKind::ScwKapsule | KubernetesKind::ScwSelfManaged => SCW_KUBERNETES_VERSION,
         KubernetesKind::Ec2 => AWS_EC2_KUBERNETES_VERSION.clone(),
         KubernetesKind::Gke | KubernetesKind::GkeSelfManaged => todo!(), // TODO(benjaminch): GKE integration
+        KubernetesKind::Aks => todo!(),                                  // TODO: AKS integration
     };
 
     let computed_infra_ctx: InfrastructureContext;
@@ -807,6 +834,7 @@ pub fn test_db(
                     EngineLocation::ClientSide,
                 ),
                 KubernetesKind::Gke | KubernetesKind::GkeSelfManaged => todo!(), // TODO(benjaminch): GKE integration
+                KubernetesKind::Aks => todo!(),                                  // TODO: AKS integration
             };
             &computed_infra_ctx
         }
@@ -927,6 +955,7 @@ pub fn test_db(
                     EngineLocation::ClientSide,
                 ),
                 KubernetesKind::Gke => todo!(), // TODO(benjaminch): GKE integration
+                KubernetesKind::Aks => todo!(), // TODO: AKS integration
                 KubernetesKind::EksSelfManaged => todo!(), // TODO byok integration
                 KubernetesKind::GkeSelfManaged => todo!(), // TODO byok integration
                 KubernetesKind::ScwSelfManaged => todo!(), // TODO byok integration
@@ -1037,6 +1066,9 @@ pub fn test_pause_managed_db(
         activate_backups: false,
         publicly_accessible: is_public,
         mode: database_mode.clone(),
+        depends_on: vec![],
+        backup: None,
+        restore: None,
     };
 
     environment.databases = vec![db];
@@ -1065,6 +1097,7 @@ pub fn test_pause_managed_db(
             SCW_KUBERNETES_VERSION,
         ),
         Kind::Gcp => todo!(),
+        Kind::Azure => todo!(), // TODO: AKS integration
         Kind::SelfManaged => todo!(),
     };
 
@@ -1116,6 +1149,7 @@ pub fn test_pause_managed_db(
                     EngineLocation::ClientSide,
                 ),
                 KubernetesKind::Gke => todo!(), // TODO(benjaminch): GKE integration
+                KubernetesKind::Aks => todo!(), // TODO: AKS integration
                 KubernetesKind::EksSelfManaged => todo!(), // TODO byok integration
                 KubernetesKind::GkeSelfManaged => todo!(), // TODO byok integration
                 KubernetesKind::ScwSelfManaged => todo!(), // TODO byok integration
@@ -1228,6 +1262,7 @@ pub fn test_pause_managed_db(
                     EngineLocation::ClientSide,
                 ),
                 KubernetesKind::Gke => todo!(), // TODO(benjaminch): GKE integration
+                KubernetesKind::Aks => todo!(), // TODO: AKS integration
                 KubernetesKind::EksSelfManaged => todo!(), // TODO byok integration
                 KubernetesKind::GkeSelfManaged => todo!(), // TODO byok integration
                 KubernetesKind::ScwSelfManaged => todo!(), // TODO byok integration
@@ -1320,6 +1355,9 @@ pub fn test_db_on_upgrade(
         activate_backups: false,
         publicly_accessible: is_public,
         mode: database_mode.clone(),
+        depends_on: vec![],
+        backup: None,
+        restore: None,
     };
 
     environment.databases = vec![db];
@@ -1371,7 +1409,8 @@ pub fn test_db_on_upgrade(
                 .to_string(),
             SCW_KUBERNETES_VERSION,
         ),
-        Kind::Gcp => todo!(), // TODO(benjaminch): GKE integration
+        Kind::Gcp => todo!(),   // TODO(benjaminch): GKE integration
+        Kind::Azure => todo!(), // TODO: AKS integration
         Kind::SelfManaged => todo!(),
     };
 
@@ -1408,7 +1447,8 @@ pub fn test_db_on_upgrade(
             CpuArchitecture::AMD64,
             EngineLocation::ClientSide,
         ),
-        Kind::Gcp => todo!(), // TODO(benjaminch): GKE integration
+        Kind::Gcp => todo!(),   // TODO(benjaminch): GKE integration
+        Kind::Azure => todo!(), // TODO: AKS integration
         Kind::SelfManaged => todo!(),
     };
 
@@ -1497,7 +1537,8 @@ pub fn test_db_on_upgrade(
             CpuArchitecture::AMD64,
             EngineLocation::ClientSide,
         ),
-        Kind::Gcp => todo!(), // TODO(benjaminch): GKE integration
+        Kind::Gcp => todo!(),   // TODO(benjaminch): GKE integration
+        Kind::Azure => todo!(), // TODO: AKS integration
         Kind::SelfManaged => todo!(),
     };
 
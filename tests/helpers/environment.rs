@@ -2,6 +2,7 @@ use crate::helpers::utilities::{generate_id, generate_password, get_svc_name};
 use base64::engine::general_purpose;
 use base64::Engine;
 use chrono::Utc;
+use qovery_engine::build_platform::BuildpacksSettings;
 use qovery_engine::cloud_provider::Kind;
 use qovery_engine::io_models::application::{Application, ApplicationAdvancedSettings, Port, Protocol, StorageType};
 use qovery_engine::io_models::context::Context;
@@ -55,6 +56,7 @@ pub fn working_environment(
             command_args: vec![],
             entrypoint: None,
             buildpack_language: None,
+            buildpacks: BuildpacksSettings::default(),
             root_path: String::from("/"),
             action: Action::Create,
             git_credentials: None,
@@ -100,12 +102,14 @@ pub fn working_environment(
             }),
             advanced_settings: settings,
             public_domain: format!("{}.{}", application_id.to_uuid(), test_domain),
+            depends_on: vec![],
         }],
         containers: vec![],
         jobs: vec![],
         routers: vec![],
         databases: vec![],
         helms: vec![],
+        rollback_on_failure: false,
     };
 
     if with_router {
@@ -120,6 +124,7 @@ pub fn working_environment(
             routes: vec![Route {
                 path: "/".to_string(),
                 service_long_id: application_id.to_uuid(),
+                headers: BTreeMap::new(),
             }],
         }]
     }
@@ -171,6 +176,7 @@ pub fn working_environment_with_application_and_stateful_crashing_if_file_doesnt
             VariableInfo {
                 value: general_purpose::STANDARD.encode(&mount_file_env_var_value),
                 is_secret: false,
+                external_secret: None,
             }, // TODO check secret value
         ), // <- https://github.com/Qovery/engine-testing/blob/app-crashing-if-file-doesnt-exist/src/main.rs#L19
         (
@@ -178,6 +184,7 @@ pub fn working_environment_with_application_and_stateful_crashing_if_file_doesnt
             VariableInfo {
                 value: general_purpose::STANDARD.encode(&mount_file_env_var_value),
                 is_secret: false,
+                external_secret: None,
             },
         ), // <- mounted file PATH
     ]);
@@ -199,6 +206,7 @@ pub fn working_environment_with_application_and_stateful_crashing_if_file_doesnt
         size_in_gib: 10,
         mount_point: format!("/tmp/{}", storage_id.short()),
         snapshot_retention_in_days: 1,
+        restore_from_snapshot: None,
     }];
 
     // attaching application & statefulset to env
@@ -259,6 +267,9 @@ pub fn environment_2_app_2_routers_1_psql(
             activate_backups: false,
             publicly_accessible: false,
             mode: CONTAINER,
+            depends_on: vec![],
+            backup: None,
+            restore: None,
         }],
         applications: vec![
             Application {
@@ -272,6 +283,7 @@ pub fn environment_2_app_2_routers_1_psql(
                 command_args: vec![],
                 entrypoint: None,
                 buildpack_language: None,
+                buildpacks: BuildpacksSettings::default(),
                 root_path: String::from("/"),
                 action: Action::Create,
                 git_credentials: None,
@@ -316,6 +328,7 @@ pub fn environment_2_app_2_routers_1_psql(
                     failure_threshold: 5,
                 }),
                 public_domain: format!("{}.{}", application_id1, test_domain),
+                depends_on: vec![],
             },
             Application {
                 long_id: application_id2,
@@ -328,6 +341,7 @@ pub fn environment_2_app_2_routers_1_psql(
                 command_args: vec![],
                 entrypoint: None,
                 buildpack_language: None,
+                buildpacks: BuildpacksSettings::default(),
                 root_path: String::from("/"),
                 action: Action::Create,
                 git_credentials: None,
@@ -372,6 +386,7 @@ pub fn environment_2_app_2_routers_1_psql(
                     success_threshold: 1,
                     failure_threshold: 5,
                 }),
+                depends_on: vec![],
             },
         ],
         containers: vec![],
@@ -388,6 +403,7 @@ pub fn environment_2_app_2_routers_1_psql(
                 routes: vec![Route {
                     path: "/".to_string(),
                     service_long_id: application_id1,
+                    headers: BTreeMap::new(),
                 }],
             },
             Router {
@@ -401,12 +417,14 @@ pub fn environment_2_app_2_routers_1_psql(
                 routes: vec![Route {
                     path: "/coco".to_string(),
                     service_long_id: application_id2,
+                    headers: BTreeMap::new(),
                 }],
             },
         ],
         max_parallel_build: 1,
         max_parallel_deploy: 1,
         helms: vec![],
+        rollback_on_failure: false,
     }
 }
 
@@ -453,12 +471,13 @@ pub fn echo_app_environment(context: &Context, test_domain: &str) -> Environment
             command_args: vec![],
             entrypoint: None,
             buildpack_language: None,
+            buildpacks: BuildpacksSettings::default(),
             root_path: String::from("/"),
             action: Action::Create,
             git_credentials: None,
             storage: vec![],
             environment_vars_with_infos: btreemap! {
-                "ECHO_TEXT".to_string() => VariableInfo {value: general_purpose::STANDARD.encode("42"), is_secret: false},
+                "ECHO_TEXT".to_string() => VariableInfo {value: general_purpose::STANDARD.encode("42"), is_secret: false, external_secret: None},
             },
             mounted_files: vec![],
             branch: "echo-app".to_string(),
@@ -500,6 +519,7 @@ pub fn echo_app_environment(context: &Context, test_domain: &str) -> Environment
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }],
         containers: vec![],
         jobs: vec![],
@@ -514,10 +534,12 @@ pub fn echo_app_environment(context: &Context, test_domain: &str) -> Environment
             routes: vec![Route {
                 path: "/".to_string(),
                 service_long_id: application_id,
+                headers: BTreeMap::new(),
             }],
         }],
         databases: vec![],
         helms: vec![],
+        rollback_on_failure: false,
     }
 }
 
@@ -561,6 +583,7 @@ pub fn environment_only_http_server(
             command_args: vec![],
             entrypoint: None,
             buildpack_language: None,
+            buildpacks: BuildpacksSettings::default(),
             root_path: String::from("/"),
             action: Action::Create,
             git_credentials: None,
@@ -606,12 +629,14 @@ pub fn environment_only_http_server(
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }],
         containers: vec![],
         jobs: vec![],
         routers: vec![],
         databases: vec![],
         helms: vec![],
+        rollback_on_failure: false,
     };
 
     if with_router {
@@ -626,6 +651,7 @@ pub fn environment_only_http_server(
             routes: vec![Route {
                 path: "/".to_string(),
                 service_long_id: application_id,
+                headers: BTreeMap::new(),
             }],
         }]
     }
@@ -149,6 +149,7 @@ pub fn get_cluster_test_kubernetes<'a>(
             .unwrap(),
         ),
         KubernetesKind::Gke => todo!(),            // TODO(benjaminch): GKE integration
+        KubernetesKind::Aks => todo!(),            // TODO: AKS integration
         KubernetesKind::EksSelfManaged => todo!(), // TODO: Byok integration
         KubernetesKind::GkeSelfManaged => todo!(), // TODO: Byok integration
         KubernetesKind::ScwSelfManaged => todo!(), // TODO: Byok integration
@@ -182,6 +183,7 @@ pub fn cluster_test(
         KubernetesKind::Ec2 => AWS_EC2_KUBERNETES_VERSION.clone(),
         KubernetesKind::ScwKapsule | KubernetesKind::ScwSelfManaged => SCW_KUBERNETES_VERSION,
         KubernetesKind::Gke | KubernetesKind::GkeSelfManaged => GCP_KUBERNETES_VERSION,
+        KubernetesKind::Aks => todo!(), // TODO: AKS integration
     };
 
     let mut engine = match provider_kind {
@@ -227,6 +229,7 @@ pub fn cluster_test(
             CpuArchitecture::AMD64,
             EngineLocation::ClientSide,
         ),
+        Kind::Azure => todo!(), // TODO: AKS integration
         Kind::SelfManaged => todo!(),
     };
     // Bootstrap
@@ -314,7 +317,8 @@ pub fn cluster_test(
                     CpuArchitecture::AMD64,
                     EngineLocation::ClientSide,
                 ),
-                Kind::Gcp => todo!(), // TODO(benjaminch): GKE integration
+                Kind::Gcp => todo!(),   // TODO(benjaminch): GKE integration
+                Kind::Azure => todo!(), // TODO: AKS integration
                 Kind::SelfManaged => todo!(),
             };
             let mut upgrade_tx = Transaction::new(&engine).unwrap();
@@ -366,7 +370,8 @@ pub fn cluster_test(
                     CpuArchitecture::AMD64,
                     EngineLocation::ClientSide,
                 ),
-                Kind::Gcp => todo!(), // TODO(benjaminch): GKE integration
+                Kind::Gcp => todo!(),   // TODO(benjaminch): GKE integration
+                Kind::Azure => todo!(), // TODO: AKS integration
                 Kind::SelfManaged => todo!(),
             };
             let mut upgrade_tx = Transaction::new(&engine).unwrap();
@@ -543,6 +548,7 @@ pub fn get_environment_test_kubernetes(
                 .expect("Cannot instantiate GKE"),
             )
         }
+        KubernetesKind::Aks => todo!(),            // TODO: AKS integration
         KubernetesKind::GkeSelfManaged => todo!(), // TODO: Byok integration
         KubernetesKind::ScwSelfManaged => todo!(), // TODO: Byok integration
         KubernetesKind::EksSelfManaged => todo!(), // TODO: Byok integration
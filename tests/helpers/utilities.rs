@@ -622,6 +622,7 @@ fn get_cloud_provider_credentials(provider_kind: Kind, secrets: &FuncTestsSecret
             ),
         ],
         Kind::Gcp => vec![],
+        Kind::Azure => vec![],
         Kind::SelfManaged => vec![],
     }
 }
@@ -810,13 +811,13 @@ pub fn db_infos(
                 db_name: database_db_name.to_string(),
                 app_commit: "0ce035590a117ff0683c273a359c7a452f639dd1".to_string(),
                 app_env_vars: btreemap! {
-                    "IS_DOCUMENTDB".to_string() => VariableInfo { value: general_purpose::STANDARD.encode((database_mode == MANAGED).to_string()), is_secret:false},
-                    "QOVERY_DATABASE_TESTING_DATABASE_FQDN".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(db_fqdn), is_secret:false},
-                    "QOVERY_DATABASE_MY_DDB_CONNECTION_URI".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_uri), is_secret:false},
-                    "QOVERY_DATABASE_TESTING_DATABASE_PORT".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_port.to_string()), is_secret:false},
-                    "MONGODB_DBNAME".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_db_name), is_secret:false},
-                    "QOVERY_DATABASE_TESTING_DATABASE_USERNAME".to_string() =>VariableInfo { value:  general_purpose::STANDARD.encode(database_username), is_secret:false},
-                    "QOVERY_DATABASE_TESTING_DATABASE_PASSWORD".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_password), is_secret:false},
+                    "IS_DOCUMENTDB".to_string() => VariableInfo { value: general_purpose::STANDARD.encode((database_mode == MANAGED).to_string()), is_secret:false, external_secret: None},
+                    "QOVERY_DATABASE_TESTING_DATABASE_FQDN".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(db_fqdn), is_secret:false, external_secret: None},
+                    "QOVERY_DATABASE_MY_DDB_CONNECTION_URI".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_uri), is_secret:false, external_secret: None},
+                    "QOVERY_DATABASE_TESTING_DATABASE_PORT".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_port.to_string()), is_secret:false, external_secret: None},
+                    "MONGODB_DBNAME".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_db_name), is_secret:false, external_secret: None},
+                    "QOVERY_DATABASE_TESTING_DATABASE_USERNAME".to_string() =>VariableInfo { value:  general_purpose::STANDARD.encode(database_username), is_secret:false, external_secret: None},
+                    "QOVERY_DATABASE_TESTING_DATABASE_PASSWORD".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_password), is_secret:false, external_secret: None},
                 },
             }
         }
@@ -828,11 +829,11 @@ pub fn db_infos(
                 db_name: database_db_name.to_string(),
                 app_commit: "0c73aac9bbab7f494da1d89a535ed40e668a8ab4".to_string(),
                 app_env_vars: btreemap! {
-                    "MYSQL_HOST".to_string() =>VariableInfo { value: general_purpose::STANDARD.encode(db_fqdn), is_secret:false},
-                    "MYSQL_PORT".to_string() => VariableInfo { value:general_purpose::STANDARD.encode(database_port.to_string()), is_secret:false},
-                    "MYSQL_DBNAME".to_string()   => VariableInfo { value:general_purpose::STANDARD.encode(database_db_name), is_secret:false},
-                    "MYSQL_USERNAME".to_string() => VariableInfo { value:general_purpose::STANDARD.encode(database_username), is_secret:false},
-                    "MYSQL_PASSWORD".to_string() => VariableInfo { value:general_purpose::STANDARD.encode(database_password), is_secret:false},
+                    "MYSQL_HOST".to_string() =>VariableInfo { value: general_purpose::STANDARD.encode(db_fqdn), is_secret:false, external_secret: None},
+                    "MYSQL_PORT".to_string() => VariableInfo { value:general_purpose::STANDARD.encode(database_port.to_string()), is_secret:false, external_secret: None},
+                    "MYSQL_DBNAME".to_string()   => VariableInfo { value:general_purpose::STANDARD.encode(database_db_name), is_secret:false, external_secret: None},
+                    "MYSQL_USERNAME".to_string() => VariableInfo { value:general_purpose::STANDARD.encode(database_username), is_secret:false, external_secret: None},
+                    "MYSQL_PASSWORD".to_string() => VariableInfo { value:general_purpose::STANDARD.encode(database_password), is_secret:false, external_secret: None},
                 },
             }
         }
@@ -848,11 +849,11 @@ pub fn db_infos(
                 db_name: database_db_name.to_string(),
                 app_commit: "1dec771b8bfdeeb71df0d56ebea9d4f6c2be5705".to_string(),
                 app_env_vars: btreemap! {
-                     "PG_DBNAME".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_db_name), is_secret:false},
-                     "PG_HOST".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(db_fqdn), is_secret:false},
-                     "PG_PORT".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_port.to_string()), is_secret:false},
-                     "PG_USERNAME".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_username), is_secret:false},
-                     "PG_PASSWORD".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_password), is_secret:false},
+                     "PG_DBNAME".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_db_name), is_secret:false, external_secret: None},
+                     "PG_HOST".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(db_fqdn), is_secret:false, external_secret: None},
+                     "PG_PORT".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_port.to_string()), is_secret:false, external_secret: None},
+                     "PG_USERNAME".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_username), is_secret:false, external_secret: None},
+                     "PG_PASSWORD".to_string() => VariableInfo { value: general_purpose::STANDARD.encode(database_password), is_secret:false, external_secret: None},
                 },
             }
         }
@@ -864,11 +865,11 @@ pub fn db_infos(
                 db_name: database_db_name,
                 app_commit: "c8dd8b57a4ebafabc860f0b948f881dad5ab632e".to_string(),
                 app_env_vars: btreemap! {
-                "IS_ELASTICCACHE".to_string() => VariableInfo { value: general_purpose::STANDARD.encode((database_mode == MANAGED && database_username == "default").to_string()), is_secret:false},
-                "REDIS_HOST".to_string()      => VariableInfo { value: general_purpose::STANDARD.encode(db_fqdn), is_secret:false},
-                "REDIS_PORT".to_string()      =>VariableInfo { value:  general_purpose::STANDARD.encode(database_port.to_string()), is_secret:false},
-                "REDIS_USERNAME".to_string()  => VariableInfo { value: general_purpose::STANDARD.encode(database_username), is_secret:false},
-                "REDIS_PASSWORD".to_string()  =>VariableInfo { value:  general_purpose::STANDARD.encode(database_password), is_secret:false},
+                "IS_ELASTICCACHE".to_string() => VariableInfo { value: general_purpose::STANDARD.encode((database_mode == MANAGED && database_username == "default").to_string()), is_secret:false, external_secret: None},
+                "REDIS_HOST".to_string()      => VariableInfo { value: general_purpose::STANDARD.encode(db_fqdn), is_secret:false, external_secret: None},
+                "REDIS_PORT".to_string()      =>VariableInfo { value:  general_purpose::STANDARD.encode(database_port.to_string()), is_secret:false, external_secret: None},
+                "REDIS_USERNAME".to_string()  => VariableInfo { value: general_purpose::STANDARD.encode(database_username), is_secret:false, external_secret: None},
+                "REDIS_PASSWORD".to_string()  =>VariableInfo { value:  general_purpose::STANDARD.encode(database_password), is_secret:false, external_secret: None},
                 },
             }
         }
@@ -882,7 +883,8 @@ pub fn db_disk_type(provider_kind: Kind, database_mode: DatabaseMode) -> String
             MANAGED => SCW_MANAGED_DATABASE_DISK_TYPE,
             DatabaseMode::CONTAINER => SCW_SELF_HOSTED_DATABASE_DISK_TYPE,
         },
-        Kind::Gcp => todo!(), // TODO(benjaminch): GKE integration
+        Kind::Gcp => todo!(),   // TODO(benjaminch): GKE integration
+        Kind::Azure => todo!(), // TODO: AKS integration
         Kind::SelfManaged => todo!(),
     }
     .to_string()
@@ -904,7 +906,8 @@ pub fn db_instance_type(
             MANAGED => Some(Box::new(SCW_MANAGED_DATABASE_INSTANCE_TYPE)),
             DatabaseMode::CONTAINER => None,
         },
-        Kind::Gcp => todo!(), // TODO(benjaminch): GKE integration
+        Kind::Gcp => todo!(),   // TODO(benjaminch): GKE integration
+        Kind::Azure => todo!(), // TODO: AKS integration
         Kind::SelfManaged => todo!(),
     }
 }
@@ -234,6 +234,7 @@ fn should_have_mounted_files_as_volume() {
                 VariableInfo {
                     value: general_purpose::STANDARD.encode(&mount_file_env_var_value),
                     is_secret: false,
+                    external_secret: None,
                 },
             ), // <- https://github.com/Qovery/engine-testing/blob/app-crashing-if-file-doesnt-exist/src/main.rs#L19
             (
@@ -241,6 +242,7 @@ fn should_have_mounted_files_as_volume() {
                 VariableInfo {
                     value: general_purpose::STANDARD.encode(&mount_file_env_var_value),
                     is_secret: false,
+                    external_secret: None,
                 },
             ), // <- mounted file PATH
         ]);
@@ -262,6 +264,7 @@ fn should_have_mounted_files_as_volume() {
             size_in_gib: 10,
             mount_point: format!("/tmp/{}", storage_id.short()),
             snapshot_retention_in_days: 1,
+            restore_from_snapshot: None,
         }];
 
         // attaching application & statefulset to env
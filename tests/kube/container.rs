@@ -239,6 +239,7 @@ fn should_have_mounted_files_as_volume() {
                 VariableInfo {
                     value: general_purpose::STANDARD.encode(mount_file_env_var_value),
                     is_secret: false,
+                    external_secret: None,
                 },
             ), // <- mounted file PATH
         ]);
@@ -259,6 +260,7 @@ fn should_have_mounted_files_as_volume() {
             size_in_gib: 10,
             mount_point: format!("/tmp/{}", storage_id.short()),
             snapshot_retention_in_days: 1,
+            restore_from_snapshot: None,
         }];
 
         // attaching application & statefulset to env
@@ -79,6 +79,8 @@ fn should_increase_db_storage_size() {
                 activate_high_availability: resized_db.activate_high_availability,
                 activate_backups: resized_db.activate_backups,
                 publicly_accessible: resized_db.publicly_accessible,
+                backup: None,
+                restore: None,
             },
             |transmitter| infra_ctx.context().get_event_details(transmitter),
         )
@@ -4,6 +4,7 @@ use crate::helpers::utilities::{
     context_for_resource, generate_id, get_svc_name, logger, metrics_registry, FuncTestsSecrets,
 };
 use chrono::Utc;
+use qovery_engine::build_platform::BuildpacksSettings;
 use qovery_engine::cloud_provider::Kind::Aws;
 use qovery_engine::engine::InfrastructureContext;
 use qovery_engine::io_models::application::{Application, Port, Protocol, Storage, StorageType};
@@ -66,6 +67,7 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
         routers: vec![],
         databases: vec![],
         helms: vec![],
+        rollback_on_failure: false,
     };
 
     match options {
@@ -95,6 +97,9 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
                 publicly_accessible: false,
                 mode: CONTAINER,
                 database_instance_type: None,
+                depends_on: vec![],
+                backup: None,
+                restore: None,
             };
             environment.databases = vec![db];
         }
@@ -172,6 +177,7 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
                         size_in_gib: NormalSize.size(),
                         mount_point: "/mnt/photos1".to_string(),
                         snapshot_retention_in_days: 0,
+                        restore_from_snapshot: None,
                     },
                     Storage {
                         id: to_short_id(&storage_2_id),
@@ -181,11 +187,13 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
                         size_in_gib: NormalSize.size(),
                         mount_point: "/mnt/photos2".to_string(),
                         snapshot_retention_in_days: 0,
+                        restore_from_snapshot: None,
                     },
                 ],
                 environment_vars_with_infos: BTreeMap::default(),
                 advanced_settings: Default::default(),
                 mounted_files: vec![],
+                depends_on: vec![],
             };
             environment.containers = vec![container];
         }
@@ -204,6 +212,7 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
                 command_args: vec![],
                 entrypoint: None,
                 buildpack_language: None,
+                buildpacks: BuildpacksSettings::default(),
                 root_path: String::from("/"),
                 action: Action::Create,
                 git_credentials: None,
@@ -216,6 +225,7 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
                         size_in_gib: NormalSize.size(),
                         mount_point: "/mnt/photos1".to_string(),
                         snapshot_retention_in_days: 0,
+                        restore_from_snapshot: None,
                     },
                     Storage {
                         id: to_short_id(&storage_2_id),
@@ -225,6 +235,7 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
                         size_in_gib: NormalSize.size(),
                         mount_point: "/mnt/photos2".to_string(),
                         snapshot_retention_in_days: 0,
+                        restore_from_snapshot: None,
                     },
                 ],
                 environment_vars_with_infos: BTreeMap::default(),
@@ -268,6 +279,7 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
                 cpu_burst: "100m".to_string(),
                 advanced_settings: Default::default(),
                 mounted_files: vec![],
+                depends_on: vec![],
             };
             environment.applications = vec![app];
         }
@@ -311,6 +323,7 @@ pub fn kube_test_env(options: TestEnvOption) -> (InfrastructureContext, Environm
                 default_port: None,
                 readiness_probe: None,
                 liveness_probe: None,
+                depends_on: vec![],
             };
             environment.jobs = vec![job];
         }
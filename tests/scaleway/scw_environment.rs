@@ -19,7 +19,7 @@ use qovery_engine::io_models::container::{Container, Registry};
 use qovery_engine::io_models::context::CloneForTest;
 use qovery_engine::io_models::job::{Job, JobSchedule, JobSource};
 use qovery_engine::io_models::probe::{Probe, ProbeType};
-use qovery_engine::io_models::router::{CustomDomain, Route, Router};
+use qovery_engine::io_models::router::{CertificateSource, CustomDomain, Route, Router};
 use qovery_engine::io_models::variable_utils::VariableInfo;
 use qovery_engine::io_models::{Action, MountedFile, QoveryIdentifier};
 use qovery_engine::models::scaleway::ScwZone;
@@ -394,6 +394,7 @@ fn scaleway_kapsule_deploy_a_working_environment_with_domain() {
                 domain: format!("fake-custom-domain-{idx}.qovery.io"),
                 target_domain: format!("validation-domain-{idx}"),
                 generate_certificate: true,
+                certificate_source: CertificateSource::Generated,
             };
 
             router.custom_domains = vec![cd];
@@ -486,6 +487,7 @@ fn scaleway_kapsule_deploy_a_working_environment_with_storage() {
                     size_in_gib: storage_size,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }];
                 app
             })
@@ -726,6 +728,7 @@ fn scaleway_kapsule_redeploy_same_app() {
                     size_in_gib: storage_size,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }];
                 app
             })
@@ -1509,9 +1512,10 @@ fn deploy_container_with_no_router_on_scw() {
                 failure_threshold: 5,
             }),
             storages: vec![],
-            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode("my_value"), is_secret: false} },
+            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode("my_value"), is_secret: false, external_secret: None} },
             mounted_files: vec![],
             advanced_settings: Default::default(),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -1622,7 +1626,7 @@ fn deploy_container_on_scw_with_mounted_files_as_volume() {
                 },
             ],
             storages: vec![],
-            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() =>  VariableInfo{ value: general_purpose::STANDARD.encode("my_value"), is_secret: false} },
+            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() =>  VariableInfo{ value: general_purpose::STANDARD.encode("my_value"), is_secret: false, external_secret: None} },
             mounted_files: vec![mounted_file.clone()],
             advanced_settings: Default::default(),
             readiness_probe: Some(Probe {
@@ -1643,6 +1647,7 @@ fn deploy_container_on_scw_with_mounted_files_as_volume() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -1762,7 +1767,7 @@ fn deploy_container_with_router_on_scw() {
                 },
             ],
             storages: vec![],
-            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{ value:general_purpose::STANDARD.encode("my_value"), is_secret: false} },
+            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{ value:general_purpose::STANDARD.encode("my_value"), is_secret: false, external_secret: None} },
             mounted_files: vec![],
             advanced_settings: Default::default(),
             readiness_probe: Some(Probe {
@@ -1783,6 +1788,7 @@ fn deploy_container_with_router_on_scw() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         environment.routers = vec![Router {
@@ -1796,6 +1802,7 @@ fn deploy_container_with_router_on_scw() {
             routes: vec![Route {
                 path: "/".to_string(),
                 service_long_id: environment.containers[0].long_id,
+                headers: BTreeMap::new(),
             }],
         }];
 
@@ -1892,6 +1899,7 @@ fn deploy_job_on_scw_kapsule() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -1986,6 +1994,7 @@ fn deploy_cronjob_on_scw_kapsule() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -2080,6 +2089,7 @@ fn deploy_cronjob_force_trigger_on_scw_kapsule() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -2173,6 +2183,7 @@ fn build_and_deploy_job_on_scw_kapsule() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -2277,6 +2288,7 @@ fn build_and_deploy_job_on_scw_kapsule_with_mounted_files() {
                 success_threshold: 1,
                 failure_threshold: 5,
             }),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();
@@ -2436,9 +2448,10 @@ fn deploy_container_with_tcp_public_port() {
                 failure_threshold: 50,
             }),
             storages: vec![],
-            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode("my_value"), is_secret:false} },
+            environment_vars_with_infos: btreemap! { "MY_VAR".to_string() => VariableInfo{ value: general_purpose::STANDARD.encode("my_value"), is_secret:false, external_secret: None} },
             mounted_files: vec![],
             advanced_settings: Default::default(),
+            depends_on: vec![],
         }];
 
         let mut environment_for_delete = environment.clone();